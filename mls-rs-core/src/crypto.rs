@@ -229,6 +229,17 @@ impl From<Vec<u8>> for SignaturePublicKey {
     }
 }
 
+/// Signature to verify with [`CipherSuiteProvider::verify_batch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureVerification<'a> {
+    /// Public key of the signer.
+    pub public_key: &'a SignaturePublicKey,
+    /// Signature over `data`.
+    pub signature: &'a [u8],
+    /// Signed data.
+    pub data: &'a [u8],
+}
+
 /// Byte representation of a signature key.
 #[cfg_attr(
     all(feature = "ffi", not(test)),
@@ -500,4 +511,21 @@ pub trait CipherSuiteProvider: Send + Sync {
         signature: &[u8],
         data: &[u8],
     ) -> Result<(), Self::Error>;
+
+    /// Verify all signatures of `batch`, failing if any of them is invalid.
+    ///
+    /// The default implementation calls [verify](CipherSuiteProvider::verify)
+    /// for each signature. Providers may only override it with batch
+    /// verification that accepts exactly the same signatures as
+    /// [verify](CipherSuiteProvider::verify). For example, cofactored Ed25519
+    /// batch verification does not, and members of a group using different
+    /// providers could otherwise disagree on the validity of a message.
+    async fn verify_batch(&self, batch: &[SignatureVerification<'_>]) -> Result<(), Self::Error> {
+        for item in batch {
+            self.verify(item.public_key, item.signature, item.data)
+                .await?;
+        }
+
+        Ok(())
+    }
 }
@@ -24,6 +24,15 @@ use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 /// | 5  | DHKEMP521   | AES 256 | SHA 512 | P521             |
 /// | 6  | DHKEMX448   | ChaCha20Poly1305 | SHA 512 | Ed448   |
 /// | 7  | DHKEMP384   | AES 256 | SHA 512 | P384             |
+///
+/// ## Custom Ciphersuites
+///
+/// Ciphersuites that are not defined by the MLS RFC, such as post-quantum
+/// hybrid suites, should use a value from the private use range
+/// `0xF000..=0xFFFF` (see [`CipherSuite::is_private_use`]). A client
+/// advertises such a suite in its capabilities as soon as its crypto provider
+/// lists it in
+/// [`supported_cipher_suites`](crate::crypto::CryptoProvider::supported_cipher_suites).
 #[derive(Debug, Copy, Clone, Eq, PartialEq, MlsSize, MlsEncode, MlsDecode, PartialOrd, Ord)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::ffi_type)]
@@ -77,6 +86,12 @@ impl CipherSuite {
         self.0
     }
 
+    /// Determine if this ciphersuite is in the range reserved for private use
+    /// by the MLS RFC.
+    pub const fn is_private_use(&self) -> bool {
+        self.0 >= 0xF000
+    }
+
     /// An iterator over all of the default MLS ciphersuites.
     pub fn all() -> impl Iterator<Item = CipherSuite> {
         (1..=7).map(CipherSuite)
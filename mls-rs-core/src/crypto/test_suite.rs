@@ -8,7 +8,8 @@ use itertools::Itertools;
 use crate::crypto::HpkeContextR;
 
 use super::{
-    CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkeContextS, HpkePublicKey, HpkeSecretKey,
+    CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkeContextS, HpkePublicKey,
+    HpkeSecretKey, SignaturePublicKey, SignatureVerification,
 };
 
 const PATH: &str = concat!(
@@ -160,6 +161,37 @@ async fn verify_signature_tests<C: CipherSuiteProvider>(
             assert_eq!(derived, public);
         }
     }
+
+    verify_signature_batch(cs).await;
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn verify_signature_batch<C: CipherSuiteProvider>(cs: &C) {
+    let test_cases = generate_signature_tests(cs).await;
+    let public_keys = test_cases
+        .iter()
+        .map(|tc| tc.public.clone().into())
+        .collect::<Vec<SignaturePublicKey>>();
+
+    let mut batch = test_cases
+        .iter()
+        .zip(&public_keys)
+        .map(|(tc, public_key)| SignatureVerification {
+            public_key,
+            signature: &tc.signature,
+            data: &tc.data,
+        })
+        .collect::<Vec<_>>();
+
+    // Checks that `cs` can verify signatures in a batch
+    cs.verify_batch(&batch).await.unwrap();
+
+    // Checks that a single signature over the wrong data invalidates the batch
+    batch[0].data = b"hello world";
+
+    let res = cs.verify_batch(&batch).await;
+
+    assert!(res.is_err());
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
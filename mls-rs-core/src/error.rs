@@ -61,3 +61,10 @@ impl IntoAnyError for mls_rs_codec::Error {
 }
 
 impl IntoAnyError for core::convert::Infallible {}
+
+#[cfg(feature = "std")]
+impl IntoAnyError for std::io::Error {
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
@@ -49,6 +49,45 @@ impl EpochRecord {
     }
 }
 
+/// Retention policy for the prior epochs of a group.
+///
+/// Storage implementations keep the `max_epochs` most recent prior epochs of
+/// each group. This type determines which stored epoch records are outside
+/// of the policy and can be deleted, e.g. when
+/// [compacting](GroupStateStorage::compact) a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochRetention {
+    max_epochs: u64,
+}
+
+impl EpochRetention {
+    /// Retain at most `max_epochs` prior epochs of each group.
+    pub const fn new(max_epochs: u64) -> Self {
+        Self { max_epochs }
+    }
+
+    /// Maximum number of prior epochs retained for each group.
+    pub fn max_epochs(&self) -> u64 {
+        self.max_epochs
+    }
+
+    /// Highest epoch id that can be deleted when the most recent stored
+    /// epoch has id `max_epoch_id`.
+    ///
+    /// All epochs with an id lower than or equal to the returned value are
+    /// safe to delete. `None` is returned if all epochs must be kept.
+    pub fn delete_up_to(&self, max_epoch_id: u64) -> Option<u64> {
+        max_epoch_id.checked_sub(self.max_epochs)
+    }
+
+    /// Check if the epoch with id `epoch_id` must be kept when the most
+    /// recent stored epoch has id `max_epoch_id`.
+    pub fn is_retained(&self, epoch_id: u64, max_epoch_id: u64) -> bool {
+        self.delete_up_to(max_epoch_id)
+            .map_or(true, |delete_up_to| epoch_id > delete_up_to)
+    }
+}
+
 /// Storage that can persist and reload a group state.
 ///
 /// A group state is recorded as a combination of the current state
@@ -63,9 +102,19 @@ impl EpochRecord {
 /// group. It is up to the implementer of this trait to provide a mechanism
 /// to delete records that can be used by an application.
 ///
+/// Records of prior epochs are deleted by [`write`](GroupStateStorage::write)
+/// as new epochs are inserted. Long-lived storage should additionally call
+/// [`compact`](GroupStateStorage::compact) after changing the retention
+/// policy, and [`vacuum`](GroupStateStorage::vacuum) periodically to reclaim
+/// space, since neither happens as part of normal group operation.
+///
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 pub trait GroupStateStorage: Send + Sync {
     type Error: IntoAnyError;
 
@@ -104,4 +153,58 @@ pub trait GroupStateStorage: Send + Sync {
     /// The [`EpochRecord::id`] value that is associated with a stored
     /// prior epoch for a particular group.
     async fn max_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error>;
+
+    /// Ids of all groups with a stored state.
+    ///
+    /// The default implementation returns an empty list. Storage that
+    /// supports enumeration should override it so that applications can
+    /// discover persisted groups without keeping their own index.
+    async fn group_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    /// Delete all records of the group with id `group_id` that are not
+    /// needed according to the [`EpochRetention`] policy of the storage.
+    ///
+    /// The default implementation does nothing.
+    async fn compact(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        let _ = group_id;
+        Ok(())
+    }
+
+    /// Delete all prior epochs of the group with id `group_id`, keeping its
+    /// current state.
+    ///
+    /// This is called once the local member is removed from the group and
+    /// its secrets are erased. The default implementation does nothing.
+    async fn delete_epochs(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        let _ = group_id;
+        Ok(())
+    }
+
+    /// Reclaim space left by deleted records across all groups, e.g. by
+    /// rewriting the underlying database file.
+    ///
+    /// The default implementation does nothing.
+    async fn vacuum(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EpochRetention;
+
+    #[test]
+    fn epoch_retention() {
+        let retention = EpochRetention::new(3);
+
+        assert_eq!(retention.delete_up_to(2), None);
+        assert_eq!(retention.delete_up_to(3), Some(0));
+        assert_eq!(retention.delete_up_to(10), Some(7));
+
+        assert!(retention.is_retained(0, 2));
+        assert!(!retention.is_retained(7, 10));
+        assert!(retention.is_retained(8, 10));
+    }
 }
@@ -57,6 +57,13 @@ impl ProposalType {
     pub const EXTERNAL_INIT: ProposalType = ProposalType(6);
     pub const GROUP_CONTEXT_EXTENSIONS: ProposalType = ProposalType(7);
 
+    /// SelfRemove proposal type defined in
+    /// [draft-ietf-mls-extensions](https://datatracker.ietf.org/doc/draft-ietf-mls-extensions/).
+    ///
+    /// This is not a default proposal type, so members must list it in their
+    /// capabilities in order to use it.
+    pub const SELF_REMOVE: ProposalType = ProposalType(0x000a);
+
     /// Default proposal types defined
     /// in [RFC 9420](https://www.rfc-editor.org/rfc/rfc9420.html#name-leaf-node-contents)
     pub const DEFAULT: &'static [ProposalType] = &[
@@ -4,15 +4,22 @@
 
 use alloc::vec;
 use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    ops::Deref,
+};
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 
 use crate::{
-    crypto::CipherSuite,
+    crypto::{CipherSuite, CipherSuiteProvider},
+    error::{AnyError, IntoAnyError},
     extension::{ExtensionList, ExtensionType},
     identity::{CredentialType, SigningIdentity},
     protocol_version::ProtocolVersion,
 };
 
+const FINGERPRINT_LABEL: &[u8] = b"MLS 1.0 member fingerprint";
+
 use super::ProposalType;
 
 #[derive(Clone, PartialEq, Eq, Debug, MlsSize, MlsEncode, MlsDecode)]
@@ -152,9 +159,87 @@ impl Member {
     pub fn extensions(&self) -> &ExtensionList {
         &self.extensions
     }
+
+    /// Compute the [`MemberFingerprint`] of this member using the hash
+    /// function of `cipher_suite_provider`.
+    ///
+    /// The fingerprint only depends on the
+    /// [signing identity](Member::signing_identity) and the cipher suite, so it
+    /// is the same across groups and epochs as long as the member does not
+    /// change its signature key or credential.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn fingerprint<P: CipherSuiteProvider>(
+        &self,
+        cipher_suite_provider: &P,
+    ) -> Result<MemberFingerprint, AnyError> {
+        let mut input = FINGERPRINT_LABEL.to_vec();
+
+        self.signing_identity
+            .mls_encode(&mut input)
+            .map_err(IntoAnyError::into_any_error)?;
+
+        cipher_suite_provider
+            .hash(&input)
+            .await
+            .map(MemberFingerprint)
+            .map_err(IntoAnyError::into_any_error)
+    }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Constant size identifier of a [`Member`] computed by
+/// [`Member::fingerprint`].
+///
+/// The size of the fingerprint is the output size of the hash function of
+/// the cipher suite in use.
+#[derive(Clone, PartialEq, Eq, Hash, Ord, PartialOrd, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemberFingerprint(
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::vec_serde"))]
+    Vec<u8>,
+);
+
+impl Debug for MemberFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::debug::pretty_bytes(&self.0)
+            .named("MemberFingerprint")
+            .fmt(f)
+    }
+}
+
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl MemberFingerprint {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for MemberFingerprint {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for MemberFingerprint {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for MemberFingerprint {
+    fn from(data: Vec<u8>) -> Self {
+        MemberFingerprint(data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 #[cfg_attr(
     all(feature = "ffi", not(test)),
@@ -12,7 +12,11 @@ use super::{CredentialType, SigningIdentity};
 /// Identity system that can be used to validate a
 /// [`SigningIdentity`](mls-rs-core::identity::SigningIdentity)
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 pub trait IdentityProvider: Send + Sync {
     /// Error type that this provider returns on internal failure.
     type Error: IntoAnyError;
@@ -29,6 +33,22 @@ pub trait IdentityProvider: Send + Sync {
         extensions: Option<&ExtensionList>,
     ) -> Result<(), Self::Error>;
 
+    /// Determine if the `leaf_node_extensions` presented by a group member
+    /// with `signing_identity` are valid, for example because they contain
+    /// an attestation of the device holding the signature key.
+    ///
+    /// This is called after [`validate_member`](IdentityProvider::validate_member)
+    /// for every leaf node that is validated. The default implementation
+    /// accepts all extensions.
+    async fn validate_leaf_node_extensions(
+        &self,
+        _signing_identity: &SigningIdentity,
+        _leaf_node_extensions: &ExtensionList,
+        _extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Determine if `signing_identity` is valid for an external sender in
     /// the ExternalSendersExtension stored in the group context.
     ///
@@ -8,7 +8,67 @@ use alloc::vec::Vec;
 use core::fmt::{self, Debug};
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 
-use crate::{crypto::HpkeSecretKey, error::IntoAnyError};
+use crate::{
+    crypto::{CipherSuite, HpkeSecretKey},
+    error::IntoAnyError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MlsEncode, MlsDecode, MlsSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+/// Metadata describing a stored key package.
+///
+/// Metadata is available without decoding the key package itself, allowing
+/// storage providers to index and query stored key packages.
+pub struct KeyPackageMetadata {
+    /// Time the key package was generated, in seconds since the unix epoch.
+    pub created_at: u64,
+    /// Time the key package expires, in seconds since the unix epoch.
+    pub expires_at: u64,
+    /// Cipher suite of the key package.
+    pub cipher_suite: CipherSuite,
+    /// Whether the key package is a last resort key package that is kept
+    /// after being used to join a group.
+    #[mls_codec(with = "bool_codec")]
+    pub last_resort: bool,
+}
+
+mod bool_codec {
+    use alloc::vec::Vec;
+    use mls_rs_codec::{MlsDecode, MlsEncode};
+
+    pub fn mls_encoded_len(_: &bool) -> usize {
+        1
+    }
+
+    pub fn mls_encode(value: &bool, writer: &mut Vec<u8>) -> Result<(), mls_rs_codec::Error> {
+        (*value as u8).mls_encode(writer)
+    }
+
+    pub fn mls_decode(reader: &mut &[u8]) -> Result<bool, mls_rs_codec::Error> {
+        match u8::mls_decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(mls_rs_codec::Error::UnsupportedEnumDiscriminant),
+        }
+    }
+}
+
+impl KeyPackageMetadata {
+    pub fn new(
+        created_at: u64,
+        expires_at: u64,
+        cipher_suite: CipherSuite,
+        last_resort: bool,
+    ) -> KeyPackageMetadata {
+        Self {
+            created_at,
+            expires_at,
+            cipher_suite,
+            last_resort,
+        }
+    }
+}
 
 #[derive(Clone, PartialEq, Eq, MlsEncode, MlsDecode, MlsSize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -19,7 +79,7 @@ pub struct KeyPackageData {
     pub key_package_bytes: Vec<u8>,
     pub init_key: HpkeSecretKey,
     pub leaf_node_key: HpkeSecretKey,
-    pub expiration: u64,
+    pub metadata: KeyPackageMetadata,
 }
 
 impl Debug for KeyPackageData {
@@ -31,7 +91,7 @@ impl Debug for KeyPackageData {
             )
             .field("init_key", &self.init_key)
             .field("leaf_node_key", &self.leaf_node_key)
-            .field("expiration", &self.expiration)
+            .field("metadata", &self.metadata)
             .finish()
     }
 }
@@ -41,20 +101,29 @@ impl KeyPackageData {
         key_package_bytes: Vec<u8>,
         init_key: HpkeSecretKey,
         leaf_node_key: HpkeSecretKey,
-        expiration: u64,
+        metadata: KeyPackageMetadata,
     ) -> KeyPackageData {
         Self {
             key_package_bytes,
             init_key,
             leaf_node_key,
-            expiration,
+            metadata,
         }
     }
+
+    /// Time the key package expires, in seconds since the unix epoch.
+    pub fn expiration(&self) -> u64 {
+        self.metadata.expires_at
+    }
 }
 
 /// Storage trait that maintains key package secrets.
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 pub trait KeyPackageStorage: Send + Sync {
     /// Error type that the underlying storage mechanism returns on internal
     /// failure.
@@ -81,4 +150,16 @@ pub trait KeyPackageStorage: Send + Sync {
     /// `None` should be returned in the event that no key packages are found
     /// that match `id`.
     async fn get(&self, id: &[u8]) -> Result<Option<KeyPackageData>, Self::Error>;
+
+    /// Retrieve the ids and metadata of all key packages that expire before
+    /// `time`, given in seconds since the unix epoch.
+    async fn expiring_before(
+        &self,
+        time: u64,
+    ) -> Result<Vec<(Vec<u8>, KeyPackageMetadata)>, Self::Error>;
+
+    /// Count stored key packages for each cipher suite.
+    ///
+    /// Cipher suites without any stored key packages are omitted.
+    async fn count_by_suite(&self) -> Result<Vec<(CipherSuite, usize)>, Self::Error>;
 }
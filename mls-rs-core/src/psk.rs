@@ -11,7 +11,7 @@ use core::{
     ops::Deref,
 };
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
-use zeroize::Zeroizing;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 #[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,6 +30,9 @@ impl Debug for PreSharedKey {
     }
 }
 
+// The inner value is zeroized when dropped.
+impl ZeroizeOnDrop for PreSharedKey {}
+
 impl PreSharedKey {
     /// Create a new PreSharedKey.
     pub fn new(data: Vec<u8>) -> Self {
@@ -119,7 +122,11 @@ impl From<Vec<u8>> for ExternalPskId {
 
 /// Storage trait to maintain a set of pre-shared key values.
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 pub trait PreSharedKeyStorage: Send + Sync {
     /// Error type that the underlying storage mechanism returns on internal
     /// failure.
@@ -7,7 +7,7 @@ use core::{
     fmt::{self, Debug},
     ops::{Deref, DerefMut},
 };
-use zeroize::Zeroizing;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 #[cfg_attr(
     all(feature = "ffi", not(test)),
@@ -23,6 +23,9 @@ impl Debug for Secret {
     }
 }
 
+// The inner value is zeroized when dropped.
+impl ZeroizeOnDrop for Secret {}
+
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
 impl Secret {
     pub fn as_bytes(&self) -> &[u8] {
@@ -33,6 +33,11 @@ impl MlsTime {
 impl MlsTime {
     /// Current system time.
     pub fn now() -> Self {
+        #[cfg(any(test, feature = "test_util"))]
+        if let Some(now) = virtual_clock::now() {
+            return now;
+        }
+
         Self {
             seconds: std::time::SystemTime::now()
                 .duration_since(std::time::SystemTime::UNIX_EPOCH)
@@ -60,8 +65,119 @@ extern "C" {
 #[cfg(target_arch = "wasm32")]
 impl MlsTime {
     pub fn now() -> Self {
+        #[cfg(all(any(test, feature = "test_util"), feature = "std"))]
+        if let Some(now) = virtual_clock::now() {
+            return now;
+        }
+
         Self {
             seconds: (date_now() / 1000.0) as u64,
         }
     }
 }
+
+#[cfg(all(any(test, feature = "test_util"), feature = "std"))]
+pub use virtual_clock::VirtualClock;
+
+#[cfg(all(any(test, feature = "test_util"), feature = "std"))]
+mod virtual_clock {
+    use core::{cell::Cell, marker::PhantomData, time::Duration};
+
+    use super::MlsTime;
+
+    std::thread_local! {
+        static VIRTUAL_NOW: Cell<Option<MlsTime>> = const { Cell::new(None) };
+    }
+
+    pub(super) fn now() -> Option<MlsTime> {
+        VIRTUAL_NOW.with(|now| now.get())
+    }
+
+    /// Virtual time source for tests.
+    ///
+    /// While a `VirtualClock` is alive, [`MlsTime::now`] returns the time of
+    /// the clock instead of the system time on the thread that started it.
+    /// This applies to everything that reads the current time such as key
+    /// package lifetimes, leaf node and credential validation and expiry
+    /// based cleanup of storage, which allows simulating long periods of
+    /// group evolution without waiting.
+    ///
+    /// The system clock is restored when the `VirtualClock` is dropped.
+    #[derive(Debug)]
+    pub struct VirtualClock {
+        // The clock is bound to the thread that started it.
+        _not_send: PhantomData<*const ()>,
+    }
+
+    impl VirtualClock {
+        /// Start a virtual clock at time `start` on the current thread.
+        ///
+        /// # Panics
+        ///
+        /// Panics if a virtual clock is already running on the current thread.
+        pub fn start(start: MlsTime) -> Self {
+            VIRTUAL_NOW.with(|now| {
+                assert!(now.get().is_none(), "virtual clock already running");
+                now.set(Some(start));
+            });
+
+            Self {
+                _not_send: PhantomData,
+            }
+        }
+
+        /// Start a virtual clock at the current system time.
+        pub fn start_now() -> Self {
+            Self::start(MlsTime::now())
+        }
+
+        /// Current time of the clock.
+        pub fn now(&self) -> MlsTime {
+            now().unwrap_or_else(|| MlsTime::from(0))
+        }
+
+        /// Move the clock to `time`. Time is allowed to go backwards.
+        pub fn set(&self, time: MlsTime) {
+            VIRTUAL_NOW.with(|now| now.set(Some(time)));
+        }
+
+        /// Move the clock forward by `duration`.
+        pub fn advance(&self, duration: Duration) {
+            let seconds = self
+                .now()
+                .seconds_since_epoch()
+                .saturating_add(duration.as_secs());
+
+            self.set(MlsTime::from(seconds));
+        }
+    }
+
+    impl Drop for VirtualClock {
+        fn drop(&mut self) {
+            VIRTUAL_NOW.with(|now| now.set(None));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use core::time::Duration;
+
+        use super::{MlsTime, VirtualClock};
+
+        #[test]
+        fn virtual_clock_controls_now() {
+            let clock = VirtualClock::start(MlsTime::from(1000));
+            assert_eq!(MlsTime::now(), MlsTime::from(1000));
+
+            clock.advance(Duration::from_secs(10 * 365 * 24 * 3600));
+
+            assert_eq!(
+                MlsTime::now().seconds_since_epoch(),
+                1000 + 10 * 365 * 24 * 3600
+            );
+
+            drop(clock);
+            assert!(MlsTime::now().seconds_since_epoch() > 1000 + 10 * 365 * 24 * 3600);
+        }
+    }
+}
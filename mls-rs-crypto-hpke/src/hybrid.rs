@@ -0,0 +1,221 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use mls_rs_crypto_traits::{KemResult, KemType};
+
+use mls_rs_core::{
+    crypto::{HpkePublicKey, HpkeSecretKey},
+    error::{AnyError, IntoAnyError},
+};
+use zeroize::Zeroizing;
+
+use alloc::vec::Vec;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum HybridKemError {
+    #[cfg_attr(feature = "std", error(transparent))]
+    FirstKemError(AnyError),
+    #[cfg_attr(feature = "std", error(transparent))]
+    SecondKemError(AnyError),
+    #[cfg_attr(feature = "std", error("hybrid KEM input has invalid length {0}"))]
+    InvalidLength(usize),
+}
+
+impl IntoAnyError for HybridKemError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+/// Byte lengths of the keys and encapsulations of the first component of a
+/// [`HybridKem`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ComponentSizes {
+    pub enc: usize,
+    pub public_key: usize,
+    pub secret_key: usize,
+}
+
+impl ComponentSizes {
+    /// Sizes of DHKEM(X25519, HKDF-SHA256).
+    pub const X25519: ComponentSizes = ComponentSizes {
+        enc: 32,
+        public_key: 32,
+        secret_key: 32,
+    };
+}
+
+/// KEM combining two component KEMs, typically a classical DHKEM and a
+/// post-quantum KEM, such that the shared secret stays secure as long as one
+/// of the components is secure.
+///
+/// Public keys, secret keys, encapsulations and shared secrets are the
+/// concatenations of the values of the first and second component. Key pairs
+/// derived with [`KemType::derive`] feed the same input keying material to both
+/// components, so this KEM does not match the key derivation of the hybrid KEMs
+/// registered for HPKE (such as X25519Kyber768Draft00) and its KEM ID must not
+/// be one of theirs.
+///
+/// A hybrid KEM can be used in [`Hpke`](crate::hpke::Hpke) like any other
+/// KEM. Ciphersuites built on top of it are not defined by the MLS RFC, so
+/// they should use a value from the private use range `0xF000..=0xFFFF` and
+/// are only negotiated with clients whose crypto provider supports them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HybridKem<K1: KemType, K2: KemType> {
+    first: K1,
+    second: K2,
+    first_sizes: ComponentSizes,
+    kem_id: u16,
+}
+
+impl<K1: KemType, K2: KemType> HybridKem<K1, K2> {
+    pub fn new(first: K1, first_sizes: ComponentSizes, second: K2, kem_id: u16) -> Self {
+        Self {
+            first,
+            second,
+            first_sizes,
+            kem_id,
+        }
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<K1: KemType, K2: KemType> KemType for HybridKem<K1, K2> {
+    type Error = HybridKemError;
+
+    fn kem_id(&self) -> u16 {
+        self.kem_id
+    }
+
+    async fn derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        // Each component derives its key pair with its own KEM suite ID, which
+        // separates the two derivations.
+        let (first_sk, first_pk) = self
+            .first
+            .derive(ikm)
+            .await
+            .map_err(|e| HybridKemError::FirstKemError(e.into_any_error()))?;
+
+        let (second_sk, second_pk) = self
+            .second
+            .derive(ikm)
+            .await
+            .map_err(|e| HybridKemError::SecondKemError(e.into_any_error()))?;
+
+        Ok(concat_key_pairs(
+            (first_sk, first_pk),
+            (second_sk, second_pk),
+        ))
+    }
+
+    async fn generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        let first = self
+            .first
+            .generate()
+            .await
+            .map_err(|e| HybridKemError::FirstKemError(e.into_any_error()))?;
+
+        let second = self
+            .second
+            .generate()
+            .await
+            .map_err(|e| HybridKemError::SecondKemError(e.into_any_error()))?;
+
+        Ok(concat_key_pairs(first, second))
+    }
+
+    async fn encap(&self, remote_key: &HpkePublicKey) -> Result<KemResult, Self::Error> {
+        let (first_pk, second_pk) = split(remote_key, self.first_sizes.public_key)?;
+
+        let first = self
+            .first
+            .encap(&first_pk.to_vec().into())
+            .await
+            .map_err(|e| HybridKemError::FirstKemError(e.into_any_error()))?;
+
+        let second = self
+            .second
+            .encap(&second_pk.to_vec().into())
+            .await
+            .map_err(|e| HybridKemError::SecondKemError(e.into_any_error()))?;
+
+        let first_ss = Zeroizing::new(first.shared_secret);
+        let second_ss = Zeroizing::new(second.shared_secret);
+
+        Ok(KemResult::new(
+            [first_ss.as_slice(), &second_ss].concat(),
+            [first.enc, second.enc].concat(),
+        ))
+    }
+
+    async fn decap(
+        &self,
+        enc: &[u8],
+        secret_key: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let (first_enc, second_enc) = split(enc, self.first_sizes.enc)?;
+        let (first_sk, second_sk) = split(secret_key, self.first_sizes.secret_key)?;
+        let (first_pk, second_pk) = split(local_public, self.first_sizes.public_key)?;
+
+        let first_ss = self
+            .first
+            .decap(
+                first_enc,
+                &first_sk.to_vec().into(),
+                &first_pk.to_vec().into(),
+            )
+            .await
+            .map(Zeroizing::new)
+            .map_err(|e| HybridKemError::FirstKemError(e.into_any_error()))?;
+
+        let second_ss = self
+            .second
+            .decap(
+                second_enc,
+                &second_sk.to_vec().into(),
+                &second_pk.to_vec().into(),
+            )
+            .await
+            .map(Zeroizing::new)
+            .map_err(|e| HybridKemError::SecondKemError(e.into_any_error()))?;
+
+        Ok([first_ss.as_slice(), &second_ss].concat())
+    }
+
+    fn public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        let (first_pk, second_pk) = split(key, self.first_sizes.public_key)?;
+
+        self.first
+            .public_key_validate(&first_pk.to_vec().into())
+            .map_err(|e| HybridKemError::FirstKemError(e.into_any_error()))?;
+
+        self.second
+            .public_key_validate(&second_pk.to_vec().into())
+            .map_err(|e| HybridKemError::SecondKemError(e.into_any_error()))
+    }
+}
+
+fn split(data: &[u8], first_len: usize) -> Result<(&[u8], &[u8]), HybridKemError> {
+    (data.len() > first_len)
+        .then(|| data.split_at(first_len))
+        .ok_or(HybridKemError::InvalidLength(data.len()))
+}
+
+fn concat_key_pairs(
+    (first_sk, first_pk): (HpkeSecretKey, HpkePublicKey),
+    (second_sk, second_pk): (HpkeSecretKey, HpkePublicKey),
+) -> (HpkeSecretKey, HpkePublicKey) {
+    (
+        [first_sk.as_ref(), &second_sk].concat().into(),
+        [first_pk.as_ref(), &second_pk].concat().into(),
+    )
+}
@@ -11,6 +11,7 @@ wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 pub mod context;
 pub mod dhkem;
 pub mod hpke;
+pub mod hybrid;
 pub mod kdf;
 
 #[cfg(feature = "test_utils")]
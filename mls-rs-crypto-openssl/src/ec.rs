@@ -269,6 +269,14 @@ pub fn private_key_from_der(data: &[u8]) -> Result<EcPrivateKey, ErrorStack> {
     PKey::private_key_from_der(data)
 }
 
+pub fn private_key_from_pkcs8(data: &[u8]) -> Result<EcPrivateKey, ErrorStack> {
+    PKey::private_key_from_pkcs8(data)
+}
+
+pub fn private_key_from_pem(data: &[u8]) -> Result<EcPrivateKey, ErrorStack> {
+    PKey::private_key_from_pem(data)
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use serde::{Deserialize, Serialize};
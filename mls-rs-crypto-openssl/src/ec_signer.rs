@@ -15,8 +15,9 @@ use thiserror::Error;
 
 use crate::ec::{
     curve_from_private_key, curve_from_public_key, generate_keypair, private_key_bytes_to_public,
-    private_key_from_bytes, private_key_from_der, private_key_to_bytes, pub_key_from_uncompressed,
-    pub_key_to_uncompressed, public_key_from_der, EcError,
+    private_key_from_bytes, private_key_from_der, private_key_from_pem, private_key_from_pkcs8,
+    private_key_to_bytes, pub_key_from_uncompressed, pub_key_to_uncompressed, public_key_from_der,
+    EcError, EcPrivateKey,
 };
 
 #[derive(Debug, Error)]
@@ -69,8 +70,28 @@ impl EcSigner {
         &self,
         der_data: &[u8],
     ) -> Result<SignatureSecretKey, EcError> {
-        let key = private_key_from_der(der_data)?;
+        self.secret_key_from_pkey(private_key_from_der(der_data)?)
+    }
+
+    /// Import a private key encoded as a DER `PrivateKeyInfo` structure as defined
+    /// in [RFC 5208](https://www.rfc-editor.org/rfc/rfc5208).
+    pub fn signature_key_import_pkcs8_private(
+        &self,
+        pkcs8_data: &[u8],
+    ) -> Result<SignatureSecretKey, EcError> {
+        self.secret_key_from_pkey(private_key_from_pkcs8(pkcs8_data)?)
+    }
+
+    /// Import a PEM encoded private key. Both `PRIVATE KEY` (PKCS#8) and
+    /// `EC PRIVATE KEY` (SEC1) blocks are accepted.
+    pub fn signature_key_import_pem_private(
+        &self,
+        pem_data: &[u8],
+    ) -> Result<SignatureSecretKey, EcError> {
+        self.secret_key_from_pkey(private_key_from_pem(pem_data)?)
+    }
 
+    fn secret_key_from_pkey(&self, key: EcPrivateKey) -> Result<SignatureSecretKey, EcError> {
         curve_from_private_key(&key)
             .filter(|&c| c == self.0)
             .ok_or(EcError::InvalidKeyBytes)?;
@@ -150,13 +171,25 @@ mod test {
     use mls_rs_crypto_traits::Curve;
 
     use crate::{
-        ec::test_utils::{
-            get_test_public_keys, get_test_public_keys_der, get_test_secret_keys,
-            get_test_secret_keys_der, TestKeys,
+        ec::{
+            private_key_from_der,
+            test_utils::{
+                get_test_public_keys, get_test_public_keys_der, get_test_secret_keys,
+                get_test_secret_keys_der, TestKeys,
+            },
+            EcError,
         },
         ec_signer::EcSigner,
     };
 
+    const SIGNATURE_CURVES: [Curve; 5] = [
+        Curve::P256,
+        Curve::P384,
+        Curve::P521,
+        Curve::Ed25519,
+        Curve::Ed448,
+    ];
+
     #[test]
     fn import_der_public() {
         let keys = get_test_public_keys();
@@ -204,4 +237,52 @@ mod test {
 
         assert_eq!(keys, converted);
     }
+
+    #[test]
+    fn import_pem_private() {
+        let keys = get_test_secret_keys();
+        let der_keys = get_test_secret_keys_der();
+
+        for curve in SIGNATURE_CURVES {
+            let pem = private_key_from_der(&der_keys.get_key_from_curve(curve))
+                .unwrap()
+                .private_key_to_pem_pkcs8()
+                .unwrap();
+
+            let imported = EcSigner(curve)
+                .signature_key_import_pem_private(&pem)
+                .unwrap();
+
+            assert_eq!(imported.to_vec(), keys.get_key_from_curve(curve));
+        }
+    }
+
+    #[test]
+    fn import_pkcs8_private() {
+        let keys = get_test_secret_keys();
+        let der_keys = get_test_secret_keys_der();
+
+        // Edwards curve keys have no traditional encoding, their DER test vectors are PKCS#8
+        for curve in [Curve::Ed25519, Curve::Ed448] {
+            let imported = EcSigner(curve)
+                .signature_key_import_pkcs8_private(&der_keys.get_key_from_curve(curve))
+                .unwrap();
+
+            assert_eq!(imported.to_vec(), keys.get_key_from_curve(curve));
+        }
+    }
+
+    #[test]
+    fn import_pem_private_wrong_curve() {
+        let der_keys = get_test_secret_keys_der();
+
+        let pem = private_key_from_der(&der_keys.p256)
+            .unwrap()
+            .private_key_to_pem_pkcs8()
+            .unwrap();
+
+        let res = EcSigner(Curve::P384).signature_key_import_pem_private(&pem);
+
+        assert!(matches!(res, Err(EcError::InvalidKeyBytes)));
+    }
 }
@@ -173,6 +173,26 @@ where
             .signature_key_import_der_private(der_data)
             .map_err(Into::into)
     }
+
+    /// Import a signature secret key encoded as a PKCS#8 DER structure.
+    pub fn import_pkcs8_private_signing_key(
+        &self,
+        pkcs8_data: &[u8],
+    ) -> Result<SignatureSecretKey, OpensslCryptoError> {
+        self.ec_signer
+            .signature_key_import_pkcs8_private(pkcs8_data)
+            .map_err(Into::into)
+    }
+
+    /// Import a PEM encoded signature secret key.
+    pub fn import_pem_private_signing_key(
+        &self,
+        pem_data: &[u8],
+    ) -> Result<SignatureSecretKey, OpensslCryptoError> {
+        self.ec_signer
+            .signature_key_import_pem_private(pem_data)
+            .map_err(Into::into)
+    }
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
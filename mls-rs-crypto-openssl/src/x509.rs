@@ -8,26 +8,29 @@ use mls_rs_core::{
     crypto::{CipherSuite, SignaturePublicKey, SignatureSecretKey},
     error::IntoAnyError,
     identity::{CertificateChain, SigningIdentity},
+    time::MlsTime,
 };
 use mls_rs_identity_x509::{
-    CertificateRequestParameters, DerCertificate, DerCertificateRequest, SubjectAltName,
-    SubjectComponent, SubjectIdentityExtractor, X509CredentialValidator, X509IdentityProvider,
-    X509RequestWriter,
+    CertificateRequestParameters, DerCertificate, DerCertificateRequest, DerCrl, DerOcspResponse,
+    RevocationStatus, SubjectAltName, SubjectComponent, SubjectIdentityExtractor,
+    X509CredentialValidator, X509IdentityProvider, X509RequestWriter, X509RevocationReader,
 };
 use openssl::{
+    asn1::{Asn1GeneralizedTimeRef, Asn1Object, Asn1Time},
     bn::BigNumContext,
     ec::PointConversionForm,
     error::ErrorStack,
     hash::MessageDigest,
     nid::Nid,
+    ocsp::{OcspCertId, OcspCertStatus, OcspFlag, OcspRequest, OcspResponse, OcspResponseStatus},
     pkey::{PKey, PKeyRef, Private, Public},
     stack::Stack,
     x509::{
         extension::{BasicConstraints, KeyUsage, SubjectAlternativeName},
         store::{X509Store, X509StoreBuilder},
         verify::{X509VerifyFlags, X509VerifyParam},
-        X509Builder, X509Extension, X509Name, X509NameBuilder, X509ReqBuilder, X509StoreContext,
-        X509VerifyResult, X509v3Context, X509,
+        CrlStatus, X509Builder, X509Crl, X509Extension, X509Name, X509NameBuilder, X509ReqBuilder,
+        X509StoreContext, X509VerifyResult, X509v3Context, X509,
     },
 };
 use thiserror::Error;
@@ -48,6 +51,12 @@ pub enum X509Error {
     InvalidCertificateLifetime,
     #[error("unsupported cipher suite")]
     UnsupportedCipherSuite,
+    #[error("certificate does not match the requested signature key")]
+    CertificateKeyMismatch,
+    #[error("revocation information is not signed by the certificate issuer")]
+    InvalidRevocationSignature,
+    #[error("invalid time in revocation information: {0}")]
+    InvalidRevocationTime(String),
     #[error(transparent)]
     EcSignerError(#[from] EcSignerError),
     #[error(transparent)]
@@ -280,6 +289,228 @@ impl mls_rs_identity_x509::X509CertificateReader for X509Reader {
         let public_key = self.parse_certificate(certificate)?.public_key()?;
         pub_key_to_uncompressed(public_key).map(Into::into)
     }
+
+    fn subject_attribute(
+        &self,
+        certificate: &DerCertificate,
+        oid: &str,
+    ) -> Result<Option<String>, Self::Error> {
+        let oid = Asn1Object::from_str(oid)?;
+        let certificate = self.parse_certificate(certificate)?;
+
+        // Objects unknown to OpenSSL all have an undefined NID and can only be
+        // told apart by their dotted representation.
+        let attribute = certificate.subject_name().entries().find(|e| {
+            e.object().nid() == oid.nid()
+                && (oid.nid() != Nid::UNDEF || e.object().to_string() == oid.to_string())
+        });
+
+        attribute
+            .map(|e| {
+                std::str::from_utf8(e.data().as_slice())
+                    .map(ToString::to_string)
+                    .map_err(|_| X509Error::InvalidCertificateData)
+            })
+            .transpose()
+    }
+}
+
+impl X509RevocationReader for X509Reader {
+    type Error = X509Error;
+
+    fn crl_distribution_points(
+        &self,
+        certificate: &DerCertificate,
+    ) -> Result<Vec<String>, Self::Error> {
+        let Some(points) = self
+            .parse_certificate(certificate)?
+            .crl_distribution_points()
+        else {
+            return Ok(vec![]);
+        };
+
+        let urls = points
+            .iter()
+            .filter_map(|point| point.distpoint()?.fullname())
+            .flat_map(|names| {
+                names
+                    .iter()
+                    .filter_map(|n| n.uri().map(ToString::to_string))
+            })
+            .collect();
+
+        Ok(urls)
+    }
+
+    fn ocsp_responders(&self, certificate: &DerCertificate) -> Result<Vec<String>, Self::Error> {
+        let Some(access) = self.parse_certificate(certificate)?.authority_info() else {
+            return Ok(vec![]);
+        };
+
+        let urls = access
+            .iter()
+            .filter(|a| a.method().nid() == Nid::AD_OCSP)
+            .filter_map(|a| a.location().uri().map(ToString::to_string))
+            .collect();
+
+        Ok(urls)
+    }
+
+    fn ocsp_request(
+        &self,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let certificate = self.parse_certificate(certificate)?;
+        let issuer = self.parse_certificate(issuer)?;
+
+        let mut request = OcspRequest::new()?;
+        request.add_id(OcspCertId::from_cert(
+            MessageDigest::sha1(),
+            &certificate,
+            &issuer,
+        )?)?;
+
+        request.to_der().map_err(Into::into)
+    }
+
+    fn crl_status(
+        &self,
+        crl: &DerCrl,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+        timestamp: Option<MlsTime>,
+    ) -> Result<RevocationStatus, Self::Error> {
+        let crl = X509Crl::from_der(crl.as_ref())?;
+        let certificate = self.parse_certificate(certificate)?;
+        let issuer = self.parse_certificate(issuer)?;
+
+        let issuer_name = issuer.subject_name().to_der()?;
+
+        // A CRL of another CA does not cover the certificate.
+        if crl.issuer_name().to_der()? != issuer_name
+            || certificate.issuer_name().to_der()? != issuer_name
+        {
+            return Ok(RevocationStatus::Unknown);
+        }
+
+        if !crl.verify(issuer.public_key()?.as_ref())? {
+            return Err(X509Error::InvalidRevocationSignature);
+        }
+
+        if let Some(timestamp) = timestamp {
+            let now = Asn1Time::from_unix(timestamp.seconds_since_epoch() as i64)?;
+
+            if crl.last_update() > now || matches!(crl.next_update(), Some(next) if next < now) {
+                return Ok(RevocationStatus::Unknown);
+            }
+        }
+
+        match crl.get_by_cert(&certificate) {
+            CrlStatus::Revoked(_) => Ok(RevocationStatus::Revoked),
+            CrlStatus::NotRevoked | CrlStatus::RemoveFromCrl(_) => Ok(RevocationStatus::Good),
+        }
+    }
+
+    fn ocsp_status(
+        &self,
+        response: &DerOcspResponse,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+        timestamp: Option<MlsTime>,
+    ) -> Result<RevocationStatus, Self::Error> {
+        let response = OcspResponse::from_der(response.as_ref())?;
+
+        if response.status() != OcspResponseStatus::SUCCESSFUL {
+            return Ok(RevocationStatus::Unknown);
+        }
+
+        let basic = response.basic()?;
+        let certificate = self.parse_certificate(certificate)?;
+        let issuer = self.parse_certificate(issuer)?;
+
+        // The issuer is the only trust anchor, so the response must be signed
+        // by the issuer itself or by a responder certificate it issued.
+        let mut builder = X509StoreBuilder::new()?;
+        builder.add_cert(issuer.clone())?;
+
+        let mut params = X509VerifyParam::new()?;
+        let mut flags = X509VerifyFlags::PARTIAL_CHAIN;
+
+        match timestamp {
+            Some(timestamp) => params.set_time(timestamp.seconds_since_epoch() as i64),
+            None => flags |= X509VerifyFlags::NO_CHECK_TIME,
+        }
+
+        params.set_flags(flags)?;
+        builder.set_param(&params)?;
+
+        let mut certs = Stack::new()?;
+        certs.push(issuer.clone())?;
+
+        basic
+            .verify(&certs, &builder.build(), OcspFlag::empty())
+            .map_err(|_| X509Error::InvalidRevocationSignature)?;
+
+        let id = OcspCertId::from_cert(MessageDigest::sha1(), &certificate, &issuer)?;
+
+        let Some(status) = basic.find_status(&id) else {
+            return Ok(RevocationStatus::Unknown);
+        };
+
+        if let Some(timestamp) = timestamp {
+            let now = Asn1Time::from_unix(timestamp.seconds_since_epoch() as i64)?;
+
+            let this_update = generalized_time_to_asn1_time(status.this_update)?;
+
+            let next_update = status
+                .next_update()
+                .map(generalized_time_to_asn1_time)
+                .transpose()?;
+
+            if this_update > now || matches!(next_update, Some(next) if next < now) {
+                return Ok(RevocationStatus::Unknown);
+            }
+        }
+
+        match status.status {
+            OcspCertStatus::GOOD => Ok(RevocationStatus::Good),
+            OcspCertStatus::REVOKED => Ok(RevocationStatus::Revoked),
+            _ => Ok(RevocationStatus::Unknown),
+        }
+    }
+}
+
+// OpenSSL only allows comparing `Asn1Time` values, so generalized times are
+// converted through their printed form, e.g. `Jan  2 08:23:51 2123 GMT`.
+fn generalized_time_to_asn1_time(time: &Asn1GeneralizedTimeRef) -> Result<Asn1Time, X509Error> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let printed = time.to_string();
+    let invalid_time = || X509Error::InvalidRevocationTime(printed.clone());
+
+    let mut fields = printed.split_whitespace();
+
+    let (Some(month), Some(day), Some(clock), Some(year)) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(invalid_time());
+    };
+
+    let month = MONTHS
+        .iter()
+        .position(|m| *m == month)
+        .ok_or_else(invalid_time)?;
+
+    let day = day.parse::<u8>().map_err(|_| invalid_time())?;
+
+    // Fractional seconds are dropped.
+    let clock = clock.split('.').next().unwrap_or_default().replace(':', "");
+
+    Asn1Time::from_str(&format!("{year}{:02}{day:02}{clock}Z", month + 1))
+        .map_err(|_| invalid_time())
 }
 
 fn ip_bytes_to_ip_addr(input: &[u8]) -> Option<IpAddr> {
@@ -453,6 +684,105 @@ impl X509RequestWriter for CertificateRequestWriter {
     }
 }
 
+#[derive(Debug, Clone)]
+/// Builder of a certificate request for a newly generated signature key,
+/// used to enroll an MLS client with a certificate authority.
+///
+/// The key matches the signature algorithm of the cipher suite. Once the
+/// certificate authority issued a certificate for the request,
+/// [`CertificateEnrollment::signing_identity`] returns the signing identity
+/// to be used by the client.
+pub struct CertificateRequestBuilder {
+    cipher_suite: CipherSuite,
+    params: CertificateRequestParameters,
+}
+
+impl CertificateRequestBuilder {
+    pub fn new(cipher_suite: CipherSuite) -> Self {
+        Self {
+            cipher_suite,
+            params: Default::default(),
+        }
+    }
+
+    /// Append a component to the subject of the request.
+    pub fn subject_component(mut self, component: SubjectComponent) -> Self {
+        self.params.subject.push(component);
+        self
+    }
+
+    /// Add a subject alt name to the request.
+    pub fn subject_alt_name(mut self, alt_name: SubjectAltName) -> Self {
+        self.params.subject_alt_names.push(alt_name);
+        self
+    }
+
+    /// Request a certificate authority certificate.
+    pub fn ca(mut self, is_ca: bool) -> Self {
+        self.params.is_ca = is_ca;
+        self
+    }
+
+    /// Generate a signature key and a certificate request signed with it.
+    pub fn build(self) -> Result<CertificateEnrollment, X509Error> {
+        let writer = CertificateRequestWriter::new_generate_key(self.cipher_suite)?;
+        let request = writer.write(self.params)?;
+
+        let public_key = writer
+            .signer
+            .signature_key_derive_public(&writer.signing_key)?;
+
+        Ok(CertificateEnrollment {
+            request,
+            signing_key: writer.signing_key,
+            public_key,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Certificate request together with the generated signature key it was
+/// signed with.
+pub struct CertificateEnrollment {
+    request: DerCertificateRequest,
+    signing_key: SignatureSecretKey,
+    public_key: SignaturePublicKey,
+}
+
+impl CertificateEnrollment {
+    /// Certificate request to submit to the certificate authority.
+    pub fn request(&self) -> &DerCertificateRequest {
+        &self.request
+    }
+
+    pub fn public_key(&self) -> &SignaturePublicKey {
+        &self.public_key
+    }
+
+    pub fn signing_key(&self) -> &SignatureSecretKey {
+        &self.signing_key
+    }
+
+    /// Returns a signing identity and its secret key from the certificate
+    /// chain issued for the request, starting with the leaf certificate.
+    pub fn signing_identity(
+        self,
+        chain: CertificateChain,
+    ) -> Result<(SigningIdentity, SignatureSecretKey), X509Error> {
+        let leaf = chain.leaf().ok_or(X509Error::EmptyCertificateChain)?;
+        let leaf_public_key = pub_key_to_uncompressed(X509::from_der(leaf)?.public_key()?)?;
+
+        if leaf_public_key != *self.public_key {
+            return Err(X509Error::CertificateKeyMismatch);
+        }
+
+        Ok((
+            SigningIdentity::new(chain.into_credential(), self.public_key),
+            self.signing_key,
+        ))
+    }
+}
+
 /// Returns a signature secret key from a key in DER or PEM format
 pub fn signature_secret_key_from_bytes(data: &[u8]) -> Result<SignatureSecretKey, X509Error> {
     let secret_key = if looks_like_der(data) {
@@ -576,19 +906,22 @@ mod tests {
         time::MlsTime,
     };
     use mls_rs_identity_x509::{
-        CertificateChain, CertificateRequestParameters, DerCertificateRequest, SubjectAltName,
-        SubjectComponent, X509CertificateReader, X509RequestWriter,
+        CertificateChain, CertificateRequestParameters, DerCertificate, DerCertificateRequest,
+        DerCrl, DerOcspResponse, RevocationStatus, SubjectAltName, SubjectComponent,
+        X509CertificateReader, X509RequestWriter, X509RevocationReader,
     };
     use openssl::{
+        asn1::Asn1Time,
+        hash::MessageDigest,
         pkey::PKey,
-        x509::{X509Name, X509Req, X509},
+        x509::{X509Builder, X509Name, X509Req, X509},
     };
 
     use crate::{
         ec::private_key_to_bytes,
         x509::{
             test_utils::{load_another_ca, load_test_invalid_ca_chain, load_test_invalid_chain},
-            CertificateRequestWriter,
+            CertificateRequestBuilder, CertificateRequestWriter,
         },
     };
 
@@ -756,6 +1089,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn subject_attribute() {
+        let test_cert = load_github_leaf();
+        let reader = X509Reader::new();
+
+        assert_eq!(
+            reader.subject_attribute(&test_cert, "2.5.4.10").unwrap(),
+            Some(String::from("GitHub, Inc."))
+        );
+
+        assert_eq!(
+            reader
+                .subject_attribute(&test_cert, "1.3.6.1.4.1.55555.1")
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn subject_alt_names() {
         let test_cert = load_github_leaf();
@@ -833,8 +1184,234 @@ mod tests {
         test_writing_csr(false)
     }
 
+    fn issue_certificate(csr: &DerCertificateRequest) -> CertificateChain {
+        let csr = X509Req::from_der(csr.as_ref()).unwrap();
+        let public_key = csr.public_key().unwrap();
+        assert!(csr.verify(&public_key).unwrap());
+
+        let ca = X509::from_pem(include_bytes!("../test_data/x509/root_ca/cert.pem")).unwrap();
+
+        let ca_key =
+            PKey::private_key_from_pem(include_bytes!("../test_data/x509/root_ca/key.pem"))
+                .unwrap();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(csr.subject_name()).unwrap();
+        builder.set_issuer_name(ca.subject_name()).unwrap();
+        builder.set_pubkey(&public_key).unwrap();
+
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+
+        builder.sign(&ca_key, MessageDigest::null()).unwrap();
+
+        CertificateChain::from(vec![
+            builder.build().to_der().unwrap(),
+            ca.to_der().unwrap(),
+        ])
+    }
+
+    #[test]
+    fn enrollment_produces_signing_identity() {
+        let enrollment = CertificateRequestBuilder::new(CipherSuite::P256_AES128)
+            .subject_component(SubjectComponent::CommonName("Leaf".to_string()))
+            .subject_alt_name(SubjectAltName::Email("leaf@leaf.org".to_string()))
+            .build()
+            .unwrap();
+
+        let csr = X509Req::from_der(enrollment.request().as_ref()).unwrap();
+
+        assert_eq!(
+            pub_key_to_uncompressed(csr.public_key().unwrap()).unwrap(),
+            enrollment.public_key().to_vec()
+        );
+
+        let chain = issue_certificate(enrollment.request());
+        let public_key = enrollment.public_key().clone();
+        let signing_key = enrollment.signing_key().clone();
+
+        assert_matches!(
+            enrollment.clone().signing_identity(load_test_cert_chain()),
+            Err(X509Error::CertificateKeyMismatch)
+        );
+
+        let (identity, secret_key) = enrollment.signing_identity(chain.clone()).unwrap();
+
+        assert_eq!(identity.signature_key, public_key);
+        assert_eq!(identity.credential.as_x509(), Some(&chain));
+        assert_eq!(secret_key, signing_key);
+    }
+
     fn ec_key_from_pem(pem_bytes: &[u8]) -> SignatureSecretKey {
         let key = PKey::private_key_from_pem(pem_bytes).unwrap();
         private_key_to_bytes(&key).unwrap().into()
     }
+
+    // The revocation fixtures are issued by `root_ca` and valid from October
+    // 2026, see `test_data/x509/revocation/generate.sh`.
+    fn revocation_fixture_certificates() -> (DerCertificate, DerCertificate, DerCertificate) {
+        let root = include_bytes!("../test_data/x509/root_ca/cert.der").to_vec();
+        let intermediate = include_bytes!("../test_data/x509/intermediate_ca/cert.der").to_vec();
+        let leaf = include_bytes!("../test_data/x509/leaf/cert.der").to_vec();
+
+        (root.into(), intermediate.into(), leaf.into())
+    }
+
+    fn revocation_fixture_crl() -> DerCrl {
+        DerCrl::new(include_bytes!("../test_data/x509/revocation/crl.der").to_vec())
+    }
+
+    fn years_since_epoch(years: u64) -> Option<MlsTime> {
+        Some(MlsTime::from_duration_since_epoch(Duration::from_secs(
+            years * 365 * 24 * 3600,
+        )))
+    }
+
+    #[test]
+    fn crl_status_of_revoked_and_good_certificates() {
+        let (root, intermediate, leaf) = revocation_fixture_certificates();
+        let crl = revocation_fixture_crl();
+        let reader = X509Reader::new();
+
+        assert_eq!(
+            reader.crl_status(&crl, &leaf, &root, None).unwrap(),
+            RevocationStatus::Revoked
+        );
+
+        assert_eq!(
+            reader.crl_status(&crl, &intermediate, &root, None).unwrap(),
+            RevocationStatus::Good
+        );
+    }
+
+    #[test]
+    fn crl_status_checks_freshness() {
+        let (root, _, leaf) = revocation_fixture_certificates();
+        let crl = revocation_fixture_crl();
+        let reader = X509Reader::new();
+
+        assert_eq!(
+            reader
+                .crl_status(&crl, &leaf, &root, years_since_epoch(60))
+                .unwrap(),
+            RevocationStatus::Revoked
+        );
+
+        assert_eq!(
+            reader
+                .crl_status(&crl, &leaf, &root, years_since_epoch(54))
+                .unwrap(),
+            RevocationStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn crl_of_another_issuer_is_ignored() {
+        let (_, _, leaf) = revocation_fixture_certificates();
+        let crl = revocation_fixture_crl();
+
+        assert_eq!(
+            X509Reader::new()
+                .crl_status(&crl, &leaf, &load_test_ca(), None)
+                .unwrap(),
+            RevocationStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn ocsp_status_of_revoked_and_good_certificates() {
+        let (root, intermediate, leaf) = revocation_fixture_certificates();
+        let reader = X509Reader::new();
+
+        let revoked = DerOcspResponse::new(
+            include_bytes!("../test_data/x509/revocation/ocsp_leaf_revoked.der").to_vec(),
+        );
+
+        let good = DerOcspResponse::new(
+            include_bytes!("../test_data/x509/revocation/ocsp_intermediate_good.der").to_vec(),
+        );
+
+        assert_eq!(
+            reader
+                .ocsp_status(&revoked, &leaf, &root, years_since_epoch(60))
+                .unwrap(),
+            RevocationStatus::Revoked
+        );
+
+        assert_eq!(
+            reader
+                .ocsp_status(&good, &intermediate, &root, None)
+                .unwrap(),
+            RevocationStatus::Good
+        );
+
+        // A response about another certificate says nothing about this one.
+        assert_eq!(
+            reader
+                .ocsp_status(&revoked, &intermediate, &root, None)
+                .unwrap(),
+            RevocationStatus::Unknown
+        );
+
+        assert_eq!(
+            reader
+                .ocsp_status(&revoked, &leaf, &root, years_since_epoch(54))
+                .unwrap(),
+            RevocationStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn ocsp_response_must_be_signed_by_issuer() {
+        let (_, _, leaf) = revocation_fixture_certificates();
+
+        let revoked = DerOcspResponse::new(
+            include_bytes!("../test_data/x509/revocation/ocsp_leaf_revoked.der").to_vec(),
+        );
+
+        assert_matches!(
+            X509Reader::new().ocsp_status(&revoked, &leaf, &load_test_ca(), None),
+            Err(X509Error::InvalidRevocationSignature)
+        );
+    }
+
+    #[test]
+    fn ocsp_request_identifies_certificate() {
+        let (root, _, leaf) = revocation_fixture_certificates();
+
+        assert_eq!(
+            X509Reader::new().ocsp_request(&leaf, &root).unwrap(),
+            include_bytes!("../test_data/x509/revocation/ocsp_leaf_request.der").to_vec()
+        );
+    }
+
+    #[test]
+    fn revocation_sources_of_certificate() {
+        let reader = X509Reader::new();
+        let github_leaf = load_github_leaf();
+
+        assert_eq!(
+            reader.crl_distribution_points(&github_leaf).unwrap(),
+            vec![
+                "http://crl3.digicert.com/DigiCertTLSHybridECCSHA3842020CA1-1.crl".to_string(),
+                "http://crl4.digicert.com/DigiCertTLSHybridECCSHA3842020CA1-1.crl".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            reader.ocsp_responders(&github_leaf).unwrap(),
+            vec!["http://ocsp.digicert.com".to_string()]
+        );
+
+        let (_, _, leaf) = revocation_fixture_certificates();
+
+        assert!(reader.crl_distribution_points(&leaf).unwrap().is_empty());
+        assert!(reader.ocsp_responders(&leaf).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,40 @@
+#!/bin/sh
+# Regenerates the revocation fixtures: root_ca revokes leaf and vouches for
+# intermediate_ca, in a CRL and in OCSP responses valid for 100 years.
+set -e
+cd "$(dirname "$0")"
+work=$(mktemp -d)
+
+cat > "$work/ca.cnf" <<CNF
+[ ca ]
+default_ca = root
+
+[ root ]
+database = $work/index.txt
+crlnumber = $work/crlnumber
+default_md = default
+default_crl_days = 36500
+CNF
+
+touch "$work/index.txt"
+echo 01 > "$work/crlnumber"
+
+ca="-config $work/ca.cnf -cert ../root_ca/cert.pem -keyfile ../root_ca/key.pem"
+openssl ca $ca -revoke ../leaf/cert.pem -crl_reason keyCompromise
+openssl ca $ca -gencrl -out "$work/crl.pem"
+openssl crl -in "$work/crl.pem" -outform der -out crl.der
+
+printf 'V\t21230101143422Z\t\t%s\tunknown\t/CN=IntermediateCA/C=CH\n' \
+    "$(openssl x509 -in ../intermediate_ca/cert.pem -noout -serial | cut -d= -f2)" >> "$work/index.txt"
+
+respond() {
+    openssl ocsp -issuer ../root_ca/cert.pem -cert "$1" -no_nonce -reqout "$2"
+    openssl ocsp -index "$work/index.txt" -CA ../root_ca/cert.pem \
+        -rsigner ../root_ca/cert.pem -rkey ../root_ca/key.pem \
+        -reqin "$2" -respout "$3" -ndays 36500 -resp_no_certs
+}
+
+respond ../leaf/cert.pem ocsp_leaf_request.der ocsp_leaf_revoked.der
+respond ../intermediate_ca/cert.pem "$work/request.der" ocsp_intermediate_good.der
+
+rm -rf "$work"
@@ -17,6 +17,7 @@ use core::array::TryFromSliceError;
 use core::fmt::{self, Debug};
 use ed25519_dalek::Signer;
 use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use p256::pkcs8::DecodePrivateKey;
 use rand_core::OsRng;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -153,6 +154,33 @@ fn ed25519_private_from_bytes(bytes: &[u8]) -> Result<EcPrivateKey, EcError> {
     Ok(EcPrivateKey::Ed25519(signing_key))
 }
 
+pub fn private_key_from_pkcs8(data: &[u8], curve: Curve) -> Result<EcPrivateKey, EcError> {
+    match curve {
+        Curve::P256 => p256::SecretKey::from_pkcs8_der(data)
+            .map_err(|_| EcError::EcKeyInvalidKeyData)
+            .map(EcPrivateKey::P256),
+        Curve::Ed25519 => ed25519_dalek::SigningKey::from_pkcs8_der(data)
+            .map_err(|_| EcError::EcKeyInvalidKeyData)
+            .map(EcPrivateKey::Ed25519),
+        _ => Err(EcError::UnsupportedCurve),
+    }
+}
+
+pub fn private_key_from_pem(data: &[u8], curve: Curve) -> Result<EcPrivateKey, EcError> {
+    let pem = core::str::from_utf8(data).map_err(|_| EcError::EcKeyInvalidKeyData)?;
+
+    match curve {
+        Curve::P256 => p256::SecretKey::from_pkcs8_pem(pem)
+            .or_else(|_| p256::SecretKey::from_sec1_pem(pem))
+            .map_err(|_| EcError::EcKeyInvalidKeyData)
+            .map(EcPrivateKey::P256),
+        Curve::Ed25519 => ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+            .map_err(|_| EcError::EcKeyInvalidKeyData)
+            .map(EcPrivateKey::Ed25519),
+        _ => Err(EcError::UnsupportedCurve),
+    }
+}
+
 pub fn private_key_to_bytes(key: &EcPrivateKey) -> Result<Vec<u8>, EcError> {
     match key {
         EcPrivateKey::X25519(key) => Ok(key.to_bytes().to_vec()),
@@ -3,9 +3,9 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::ec::{
-    generate_keypair, private_key_bytes_to_public, private_key_from_bytes,
-    pub_key_from_uncompressed, sign_ed25519, sign_p256, verify_ed25519, verify_p256, EcError,
-    EcPrivateKey, EcPublicKey,
+    generate_keypair, private_key_bytes_to_public, private_key_from_bytes, private_key_from_pem,
+    private_key_from_pkcs8, private_key_to_bytes, pub_key_from_uncompressed, sign_ed25519,
+    sign_p256, verify_ed25519, verify_p256, EcError, EcPrivateKey, EcPublicKey,
 };
 use alloc::vec::Vec;
 use core::ops::Deref;
@@ -63,6 +63,26 @@ impl EcSigner {
         Ok(private_key_bytes_to_public(secret_key, self.0)?.into())
     }
 
+    /// Import a private key encoded as a DER `PrivateKeyInfo` structure as defined
+    /// in [RFC 5208](https://www.rfc-editor.org/rfc/rfc5208).
+    pub fn signature_key_import_pkcs8_private(
+        &self,
+        pkcs8_data: &[u8],
+    ) -> Result<SignatureSecretKey, EcSignerError> {
+        let key = private_key_from_pkcs8(pkcs8_data, self.0)?;
+        Ok(private_key_to_bytes(&key)?.into())
+    }
+
+    /// Import a PEM encoded private key. `PRIVATE KEY` (PKCS#8) blocks are
+    /// accepted for all curves, `EC PRIVATE KEY` (SEC1) blocks for P-256.
+    pub fn signature_key_import_pem_private(
+        &self,
+        pem_data: &[u8],
+    ) -> Result<SignatureSecretKey, EcSignerError> {
+        let key = private_key_from_pem(pem_data, self.0)?;
+        Ok(private_key_to_bytes(&key)?.into())
+    }
+
     pub fn sign(
         &self,
         secret_key: &SignatureSecretKey,
@@ -94,3 +114,71 @@ impl EcSigner {
         ver.then_some(()).ok_or(EcSignerError::InvalidSignature)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use assert_matches::assert_matches;
+    use ed25519_dalek::pkcs8::EncodePrivateKey;
+    use mls_rs_crypto_traits::Curve;
+
+    use crate::{
+        ec::{private_key_from_bytes, test_utils::get_test_secret_keys, EcError, EcPrivateKey},
+        ec_signer::{EcSigner, EcSignerError},
+    };
+
+    fn test_key_pkcs8(curve: Curve) -> Vec<u8> {
+        let key_bytes = get_test_secret_keys().get_key_from_curve(curve);
+
+        match private_key_from_bytes(&key_bytes, curve).unwrap() {
+            EcPrivateKey::P256(key) => key.to_pkcs8_der().unwrap().as_bytes().to_vec(),
+            EcPrivateKey::Ed25519(key) => key.to_pkcs8_der().unwrap().as_bytes().to_vec(),
+            EcPrivateKey::X25519(_) => panic!("not a signature curve"),
+        }
+    }
+
+    #[test]
+    fn import_pkcs8_private() {
+        for curve in [Curve::P256, Curve::Ed25519] {
+            let imported = EcSigner(curve)
+                .signature_key_import_pkcs8_private(&test_key_pkcs8(curve))
+                .unwrap();
+
+            let expected = get_test_secret_keys().get_key_from_curve(curve);
+
+            assert_eq!(imported.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn import_pem_private() {
+        let keys = get_test_secret_keys();
+
+        for curve in [Curve::P256, Curve::Ed25519] {
+            let key_bytes = keys.get_key_from_curve(curve);
+
+            let pem = match private_key_from_bytes(&key_bytes, curve).unwrap() {
+                EcPrivateKey::P256(key) => key.to_sec1_pem(Default::default()).unwrap(),
+                EcPrivateKey::Ed25519(key) => key.to_pkcs8_pem(Default::default()).unwrap(),
+                EcPrivateKey::X25519(_) => panic!("not a signature curve"),
+            };
+
+            let imported = EcSigner(curve)
+                .signature_key_import_pem_private(pem.as_bytes())
+                .unwrap();
+
+            assert_eq!(imported.to_vec(), key_bytes);
+        }
+    }
+
+    #[test]
+    fn import_pkcs8_private_wrong_curve() {
+        let res = EcSigner(Curve::Ed25519)
+            .signature_key_import_pkcs8_private(&test_key_pkcs8(Curve::P256));
+
+        assert_matches!(
+            res,
+            Err(EcSignerError::EcError(EcError::EcKeyInvalidKeyData))
+        );
+    }
+}
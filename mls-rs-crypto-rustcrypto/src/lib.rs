@@ -184,6 +184,24 @@ where
         })
     }
 
+    /// Import a signature secret key encoded as a PKCS#8 DER structure.
+    pub fn import_pkcs8_private_signing_key(
+        &self,
+        pkcs8_data: &[u8],
+    ) -> Result<SignatureSecretKey, RustCryptoError> {
+        Ok(self
+            .ec_signer
+            .signature_key_import_pkcs8_private(pkcs8_data)?)
+    }
+
+    /// Import a PEM encoded signature secret key.
+    pub fn import_pem_private_signing_key(
+        &self,
+        pem_data: &[u8],
+    ) -> Result<SignatureSecretKey, RustCryptoError> {
+        Ok(self.ec_signer.signature_key_import_pem_private(pem_data)?)
+    }
+
     pub fn random_bytes(&self, out: &mut [u8]) -> Result<(), RustCryptoError> {
         OsRng.try_fill_bytes(out).map_err(Into::into)
     }
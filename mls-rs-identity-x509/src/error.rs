@@ -20,6 +20,8 @@ pub enum X509IdentityError {
     InvalidOffset,
     #[cfg_attr(feature = "std", error("empty certificate chain"))]
     EmptyCertificateChain,
+    #[cfg_attr(feature = "std", error("no identity source found in certificate"))]
+    IdentityNotFound,
     #[cfg_attr(feature = "std", error(transparent))]
     CredentialEncodingError(AnyError),
     #[cfg_attr(feature = "std", error(transparent))]
@@ -30,6 +32,22 @@ pub enum X509IdentityError {
     X509ValidationError(AnyError),
     #[cfg_attr(feature = "std", error(transparent))]
     IdentityWarningProviderError(AnyError),
+    #[cfg_attr(
+        feature = "std",
+        error("certificate at index {0} of the chain is revoked")
+    )]
+    CertificateRevoked(usize),
+    #[cfg_attr(
+        feature = "std",
+        error("revocation status of the certificate at index {0} of the chain is unknown")
+    )]
+    RevocationStatusUnknown(usize),
+    #[cfg_attr(feature = "std", error(transparent))]
+    RevocationReaderError(AnyError),
+    #[cfg_attr(feature = "std", error(transparent))]
+    RevocationFetcherError(AnyError),
+    #[cfg_attr(feature = "std", error(transparent))]
+    RevocationCheckError(AnyError),
 }
 
 impl mls_rs_core::error::IntoAnyError for X509IdentityError {
@@ -2,11 +2,11 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use mls_rs_core::{error::IntoAnyError, identity::CertificateChain};
 
 use crate::{
-    DerCertificate, SubjectComponent, X509CertificateReader, X509IdentityError,
+    DerCertificate, SubjectAltName, SubjectComponent, X509CertificateReader, X509IdentityError,
     X509IdentityExtractor,
 };
 
@@ -115,6 +115,144 @@ where
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Certificate field providing the identity of its subject.
+pub enum IdentitySource {
+    /// Email subject alt name.
+    SanEmail,
+    /// URI subject alt name.
+    SanUri,
+    /// DNS subject alt name.
+    SanDns,
+    /// Common name component of the subject.
+    CommonName,
+    /// Subject attribute with the type identified by an OID in dotted
+    /// decimal notation. Requires support from the [`X509CertificateReader`].
+    SubjectAttribute(String),
+}
+
+#[derive(Debug, Clone)]
+/// A utility to determine unique identity for use with MLS by reading a
+/// configurable list of certificate fields.
+///
+/// The identity is the UTF-8 value of the first [`IdentitySource`] found in
+/// the certificate, trying the sources in the order they were configured.
+/// Certificates containing none of the sources are rejected.
+pub struct SourceIdentityExtractor<R: X509CertificateReader> {
+    offset: usize,
+    sources: Vec<IdentitySource>,
+    reader: R,
+}
+
+impl<R> SourceIdentityExtractor<R>
+where
+    R: X509CertificateReader,
+{
+    /// Create a new identity extractor trying `sources` in order.
+    ///
+    /// `offset` is used to determine which certificate in a [`CertificateChain`]
+    /// should be used to evaluate identity. A value of 0 indicates to use the
+    /// leaf (first value) of the chain.
+    pub fn new(offset: usize, sources: Vec<IdentitySource>, reader: R) -> Self {
+        Self {
+            offset,
+            sources,
+            reader,
+        }
+    }
+
+    fn extract(
+        &self,
+        certificate: &DerCertificate,
+        source: &IdentitySource,
+    ) -> Result<Option<String>, R::Error> {
+        let alt_name = |matches: fn(&SubjectAltName) -> Option<&String>| -> Result<_, R::Error> {
+            Ok(self
+                .reader
+                .subject_alt_names(certificate)?
+                .iter()
+                .find_map(matches)
+                .cloned())
+        };
+
+        match source {
+            IdentitySource::SanEmail => alt_name(|name| match name {
+                SubjectAltName::Email(email) => Some(email),
+                _ => None,
+            }),
+            IdentitySource::SanUri => alt_name(|name| match name {
+                SubjectAltName::Uri(uri) => Some(uri),
+                _ => None,
+            }),
+            IdentitySource::SanDns => alt_name(|name| match name {
+                SubjectAltName::Dns(dns) => Some(dns),
+                _ => None,
+            }),
+            IdentitySource::CommonName => Ok(self
+                .reader
+                .subject_components(certificate)?
+                .into_iter()
+                .find_map(|component| match component {
+                    SubjectComponent::CommonName(common_name) => Some(common_name),
+                    _ => None,
+                })),
+            IdentitySource::SubjectAttribute(oid) => {
+                self.reader.subject_attribute(certificate, oid)
+            }
+        }
+    }
+
+    /// Get a unique identifier for a `certificate_chain`.
+    pub fn identity(
+        &self,
+        certificate_chain: &CertificateChain,
+    ) -> Result<Vec<u8>, X509IdentityError> {
+        let cert = get_certificate(certificate_chain, self.offset)?;
+
+        for source in &self.sources {
+            let identity = self
+                .extract(cert, source)
+                .map_err(|e| X509IdentityError::X509ReaderError(e.into_any_error()))?;
+
+            if let Some(identity) = identity {
+                return Ok(identity.into_bytes());
+            }
+        }
+
+        Err(X509IdentityError::IdentityNotFound)
+    }
+
+    /// Determine if `successor` resolves to the same
+    /// identity value as `predecessor`, indicating that
+    /// `predecessor` and `successor` are controlled by the same entity.
+    pub fn valid_successor(
+        &self,
+        predecessor: &CertificateChain,
+        successor: &CertificateChain,
+    ) -> Result<bool, X509IdentityError> {
+        Ok(self.identity(predecessor)? == self.identity(successor)?)
+    }
+}
+
+impl<R> X509IdentityExtractor for SourceIdentityExtractor<R>
+where
+    R: X509CertificateReader,
+{
+    type Error = X509IdentityError;
+
+    fn identity(&self, certificate_chain: &CertificateChain) -> Result<Vec<u8>, Self::Error> {
+        self.identity(certificate_chain)
+    }
+
+    fn valid_successor(
+        &self,
+        predecessor: &CertificateChain,
+        successor: &CertificateChain,
+    ) -> Result<bool, Self::Error> {
+        self.valid_successor(predecessor, successor)
+    }
+}
+
 fn get_certificate(
     certificate_chain: &CertificateChain,
     offset: usize,
@@ -127,8 +265,9 @@ fn get_certificate(
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::{
-        test_utils::test_certificate_chain, MockX509CertificateReader, SubjectComponent,
-        SubjectIdentityExtractor, X509IdentityError,
+        test_utils::test_certificate_chain, IdentitySource, MockX509CertificateReader,
+        SourceIdentityExtractor, SubjectAltName, SubjectComponent, SubjectIdentityExtractor,
+        X509IdentityError,
     };
 
     use alloc::vec;
@@ -399,4 +538,86 @@ mod tests {
             "Successor cert chain with different subjects passed valid check!"
         );
     }
+
+    fn test_source_setup<F>(
+        sources: Vec<IdentitySource>,
+        mut mock_setup: F,
+    ) -> SourceIdentityExtractor<MockX509CertificateReader>
+    where
+        F: FnMut(&mut MockX509CertificateReader),
+    {
+        let mut x509_reader = MockX509CertificateReader::new();
+
+        mock_setup(&mut x509_reader);
+
+        SourceIdentityExtractor::new(0, sources, x509_reader)
+    }
+
+    #[test]
+    fn sources_are_tried_in_order() {
+        let sources = vec![
+            IdentitySource::SanEmail,
+            IdentitySource::SubjectAttribute("0.9.2342.19200300.100.1.1".to_string()),
+            IdentitySource::SanDns,
+            IdentitySource::CommonName,
+        ];
+
+        let subject_extractor = test_source_setup(sources, |reader| {
+            reader.expect_subject_alt_names().returning(|_| {
+                Ok(vec![
+                    SubjectAltName::Uri("https://example.com".to_string()),
+                    SubjectAltName::Dns("example.com".to_string()),
+                ])
+            });
+
+            reader
+                .expect_subject_attribute()
+                .once()
+                .returning(|_, oid| {
+                    assert_eq!(oid, "0.9.2342.19200300.100.1.1");
+                    Ok(None)
+                });
+
+            reader.expect_subject_components().never();
+        });
+
+        assert_eq!(
+            subject_extractor
+                .identity(&test_certificate_chain())
+                .unwrap(),
+            b"example.com".to_vec()
+        );
+    }
+
+    #[test]
+    fn missing_sources_are_rejected() {
+        let subject_extractor = test_source_setup(vec![IdentitySource::CommonName], |reader| {
+            reader
+                .expect_subject_components()
+                .returning(|_| Ok(vec![SubjectComponent::CountryName("US".to_string())]));
+        });
+
+        assert_matches!(
+            subject_extractor.identity(&test_certificate_chain()),
+            Err(X509IdentityError::IdentityNotFound)
+        );
+    }
+
+    #[test]
+    fn successor_with_same_source_value_is_valid() {
+        let predecessor = test_certificate_chain();
+        let successor = test_certificate_chain();
+
+        let subject_extractor = test_source_setup(vec![IdentitySource::SanUri], |reader| {
+            reader.expect_subject_alt_names().returning(|_| {
+                Ok(vec![SubjectAltName::Uri(
+                    "im:alice@example.com".to_string(),
+                )])
+            });
+        });
+
+        assert!(subject_extractor
+            .valid_successor(&predecessor, &successor)
+            .unwrap());
+    }
 }
@@ -8,6 +8,7 @@ extern crate alloc;
 mod error;
 mod identity_extractor;
 mod provider;
+mod revocation;
 mod traits;
 mod util;
 
@@ -17,6 +18,7 @@ use core::fmt::{self, Debug};
 pub use error::*;
 pub use identity_extractor::*;
 pub use provider::*;
+pub use revocation::*;
 pub use traits::*;
 
 pub use mls_rs_core::identity::{CertificateChain, DerCertificate};
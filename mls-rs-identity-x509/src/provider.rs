@@ -2,7 +2,10 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::{util::credential_to_chain, CertificateChain, X509IdentityError};
+use crate::{
+    util::credential_to_chain, CertificateChain, NoRevocationCheck, X509IdentityError,
+    X509RevocationChecker,
+};
 use alloc::vec;
 use alloc::vec::Vec;
 use mls_rs_core::{
@@ -60,9 +63,10 @@ pub trait X509CredentialValidator {
 /// behavior to its generic sub-components.
 ///
 /// Only X509 credentials are supported by this provider.
-pub struct X509IdentityProvider<IE, V> {
+pub struct X509IdentityProvider<IE, V, RC = NoRevocationCheck> {
     pub identity_extractor: IE,
     pub validator: V,
+    pub revocation_checker: RC,
 }
 
 impl<IE, V> X509IdentityProvider<IE, V>
@@ -70,16 +74,41 @@ where
     IE: X509IdentityExtractor,
     V: X509CredentialValidator,
 {
-    /// Create a new identity provider.
+    /// Create a new identity provider that doesn't check for revocation.
     pub fn new(identity_extractor: IE, validator: V) -> Self {
         Self {
             identity_extractor,
             validator,
+            revocation_checker: NoRevocationCheck,
+        }
+    }
+}
+
+impl<IE, V, RC> X509IdentityProvider<IE, V, RC>
+where
+    IE: X509IdentityExtractor,
+    V: X509CredentialValidator,
+    RC: X509RevocationChecker,
+{
+    /// Check chains accepted by the validator for revoked certificates
+    /// using `revocation_checker`, for example a
+    /// [`RevocationChecker`](crate::RevocationChecker).
+    pub fn with_revocation_checker<RC2>(
+        self,
+        revocation_checker: RC2,
+    ) -> X509IdentityProvider<IE, V, RC2>
+    where
+        RC2: X509RevocationChecker,
+    {
+        X509IdentityProvider {
+            identity_extractor: self.identity_extractor,
+            validator: self.validator,
+            revocation_checker,
         }
     }
 
     /// Determine if a certificate is valid based on the behavior of the
-    /// underlying validator provided.
+    /// underlying validator and revocation checker provided.
     pub fn validate(
         &self,
         signing_identity: &mls_rs_core::identity::SigningIdentity,
@@ -92,6 +121,10 @@ where
             .validate_chain(&chain, timestamp)
             .map_err(|e| X509IdentityError::X509ValidationError(e.into_any_error()))?;
 
+        self.revocation_checker
+            .check_chain(&chain, timestamp)
+            .map_err(|e| X509IdentityError::RevocationCheckError(e.into_any_error()))?;
+
         if leaf_public_key != signing_identity.signature_key {
             return Err(X509IdentityError::SignatureKeyMismatch);
         }
@@ -135,11 +168,16 @@ where
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
-impl<IE, V> IdentityProvider for X509IdentityProvider<IE, V>
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<IE, V, RC> IdentityProvider for X509IdentityProvider<IE, V, RC>
 where
     IE: X509IdentityExtractor + Send + Sync,
     V: X509CredentialValidator + Send + Sync,
+    RC: X509RevocationChecker + Send + Sync,
 {
     type Error = X509IdentityError;
 
@@ -192,8 +230,8 @@ mod tests {
             test_certificate_chain, test_signing_identity, test_signing_identity_with_chain,
             TestError,
         },
-        MockX509CredentialValidator, MockX509IdentityExtractor, X509IdentityError,
-        X509IdentityProvider,
+        MockX509CredentialValidator, MockX509IdentityExtractor, MockX509RevocationChecker,
+        X509IdentityError, X509IdentityProvider,
     };
 
     use alloc::vec;
@@ -284,4 +322,31 @@ mod tests {
             Err(X509IdentityError::X509ValidationError(_))
         )
     }
+
+    #[test]
+    fn test_revoked_chain() {
+        let test_signing_identity = test_signing_identity();
+
+        let test_provider = test_setup(|_, validator| {
+            let validation_result = test_signing_identity.signature_key.clone();
+
+            validator
+                .expect_validate_chain()
+                .return_once_st(|_, _| Ok(validation_result));
+        });
+
+        let mut revocation_checker = MockX509RevocationChecker::new();
+
+        revocation_checker
+            .expect_check_chain()
+            .once()
+            .return_once_st(|_, _| Err(TestError));
+
+        let test_provider = test_provider.with_revocation_checker(revocation_checker);
+
+        assert_matches!(
+            test_provider.validate(&test_signing_identity, None),
+            Err(X509IdentityError::RevocationCheckError(_))
+        )
+    }
 }
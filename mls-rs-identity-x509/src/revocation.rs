@@ -0,0 +1,570 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use core::{
+    convert::Infallible,
+    fmt::{self, Debug},
+};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use mls_rs_core::{error::IntoAnyError, time::MlsTime};
+
+use crate::{CertificateChain, DerCertificate, X509IdentityError};
+
+#[cfg(all(test, feature = "std"))]
+use mockall::automock;
+
+#[derive(Clone, PartialEq, Eq)]
+/// X.509 certificate revocation list in DER format.
+pub struct DerCrl(Vec<u8>);
+
+impl Debug for DerCrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        mls_rs_core::debug::pretty_bytes(&self.0)
+            .named("DerCrl")
+            .fmt(f)
+    }
+}
+
+impl DerCrl {
+    /// Create a DER certificate revocation list from raw bytes.
+    pub fn new(data: Vec<u8>) -> DerCrl {
+        DerCrl(data)
+    }
+
+    /// Convert this certificate revocation list into raw bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for DerCrl {
+    fn from(data: Vec<u8>) -> Self {
+        DerCrl(data)
+    }
+}
+
+impl AsRef<[u8]> for DerCrl {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+/// OCSP response in DER format.
+pub struct DerOcspResponse(Vec<u8>);
+
+impl Debug for DerOcspResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        mls_rs_core::debug::pretty_bytes(&self.0)
+            .named("DerOcspResponse")
+            .fmt(f)
+    }
+}
+
+impl DerOcspResponse {
+    /// Create a DER OCSP response from raw bytes.
+    pub fn new(data: Vec<u8>) -> DerOcspResponse {
+        DerOcspResponse(data)
+    }
+
+    /// Convert this OCSP response into raw bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for DerOcspResponse {
+    fn from(data: Vec<u8>) -> Self {
+        DerOcspResponse(data)
+    }
+}
+
+impl AsRef<[u8]> for DerOcspResponse {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Revocation status of a certificate according to a CRL or OCSP response.
+pub enum RevocationStatus {
+    Good,
+    Revoked,
+    /// The source does not cover the certificate, or is not valid at the
+    /// time of the check.
+    Unknown,
+}
+
+#[cfg_attr(all(test, feature = "std"), automock(type Error = crate::test_utils::TestError;))]
+/// Trait for parsing and verifying revocation information.
+pub trait X509RevocationReader {
+    type Error: IntoAnyError;
+
+    /// URLs of the CRL distribution points extension of a certificate.
+    fn crl_distribution_points(
+        &self,
+        certificate: &DerCertificate,
+    ) -> Result<Vec<String>, Self::Error>;
+
+    /// URLs of the OCSP responders in the authority information access
+    /// extension of a certificate.
+    fn ocsp_responders(&self, certificate: &DerCertificate) -> Result<Vec<String>, Self::Error>;
+
+    /// DER encoded OCSP request for the status of `certificate`.
+    fn ocsp_request(
+        &self,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Status of `certificate` according to `crl`, after verifying that `crl`
+    /// is signed by `issuer`.
+    ///
+    /// If `timestamp` is set to `None` then freshness checks should be
+    /// skipped.
+    fn crl_status(
+        &self,
+        crl: &DerCrl,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+        timestamp: Option<MlsTime>,
+    ) -> Result<RevocationStatus, Self::Error>;
+
+    /// Status of `certificate` according to `response`, after verifying that
+    /// `response` is signed by `issuer` or a responder it delegated to.
+    ///
+    /// If `timestamp` is set to `None` then freshness checks should be
+    /// skipped.
+    fn ocsp_status(
+        &self,
+        response: &DerOcspResponse,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+        timestamp: Option<MlsTime>,
+    ) -> Result<RevocationStatus, Self::Error>;
+}
+
+#[cfg_attr(all(test, feature = "std"), automock(type Error = crate::test_utils::TestError;))]
+/// Trait for retrieving revocation information over the network.
+pub trait X509RevocationFetcher {
+    type Error: IntoAnyError;
+
+    /// Download the CRL published at `url`, or `None` if it is not available.
+    fn fetch_crl(&self, url: &str) -> Result<Option<DerCrl>, Self::Error>;
+
+    /// Send the DER encoded OCSP `request` to the responder at `url` and
+    /// return its response, or `None` if it is not available.
+    fn fetch_ocsp_response(
+        &self,
+        url: &str,
+        request: &[u8],
+    ) -> Result<Option<DerOcspResponse>, Self::Error>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Fetcher that never retrieves anything, for offline use where all
+/// revocation information is provided to the [`RevocationChecker`] upfront.
+pub struct NoFetch;
+
+impl X509RevocationFetcher for NoFetch {
+    type Error = Infallible;
+
+    fn fetch_crl(&self, _url: &str) -> Result<Option<DerCrl>, Self::Error> {
+        Ok(None)
+    }
+
+    fn fetch_ocsp_response(
+        &self,
+        _url: &str,
+        _request: &[u8],
+    ) -> Result<Option<DerOcspResponse>, Self::Error> {
+        Ok(None)
+    }
+}
+
+#[cfg_attr(all(test, feature = "std"), automock(type Error = crate::test_utils::TestError;))]
+/// X.509 certificate revocation checking trait.
+pub trait X509RevocationChecker {
+    type Error: IntoAnyError;
+
+    /// Check that no certificate of a chain, already validated by a
+    /// [`X509CredentialValidator`](crate::X509CredentialValidator), is
+    /// revoked.
+    fn check_chain(
+        &self,
+        chain: &CertificateChain,
+        timestamp: Option<MlsTime>,
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Revocation checker accepting every chain.
+pub struct NoRevocationCheck;
+
+impl X509RevocationChecker for NoRevocationCheck {
+    type Error = Infallible;
+
+    fn check_chain(
+        &self,
+        _chain: &CertificateChain,
+        _timestamp: Option<MlsTime>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Behavior of a [`RevocationChecker`] when the status of a certificate
+/// can't be determined.
+pub enum RevocationPolicy {
+    /// Accept the certificate.
+    SoftFail,
+    /// Reject the certificate.
+    #[default]
+    HardFail,
+}
+
+#[derive(Clone, Debug)]
+/// Revocation checker using CRLs and OCSP responses.
+///
+/// The status of every certificate of a chain except the trust anchor is
+/// looked up in order in
+///
+/// 1. the OCSP responses stapled with
+///    [`RevocationChecker::staple_ocsp_response`],
+/// 2. the CRLs added with [`RevocationChecker::add_crl`],
+/// 3. the OCSP responders of the certificate, queried with the fetcher,
+/// 4. the CRL distribution points of the certificate, downloaded with the
+///    fetcher.
+///
+/// The first source knowing the status of the certificate decides. Using
+/// [`NoFetch`] together with stapled responses and CRLs allows checking
+/// revocation offline.
+pub struct RevocationChecker<R, F = NoFetch> {
+    reader: R,
+    fetcher: F,
+    policy: RevocationPolicy,
+    crls: Vec<DerCrl>,
+    stapled: Vec<(DerCertificate, DerOcspResponse)>,
+}
+
+impl<R> RevocationChecker<R>
+where
+    R: X509RevocationReader,
+{
+    /// Create a new revocation checker using only the revocation information
+    /// provided to it.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            fetcher: NoFetch,
+            policy: RevocationPolicy::default(),
+            crls: Vec::new(),
+            stapled: Vec::new(),
+        }
+    }
+}
+
+impl<R, F> RevocationChecker<R, F>
+where
+    R: X509RevocationReader,
+    F: X509RevocationFetcher,
+{
+    /// Retrieve revocation information missing from this checker with
+    /// `fetcher`.
+    pub fn with_fetcher<F2>(self, fetcher: F2) -> RevocationChecker<R, F2>
+    where
+        F2: X509RevocationFetcher,
+    {
+        RevocationChecker {
+            reader: self.reader,
+            fetcher,
+            policy: self.policy,
+            crls: self.crls,
+            stapled: self.stapled,
+        }
+    }
+
+    /// Set the behavior when the status of a certificate can't be
+    /// determined.
+    pub fn with_policy(self, policy: RevocationPolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Add a CRL, for example downloaded in advance from a distribution
+    /// point.
+    pub fn add_crl(&mut self, crl: DerCrl) {
+        self.crls.push(crl);
+    }
+
+    /// Add an OCSP response for `certificate`, for example stapled to the
+    /// credential by its owner. A response replaces the previous response
+    /// for the same certificate.
+    pub fn staple_ocsp_response(&mut self, certificate: DerCertificate, response: DerOcspResponse) {
+        self.stapled.retain(|(c, _)| c != &certificate);
+        self.stapled.push((certificate, response));
+    }
+
+    fn status(
+        &self,
+        certificate: &DerCertificate,
+        issuer: &DerCertificate,
+        timestamp: Option<MlsTime>,
+    ) -> Result<RevocationStatus, X509IdentityError> {
+        let reader_error =
+            |e: R::Error| X509IdentityError::RevocationReaderError(e.into_any_error());
+
+        let stapled = self
+            .stapled
+            .iter()
+            .filter(|(c, _)| c == certificate)
+            .map(|(_, response)| {
+                self.reader
+                    .ocsp_status(response, certificate, issuer, timestamp)
+            });
+
+        let stored = self
+            .crls
+            .iter()
+            .map(|crl| self.reader.crl_status(crl, certificate, issuer, timestamp));
+
+        for status in stapled.chain(stored) {
+            match status.map_err(reader_error)? {
+                RevocationStatus::Unknown => continue,
+                status => return Ok(status),
+            }
+        }
+
+        for url in self
+            .reader
+            .ocsp_responders(certificate)
+            .map_err(reader_error)?
+        {
+            let request = self
+                .reader
+                .ocsp_request(certificate, issuer)
+                .map_err(reader_error)?;
+
+            let Some(response) = self.fetch(self.fetcher.fetch_ocsp_response(&url, &request))?
+            else {
+                continue;
+            };
+
+            match self
+                .reader
+                .ocsp_status(&response, certificate, issuer, timestamp)
+                .map_err(reader_error)?
+            {
+                RevocationStatus::Unknown => continue,
+                status => return Ok(status),
+            }
+        }
+
+        for url in self
+            .reader
+            .crl_distribution_points(certificate)
+            .map_err(reader_error)?
+        {
+            let Some(crl) = self.fetch(self.fetcher.fetch_crl(&url))? else {
+                continue;
+            };
+
+            match self
+                .reader
+                .crl_status(&crl, certificate, issuer, timestamp)
+                .map_err(reader_error)?
+            {
+                RevocationStatus::Unknown => continue,
+                status => return Ok(status),
+            }
+        }
+
+        Ok(RevocationStatus::Unknown)
+    }
+
+    // Unreachable sources are treated like sources not knowing the status
+    // when failing softly.
+    fn fetch<T>(&self, res: Result<Option<T>, F::Error>) -> Result<Option<T>, X509IdentityError> {
+        match res {
+            Ok(fetched) => Ok(fetched),
+            Err(_) if self.policy == RevocationPolicy::SoftFail => Ok(None),
+            Err(e) => Err(X509IdentityError::RevocationFetcherError(
+                e.into_any_error(),
+            )),
+        }
+    }
+}
+
+impl<R, F> X509RevocationChecker for RevocationChecker<R, F>
+where
+    R: X509RevocationReader,
+    F: X509RevocationFetcher,
+{
+    type Error = X509IdentityError;
+
+    fn check_chain(
+        &self,
+        chain: &CertificateChain,
+        timestamp: Option<MlsTime>,
+    ) -> Result<(), Self::Error> {
+        for (index, pair) in chain.windows(2).enumerate() {
+            match self.status(&pair[0], &pair[1], timestamp)? {
+                RevocationStatus::Good => (),
+                RevocationStatus::Revoked => {
+                    return Err(X509IdentityError::CertificateRevoked(index))
+                }
+                RevocationStatus::Unknown if self.policy == RevocationPolicy::SoftFail => (),
+                RevocationStatus::Unknown => {
+                    return Err(X509IdentityError::RevocationStatusUnknown(index))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::{string::ToString, vec};
+    use assert_matches::assert_matches;
+    use mockall::predicate::eq;
+
+    use crate::{
+        test_utils::{test_certificate_chain, TestError},
+        DerCrl, DerOcspResponse, MockX509RevocationFetcher, MockX509RevocationReader,
+        RevocationChecker, RevocationPolicy, RevocationStatus, X509IdentityError,
+        X509RevocationChecker,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn reader_without_sources() -> MockX509RevocationReader {
+        let mut reader = MockX509RevocationReader::new();
+
+        reader.expect_ocsp_responders().returning(|_| Ok(vec![]));
+
+        reader
+            .expect_crl_distribution_points()
+            .returning(|_| Ok(vec![]));
+
+        reader
+    }
+
+    #[test]
+    fn stapled_response_takes_precedence_over_crls() {
+        let chain = test_certificate_chain();
+        let leaf = chain[0].clone();
+
+        let mut reader = MockX509RevocationReader::new();
+
+        reader
+            .expect_ocsp_status()
+            .with(
+                eq(DerOcspResponse::new(vec![1])),
+                eq(leaf.clone()),
+                eq(chain[1].clone()),
+                eq(None),
+            )
+            .once()
+            .return_once(|_, _, _, _| Ok(RevocationStatus::Good));
+
+        reader
+            .expect_crl_status()
+            .returning(|_, _, _, _| Ok(RevocationStatus::Revoked));
+
+        let mut checker = RevocationChecker::new(reader);
+        checker.staple_ocsp_response(leaf, DerOcspResponse::new(vec![1]));
+        checker.add_crl(DerCrl::new(vec![2]));
+
+        assert_matches!(
+            checker.check_chain(&chain, None),
+            Err(X509IdentityError::CertificateRevoked(1))
+        );
+    }
+
+    #[test]
+    fn unknown_status_depends_on_policy() {
+        let chain = test_certificate_chain();
+
+        let checker = RevocationChecker::new(reader_without_sources());
+
+        assert_matches!(
+            checker.check_chain(&chain, None),
+            Err(X509IdentityError::RevocationStatusUnknown(0))
+        );
+
+        let checker = checker.with_policy(RevocationPolicy::SoftFail);
+
+        assert_matches!(checker.check_chain(&chain, None), Ok(()));
+    }
+
+    #[test]
+    fn distribution_points_are_fetched() {
+        let chain = test_certificate_chain();
+
+        let mut reader = MockX509RevocationReader::new();
+
+        reader.expect_ocsp_responders().returning(|_| Ok(vec![]));
+
+        reader
+            .expect_crl_distribution_points()
+            .returning(|_| Ok(vec!["http://crl.example.com".to_string()]));
+
+        reader
+            .expect_crl_status()
+            .returning(|crl, _, _, _| match crl.as_ref() {
+                [0] => Ok(RevocationStatus::Good),
+                _ => Ok(RevocationStatus::Revoked),
+            });
+
+        let mut fetcher = MockX509RevocationFetcher::new();
+
+        fetcher.expect_fetch_crl().times(2).returning(|url| {
+            assert_eq!(url, "http://crl.example.com");
+            Ok(Some(DerCrl::new(vec![0])))
+        });
+
+        let checker = RevocationChecker::new(reader).with_fetcher(fetcher);
+
+        assert_matches!(checker.check_chain(&chain, None), Ok(()));
+    }
+
+    #[test]
+    fn fetcher_errors_depend_on_policy() {
+        let chain = test_certificate_chain();
+
+        let mut reader = MockX509RevocationReader::new();
+
+        reader
+            .expect_ocsp_responders()
+            .returning(|_| Ok(vec!["http://ocsp.example.com".to_string()]));
+
+        reader.expect_ocsp_request().returning(|_, _| Ok(vec![]));
+
+        reader
+            .expect_crl_distribution_points()
+            .returning(|_| Ok(vec![]));
+
+        let mut fetcher = MockX509RevocationFetcher::new();
+
+        fetcher
+            .expect_fetch_ocsp_response()
+            .returning(|_, _| Err(TestError));
+
+        let checker = RevocationChecker::new(reader).with_fetcher(fetcher);
+
+        assert_matches!(
+            checker.check_chain(&chain, None),
+            Err(X509IdentityError::RevocationFetcherError(_))
+        );
+
+        let checker = checker.with_policy(RevocationPolicy::SoftFail);
+
+        assert_matches!(checker.check_chain(&chain, None), Ok(()));
+    }
+}
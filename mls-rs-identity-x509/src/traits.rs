@@ -86,4 +86,17 @@ pub trait X509CertificateReader {
 
     /// Get the subject public key of a certificate.
     fn public_key(&self, certificate: &DerCertificate) -> Result<SignaturePublicKey, Self::Error>;
+
+    /// Value of the first attribute of a certificate subject with the type
+    /// identified by `oid` in dotted decimal notation, for example
+    /// `"2.5.4.3"`.
+    ///
+    /// Readers that can't parse arbitrary attributes return `None`.
+    fn subject_attribute(
+        &self,
+        _certificate: &DerCertificate,
+        _oid: &str,
+    ) -> Result<Option<String>, Self::Error> {
+        Ok(None)
+    }
 }
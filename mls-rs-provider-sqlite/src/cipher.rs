@@ -58,6 +58,30 @@ impl SqlCipherConfig {
             ..self
         }
     }
+
+    fn validate(&self) -> Result<(), SqLiteDataStorageError> {
+        if self.plaintext_header_size > 0 && !matches!(self.key, SqlCipherKey::RawKeyWithSalt(_)) {
+            return Err(SqLiteDataStorageError::SqlCipherKeyInvalidWithHeader);
+        }
+
+        Ok(())
+    }
+}
+
+/// Progress of a key rotation performed by
+/// [`CipheredConnectionStrategy::rotate_kek`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeyRotationProgress {
+    /// The database was unlocked with the old key.
+    Unlocked {
+        /// Number of pages that will be re-encrypted.
+        page_count: u64,
+    },
+    /// All pages were re-encrypted with the new key.
+    Rekeyed,
+    /// The database was successfully unlocked with the new key.
+    Verified,
 }
 
 /// Encrypted database connection with SQLCipher.
@@ -80,18 +104,66 @@ where
             cipher_config,
         }
     }
-}
 
-impl<I> ConnectionStrategy for CipheredConnectionStrategy<I>
-where
-    I: ConnectionStrategy,
-{
-    fn make_connection(&self) -> Result<Connection, SqLiteDataStorageError> {
-        if self.cipher_config.plaintext_header_size > 0
-            && !matches!(self.cipher_config.key, SqlCipherKey::RawKeyWithSalt(_))
-        {
-            return Err(SqLiteDataStorageError::SqlCipherKeyInvalidWithHeader);
-        }
+    /// Re-encrypt the database from the `old` key to the `new` key and use
+    /// the `new` key for all further connections.
+    ///
+    /// The pages of the database are re-encrypted in place by SQLCipher, so
+    /// no plaintext copy of the stored group secrets is written to disk.
+    /// `progress` is called after each step of the rotation.
+    ///
+    /// Connections created before the rotation, including the storages
+    /// returned by [`SqLiteDataStorageEngine`](crate::SqLiteDataStorageEngine),
+    /// keep using the old key and must be recreated afterwards.
+    pub fn rotate_kek<F>(
+        &mut self,
+        old: SqlCipherKey,
+        new: SqlCipherKey,
+        mut progress: F,
+    ) -> Result<(), SqLiteDataStorageError>
+    where
+        F: FnMut(KeyRotationProgress),
+    {
+        let old_config = SqlCipherConfig {
+            key: old,
+            ..self.cipher_config.clone()
+        };
+
+        let new_config = SqlCipherConfig {
+            key: new,
+            ..self.cipher_config.clone()
+        };
+
+        new_config.validate()?;
+
+        let connection = self.keyed_connection(&old_config)?;
+
+        let page_count = connection
+            .pragma_query_value(None, "page_count", |row| row.get::<_, u64>(0))
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        progress(KeyRotationProgress::Unlocked { page_count });
+
+        connection
+            .pragma_update(None, "rekey", new_config.key.to_key_pragma_value().as_str())
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        progress(KeyRotationProgress::Rekeyed);
+
+        drop(connection);
+        self.keyed_connection(&new_config)?;
+        self.cipher_config = new_config;
+
+        progress(KeyRotationProgress::Verified);
+
+        Ok(())
+    }
+
+    fn keyed_connection(
+        &self,
+        cipher_config: &SqlCipherConfig,
+    ) -> Result<Connection, SqLiteDataStorageError> {
+        cipher_config.validate()?;
 
         let connection = self.inner.make_connection()?;
 
@@ -99,7 +171,7 @@ where
             .pragma_update(
                 None,
                 "key",
-                self.cipher_config.key.to_key_pragma_value().as_str(),
+                cipher_config.key.to_key_pragma_value().as_str(),
             )
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
 
@@ -107,7 +179,7 @@ where
             .pragma_update(
                 None,
                 "cipher_plaintext_header_size",
-                self.cipher_config.plaintext_header_size,
+                cipher_config.plaintext_header_size,
             )
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
 
@@ -120,6 +192,15 @@ where
     }
 }
 
+impl<I> ConnectionStrategy for CipheredConnectionStrategy<I>
+where
+    I: ConnectionStrategy,
+{
+    fn make_connection(&self) -> Result<Connection, SqLiteDataStorageError> {
+        self.keyed_connection(&self.cipher_config)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -130,7 +211,7 @@ mod tests {
     use crate::test_utils::gen_rand_bytes;
     use crate::{connection_strategy::FileConnectionStrategy, SqLiteDataStorageError};
 
-    use super::{CipheredConnectionStrategy, SqlCipherKey};
+    use super::{CipheredConnectionStrategy, KeyRotationProgress, SqlCipherKey};
 
     fn sql_cipher_test(config: SqlCipherConfig) {
         let temp_file = NamedTempFile::new().unwrap();
@@ -207,6 +288,58 @@ mod tests {
         sql_cipher_test(config);
     }
 
+    #[test]
+    fn sql_cipher_key_rotation() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let old_key = SqlCipherKey::RawKey(gen_rand_bytes(32).try_into().unwrap());
+        let new_key = SqlCipherKey::RawKey(gen_rand_bytes(32).try_into().unwrap());
+
+        let mut sqlcipher_strategy = CipheredConnectionStrategy::new(
+            FileConnectionStrategy::new(temp_file.path()),
+            SqlCipherConfig::new(old_key.clone()),
+        );
+
+        let connection = sqlcipher_strategy.make_connection().unwrap();
+        connection.execute("CREATE TABLE test(item)", []).unwrap();
+        connection
+            .execute("INSERT INTO test VALUES (42)", [])
+            .unwrap();
+        drop(connection);
+
+        let mut steps = Vec::new();
+
+        sqlcipher_strategy
+            .rotate_kek(old_key.clone(), new_key, |step| steps.push(step))
+            .unwrap();
+
+        assert_matches!(
+            steps.as_slice(),
+            [
+                KeyRotationProgress::Unlocked { page_count },
+                KeyRotationProgress::Rekeyed,
+                KeyRotationProgress::Verified
+            ] if *page_count > 0
+        );
+
+        let item: u32 = sqlcipher_strategy
+            .make_connection()
+            .unwrap()
+            .query_row("SELECT item FROM test", [], |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(item, 42);
+
+        let old_strategy = CipheredConnectionStrategy::new(
+            FileConnectionStrategy::new(temp_file.path()),
+            SqlCipherConfig::new(old_key),
+        );
+
+        assert_matches!(
+            old_strategy.make_connection(),
+            Err(SqLiteDataStorageError::SqlEngineError(_))
+        );
+    }
+
     #[test]
     fn sql_cipher_invalid_key_plaintext_header() {
         let config = SqlCipherConfig::new(SqlCipherKey::Passphrase("correct".to_string()))
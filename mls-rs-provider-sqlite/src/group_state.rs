@@ -2,7 +2,7 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use mls_rs_core::group::{EpochRecord, GroupState, GroupStateStorage};
+use mls_rs_core::group::{EpochRecord, EpochRetention, GroupState, GroupStateStorage};
 use rusqlite::{params, Connection, OptionalExtension};
 use std::{
     fmt::Debug,
@@ -72,6 +72,56 @@ impl SqLiteGroupStateStorage {
         self.max_epoch_retention
     }
 
+    fn retention(&self) -> EpochRetention {
+        EpochRetention::new(self.max_epoch_retention)
+    }
+
+    /// Delete the epochs of `group_id` exceeding the maximum epoch retention.
+    ///
+    /// This is only needed after lowering the retention of existing storage,
+    /// since old epochs are otherwise deleted as new ones are written.
+    pub fn compact_group(&self, group_id: &[u8]) -> Result<(), SqLiteDataStorageError> {
+        let Some(delete_up_to) = self
+            .max_epoch_id(group_id)?
+            .and_then(|max_epoch_id| self.retention().delete_up_to(max_epoch_id))
+        else {
+            return Ok(());
+        };
+
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .execute(
+                "DELETE FROM epoch WHERE group_id = ? AND epoch_id <= ?",
+                params![group_id, delete_up_to],
+            )
+            .map(|_| ())
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
+    /// Delete all epochs of `group_id`, keeping its current state.
+    pub fn delete_group_epochs(&self, group_id: &[u8]) -> Result<(), SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .execute("DELETE FROM epoch WHERE group_id = ?", params![group_id])
+            .map(|_| ())
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
+    /// Delete epochs that don't belong to a stored group and rebuild the
+    /// database file to reclaim the space of deleted records.
+    pub fn vacuum_storage(&self) -> Result<(), SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .execute_batch(
+                "DELETE FROM epoch WHERE group_id NOT IN (SELECT group_id FROM mls_group);
+                VACUUM;",
+            )
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
     fn get_snapshot_data(
         &self,
         group_id: &[u8],
@@ -163,9 +213,7 @@ impl SqLiteGroupStateStorage {
 
         // Delete old epochs as needed
         if let Some(max_epoch_id) = max_epoch_id {
-            if max_epoch_id >= self.max_epoch_retention {
-                let delete_under = max_epoch_id - self.max_epoch_retention;
-
+            if let Some(delete_under) = self.retention().delete_up_to(max_epoch_id) {
                 transaction
                     .execute(
                         "DELETE FROM epoch WHERE group_id = ? AND epoch_id <= ?",
@@ -183,7 +231,11 @@ impl SqLiteGroupStateStorage {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 impl GroupStateStorage for SqLiteGroupStateStorage {
     type Error = SqLiteDataStorageError;
 
@@ -210,6 +262,22 @@ impl GroupStateStorage for SqLiteGroupStateStorage {
     async fn epoch(&self, group_id: &[u8], epoch_id: u64) -> Result<Option<Vec<u8>>, Self::Error> {
         self.get_epoch_data(group_id, epoch_id)
     }
+
+    async fn group_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        self.group_ids()
+    }
+
+    async fn compact(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        self.compact_group(group_id)
+    }
+
+    async fn delete_epochs(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        self.delete_group_epochs(group_id)
+    }
+
+    async fn vacuum(&mut self) -> Result<(), Self::Error> {
+        self.vacuum_storage()
+    }
 }
 
 #[cfg(test)]
@@ -365,6 +433,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compaction_applies_lowered_retention() {
+        let test_data = setup_group_storage_test();
+        let storage = test_data.storage.with_max_epoch_retention(10);
+
+        let test_epochs = (1..5).map(test_epoch).collect::<Vec<_>>();
+
+        storage
+            .update_group_state(&test_data.group_id, test_snapshot(), test_epochs, vec![])
+            .unwrap();
+
+        let storage = storage.with_max_epoch_retention(2);
+        storage.compact_group(&test_data.group_id).unwrap();
+        storage.vacuum_storage().unwrap();
+
+        for epoch_id in 0..5 {
+            let stored = storage
+                .get_epoch_data(&test_data.group_id, epoch_id)
+                .unwrap();
+
+            assert_eq!(stored.is_some(), epoch_id > 2);
+        }
+    }
+
+    #[test]
+    fn group_epochs_can_be_deleted() {
+        let test_data = setup_group_storage_test();
+
+        test_data
+            .storage
+            .delete_group_epochs(&test_data.group_id)
+            .unwrap();
+
+        let max_epoch_id = test_data.storage.max_epoch_id(&test_data.group_id).unwrap();
+        assert_eq!(max_epoch_id, None);
+
+        let snapshot = test_data
+            .storage
+            .get_snapshot_data(&test_data.group_id)
+            .unwrap();
+
+        assert!(snapshot.is_some());
+    }
+
     #[test]
     fn epoch_insert_update_old_epoch() {
         let test_data = setup_group_storage_test();
@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use mls_rs_core::{
-    key_package::{KeyPackageData, KeyPackageStorage},
+    crypto::CipherSuite,
+    key_package::{KeyPackageData, KeyPackageMetadata, KeyPackageStorage},
     mls_rs_codec::{MlsDecode, MlsEncode},
     time::MlsTime,
 };
@@ -34,10 +35,15 @@ impl SqLiteKeyPackageStorage {
 
         connection
             .execute(
-                "INSERT INTO key_package (id, expiration, data) VALUES (?,?,?)",
+                "INSERT INTO key_package \
+                (id, expiration, created_at, cipher_suite, last_resort, data) \
+                VALUES (?,?,?,?,?,?)",
                 params![
                     id,
-                    key_package.expiration,
+                    key_package.metadata.expires_at,
+                    key_package.metadata.created_at,
+                    *key_package.metadata.cipher_suite,
+                    key_package.metadata.last_resort,
                     key_package
                         .mls_encode_to_vec()
                         .map_err(|e| SqLiteDataStorageError::DataConversionError(e.into()))?
@@ -54,15 +60,15 @@ impl SqLiteKeyPackageStorage {
             .query_row(
                 "SELECT data FROM key_package WHERE id = ?",
                 params![id],
-                |row| {
-                    Ok(
-                        KeyPackageData::mls_decode(&mut row.get::<_, Vec<u8>>(0)?.as_slice())
-                            .unwrap(),
-                    )
-                },
+                |row| row.get::<_, Vec<u8>>(0),
             )
             .optional()
-            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?
+            .map(|data| {
+                KeyPackageData::mls_decode(&mut data.as_slice())
+                    .map_err(|e| SqLiteDataStorageError::DataConversionError(e.into()))
+            })
+            .transpose()
     }
 
     /// Delete a specific key package from storage based on it's id.
@@ -91,6 +97,63 @@ impl SqLiteKeyPackageStorage {
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
+    /// Get the ids and metadata of all key packages that expire before `time`,
+    /// given in seconds since the unix epoch.
+    ///
+    /// Key packages stored before metadata was recorded are not returned.
+    pub fn expiring_before(
+        &self,
+        time: u64,
+    ) -> Result<Vec<(Vec<u8>, KeyPackageMetadata)>, SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        let mut stmt = connection
+            .prepare(
+                "SELECT id, created_at, expiration, cipher_suite, last_resort FROM key_package \
+                WHERE expiration < ? AND cipher_suite IS NOT NULL",
+            )
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        let rows = stmt
+            .query_map(params![time], |row| {
+                let metadata = KeyPackageMetadata::new(
+                    row.get(1)?,
+                    row.get(2)?,
+                    CipherSuite::from(row.get::<_, u16>(3)?),
+                    row.get(4)?,
+                );
+
+                Ok((row.get(0)?, metadata))
+            })
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
+    /// Count stored key packages for each cipher suite.
+    ///
+    /// Key packages stored before metadata was recorded are not counted.
+    pub fn count_by_suite(&self) -> Result<Vec<(CipherSuite, usize)>, SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        let mut stmt = connection
+            .prepare(
+                "SELECT cipher_suite, count(*) FROM key_package \
+                WHERE cipher_suite IS NOT NULL GROUP BY cipher_suite ORDER BY cipher_suite",
+            )
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        let rows = stmt
+            .query_map(params![], |row| {
+                Ok((CipherSuite::from(row.get::<_, u16>(0)?), row.get(1)?))
+            })
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
     pub fn count(&self) -> Result<usize, SqLiteDataStorageError> {
         let connection = self.connection.lock().unwrap();
 
@@ -103,7 +166,11 @@ impl SqLiteKeyPackageStorage {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 impl KeyPackageStorage for SqLiteKeyPackageStorage {
     type Error = SqLiteDataStorageError;
 
@@ -118,6 +185,17 @@ impl KeyPackageStorage for SqLiteKeyPackageStorage {
     async fn delete(&mut self, id: &[u8]) -> Result<(), Self::Error> {
         (*self).delete(id)
     }
+
+    async fn expiring_before(
+        &self,
+        time: u64,
+    ) -> Result<Vec<(Vec<u8>, KeyPackageMetadata)>, Self::Error> {
+        (*self).expiring_before(time)
+    }
+
+    async fn count_by_suite(&self) -> Result<Vec<(CipherSuite, usize)>, Self::Error> {
+        (*self).count_by_suite()
+    }
 }
 
 #[cfg(test)]
@@ -128,7 +206,10 @@ mod tests {
         {connection_strategy::MemoryStrategy, test_utils::gen_rand_bytes},
     };
     use assert_matches::assert_matches;
-    use mls_rs_core::{crypto::HpkeSecretKey, key_package::KeyPackageData};
+    use mls_rs_core::{
+        crypto::{CipherSuite, HpkeSecretKey},
+        key_package::{KeyPackageData, KeyPackageMetadata},
+    };
 
     fn test_storage() -> SqLiteKeyPackageStorage {
         SqLiteDataStorageEngine::new(MemoryStrategy)
@@ -143,7 +224,7 @@ mod tests {
             gen_rand_bytes(256),
             HpkeSecretKey::from(gen_rand_bytes(256)),
             HpkeSecretKey::from(gen_rand_bytes(256)),
-            123,
+            KeyPackageMetadata::new(100, 123, CipherSuite::CURVE25519_AES128, false),
         );
 
         (key_id, key_package)
@@ -205,7 +286,7 @@ mod tests {
 
         let data = [1, 15, 30, 1698652376].map(|exp| {
             let mut kp = test_key_package();
-            kp.1.expiration = exp;
+            kp.1.metadata.expires_at = exp;
             kp
         });
 
@@ -240,4 +321,44 @@ mod tests {
 
         assert_eq!(storage.count().unwrap(), 10);
     }
+
+    #[test]
+    fn key_package_queries() {
+        let mut storage = test_storage();
+
+        let data = [
+            (10, CipherSuite::CURVE25519_AES128),
+            (20, CipherSuite::P256_AES128),
+            (30, CipherSuite::CURVE25519_AES128),
+        ]
+        .map(|(exp, cipher_suite)| {
+            let mut kp = test_key_package();
+            kp.1.metadata.expires_at = exp;
+            kp.1.metadata.cipher_suite = cipher_suite;
+            kp
+        });
+
+        for (id, data) in &data {
+            storage.insert(id, data.clone()).unwrap();
+        }
+
+        let mut expiring = storage.expiring_before(25).unwrap();
+        expiring.sort_by_key(|(_, metadata)| metadata.expires_at);
+
+        assert_eq!(
+            expiring,
+            vec![
+                (data[0].0.clone(), data[0].1.metadata),
+                (data[1].0.clone(), data[1].1.metadata)
+            ]
+        );
+
+        assert_eq!(
+            storage.count_by_suite().unwrap(),
+            vec![
+                (CipherSuite::CURVE25519_AES128, 2),
+                (CipherSuite::P256_AES128, 1)
+            ]
+        );
+    }
 }
@@ -83,10 +83,14 @@ where
             .pragma_query_value(None, "user_version", |rows| rows.get::<_, u32>(0))
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
 
-        if current_schema != 1 {
+        if current_schema < 1 {
             create_tables_v1(&connection)?;
         }
 
+        if current_schema < 2 {
+            migrate_tables_v2(&connection)?;
+        }
+
         Ok(connection)
     }
 
@@ -115,6 +119,26 @@ where
     }
 }
 
+#[cfg(any(feature = "sqlcipher", feature = "sqlcipher-bundled"))]
+impl<I> SqLiteDataStorageEngine<connection_strategy::CipheredConnectionStrategy<I>>
+where
+    I: ConnectionStrategy,
+{
+    /// Rotate the SQLCipher key of the database, see
+    /// [`CipheredConnectionStrategy::rotate_kek`](connection_strategy::CipheredConnectionStrategy::rotate_kek).
+    pub fn rotate_kek<F>(
+        &mut self,
+        old: connection_strategy::SqlCipherKey,
+        new: connection_strategy::SqlCipherKey,
+        progress: F,
+    ) -> Result<(), SqLiteDataStorageError>
+    where
+        F: FnMut(connection_strategy::KeyRotationProgress),
+    {
+        self.connection_strategy.rotate_kek(old, new, progress)
+    }
+}
+
 fn create_tables_v1(connection: &Connection) -> Result<(), SqLiteDataStorageError> {
     connection
         .execute_batch(
@@ -150,6 +174,20 @@ fn create_tables_v1(connection: &Connection) -> Result<(), SqLiteDataStorageErro
         .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
 }
 
+fn migrate_tables_v2(connection: &Connection) -> Result<(), SqLiteDataStorageError> {
+    connection
+        .execute_batch(
+            "BEGIN;
+            ALTER TABLE key_package ADD COLUMN created_at INTEGER;
+            ALTER TABLE key_package ADD COLUMN cipher_suite INTEGER;
+            ALTER TABLE key_package ADD COLUMN last_resort INTEGER NOT NULL DEFAULT 0;
+            CREATE INDEX key_package_suite ON key_package (cipher_suite);
+            PRAGMA user_version = 2;
+            COMMIT;",
+        )
+        .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{connection_strategy::MemoryStrategy, SqLiteDataStorageEngine};
@@ -168,6 +206,6 @@ mod tests {
             .pragma_query_value(None, "user_version", |rows| rows.get::<_, u32>(0))
             .unwrap();
 
-        assert_eq!(current_schema, 1);
+        assert_eq!(current_schema, 2);
     }
 }
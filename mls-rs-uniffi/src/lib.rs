@@ -776,6 +776,7 @@ impl Group {
             group::ReceivedMessage::GroupInfo(_) => Ok(ReceivedMessage::GroupInfo),
             group::ReceivedMessage::Welcome => Ok(ReceivedMessage::Welcome),
             group::ReceivedMessage::KeyPackage(_) => Ok(ReceivedMessage::KeyPackage),
+            _ => todo!("Messages from optional mls-rs features are not supported"),
         }
     }
 }
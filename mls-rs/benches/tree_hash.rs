@@ -0,0 +1,30 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use criterion::{BenchmarkId, Criterion};
+use mls_rs::{
+    test_utils::benchmarks::{load_group_states, recompute_tree_hash},
+    CipherSuite,
+};
+
+// Compare runs with and without the `parallel` feature to measure the speedup
+// of parallel tree hashing.
+fn bench(c: &mut Criterion) {
+    let cipher_suite = CipherSuite::CURVE25519_AES128;
+    let group_states = load_group_states(cipher_suite);
+    let mut bench_group = c.benchmark_group("tree_hash");
+
+    for (i, group_states) in group_states.into_iter().enumerate() {
+        bench_group.bench_with_input(
+            BenchmarkId::new(format!("{cipher_suite:?}"), i),
+            &i,
+            |b, _| b.iter(|| recompute_tree_hash(&group_states.receiver)),
+        );
+    }
+
+    bench_group.finish();
+}
+
+criterion::criterion_group!(benches, bench);
+criterion::criterion_main!(benches);
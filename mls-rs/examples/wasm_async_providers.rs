@@ -0,0 +1,319 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+// The example shows how to back a browser client with providers implemented in
+// JavaScript. Group state is persisted by an IndexedDB wrapper exposed as
+// `globalThis.mlsStorage` and member credentials are checked by a remote
+// service reachable through `globalThis.mlsIdentity`. Every call into JavaScript
+// returns a `Promise` that is awaited from Rust, which requires the async
+// build of mls-rs:
+//
+//   RUSTFLAGS='--cfg mls_build_async' cargo build --example wasm_async_providers \
+//       --target wasm32-unknown-unknown --features std
+//
+// The JavaScript side is expected to provide the following functions:
+//
+//   mlsStorage.loadState(groupId: Uint8Array): Promise<Uint8Array | undefined>
+//   mlsStorage.loadEpoch(groupId: Uint8Array, epochId: number): Promise<Uint8Array | undefined>
+//   mlsStorage.maxEpochId(groupId: Uint8Array): Promise<number | undefined>
+//   mlsStorage.write(groupId: Uint8Array, state: Uint8Array, inserts: [number, Uint8Array][],
+//       updates: [number, Uint8Array][], deleteUpTo: number | undefined): Promise<void>
+//   mlsIdentity.validate(identity: Uint8Array): Promise<boolean>
+
+#[cfg(all(target_arch = "wasm32", mls_build_async))]
+mod browser {
+    use js_sys::{Array, Promise, Uint8Array};
+    use mls_rs::{
+        client_builder::MlsConfig,
+        error::{IntoAnyError, MlsError},
+        identity::{
+            basic::{BasicCredential, BasicIdentityProvider},
+            CredentialType, SigningIdentity,
+        },
+        time::MlsTime,
+        CipherSuite, CipherSuiteProvider, Client, CryptoProvider, ExtensionList, GroupStateStorage,
+        IdentityProvider,
+    };
+    use mls_rs_core::group::{EpochRecord, EpochRetention, GroupState};
+    use mls_rs_crypto_webcrypto::WebCryptoProvider;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::JsFuture;
+
+    const CIPHERSUITE: CipherSuite = CipherSuite::P256_AES128;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = mlsStorage, js_name = loadState)]
+        fn load_state(group_id: &Uint8Array) -> Promise;
+
+        #[wasm_bindgen(js_namespace = mlsStorage, js_name = loadEpoch)]
+        fn load_epoch(group_id: &Uint8Array, epoch_id: f64) -> Promise;
+
+        #[wasm_bindgen(js_namespace = mlsStorage, js_name = maxEpochId)]
+        fn load_max_epoch_id(group_id: &Uint8Array) -> Promise;
+
+        #[wasm_bindgen(js_namespace = mlsStorage, js_name = write)]
+        fn write_group(
+            group_id: &Uint8Array,
+            state: &Uint8Array,
+            inserts: Array,
+            updates: Array,
+            delete_up_to: Option<f64>,
+        ) -> Promise;
+
+        #[wasm_bindgen(js_namespace = mlsIdentity, js_name = validate)]
+        fn validate_identity(identity: &Uint8Array) -> Promise;
+
+        #[wasm_bindgen(js_namespace = console, js_name = log)]
+        fn console_log(message: &str);
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("JavaScript provider failed: {0}")]
+    pub struct JsProviderError(String);
+
+    impl From<JsValue> for JsProviderError {
+        fn from(value: JsValue) -> Self {
+            Self(format!("{value:?}"))
+        }
+    }
+
+    impl IntoAnyError for JsProviderError {
+        fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+            Ok(Box::new(self))
+        }
+    }
+
+    // Copies `data` into the JavaScript heap so that the callee can keep it
+    // across awaits without referencing wasm memory.
+    fn js_bytes(data: &[u8]) -> Uint8Array {
+        Uint8Array::from(data)
+    }
+
+    fn optional_bytes(value: JsValue) -> Option<Vec<u8>> {
+        (!value.is_undefined() && !value.is_null()).then(|| Uint8Array::new(&value).to_vec())
+    }
+
+    fn epoch_records(records: Vec<EpochRecord>) -> Array {
+        records
+            .into_iter()
+            .map(|record| {
+                Array::of2(
+                    &JsValue::from(record.id as f64),
+                    &js_bytes(&record.data).into(),
+                )
+            })
+            .collect()
+    }
+
+    /// Group state storage delegating to an IndexedDB wrapper in JavaScript.
+    ///
+    /// Epoch ids are passed as JavaScript numbers, which is lossless as long
+    /// as a group stays below 2^53 epochs.
+    #[derive(Clone, Debug)]
+    pub struct JsGroupStateStorage {
+        retention: EpochRetention,
+    }
+
+    impl JsGroupStateStorage {
+        pub fn new(max_epochs: u64) -> Self {
+            Self {
+                retention: EpochRetention::new(max_epochs),
+            }
+        }
+    }
+
+    // `JsFuture` is not `Send`, hence the `?Send` variant used for all
+    // provider traits on wasm32.
+    #[maybe_async::must_be_async(?Send)]
+    impl GroupStateStorage for JsGroupStateStorage {
+        type Error = JsProviderError;
+
+        async fn state(&self, group_id: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+            let state = JsFuture::from(load_state(&js_bytes(group_id))).await?;
+            Ok(optional_bytes(state))
+        }
+
+        async fn epoch(
+            &self,
+            group_id: &[u8],
+            epoch_id: u64,
+        ) -> Result<Option<Vec<u8>>, Self::Error> {
+            let epoch = JsFuture::from(load_epoch(&js_bytes(group_id), epoch_id as f64)).await?;
+            Ok(optional_bytes(epoch))
+        }
+
+        async fn write(
+            &mut self,
+            state: GroupState,
+            epoch_inserts: Vec<EpochRecord>,
+            epoch_updates: Vec<EpochRecord>,
+        ) -> Result<(), Self::Error> {
+            let delete_up_to = epoch_inserts
+                .iter()
+                .map(|record| record.id)
+                .max()
+                .and_then(|max_epoch_id| self.retention.delete_up_to(max_epoch_id))
+                .map(|epoch_id| epoch_id as f64);
+
+            // A single call lets the JavaScript side apply the whole update in
+            // one IndexedDB transaction.
+            let promise = write_group(
+                &js_bytes(&state.id),
+                &js_bytes(&state.data),
+                epoch_records(epoch_inserts),
+                epoch_records(epoch_updates),
+                delete_up_to,
+            );
+
+            JsFuture::from(promise).await?;
+
+            Ok(())
+        }
+
+        async fn max_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error> {
+            let max_epoch_id = JsFuture::from(load_max_epoch_id(&js_bytes(group_id))).await?;
+            Ok(max_epoch_id.as_f64().map(|epoch_id| epoch_id as u64))
+        }
+    }
+
+    /// Identity provider for basic credentials that asks a remote service
+    /// whether a member is allowed.
+    #[derive(Clone, Debug, Default)]
+    pub struct JsIdentityProvider {
+        basic: BasicIdentityProvider,
+    }
+
+    impl JsIdentityProvider {
+        async fn validate(
+            &self,
+            signing_identity: &SigningIdentity,
+        ) -> Result<(), JsProviderError> {
+            let identity = self
+                .identity(signing_identity, &ExtensionList::default())
+                .await?;
+
+            let valid = JsFuture::from(validate_identity(&js_bytes(&identity))).await?;
+
+            valid
+                .as_bool()
+                .filter(|valid| *valid)
+                .map(|_| ())
+                .ok_or_else(|| JsProviderError("identity rejected".into()))
+        }
+    }
+
+    #[maybe_async::must_be_async(?Send)]
+    impl IdentityProvider for JsIdentityProvider {
+        type Error = JsProviderError;
+
+        async fn validate_member(
+            &self,
+            signing_identity: &SigningIdentity,
+            _timestamp: Option<MlsTime>,
+            _extensions: Option<&ExtensionList>,
+        ) -> Result<(), Self::Error> {
+            self.validate(signing_identity).await
+        }
+
+        async fn validate_external_sender(
+            &self,
+            signing_identity: &SigningIdentity,
+            _timestamp: Option<MlsTime>,
+            _extensions: Option<&ExtensionList>,
+        ) -> Result<(), Self::Error> {
+            self.validate(signing_identity).await
+        }
+
+        async fn identity(
+            &self,
+            signing_identity: &SigningIdentity,
+            extensions: &ExtensionList,
+        ) -> Result<Vec<u8>, Self::Error> {
+            self.basic
+                .identity(signing_identity, extensions)
+                .await
+                .map_err(|e| JsProviderError(e.to_string()))
+        }
+
+        async fn valid_successor(
+            &self,
+            predecessor: &SigningIdentity,
+            successor: &SigningIdentity,
+            extensions: &ExtensionList,
+        ) -> Result<bool, Self::Error> {
+            self.basic
+                .valid_successor(predecessor, successor, extensions)
+                .await
+                .map_err(|e| JsProviderError(e.to_string()))
+        }
+
+        fn supported_types(&self) -> Vec<CredentialType> {
+            self.basic.supported_types()
+        }
+    }
+
+    async fn make_client(name: &str) -> Result<Client<impl MlsConfig>, MlsError> {
+        let crypto_provider = WebCryptoProvider::new();
+        let cipher_suite = crypto_provider.cipher_suite_provider(CIPHERSUITE).unwrap();
+
+        let (secret, public) = cipher_suite.signature_key_generate().await.unwrap();
+
+        let basic_identity = BasicCredential::new(name.as_bytes().to_vec());
+        let signing_identity = SigningIdentity::new(basic_identity.into_credential(), public);
+
+        Ok(Client::builder()
+            .group_state_storage(JsGroupStateStorage::new(3))
+            .identity_provider(JsIdentityProvider::default())
+            .crypto_provider(crypto_provider)
+            .signing_identity(signing_identity, secret, CIPHERSUITE)
+            .build())
+    }
+
+    #[wasm_bindgen]
+    pub async fn run() -> Result<(), JsError> {
+        let alice = make_client("alice").await?;
+        let bob = make_client("bob").await?;
+
+        // Bob's credential is checked by the remote identity service when
+        // Alice adds him.
+        let mut alice_group = alice.create_group(ExtensionList::default()).await?;
+        let bob_key_package = bob.generate_key_package_message().await?;
+
+        alice_group
+            .commit_builder()
+            .add_member(bob_key_package)?
+            .build()
+            .await?;
+
+        alice_group.apply_pending_commit().await?;
+
+        // The state is written to IndexedDB and can be reloaded after the
+        // page is refreshed.
+        alice_group.write_to_storage().await?;
+
+        let alice_group = alice.load_group(alice_group.group_id()).await?;
+
+        console_log(&format!(
+            "Reloaded group at epoch {}",
+            alice_group.current_epoch()
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", mls_build_async))]
+fn main() {
+    wasm_bindgen_futures::spawn_local(async {
+        if let Err(e) = browser::run().await {
+            wasm_bindgen::throw_val(e.into());
+        }
+    });
+}
+
+#[cfg(not(all(target_arch = "wasm32", mls_build_async)))]
+fn main() {
+    println!("This example requires a wasm32 target and RUSTFLAGS='--cfg mls_build_async'");
+}
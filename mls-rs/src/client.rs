@@ -7,6 +7,9 @@ use crate::client_builder::{recreate_config, BaseConfig, ClientBuilder, MakeConf
 use crate::client_config::ClientConfig;
 use crate::group::framing::MlsMessage;
 
+#[cfg(feature = "tree_fetcher")]
+use crate::group::TreeFetcher;
+
 #[cfg(feature = "by_ref_proposal")]
 use crate::group::{
     framing::{Content, MlsMessagePayload, PublicMessage, Sender, WireFormat},
@@ -18,6 +21,11 @@ use crate::identity::SigningIdentity;
 use crate::key_package::{KeyPackageGeneration, KeyPackageGenerator};
 use crate::protocol_version::ProtocolVersion;
 use crate::tree_kem::node::NodeIndex;
+#[cfg(feature = "content_advertisement")]
+use crate::{
+    extension::RequiredMediaTypesExt,
+    group::content_advertisement::{accepted_media_types, common_media_types},
+};
 use alloc::vec::Vec;
 use mls_rs_codec::MlsDecode;
 use mls_rs_core::crypto::{CryptoProvider, SignatureSecretKey};
@@ -53,12 +61,21 @@ pub enum MlsError {
     SerializationError(AnyError),
     #[cfg_attr(feature = "std", error(transparent))]
     ExtensionError(AnyError),
+    #[cfg_attr(feature = "std", error(transparent))]
+    TreeFetcherError(AnyError),
+    #[cfg_attr(feature = "std", error(transparent))]
+    DeliveryServiceError(AnyError),
     #[cfg_attr(feature = "std", error("Cipher suite does not match"))]
     CipherSuiteMismatch,
     #[cfg_attr(feature = "std", error("Invalid commit, missing required path"))]
     CommitMissingPath,
     #[cfg_attr(feature = "std", error("plaintext message for incorrect epoch"))]
     InvalidEpoch,
+    #[cfg_attr(
+        feature = "std",
+        error("message from future epoch {0}, the group state must be resynced")
+    )]
+    StateDesync(u64),
     #[cfg_attr(feature = "std", error("invalid signature found"))]
     InvalidSignature,
     #[cfg_attr(feature = "std", error("invalid confirmation tag"))]
@@ -131,6 +148,91 @@ pub enum MlsError {
         )
     )]
     GroupUsedAfterReInit,
+    #[cfg_attr(
+        feature = "std",
+        error("this member was removed from the group and can no longer use it")
+    )]
+    MembershipRevoked,
+    #[cfg_attr(
+        feature = "std",
+        error("external commit is missing a required join ticket")
+    )]
+    JoinTicketRequired,
+    #[cfg_attr(feature = "std", error("join ticket is expired"))]
+    JoinTicketExpired,
+    #[cfg_attr(feature = "std", error("invalid join ticket"))]
+    InvalidJoinTicket,
+    #[cfg_attr(
+        feature = "std",
+        error("commit requires {0} co-signatures but only {1} were found")
+    )]
+    InsufficientCoSignatures(u32, u32),
+    #[cfg_attr(
+        feature = "std",
+        error("epoch {0} is not designated for archival access")
+    )]
+    EpochNotArchived(u64),
+    #[cfg_attr(
+        feature = "std",
+        error("push preview payload of {0} bytes exceeds the maximum size")
+    )]
+    PushPreviewTooLarge(usize),
+    #[cfg_attr(feature = "std", error("member {0} is not quarantined"))]
+    MemberNotQuarantined(u32),
+    #[cfg_attr(
+        feature = "std",
+        error("application messages can't be sent while members are quarantined")
+    )]
+    MembersQuarantined,
+    #[cfg_attr(
+        feature = "std",
+        error("self remove proposals are not supported by all members")
+    )]
+    SelfRemoveUnsupported,
+    #[cfg_attr(feature = "std", error("invalid padding of shaped handshake message"))]
+    InvalidHandshakePadding,
+    #[cfg_attr(
+        feature = "std",
+        error("identity renderer produced a different number of fields than it declared")
+    )]
+    InvalidRosterExportFields,
+    #[cfg_attr(feature = "std", error("unknown extension {0:?}"))]
+    UnknownExtension(ExtensionType),
+    #[cfg_attr(feature = "std", error("invalid membership proof"))]
+    InvalidMembershipProof,
+    #[cfg_attr(
+        feature = "std",
+        error("device attestation required by the group not found")
+    )]
+    DeviceAttestationRequired,
+    #[cfg_attr(
+        feature = "std",
+        error("member {0} can't extend or remove its membership expiry")
+    )]
+    MemberExpiryExtended(u32),
+    #[cfg_attr(feature = "std", error("invalid blinded credential"))]
+    InvalidBlindedCredential,
+    #[cfg_attr(feature = "std", error("member {0} is not hidden"))]
+    MemberNotHidden(u32),
+    #[cfg_attr(
+        feature = "std",
+        error("reset member can only be added back with the pre-shared key of its reset slot")
+    )]
+    MemberResetPskRequired,
+    #[cfg_attr(feature = "std", error("invalid verification code length {0}"))]
+    InvalidVerificationCodeLength(usize),
+    #[cfg_attr(
+        feature = "std",
+        error("secret key does not match the public key of the leaf node")
+    )]
+    LeafNodeKeyMismatch,
+    #[cfg_attr(feature = "std", error("invalid message envelope: {0:?}"))]
+    InvalidMessageEnvelope(EnvelopeRejection),
+    #[cfg_attr(
+        feature = "std",
+        error("key package cipher suite {0:?} does not match group cipher suite {1:?}")
+    )]
+    KeyPackageCipherSuiteMismatch(CipherSuite, CipherSuite),
     #[cfg_attr(feature = "std", error("Pending ReIinit not found."))]
     PendingReInitNotFound,
     #[cfg_attr(
@@ -138,14 +240,67 @@ pub enum MlsError {
         error("The extensions in the welcome message and in the reinit do not match.")
     )]
     ReInitExtensionsMismatch,
+    #[cfg_attr(
+        feature = "std",
+        error("key packages of a shadow group must match the other members of the group")
+    )]
+    ShadowMembershipMismatch,
+    #[cfg_attr(
+        feature = "std",
+        error("cutover epoch {0} is not after the current epoch")
+    )]
+    InvalidCutoverEpoch(u64),
+    #[cfg_attr(feature = "std", error("group is not a shadow group of this group"))]
+    NotShadowGroup,
+    #[cfg_attr(feature = "std", error("compact group state is corrupted"))]
+    InvalidCompactState,
+    #[cfg_attr(feature = "std", error("unsupported compact group state version {0}"))]
+    UnsupportedCompactStateVersion(u16),
+    #[cfg_attr(
+        feature = "std",
+        error("private key for a node of the own direct path not found in OpenMLS state")
+    )]
+    OpenMlsKeyNotFound,
+    #[cfg_attr(
+        feature = "std",
+        error("secret {0} of OpenMLS state has invalid length for the cipher suite")
+    )]
+    InvalidOpenMlsSecretLength(&'static str),
+    #[cfg_attr(
+        feature = "std",
+        error("application message is not bound to the roster of its epoch")
+    )]
+    RosterBindingMismatch,
+    #[cfg_attr(
+        feature = "std",
+        error("targeted message is encrypted to leaf index {0}")
+    )]
+    TargetedMessageRecipientMismatch(u32),
+    #[cfg_attr(
+        feature = "std",
+        error("no welcome of the current epoch can be retargeted")
+    )]
+    NoRetargetableWelcome,
+    #[cfg_attr(
+        feature = "std",
+        error("key package does not keep the encryption key of the added member")
+    )]
+    RetargetedEncryptionKeyMismatch,
     #[cfg_attr(feature = "std", error("signer not found for given identity"))]
     SignerNotFound,
+    #[cfg_attr(
+        feature = "std",
+        error("signer does not match the signature key of the signing identity")
+    )]
+    SignerIdentityMismatch,
     #[cfg_attr(feature = "std", error("commit already pending"))]
     ExistingPendingCommit,
     #[cfg_attr(feature = "std", error("pending commit not found"))]
     PendingCommitNotFound,
     #[cfg_attr(feature = "std", error("unexpected message type for action"))]
     UnexpectedMessageType,
+    #[cfg_attr(feature = "std", error("unexpected content type {0:?} for action"))]
+    UnexpectedContentType(crate::group::ContentType),
     #[cfg_attr(
         feature = "std",
         error("membership tag on MlsPlaintext for non-member sender")
@@ -155,6 +310,13 @@ pub enum MlsError {
     MemberNotFound,
     #[cfg_attr(feature = "std", error("group not found"))]
     GroupNotFound,
+    #[cfg_attr(feature = "std", error("no key package published for identity"))]
+    KeyPackageNotFound,
+    #[cfg_attr(
+        feature = "std",
+        error("key package bundle metadata does not match its key package")
+    )]
+    InvalidKeyPackageBundle,
     #[cfg_attr(feature = "std", error("unexpected PSK ID"))]
     UnexpectedPskId,
     #[cfg_attr(feature = "std", error("invalid sender for content type"))]
@@ -178,6 +340,11 @@ pub enum MlsError {
         error("requested generation {0} is too far ahead of current generation")
     )]
     InvalidFutureGeneration(u32),
+    #[cfg_attr(
+        feature = "std",
+        error("message of sender {0} skips too many generations to generation {1}")
+    )]
+    GenerationSkipTooLarge(u32, u32),
     #[cfg_attr(feature = "std", error("leaf node has no children"))]
     LeafNodeNoChildren,
     #[cfg_attr(feature = "std", error("root node has no parent"))]
@@ -196,6 +363,17 @@ pub enum MlsError {
     RequiredProposalNotFound(ProposalType),
     #[cfg_attr(feature = "std", error("required credential not found"))]
     RequiredCredentialNotFound(CredentialType),
+    #[cfg_attr(
+        feature = "std",
+        error("required capabilities not supported by members at leaf indices {0:?}")
+    )]
+    RequiredCapabilitiesNotSupported(Vec<u32>),
+    #[cfg(feature = "group_features")]
+    #[cfg_attr(feature = "std", error("required feature not supported: {0}"))]
+    RequiredFeatureNotSupported(u16),
+    #[cfg(feature = "content_advertisement")]
+    #[cfg_attr(feature = "std", error("required media type not accepted: {0:?}"))]
+    RequiredMediaTypeNotAccepted(crate::extension::MediaType),
     #[cfg_attr(feature = "std", error("capabilities must describe extensions used"))]
     ExtensionNotInCapabilities(ExtensionType),
     #[cfg_attr(feature = "std", error("expected non-blank node"))]
@@ -269,6 +447,11 @@ pub enum MlsError {
         error("ReInit proposal protocol version is less than the version of the original group")
     )]
     InvalidProtocolVersionInReInit,
+    #[cfg_attr(
+        feature = "std",
+        error("ReInit proposal downgrades the cipher suite from {0:?} to {1:?}")
+    )]
+    CipherSuiteDowngrade(CipherSuite, CipherSuite),
     #[cfg_attr(feature = "std", error("More than one proposal applying to leaf: {0}"))]
     MoreThanOneProposalForLeaf(u32),
     #[cfg_attr(
@@ -335,6 +518,41 @@ pub enum MlsError {
     InvalidGroupInfo,
     #[cfg_attr(feature = "std", error("Invalid welcome message"))]
     InvalidWelcomeMessage,
+    #[cfg(feature = "tree_compression")]
+    #[cfg_attr(
+        feature = "std",
+        error("compressed ratchet tree is invalid or too large")
+    )]
+    InvalidCompressedTree,
+    #[cfg_attr(
+        feature = "std",
+        error("welcome message for a single member exceeds the maximum size of {0} bytes")
+    )]
+    WelcomeMessageTooLarge(usize),
+}
+
+/// Reason for rejecting an application message envelope in
+/// `ExternalGroup::verify_application_message_envelope`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnvelopeRejection {
+    /// The message is not a private message.
+    NotPrivateMessage,
+    /// The protocol version of the message differs from the one of the group.
+    ProtocolVersionMismatch,
+    /// The message was sent to a different group.
+    GroupIdMismatch,
+    /// The content type of the message is not application.
+    NotApplicationData,
+    /// The message was sent in an epoch that is older than the configured
+    /// maximum epoch jitter allows.
+    EpochTooOld(u64),
+    /// The message was sent in an epoch the group has not reached yet.
+    FutureEpoch(u64),
+    /// The encrypted sender data has an invalid length.
+    InvalidSenderDataLength(usize),
+    /// The ciphertext is too short to contain an authentication tag.
+    InvalidCiphertextLength(usize),
 }
 
 impl IntoAnyError for MlsError {
@@ -375,6 +593,26 @@ pub struct Client<C> {
     pub(crate) version: ProtocolVersion,
 }
 
+/// Everything supported by the configuration of a [`Client`], as returned by
+/// [`Client::capabilities_summary`].
+///
+/// Unlike the capabilities advertised in leaf nodes, the extension and
+/// proposal types include the default types defined by RFC 9420.
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CapabilitiesSummary {
+    pub cipher_suites: Vec<CipherSuite>,
+    pub protocol_versions: Vec<ProtocolVersion>,
+    pub credential_types: Vec<CredentialType>,
+    pub extension_types: Vec<ExtensionType>,
+    pub proposal_types: Vec<ProposalType>,
+}
+
 impl Client<()> {
     /// Returns a [`ClientBuilder`]
     /// used to configure client preferences and providers.
@@ -427,11 +665,112 @@ where
     /// A key package message may only be used once.
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn generate_key_package_message(&self) -> Result<MlsMessage, MlsError> {
-        Ok(self.generate_key_package().await?.key_package_message())
+        Ok(self
+            .generate_key_package(false)
+            .await?
+            .key_package_message())
+    }
+
+    /// Creates a new key package message as by
+    /// [generate_key_package_message](Client::generate_key_package_message)
+    /// and wraps it in a [`KeyPackageBundle`](crate::KeyPackageBundle) for
+    /// upload to a key directory.
+    #[cfg(feature = "key_package_bundle")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn generate_key_package_bundle(&self) -> Result<crate::KeyPackageBundle, MlsError> {
+        let message = self.generate_key_package_message().await?;
+
+        crate::KeyPackageBundle::new(message, &self.config.crypto_provider()).await
+    }
+
+    /// Creates a new last resort key package message.
+    ///
+    /// This function behaves the same way as
+    /// [generate_key_package_message](Client::generate_key_package_message)
+    /// except that the secret keys are not erased when the key package is
+    /// used to join a group. The stored key package is marked as
+    /// [last resort](mls_rs_core::key_package::KeyPackageMetadata::last_resort)
+    /// and must be deleted by the application, e.g. after its expiration.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn generate_last_resort_key_package_message(&self) -> Result<MlsMessage, MlsError> {
+        Ok(self.generate_key_package(true).await?.key_package_message())
+    }
+
+    /// Replace `key_package`, generated by this client, with a key package
+    /// using a new init key and lifetime but the same leaf encryption key.
+    ///
+    /// The secret init key of `key_package` is erased from the
+    /// [KeyPackageStorage](crate::KeyPackageStorage). A welcome message
+    /// encrypted to `key_package` can be retargeted to the new key package
+    /// by the member who created it with
+    /// [`Group::retarget_welcome`](crate::group::Group::retarget_welcome),
+    /// and joined with [join_group_retargeted](Client::join_group_retargeted).
+    #[cfg(feature = "welcome_retargeting")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn rotate_key_package_init_key(
+        &self,
+        key_package: &MlsMessage,
+    ) -> Result<MlsMessage, MlsError> {
+        let crate::group::framing::MlsMessagePayload::KeyPackage(key_package) =
+            &key_package.payload
+        else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        let (signing_identity, cipher_suite) = self.signing_identity()?;
+
+        if key_package.cipher_suite != cipher_suite {
+            return Err(MlsError::CipherSuiteMismatch);
+        }
+
+        let cipher_suite_provider = self
+            .config
+            .crypto_provider()
+            .cipher_suite_provider(cipher_suite)
+            .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))?;
+
+        let reference = key_package.to_reference(&cipher_suite_provider).await?;
+        let mut key_package_repo = self.config.key_package_repo();
+
+        let data = key_package_repo
+            .get(&reference)
+            .await
+            .map_err(|e| MlsError::KeyPackageRepoError(e.into_any_error()))?
+            .ok_or(MlsError::KeyPackageNotFound)?;
+
+        let generation = KeyPackageGeneration::from_storage(reference.to_vec(), data)?;
+
+        let key_package_generator = KeyPackageGenerator {
+            protocol_version: self.version,
+            cipher_suite_provider: &cipher_suite_provider,
+            signing_key: self.signer()?,
+            signing_identity,
+        };
+
+        let rotated = key_package_generator
+            .rotate_init_key(&generation, self.config.lifetime())
+            .await?;
+
+        let (id, rotated_data) = rotated.to_storage()?;
+
+        key_package_repo
+            .insert(id, rotated_data)
+            .await
+            .map_err(|e| MlsError::KeyPackageRepoError(e.into_any_error()))?;
+
+        key_package_repo
+            .delete(&reference)
+            .await
+            .map_err(|e| MlsError::KeyPackageRepoError(e.into_any_error()))?;
+
+        Ok(rotated.key_package_message())
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    async fn generate_key_package(&self) -> Result<KeyPackageGeneration, MlsError> {
+    async fn generate_key_package(
+        &self,
+        last_resort: bool,
+    ) -> Result<KeyPackageGeneration, MlsError> {
         let (signing_identity, cipher_suite) = self.signing_identity()?;
 
         let cipher_suite_provider = self
@@ -447,7 +786,7 @@ where
             signing_identity,
         };
 
-        let key_pkg_gen = key_package_generator
+        let mut key_pkg_gen = key_package_generator
             .generate(
                 self.config.lifetime(),
                 self.config.capabilities(),
@@ -456,6 +795,8 @@ where
             )
             .await?;
 
+        key_pkg_gen.last_resort = last_resort;
+
         let (id, key_package_data) = key_pkg_gen.to_storage()?;
 
         self.config
@@ -522,6 +863,66 @@ where
         .await
     }
 
+    /// Media types to require in a group created with the members owning
+    /// `key_packages`.
+    ///
+    /// The result contains the media types of the
+    /// [`AcceptedMediaTypesExt`](crate::extension::AcceptedMediaTypesExt) of
+    /// this client that are also accepted by all `key_packages`, in the
+    /// order of preference of this client. It can be included in the
+    /// group context extensions passed to [create_group](Client::create_group).
+    #[cfg(feature = "content_advertisement")]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn negotiate_media_types(
+        &self,
+        key_packages: &[MlsMessage],
+    ) -> Result<RequiredMediaTypesExt, MlsError> {
+        let key_packages = key_packages
+            .iter()
+            .map(|message| match &message.payload {
+                crate::group::framing::MlsMessagePayload::KeyPackage(key_package) => {
+                    Ok(key_package)
+                }
+                _ => Err(MlsError::UnexpectedMessageType),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let preferred = accepted_media_types(&self.config.leaf_node_extensions())?;
+
+        let media_types = common_media_types(
+            preferred,
+            key_packages
+                .iter()
+                .map(|key_package| &key_package.leaf_node.extensions),
+        )?;
+
+        Ok(RequiredMediaTypesExt::new(media_types))
+    }
+
+    /// Create a MLS group that is ready to accept members at a later time.
+    ///
+    /// This function behaves the same way as [create_group](Client::create_group)
+    /// but also returns a group info message for epoch 0 that includes the
+    /// ratchet tree and an [ExternalPubExt](crate::extension::ExternalPubExt).
+    /// The group info can be published right away so that new members are
+    /// able to [join by external commit](Client::commit_external) or
+    /// [request to be added](Client::external_add_proposal) before the
+    /// creator has sent any commit.
+    ///
+    /// The group info is only valid for epoch 0. Once the group advances to
+    /// a new epoch, a fresh one can be obtained with
+    /// [group_info_message_allowing_ext_commit](crate::group::Group::group_info_message_allowing_ext_commit).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn create_group_deferred(
+        &self,
+        group_context_extensions: ExtensionList,
+    ) -> Result<(Group<C>, MlsMessage), MlsError> {
+        let group = self.create_group(group_context_extensions).await?;
+        let group_info = group.group_info_message_allowing_ext_commit(true).await?;
+
+        Ok((group, group_info))
+    }
+
     /// Join a MLS group via a welcome message created by a
     /// [Commit](crate::group::CommitOutput).
     ///
@@ -546,6 +947,54 @@ where
         .await
     }
 
+    /// Join a MLS group via a welcome message retargeted to a key package
+    /// rotated with [rotate_key_package_init_key](Client::rotate_key_package_init_key).
+    ///
+    /// This function behaves the same way as [join_group](Client::join_group)
+    /// except for the leaf of this client in the ratchet tree. RFC 9420
+    /// requires it to equal the leaf node of the key package. Here, it may
+    /// instead contain the leaf node of the original key package, with the
+    /// same signature key and encryption key.
+    #[cfg(feature = "welcome_retargeting")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn join_group_retargeted(
+        &self,
+        tree_data: Option<ExportedTree<'_>>,
+        welcome_message: &MlsMessage,
+    ) -> Result<(Group<C>, NewMemberInfo), MlsError> {
+        Group::join_retargeted(
+            welcome_message,
+            tree_data,
+            self.config.clone(),
+            self.signer()?.clone(),
+        )
+        .await
+    }
+
+    /// Join a MLS group via a welcome message whose ratchet tree is not
+    /// embedded in the message.
+    ///
+    /// If the GroupInfo of `welcome_message` does not contain the
+    /// `ratchet_tree_extension`, the tree is obtained from `tree_fetcher`
+    /// using the [`RatchetTreeLocationExt`](crate::extension::built_in::RatchetTreeLocationExt)
+    /// of the GroupInfo, if present. The fetched tree is verified against the
+    /// tree hash of the GroupInfo before it is used.
+    #[cfg(feature = "tree_fetcher")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn join_group_with_tree_fetcher<F: TreeFetcher>(
+        &self,
+        welcome_message: &MlsMessage,
+        tree_fetcher: &F,
+    ) -> Result<(Group<C>, NewMemberInfo), MlsError> {
+        Group::join_with_tree_fetcher(
+            welcome_message,
+            tree_fetcher,
+            self.config.clone(),
+            self.signer()?.clone(),
+        )
+        .await
+    }
+
     /// 0-RTT add to an existing [group](crate::group::Group)
     ///
     /// External commits allow for immediate entry into a
@@ -612,13 +1061,141 @@ where
             .state(group_id)
             .await
             .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+            .map(zeroize::Zeroizing::new)
             .ok_or(MlsError::GroupNotFound)?;
 
-        let snapshot = Snapshot::mls_decode(&mut &*snapshot)?;
+        let snapshot = Snapshot::mls_decode(&mut &**snapshot)?;
 
         Group::from_snapshot(self.config.clone(), snapshot).await
     }
 
+    /// Ids of all groups stored by the
+    /// [GroupStateStorage](crate::GroupStateStorage) that this client was
+    /// configured to use.
+    ///
+    /// Groups can then be loaded on demand with
+    /// [load_group](Client::load_group).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub async fn list_groups(&self) -> Result<Vec<Vec<u8>>, MlsError> {
+        self.config
+            .group_state_storage()
+            .group_ids()
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))
+    }
+
+    /// Load all stored groups whose id is accepted by `filter`.
+    ///
+    /// Groups are loaded in the order returned by
+    /// [list_groups](Client::list_groups).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub async fn load_all_groups<F>(&self, mut filter: F) -> Result<Vec<Group<C>>, MlsError>
+    where
+        F: FnMut(&[u8]) -> bool + Send,
+    {
+        let mut groups = Vec::new();
+
+        for group_id in self.list_groups().await? {
+            if filter(&group_id) {
+                groups.push(self.load_group(&group_id).await?);
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Load a group from a blob created with
+    /// [`Group::write_to_storage_compact`](crate::group::Group::write_to_storage_compact).
+    ///
+    /// The group is not read from the
+    /// [GroupStateStorage](crate::GroupStateStorage) that this client was
+    /// configured to use, but it is written there by the next call to
+    /// [`Group::write_to_storage`](crate::group::Group::write_to_storage).
+    #[cfg(feature = "compact_state")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn load_group_compact(&self, bytes: &[u8]) -> Result<Group<C>, MlsError> {
+        Group::from_compact_state(self.config.clone(), bytes).await
+    }
+
+    /// Import a group state exported from OpenMLS.
+    ///
+    /// The ratchet tree is validated against the group context, and the
+    /// private keys of the state must match the leaf of this member in the
+    /// tree. Groups this client can't take part in are rejected, see
+    /// [`OpenMlsGroupState`](crate::group::OpenMlsGroupState).
+    ///
+    /// The imported group is not written to the
+    /// [GroupStateStorage](crate::GroupStateStorage) that this client was
+    /// configured to use until the next call to
+    /// [`Group::write_to_storage`](crate::group::Group::write_to_storage).
+    #[cfg(feature = "openmls_import")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn import_openmls_group(
+        &self,
+        state: crate::group::OpenMlsGroupState,
+    ) -> Result<Group<C>, MlsError> {
+        Group::from_openmls_state(self.config.clone(), state).await
+    }
+
+    /// Load an existing group state as a decryption-only
+    /// [ArchivalGroup](crate::group::ArchivalGroup) that can decrypt
+    /// application messages sent in the given `epochs`.
+    ///
+    /// The group state is read from the
+    /// [GroupStateStorage](crate::GroupStateStorage) that this client was
+    /// configured to use. This client does not need a signing identity.
+    #[cfg(feature = "archival_client")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn load_archival_group(
+        &self,
+        group_id: &[u8],
+        epochs: Vec<u64>,
+    ) -> Result<crate::group::ArchivalGroup<C>, MlsError> {
+        Ok(crate::group::ArchivalGroup::new(
+            self.load_group(group_id).await?,
+            epochs,
+        ))
+    }
+
+    /// Load a read-only [HistoricalEpoch](crate::group::HistoricalEpoch)
+    /// with the context and roster of the group with id `group_id` at
+    /// epoch `epoch`.
+    ///
+    /// Past epochs are available as long as they are retained by the
+    /// [GroupStateStorage](crate::GroupStateStorage) that this client was
+    /// configured to use, otherwise [`MlsError::EpochNotFound`] is returned.
+    /// Epochs stored by a build without the `epoch_history` feature can't
+    /// be loaded. This client does not need a signing identity.
+    #[cfg(feature = "epoch_history")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn load_group_at_epoch(
+        &self,
+        group_id: &[u8],
+        epoch: u64,
+    ) -> Result<crate::group::HistoricalEpoch, MlsError> {
+        crate::group::epoch_history::load_historical_epoch(
+            &self.config.group_state_storage(),
+            group_id,
+            epoch,
+        )
+        .await
+    }
+
+    /// Create a receive-only [CompanionGroup](crate::group::CompanionGroup)
+    /// from a [CompanionBundle](crate::group::CompanionBundle) minted by the
+    /// primary device of a member.
+    ///
+    /// This client does not need a signing identity.
+    #[cfg(feature = "companion_device")]
+    pub fn companion_group(
+        &self,
+        bundle: crate::group::CompanionBundle,
+    ) -> Result<crate::group::CompanionGroup<C>, MlsError> {
+        crate::group::CompanionGroup::new(&self.config, bundle)
+    }
+
     /// Request to join an existing [group](crate::group::Group).
     ///
     /// An existing group member will need to perform a
@@ -659,7 +1236,7 @@ where
         )
         .await?;
 
-        let key_package = self.generate_key_package().await?.key_package;
+        let key_package = self.generate_key_package(false).await?.key_package;
 
         (key_package.cipher_suite == cipher_suite)
             .then_some(())
@@ -702,6 +1279,37 @@ where
             .ok_or(MlsError::SignerNotFound)
     }
 
+    /// Cipher suites supported by the crypto provider of this client.
+    pub fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        self.config.crypto_provider().supported_cipher_suites()
+    }
+
+    /// Summary of the cipher suites, protocol versions, credential types,
+    /// extension types and proposal types supported by this client.
+    pub fn capabilities_summary(&self) -> CapabilitiesSummary {
+        let capabilities = self.config.capabilities();
+
+        let extension_types = ExtensionType::DEFAULT
+            .iter()
+            .copied()
+            .chain(capabilities.extensions)
+            .collect();
+
+        let proposal_types = ProposalType::DEFAULT
+            .iter()
+            .copied()
+            .chain(capabilities.proposals)
+            .collect();
+
+        CapabilitiesSummary {
+            cipher_suites: capabilities.cipher_suites,
+            protocol_versions: capabilities.protocol_versions,
+            credential_types: capabilities.credentials,
+            extension_types,
+            proposal_types,
+        }
+    }
+
     /// Returns key package extensions used by this client
     pub fn key_package_extensions(&self) -> ExtensionList {
         self.config.key_package_extensions()
@@ -787,6 +1395,7 @@ mod tests {
 
     use crate::{
         group::{
+            framing::Content,
             message_processor::ProposalMessageDescription,
             proposal::Proposal,
             test_utils::{test_group, test_group_custom_config},
@@ -797,6 +1406,36 @@ mod tests {
 
     use alloc::vec;
 
+    #[test]
+    fn capabilities_summary_includes_default_and_custom_types() {
+        let client = TestClientBuilder::new_for_test()
+            .extension_type(ExtensionType::new(65000))
+            .custom_proposal_type(TEST_CUSTOM_PROPOSAL_TYPE)
+            .build();
+
+        let summary = client.capabilities_summary();
+
+        assert!(summary.cipher_suites.contains(&TEST_CIPHER_SUITE));
+        assert!(summary.protocol_versions.contains(&TEST_PROTOCOL_VERSION));
+        assert!(summary.credential_types.contains(&CredentialType::BASIC));
+        assert!(summary
+            .extension_types
+            .contains(&ExtensionType::RATCHET_TREE));
+        assert!(summary.extension_types.contains(&ExtensionType::new(65000)));
+        assert!(summary.proposal_types.contains(&ProposalType::ADD));
+        assert!(summary.proposal_types.contains(&TEST_CUSTOM_PROPOSAL_TYPE));
+    }
+
+    #[test]
+    fn supported_cipher_suites_come_from_crypto_provider() {
+        let client = TestClientBuilder::new_for_test().build();
+
+        assert_eq!(
+            client.supported_cipher_suites(),
+            TestCryptoProvider::new().supported_cipher_suites()
+        );
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn test_keygen() {
         // This is meant to test the inputs to the internal key package generator
@@ -1037,6 +1676,220 @@ mod tests {
         assert_matches!(res, Err(_));
     }
 
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn deferred_group_can_be_joined_by_external_commit_at_epoch_zero() {
+        let alice = test_client("alice").await;
+
+        let (mut alice_group, group_info) = alice
+            .create_group_deferred(Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(alice_group.current_epoch(), 0);
+
+        let bob = test_client("bob").await;
+        let (bob_group, external_commit) = bob.commit_external(group_info).await.unwrap();
+
+        alice_group
+            .process_incoming_message(external_commit)
+            .await
+            .unwrap();
+
+        assert_eq!(alice_group.current_epoch(), 1);
+        assert_eq!(alice_group.roster().members_iter().count(), 2);
+
+        assert_eq!(
+            alice_group.epoch_authenticator().unwrap(),
+            bob_group.epoch_authenticator().unwrap()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn deferred_group_can_add_members_later() {
+        let alice = test_client("alice").await;
+        let (mut alice_group, _) = alice
+            .create_group_deferred(Default::default())
+            .await
+            .unwrap();
+
+        // A single member group can commit with no proposals
+        alice_group.commit(vec![]).await.unwrap();
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let bob = test_client("bob").await;
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        assert_eq!(bob_group.current_epoch(), 2);
+
+        assert_eq!(
+            alice_group.epoch_authenticator().unwrap(),
+            bob_group.epoch_authenticator().unwrap()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn last_resort_key_package_is_kept_after_join() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let bob = test_client("bob").await;
+        let key_package = bob
+            .generate_last_resort_key_package_message()
+            .await
+            .unwrap();
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let (mut bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        bob_group.write_to_storage().await.unwrap();
+
+        let stored = bob.key_package_store().expiring_before(u64::MAX);
+
+        assert_eq!(stored.len(), 1);
+        assert!(stored[0].1.last_resort);
+        assert_eq!(stored[0].1.cipher_suite, TEST_CIPHER_SUITE);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn single_member_group_add_commit_has_no_path() {
+        let alice = test_client("alice").await;
+        let (mut alice_group, _) = alice
+            .create_group_deferred(Default::default())
+            .await
+            .unwrap();
+
+        let bob = test_client("bob").await;
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let Content::Commit(commit_content) = commit
+            .commit_message
+            .clone()
+            .into_plaintext()
+            .unwrap()
+            .content
+            .content
+        else {
+            panic!("expected commit")
+        };
+
+        assert!(commit_content.path.is_none());
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            alice_group.epoch_authenticator().unwrap(),
+            bob_group.epoch_authenticator().unwrap()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn deferred_group_info_is_rejected_after_epoch_change() {
+        let alice = test_client("alice").await;
+
+        let (mut alice_group, group_info) = alice
+            .create_group_deferred(Default::default())
+            .await
+            .unwrap();
+
+        alice_group.commit(vec![]).await.unwrap();
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let bob = test_client("bob").await;
+        let (_, external_commit) = bob.commit_external(group_info).await.unwrap();
+
+        let res = alice_group.process_incoming_message(external_commit).await;
+
+        assert_matches!(res, Err(MlsError::InvalidEpoch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn stored_groups_can_be_listed_and_loaded() {
+        let alice = test_client("alice").await;
+
+        let listed = alice.list_groups().await.unwrap();
+        assert!(listed.is_empty());
+
+        let mut group_ids = Vec::new();
+
+        for id in [
+            b"group a".to_vec(),
+            b"group b".to_vec(),
+            b"group c".to_vec(),
+        ] {
+            let mut group = alice
+                .create_group_with_id(id.clone(), Default::default())
+                .await
+                .unwrap();
+
+            group.write_to_storage().await.unwrap();
+            group_ids.push(id);
+        }
+
+        let mut listed = alice.list_groups().await.unwrap();
+        listed.sort();
+
+        assert_eq!(listed, group_ids);
+
+        let loaded = alice.load_all_groups(|id| id != b"group b").await.unwrap();
+
+        let mut loaded_ids = loaded
+            .iter()
+            .map(|group| group.group_id().to_vec())
+            .collect::<Vec<_>>();
+
+        loaded_ids.sort();
+
+        assert_eq!(loaded_ids, [b"group a".to_vec(), b"group c".to_vec()]);
+    }
+
     #[test]
     fn builder_can_be_obtained_from_client_to_edit_properties_for_new_client() {
         let alice = TestClientBuilder::new_for_test()
@@ -14,6 +14,7 @@ use crate::{
     group::{
         mls_rules::{DefaultMlsRules, MlsRules},
         proposal::ProposalType,
+        ComplianceMode, RemovedSecretsPolicy,
     },
     identity::CredentialType,
     identity::SigningIdentity,
@@ -29,6 +30,9 @@ use crate::{
 #[cfg(feature = "std")]
 use crate::time::MlsTime;
 
+#[cfg(feature = "group_events")]
+use crate::group::event_listener::SharedGroupEventListener;
+
 use alloc::vec::Vec;
 
 #[cfg(feature = "sqlite")]
@@ -111,6 +115,14 @@ pub type BaseSqlConfig = Config<
 ///     .build();
 /// ```
 ///
+/// # Async providers on wasm32
+///
+/// When built with `--cfg mls_build_async`, all storage and identity provider
+/// traits are async. On `wasm32` targets the futures they return are not
+/// required to be `Send`, so providers can await JavaScript promises, e.g. to
+/// persist state in IndexedDB or to validate credentials with a remote
+/// service. See the `wasm_async_providers` example.
+///
 /// # Spelling out a `Client` type
 ///
 /// There are two main ways to spell out a `Client` type if needed (e.g. function return type).
@@ -354,6 +366,107 @@ impl<C: IntoConfig> ClientBuilder<C> {
         ClientBuilder(c)
     }
 
+    /// Set how the secrets of a group are handled once this client is
+    /// removed from it.
+    ///
+    /// By default, all secrets are erased.
+    pub fn removed_secrets_policy(
+        self,
+        policy: RemovedSecretsPolicy,
+    ) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.removed_secrets_policy = policy;
+        ClientBuilder(c)
+    }
+
+    /// Set the [`ComplianceMode`] of the client.
+    ///
+    /// By default, conveniences enabled by other settings are applied.
+    pub fn compliance_mode(self, mode: ComplianceMode) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.compliance_mode = mode;
+        ClientBuilder(c)
+    }
+
+    /// Set the number of decrypted application messages remembered by each
+    /// group of the client.
+    ///
+    /// Processing a remembered message again results in
+    /// [`ReceivedMessage::AlreadyProcessed`](crate::group::ReceivedMessage::AlreadyProcessed)
+    /// instead of a decryption error. The journal is persisted together with
+    /// the group state by [`Group::write_to_storage`](crate::group::Group::write_to_storage).
+    ///
+    /// By default, the size is 0 and no messages are remembered.
+    #[cfg(feature = "decryption_journal")]
+    pub fn decryption_journal_size(self, size: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.decryption_journal_size = size;
+        ClientBuilder(c)
+    }
+
+    /// Accept received commits that add members without waiting for the
+    /// [`IdentityProvider`](crate::IdentityProvider) to validate the new
+    /// members.
+    ///
+    /// The added members are quarantined instead, see
+    /// [`Group::quarantined_members`](crate::group::Group::quarantined_members).
+    /// Their signatures and capabilities are still verified.
+    ///
+    /// By default, new members are validated while processing the commit.
+    #[cfg(feature = "member_quarantine")]
+    pub fn quarantine_new_members(self, enabled: bool) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.quarantine_new_members = enabled;
+        ClientBuilder(c)
+    }
+
+    /// Set the number of member events remembered by each group of the
+    /// client.
+    ///
+    /// Remembered events are delivered to subscriptions created with
+    /// [`Group::on_member_matching`](crate::group::Group::on_member_matching)
+    /// that ask for missed events. The log is persisted together with the
+    /// group state by [`Group::write_to_storage`](crate::group::Group::write_to_storage).
+    ///
+    /// By default, the size is 0 and no events are remembered.
+    #[cfg(feature = "member_events")]
+    pub fn member_event_log_size(self, size: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.member_event_log_size = size;
+        ClientBuilder(c)
+    }
+
+    /// Set the listener notified of the changes made by every commit applied
+    /// to a group of the client, see
+    /// [`GroupEventListener`](crate::group::GroupEventListener).
+    ///
+    /// By default, no listener is set.
+    #[cfg(feature = "group_events")]
+    pub fn group_event_listener<L>(self, listener: L) -> ClientBuilder<IntoConfigOutput<C>>
+    where
+        L: crate::group::GroupEventListener + 'static,
+    {
+        let mut c = self.0.into_config();
+        c.0.settings.group_event_listener =
+            Some(SharedGroupEventListener(std::sync::Arc::new(listener)));
+        ClientBuilder(c)
+    }
+
+    /// Register a migration of a deprecated extension type, applied to the
+    /// group context extensions proposals created by the client.
+    ///
+    /// Migrations are applied in the order in which they are registered.
+    /// See [`ExtensionMigration`](crate::extension::ExtensionMigration).
+    #[cfg(feature = "extension_migration")]
+    pub fn extension_migration(
+        self,
+        migration: crate::extension::ExtensionMigration,
+    ) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.extension_migrations.push(migration);
+        ClientBuilder(c)
+    }
+
     /// Set the key package repository to be used by the client.
     ///
     /// By default, an in-memory repository is used.
@@ -763,6 +876,39 @@ where
     fn supported_custom_proposals(&self) -> Vec<crate::group::proposal::ProposalType> {
         self.settings.custom_proposal_types.clone()
     }
+
+    fn removed_secrets_policy(&self) -> RemovedSecretsPolicy {
+        self.settings.removed_secrets_policy
+    }
+
+    fn compliance_mode(&self) -> ComplianceMode {
+        self.settings.compliance_mode
+    }
+
+    #[cfg(feature = "decryption_journal")]
+    fn decryption_journal_size(&self) -> usize {
+        self.settings.decryption_journal_size
+    }
+
+    #[cfg(feature = "member_quarantine")]
+    fn quarantine_new_members(&self) -> bool {
+        self.settings.quarantine_new_members
+    }
+
+    #[cfg(feature = "member_events")]
+    fn member_event_log_size(&self) -> usize {
+        self.settings.member_event_log_size
+    }
+
+    #[cfg(feature = "group_events")]
+    fn group_event_listener(&self) -> Option<std::sync::Arc<dyn crate::group::GroupEventListener>> {
+        self.settings.group_event_listener.clone().map(|l| l.0)
+    }
+
+    #[cfg(feature = "extension_migration")]
+    fn extension_migrations(&self) -> Vec<crate::extension::ExtensionMigration> {
+        self.settings.extension_migrations.clone()
+    }
 }
 
 impl<Kpr, Ps, Gss, Ip, Pr, Cp> Sealed for Config<Kpr, Ps, Gss, Ip, Pr, Cp> {}
@@ -852,6 +998,39 @@ impl<T: MlsConfig> ClientConfig for T {
         self.get().lifetime()
     }
 
+    fn removed_secrets_policy(&self) -> RemovedSecretsPolicy {
+        self.get().removed_secrets_policy()
+    }
+
+    fn compliance_mode(&self) -> ComplianceMode {
+        self.get().compliance_mode()
+    }
+
+    #[cfg(feature = "decryption_journal")]
+    fn decryption_journal_size(&self) -> usize {
+        self.get().decryption_journal_size()
+    }
+
+    #[cfg(feature = "member_quarantine")]
+    fn quarantine_new_members(&self) -> bool {
+        self.get().quarantine_new_members()
+    }
+
+    #[cfg(feature = "member_events")]
+    fn member_event_log_size(&self) -> usize {
+        self.get().member_event_log_size()
+    }
+
+    #[cfg(feature = "group_events")]
+    fn group_event_listener(&self) -> Option<std::sync::Arc<dyn crate::group::GroupEventListener>> {
+        self.get().group_event_listener()
+    }
+
+    #[cfg(feature = "extension_migration")]
+    fn extension_migrations(&self) -> Vec<crate::extension::ExtensionMigration> {
+        self.get().extension_migrations()
+    }
+
     fn capabilities(&self) -> Capabilities {
         self.get().capabilities()
     }
@@ -873,6 +1052,18 @@ pub(crate) struct Settings {
     pub(crate) key_package_extensions: ExtensionList,
     pub(crate) leaf_node_extensions: ExtensionList,
     pub(crate) lifetime_in_s: u64,
+    pub(crate) removed_secrets_policy: RemovedSecretsPolicy,
+    pub(crate) compliance_mode: ComplianceMode,
+    #[cfg(feature = "decryption_journal")]
+    pub(crate) decryption_journal_size: usize,
+    #[cfg(feature = "member_quarantine")]
+    pub(crate) quarantine_new_members: bool,
+    #[cfg(feature = "member_events")]
+    pub(crate) member_event_log_size: usize,
+    #[cfg(feature = "group_events")]
+    pub(crate) group_event_listener: Option<SharedGroupEventListener>,
+    #[cfg(feature = "extension_migration")]
+    pub(crate) extension_migrations: Vec<crate::extension::ExtensionMigration>,
     #[cfg(any(test, feature = "test_util"))]
     pub(crate) key_package_not_before: Option<u64>,
 }
@@ -886,6 +1077,18 @@ impl Default for Settings {
             leaf_node_extensions: Default::default(),
             lifetime_in_s: 365 * 24 * 3600,
             custom_proposal_types: Default::default(),
+            removed_secrets_policy: Default::default(),
+            compliance_mode: Default::default(),
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal_size: 0,
+            #[cfg(feature = "member_quarantine")]
+            quarantine_new_members: false,
+            #[cfg(feature = "member_events")]
+            member_event_log_size: 0,
+            #[cfg(feature = "group_events")]
+            group_event_listener: None,
+            #[cfg(feature = "extension_migration")]
+            extension_migrations: Vec::new(),
             #[cfg(any(test, feature = "test_util"))]
             key_package_not_before: None,
         }
@@ -909,6 +1112,18 @@ pub(crate) fn recreate_config<T: ClientConfig>(
                 let l = c.lifetime();
                 l.not_after - l.not_before
             },
+            removed_secrets_policy: c.removed_secrets_policy(),
+            compliance_mode: c.compliance_mode(),
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal_size: c.decryption_journal_size(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine_new_members: c.quarantine_new_members(),
+            #[cfg(feature = "member_events")]
+            member_event_log_size: c.member_event_log_size(),
+            #[cfg(feature = "group_events")]
+            group_event_listener: c.group_event_listener().map(SharedGroupEventListener),
+            #[cfg(feature = "extension_migration")]
+            extension_migrations: c.extension_migrations(),
             #[cfg(any(test, feature = "test_util"))]
             key_package_not_before: None,
         },
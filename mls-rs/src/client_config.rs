@@ -4,13 +4,16 @@
 
 use crate::{
     extension::ExtensionType,
-    group::{mls_rules::MlsRules, proposal::ProposalType},
+    group::{mls_rules::MlsRules, proposal::ProposalType, ComplianceMode, RemovedSecretsPolicy},
     identity::CredentialType,
     protocol_version::ProtocolVersion,
     tree_kem::{leaf_node::ConfigProperties, Capabilities, Lifetime},
     ExtensionList,
 };
 use alloc::vec::Vec;
+
+#[cfg(feature = "group_events")]
+use crate::group::GroupEventListener;
 use mls_rs_core::{
     crypto::CryptoProvider, group::GroupStateStorage, identity::IdentityProvider,
     key_package::KeyPackageStorage, psk::PreSharedKeyStorage,
@@ -41,6 +44,39 @@ pub trait ClientConfig: Send + Sync + Clone {
     fn leaf_node_extensions(&self) -> ExtensionList;
     fn lifetime(&self) -> Lifetime;
 
+    fn removed_secrets_policy(&self) -> RemovedSecretsPolicy {
+        RemovedSecretsPolicy::default()
+    }
+
+    fn compliance_mode(&self) -> ComplianceMode {
+        ComplianceMode::default()
+    }
+
+    #[cfg(feature = "decryption_journal")]
+    fn decryption_journal_size(&self) -> usize {
+        0
+    }
+
+    #[cfg(feature = "member_quarantine")]
+    fn quarantine_new_members(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "member_events")]
+    fn member_event_log_size(&self) -> usize {
+        0
+    }
+
+    #[cfg(feature = "group_events")]
+    fn group_event_listener(&self) -> Option<std::sync::Arc<dyn GroupEventListener>> {
+        None
+    }
+
+    #[cfg(feature = "extension_migration")]
+    fn extension_migrations(&self) -> Vec<crate::extension::ExtensionMigration> {
+        Vec::new()
+    }
+
     fn capabilities(&self) -> Capabilities {
         Capabilities {
             protocol_versions: self.supported_protocol_versions(),
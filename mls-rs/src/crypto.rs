@@ -6,11 +6,30 @@ pub(crate) use mls_rs_core::crypto::CipherSuiteProvider;
 
 pub use mls_rs_core::crypto::{
     HpkeCiphertext, HpkeContextR, HpkeContextS, HpkePublicKey, HpkeSecretKey, SignaturePublicKey,
-    SignatureSecretKey,
+    SignatureSecretKey, SignatureVerification,
 };
 
 pub use mls_rs_core::secret::Secret;
 
+pub(crate) mod secret_material;
+
+#[cfg(feature = "deterministic_crypto")]
+mod deterministic;
+
+#[cfg(feature = "deterministic_crypto")]
+pub use deterministic::{
+    DeterministicCipherSuiteProvider, DeterministicCryptoProvider, DETERMINISTIC_SEED_LEN,
+};
+
+#[cfg(feature = "validation_cache")]
+mod validation_cache;
+
+#[cfg(feature = "validation_cache")]
+pub use validation_cache::{
+    ValidationCachingCipherSuiteProvider, ValidationCachingCryptoProvider,
+    DEFAULT_VALIDATION_CACHE_CAPACITY,
+};
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use cfg_if::cfg_if;
@@ -0,0 +1,470 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+#[cfg(not(target_has_atomic = "ptr"))]
+use portable_atomic_util::Arc;
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Debug};
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+use mls_rs_core::crypto::{
+    CipherSuite, CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkePublicKey, HpkeSecretKey,
+    SignaturePublicKey, SignatureSecretKey, SignatureVerification,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Length of the seed of a [`DeterministicCryptoProvider`].
+pub const DETERMINISTIC_SEED_LEN: usize = 32;
+
+const CHACHA_CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+const CHACHA_BLOCK_LEN: usize = 64;
+
+/// ChaCha20 keystream used as a DRBG, see RFC 8439.
+struct ChaChaDrbg {
+    key: [u32; 8],
+    counter: u64,
+    block: [u8; CHACHA_BLOCK_LEN],
+    offset: usize,
+}
+
+impl ChaChaDrbg {
+    fn new(seed: [u8; DETERMINISTIC_SEED_LEN]) -> Self {
+        let mut key = [0u32; 8];
+
+        key.iter_mut()
+            .zip(seed.chunks_exact(4))
+            .for_each(|(k, c)| *k = u32::from_le_bytes([c[0], c[1], c[2], c[3]]));
+
+        Self {
+            key,
+            counter: 0,
+            block: [0u8; CHACHA_BLOCK_LEN],
+            offset: CHACHA_BLOCK_LEN,
+        }
+    }
+
+    fn fill(&mut self, out: &mut [u8]) {
+        for byte in out {
+            if self.offset == CHACHA_BLOCK_LEN {
+                self.next_block();
+            }
+
+            *byte = self.block[self.offset];
+            self.offset += 1;
+        }
+    }
+
+    fn next_block(&mut self) {
+        let mut input = [0u32; 16];
+
+        input[..4].copy_from_slice(&CHACHA_CONSTANTS);
+        input[4..12].copy_from_slice(&self.key);
+        input[12] = self.counter as u32;
+        input[13] = (self.counter >> 32) as u32;
+
+        let mut state = input;
+
+        for _ in 0..10 {
+            quarter_round(&mut state, 0, 4, 8, 12);
+            quarter_round(&mut state, 1, 5, 9, 13);
+            quarter_round(&mut state, 2, 6, 10, 14);
+            quarter_round(&mut state, 3, 7, 11, 15);
+            quarter_round(&mut state, 0, 5, 10, 15);
+            quarter_round(&mut state, 1, 6, 11, 12);
+            quarter_round(&mut state, 2, 7, 8, 13);
+            quarter_round(&mut state, 3, 4, 9, 14);
+        }
+
+        state
+            .iter()
+            .zip(input.iter())
+            .zip(self.block.chunks_exact_mut(4))
+            .for_each(|((s, i), out)| out.copy_from_slice(&s.wrapping_add(*i).to_le_bytes()));
+
+        state.zeroize();
+        input.zeroize();
+
+        self.counter = self.counter.wrapping_add(1);
+        self.offset = 0;
+    }
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+impl Drop for ChaChaDrbg {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.block.zeroize();
+    }
+}
+
+#[derive(Clone)]
+struct SharedDrbg(Arc<Mutex<ChaChaDrbg>>);
+
+impl SharedDrbg {
+    fn fill(&self, out: &mut [u8]) {
+        #[cfg(feature = "std")]
+        let mut drbg = self.0.lock().unwrap();
+
+        #[cfg(not(feature = "std"))]
+        let mut drbg = self.0.lock();
+
+        drbg.fill(out)
+    }
+}
+
+/// Crypto provider drawing all randomness from a DRBG seeded by the
+/// application.
+///
+/// Random values such as path secrets, commit secrets, nonces and
+/// reuse guards, as well as HPKE key pairs generated for leaf nodes and key
+/// packages, are derived from the seed. Two clients using the same group
+/// state and seed therefore construct byte-for-byte identical commits,
+/// which is useful for testing, differential fuzzing and audit replay.
+///
+/// HPKE encapsulation and signatures are computed by the wrapped provider.
+/// Commits encrypting path secrets to other members and signatures of
+/// non-deterministic schemes such as ECDSA are reproducible only if the
+/// wrapped provider implements these operations deterministically.
+///
+/// # Warning
+///
+/// All secrets generated by this provider can be recomputed by anyone who
+/// knows the seed. It must not be used to protect real application data.
+#[derive(Clone)]
+pub struct DeterministicCryptoProvider<P> {
+    inner: P,
+    drbg: SharedDrbg,
+}
+
+impl<P> DeterministicCryptoProvider<P> {
+    /// Wrap `inner` such that randomness is drawn from a DRBG seeded with
+    /// `seed`.
+    pub fn new(inner: P, seed: [u8; DETERMINISTIC_SEED_LEN]) -> Self {
+        Self {
+            inner,
+            drbg: SharedDrbg(Arc::new(Mutex::new(ChaChaDrbg::new(seed)))),
+        }
+    }
+}
+
+impl<P: Debug> Debug for DeterministicCryptoProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeterministicCryptoProvider")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<P> CryptoProvider for DeterministicCryptoProvider<P>
+where
+    P: CryptoProvider,
+{
+    type CipherSuiteProvider = DeterministicCipherSuiteProvider<P::CipherSuiteProvider>;
+
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        self.inner.supported_cipher_suites()
+    }
+
+    fn cipher_suite_provider(
+        &self,
+        cipher_suite: CipherSuite,
+    ) -> Option<Self::CipherSuiteProvider> {
+        self.inner.cipher_suite_provider(cipher_suite).map(|inner| {
+            DeterministicCipherSuiteProvider {
+                inner,
+                drbg: self.drbg.clone(),
+            }
+        })
+    }
+}
+
+/// Cipher suite provider of a [`DeterministicCryptoProvider`].
+///
+/// All cipher suite providers created by the same crypto provider share
+/// the same DRBG.
+#[derive(Clone)]
+pub struct DeterministicCipherSuiteProvider<P> {
+    inner: P,
+    drbg: SharedDrbg,
+}
+
+impl<P: Debug> Debug for DeterministicCipherSuiteProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeterministicCipherSuiteProvider")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<P> CipherSuiteProvider for DeterministicCipherSuiteProvider<P>
+where
+    P: CipherSuiteProvider,
+{
+    type Error = P::Error;
+    type HpkeContextS = P::HpkeContextS;
+    type HpkeContextR = P::HpkeContextR;
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.inner.cipher_suite()
+    }
+
+    async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.inner.hash(data).await
+    }
+
+    async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.inner.mac(key, data).await
+    }
+
+    async fn aead_seal(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.aead_seal(key, data, aad, nonce).await
+    }
+
+    async fn aead_open(
+        &self,
+        key: &[u8],
+        ciphertext: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.aead_open(key, ciphertext, aad, nonce).await
+    }
+
+    fn aead_key_size(&self) -> usize {
+        self.inner.aead_key_size()
+    }
+
+    fn aead_nonce_size(&self) -> usize {
+        self.inner.aead_nonce_size()
+    }
+
+    async fn kdf_extract(
+        &self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.kdf_extract(salt, ikm).await
+    }
+
+    async fn kdf_expand(
+        &self,
+        prk: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.kdf_expand(prk, info, len).await
+    }
+
+    fn kdf_extract_size(&self) -> usize {
+        self.inner.kdf_extract_size()
+    }
+
+    async fn hpke_seal(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+        pt: &[u8],
+    ) -> Result<HpkeCiphertext, Self::Error> {
+        self.inner.hpke_seal(remote_key, info, aad, pt).await
+    }
+
+    async fn hpke_open(
+        &self,
+        ciphertext: &HpkeCiphertext,
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner
+            .hpke_open(ciphertext, local_secret, local_public, info, aad)
+            .await
+    }
+
+    async fn hpke_setup_s(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+        self.inner.hpke_setup_s(remote_key, info).await
+    }
+
+    async fn hpke_setup_r(
+        &self,
+        kem_output: &[u8],
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<Self::HpkeContextR, Self::Error> {
+        self.inner
+            .hpke_setup_r(kem_output, local_secret, local_public, info)
+            .await
+    }
+
+    async fn kem_derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.inner.kem_derive(ikm).await
+    }
+
+    async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        let mut ikm = Zeroizing::new(vec![0u8; self.inner.kdf_extract_size()]);
+        self.drbg.fill(&mut ikm);
+
+        self.inner.kem_derive(&ikm).await
+    }
+
+    fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        self.inner.kem_public_key_validate(key)
+    }
+
+    fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+        self.drbg.fill(out);
+        Ok(())
+    }
+
+    async fn signature_key_generate(
+        &self,
+    ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+        self.inner.signature_key_generate().await
+    }
+
+    async fn signature_key_derive_public(
+        &self,
+        secret_key: &SignatureSecretKey,
+    ) -> Result<SignaturePublicKey, Self::Error> {
+        self.inner.signature_key_derive_public(secret_key).await
+    }
+
+    async fn sign(
+        &self,
+        secret_key: &SignatureSecretKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.sign(secret_key, data).await
+    }
+
+    async fn verify(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.inner.verify(public_key, signature, data).await
+    }
+
+    async fn verify_batch(&self, batch: &[SignatureVerification<'_>]) -> Result<(), Self::Error> {
+        self.inner.verify_batch(batch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        client_builder::ClientBuilder,
+        crypto::test_utils::TestCryptoProvider,
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        storage_provider::in_memory::InMemoryGroupStateStorage,
+        CipherSuite, ExtensionList,
+    };
+
+    use super::{ChaChaDrbg, DeterministicCryptoProvider};
+
+    // Ed25519 signatures are deterministic.
+    const CIPHER_SUITE: CipherSuite = CipherSuite::CURVE25519_AES128;
+
+    #[test]
+    fn chacha_keystream_matches_rfc_8439() {
+        // Test vector #1 of RFC 8439 appendix A.1.
+        let mut drbg = ChaChaDrbg::new([0u8; 32]);
+        let mut out = [0u8; 32];
+        drbg.fill(&mut out);
+
+        assert_eq!(
+            out,
+            [
+                0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86,
+                0xbd, 0x28, 0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc,
+                0x8b, 0x77, 0x0d, 0xc7
+            ]
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commits_are_reproducible() {
+        let (identity, secret_key) = get_test_signing_identity(CIPHER_SUITE, b"alice").await;
+        let storage = InMemoryGroupStateStorage::new();
+
+        let creator = ClientBuilder::new()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(BasicIdentityProvider::new())
+            .group_state_storage(storage.clone())
+            .signing_identity(identity.clone(), secret_key.clone(), CIPHER_SUITE)
+            .build();
+
+        let mut group = creator
+            .create_group(ExtensionList::default())
+            .await
+            .unwrap();
+
+        group.write_to_storage().await.unwrap();
+
+        let mut commits = vec![];
+
+        for seed in [[1u8; 32], [1u8; 32], [2u8; 32]] {
+            let client = ClientBuilder::new()
+                .crypto_provider(DeterministicCryptoProvider::new(
+                    TestCryptoProvider::new(),
+                    seed,
+                ))
+                .identity_provider(BasicIdentityProvider::new())
+                .group_state_storage(storage.clone())
+                .signing_identity(identity.clone(), secret_key.clone(), CIPHER_SUITE)
+                .build();
+
+            let mut group = client.load_group(group.group_id()).await.unwrap();
+            let commit = group.commit(vec![]).await.unwrap();
+
+            commits.push(commit.commit_message.to_bytes().unwrap());
+        }
+
+        assert_eq!(commits[0], commits[1]);
+        assert_ne!(commits[0], commits[2]);
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use zeroize::Zeroize;
+
+/// Aggregate of secrets that can be wiped in place.
+///
+/// Every type holding secret key material zeroizes it when dropped, which is
+/// marked by implementing [`ZeroizeOnDrop`](zeroize::ZeroizeOnDrop). Types
+/// that are discarded explicitly, such as the secrets of an epoch that is
+/// replaced by a commit, additionally implement this trait so that they can
+/// be wiped with [`wipe`] and, with the `zeroize_audit` feature, checked.
+pub(crate) trait SecretMaterial: Zeroize {
+    /// Returns true if no secret is left in `self`.
+    #[cfg(any(test, feature = "zeroize_audit"))]
+    fn is_wiped(&self) -> bool;
+}
+
+/// Zeroize `secret`.
+///
+/// With the `zeroize_audit` feature, this panics if any secret is left
+/// afterwards.
+pub(crate) fn wipe<T: SecretMaterial>(secret: &mut T) {
+    secret.zeroize();
+
+    #[cfg(feature = "zeroize_audit")]
+    assert!(
+        secret.is_wiped(),
+        "secret material of type {} was not wiped",
+        core::any::type_name::<T>()
+    );
+}
+
+// Types holding secrets must zeroize them on drop.
+#[allow(dead_code)]
+fn zeroize_on_drop_policy() {
+    fn zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+
+    zeroize_on_drop::<crate::crypto::HpkeSecretKey>();
+    zeroize_on_drop::<crate::crypto::SignatureSecretKey>();
+    zeroize_on_drop::<crate::crypto::Secret>();
+    zeroize_on_drop::<mls_rs_core::psk::PreSharedKey>();
+    zeroize_on_drop::<crate::tree_kem::path_secret::PathSecret>();
+    zeroize_on_drop::<crate::group::epoch::EpochSecrets>();
+    zeroize_on_drop::<crate::group::epoch::SenderDataSecret>();
+    zeroize_on_drop::<crate::group::key_schedule::KeySchedule>();
+
+    #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+    {
+        use crate::{
+            group::secret_tree::{MessageKeyData, SecretTree},
+            tree_kem::node::NodeIndex,
+        };
+
+        zeroize_on_drop::<MessageKeyData>();
+        zeroize_on_drop::<SecretTree<NodeIndex>>();
+    }
+
+    #[cfg(feature = "prior_epoch")]
+    zeroize_on_drop::<crate::group::epoch::PriorEpoch>();
+}
+
+#[cfg(all(test, feature = "prior_epoch"))]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        client::test_utils::TEST_CIPHER_SUITE, group::epoch::test_utils::get_test_epoch_with_id,
+    };
+
+    use super::{wipe, SecretMaterial};
+
+    #[test]
+    fn prior_epoch_is_wiped() {
+        let mut epoch = get_test_epoch_with_id(vec![1, 2, 3], TEST_CIPHER_SUITE, 0);
+        epoch.epoch_authenticator = vec![42; 32].into();
+
+        assert!(!epoch.is_wiped());
+
+        wipe(&mut epoch);
+
+        assert!(epoch.is_wiped());
+        assert_eq!(epoch.epoch_id(), 0);
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::sync::{Arc, Mutex};
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::{
+    collections::{BTreeSet, VecDeque},
+    vec::Vec,
+};
+use core::fmt::{self, Debug};
+
+use mls_rs_core::crypto::{
+    CipherSuite, CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkePublicKey, HpkeSecretKey,
+    SignaturePublicKey, SignatureSecretKey, SignatureVerification,
+};
+use zeroize::Zeroizing;
+
+/// Default number of public keys remembered as valid by a
+/// [`ValidationCachingCryptoProvider`].
+pub const DEFAULT_VALIDATION_CACHE_CAPACITY: usize = 4096;
+
+type CacheKey = (CipherSuite, Vec<u8>);
+
+/// Bounded set of public keys that passed validation. Once full, the
+/// oldest entry is evicted first.
+struct ValidationCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    keys: BTreeSet<CacheKey>,
+    order: VecDeque<CacheKey>,
+}
+
+impl ValidationCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+
+    fn contains(&self, cipher_suite: CipherSuite, key: &HpkePublicKey) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.keys.contains(&(cipher_suite, key.to_vec()))
+    }
+
+    fn insert(&self, cipher_suite: CipherSuite, key: &HpkePublicKey) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        let cache_key = (cipher_suite, key.to_vec());
+
+        if !entries.keys.insert(cache_key.clone()) {
+            return;
+        }
+
+        entries.order.push_back(cache_key);
+
+        if entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.keys.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Crypto provider remembering which HPKE public keys passed
+/// [`CipherSuiteProvider::kem_public_key_validate`].
+///
+/// Validating the same init and leaf keys again, for example when a
+/// delivery service checks key packages in bulk, then skips the expensive
+/// point validation of the wrapped provider. Only successful validations are
+/// cached, in a bounded set owned by this provider and shared by the cipher
+/// suite providers it creates. Clients that want caching opt in by passing
+/// this provider to
+/// [`ClientBuilder::crypto_provider`](crate::client_builder::ClientBuilder::crypto_provider).
+#[derive(Clone)]
+pub struct ValidationCachingCryptoProvider<P> {
+    inner: P,
+    cache: Arc<ValidationCache>,
+}
+
+impl<P> ValidationCachingCryptoProvider<P> {
+    /// Wrap `inner` with a cache holding up to
+    /// [`DEFAULT_VALIDATION_CACHE_CAPACITY`] keys.
+    pub fn new(inner: P) -> Self {
+        Self::with_capacity(inner, DEFAULT_VALIDATION_CACHE_CAPACITY)
+    }
+
+    /// Wrap `inner` with a cache holding up to `capacity` keys.
+    pub fn with_capacity(inner: P, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(ValidationCache::new(capacity)),
+        }
+    }
+}
+
+impl<P: Debug> Debug for ValidationCachingCryptoProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationCachingCryptoProvider")
+            .field("inner", &self.inner)
+            .field("capacity", &self.cache.capacity)
+            .finish()
+    }
+}
+
+impl<P> CryptoProvider for ValidationCachingCryptoProvider<P>
+where
+    P: CryptoProvider,
+{
+    type CipherSuiteProvider = ValidationCachingCipherSuiteProvider<P::CipherSuiteProvider>;
+
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        self.inner.supported_cipher_suites()
+    }
+
+    fn cipher_suite_provider(
+        &self,
+        cipher_suite: CipherSuite,
+    ) -> Option<Self::CipherSuiteProvider> {
+        self.inner.cipher_suite_provider(cipher_suite).map(|inner| {
+            ValidationCachingCipherSuiteProvider {
+                inner,
+                cache: self.cache.clone(),
+            }
+        })
+    }
+}
+
+/// Cipher suite provider of a [`ValidationCachingCryptoProvider`].
+#[derive(Clone)]
+pub struct ValidationCachingCipherSuiteProvider<P> {
+    inner: P,
+    cache: Arc<ValidationCache>,
+}
+
+impl<P: Debug> Debug for ValidationCachingCipherSuiteProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationCachingCipherSuiteProvider")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<P> CipherSuiteProvider for ValidationCachingCipherSuiteProvider<P>
+where
+    P: CipherSuiteProvider,
+{
+    type Error = P::Error;
+    type HpkeContextS = P::HpkeContextS;
+    type HpkeContextR = P::HpkeContextR;
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.inner.cipher_suite()
+    }
+
+    async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.inner.hash(data).await
+    }
+
+    async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.inner.mac(key, data).await
+    }
+
+    async fn aead_seal(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.aead_seal(key, data, aad, nonce).await
+    }
+
+    async fn aead_open(
+        &self,
+        key: &[u8],
+        ciphertext: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.aead_open(key, ciphertext, aad, nonce).await
+    }
+
+    fn aead_key_size(&self) -> usize {
+        self.inner.aead_key_size()
+    }
+
+    fn aead_nonce_size(&self) -> usize {
+        self.inner.aead_nonce_size()
+    }
+
+    async fn kdf_extract(
+        &self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.kdf_extract(salt, ikm).await
+    }
+
+    async fn kdf_expand(
+        &self,
+        prk: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.inner.kdf_expand(prk, info, len).await
+    }
+
+    fn kdf_extract_size(&self) -> usize {
+        self.inner.kdf_extract_size()
+    }
+
+    async fn hpke_seal(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+        pt: &[u8],
+    ) -> Result<HpkeCiphertext, Self::Error> {
+        self.inner.hpke_seal(remote_key, info, aad, pt).await
+    }
+
+    async fn hpke_open(
+        &self,
+        ciphertext: &HpkeCiphertext,
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner
+            .hpke_open(ciphertext, local_secret, local_public, info, aad)
+            .await
+    }
+
+    async fn hpke_setup_s(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+        self.inner.hpke_setup_s(remote_key, info).await
+    }
+
+    async fn hpke_setup_r(
+        &self,
+        kem_output: &[u8],
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<Self::HpkeContextR, Self::Error> {
+        self.inner
+            .hpke_setup_r(kem_output, local_secret, local_public, info)
+            .await
+    }
+
+    async fn kem_derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.inner.kem_derive(ikm).await
+    }
+
+    async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.inner.kem_generate().await
+    }
+
+    fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        let cipher_suite = self.inner.cipher_suite();
+
+        if self.cache.contains(cipher_suite, key) {
+            return Ok(());
+        }
+
+        self.inner.kem_public_key_validate(key)?;
+        self.cache.insert(cipher_suite, key);
+
+        Ok(())
+    }
+
+    fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+        self.inner.random_bytes(out)
+    }
+
+    async fn signature_key_generate(
+        &self,
+    ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+        self.inner.signature_key_generate().await
+    }
+
+    async fn signature_key_derive_public(
+        &self,
+        secret_key: &SignatureSecretKey,
+    ) -> Result<SignaturePublicKey, Self::Error> {
+        self.inner.signature_key_derive_public(secret_key).await
+    }
+
+    async fn sign(
+        &self,
+        secret_key: &SignatureSecretKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.sign(secret_key, data).await
+    }
+
+    async fn verify(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.inner.verify(public_key, signature, data).await
+    }
+
+    async fn verify_batch(&self, batch: &[SignatureVerification<'_>]) -> Result<(), Self::Error> {
+        self.inner.verify_batch(batch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use mls_rs_core::crypto::{CipherSuite, CipherSuiteProvider, CryptoProvider, HpkePublicKey};
+
+    use crate::crypto::test_utils::TestCryptoProvider;
+
+    use super::{ValidationCache, ValidationCachingCryptoProvider};
+
+    fn key(byte: u8) -> HpkePublicKey {
+        HpkePublicKey::from(vec![byte; 32])
+    }
+
+    #[test]
+    fn cache_is_keyed_by_cipher_suite() {
+        let cache = ValidationCache::new(2);
+
+        cache.insert(CipherSuite::CURVE25519_AES128, &key(1));
+
+        assert!(cache.contains(CipherSuite::CURVE25519_AES128, &key(1)));
+        assert!(!cache.contains(CipherSuite::P256_AES128, &key(1)));
+        assert!(!cache.contains(CipherSuite::CURVE25519_AES128, &key(2)));
+    }
+
+    #[test]
+    fn cache_evicts_oldest_key() {
+        let cache = ValidationCache::new(2);
+
+        (1..=3).for_each(|i| cache.insert(CipherSuite::CURVE25519_AES128, &key(i)));
+
+        assert!(!cache.contains(CipherSuite::CURVE25519_AES128, &key(1)));
+        assert!(cache.contains(CipherSuite::CURVE25519_AES128, &key(2)));
+        assert!(cache.contains(CipherSuite::CURVE25519_AES128, &key(3)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn providers_do_not_share_caches() {
+        let cipher_suite = CipherSuite::CURVE25519_AES128;
+        let first = ValidationCachingCryptoProvider::new(TestCryptoProvider::new());
+        let second = ValidationCachingCryptoProvider::new(TestCryptoProvider::new());
+
+        let cs = first.cipher_suite_provider(cipher_suite).unwrap();
+        let (_, public_key) = cs.kem_generate().await.unwrap();
+
+        cs.kem_public_key_validate(&public_key).unwrap();
+
+        // Cipher suite providers of the same crypto provider share its cache.
+        assert!(first.cache.contains(cipher_suite, &public_key));
+        assert!(!second.cache.contains(cipher_suite, &public_key));
+    }
+
+    #[test]
+    fn invalid_keys_are_not_cached() {
+        let cipher_suite = CipherSuite::P256_AES128;
+        let provider = ValidationCachingCryptoProvider::new(TestCryptoProvider::new());
+
+        let Some(cs) = provider.cipher_suite_provider(cipher_suite) else {
+            return;
+        };
+
+        assert!(cs.kem_public_key_validate(&key(1)).is_err());
+        assert!(!provider.cache.contains(cipher_suite, &key(1)));
+    }
+}
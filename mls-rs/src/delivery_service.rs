@@ -0,0 +1,546 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use mls_rs_core::{
+    crypto::{CryptoProvider, SignaturePublicKey},
+    error::IntoAnyError,
+    extension::ExtensionList,
+    identity::IdentityProvider,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{framing::WireFormat, CommitMessageDescription, CommitOutput, Group, ReceivedMessage},
+    Client, KeyPackageRef, MlsMessage,
+};
+
+mod in_memory;
+
+pub use in_memory::InMemoryDeliveryService;
+
+/// Transport that delivers messages and key packages between clients.
+///
+/// Clients are addressed by the signature public key of their signing
+/// identity, which is known both from the roster of a group and from the
+/// key packages of new members. A [`Session`] uses this trait to send the
+/// output of its groups to the other members automatically.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait DeliveryService: Send + Sync {
+    /// Error type that this delivery service returns on internal failure.
+    type Error: IntoAnyError;
+
+    /// Send `message` to each client in `recipients`.
+    async fn send_message(
+        &mut self,
+        recipients: &[SignaturePublicKey],
+        message: &MlsMessage,
+    ) -> Result<(), Self::Error>;
+
+    /// Fetch all messages sent to `recipient` since the previous fetch, in
+    /// the order in which they were sent.
+    async fn fetch_messages(
+        &mut self,
+        recipient: &SignaturePublicKey,
+    ) -> Result<Vec<MlsMessage>, Self::Error>;
+
+    /// Publish `key_package` under the application level `identity` of its
+    /// owner, as determined by the
+    /// [`IdentityProvider`](crate::IdentityProvider) in use.
+    async fn publish_key_package(
+        &mut self,
+        identity: &[u8],
+        key_package: &MlsMessage,
+    ) -> Result<(), Self::Error>;
+
+    /// Take all key packages published under `identity`, one for each of
+    /// its clients. Each key package is returned at most once.
+    async fn fetch_key_packages(&mut self, identity: &[u8])
+        -> Result<Vec<MlsMessage>, Self::Error>;
+}
+
+/// Event produced by [`Session::receive`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SessionEvent {
+    /// The session joined the group with id `group_id` using a Welcome
+    /// message.
+    Joined { group_id: Vec<u8> },
+    /// A message for the group with id `group_id` was processed.
+    Received {
+        group_id: Vec<u8>,
+        message: ReceivedMessage,
+    },
+    /// A message could not be processed. The message is not fetched again.
+    Rejected {
+        message: MlsMessage,
+        error: MlsError,
+    },
+}
+
+/// Driver that connects the groups of a [`Client`] to a
+/// [`DeliveryService`].
+///
+/// Commits created by the session are sent to all other members of the
+/// group before they are applied, and resulting Welcome messages are sent to
+/// the new members. Group states are written to storage after each change.
+pub struct Session<C, D>
+where
+    C: ClientConfig,
+{
+    client: Client<C>,
+    delivery_service: D,
+    groups: Vec<Group<C>>,
+}
+
+impl<C, D> Session<C, D>
+where
+    C: ClientConfig + Clone,
+    D: DeliveryService,
+{
+    pub fn new(client: Client<C>, delivery_service: D) -> Self {
+        Self {
+            client,
+            delivery_service,
+            groups: Vec::new(),
+        }
+    }
+
+    pub fn client(&self) -> &Client<C> {
+        &self.client
+    }
+
+    pub fn delivery_service(&self) -> &D {
+        &self.delivery_service
+    }
+
+    /// Group with id `group_id` that was created or joined by this session.
+    pub fn group(&self, group_id: &[u8]) -> Option<&Group<C>> {
+        self.groups
+            .iter()
+            .find(|group| group.group_id() == group_id)
+    }
+
+    /// Ids of all groups that were created or joined by this session.
+    pub fn group_ids(&self) -> Vec<Vec<u8>> {
+        self.groups
+            .iter()
+            .map(|group| group.group_id().to_vec())
+            .collect()
+    }
+
+    /// Application level identity of the client of this session.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn identity(&self) -> Result<Vec<u8>, MlsError> {
+        let (signing_identity, _) = self.client.signing_identity()?;
+
+        self.client
+            .config
+            .identity_provider()
+            .identity(signing_identity, &ExtensionList::new())
+            .await
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))
+    }
+
+    /// Generate a new key package and publish it under the
+    /// [identity](Self::identity) of the client.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn publish_key_package(&mut self) -> Result<(), MlsError> {
+        let key_package = self.client.generate_key_package_message().await?;
+        let identity = self.identity().await?;
+
+        self.delivery_service
+            .publish_key_package(&identity, &key_package)
+            .await
+            .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))
+    }
+
+    /// Create a new group and return its id.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn create_group(
+        &mut self,
+        group_context_extensions: ExtensionList,
+    ) -> Result<Vec<u8>, MlsError> {
+        let mut group = self.client.create_group(group_context_extensions).await?;
+        group.write_to_storage().await?;
+
+        let group_id = group.group_id().to_vec();
+        self.groups.push(group);
+
+        Ok(group_id)
+    }
+
+    /// Add all clients of each of `identities` to the group with id
+    /// `group_id`.
+    ///
+    /// The key packages of all `identities` are fetched before the commit is
+    /// created. If no key package is published for one of them, the commit
+    /// is not created and [`MlsError::KeyPackageNotFound`] is returned.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn add_members(
+        &mut self,
+        group_id: &[u8],
+        identities: &[&[u8]],
+    ) -> Result<CommitMessageDescription, MlsError> {
+        let index = self.group_index(group_id)?;
+        let mut key_packages = Vec::new();
+
+        for identity in identities {
+            let fetched = self
+                .delivery_service
+                .fetch_key_packages(identity)
+                .await
+                .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))?;
+
+            if fetched.is_empty() {
+                return Err(MlsError::KeyPackageNotFound);
+            }
+
+            key_packages.extend(fetched);
+        }
+
+        let mut new_members = Vec::new();
+
+        for key_package in &key_packages {
+            new_members.push(self.new_member_address(key_package).await?);
+        }
+
+        let mut builder = self.groups[index].commit_builder();
+
+        for key_package in key_packages {
+            builder = builder.add_member(key_package)?;
+        }
+
+        let commit = builder.build().await?;
+
+        self.send_commit(index, commit, &new_members).await
+    }
+
+    /// Remove the members with leaf indexes `members` from the group with
+    /// id `group_id`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn remove_members(
+        &mut self,
+        group_id: &[u8],
+        members: &[u32],
+    ) -> Result<CommitMessageDescription, MlsError> {
+        let index = self.group_index(group_id)?;
+        let mut builder = self.groups[index].commit_builder();
+
+        for member in members {
+            builder = builder.remove_member(*member)?;
+        }
+
+        let commit = builder.build().await?;
+
+        self.send_commit(index, commit, &[]).await
+    }
+
+    /// Commit all pending proposals of the group with id `group_id`,
+    /// updating the path of the client.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn commit(&mut self, group_id: &[u8]) -> Result<CommitMessageDescription, MlsError> {
+        let index = self.group_index(group_id)?;
+        let commit = self.groups[index].commit(Vec::new()).await?;
+
+        self.send_commit(index, commit, &[]).await
+    }
+
+    /// Encrypt `message` for the group with id `group_id` and send it to all
+    /// other members.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn send_application_message(
+        &mut self,
+        group_id: &[u8],
+        message: &[u8],
+    ) -> Result<(), MlsError> {
+        let index = self.group_index(group_id)?;
+        let group = &mut self.groups[index];
+
+        let message = group
+            .encrypt_application_message(message, Vec::new())
+            .await?;
+
+        group.write_to_storage().await?;
+
+        let recipients = other_members(group);
+
+        self.delivery_service
+            .send_message(&recipients, &message)
+            .await
+            .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))
+    }
+
+    /// Fetch and process all messages sent to this session.
+    ///
+    /// Welcome messages are used to join new groups. All other messages are
+    /// processed by the group they belong to. Messages that can't be
+    /// processed are reported as [`SessionEvent::Rejected`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn receive(&mut self) -> Result<Vec<SessionEvent>, MlsError> {
+        let recipient = self.client.signing_identity()?.0.signature_key.clone();
+
+        let messages = self
+            .delivery_service
+            .fetch_messages(&recipient)
+            .await
+            .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))?;
+
+        let mut events = Vec::with_capacity(messages.len());
+
+        for message in messages {
+            let event = match self.process_message(&message).await {
+                Ok(event) => event,
+                Err(error) => SessionEvent::Rejected { message, error },
+            };
+
+            events.push(event);
+        }
+
+        Ok(events)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn process_message(&mut self, message: &MlsMessage) -> Result<SessionEvent, MlsError> {
+        if message.wire_format() == WireFormat::Welcome {
+            let (mut group, _) = self.client.join_group(None, message).await?;
+            group.write_to_storage().await?;
+
+            let group_id = group.group_id().to_vec();
+            self.groups.push(group);
+
+            return Ok(SessionEvent::Joined { group_id });
+        }
+
+        let group_id = message
+            .group_id()
+            .ok_or(MlsError::UnexpectedMessageType)?
+            .to_vec();
+
+        let index = self.group_index(&group_id)?;
+        let group = &mut self.groups[index];
+        let message = group.process_incoming_message(message.clone()).await?;
+        group.write_to_storage().await?;
+
+        Ok(SessionEvent::Received { group_id, message })
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn send_commit(
+        &mut self,
+        index: usize,
+        commit: CommitOutput,
+        new_members: &[(KeyPackageRef, SignaturePublicKey)],
+    ) -> Result<CommitMessageDescription, MlsError> {
+        let group = &mut self.groups[index];
+
+        // The roster still contains the members removed by the commit, so
+        // that they learn about their removal.
+        let recipients = other_members(group);
+
+        self.delivery_service
+            .send_message(&recipients, &commit.commit_message)
+            .await
+            .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))?;
+
+        let description = group.apply_pending_commit().await?;
+        group.write_to_storage().await?;
+
+        for welcome in &commit.welcome_messages {
+            let references = welcome.welcome_key_package_references();
+
+            let recipients = new_members
+                .iter()
+                .filter(|(reference, _)| references.contains(&reference))
+                .map(|(_, signature_key)| signature_key.clone())
+                .collect::<Vec<_>>();
+
+            self.delivery_service
+                .send_message(&recipients, welcome)
+                .await
+                .map_err(|e| MlsError::DeliveryServiceError(e.into_any_error()))?;
+        }
+
+        Ok(description)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn new_member_address(
+        &self,
+        key_package: &MlsMessage,
+    ) -> Result<(KeyPackageRef, SignaturePublicKey), MlsError> {
+        let cipher_suite = key_package
+            .cipher_suite()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        let cipher_suite_provider = self
+            .client
+            .config
+            .crypto_provider()
+            .cipher_suite_provider(cipher_suite)
+            .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))?;
+
+        let reference = key_package
+            .key_package_reference(&cipher_suite_provider)
+            .await?
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        let signature_key = key_package
+            .clone()
+            .into_key_package()
+            .ok_or(MlsError::UnexpectedMessageType)?
+            .signing_identity()
+            .signature_key
+            .clone();
+
+        Ok((reference, signature_key))
+    }
+
+    fn group_index(&self, group_id: &[u8]) -> Result<usize, MlsError> {
+        self.groups
+            .iter()
+            .position(|group| group.group_id() == group_id)
+            .ok_or(MlsError::GroupNotFound)
+    }
+}
+
+fn other_members<C: ClientConfig>(group: &Group<C>) -> Vec<SignaturePublicKey> {
+    let own_index = group.current_member_index();
+
+    group
+        .roster()
+        .members_iter()
+        .filter(|member| member.index != own_index)
+        .map(|member| member.signing_identity.signature_key)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TestClientBuilder, TestClientConfig, TEST_CIPHER_SUITE},
+            MlsError,
+        },
+        group::ReceivedMessage,
+        identity::test_utils::get_test_signing_identity,
+    };
+
+    use super::{InMemoryDeliveryService, Session, SessionEvent};
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_session(
+        name: &str,
+        delivery_service: &InMemoryDeliveryService,
+    ) -> Session<TestClientConfig, InMemoryDeliveryService> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        let client = TestClientBuilder::new_for_test()
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build();
+
+        Session::new(client, delivery_service.clone())
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn sessions_exchange_messages_through_delivery_service() {
+        let delivery_service = InMemoryDeliveryService::new();
+        let mut alice = test_session("alice", &delivery_service).await;
+        let mut bob = test_session("bob", &delivery_service).await;
+        let mut carol = test_session("carol", &delivery_service).await;
+
+        bob.publish_key_package().await.unwrap();
+        carol.publish_key_package().await.unwrap();
+
+        let group_id = alice.create_group(Default::default()).await.unwrap();
+
+        alice
+            .add_members(&group_id, &[b"bob".as_slice()])
+            .await
+            .unwrap();
+
+        let events = bob.receive().await.unwrap();
+        assert_matches!(&events[..], [SessionEvent::Joined { group_id: id }] if id == &group_id);
+
+        alice
+            .add_members(&group_id, &[b"carol".as_slice()])
+            .await
+            .unwrap();
+
+        carol.receive().await.unwrap();
+        bob.receive().await.unwrap();
+
+        alice
+            .send_application_message(&group_id, b"hello")
+            .await
+            .unwrap();
+
+        for session in [&mut bob, &mut carol] {
+            let events = session.receive().await.unwrap();
+
+            assert_matches!(
+                &events[..],
+                [SessionEvent::Received {
+                    message: ReceivedMessage::ApplicationMessage(message),
+                    ..
+                }] if message.data() == b"hello"
+            );
+
+            assert_eq!(session.group(&group_id).unwrap().current_epoch(), 2);
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_receives_its_removal() {
+        let delivery_service = InMemoryDeliveryService::new();
+        let mut alice = test_session("alice", &delivery_service).await;
+        let mut bob = test_session("bob", &delivery_service).await;
+
+        bob.publish_key_package().await.unwrap();
+
+        let group_id = alice.create_group(Default::default()).await.unwrap();
+        alice
+            .add_members(&group_id, &[b"bob".as_slice()])
+            .await
+            .unwrap();
+        bob.receive().await.unwrap();
+
+        alice.remove_members(&group_id, &[1]).await.unwrap();
+
+        let events = bob.receive().await.unwrap();
+
+        assert_matches!(
+            &events[..],
+            [SessionEvent::Received {
+                message: ReceivedMessage::Commit(commit),
+                ..
+            }] if !commit.state_update.is_active()
+        );
+
+        assert_eq!(alice.group(&group_id).unwrap().roster().members().len(), 1);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn adding_identity_without_key_package_fails() {
+        let delivery_service = InMemoryDeliveryService::new();
+        let mut alice = test_session("alice", &delivery_service).await;
+
+        let group_id = alice.create_group(Default::default()).await.unwrap();
+        let res = alice.add_members(&group_id, &[b"bob".as_slice()]).await;
+
+        assert_matches!(res, Err(MlsError::KeyPackageNotFound));
+        assert_eq!(alice.group(&group_id).unwrap().current_epoch(), 0);
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+#[cfg(not(target_has_atomic = "ptr"))]
+use portable_atomic_util::Arc;
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use mls_rs_core::crypto::SignaturePublicKey;
+
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+use crate::{map::LargeMap, MlsMessage};
+
+use super::DeliveryService;
+
+#[derive(Debug, Default)]
+struct Mailboxes {
+    messages: LargeMap<SignaturePublicKey, Vec<MlsMessage>>,
+    key_packages: LargeMap<Vec<u8>, Vec<MlsMessage>>,
+}
+
+/// In memory [`DeliveryService`] for tests and examples.
+///
+/// All clones of an instance of this type share the same mailboxes, so each
+/// client of a test can be given its own clone.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryDeliveryService {
+    inner: Arc<Mutex<Mailboxes>>,
+}
+
+impl InMemoryDeliveryService {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Number of messages sent to `recipient` that were not fetched yet.
+    pub fn pending_messages(&self, recipient: &SignaturePublicKey) -> usize {
+        self.lock().messages.get(recipient).map_or(0, Vec::len)
+    }
+
+    /// Number of key packages published under `identity` that were not
+    /// fetched yet.
+    pub fn pending_key_packages(&self, identity: &[u8]) -> usize {
+        self.lock().key_packages.get(identity).map_or(0, Vec::len)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Mailboxes> {
+        #[cfg(feature = "std")]
+        return self.inner.lock().unwrap();
+
+        #[cfg(not(feature = "std"))]
+        return self.inner.lock();
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl DeliveryService for InMemoryDeliveryService {
+    type Error = Infallible;
+
+    async fn send_message(
+        &mut self,
+        recipients: &[SignaturePublicKey],
+        message: &MlsMessage,
+    ) -> Result<(), Self::Error> {
+        let mut mailboxes = self.lock();
+
+        for recipient in recipients {
+            mailboxes
+                .messages
+                .entry(recipient.clone())
+                .or_default()
+                .push(message.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_messages(
+        &mut self,
+        recipient: &SignaturePublicKey,
+    ) -> Result<Vec<MlsMessage>, Self::Error> {
+        Ok(self.lock().messages.remove(recipient).unwrap_or_default())
+    }
+
+    async fn publish_key_package(
+        &mut self,
+        identity: &[u8],
+        key_package: &MlsMessage,
+    ) -> Result<(), Self::Error> {
+        self.lock()
+            .key_packages
+            .entry(identity.to_vec())
+            .or_default()
+            .push(key_package.clone());
+
+        Ok(())
+    }
+
+    async fn fetch_key_packages(
+        &mut self,
+        identity: &[u8],
+    ) -> Result<Vec<MlsMessage>, Self::Error> {
+        Ok(self
+            .lock()
+            .key_packages
+            .remove(identity)
+            .unwrap_or_default())
+    }
+}
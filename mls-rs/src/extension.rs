@@ -9,6 +9,12 @@ pub(crate) use built_in::*;
 /// Default extension types required by the MLS RFC.
 pub mod built_in;
 
+#[cfg(feature = "extension_migration")]
+pub(crate) mod migration;
+
+#[cfg(feature = "extension_migration")]
+pub use migration::{ExtensionMigration, ExtensionTranslation};
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use alloc::vec::Vec;
@@ -20,6 +20,9 @@ use crate::group::ExportedTree;
 
 use mls_rs_core::crypto::HpkePublicKey;
 
+#[cfg(feature = "member_reset")]
+use mls_rs_core::psk::ExternalPskId;
+
 /// Application specific identifier.
 ///
 /// A custom application level identifier that can be optionally stored
@@ -235,6 +238,783 @@ impl MlsCodecExtension for ExternalSendersExt {
     }
 }
 
+/// Application features enabled within a group.
+///
+/// Features are identified by application defined numeric flags and are
+/// stored within the group context extensions. Every member of a group
+/// that has a feature enabled MUST advertise support for it using a
+/// [`SupportedFeaturesExt`] within its leaf node extensions.
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[cfg(feature = "group_features")]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode, Default)]
+pub struct GroupFeaturesExt {
+    pub enabled: Vec<u16>,
+}
+
+#[cfg(feature = "group_features")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl GroupFeaturesExt {
+    /// Create a group features extension with the `enabled` feature flags.
+    pub fn new(enabled: Vec<u16>) -> Self {
+        Self { enabled }
+    }
+
+    /// Determine if `flag` is enabled.
+    pub fn is_enabled(&self, flag: u16) -> bool {
+        self.enabled.contains(&flag)
+    }
+}
+
+#[cfg(feature = "group_features")]
+impl MlsCodecExtension for GroupFeaturesExt {
+    fn extension_type() -> ExtensionType {
+        GROUP_FEATURES_EXTENSION_TYPE
+    }
+}
+
+/// Application features supported by a group member.
+///
+/// Stored within the `leaf_node_extensions` of a group
+/// [Member](crate::group::Member). A member can only be added to a group
+/// if it supports all the features of the group's [`GroupFeaturesExt`].
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[cfg(feature = "group_features")]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode, Default)]
+pub struct SupportedFeaturesExt {
+    pub supported: Vec<u16>,
+}
+
+#[cfg(feature = "group_features")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl SupportedFeaturesExt {
+    /// Create a supported features extension with the `supported` feature flags.
+    pub fn new(supported: Vec<u16>) -> Self {
+        Self { supported }
+    }
+
+    /// Determine if `flag` is supported.
+    pub fn is_supported(&self, flag: u16) -> bool {
+        self.supported.contains(&flag)
+    }
+}
+
+#[cfg(feature = "group_features")]
+impl MlsCodecExtension for SupportedFeaturesExt {
+    fn extension_type() -> ExtensionType {
+        SUPPORTED_FEATURES_EXTENSION_TYPE
+    }
+}
+
+/// Authorization for a specific client to join a group using an external
+/// commit.
+///
+/// Join tickets are issued by a group member with
+/// [`Group::issue_join_ticket`](crate::Group::issue_join_ticket) and are only
+/// valid for the epoch they were issued in. They are delivered to the joiner
+/// within the GroupInfo extensions and the joiner presents them within the
+/// `leaf_node_extensions` of its new leaf.
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct JoinTicketExt {
+    /// Signature public key of the client allowed to join.
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub signature_key: Vec<u8>,
+    /// Expiration time of the ticket in seconds since the Unix epoch.
+    pub not_after: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub(crate) tag: Vec<u8>,
+}
+
+impl Debug for JoinTicketExt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinTicketExt")
+            .field(
+                "signature_key",
+                &mls_rs_core::debug::pretty_bytes(&self.signature_key),
+            )
+            .field("not_after", &self.not_after)
+            .field("tag", &mls_rs_core::debug::pretty_bytes(&self.tag))
+            .finish()
+    }
+}
+
+impl MlsCodecExtension for JoinTicketExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(JOIN_TICKET_EXTENSION_TYPE)
+    }
+}
+
+/// Requirement for new members joining with an external commit to present
+/// a valid [`JoinTicketExt`].
+///
+/// Stored within the group context extensions.
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct JoinTicketRequiredExt {
+    /// Maximum lifetime in seconds of an accepted ticket, measured from the
+    /// time the external commit is received.
+    pub max_lifetime_in_s: u64,
+}
+
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl JoinTicketRequiredExt {
+    pub fn new(max_lifetime_in_s: u64) -> Self {
+        Self { max_lifetime_in_s }
+    }
+}
+
+impl MlsCodecExtension for JoinTicketRequiredExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(JOIN_TICKET_REQUIRED_EXTENSION_TYPE)
+    }
+}
+
+/// Reason for a commit set by the committer, see [`CommitReasonExt`].
+///
+/// Values other than the associated constants can be used for application
+/// specific reasons.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::ffi_type)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct CommitReason(u16);
+
+impl CommitReason {
+    /// Members were added to or removed from the group.
+    pub const MEMBERSHIP_CHANGE: CommitReason = CommitReason(1);
+    /// The committer rotated its keys.
+    pub const KEY_ROTATION: CommitReason = CommitReason(2);
+    /// Group policy such as the group context extensions changed.
+    pub const POLICY_CHANGE: CommitReason = CommitReason(3);
+    /// The committer recovered from a lost or inconsistent state.
+    pub const RECOVERY: CommitReason = CommitReason(4);
+    /// A member suspected to be compromised was removed and can only be
+    /// added back with a pre-shared key.
+    pub const MEMBER_RESET: CommitReason = CommitReason(5);
+
+    /// Commit reason from a raw value.
+    pub const fn new(value: u16) -> CommitReason {
+        CommitReason(value)
+    }
+
+    /// Raw numerical value of the reason.
+    pub const fn raw_value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for CommitReason {
+    fn from(value: u16) -> Self {
+        CommitReason(value)
+    }
+}
+
+impl From<CommitReason> for u16 {
+    fn from(value: CommitReason) -> Self {
+        value.0
+    }
+}
+
+/// Reason for a commit, allowing receivers to describe the change of epoch
+/// without inferring it from the committed proposals.
+///
+/// Stored within the `leaf_node_extensions` of the committer's leaf node in
+/// the update path of the commit. It is set with
+/// [`CommitBuilder::commit_reason`](crate::group::CommitBuilder::commit_reason)
+/// and surfaced to receivers by `StateUpdate::commit_reason`.
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct CommitReasonExt {
+    pub reason: CommitReason,
+}
+
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl CommitReasonExt {
+    pub fn new(reason: CommitReason) -> Self {
+        Self { reason }
+    }
+}
+
+impl MlsCodecExtension for CommitReasonExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(COMMIT_REASON_EXTENSION_TYPE)
+    }
+}
+
+/// Designated members whose co-signatures are required to commit changes to
+/// the membership of the group.
+///
+/// Stored within the group context extensions and enforced by
+/// [`CoSignedMlsRules`](crate::group::co_signature::CoSignedMlsRules).
+#[cfg(feature = "co_signed_commit")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct CoSignersExt {
+    /// Number of distinct designated members that must approve a commit.
+    pub threshold: u32,
+    /// Leaf indices of the designated members.
+    pub signers: Vec<u32>,
+}
+
+#[cfg(feature = "co_signed_commit")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl CoSignersExt {
+    /// Require `threshold` approvals out of the members at leaf indices
+    /// `signers`.
+    pub fn new(threshold: u32, signers: Vec<u32>) -> Self {
+        Self { threshold, signers }
+    }
+
+    /// Determine if the member at leaf index `index` is a designated signer.
+    pub fn is_signer(&self, index: u32) -> bool {
+        self.signers.contains(&index)
+    }
+}
+
+#[cfg(feature = "co_signed_commit")]
+impl MlsCodecExtension for CoSignersExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(CO_SIGNERS_EXTENSION_TYPE)
+    }
+}
+
+/// Device attestation proving that the signature key of a member is held in
+/// attested hardware, such as a secure enclave, StrongBox or TPM.
+///
+/// Stored within the `leaf_node_extensions` of a group [Member](crate::group::Member).
+/// The attestation is opaque to the library and verified by an
+/// [`AttestationVerifier`](crate::identity::attestation::AttestationVerifier).
+#[cfg(feature = "device_attestation")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct DeviceAttestationExt {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub attestation: Vec<u8>,
+}
+
+#[cfg(feature = "device_attestation")]
+impl Debug for DeviceAttestationExt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceAttestationExt")
+            .field(
+                "attestation",
+                &mls_rs_core::debug::pretty_bytes(&self.attestation),
+            )
+            .finish()
+    }
+}
+
+#[cfg(feature = "device_attestation")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl DeviceAttestationExt {
+    pub fn new(attestation: Vec<u8>) -> Self {
+        Self { attestation }
+    }
+}
+
+#[cfg(feature = "device_attestation")]
+impl MlsCodecExtension for DeviceAttestationExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(DEVICE_ATTESTATION_EXTENSION_TYPE)
+    }
+}
+
+/// Requirement for all members to present a [`DeviceAttestationExt`].
+///
+/// Stored within the group context extensions. Leaf nodes without an
+/// attestation are rejected with [`MlsError::DeviceAttestationRequired`](crate::client::MlsError::DeviceAttestationRequired).
+#[cfg(feature = "device_attestation")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct DeviceAttestationRequiredExt {}
+
+#[cfg(feature = "device_attestation")]
+impl MlsCodecExtension for DeviceAttestationRequiredExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(DEVICE_ATTESTATION_REQUIRED_EXTENSION_TYPE)
+    }
+}
+
+/// Location of the ratchet tree of a group that is not embedded in the
+/// GroupInfo of a Welcome message.
+///
+/// Stored within the GroupInfo extensions. The location is opaque to the
+/// library, for example a URL, and is passed to the
+/// [`TreeFetcher`](crate::group::TreeFetcher) of a joining client.
+#[cfg(feature = "tree_fetcher")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct RatchetTreeLocationExt {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub location: Vec<u8>,
+}
+
+#[cfg(feature = "tree_fetcher")]
+impl Debug for RatchetTreeLocationExt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RatchetTreeLocationExt")
+            .field(
+                "location",
+                &mls_rs_core::debug::pretty_bytes(&self.location),
+            )
+            .finish()
+    }
+}
+
+#[cfg(feature = "tree_fetcher")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl RatchetTreeLocationExt {
+    pub fn new(location: Vec<u8>) -> Self {
+        Self { location }
+    }
+}
+
+#[cfg(feature = "tree_fetcher")]
+impl MlsCodecExtension for RatchetTreeLocationExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(RATCHET_TREE_LOCATION_EXTENSION_TYPE)
+    }
+}
+
+/// Expiry time of a temporary member, such as a guest with time limited
+/// access.
+///
+/// Stored within the `leaf_node_extensions` of a group [Member](crate::group::Member).
+/// A member can't remove the extension or move the expiry time later when
+/// updating its leaf. Expired members are removed by committers using
+/// [`DefaultMlsRules::with_remove_expired_members`](crate::mls_rules::DefaultMlsRules::with_remove_expired_members)
+/// or [`CommitBuilder::remove_expired_members`](crate::group::CommitBuilder::remove_expired_members).
+#[cfg(feature = "member_expiry")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct MemberExpiryExt {
+    /// Expiry time in seconds since the unix epoch.
+    pub expires_at: u64,
+}
+
+#[cfg(feature = "member_expiry")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl MemberExpiryExt {
+    pub fn new(expires_at: mls_rs_core::time::MlsTime) -> Self {
+        Self {
+            expires_at: expires_at.seconds_since_epoch(),
+        }
+    }
+
+    pub fn expires_at(&self) -> mls_rs_core::time::MlsTime {
+        self.expires_at.into()
+    }
+
+    /// Check if the member is expired at `time`.
+    pub fn is_expired(&self, time: mls_rs_core::time::MlsTime) -> bool {
+        time.seconds_since_epoch() >= self.expires_at
+    }
+}
+
+#[cfg(feature = "member_expiry")]
+impl MlsCodecExtension for MemberExpiryExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(MEMBER_EXPIRY_EXTENSION_TYPE)
+    }
+}
+
+/// Admin key of a group that accepts hidden members.
+///
+/// Stored within the group context extensions. Members with a
+/// [`BlindedCredential`](crate::identity::blinded::BlindedCredential) are only
+/// accepted in groups with this extension, and their real credentials are
+/// sealed to `admin_key` so that only admins holding the corresponding
+/// secret key can unblind them.
+#[cfg(feature = "hidden_members")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct HiddenMembersExt {
+    pub admin_key: HpkePublicKey,
+}
+
+#[cfg(feature = "hidden_members")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl HiddenMembersExt {
+    pub fn new(admin_key: HpkePublicKey) -> Self {
+        Self { admin_key }
+    }
+}
+
+#[cfg(feature = "hidden_members")]
+impl MlsCodecExtension for HiddenMembersExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(HIDDEN_MEMBERS_EXTENSION_TYPE)
+    }
+}
+
+/// Member removed by
+/// [`Group::force_reset_member`](crate::group::Group::force_reset_member)
+/// that can only be added back to the group by a commit including the
+/// external pre-shared key `psk_id`.
+#[cfg(feature = "member_reset")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct MemberResetSlot {
+    /// Identity of the removed member, as reported by the
+    /// [`IdentityProvider`](mls_rs_core::identity::IdentityProvider).
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub identity: Vec<u8>,
+    /// Pre-shared key shared out of band with the removed member.
+    pub psk_id: ExternalPskId,
+}
+
+/// Members that were reset and are waiting to be added back to the group.
+///
+/// Stored within the group context extensions. Adding a member, either with
+/// an Add proposal or an external commit, whose identity matches a slot
+/// requires a PreSharedKey proposal for the pre-shared key of the slot in
+/// the same commit. Slots are kept until the group context extensions are
+/// changed again.
+#[cfg(feature = "member_reset")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct MemberResetExt {
+    pub slots: Vec<MemberResetSlot>,
+}
+
+#[cfg(feature = "member_reset")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl MemberResetExt {
+    pub fn new(slots: Vec<MemberResetSlot>) -> Self {
+        Self { slots }
+    }
+}
+
+#[cfg(feature = "member_reset")]
+impl MlsCodecExtension for MemberResetExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(MEMBER_RESET_EXTENSION_TYPE)
+    }
+}
+
+/// Migration of a group to a shadow group created with
+/// [`Group::create_shadow_group`](crate::group::Group::create_shadow_group).
+///
+/// Stored within the group context extensions of the shadow group.
+#[cfg(feature = "shadow_migration")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct ShadowMigrationExt {
+    /// Group ID of the group being migrated.
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub source_group_id: Vec<u8>,
+    /// Epoch of the group being migrated whose resumption secret was used to
+    /// create the shadow group.
+    pub source_epoch: u64,
+    /// Epoch of the group being migrated from which on only the shadow group
+    /// is used.
+    pub cutover_epoch: u64,
+}
+
+#[cfg(feature = "shadow_migration")]
+impl MlsCodecExtension for ShadowMigrationExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(SHADOW_MIGRATION_EXTENSION_TYPE)
+    }
+}
+
+/// Media type of application content, such as `text/plain;charset=utf-8`,
+/// as defined in [RFC 6838](https://www.rfc-editor.org/rfc/rfc6838.html).
+///
+/// Media types are compared byte by byte, so applications should use a
+/// canonical form, e.g. lowercase type and subtype names.
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, MlsSize, MlsEncode, MlsDecode)]
+pub struct MediaType {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub media_type: Vec<u8>,
+}
+
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl MediaType {
+    pub fn new(media_type: &str) -> Self {
+        Self {
+            media_type: media_type.as_bytes().to_vec(),
+        }
+    }
+
+    /// The media type as a string, if it is valid UTF-8.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.media_type).ok()
+    }
+}
+
+/// Media types of application content accepted by a group member, as
+/// defined by the content advertisement of
+/// [draft-ietf-mls-extensions](https://datatracker.ietf.org/doc/draft-ietf-mls-extensions/).
+///
+/// Stored within the `leaf_node_extensions` of a group
+/// [Member](crate::group::Member), in order of preference. A member can only
+/// be added to a group if it accepts all media types of the group's
+/// [`RequiredMediaTypesExt`].
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode, Default)]
+pub struct AcceptedMediaTypesExt {
+    pub media_types: Vec<MediaType>,
+}
+
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl AcceptedMediaTypesExt {
+    pub fn new(media_types: Vec<MediaType>) -> Self {
+        Self { media_types }
+    }
+
+    /// Determine if `media_type` is accepted.
+    pub fn accepts(&self, media_type: &MediaType) -> bool {
+        self.media_types.contains(media_type)
+    }
+}
+
+#[cfg(feature = "content_advertisement")]
+impl MlsCodecExtension for AcceptedMediaTypesExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(ACCEPTED_MEDIA_TYPES_EXTENSION_TYPE)
+    }
+}
+
+/// Media types of application content that every member of a group must
+/// accept, as defined by the content advertisement of
+/// [draft-ietf-mls-extensions](https://datatracker.ietf.org/doc/draft-ietf-mls-extensions/).
+///
+/// Stored within the group context extensions. Every member of the group
+/// MUST list these media types in its [`AcceptedMediaTypesExt`].
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode, Default)]
+pub struct RequiredMediaTypesExt {
+    pub media_types: Vec<MediaType>,
+}
+
+#[cfg(feature = "content_advertisement")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl RequiredMediaTypesExt {
+    pub fn new(media_types: Vec<MediaType>) -> Self {
+        Self { media_types }
+    }
+}
+
+#[cfg(feature = "content_advertisement")]
+impl MlsCodecExtension for RequiredMediaTypesExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(REQUIRED_MEDIA_TYPES_EXTENSION_TYPE)
+    }
+}
+
+/// Proof that the signature key of a member is published in a key
+/// transparency log, such as an inclusion proof.
+///
+/// Stored within the `leaf_node_extensions` of a group [Member](crate::group::Member).
+/// The proof is opaque to the library and verified by a
+/// [`KeyTransparency`](crate::identity::transparency::KeyTransparency) log.
+#[cfg(feature = "key_transparency")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct KeyTransparencyProofExt {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub proof: Vec<u8>,
+}
+
+#[cfg(feature = "key_transparency")]
+impl Debug for KeyTransparencyProofExt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyTransparencyProofExt")
+            .field("proof", &mls_rs_core::debug::pretty_bytes(&self.proof))
+            .finish()
+    }
+}
+
+#[cfg(feature = "key_transparency")]
+#[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
+impl KeyTransparencyProofExt {
+    pub fn new(proof: Vec<u8>) -> Self {
+        Self { proof }
+    }
+}
+
+#[cfg(feature = "key_transparency")]
+impl MlsCodecExtension for KeyTransparencyProofExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(KEY_TRANSPARENCY_PROOF_EXTENSION_TYPE)
+    }
+}
+
+/// Ratchet tree compressed with deflate.
+///
+/// Alternative to [`RatchetTreeExt`] within group info messages, reducing
+/// their size in large groups. New members decompress the tree when joining.
+#[cfg(feature = "tree_compression")]
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct CompressedRatchetTreeExt {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub compressed_tree: Vec<u8>,
+}
+
+#[cfg(feature = "tree_compression")]
+impl Debug for CompressedRatchetTreeExt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressedRatchetTreeExt")
+            .field(
+                "compressed_tree",
+                &mls_rs_core::debug::pretty_bytes(&self.compressed_tree),
+            )
+            .finish()
+    }
+}
+
+#[cfg(feature = "tree_compression")]
+impl CompressedRatchetTreeExt {
+    pub fn new(tree: &ExportedTree<'_>) -> Result<Self, crate::client::MlsError> {
+        Ok(Self {
+            compressed_tree: tree.to_compressed_bytes()?,
+        })
+    }
+
+    /// Decompress the ratchet tree.
+    pub fn tree(&self) -> Result<ExportedTree<'static>, crate::client::MlsError> {
+        ExportedTree::from_compressed_bytes(&self.compressed_tree)
+    }
+}
+
+#[cfg(feature = "tree_compression")]
+impl MlsCodecExtension for CompressedRatchetTreeExt {
+    fn extension_type() -> ExtensionType {
+        ExtensionType::new(COMPRESSED_RATCHET_TREE_EXTENSION_TYPE)
+    }
+}
+
+/// Extension type of [`GroupFeaturesExt`], taken from the private use range.
+#[cfg(feature = "group_features")]
+pub const GROUP_FEATURES_EXTENSION_TYPE: ExtensionType = ExtensionType::new(0xF0A0);
+
+/// Extension type of [`SupportedFeaturesExt`], taken from the private use range.
+#[cfg(feature = "group_features")]
+pub const SUPPORTED_FEATURES_EXTENSION_TYPE: ExtensionType = ExtensionType::new(0xF0A1);
+
+/// Extension type of [`JoinTicketExt`], taken from the private use range.
+pub const JOIN_TICKET_EXTENSION_TYPE: u16 = 0xF0A2;
+
+/// Extension type of [`JoinTicketRequiredExt`], taken from the private use range.
+pub const JOIN_TICKET_REQUIRED_EXTENSION_TYPE: u16 = 0xF0A3;
+
+/// Extension type of [`CommitReasonExt`], taken from the private use range.
+pub const COMMIT_REASON_EXTENSION_TYPE: u16 = 0xF0A4;
+
+/// Extension type of [`CoSignersExt`], taken from the private use range.
+#[cfg(feature = "co_signed_commit")]
+pub const CO_SIGNERS_EXTENSION_TYPE: u16 = 0xF0A5;
+
+/// Extension type of [`DeviceAttestationExt`], taken from the private use range.
+#[cfg(feature = "device_attestation")]
+pub const DEVICE_ATTESTATION_EXTENSION_TYPE: u16 = 0xF0A6;
+
+/// Extension type of [`DeviceAttestationRequiredExt`], taken from the private use range.
+#[cfg(feature = "device_attestation")]
+pub const DEVICE_ATTESTATION_REQUIRED_EXTENSION_TYPE: u16 = 0xF0A7;
+
+/// Extension type of [`RatchetTreeLocationExt`], taken from the private use range.
+#[cfg(feature = "tree_fetcher")]
+pub const RATCHET_TREE_LOCATION_EXTENSION_TYPE: u16 = 0xF0A8;
+
+/// Extension type of [`MemberExpiryExt`], taken from the private use range.
+#[cfg(feature = "member_expiry")]
+pub const MEMBER_EXPIRY_EXTENSION_TYPE: u16 = 0xF0A9;
+
+/// Extension type of [`HiddenMembersExt`], taken from the private use range.
+#[cfg(feature = "hidden_members")]
+pub const HIDDEN_MEMBERS_EXTENSION_TYPE: u16 = 0xF0AA;
+
+/// Extension type of [`MemberResetExt`], taken from the private use range.
+#[cfg(feature = "member_reset")]
+pub const MEMBER_RESET_EXTENSION_TYPE: u16 = 0xF0AB;
+
+/// Extension type of [`ShadowMigrationExt`], taken from the private use range.
+#[cfg(feature = "shadow_migration")]
+pub const SHADOW_MIGRATION_EXTENSION_TYPE: u16 = 0xF0AC;
+
+/// Extension type of [`AcceptedMediaTypesExt`], taken from the private use range.
+#[cfg(feature = "content_advertisement")]
+pub const ACCEPTED_MEDIA_TYPES_EXTENSION_TYPE: u16 = 0xF0AD;
+
+/// Extension type of [`RequiredMediaTypesExt`], taken from the private use range.
+#[cfg(feature = "content_advertisement")]
+pub const REQUIRED_MEDIA_TYPES_EXTENSION_TYPE: u16 = 0xF0AE;
+
+/// Extension type of [`KeyTransparencyProofExt`], taken from the private use range.
+#[cfg(feature = "key_transparency")]
+pub const KEY_TRANSPARENCY_PROOF_EXTENSION_TYPE: u16 = 0xF0AF;
+
+/// Extension type of [`CompressedRatchetTreeExt`], taken from the private use range.
+#[cfg(feature = "tree_compression")]
+pub const COMPRESSED_RATCHET_TREE_EXTENSION_TYPE: u16 = 0xF0B0;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,4 +1107,94 @@ mod tests {
         let restored = ExternalPubExt::from_extension(&as_extension).unwrap();
         assert_eq!(ext, restored)
     }
+
+    #[cfg(feature = "group_features")]
+    #[test]
+    fn test_group_features_extension() {
+        let test_extension = GroupFeaturesExt::new(vec![1, 7]);
+
+        let as_extension = test_extension.clone().into_extension().unwrap();
+
+        assert_eq!(as_extension.extension_type, GROUP_FEATURES_EXTENSION_TYPE);
+
+        let restored = GroupFeaturesExt::from_extension(&as_extension).unwrap();
+        assert_eq!(restored, test_extension);
+        assert!(restored.is_enabled(7));
+        assert!(!restored.is_enabled(2));
+    }
+
+    #[cfg(feature = "group_features")]
+    #[test]
+    fn test_supported_features_extension() {
+        let test_extension = SupportedFeaturesExt::new(vec![3]);
+
+        let as_extension = test_extension.clone().into_extension().unwrap();
+
+        assert_eq!(
+            as_extension.extension_type,
+            SUPPORTED_FEATURES_EXTENSION_TYPE
+        );
+
+        assert_eq!(
+            SupportedFeaturesExt::from_extension(&as_extension).unwrap(),
+            test_extension
+        );
+    }
+
+    #[cfg(feature = "co_signed_commit")]
+    #[test]
+    fn test_co_signers_extension() {
+        let test_extension = CoSignersExt::new(2, vec![0, 3, 5]);
+
+        let as_extension = test_extension.clone().into_extension().unwrap();
+
+        assert_eq!(
+            as_extension.extension_type,
+            ExtensionType::new(CO_SIGNERS_EXTENSION_TYPE)
+        );
+
+        let restored = CoSignersExt::from_extension(&as_extension).unwrap();
+        assert_eq!(restored, test_extension);
+        assert!(restored.is_signer(3));
+        assert!(!restored.is_signer(1));
+    }
+
+    #[cfg(feature = "content_advertisement")]
+    #[test]
+    fn test_accepted_media_types_extension() {
+        let test_extension = AcceptedMediaTypesExt::new(vec![
+            MediaType::new("text/markdown"),
+            MediaType::new("text/plain;charset=utf-8"),
+        ]);
+
+        let as_extension = test_extension.clone().into_extension().unwrap();
+
+        assert_eq!(
+            as_extension.extension_type,
+            ExtensionType::new(ACCEPTED_MEDIA_TYPES_EXTENSION_TYPE)
+        );
+
+        let restored = AcceptedMediaTypesExt::from_extension(&as_extension).unwrap();
+        assert_eq!(restored, test_extension);
+        assert!(restored.accepts(&MediaType::new("text/markdown")));
+        assert!(!restored.accepts(&MediaType::new("image/png")));
+    }
+
+    #[cfg(feature = "tree_fetcher")]
+    #[test]
+    fn test_ratchet_tree_location_extension() {
+        let test_extension = RatchetTreeLocationExt::new(b"https://cdn.example/tree".to_vec());
+
+        let as_extension = test_extension.clone().into_extension().unwrap();
+
+        assert_eq!(
+            as_extension.extension_type,
+            ExtensionType::new(RATCHET_TREE_LOCATION_EXTENSION_TYPE)
+        );
+
+        assert_eq!(
+            RatchetTreeLocationExt::from_extension(&as_extension).unwrap(),
+            test_extension
+        );
+    }
 }
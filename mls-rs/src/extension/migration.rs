@@ -0,0 +1,223 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{
+    error::IntoAnyError,
+    extension::{Extension, ExtensionError, ExtensionList, ExtensionType, MlsExtension},
+};
+
+use crate::{client::MlsError, extension::RequiredCapabilitiesExt, tree_kem::Capabilities};
+
+/// Translation of the data of a deprecated extension into the data of its
+/// replacement.
+pub type ExtensionTranslation = fn(&[u8]) -> Result<Vec<u8>, ExtensionError>;
+
+/// Replacement of a deprecated extension type by a new extension type.
+///
+/// Migrations are registered with
+/// [`ClientBuilder::extension_migration`](crate::client_builder::ClientBuilder::extension_migration).
+/// Every group context extensions proposal created by the client replaces
+/// the deprecated extension by its replacement as soon as all members of the
+/// group support the replacement. The deprecated type is also replaced in the
+/// [`RequiredCapabilitiesExt`].
+#[derive(Clone, Debug)]
+pub struct ExtensionMigration {
+    deprecated: ExtensionType,
+    replacement: ExtensionType,
+    translation: ExtensionTranslation,
+}
+
+impl ExtensionMigration {
+    /// Replace `deprecated` by `replacement`, keeping the extension data
+    /// unchanged.
+    pub fn new(deprecated: ExtensionType, replacement: ExtensionType) -> Self {
+        Self {
+            deprecated,
+            replacement,
+            translation: |data| Ok(data.to_vec()),
+        }
+    }
+
+    /// Replace the extension `Old` by the extension `New` created from it.
+    pub fn between<Old, New>() -> Self
+    where
+        Old: MlsExtension,
+        New: MlsExtension + From<Old>,
+    {
+        Self {
+            deprecated: Old::extension_type(),
+            replacement: New::extension_type(),
+            translation: translate::<Old, New>,
+        }
+    }
+
+    /// Translate the extension data with `translation` instead of copying
+    /// it.
+    pub fn with_translation(self, translation: ExtensionTranslation) -> Self {
+        Self {
+            translation,
+            ..self
+        }
+    }
+
+    /// Deprecated extension type.
+    pub fn deprecated(&self) -> ExtensionType {
+        self.deprecated
+    }
+
+    /// Extension type replacing the deprecated one.
+    pub fn replacement(&self) -> ExtensionType {
+        self.replacement
+    }
+
+    fn apply(
+        &self,
+        extensions: &mut ExtensionList,
+        members: &[&Capabilities],
+    ) -> Result<(), MlsError> {
+        let Some(deprecated) = extensions.get(self.deprecated) else {
+            return Ok(());
+        };
+
+        let supported = self.replacement.is_default()
+            || members
+                .iter()
+                .all(|capabilities| capabilities.extensions.contains(&self.replacement));
+
+        if !supported || extensions.has_extension(self.replacement) {
+            return Ok(());
+        }
+
+        let data = (self.translation)(&deprecated.extension_data)?;
+
+        extensions.remove(self.deprecated);
+        extensions.set(Extension::new(self.replacement, data));
+
+        if let Some(mut required) = extensions.get_as::<RequiredCapabilitiesExt>()? {
+            required.extensions.retain(|t| *t != self.replacement);
+
+            for extension_type in required.extensions.iter_mut() {
+                if *extension_type == self.deprecated {
+                    *extension_type = self.replacement;
+                }
+            }
+
+            extensions.set_from(required)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn translate<Old, New>(data: &[u8]) -> Result<Vec<u8>, ExtensionError>
+where
+    Old: MlsExtension,
+    New: MlsExtension + From<Old>,
+{
+    let old = Old::from_bytes(data)
+        .map_err(|e| ExtensionError::DeserializationError(e.into_any_error()))?;
+
+    New::from(old)
+        .to_bytes()
+        .map_err(|e| ExtensionError::SerializationError(e.into_any_error()))
+}
+
+/// Apply `migrations` in order to the group context `extensions` of a group
+/// whose members have the `members` capabilities.
+pub(crate) fn migrate_extensions(
+    migrations: &[ExtensionMigration],
+    mut extensions: ExtensionList,
+    members: &[&Capabilities],
+) -> Result<ExtensionList, MlsError> {
+    for migration in migrations {
+        migration.apply(&mut extensions, members)?;
+    }
+
+    Ok(extensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+    use mls_rs_core::extension::{ExtensionList, ExtensionType, MlsCodecExtension};
+
+    use crate::{extension::RequiredCapabilitiesExt, tree_kem::Capabilities};
+
+    use super::{migrate_extensions, ExtensionMigration};
+
+    #[derive(Debug, Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+    struct OldExtension(u8);
+
+    #[derive(Debug, Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+    struct NewExtension(u16);
+
+    impl MlsCodecExtension for OldExtension {
+        fn extension_type() -> ExtensionType {
+            ExtensionType::new(0xFF00)
+        }
+    }
+
+    impl MlsCodecExtension for NewExtension {
+        fn extension_type() -> ExtensionType {
+            ExtensionType::new(0xFF01)
+        }
+    }
+
+    impl From<OldExtension> for NewExtension {
+        fn from(old: OldExtension) -> Self {
+            NewExtension(old.0.into())
+        }
+    }
+
+    fn capabilities(extensions: &[u16]) -> Capabilities {
+        Capabilities {
+            extensions: extensions.iter().copied().map(ExtensionType::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extension_is_migrated_when_all_members_support_replacement() {
+        let migration = ExtensionMigration::between::<OldExtension, NewExtension>();
+
+        let mut extensions = ExtensionList::new();
+        extensions.set_from(OldExtension(7)).unwrap();
+
+        extensions
+            .set_from(RequiredCapabilitiesExt {
+                extensions: vec![ExtensionType::new(0xFF00)],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let (alice, bob) = (capabilities(&[0xFF00, 0xFF01]), capabilities(&[0xFF00]));
+
+        let unchanged =
+            migrate_extensions(&[migration.clone()], extensions.clone(), &[&alice, &bob]).unwrap();
+
+        assert_eq!(unchanged, extensions);
+
+        let bob = capabilities(&[0xFF01]);
+
+        let migrated = migrate_extensions(&[migration], extensions, &[&alice, &bob]).unwrap();
+
+        assert!(!migrated.has_extension(ExtensionType::new(0xFF00)));
+
+        assert_eq!(
+            migrated.get_as::<NewExtension>().unwrap(),
+            Some(NewExtension(7))
+        );
+
+        assert_eq!(
+            migrated
+                .get_as::<RequiredCapabilitiesExt>()
+                .unwrap()
+                .unwrap()
+                .extensions,
+            [ExtensionType::new(0xFF01)]
+        );
+    }
+}
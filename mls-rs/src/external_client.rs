@@ -4,14 +4,24 @@
 
 use crate::{
     client::MlsError,
-    group::{framing::MlsMessage, message_processor::validate_key_package, ExportedTree},
+    group::{
+        framing::MlsMessage, message_processor::validate_key_package, ExportedTree, GroupContext,
+    },
     KeyPackage,
 };
 
 pub mod builder;
+#[cfg(feature = "commit_validation")]
+mod commit_validation;
 mod config;
 mod group;
 
+#[cfg(feature = "key_package_intake")]
+mod intake;
+
+#[cfg(feature = "external_group_storage")]
+mod storage;
+
 pub(crate) use config::ExternalClientConfig;
 use mls_rs_core::{
     crypto::{CryptoProvider, SignatureSecretKey},
@@ -20,8 +30,17 @@ use mls_rs_core::{
 
 use builder::{ExternalBaseConfig, ExternalClientBuilder};
 
+pub use crate::client::EnvelopeRejection;
 pub use group::{ExternalGroup, ExternalReceivedMessage, ExternalSnapshot};
 
+#[cfg(feature = "commit_validation")]
+pub use commit_validation::{CommitRejection, CommitRejectionKind, CommitVerdict};
+
+#[cfg(feature = "key_package_intake")]
+pub use intake::{
+    AcceptedKeyPackage, IntakeOutcome, IntakePolicy, IntakeRejection, KeyPackageIntake,
+};
+
 /// A client capable of observing a group's state without having
 /// private keys required to read content.
 ///
@@ -86,6 +105,34 @@ where
         .await
     }
 
+    /// Begin observing a group based on its current `context` and `tree_data`
+    /// instead of a GroupInfo message.
+    ///
+    /// This allows observing groups that were created before an external
+    /// client was involved, with the cooperation of a member providing
+    /// [Group::context](crate::group::Group::context),
+    /// [Group::export_tree](crate::group::Group::export_tree) and
+    /// [Group::confirmation_tag](crate::group::Group::confirmation_tag).
+    /// The tree is validated against the tree hash in `context`, but unlike
+    /// a GroupInfo message, the parts are not signed by a member, so they must
+    /// be obtained from a trusted source.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn observe_from_parts(
+        &self,
+        context: GroupContext,
+        tree_data: ExportedTree<'_>,
+        confirmation_tag: &[u8],
+    ) -> Result<ExternalGroup<C>, MlsError> {
+        ExternalGroup::from_parts(
+            self.config.clone(),
+            self.signing_data.clone(),
+            context,
+            tree_data,
+            confirmation_tag,
+        )
+        .await
+    }
+
     /// Load an existing observed group by loading a snapshot that was
     /// generated by
     /// [ExternalGroup::snapshot](self::ExternalGroup::snapshot).
@@ -120,6 +167,13 @@ where
 
         Ok(key_package)
     }
+
+    /// Create a [`KeyPackageIntake`] validating uploaded key packages
+    /// according to `policy`.
+    #[cfg(feature = "key_package_intake")]
+    pub fn key_package_intake(&self, policy: IntakePolicy) -> KeyPackageIntake<C> {
+        KeyPackageIntake::new(self.config.clone(), policy)
+    }
 }
 
 #[cfg(test)]
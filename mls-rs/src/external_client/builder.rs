@@ -270,6 +270,9 @@ impl<C: IntoConfig> ExternalClientBuilder<C> {
     }
 
     /// Set the signature secret key used by the client to send external proposals.
+    ///
+    /// `signer` must correspond to the signature key of `signing_identity`, otherwise
+    /// observing a group fails with [`MlsError::SignerIdentityMismatch`](crate::client::MlsError::SignerIdentityMismatch).
     pub fn signer(
         self,
         signer: SignatureSecretKey,
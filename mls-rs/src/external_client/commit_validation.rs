@@ -0,0 +1,306 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{
+    client::MlsError,
+    external_client::{ExternalClientConfig, ExternalGroup, ExternalReceivedMessage},
+    group::{
+        framing::{Content, MlsMessagePayload},
+        message_processor::CommitMessageDescription,
+    },
+    MlsMessage,
+};
+
+/// Category of the check failed by a commit rejected by
+/// [`ExternalGroup::validate_commit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommitRejectionKind {
+    /// The message is not a commit sent as a public message. Encrypted
+    /// commits can't be validated by an external group.
+    NotPublicCommit,
+    /// The commit was sent to a different group, protocol version or epoch.
+    Misdirected,
+    /// The signature or membership tag of the commit is invalid.
+    InvalidSignature,
+    /// A proposal covered by the commit is invalid, or the proposals can't
+    /// be committed together.
+    InvalidProposals,
+    /// A proposal removes or updates a leaf that is not a member.
+    InvalidMembership,
+    /// The update path is missing or invalid, e.g. its leaf node has an
+    /// invalid signature, lifetime or capabilities.
+    InvalidPath,
+    /// The parent hashes or the tree hash of the new tree are invalid.
+    InvalidParentHash,
+    /// The identity provider rejected a credential of the commit.
+    InvalidIdentity,
+    /// The [MlsRules](crate::MlsRules) of the external client rejected the
+    /// commit.
+    RejectedByRules,
+    /// Any other protocol violation.
+    Other,
+}
+
+impl CommitRejectionKind {
+    // Returns `None` for errors that are not caused by the commit, e.g.
+    // failures of the crypto provider.
+    fn from_error(error: &MlsError) -> Option<Self> {
+        let kind = match error {
+            MlsError::CryptoProviderError(_)
+            | MlsError::GroupStorageError(_)
+            | MlsError::KeyPackageRepoError(_)
+            | MlsError::PskStoreError(_)
+            | MlsError::TreeFetcherError(_)
+            | MlsError::DeliveryServiceError(_) => return None,
+            MlsError::UnexpectedMessageType | MlsError::UnexpectedContentType(_) => {
+                Self::NotPublicCommit
+            }
+            MlsError::InvalidEpoch
+            | MlsError::StateDesync(_)
+            | MlsError::GroupIdMismatch
+            | MlsError::ProtocolVersionMismatch => Self::Misdirected,
+            MlsError::InvalidSignature
+            | MlsError::InvalidMembershipTag
+            | MlsError::MembershipTagForNonMember => Self::InvalidSignature,
+            MlsError::RemovingNonExistingMember
+            | MlsError::UpdatingNonExistingMember
+            | MlsError::LeafNotFound(_)
+            | MlsError::CommitterSelfRemoval => Self::InvalidMembership,
+            MlsError::CommitMissingPath
+            | MlsError::WrongPathLen
+            | MlsError::PubKeyMismatch
+            | MlsError::InvalidLeafNodeSource
+            | MlsError::InvalidLifetime
+            | MlsError::DifferentIdentityInUpdate(_)
+            | MlsError::SameHpkeKey(_)
+            | MlsError::DuplicateLeafData(_)
+            | MlsError::InvalidCommitSelfUpdate => Self::InvalidPath,
+            MlsError::ParentHashMismatch
+            | MlsError::UnmergedLeavesMismatch
+            | MlsError::TreeHashMismatch => Self::InvalidParentHash,
+            MlsError::ProposalNotFound
+            | MlsError::MoreThanOneProposalForLeaf(_)
+            | MlsError::MoreThanOneGroupContextExtensionsProposal
+            | MlsError::InvalidProposalTypeForSender
+            | MlsError::ExternalCommitMustHaveExactlyOneExternalInit
+            | MlsError::ExternalCommitMustHaveNewLeaf
+            | MlsError::ExternalCommitRemovesOtherIdentity
+            | MlsError::ExternalCommitWithMoreThanOneRemove
+            | MlsError::DuplicatePskIds
+            | MlsError::InvalidProposalTypeInExternalCommit(_)
+            | MlsError::OnlyMembersCanCommitProposalsByRef
+            | MlsError::OtherProposalWithReInit
+            | MlsError::UnsupportedGroupExtension(_)
+            | MlsError::UnsupportedCustomProposal(_)
+            | MlsError::InvalidTypeOrUsageInPreSharedKeyProposal
+            | MlsError::InvalidPskNonceLength
+            | MlsError::InvalidProtocolVersionInReInit
+            | MlsError::CipherSuiteDowngrade(..)
+            | MlsError::InvalidInitKey
+            | MlsError::InitLeafKeyEquality
+            | MlsError::RequiredExtensionNotFound(_)
+            | MlsError::RequiredProposalNotFound(_)
+            | MlsError::RequiredCredentialNotFound(_)
+            | MlsError::RequiredCapabilitiesNotSupported(_)
+            | MlsError::ExtensionNotInCapabilities(_)
+            | MlsError::InUseCredentialTypeUnsupportedByNewLeaf
+            | MlsError::CredentialTypeOfNewLeafIsUnsupported => Self::InvalidProposals,
+            MlsError::IdentityProviderError(_) => Self::InvalidIdentity,
+            MlsError::MlsRulesError(_) => Self::RejectedByRules,
+            _ => Self::Other,
+        };
+
+        Some(kind)
+    }
+}
+
+/// Commit rejected by [`ExternalGroup::validate_commit`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CommitRejection {
+    /// Category of the failed check.
+    pub kind: CommitRejectionKind,
+    /// Error returned when processing the commit.
+    pub error: MlsError,
+}
+
+/// Result of [`ExternalGroup::validate_commit`].
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum CommitVerdict {
+    /// The commit is valid and results in the described state update.
+    Valid(CommitMessageDescription),
+    /// The commit is invalid and should not be delivered.
+    Rejected(CommitRejection),
+}
+
+impl CommitVerdict {
+    /// True if the commit is valid.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid(_))
+    }
+
+    fn rejected(kind: CommitRejectionKind, error: MlsError) -> Self {
+        Self::Rejected(CommitRejection { kind, error })
+    }
+}
+
+impl<C> ExternalGroup<C>
+where
+    C: ExternalClientConfig + Clone,
+{
+    /// Validate a commit without applying it to the group.
+    ///
+    /// The commit goes through all checks performed by
+    /// [`ExternalGroup::process_incoming_message`], including the validity
+    /// of the proposals, the membership of removed and updated leaves, and
+    /// the signatures and parent hashes of the update path. A delivery
+    /// service can use the verdict to drop invalid commits before fanning
+    /// them out, and process valid commits once they are accepted.
+    ///
+    /// The checks run on a copy of the group state, which shares the
+    /// ratchet tree with this group until the commit modifies it. Errors
+    /// that are not caused by the commit, e.g. failures of the crypto
+    /// provider, are returned as `Err`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn validate_commit(&self, message: &MlsMessage) -> Result<CommitVerdict, MlsError> {
+        let is_public_commit = matches!(
+            &message.payload,
+            MlsMessagePayload::Plain(plaintext)
+                if matches!(plaintext.content.content, Content::Commit(_))
+        );
+
+        if !is_public_commit {
+            return Ok(CommitVerdict::rejected(
+                CommitRejectionKind::NotPublicCommit,
+                MlsError::UnexpectedMessageType,
+            ));
+        }
+
+        // The derived Clone also requires the crypto provider to be Clone,
+        // which is not needed to copy the group.
+        let mut scratch = ExternalGroup {
+            config: self.config.clone(),
+            cipher_suite_provider: self.cipher_suite_provider.clone(),
+            state: self.state.clone(),
+            signing_data: self.signing_data.clone(),
+        };
+
+        match scratch.process_incoming_message(message.clone()).await {
+            Ok(ExternalReceivedMessage::Commit(description)) => {
+                Ok(CommitVerdict::Valid(description))
+            }
+            Ok(_) => Ok(CommitVerdict::rejected(
+                CommitRejectionKind::NotPublicCommit,
+                MlsError::UnexpectedMessageType,
+            )),
+            Err(error) => match CommitRejectionKind::from_error(&error) {
+                Some(kind) => Ok(CommitVerdict::rejected(kind, error)),
+                None => Err(error),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        external_client::group::test_utils::make_external_group,
+        group::{
+            framing::{MlsMessagePayload, PublicMessage},
+            test_utils::test_group,
+        },
+    };
+
+    use super::{CommitRejection, CommitRejectionKind, CommitVerdict};
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn valid_commit_is_accepted_without_changing_state() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let commit = alice.group.commit(vec![]).await.unwrap();
+
+        let verdict = server
+            .validate_commit(&commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_matches!(verdict, CommitVerdict::Valid(ref d) if d.committer == 0);
+        assert_eq!(server.group_context().epoch, alice.group.current_epoch());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn tampered_commits_are_rejected() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+
+        let tampered = |f: fn(&mut PublicMessage)| {
+            let mut commit = commit.clone();
+
+            let MlsMessagePayload::Plain(plaintext) = &mut commit.payload else {
+                panic!("expected public message");
+            };
+
+            f(plaintext);
+            commit
+        };
+
+        let future_epoch = tampered(|plaintext| plaintext.content.epoch += 1);
+
+        let verdict = server.validate_commit(&future_epoch).await.unwrap();
+
+        assert_matches!(
+            verdict,
+            CommitVerdict::Rejected(CommitRejection {
+                kind: CommitRejectionKind::Misdirected,
+                error: MlsError::StateDesync(_),
+            })
+        );
+
+        let bad_signature = tampered(|plaintext| plaintext.auth.signature = vec![].into());
+
+        let verdict = server.validate_commit(&bad_signature).await.unwrap();
+
+        assert_matches!(
+            verdict,
+            CommitVerdict::Rejected(CommitRejection {
+                kind: CommitRejectionKind::InvalidSignature,
+                ..
+            })
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn non_commit_is_rejected() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let group_info = alice
+            .group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let verdict = server.validate_commit(&group_info).await.unwrap();
+
+        assert_matches!(
+            verdict,
+            CommitVerdict::Rejected(CommitRejection {
+                kind: CommitRejectionKind::NotPublicCommit,
+                ..
+            })
+        );
+    }
+}
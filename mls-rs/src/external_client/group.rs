@@ -4,7 +4,10 @@
 
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::{
-    crypto::SignatureSecretKey, error::IntoAnyError, extension::ExtensionList, group::Member,
+    crypto::{CipherSuiteProvider, SignatureSecretKey},
+    error::IntoAnyError,
+    extension::{ExtensionList, ExtensionType},
+    group::Member,
     identity::IdentityProvider,
 };
 
@@ -24,12 +27,13 @@ use crate::{
         snapshot::RawGroupState,
         state::GroupState,
         transcript_hash::InterimTranscriptHash,
-        validate_group_info_joiner, ContentType, ExportedTree, GroupContext, GroupInfo, Roster,
-        Welcome,
+        validate_group_info_joiner, validate_tree_joiner, ContentType, ExportedTree, GroupContext,
+        GroupInfo, Roster, Welcome,
     },
     identity::SigningIdentity,
     protocol_version::ProtocolVersion,
     psk::AlwaysFoundPskStorage,
+    signer::signer_matches_identity,
     tree_kem::{node::LeafIndex, path_secret::PathSecret, TreeKemPrivate},
     CryptoProvider, KeyPackage, MlsMessage,
 };
@@ -51,7 +55,7 @@ use crate::{
 use crate::group::proposal::CustomProposal;
 
 #[cfg(feature = "by_ref_proposal")]
-use mls_rs_core::{crypto::CipherSuiteProvider, psk::ExternalPskId};
+use mls_rs_core::psk::ExternalPskId;
 
 #[cfg(feature = "by_ref_proposal")]
 use crate::{
@@ -68,7 +72,17 @@ use crate::{
 };
 
 #[cfg(feature = "private_message")]
-use crate::group::framing::PrivateMessage;
+use crate::{client::EnvelopeRejection, group::framing::PrivateMessage};
+
+#[cfg(all(feature = "private_message", not(feature = "by_ref_proposal")))]
+use crate::group::framing::MlsMessagePayload;
+
+#[cfg(all(feature = "private_message", not(feature = "by_ref_proposal")))]
+use mls_rs_core::crypto::CipherSuiteProvider;
+
+// Encoded size of the sender data: leaf index, generation and reuse guard.
+#[cfg(feature = "private_message")]
+const SENDER_DATA_SIZE: usize = 12;
 
 use alloc::boxed::Box;
 
@@ -127,6 +141,8 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
             group_info.group_context.cipher_suite,
         )?;
 
+        verify_signing_data(&signing_data, &cipher_suite_provider).await?;
+
         let public_tree = validate_group_info_joiner(
             protocol_version,
             &group_info,
@@ -156,6 +172,55 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
         })
     }
 
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn from_parts(
+        config: C,
+        signing_data: Option<(SignatureSecretKey, SigningIdentity)>,
+        context: GroupContext,
+        tree_data: ExportedTree<'_>,
+        confirmation_tag: &[u8],
+    ) -> Result<Self, MlsError> {
+        if !config.version_supported(context.protocol_version) {
+            return Err(MlsError::UnsupportedProtocolVersion(
+                context.protocol_version,
+            ));
+        }
+
+        let cipher_suite_provider =
+            cipher_suite_provider(config.crypto_provider(), context.cipher_suite)?;
+
+        verify_signing_data(&signing_data, &cipher_suite_provider).await?;
+
+        let public_tree = validate_tree_joiner(
+            &context,
+            tree_data,
+            &config.identity_provider(),
+            &cipher_suite_provider,
+        )
+        .await?;
+
+        let confirmation_tag = ConfirmationTag::from(confirmation_tag.to_vec());
+
+        let interim_transcript_hash = InterimTranscriptHash::create(
+            &cipher_suite_provider,
+            &context.confirmed_transcript_hash,
+            &confirmation_tag,
+        )
+        .await?;
+
+        Ok(Self {
+            config,
+            signing_data,
+            state: GroupState::new(
+                context,
+                public_tree,
+                interim_transcript_hash,
+                confirmation_tag,
+            ),
+            cipher_suite_provider,
+        })
+    }
+
     /// Process a message that was sent to the group.
     ///
     /// * Proposals will be stored in the group state and processed by the
@@ -517,6 +582,91 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
         &self.group_state().context.tree_hash
     }
 
+    /// Check that `message` is well formed application data for the current
+    /// state of the group without decrypting it.
+    ///
+    /// The group id, protocol version and content type of the message must
+    /// match the group, the epoch must be neither in the future nor older
+    /// than the configured
+    /// [maximum epoch jitter](crate::external_client::builder::ExternalClientBuilder::max_epoch_jitter),
+    /// and the encrypted sender data and ciphertext must have lengths that
+    /// are possible for the cipher suite of the group.
+    ///
+    /// This allows a server to drop malformed or misdirected messages before
+    /// fanning them out. Passing this check does not mean that the message
+    /// is authentic, since it can only be verified by members of the group.
+    /// Rejected messages result in [`MlsError::InvalidMessageEnvelope`].
+    #[cfg(feature = "private_message")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_application_message_envelope(
+        &self,
+        message: &MlsMessage,
+    ) -> Result<(), MlsError> {
+        let context = self.group_context();
+
+        let reject = |reason| Err(MlsError::InvalidMessageEnvelope(reason));
+
+        let MlsMessagePayload::Cipher(ciphertext) = &message.payload else {
+            return reject(EnvelopeRejection::NotPrivateMessage);
+        };
+
+        if message.version != context.protocol_version {
+            return reject(EnvelopeRejection::ProtocolVersionMismatch);
+        }
+
+        if ciphertext.group_id != context.group_id {
+            return reject(EnvelopeRejection::GroupIdMismatch);
+        }
+
+        if ciphertext.content_type != ContentType::Application {
+            return reject(EnvelopeRejection::NotApplicationData);
+        }
+
+        if ciphertext.epoch > context.epoch {
+            return reject(EnvelopeRejection::FutureEpoch(ciphertext.epoch));
+        }
+
+        let too_old = self.config.max_epoch_jitter().map_or(false, |jitter| {
+            ciphertext.epoch < context.epoch.saturating_sub(jitter)
+        });
+
+        if too_old {
+            return reject(EnvelopeRejection::EpochTooOld(ciphertext.epoch));
+        }
+
+        let tag_size = self.aead_tag_size().await?;
+
+        let sender_data_len = ciphertext.encrypted_sender_data.len();
+
+        if sender_data_len != SENDER_DATA_SIZE + tag_size {
+            return reject(EnvelopeRejection::InvalidSenderDataLength(sender_data_len));
+        }
+
+        let ciphertext_len = ciphertext.ciphertext.len();
+
+        if ciphertext_len < tag_size {
+            return reject(EnvelopeRejection::InvalidCiphertextLength(ciphertext_len));
+        }
+
+        Ok(())
+    }
+
+    // The AEAD interface does not expose the size of the authentication tag,
+    // so it is measured by sealing an empty plaintext.
+    #[cfg(feature = "private_message")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn aead_tag_size(&self) -> Result<usize, MlsError> {
+        let cs = &self.cipher_suite_provider;
+        let key = alloc::vec![0u8; cs.aead_key_size()];
+        let nonce = alloc::vec![0u8; cs.aead_nonce_size()];
+
+        // Some providers reject empty plaintexts.
+        cs.aead_seal(&key, &[0], None, &nonce)
+            .await
+            .map(|sealed| sealed.len() - 1)
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+    }
+
     /// Find a member based on their identity.
     ///
     /// Identities are matched based on the
@@ -554,6 +704,24 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
     }
 }
 
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn verify_signing_data<P: CipherSuiteProvider>(
+    signing_data: &Option<(SignatureSecretKey, SigningIdentity)>,
+    cipher_suite_provider: &P,
+) -> Result<(), MlsError> {
+    let Some((signer, signing_identity)) = signing_data else {
+        return Ok(());
+    };
+
+    let matches = signer_matches_identity(cipher_suite_provider, signing_identity, signer).await?;
+
+    if !matches {
+        return Err(MlsError::SignerIdentityMismatch);
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
 #[cfg_attr(
@@ -621,6 +789,10 @@ where
         self.config.identity_provider()
     }
 
+    fn supported_extensions(&self) -> Vec<ExtensionType> {
+        self.config.supported_extensions()
+    }
+
     fn psk_storage(&self) -> Self::PreSharedKeyStorage {
         AlwaysFoundPskStorage
     }
@@ -816,6 +988,8 @@ mod tests {
         protocol_version::ProtocolVersion,
         ExtensionList, MlsMessage,
     };
+    #[cfg(feature = "private_message")]
+    use crate::{client::EnvelopeRejection, group::framing::PrivateMessage};
     use assert_matches::assert_matches;
     use mls_rs_codec::{MlsDecode, MlsEncode};
 
@@ -888,6 +1062,52 @@ mod tests {
         assert_eq!(alice.group.state, server.state);
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_can_be_created_from_parts() {
+        let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let mut server = TestExternalClientBuilder::new_for_test()
+            .build()
+            .observe_from_parts(
+                alice.group.context().clone(),
+                alice.group.export_tree(),
+                alice.group.confirmation_tag(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(alice.group.state, server.state);
+
+        let commit_output = alice.group.commit(Vec::new()).await.unwrap();
+        alice.group.apply_pending_commit().await.unwrap();
+
+        server
+            .process_incoming_message(commit_output.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(alice.group.state, server.state);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_from_parts_rejects_mismatched_tree() {
+        let alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let mut context = alice.group.context().clone();
+        context.tree_hash = vec![0; context.tree_hash.len()];
+
+        let res = TestExternalClientBuilder::new_for_test()
+            .build()
+            .observe_from_parts(
+                context,
+                alice.group.export_tree(),
+                alice.group.confirmation_tag(),
+            )
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::TreeHashMismatch));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn external_group_can_process_proposals_by_reference() {
         let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -1058,6 +1278,55 @@ mod tests {
         );
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_will_reject_mismatched_signer() {
+        let alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (server_identity, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"server").await;
+        let (_, other_key) = get_test_signing_identity(TEST_CIPHER_SUITE, b"other").await;
+
+        let group_info = alice
+            .group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let res = ExternalGroup::join(
+            TestExternalClientBuilder::new_for_test().build_config(),
+            Some((other_key, server_identity)),
+            group_info,
+            None,
+        )
+        .await
+        .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::SignerIdentityMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_accepts_matching_signer() {
+        let alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (server_identity, server_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, b"server").await;
+
+        let group_info = alice
+            .group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let res = ExternalGroup::join(
+            TestExternalClientBuilder::new_for_test().build_config(),
+            Some((server_key, server_identity)),
+            group_info,
+            None,
+        )
+        .await;
+
+        assert!(res.is_ok());
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn setup_extern_proposal_test(
@@ -1228,6 +1497,119 @@ mod tests {
         assert_matches!(res, Err(MlsError::InvalidEpoch));
     }
 
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn valid_application_message_envelope_is_accepted() {
+        let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        server
+            .verify_application_message_envelope(&message)
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn malformed_application_message_envelope_is_rejected() {
+        let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let modify = |f: fn(&mut PrivateMessage)| {
+            let mut message = message.clone();
+
+            match message.payload {
+                MlsMessagePayload::Cipher(ref mut ciphertext) => f(ciphertext),
+                _ => panic!("expected private message"),
+            }
+
+            message
+        };
+
+        let cases = [
+            (
+                modify(|m| m.group_id = b"other group".to_vec()),
+                EnvelopeRejection::GroupIdMismatch,
+            ),
+            (modify(|m| m.epoch += 1), EnvelopeRejection::FutureEpoch(2)),
+            (
+                modify(|m| {
+                    m.encrypted_sender_data.pop();
+                }),
+                EnvelopeRejection::InvalidSenderDataLength(27),
+            ),
+            (
+                modify(|m| m.ciphertext.truncate(3)),
+                EnvelopeRejection::InvalidCiphertextLength(3),
+            ),
+        ];
+
+        for (message, reason) in cases {
+            let res = server.verify_application_message_envelope(&message).await;
+            assert_matches!(res, Err(MlsError::InvalidMessageEnvelope(r)) if r == reason);
+        }
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        let res = server.verify_application_message_envelope(&commit).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::InvalidMessageEnvelope(
+                EnvelopeRejection::NotPrivateMessage
+            ))
+        );
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn old_application_message_envelope_is_rejected() {
+        let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let mut server = make_external_group_with_config(
+            &alice,
+            TestExternalClientBuilder::new_for_test()
+                .max_epoch_jitter(0)
+                .build_config(),
+        )
+        .await;
+
+        let old_message = alice
+            .group
+            .encrypt_application_message(&[], vec![])
+            .await
+            .unwrap();
+
+        let commit_output = alice.group.commit(vec![]).await.unwrap();
+
+        server
+            .process_incoming_message(commit_output.commit_message)
+            .await
+            .unwrap();
+
+        let res = server
+            .verify_application_message_envelope(&old_message)
+            .await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::InvalidMessageEnvelope(
+                EnvelopeRejection::EpochTooOld(1)
+            ))
+        );
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn proposals_can_be_cached_externally() {
         let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -0,0 +1,492 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use mls_rs_core::{
+    crypto::{CipherSuite, CryptoProvider},
+    error::IntoAnyError,
+    extension::ExtensionList,
+    identity::IdentityProvider,
+    protocol_version::ProtocolVersion,
+    time::MlsTime,
+};
+use std::collections::HashMap;
+
+#[cfg(all(not(mls_build_async), feature = "rayon"))]
+use rayon::prelude::*;
+
+use crate::{
+    client::MlsError, group::message_processor::validate_key_package, KeyPackage, KeyPackageRef,
+    MlsMessage,
+};
+
+use super::ExternalClientConfig;
+
+/// Limits applied by a [`KeyPackageIntake`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IntakePolicy {
+    /// Maximum number of key packages verified concurrently.
+    pub max_parallelism: usize,
+    /// Maximum number of key packages accepted for verification per identity
+    /// within [`IntakePolicy::rate_window`].
+    pub max_per_identity: usize,
+    /// Length of the rate limiting window in seconds.
+    pub rate_window: u64,
+    /// Maximum lifetime of a key package in seconds, if any.
+    pub max_lifetime: Option<u64>,
+    /// Accepted cipher suites. If `None`, all cipher suites supported by the
+    /// crypto provider are accepted.
+    pub cipher_suites: Option<Vec<CipherSuite>>,
+}
+
+impl Default for IntakePolicy {
+    fn default() -> Self {
+        Self {
+            max_parallelism: 16,
+            max_per_identity: 100,
+            rate_window: 3600,
+            max_lifetime: None,
+            cipher_suites: None,
+        }
+    }
+}
+
+impl IntakePolicy {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_max_parallelism(self, max_parallelism: usize) -> Self {
+        Self {
+            max_parallelism: max_parallelism.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_rate_limit(self, max_per_identity: usize, rate_window: u64) -> Self {
+        Self {
+            max_per_identity,
+            rate_window,
+            ..self
+        }
+    }
+
+    pub fn with_max_lifetime(self, max_lifetime: u64) -> Self {
+        Self {
+            max_lifetime: Some(max_lifetime),
+            ..self
+        }
+    }
+
+    pub fn with_cipher_suites(self, cipher_suites: Vec<CipherSuite>) -> Self {
+        Self {
+            cipher_suites: Some(cipher_suites),
+            ..self
+        }
+    }
+}
+
+/// Key package accepted by a [`KeyPackageIntake`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct AcceptedKeyPackage {
+    /// Identity of the owner of the key package, as reported by the
+    /// [`IdentityProvider`](crate::IdentityProvider) in use.
+    pub identity: Vec<u8>,
+    pub reference: KeyPackageRef,
+    pub key_package: KeyPackage,
+}
+
+/// Reason for rejecting a key package submitted to a [`KeyPackageIntake`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IntakeRejection {
+    /// The message does not contain a key package.
+    NotKeyPackage,
+    /// The cipher suite is not accepted by the policy or not supported by
+    /// the crypto provider.
+    UnsupportedCipherSuite(CipherSuite),
+    /// The key package is not valid at the time of submission.
+    Expired,
+    /// The lifetime of the key package, in seconds, exceeds the maximum
+    /// allowed by the policy.
+    LifetimeTooLong(u64),
+    /// Too many key packages were submitted for the identity within the
+    /// rate limiting window.
+    RateLimited(Vec<u8>),
+    /// The key package failed validation.
+    Invalid(MlsError),
+}
+
+/// Outcome of submitting a single key package to a [`KeyPackageIntake`].
+#[derive(Debug)]
+pub enum IntakeOutcome {
+    /// The key package passed all checks and can be stored for later use.
+    Accepted(Box<AcceptedKeyPackage>),
+    /// The key package was rejected and should not be stored.
+    Rejected(IntakeRejection),
+}
+
+impl IntakeOutcome {
+    /// Determine if the key package was accepted.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted(_))
+    }
+}
+
+struct Candidate {
+    position: usize,
+    version: ProtocolVersion,
+    identity: Vec<u8>,
+    key_package: KeyPackage,
+}
+
+/// Server side intake of uploaded key packages.
+///
+/// Cheap checks of the cipher suite, the lifetime and the per-identity rate
+/// limit run first, so that floods of key packages for one identity are
+/// rejected before their signatures are verified. The remaining key packages
+/// are then fully validated, up to [`IntakePolicy::max_parallelism`] at a
+/// time. Every key package passing the cheap checks counts towards the rate
+/// limit of its claimed identity, whether it is accepted or not.
+///
+/// An intake is created with
+/// [`ExternalClient::key_package_intake`](crate::external_client::ExternalClient::key_package_intake).
+pub struct KeyPackageIntake<C> {
+    config: C,
+    policy: IntakePolicy,
+    submissions: HashMap<Vec<u8>, VecDeque<u64>>,
+}
+
+impl<C> KeyPackageIntake<C>
+where
+    C: ExternalClientConfig + Clone,
+{
+    pub(crate) fn new(config: C, policy: IntakePolicy) -> Self {
+        Self {
+            config,
+            policy,
+            submissions: Default::default(),
+        }
+    }
+
+    pub fn policy(&self) -> &IntakePolicy {
+        &self.policy
+    }
+
+    /// Validate `messages` submitted at time `now`.
+    ///
+    /// The outcomes are returned in the order of `messages`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn submit(&mut self, messages: Vec<MlsMessage>, now: MlsTime) -> Vec<IntakeOutcome> {
+        let now = now.seconds_since_epoch();
+        self.expire_submissions(now);
+
+        let mut outcomes = Vec::with_capacity(messages.len());
+        let mut candidates = Vec::new();
+
+        for (position, message) in messages.into_iter().enumerate() {
+            match self.precheck(position, message, now).await {
+                Ok(candidate) => {
+                    candidates.push(candidate);
+                    outcomes.push(None);
+                }
+                Err(rejection) => outcomes.push(Some(IntakeOutcome::Rejected(rejection))),
+            }
+        }
+
+        let mut candidates = candidates.into_iter().peekable();
+
+        while candidates.peek().is_some() {
+            let chunk = candidates
+                .by_ref()
+                .take(self.policy.max_parallelism.max(1))
+                .collect::<Vec<_>>();
+
+            for (position, outcome) in verify_chunk(&self.config, chunk).await {
+                outcomes[position] = Some(outcome);
+            }
+        }
+
+        outcomes.into_iter().flatten().collect()
+    }
+
+    fn expire_submissions(&mut self, now: u64) {
+        let window_start = now.saturating_sub(self.policy.rate_window);
+
+        self.submissions.retain(|_, times| {
+            while times.front().map_or(false, |t| *t <= window_start) {
+                times.pop_front();
+            }
+
+            !times.is_empty()
+        });
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn precheck(
+        &mut self,
+        position: usize,
+        message: MlsMessage,
+        now: u64,
+    ) -> Result<Candidate, IntakeRejection> {
+        let version = message.version;
+
+        let key_package = message
+            .into_key_package()
+            .ok_or(IntakeRejection::NotKeyPackage)?;
+
+        let cipher_suite = key_package.cipher_suite;
+
+        let allowed = self
+            .policy
+            .cipher_suites
+            .as_ref()
+            .map_or(true, |suites| suites.contains(&cipher_suite));
+
+        if !allowed
+            || self
+                .config
+                .crypto_provider()
+                .cipher_suite_provider(cipher_suite)
+                .is_none()
+        {
+            return Err(IntakeRejection::UnsupportedCipherSuite(cipher_suite));
+        }
+
+        let lifetime = key_package.lifetime().map_err(IntakeRejection::Invalid)?;
+
+        if now < lifetime.not_before || now > lifetime.not_after {
+            return Err(IntakeRejection::Expired);
+        }
+
+        let duration = lifetime.not_after.saturating_sub(lifetime.not_before);
+
+        if self.policy.max_lifetime.map_or(false, |max| duration > max) {
+            return Err(IntakeRejection::LifetimeTooLong(duration));
+        }
+
+        let identity = self
+            .config
+            .identity_provider()
+            .identity(
+                &key_package.leaf_node.signing_identity,
+                &ExtensionList::new(),
+            )
+            .await
+            .map_err(|e| {
+                IntakeRejection::Invalid(MlsError::IdentityProviderError(e.into_any_error()))
+            })?;
+
+        let submissions = self.submissions.entry(identity.clone()).or_default();
+
+        if submissions.len() >= self.policy.max_per_identity {
+            return Err(IntakeRejection::RateLimited(identity));
+        }
+
+        submissions.push_back(now);
+
+        Ok(Candidate {
+            position,
+            version,
+            identity,
+            key_package,
+        })
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn verify<C: ExternalClientConfig>(config: &C, candidate: Candidate) -> IntakeOutcome {
+    match verify_key_package(config, &candidate).await {
+        Ok(reference) => IntakeOutcome::Accepted(Box::new(AcceptedKeyPackage {
+            identity: candidate.identity,
+            reference,
+            key_package: candidate.key_package,
+        })),
+        Err(e) => IntakeOutcome::Rejected(IntakeRejection::Invalid(e)),
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn verify_key_package<C: ExternalClientConfig>(
+    config: &C,
+    candidate: &Candidate,
+) -> Result<KeyPackageRef, MlsError> {
+    let key_package = &candidate.key_package;
+
+    let cs = config
+        .crypto_provider()
+        .cipher_suite_provider(key_package.cipher_suite)
+        .ok_or(MlsError::UnsupportedCipherSuite(key_package.cipher_suite))?;
+
+    validate_key_package(
+        key_package,
+        candidate.version,
+        &cs,
+        &config.identity_provider(),
+    )
+    .await?;
+
+    key_package.to_reference(&cs).await
+}
+
+#[cfg(mls_build_async)]
+async fn verify_chunk<C: ExternalClientConfig>(
+    config: &C,
+    chunk: Vec<Candidate>,
+) -> Vec<(usize, IntakeOutcome)> {
+    futures::future::join_all(chunk.into_iter().map(|candidate| async move {
+        let position = candidate.position;
+        (position, verify(config, candidate).await)
+    }))
+    .await
+}
+
+#[cfg(all(not(mls_build_async), feature = "rayon"))]
+fn verify_chunk<C: ExternalClientConfig>(
+    config: &C,
+    chunk: Vec<Candidate>,
+) -> Vec<(usize, IntakeOutcome)> {
+    chunk
+        .into_par_iter()
+        .map(|candidate| (candidate.position, verify(config, candidate)))
+        .collect()
+}
+
+#[cfg(not(any(mls_build_async, feature = "rayon")))]
+fn verify_chunk<C: ExternalClientConfig>(
+    config: &C,
+    chunk: Vec<Candidate>,
+) -> Vec<(usize, IntakeOutcome)> {
+    chunk
+        .into_iter()
+        .map(|candidate| (candidate.position, verify(config, candidate)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::time::MlsTime;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        external_client::tests_utils::TestExternalClientBuilder,
+        group::framing::MlsMessagePayload,
+        key_package::test_utils::test_key_package_message,
+        MlsMessage,
+    };
+
+    use super::{IntakeOutcome, IntakePolicy, IntakeRejection};
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn key_packages(name: &str, count: usize) -> Vec<MlsMessage> {
+        let mut key_packages = Vec::new();
+
+        for _ in 0..count {
+            key_packages.push(
+                test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, name).await,
+            );
+        }
+
+        key_packages
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn valid_key_packages_are_accepted_in_order() {
+        let server = TestExternalClientBuilder::new_for_test().build();
+        let mut intake = server.key_package_intake(IntakePolicy::new().with_max_parallelism(2));
+
+        let mut messages = key_packages("alice", 3).await;
+        messages.extend(key_packages("bob", 2).await);
+
+        let outcomes = intake.submit(messages.clone(), MlsTime::now()).await;
+
+        assert_eq!(outcomes.len(), messages.len());
+
+        for (outcome, message) in outcomes.into_iter().zip(messages) {
+            let IntakeOutcome::Accepted(accepted) = outcome else {
+                panic!("expected accepted key package");
+            };
+
+            assert_eq!(Some(accepted.key_package), message.into_key_package());
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn identities_are_rate_limited() {
+        let server = TestExternalClientBuilder::new_for_test().build();
+        let mut intake = server.key_package_intake(IntakePolicy::new().with_rate_limit(2, 60));
+
+        let now = MlsTime::now();
+        let outcomes = intake.submit(key_packages("alice", 3).await, now).await;
+
+        assert!(outcomes[0].is_accepted());
+        assert!(outcomes[1].is_accepted());
+
+        assert_matches!(
+            &outcomes[2],
+            IntakeOutcome::Rejected(IntakeRejection::RateLimited(identity)) if identity == b"alice"
+        );
+
+        let outcomes = intake.submit(key_packages("bob", 1).await, now).await;
+        assert!(outcomes[0].is_accepted());
+
+        let later = MlsTime::from(now.seconds_since_epoch() + 61);
+        let outcomes = intake.submit(key_packages("alice", 1).await, later).await;
+        assert!(outcomes[0].is_accepted());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn invalid_submissions_are_rejected() {
+        let server = TestExternalClientBuilder::new_for_test().build();
+        let mut intake = server.key_package_intake(IntakePolicy::new().with_max_lifetime(60));
+
+        let mut messages = key_packages("alice", 2).await;
+
+        if let MlsMessagePayload::KeyPackage(key_package) = &mut messages[1].payload {
+            key_package.signature[0] ^= 1;
+        }
+
+        let outcomes = intake.submit(messages, MlsTime::now()).await;
+
+        // The lifetime is checked before the signature.
+        for outcome in outcomes {
+            assert_matches!(
+                outcome,
+                IntakeOutcome::Rejected(IntakeRejection::LifetimeTooLong(_))
+            );
+        }
+
+        let mut intake = server.key_package_intake(IntakePolicy::new());
+        let mut messages = key_packages("alice", 1).await;
+
+        if let MlsMessagePayload::KeyPackage(key_package) = &mut messages[0].payload {
+            key_package.signature[0] ^= 1;
+        }
+
+        let outcomes = intake.submit(messages, MlsTime::now()).await;
+
+        assert_matches!(
+            outcomes[0],
+            IntakeOutcome::Rejected(IntakeRejection::Invalid(MlsError::InvalidSignature))
+        );
+
+        let mut intake = server.key_package_intake(IntakePolicy::new().with_cipher_suites(vec![]));
+
+        let outcomes = intake
+            .submit(key_packages("alice", 1).await, MlsTime::now())
+            .await;
+
+        assert_matches!(
+            outcomes[0],
+            IntakeOutcome::Rejected(IntakeRejection::UnsupportedCipherSuite(cs)) if cs == TEST_CIPHER_SUITE
+        );
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{
+    error::IntoAnyError,
+    group::{GroupState, GroupStateStorage},
+};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::MlsError,
+    external_client::{ExternalClient, ExternalClientConfig, ExternalGroup, ExternalSnapshot},
+};
+
+impl<C> ExternalGroup<C>
+where
+    C: ExternalClientConfig + Clone,
+{
+    /// Write the current state of the group to `storage`.
+    ///
+    /// The state is stored under the group id as an [`ExternalSnapshot`],
+    /// replacing any previously written state. External groups keep no
+    /// prior epochs, so no epoch records are written. The snapshot
+    /// contains the signer used to send external proposals, if any.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn write_to_storage<S: GroupStateStorage>(
+        &self,
+        storage: &mut S,
+    ) -> Result<(), MlsError> {
+        let state = GroupState {
+            id: self.group_context().group_id.clone(),
+            data: self.snapshot().to_bytes()?,
+        };
+
+        storage
+            .write(state, Vec::new(), Vec::new())
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))
+    }
+}
+
+impl<C> ExternalClient<C>
+where
+    C: ExternalClientConfig + Clone,
+{
+    /// Load an observed group written to `storage` by
+    /// [`ExternalGroup::write_to_storage`].
+    ///
+    /// Returns [`MlsError::GroupNotFound`] if `storage` contains no state
+    /// for `group_id`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn load_group_from_storage<S: GroupStateStorage>(
+        &self,
+        storage: &S,
+        group_id: &[u8],
+    ) -> Result<ExternalGroup<C>, MlsError> {
+        let state = storage
+            .state(group_id)
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+            .map(Zeroizing::new)
+            .ok_or(MlsError::GroupNotFound)?;
+
+        let snapshot = ExternalSnapshot::from_bytes(&state)?;
+
+        self.load_group(snapshot).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        external_client::{group::test_utils::make_external_group, tests_utils::*},
+        group::test_utils::test_group,
+        storage_provider::in_memory::InMemoryGroupStateStorage,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_survives_restart() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let server = make_external_group(&alice).await;
+
+        let mut storage = InMemoryGroupStateStorage::new();
+        server.write_to_storage(&mut storage).await.unwrap();
+
+        let restarted = TestExternalClientBuilder::new_for_test().build();
+
+        let mut loaded = restarted
+            .load_group_from_storage(&storage, alice.group.group_id())
+            .await
+            .unwrap();
+
+        assert_eq!(loaded.group_context(), server.group_context());
+
+        let commit = alice.group.commit(vec![]).await.unwrap();
+
+        loaded
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        loaded.write_to_storage(&mut storage).await.unwrap();
+
+        let reloaded = restarted
+            .load_group_from_storage(&storage, alice.group.group_id())
+            .await
+            .unwrap();
+
+        assert_eq!(reloaded.group_context().epoch, 1);
+
+        let res = restarted
+            .load_group_from_storage(&storage, b"unknown")
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::GroupNotFound));
+    }
+}
@@ -207,12 +207,12 @@ mod tests {
             .member_with_index(0)
             .unwrap();
 
-        assert!(!is_ext_greased(member.extensions()));
-        assert!(!is_greased(member.capabilities().protocol_versions()));
-        assert!(!is_greased(member.capabilities().cipher_suites()));
-        assert!(!is_greased(member.capabilities().extensions()));
-        assert!(!is_greased(member.capabilities().proposals()));
-        assert!(!is_greased(member.capabilities().credentials()));
+        assert!(!is_ext_greased(&member.extensions));
+        assert!(!is_greased(&member.capabilities.protocol_versions));
+        assert!(!is_greased(&member.capabilities.cipher_suites));
+        assert!(!is_greased(&member.capabilities.extensions));
+        assert!(!is_greased(&member.capabilities.proposals));
+        assert!(!is_greased(&member.capabilities.credentials));
     }
 
     fn is_greased<T: Deref<Target = u16>>(list: &[T]) -> bool {
@@ -222,6 +222,6 @@ mod tests {
     fn is_ext_greased(extensions: &ExtensionList) -> bool {
         extensions
             .iter()
-            .any(|ext| GREASE_VALUES.contains(&*ext.extension_type()))
+            .any(|ext| GREASE_VALUES.contains(&*ext.extension_type))
     }
 }
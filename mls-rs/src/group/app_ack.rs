@@ -0,0 +1,352 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Experimental acknowledgement of application messages with the AppAck
+//! proposal of
+//! [draft-ietf-mls-extensions](https://datatracker.ietf.org/doc/draft-ietf-mls-extensions/).
+//!
+//! Every member tracks the generations of the application messages it
+//! decrypted in the current epoch. A member acknowledges them by sending an
+//! [`AppAckProposal`] with [`Group::propose_app_ack`]. Acknowledgements
+//! received from other members can be queried with [`Group::app_acks`] and
+//! [`Group::acked_by`].
+//!
+//! Generations are only meaningful within an epoch, so received messages
+//! and acknowledgements are forgotten when the group moves to a new epoch.
+//! They are only kept in memory. All members of the group must support the
+//! [`APP_ACK_PROPOSAL_TYPE`] custom proposal type.
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::group::ProposalType;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        framing::{Content, FramedContent, Sender},
+        message_processor::ProposalMessageDescription,
+        proposal::{MlsCustomProposal, Proposal},
+        Group, ProposalSender,
+    },
+    MlsMessage,
+};
+
+/// Custom proposal type of [`AppAckProposal`], taken from the private use
+/// range.
+pub const APP_ACK_PROPOSAL_TYPE: u16 = 0xF0A1;
+
+/// Consecutive generations of the application messages of a sender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct MessageRange {
+    /// Leaf index of the sender of the messages.
+    pub sender: u32,
+    /// First generation of the range.
+    pub first_generation: u32,
+    /// Last generation of the range, inclusive.
+    pub last_generation: u32,
+}
+
+impl MessageRange {
+    /// True if the message of `sender` with generation `generation` is in
+    /// this range.
+    pub fn contains(&self, sender: u32, generation: u32) -> bool {
+        self.sender == sender
+            && self.first_generation <= generation
+            && generation <= self.last_generation
+    }
+}
+
+/// Acknowledgement of the application messages received by the sender of
+/// the proposal in the current epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct AppAckProposal {
+    /// Ranges of received messages.
+    pub received_ranges: Vec<MessageRange>,
+}
+
+impl MlsCustomProposal for AppAckProposal {
+    fn proposal_type() -> ProposalType {
+        ProposalType::new(APP_ACK_PROPOSAL_TYPE)
+    }
+}
+
+/// Acknowledgement of a range of application messages by a member.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AppAck {
+    /// Leaf index of the member that acknowledged the messages.
+    pub acked_by: u32,
+    /// Acknowledged messages.
+    pub range: MessageRange,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AppAckState {
+    epoch: u64,
+    received: Vec<MessageRange>,
+    acks: Vec<AppAck>,
+}
+
+impl AppAckState {
+    fn for_epoch(&mut self, epoch: u64) -> &mut Self {
+        if self.epoch != epoch {
+            *self = Self {
+                epoch,
+                ..Default::default()
+            };
+        }
+
+        self
+    }
+
+    pub(crate) fn record_received(&mut self, epoch: u64, content: &FramedContent, generation: u32) {
+        let (Content::Application(_), Sender::Member(sender)) = (&content.content, content.sender)
+        else {
+            return;
+        };
+
+        let received = &mut self.for_epoch(epoch).received;
+
+        let adjacent = received.iter_mut().find(|range| {
+            range.sender == sender
+                && range.first_generation <= generation.saturating_add(1)
+                && generation <= range.last_generation.saturating_add(1)
+        });
+
+        match adjacent {
+            Some(range) => {
+                range.first_generation = range.first_generation.min(generation);
+                range.last_generation = range.last_generation.max(generation);
+            }
+            None => received.push(MessageRange {
+                sender,
+                first_generation: generation,
+                last_generation: generation,
+            }),
+        }
+
+        // Extending a range can make it adjacent to another range of the
+        // same sender.
+        received.sort_unstable_by_key(|range| (range.sender, range.first_generation));
+
+        received.dedup_by(|next, range| {
+            let merge = next.sender == range.sender
+                && next.first_generation <= range.last_generation.saturating_add(1);
+
+            if merge {
+                range.last_generation = range.last_generation.max(next.last_generation);
+            }
+
+            merge
+        });
+    }
+
+    fn record_ack(&mut self, epoch: u64, acked_by: u32, proposal: &AppAckProposal) {
+        let acks = &mut self.for_epoch(epoch).acks;
+
+        acks.extend(
+            proposal
+                .received_ranges
+                .iter()
+                .map(|&range| AppAck { acked_by, range }),
+        );
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Acknowledgement of all application messages decrypted by this member
+    /// in the current epoch.
+    pub fn app_ack_proposal(&self) -> AppAckProposal {
+        let received_ranges = if self.app_acks.epoch == self.current_epoch() {
+            self.app_acks.received.clone()
+        } else {
+            Vec::new()
+        };
+
+        AppAckProposal { received_ranges }
+    }
+
+    /// Create a proposal message acknowledging all application messages
+    /// decrypted by this member in the current epoch.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_app_ack(
+        &mut self,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        let proposal = self.app_ack_proposal();
+        let message = self
+            .propose_custom(proposal.to_custom_proposal()?, authenticated_data)
+            .await?;
+
+        let (epoch, self_index) = (self.current_epoch(), self.current_member_index());
+        self.app_acks.record_ack(epoch, self_index, &proposal);
+
+        Ok(message)
+    }
+
+    /// Acknowledgements sent or received by this member in the current
+    /// epoch, in the order in which they were processed.
+    pub fn app_acks(&self) -> &[AppAck] {
+        if self.app_acks.epoch == self.current_epoch() {
+            &self.app_acks.acks
+        } else {
+            &[]
+        }
+    }
+
+    /// Leaf indices of the members that acknowledged the application
+    /// message of `sender` with generation `generation` in the current
+    /// epoch.
+    pub fn acked_by(&self, sender: u32, generation: u32) -> Vec<u32> {
+        let mut members = self
+            .app_acks()
+            .iter()
+            .filter(|ack| ack.range.contains(sender, generation))
+            .map(|ack| ack.acked_by)
+            .collect::<Vec<_>>();
+
+        members.sort_unstable();
+        members.dedup();
+        members
+    }
+
+    pub(crate) fn record_app_ack(&mut self, description: &ProposalMessageDescription) {
+        let (ProposalSender::Member(sender), Proposal::Custom(proposal)) =
+            (&description.sender, &description.proposal)
+        else {
+            return;
+        };
+
+        if proposal.proposal_type() != AppAckProposal::proposal_type() {
+            return;
+        }
+
+        // A malformed acknowledgement doesn't invalidate the proposal, which
+        // was already accepted by the rules of the group.
+        if let Ok(ack) = AppAckProposal::from_custom_proposal(proposal) {
+            let epoch = self.current_epoch();
+            self.app_acks.record_ack(epoch, *sender, &ack);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client_builder::test_utils::TestClientConfig,
+        group::{
+            framing::{Content, FramedContent, Sender},
+            test_utils::test_n_member_group,
+            Group,
+        },
+    };
+
+    use super::{AppAck, AppAckState, MessageRange};
+
+    fn range(sender: u32, first_generation: u32, last_generation: u32) -> MessageRange {
+        MessageRange {
+            sender,
+            first_generation,
+            last_generation,
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_groups() -> Vec<Group<TestClientConfig>> {
+        test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3)
+            .await
+            .into_iter()
+            .map(|g| g.group)
+            .collect()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn received_messages_are_acked() {
+        let mut groups = test_groups().await;
+
+        for _ in 0..3 {
+            let message = groups[0]
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+
+            groups[1].process_incoming_message(message).await.unwrap();
+        }
+
+        let message = groups[2]
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        groups[1].process_incoming_message(message).await.unwrap();
+
+        assert_eq!(
+            groups[1].app_ack_proposal().received_ranges,
+            [range(0, 0, 2), range(2, 0, 0)]
+        );
+
+        let proposal = groups[1].propose_app_ack(vec![]).await.unwrap();
+        groups[0].process_incoming_message(proposal).await.unwrap();
+
+        assert_eq!(groups[0].acked_by(0, 1), [1]);
+        assert!(groups[0].acked_by(0, 3).is_empty());
+
+        assert_eq!(
+            groups[0].app_acks(),
+            [
+                AppAck {
+                    acked_by: 1,
+                    range: range(0, 0, 2)
+                },
+                AppAck {
+                    acked_by: 1,
+                    range: range(2, 0, 0)
+                }
+            ]
+        );
+
+        assert_eq!(groups[1].app_acks(), groups[0].app_acks());
+
+        groups[0].commit(vec![]).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+
+        assert!(groups[0].app_acks().is_empty());
+    }
+
+    #[test]
+    fn out_of_order_generations_are_merged() {
+        let mut state = AppAckState::default();
+
+        let content = |sender| FramedContent {
+            group_id: vec![],
+            epoch: 0,
+            sender: Sender::Member(sender),
+            authenticated_data: vec![],
+            content: Content::Application(vec![].into()),
+        };
+
+        for generation in [4, 0, 2, 1, 7] {
+            state.record_received(0, &content(3), generation);
+        }
+
+        assert_eq!(
+            state.received,
+            [range(3, 0, 2), range(3, 4, 4), range(3, 7, 7)]
+        );
+
+        state.record_received(0, &content(3), 3);
+
+        assert_eq!(state.received, [range(3, 0, 4), range(3, 7, 7)]);
+    }
+}
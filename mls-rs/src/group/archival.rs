@@ -0,0 +1,224 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::crypto::{CipherSuite, SignatureSecretKey};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        framing::{Content, ContentType},
+        message_processor::{ApplicationMessageDescription, MessageProcessor},
+        Group, GroupContext, Roster,
+    },
+    MlsMessage,
+};
+
+/// Decryption-only view of a group for compliance archive readers.
+///
+/// An archival group is loaded from storage with
+/// [`Client::load_archival_group`](crate::Client::load_archival_group). It
+/// can decrypt application messages sent in a designated set of epochs, but
+/// offers no way to send messages, commit, process handshake messages or
+/// write the group back to storage. The signature secret key and pending
+/// updates of the member are discarded when loading, so an archival group
+/// can not be used to impersonate the member.
+///
+/// Decrypting a message consumes its key as required by forward secrecy.
+/// The same message can be decrypted again after loading the archival group
+/// again, since the stored group state is never modified.
+pub struct ArchivalGroup<C>
+where
+    C: ClientConfig,
+{
+    group: Group<C>,
+    epochs: Vec<u64>,
+}
+
+impl<C> ArchivalGroup<C>
+where
+    C: ClientConfig + Clone,
+{
+    pub(crate) fn new(mut group: Group<C>, epochs: Vec<u64>) -> Self {
+        group.signer = SignatureSecretKey::new(Vec::new());
+        group.pending_commit = None;
+
+        #[cfg(feature = "by_ref_proposal")]
+        group.pending_updates.clear();
+
+        Self { group, epochs }
+    }
+
+    /// Group id of the archived group.
+    pub fn group_id(&self) -> &[u8] {
+        self.group.group_id()
+    }
+
+    /// Cipher suite of the archived group.
+    pub fn cipher_suite(&self) -> CipherSuite {
+        self.group.cipher_suite()
+    }
+
+    /// Epoch of the group when it was last written to storage.
+    pub fn current_epoch(&self) -> u64 {
+        self.group.current_epoch()
+    }
+
+    /// Group context of the group when it was last written to storage.
+    pub fn context(&self) -> &GroupContext {
+        self.group.context()
+    }
+
+    /// Members of the group when it was last written to storage.
+    pub fn roster(&self) -> Roster<'_> {
+        self.group.roster()
+    }
+
+    /// Epochs in which messages can be decrypted.
+    pub fn designated_epochs(&self) -> &[u64] {
+        &self.epochs
+    }
+
+    /// Decrypt an application message sent in one of the
+    /// [designated epochs](ArchivalGroup::designated_epochs).
+    ///
+    /// Messages of prior epochs can only be decrypted if the epoch is still
+    /// retained by the [GroupStateStorage](crate::GroupStateStorage) and the
+    /// member did not decrypt the message before the state was written.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn decrypt_application_message(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<ApplicationMessageDescription, MlsError> {
+        let ciphertext = message
+            .into_ciphertext()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        if ciphertext.content_type != ContentType::Application {
+            return Err(MlsError::UnexpectedMessageType);
+        }
+
+        if !self.epochs.contains(&ciphertext.epoch) {
+            return Err(MlsError::EpochNotArchived(ciphertext.epoch));
+        }
+
+        let auth_content = self.group.decrypt_incoming_ciphertext(&ciphertext).await?;
+
+        let Content::Application(data) = auth_content.content.content else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        self.group.process_application_message(
+            data,
+            auth_content.content.sender,
+            auth_content.content.authenticated_data,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientBuilder,
+        client_config::ClientConfig,
+        group::test_utils::test_n_member_group,
+        MlsMessage,
+    };
+
+    // Alice sends a message in epoch 1 and in epoch 2. Bob only processes the
+    // commits, so the message keys are still available when he writes his
+    // state to storage.
+    #[cfg(feature = "prior_epoch")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn archived_messages() -> (crate::group::test_utils::TestGroup, Vec<MlsMessage>) {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let mut messages = Vec::new();
+
+        for data in [b"epoch 1", b"epoch 2"] {
+            let message = groups[0]
+                .group
+                .encrypt_application_message(data, vec![])
+                .await
+                .unwrap();
+
+            messages.push(message);
+
+            let commit = groups[0].group.commit(vec![]).await.unwrap();
+            groups[0].process_pending_commit().await.unwrap();
+
+            groups[1]
+                .process_message(commit.commit_message)
+                .await
+                .unwrap();
+        }
+
+        groups[1].group.write_to_storage().await.unwrap();
+
+        (groups.remove(1), messages)
+    }
+
+    #[cfg(feature = "prior_epoch")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn archival_group_decrypts_designated_epochs() {
+        let (bob, messages) = archived_messages().await;
+
+        let archive_reader = TestClientBuilder::new_for_test()
+            .group_state_storage(bob.group.config.group_state_storage())
+            .build();
+
+        let mut archive = archive_reader
+            .load_archival_group(bob.group.group_id(), vec![1])
+            .await
+            .unwrap();
+
+        assert_eq!(archive.current_epoch(), 3);
+
+        let decrypted = archive
+            .decrypt_application_message(messages[0].clone())
+            .await
+            .unwrap();
+
+        assert_eq!(decrypted.sender_index, 0);
+        assert_eq!(decrypted.data(), b"epoch 1");
+
+        let res = archive
+            .decrypt_application_message(messages[1].clone())
+            .await;
+
+        assert_matches!(res, Err(MlsError::EpochNotArchived(2)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn archival_group_rejects_handshake_messages() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        groups[1].group.write_to_storage().await.unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap();
+
+        let archive_reader = TestClientBuilder::new_for_test()
+            .group_state_storage(groups[1].group.config.group_state_storage())
+            .build();
+
+        let mut archive = archive_reader
+            .load_archival_group(groups[1].group.group_id(), vec![1])
+            .await
+            .unwrap();
+
+        let res = archive
+            .decrypt_application_message(commit.commit_message)
+            .await;
+
+        assert_matches!(res, Err(MlsError::UnexpectedMessageType));
+    }
+}
@@ -13,7 +13,7 @@ use super::{
     framing::{ContentType, FramedContent, Sender, WireFormat},
     message_signature::AuthenticatedContent,
     padding::PaddingMode,
-    secret_tree::{KeyType, MessageKeyData},
+    secret_tree::{KeyType, MessageKeyData, MAX_RATCHET_BACK_HISTORY},
     GroupContext,
 };
 use crate::{
@@ -48,6 +48,8 @@ where
 {
     group_state: &'a mut GS,
     cipher_suite_provider: CP,
+    max_out_of_order_generations: u32,
+    max_generation_skip: Option<u32>,
 }
 
 impl<'a, GS, CP> CiphertextProcessor<'a, GS, CP>
@@ -62,6 +64,22 @@ where
         Self {
             group_state,
             cipher_suite_provider,
+            max_out_of_order_generations: MAX_RATCHET_BACK_HISTORY,
+            max_generation_skip: None,
+        }
+    }
+
+    pub fn with_max_out_of_order_generations(self, max_out_of_order_generations: u32) -> Self {
+        Self {
+            max_out_of_order_generations,
+            ..self
+        }
+    }
+
+    pub fn with_max_generation_skip(self, max_generation_skip: Option<u32>) -> Self {
+        Self {
+            max_generation_skip,
+            ..self
         }
     }
 
@@ -86,12 +104,32 @@ where
         key_type: KeyType,
         generation: u32,
     ) -> Result<MessageKeyData, MlsError> {
+        let sender_index = *sender;
         let sender = NodeIndex::from(sender);
+        let secret_tree = &mut self.group_state.epoch_secrets_mut().secret_tree;
 
-        self.group_state
-            .epoch_secrets_mut()
-            .secret_tree
-            .message_key_generation(&self.cipher_suite_provider, sender, key_type, generation)
+        // Deriving the keys of skipped generations is the dominant cost of
+        // decryption, so large skips are rejected before deriving anything.
+        if let KeyType::Application = key_type {
+            let max_skip = self
+                .max_generation_skip
+                .unwrap_or(self.max_out_of_order_generations);
+
+            let skip = generation.saturating_sub(secret_tree.current_generation(&sender, key_type));
+
+            if skip > max_skip {
+                return Err(MlsError::GenerationSkipTooLarge(sender_index, generation));
+            }
+        }
+
+        secret_tree
+            .message_key_generation(
+                &self.cipher_suite_provider,
+                sender,
+                key_type,
+                generation,
+                self.max_out_of_order_generations,
+            )
             .await
     }
 
@@ -192,10 +230,10 @@ where
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    pub async fn open(
-        &mut self,
+    pub async fn open_sender_data(
+        &self,
         ciphertext: &PrivateMessage,
-    ) -> Result<AuthenticatedContent, MlsError> {
+    ) -> Result<SenderData, MlsError> {
         // Decrypt the sender data with the derived sender_key and sender_nonce from the message
         // epoch's key schedule
         let sender_data_aad = SenderDataAAD {
@@ -211,9 +249,29 @@ where
         )
         .await?;
 
-        let sender_data = sender_data_key
+        sender_data_key
             .open(&ciphertext.encrypted_sender_data, &sender_data_aad)
-            .await?;
+            .await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn open(
+        &mut self,
+        ciphertext: &PrivateMessage,
+    ) -> Result<AuthenticatedContent, MlsError> {
+        self.open_with_generation(ciphertext)
+            .await
+            .map(|(auth_content, _)| auth_content)
+    }
+
+    /// Same as [`Self::open`], also returning the generation of the key used
+    /// to decrypt the message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn open_with_generation(
+        &mut self,
+        ciphertext: &PrivateMessage,
+    ) -> Result<(AuthenticatedContent, u32), MlsError> {
+        let sender_data = self.open_sender_data(ciphertext).await?;
 
         if self.group_state.self_index() == sender_data.sender {
             return Err(MlsError::CantProcessMessageFromSelf);
@@ -258,7 +316,7 @@ where
             auth: ciphertext_content.auth,
         };
 
-        Ok(auth_content)
+        Ok((auth_content, sender_data.generation))
     }
 }
 
@@ -356,7 +414,13 @@ mod test {
             .await
             .unwrap();
 
+        let ciphertext_fixed = ciphertext_processor
+            .seal(test_data.content.clone(), PaddingMode::Fixed(1024))
+            .await
+            .unwrap();
+
         assert!(ciphertext_step.ciphertext.len() > ciphertext_no_pad.ciphertext.len());
+        assert!(ciphertext_fixed.ciphertext.len() > ciphertext_step.ciphertext.len());
     }
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
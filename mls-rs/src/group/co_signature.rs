@@ -0,0 +1,516 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Experimental authorization of membership changes by k-of-n co-signatures.
+//!
+//! Groups opt in by setting a [`CoSignersExt`] group context extension that
+//! designates the members allowed to approve commits and the number of
+//! approvals required. A designated member approves a commit by sending a
+//! [`CoSignatureProposal`] describing exactly the membership changes of the
+//! pending commit, either by reference with
+//! [`Group::propose_co_signature`] or, in the case of the committer, by value
+//! with [`CommitBuilder::custom_proposal`](crate::group::CommitBuilder::custom_proposal).
+//! Since proposals are signed by their sender, no additional signatures are
+//! needed.
+//!
+//! Approvals are checked by [`CoSignedMlsRules`], which every member of the
+//! group must use. All members must also support the [`CoSignersExt`]
+//! extension type and the [`CO_SIGNATURE_PROPOSAL_TYPE`] custom proposal type.
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::SignaturePublicKey, error::IntoAnyError, extension::ExtensionList, group::ProposalType,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        mls_rules::{
+            CommitDirection, CommitOptions, CommitSource, EncryptionOptions, MlsRules,
+            UnknownExtensionPolicy,
+        },
+        proposal::{AddProposal, MlsCustomProposal, RemoveProposal},
+        proposal_filter::ProposalBundle,
+        Group, Roster, Sender,
+    },
+    MlsMessage,
+};
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+
+pub use crate::extension::built_in::{CoSignersExt, CO_SIGNERS_EXTENSION_TYPE};
+
+/// Custom proposal type of [`CoSignatureProposal`], taken from the private
+/// use range.
+pub const CO_SIGNATURE_PROPOSAL_TYPE: u16 = 0xF0A0;
+
+/// Approval of the membership changes made by a pending commit.
+///
+/// A commit is approved if the added members, removed members and new group
+/// context extensions listed in the proposal are exactly the ones of the
+/// commit. The order in which members are listed does not matter.
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct CoSignatureProposal {
+    /// Signature keys of the key packages added by the commit.
+    pub added: Vec<SignaturePublicKey>,
+    /// Leaf indices of the members removed by the commit.
+    pub removed: Vec<u32>,
+    /// Group context extensions set by the commit, if any.
+    pub group_context_extensions: Option<ExtensionList>,
+}
+
+impl CoSignatureProposal {
+    /// Create an approval of a commit that does not change membership.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Approve adding the member with key package `key_package`.
+    pub fn add_member(mut self, key_package: &MlsMessage) -> Result<Self, MlsError> {
+        let key_package = key_package
+            .clone()
+            .into_key_package()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        self.added
+            .push(key_package.signing_identity().signature_key.clone());
+
+        Ok(self)
+    }
+
+    /// Approve removing the member at leaf index `index`.
+    pub fn remove_member(mut self, index: u32) -> Self {
+        self.removed.push(index);
+        self
+    }
+
+    /// Approve setting the group context extensions to `extensions`.
+    pub fn set_group_context_ext(self, extensions: ExtensionList) -> Self {
+        Self {
+            group_context_extensions: Some(extensions),
+            ..self
+        }
+    }
+
+    fn from_bundle(proposals: &ProposalBundle) -> Self {
+        let added = proposals
+            .by_type::<AddProposal>()
+            .map(|p| p.proposal.signing_identity().signature_key.clone())
+            .collect();
+
+        let removed = proposals
+            .by_type::<RemoveProposal>()
+            .map(|p| p.proposal.to_remove())
+            .collect();
+
+        let group_context_extensions = proposals
+            .group_context_ext_proposals()
+            .first()
+            .map(|p| p.proposal.clone());
+
+        Self {
+            added,
+            removed,
+            group_context_extensions,
+        }
+        .normalized()
+    }
+
+    fn normalized(mut self) -> Self {
+        self.added.sort_unstable();
+        self.removed.sort_unstable();
+        self
+    }
+
+    fn changes_membership(&self) -> bool {
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || self.group_context_extensions.is_some()
+    }
+}
+
+impl MlsCustomProposal for CoSignatureProposal {
+    fn proposal_type() -> ProposalType {
+        ProposalType::new(CO_SIGNATURE_PROPOSAL_TYPE)
+    }
+}
+
+/// [`MlsRules`] requiring commits of existing members that add or remove
+/// members, or change the group context extensions, to be approved by the
+/// designated members of the group's [`CoSignersExt`].
+///
+/// Other rules are delegated to the inner rules, which are applied first.
+/// When preparing a commit that is not sufficiently approved, membership
+/// changes received by reference are left out of the commit. Commits that
+/// are still not approved after that, as well as received commits that are
+/// not approved, are rejected with [`MlsError::InsufficientCoSignatures`].
+///
+/// Commits from new members joining with an external commit are not
+/// affected, they are controlled by the external commit policy of the group.
+#[derive(Clone, Debug)]
+pub struct CoSignedMlsRules<R> {
+    inner: R,
+}
+
+impl<R> CoSignedMlsRules<R> {
+    /// Enforce co-signatures on top of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+fn co_signers_count(
+    co_signers: &CoSignersExt,
+    proposals: &ProposalBundle,
+) -> Result<u32, MlsError> {
+    let approved = CoSignatureProposal::from_bundle(proposals);
+    let mut signers = Vec::new();
+
+    for info in proposals.custom_proposals() {
+        if info.proposal.proposal_type() != CoSignatureProposal::proposal_type() {
+            continue;
+        }
+
+        let Sender::Member(index) = info.sender else {
+            continue;
+        };
+
+        if !co_signers.is_signer(index) || signers.contains(&index) {
+            continue;
+        }
+
+        let approval = CoSignatureProposal::from_custom_proposal(&info.proposal)?;
+
+        if approval.normalized() == approved {
+            signers.push(index);
+        }
+    }
+
+    Ok(signers.len() as u32)
+}
+
+fn retain_by_value(proposals: &mut ProposalBundle) -> Result<(), MlsError> {
+    proposals
+        .retain_by_type::<AddProposal, _, _>(|p| Ok::<_, MlsError>(p.proposal_ref().is_none()))?;
+    proposals.retain_by_type::<RemoveProposal, _, _>(|p| {
+        Ok::<_, MlsError>(p.proposal_ref().is_none())
+    })?;
+    proposals
+        .retain_by_type::<ExtensionList, _, _>(|p| Ok::<_, MlsError>(p.proposal_ref().is_none()))
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+impl<R: MlsRules> MlsRules for CoSignedMlsRules<R> {
+    type Error = MlsError;
+
+    async fn filter_proposals(
+        &self,
+        direction: CommitDirection,
+        source: CommitSource,
+        current_roster: &Roster,
+        extension_list: &ExtensionList,
+        proposals: ProposalBundle,
+    ) -> Result<ProposalBundle, Self::Error> {
+        let mut proposals = self
+            .inner
+            .filter_proposals(
+                direction,
+                source.clone(),
+                current_roster,
+                extension_list,
+                proposals,
+            )
+            .await
+            .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
+
+        let Some(co_signers) = extension_list.get_as::<CoSignersExt>()? else {
+            return Ok(proposals);
+        };
+
+        if matches!(source, CommitSource::NewMember(_))
+            || !CoSignatureProposal::from_bundle(&proposals).changes_membership()
+        {
+            return Ok(proposals);
+        }
+
+        let mut collected = co_signers_count(&co_signers, &proposals)?;
+
+        if collected < co_signers.threshold && direction == CommitDirection::Send {
+            retain_by_value(&mut proposals)?;
+
+            if !CoSignatureProposal::from_bundle(&proposals).changes_membership() {
+                return Ok(proposals);
+            }
+
+            collected = co_signers_count(&co_signers, &proposals)?;
+        }
+
+        if collected < co_signers.threshold {
+            return Err(MlsError::InsufficientCoSignatures(
+                co_signers.threshold,
+                collected,
+            ));
+        }
+
+        Ok(proposals)
+    }
+
+    fn commit_options(
+        &self,
+        new_roster: &Roster,
+        new_extension_list: &ExtensionList,
+        proposals: &ProposalBundle,
+    ) -> Result<CommitOptions, Self::Error> {
+        self.inner
+            .commit_options(new_roster, new_extension_list, proposals)
+            .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))
+    }
+
+    fn encryption_options(
+        &self,
+        current_roster: &Roster,
+        current_extension_list: &ExtensionList,
+    ) -> Result<EncryptionOptions, Self::Error> {
+        self.inner
+            .encryption_options(current_roster, current_extension_list)
+            .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))
+    }
+
+    fn unknown_extension_policy(&self) -> UnknownExtensionPolicy {
+        self.inner.unknown_extension_policy()
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create a proposal message approving a pending commit as one of the
+    /// designated members of the group's [`CoSignersExt`].
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_co_signature(
+        &mut self,
+        approval: CoSignatureProposal,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        self.propose_custom(approval.to_custom_proposal()?, authenticated_data)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        extension::{ExtensionList, ExtensionType},
+        group::ProposalType,
+    };
+
+    use super::{
+        CoSignatureProposal, CoSignedMlsRules, CoSignersExt, CO_SIGNATURE_PROPOSAL_TYPE,
+        CO_SIGNERS_EXTENSION_TYPE,
+    };
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        client_builder::{
+            test_utils::{TestClientBuilder, TestClientConfig},
+            WithMlsRules,
+        },
+        group::{proposal::MlsCustomProposal, Group},
+        identity::test_utils::get_test_signing_identity,
+        mls_rules::DefaultMlsRules,
+        Client, MlsRules,
+    };
+
+    type CoSignedConfig = WithMlsRules<CoSignedMlsRules<DefaultMlsRules>, TestClientConfig>;
+
+    fn co_signed_rules() -> CoSignedMlsRules<DefaultMlsRules> {
+        CoSignedMlsRules::new(DefaultMlsRules::new())
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client<R: MlsRules + Clone>(
+        name: &str,
+        mls_rules: R,
+    ) -> Client<WithMlsRules<R, TestClientConfig>> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .extension_types(vec![ExtensionType::new(CO_SIGNERS_EXTENSION_TYPE)])
+            .custom_proposal_type(ProposalType::new(CO_SIGNATURE_PROPOSAL_TYPE))
+            .mls_rules(mls_rules)
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    // Alice, using `alice_rules`, creates a group with Bob and Carol that
+    // requires 2 out of 3 co-signatures.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn co_signed_groups<R: MlsRules + Clone>(
+        alice_rules: R,
+    ) -> (
+        Group<WithMlsRules<R, TestClientConfig>>,
+        Vec<Group<CoSignedConfig>>,
+    ) {
+        let alice = test_client("alice", alice_rules).await;
+        let bob = test_client("bob", co_signed_rules()).await;
+        let carol = test_client("carol", co_signed_rules()).await;
+
+        let mut alice_group = alice.create_group(ExtensionList::new()).await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(bob.generate_key_package_message().await.unwrap())
+            .unwrap()
+            .add_member(carol.generate_key_package_message().await.unwrap())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let welcome = &commit.welcome_messages[0];
+
+        let (bob_group, _) = bob.join_group(None, welcome).await.unwrap();
+        let (carol_group, _) = carol.join_group(None, welcome).await.unwrap();
+
+        let mut others = vec![bob_group, carol_group];
+
+        let mut extensions = ExtensionList::new();
+        extensions
+            .set_from(CoSignersExt::new(2, vec![0, 1, 2]))
+            .unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        for group in others.iter_mut() {
+            group
+                .process_incoming_message(commit.commit_message.clone())
+                .await
+                .unwrap();
+        }
+
+        (alice_group, others)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn membership_change_without_co_signatures_is_rejected() {
+        let (mut alice, _) = co_signed_groups(co_signed_rules()).await;
+
+        let dave = test_client("dave", co_signed_rules()).await;
+        let key_package = dave.generate_key_package_message().await.unwrap();
+
+        let res = alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await;
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn membership_change_with_threshold_co_signatures_is_accepted() {
+        let (mut alice, mut others) = co_signed_groups(co_signed_rules()).await;
+
+        let dave = test_client("dave", co_signed_rules()).await;
+        let key_package = dave.generate_key_package_message().await.unwrap();
+
+        let approval = CoSignatureProposal::new().add_member(&key_package).unwrap();
+
+        let proposal = others[0]
+            .propose_co_signature(approval.clone(), vec![])
+            .await
+            .unwrap();
+
+        alice
+            .process_incoming_message(proposal.clone())
+            .await
+            .unwrap();
+
+        others[1].process_incoming_message(proposal).await.unwrap();
+
+        let commit = alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .custom_proposal(approval.to_custom_proposal().unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        alice.apply_pending_commit().await.unwrap();
+
+        for group in others.iter_mut() {
+            group
+                .process_incoming_message(commit.commit_message.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(group.roster().members().len(), 4);
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn co_signature_of_different_change_is_not_counted() {
+        let (mut alice, _) = co_signed_groups(co_signed_rules()).await;
+
+        let dave = test_client("dave", co_signed_rules()).await;
+        let key_package = dave.generate_key_package_message().await.unwrap();
+
+        let approval = CoSignatureProposal::new().remove_member(2);
+
+        let res = alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .custom_proposal(approval.to_custom_proposal().unwrap())
+            .build()
+            .await;
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn receiver_rejects_commit_without_co_signatures() {
+        let (mut alice, mut others) = co_signed_groups(DefaultMlsRules::new()).await;
+
+        let dave = test_client("dave", co_signed_rules()).await;
+        let key_package = dave.generate_key_package_message().await.unwrap();
+
+        let commit = alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let res = others[0]
+            .process_incoming_message(commit.commit_message)
+            .await;
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+    }
+}
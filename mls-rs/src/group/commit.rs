@@ -12,16 +12,20 @@ use crate::{
     cipher_suite::CipherSuite,
     client::MlsError,
     client_config::ClientConfig,
-    extension::RatchetTreeExt,
+    extension::{CommitReason, CommitReasonExt, JoinTicketExt, MlsCodecExtension, RatchetTreeExt},
     identity::SigningIdentity,
     protocol_version::ProtocolVersion,
     signer::Signable,
     tree_kem::{
-        kem::TreeKem, node::LeafIndex, path_secret::PathSecret, TreeKemPrivate, UpdatePath,
+        kem::TreeKem, node::LeafIndex, path_secret::PathSecret, TreeKemPrivate, TreeKemPublic,
+        UpdatePath,
     },
-    ExtensionList, MlsRules,
+    Extension, ExtensionList, MlsRules,
 };
 
+#[cfg(feature = "tree_compression")]
+use crate::extension::CompressedRatchetTreeExt;
+
 #[cfg(all(not(mls_build_async), feature = "rayon"))]
 use {crate::iter::ParallelIteratorExt, rayon::prelude::*};
 
@@ -43,7 +47,7 @@ use super::{
     message_hash::MessageHash,
     message_processor::{path_update_required, MessageProcessor},
     message_signature::AuthenticatedContent,
-    mls_rules::CommitDirection,
+    mls_rules::{CommitDirection, CommitOptions, WelcomeSize},
     proposal::{Proposal, ProposalOrRef},
     ConfirmedTranscriptHash, EncryptedGroupSecrets, ExportedTree, Group, GroupContext, GroupInfo,
     Welcome,
@@ -86,8 +90,9 @@ pub struct CommitOutput {
     pub commit_message: MlsMessage,
     /// Welcome messages to send to new group members. If the commit does not add members,
     /// this list is empty. Otherwise, if [`MlsRules::commit_options`] returns `single_welcome_message`
-    /// set to true, then this list contains a single message sent to all members. Else, the list
-    /// contains one message for each added member. Recipients of each message can be identified using
+    /// set to true, then this list contains a single message sent to all members, or as many
+    /// messages as needed to respect `max_welcome_size`. Else, the list contains one message for
+    /// each added member. Recipients of each message can be identified using
     /// [`MlsMessage::key_package_reference`] of their key packages and
     /// [`MlsMessage::welcome_key_package_references`].
     pub welcome_messages: Vec<MlsMessage>,
@@ -158,6 +163,8 @@ where
     group_info_extensions: ExtensionList,
     new_signer: Option<SignatureSecretKey>,
     new_signing_identity: Option<SigningIdentity>,
+    commit_reason: Option<CommitReason>,
+    yield_between_phases: bool,
 }
 
 impl<'a, C> CommitBuilder<'a, C>
@@ -197,11 +204,29 @@ where
         Ok(self)
     }
 
+    /// Insert a [`RemoveProposal`](crate::group::proposal::RemoveProposal)
+    /// into the current commit for every member other than the committer
+    /// whose [`MemberExpiryExt`](crate::extension::MemberExpiryExt) is
+    /// expired at `time`.
+    #[cfg(feature = "member_expiry")]
+    pub fn remove_expired_members(mut self, time: crate::time::MlsTime) -> Result<Self, MlsError> {
+        let own_index = self.group.current_member_index();
+
+        for index in self.group.expired_members(time) {
+            if index != own_index {
+                let proposal = self.group.remove_proposal(index)?;
+                self.proposals.push(proposal);
+            }
+        }
+
+        Ok(self)
+    }
+
     /// Insert a
     /// [`GroupContextExtensions`](crate::group::proposal::Proposal::GroupContextExtensions)
     /// into the current commit that is being built.
     pub fn set_group_context_ext(mut self, extensions: ExtensionList) -> Result<Self, MlsError> {
-        let proposal = self.group.group_context_extensions_proposal(extensions);
+        let proposal = self.group.group_context_extensions_proposal(extensions)?;
         self.proposals.push(proposal);
         Ok(self)
     }
@@ -306,6 +331,18 @@ where
         }
     }
 
+    /// Attach a [`CommitReason`] to the commit that receivers can use to
+    /// describe the change, see [`StateUpdate::commit_reason`](crate::group::StateUpdate::commit_reason).
+    ///
+    /// The reason is sent as a [`CommitReasonExt`] in the committer's new
+    /// leaf node, so setting a reason always results in a path update.
+    pub fn commit_reason(self, reason: CommitReason) -> Self {
+        Self {
+            commit_reason: Some(reason),
+            ..self
+        }
+    }
+
     /// Finalize the commit to send.
     ///
     /// # Errors
@@ -324,6 +361,8 @@ where
                 self.group_info_extensions,
                 self.new_signer,
                 self.new_signing_identity,
+                self.commit_reason,
+                self.yield_between_phases,
             )
             .await
     }
@@ -382,6 +421,8 @@ where
             Default::default(),
             None,
             None,
+            None,
+            false,
         )
         .await
     }
@@ -396,6 +437,23 @@ where
             group_info_extensions: Default::default(),
             new_signer: Default::default(),
             new_signing_identity: Default::default(),
+            commit_reason: Default::default(),
+            yield_between_phases: false,
+        }
+    }
+
+    /// Create a new commit builder whose [`build`](CommitBuilder::build)
+    /// future yields to the async runtime between the phases of the commit.
+    ///
+    /// The phases are applying the proposals, updating the ratchet tree,
+    /// advancing the key schedule and encrypting the welcome message to each
+    /// new member. This prevents large commits from blocking single threaded
+    /// runtimes for their whole duration. In a sync build, this is the same as
+    /// [`Group::commit_builder`].
+    pub fn commit_stream(&mut self) -> CommitBuilder<'_, C> {
+        CommitBuilder {
+            yield_between_phases: true,
+            ..self.commit_builder()
         }
     }
 
@@ -411,7 +469,11 @@ where
         mut welcome_group_info_extensions: ExtensionList,
         new_signer: Option<SignatureSecretKey>,
         new_signing_identity: Option<SigningIdentity>,
+        commit_reason: Option<CommitReason>,
+        yield_between_phases: bool,
     ) -> Result<CommitOutput, MlsError> {
+        self.check_can_send()?;
+
         if self.pending_commit.is_some() {
             return Err(MlsError::ExistingPendingCommit);
         }
@@ -474,6 +536,8 @@ where
             self.private_tree.self_index = provisional_private_tree.self_index;
         }
 
+        yield_if(yield_between_phases).await;
+
         let mut provisional_group_context = provisional_state.group_context;
 
         // Decide whether to populate the path field: If the path field is required based on the
@@ -488,6 +552,7 @@ where
             .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
 
         let perform_path_update = commit_options.path_required
+            || commit_reason.is_some()
             || path_update_required(&provisional_state.applied_proposals);
 
         let (update_path, path_secrets, commit_secret) = if perform_path_update {
@@ -497,6 +562,27 @@ where
             // group_id, epoch, tree_hash, and confirmed_transcript_hash values in the initial
             // GroupContext object. The leaf_key_package for this UpdatePath must have a
             // parent_hash extension.
+            let mut leaf_properties = self.config.leaf_properties();
+
+            // Keep the join ticket of the leaf an external commit was built with.
+            if let Some(leaf) = external_leaf {
+                if let Some(ticket) = leaf.extensions.get_as::<JoinTicketExt>()? {
+                    leaf_properties.extensions.set_from(ticket)?;
+                }
+            }
+
+            if let Some(reason) = commit_reason {
+                let ext_type = CommitReasonExt::extension_type();
+
+                if !leaf_properties.capabilities.extensions.contains(&ext_type) {
+                    leaf_properties.capabilities.extensions.push(ext_type);
+                }
+
+                leaf_properties
+                    .extensions
+                    .set_from(CommitReasonExt::new(reason))?;
+            }
+
             let encap_gen = TreeKem::new(
                 &mut provisional_state.public_tree,
                 &mut provisional_private_tree,
@@ -505,7 +591,7 @@ where
                 &mut provisional_group_context,
                 &provisional_state.indexes_of_added_kpkgs,
                 new_signer_ref,
-                self.config.leaf_properties(),
+                leaf_properties,
                 new_signing_identity,
                 &self.cipher_suite_provider,
                 #[cfg(test)]
@@ -536,6 +622,8 @@ where
             (None, None, PathSecret::empty(&self.cipher_suite_provider))
         };
 
+        yield_if(yield_between_phases).await;
+
         #[cfg(feature = "psk")]
         let (psk_secret, psks) = self
             .get_psk(&provisional_state.applied_proposals.psks)
@@ -603,9 +691,8 @@ where
 
         let ratchet_tree_ext = commit_options
             .ratchet_tree_extension
-            .then(|| RatchetTreeExt {
-                tree_data: ExportedTree::new(provisional_state.public_tree.nodes.clone()),
-            });
+            .then(|| ratchet_tree_extension(&provisional_state.public_tree, &commit_options))
+            .transpose()?;
 
         // Generate external commit group info if required by commit_options
         let external_commit_group_info = match commit_options.allow_external_commit {
@@ -620,7 +707,7 @@ where
                 })?;
 
                 if let Some(ref ratchet_tree_ext) = ratchet_tree_ext {
-                    extensions.set_from(ratchet_tree_ext.clone())?;
+                    extensions.set(ratchet_tree_ext.clone());
                 }
 
                 let info = self
@@ -640,10 +727,12 @@ where
             false => None,
         };
 
+        yield_if(yield_between_phases).await;
+
         // Build the group info that will be placed into the welcome messages.
         // Add the ratchet tree extension if necessary
         if let Some(ratchet_tree_ext) = ratchet_tree_ext {
-            welcome_group_info_extensions.set_from(ratchet_tree_ext)?;
+            welcome_group_info_extensions.set(ratchet_tree_ext);
         }
 
         let welcome_group_info = self
@@ -671,6 +760,26 @@ where
         // Encrypt path secrets and joiner secret to new members
         let path_secrets = path_secrets.as_ref();
 
+        #[cfg(feature = "welcome_retargeting")]
+        let retargetable_members = added_key_pkgs
+            .iter()
+            .zip(&provisional_state.indexes_of_added_kpkgs)
+            .map(|(key_package, leaf_index)| {
+                let group_secrets = self.new_member_group_secrets(
+                    *leaf_index,
+                    &key_schedule_result.joiner_secret,
+                    path_secrets,
+                    #[cfg(feature = "psk")]
+                    psks.clone(),
+                )?;
+
+                Ok((
+                    key_package.leaf_node.signing_identity.clone(),
+                    group_secrets,
+                ))
+            })
+            .collect::<Result<Vec<_>, MlsError>>()?;
+
         #[cfg(not(any(mls_build_async, not(feature = "rayon"))))]
         let encrypted_path_secrets: Vec<_> = added_key_pkgs
             .into_par_iter()
@@ -708,20 +817,27 @@ where
                     )
                     .await?,
                 );
+
+                yield_if(yield_between_phases).await;
             }
 
             secrets
         };
 
-        let welcome_messages =
-            if commit_options.single_welcome_message && !encrypted_path_secrets.is_empty() {
-                vec![self.make_welcome_message(encrypted_path_secrets, encrypted_group_info)]
-            } else {
-                encrypted_path_secrets
-                    .into_iter()
-                    .map(|s| self.make_welcome_message(vec![s], encrypted_group_info.clone()))
-                    .collect()
-            };
+        #[cfg(feature = "welcome_retargeting")]
+        {
+            self.retargetable_welcome = super::welcome_retargeting::RetargetableWelcome::new(
+                &provisional_group_context,
+                &encrypted_group_info,
+                retargetable_members,
+            );
+        }
+
+        let welcome_messages = self.make_welcome_messages(
+            encrypted_path_secrets,
+            encrypted_group_info,
+            &commit_options,
+        )?;
 
         let commit_message = self.format_for_wire(auth_content.clone()).await?;
 
@@ -780,6 +896,67 @@ where
         Ok(group_info)
     }
 
+    // Partition the secrets of new members into Welcome messages according to
+    // `single_welcome_message` and `max_welcome_size`
+    fn make_welcome_messages(
+        &self,
+        secrets: Vec<EncryptedGroupSecrets>,
+        encrypted_group_info: Vec<u8>,
+        commit_options: &CommitOptions,
+    ) -> Result<Vec<MlsMessage>, MlsError> {
+        let max_recipients = match commit_options.max_welcome_size {
+            _ if !commit_options.single_welcome_message => 1,
+            Some(WelcomeSize::Recipients(max)) => max.max(1),
+            _ => usize::MAX,
+        };
+
+        let max_bytes = match commit_options.max_welcome_size {
+            Some(WelcomeSize::Bytes(max)) => Some(max),
+            _ => None,
+        };
+
+        // Size of a Welcome message without any secrets
+        let base_size = max_bytes.map(|_| {
+            self.make_welcome_message(Vec::new(), encrypted_group_info.clone())
+                .mls_encoded_len()
+                - Vec::<EncryptedGroupSecrets>::new().mls_encoded_len()
+        });
+
+        let fits = |batch: &Vec<EncryptedGroupSecrets>| {
+            batch.len() <= max_recipients
+                && max_bytes
+                    .zip(base_size)
+                    .map_or(true, |(max, base)| base + batch.mls_encoded_len() <= max)
+        };
+
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+
+        for secret in secrets {
+            batch.push(secret);
+
+            if !fits(&batch) && batch.len() > 1 {
+                let last = batch.split_off(batch.len() - 1);
+                batches.push(core::mem::replace(&mut batch, last));
+            }
+
+            if !fits(&batch) {
+                return Err(MlsError::WelcomeMessageTooLarge(
+                    max_bytes.unwrap_or_default(),
+                ));
+            }
+        }
+
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+
+        Ok(batches
+            .into_iter()
+            .map(|secrets| self.make_welcome_message(secrets, encrypted_group_info.clone()))
+            .collect())
+    }
+
     fn make_welcome_message(
         &self,
         secrets: Vec<EncryptedGroupSecrets>,
@@ -796,6 +973,65 @@ where
     }
 }
 
+// Extension sending `tree` in-band to new members, compressed if required by
+// `commit_options`
+fn ratchet_tree_extension(
+    tree: &TreeKemPublic,
+    commit_options: &CommitOptions,
+) -> Result<Extension, MlsError> {
+    let tree_data = ExportedTree::new_borrowed(&tree.nodes);
+
+    #[cfg(feature = "tree_compression")]
+    if commit_options.compress_ratchet_tree {
+        let ext = CompressedRatchetTreeExt::new(&tree_data)?;
+
+        return Ok(crate::extension::MlsExtension::into_extension(ext)?);
+    }
+
+    #[cfg(not(feature = "tree_compression"))]
+    let _ = commit_options;
+
+    let ext = RatchetTreeExt {
+        tree_data: tree_data.into_owned(),
+    };
+
+    Ok(crate::extension::MlsExtension::into_extension(ext)?)
+}
+
+// Future that is pending exactly once, giving the executor a chance to run
+// other tasks.
+#[cfg(mls_build_async)]
+struct YieldNow(bool);
+
+#[cfg(mls_build_async)]
+impl core::future::Future for YieldNow {
+    type Output = ();
+
+    fn poll(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<()> {
+        if self.0 {
+            return core::task::Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        core::task::Poll::Pending
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn yield_if(enabled: bool) {
+    #[cfg(mls_build_async)]
+    if enabled {
+        YieldNow(false).await;
+    }
+
+    #[cfg(not(mls_build_async))]
+    let _ = enabled;
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use alloc::vec::Vec;
@@ -828,6 +1064,7 @@ mod tests {
     use alloc::boxed::Box;
 
     use mls_rs_core::{
+        crypto::CryptoProvider,
         error::IntoAnyError,
         extension::ExtensionType,
         identity::{CredentialType, IdentityProvider},
@@ -854,7 +1091,7 @@ mod tests {
         extension::test_utils::{TestExtension, TEST_EXTENSION_TYPE},
         group::{
             proposal::ProposalType,
-            test_utils::{test_group_custom_config, test_n_member_group},
+            test_utils::{test_group_custom_config, test_n_member_group, TestGroup},
         },
         identity::test_utils::get_test_signing_identity,
         identity::{basic::BasicIdentityProvider, test_utils::get_test_basic_credential},
@@ -863,6 +1100,11 @@ mod tests {
 
     use crate::extension::RequiredCapabilitiesExt;
 
+    #[cfg(feature = "state_update")]
+    use crate::group::{test_utils::test_group, CommitMessageDescription, ReceivedMessage};
+
+    use assert_matches::assert_matches;
+
     #[cfg(feature = "psk")]
     use crate::{
         group::proposal::PreSharedKeyProposal,
@@ -954,6 +1196,56 @@ mod tests {
         assert_commit_builder_output(group, commit_output, vec![expected_add], 1)
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn test_commit_stream_add() {
+        let mut group = test_commit_builder_group().await;
+
+        let key_packages = [
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await,
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await,
+        ];
+
+        let commit_output = group
+            .commit_stream()
+            .add_member(key_packages[0].clone())
+            .unwrap()
+            .add_member(key_packages[1].clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let expected_adds = key_packages
+            .into_iter()
+            .map(|kp| group.add_proposal(kp).unwrap())
+            .collect();
+
+        assert_commit_builder_output(group, commit_output, expected_adds, 2)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn test_commit_builder_add_with_other_cipher_suite() {
+        let mut group = test_commit_builder_group().await;
+
+        let other_cipher_suites = TestCryptoProvider::new()
+            .supported_cipher_suites()
+            .into_iter()
+            .filter(|cs| cs != &TEST_CIPHER_SUITE);
+
+        for cipher_suite in other_cipher_suites {
+            let key_package =
+                test_key_package_message(TEST_PROTOCOL_VERSION, cipher_suite, "alice").await;
+
+            let res = group.commit_builder().add_member(key_package).map(|_| ());
+
+            assert_matches!(
+                res,
+                Err(MlsError::KeyPackageCipherSuiteMismatch(found, expected))
+                    if found == cipher_suite && expected == TEST_CIPHER_SUITE
+            );
+        }
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn test_commit_builder_add_with_ext() {
         let mut group = test_commit_builder_group().await;
@@ -1058,7 +1350,7 @@ mod tests {
             .await
             .unwrap();
 
-        let expected_ext = group.group_context_extensions_proposal(test_ext);
+        let expected_ext = group.group_context_extensions_proposal(test_ext).unwrap();
 
         assert_commit_builder_output(group, commit_output, vec![expected_ext], 0);
     }
@@ -1219,6 +1511,98 @@ mod tests {
         }
     }
 
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn add_three_members(
+        group: &mut TestGroup,
+        max_welcome_size: Option<WelcomeSize>,
+    ) -> Result<(Vec<MlsMessage>, Vec<MlsMessage>), MlsError> {
+        group
+            .group
+            .config
+            .0
+            .mls_rules
+            .commit_options
+            .max_welcome_size = max_welcome_size;
+
+        let mut key_packages = Vec::new();
+        let mut builder = group.group.commit_builder();
+
+        for name in ["a", "b", "c"] {
+            let (_, key_package) =
+                test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, name).await;
+
+            builder = builder.add_member(key_package.clone())?;
+            key_packages.push(key_package);
+        }
+
+        let welcomes = builder.build().await?.welcome_messages;
+        group.group.clear_pending_commit();
+
+        Ok((welcomes, key_packages))
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_messages_are_split_by_size() {
+        let mut group = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 1)
+            .await
+            .remove(0);
+
+        let (welcomes, _) = add_three_members(&mut group, None).await.unwrap();
+        assert_eq!(welcomes.len(), 1);
+
+        let three_recipients_len = welcomes[0].mls_encoded_len();
+
+        let (welcomes, key_packages) =
+            add_three_members(&mut group, Some(WelcomeSize::Recipients(2)))
+                .await
+                .unwrap();
+
+        assert_eq!(welcomes.len(), 2);
+        assert_each_member_welcomed_once(&welcomes, key_packages).await;
+
+        let two_recipients_len = welcomes.iter().map(|w| w.mls_encoded_len()).max().unwrap();
+
+        // Sizes vary slightly between commits, so leave a margin of half a
+        // member's secrets on both sides.
+        let max_bytes = (two_recipients_len + three_recipients_len) / 2;
+
+        let (welcomes, key_packages) =
+            add_three_members(&mut group, Some(WelcomeSize::Bytes(max_bytes)))
+                .await
+                .unwrap();
+
+        assert_eq!(welcomes.len(), 2);
+        assert!(welcomes.iter().all(|w| w.mls_encoded_len() <= max_bytes));
+        assert_each_member_welcomed_once(&welcomes, key_packages).await;
+
+        let res = add_three_members(&mut group, Some(WelcomeSize::Bytes(max_bytes / 2))).await;
+
+        assert_matches!(res, Err(MlsError::WelcomeMessageTooLarge(_)));
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn assert_each_member_welcomed_once(
+        welcomes: &[MlsMessage],
+        key_packages: Vec<MlsMessage>,
+    ) {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        for key_package in key_packages {
+            let kp_ref = key_package
+                .key_package_reference(&cs)
+                .await
+                .unwrap()
+                .unwrap();
+
+            let recipients = welcomes
+                .iter()
+                .filter(|w| w.welcome_key_package_references().contains(&&kp_ref))
+                .count();
+
+            assert_eq!(recipients, 1);
+        }
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_can_change_credential() {
         let cs = TEST_CIPHER_SUITE;
@@ -1304,6 +1688,23 @@ mod tests {
         assert!(commit.ratchet_tree.is_none());
     }
 
+    #[cfg(feature = "tree_compression")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn new_members_join_with_compressed_tree() {
+        let mut group = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            Default::default(),
+            None,
+            Some(CommitOptions::new().with_compress_ratchet_tree(true)),
+        )
+        .await;
+
+        let (bob, _) = group.join("bob").await;
+
+        assert_eq!(bob.group.export_tree(), group.group.export_tree());
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_includes_external_commit_group_info_if_requested() {
         let mut group = test_group_custom(
@@ -1499,7 +1900,11 @@ mod tests {
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
     impl IdentityProvider for IdentityProviderWithExtension {
         type Error = IdentityProviderWithExtensionError;
 
@@ -1567,4 +1972,54 @@ mod tests {
             .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
             .build()
     }
+
+    #[cfg(feature = "state_update")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_reason_is_surfaced_to_receivers() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let commit_output = alice
+            .group
+            .commit_builder()
+            .commit_reason(CommitReason::KEY_ROTATION)
+            .build()
+            .await
+            .unwrap();
+
+        let update = alice.group.apply_pending_commit().await.unwrap();
+
+        assert_eq!(
+            update.state_update.commit_reason(),
+            Some(CommitReason::KEY_ROTATION)
+        );
+
+        let received = bob
+            .group
+            .process_incoming_message(commit_output.commit_message)
+            .await
+            .unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedMessage::Commit(CommitMessageDescription { state_update, .. })
+                if state_update.commit_reason() == Some(CommitReason::KEY_ROTATION)
+        );
+
+        // The reason only applies to the commit it was set for.
+        let commit_output = alice.group.commit(vec![]).await.unwrap();
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let received = bob
+            .group
+            .process_incoming_message(commit_output.commit_message)
+            .await
+            .unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedMessage::Commit(CommitMessageDescription { state_update, .. })
+                if state_update.commit_reason().is_none()
+        );
+    }
 }
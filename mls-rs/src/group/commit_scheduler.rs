@@ -0,0 +1,298 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::time::MlsTime;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{CommitOutput, Group},
+    MlsMessage,
+};
+
+/// Policy of a [`CommitScheduler`] describing when pending changes are
+/// committed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CadencePolicy {
+    /// Maximum time in seconds a requested change waits before it is
+    /// committed.
+    pub max_delay_seconds: u64,
+    /// Number of pending adds that triggers a commit right away. If 0, adds
+    /// only wait for the delay.
+    pub max_pending_adds: usize,
+}
+
+impl CadencePolicy {
+    pub fn new(max_delay_seconds: u64, max_pending_adds: usize) -> Self {
+        Self {
+            max_delay_seconds,
+            max_pending_adds,
+        }
+    }
+}
+
+/// Aggregator of the membership changes requested by the application,
+/// reducing the number of epochs in busy groups.
+///
+/// Changes are requested with [`CommitScheduler::request_add`],
+/// [`CommitScheduler::request_remove`] and
+/// [`CommitScheduler::request_update`]. All pending changes are committed
+/// together by [`Group::commit_scheduled`] once the [`CadencePolicy`] says
+/// so. Removes requested with [`CommitScheduler::request_urgent_remove`],
+/// for example of a compromised device, are due right away. The
+/// application is responsible for calling [`Group::commit_scheduled`] once
+/// [`CommitScheduler::next_commit`] has passed.
+#[derive(Clone, Debug, Default)]
+pub struct CommitScheduler {
+    policy: CadencePolicy,
+    adds: Vec<MlsMessage>,
+    removes: Vec<u32>,
+    update: bool,
+    due: Option<MlsTime>,
+}
+
+impl CommitScheduler {
+    pub fn new(policy: CadencePolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.due.is_none()
+    }
+
+    /// Key packages of the members waiting to be added.
+    pub fn pending_adds(&self) -> &[MlsMessage] {
+        &self.adds
+    }
+
+    /// Leaf indices of the members waiting to be removed.
+    pub fn pending_removes(&self) -> &[u32] {
+        &self.removes
+    }
+
+    /// Determine if an update of the path of this member is pending.
+    pub fn update_requested(&self) -> bool {
+        self.update
+    }
+
+    /// Time at which the pending changes are due to be committed.
+    pub fn next_commit(&self) -> Option<MlsTime> {
+        self.due
+    }
+
+    /// Determine if the pending changes should be committed at `now`.
+    pub fn is_due(&self, now: MlsTime) -> bool {
+        self.due.map_or(false, |due| due <= now)
+    }
+
+    /// Request adding the member with `key_package`.
+    pub fn request_add(&mut self, key_package: MlsMessage, now: MlsTime) {
+        self.adds.push(key_package);
+
+        let threshold = self.policy.max_pending_adds;
+
+        if threshold > 0 && self.adds.len() >= threshold {
+            self.schedule_at(now);
+        } else {
+            self.schedule(now);
+        }
+    }
+
+    /// Request removing the member with leaf index `index`.
+    pub fn request_remove(&mut self, index: u32, now: MlsTime) {
+        if !self.removes.contains(&index) {
+            self.removes.push(index);
+        }
+
+        self.schedule(now);
+    }
+
+    /// Request removing the member with leaf index `index` in the next
+    /// commit, together with all other pending changes.
+    pub fn request_urgent_remove(&mut self, index: u32, now: MlsTime) {
+        self.request_remove(index, now);
+        self.schedule_at(now);
+    }
+
+    /// Request updating the path of this member, for example to refresh
+    /// its keys.
+    pub fn request_update(&mut self, now: MlsTime) {
+        self.update = true;
+        self.schedule(now);
+    }
+
+    fn schedule(&mut self, now: MlsTime) {
+        let due = now
+            .seconds_since_epoch()
+            .saturating_add(self.policy.max_delay_seconds);
+
+        self.schedule_at(MlsTime::from(due));
+    }
+
+    fn schedule_at(&mut self, due: MlsTime) {
+        self.due = Some(self.due.map_or(due, |current| current.min(due)));
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new(self.policy.clone());
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Commit all changes pending in `scheduler` if they are due at `now`.
+    ///
+    /// Returns `None` if no commit is due. Otherwise, the pending changes
+    /// are removed from `scheduler` and the commit must be applied with
+    /// [`Group::apply_pending_commit`] as usual. Removes of members that
+    /// already left the group are skipped. If the commit fails, the pending
+    /// changes are kept.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the commit message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn commit_scheduled(
+        &mut self,
+        scheduler: &mut CommitScheduler,
+        now: MlsTime,
+        authenticated_data: Vec<u8>,
+    ) -> Result<Option<CommitOutput>, MlsError> {
+        if !scheduler.is_due(now) {
+            return Ok(None);
+        }
+
+        let roster = self.roster();
+
+        let removes = scheduler
+            .removes
+            .iter()
+            .copied()
+            .filter(|&index| roster.member_with_index(index).is_ok())
+            .collect::<Vec<_>>();
+
+        // A commit without proposals updates the path of the committer, so
+        // a requested update needs no proposal of its own.
+        let mut builder = self.commit_builder().authenticated_data(authenticated_data);
+
+        for index in removes {
+            builder = builder.remove_member(index)?;
+        }
+
+        for key_package in scheduler.adds.iter().cloned() {
+            builder = builder.add_member(key_package)?;
+        }
+
+        let output = builder.build().await?;
+        scheduler.clear();
+
+        Ok(Some(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use mls_rs_core::time::MlsTime;
+
+    use crate::{
+        client::test_utils::{test_client_with_key_pkg, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::{CadencePolicy, CommitScheduler};
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn pending_changes_are_committed_together() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+        let mut scheduler = CommitScheduler::new(CadencePolicy::new(60, 3));
+
+        let (_, bob) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let (_, carol) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await;
+
+        scheduler.request_add(bob, MlsTime::from(1000));
+        scheduler.request_remove(1, MlsTime::from(1010));
+        scheduler.request_add(carol, MlsTime::from(1020));
+        scheduler.request_update(MlsTime::from(1030));
+
+        assert!(scheduler.update_requested());
+
+        assert_eq!(scheduler.next_commit(), Some(MlsTime::from(1060)));
+
+        let output = groups[0]
+            .group
+            .commit_scheduled(&mut scheduler, MlsTime::from(1059), vec![])
+            .await
+            .unwrap();
+
+        assert!(output.is_none());
+
+        let output = groups[0]
+            .group
+            .commit_scheduled(&mut scheduler, MlsTime::from(1060), vec![])
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(scheduler.is_empty());
+        assert_eq!(output.welcome_messages.len(), 1);
+
+        let epoch = groups[0].group.current_epoch();
+        groups[0].process_pending_commit().await.unwrap();
+
+        assert_eq!(groups[0].group.current_epoch(), epoch + 1);
+        assert_eq!(groups[0].group.roster().members().len(), 4);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn thresholds_and_urgent_removes_commit_right_away() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+        let mut scheduler = CommitScheduler::new(CadencePolicy::new(3600, 1));
+
+        let (_, bob) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        scheduler.request_add(bob, MlsTime::from(1000));
+        assert_eq!(scheduler.next_commit(), Some(MlsTime::from(1000)));
+
+        let mut scheduler = CommitScheduler::new(CadencePolicy::new(3600, 0));
+
+        scheduler.request_remove(2, MlsTime::from(1000));
+        assert!(!scheduler.is_due(MlsTime::from(1010)));
+
+        scheduler.request_urgent_remove(1, MlsTime::from(1010));
+        assert!(scheduler.is_due(MlsTime::from(1010)));
+
+        // Removes of members that already left are skipped.
+        groups[0]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[0].process_pending_commit().await.unwrap();
+
+        groups[0]
+            .group
+            .commit_scheduled(&mut scheduler, MlsTime::from(1010), vec![])
+            .await
+            .unwrap()
+            .unwrap();
+
+        groups[0].process_pending_commit().await.unwrap();
+
+        assert_eq!(groups[0].group.roster().members().len(), 1);
+    }
+}
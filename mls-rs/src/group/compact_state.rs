@@ -0,0 +1,234 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::{CipherSuite, CipherSuiteProvider},
+    error::IntoAnyError,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{cipher_suite_provider, snapshot::Snapshot, Group},
+};
+
+#[cfg(feature = "prior_epoch")]
+use crate::group::PriorEpoch;
+
+const COMPACT_STATE_VERSION: u16 = 1;
+
+/// Self-contained encoding of a group state produced by
+/// [`Group::write_to_storage_compact`].
+///
+/// The payload is followed by its hash, computed with the cipher suite of
+/// the group, so that truncated or corrupted blobs are detected when
+/// loading.
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+struct CompactGroupState {
+    version: u16,
+    cipher_suite: CipherSuite,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    payload: Vec<u8>,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    digest: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+struct CompactPayload {
+    snapshot: Snapshot,
+    // Encoded prior epochs, oldest first. Always empty without the
+    // `prior_epoch` feature.
+    prior_epochs: Vec<Vec<u8>>,
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Write the group state to storage and export it, together with the
+    /// prior epochs kept in storage, as a single versioned blob.
+    ///
+    /// The blob contains all secrets of the group, including the signature
+    /// secret key of this member, and should be protected accordingly, e.g.
+    /// using a platform keystore. It can be loaded with
+    /// [`Client::load_group_compact`](crate::Client::load_group_compact).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn write_to_storage_compact(&mut self) -> Result<Vec<u8>, MlsError> {
+        self.write_to_storage().await?;
+
+        #[cfg(feature = "prior_epoch")]
+        let prior_epochs = self
+            .state_repo
+            .stored_epochs()
+            .await?
+            .iter()
+            .map(|epoch| epoch.mls_encode_to_vec())
+            .collect::<Result<_, _>>()?;
+
+        #[cfg(not(feature = "prior_epoch"))]
+        let prior_epochs = Vec::new();
+
+        let payload = CompactPayload {
+            snapshot: self.snapshot(),
+            prior_epochs,
+        }
+        .mls_encode_to_vec()?;
+
+        let digest = self
+            .cipher_suite_provider
+            .hash(&payload)
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        CompactGroupState {
+            version: COMPACT_STATE_VERSION,
+            cipher_suite: self.cipher_suite(),
+            payload,
+            digest,
+        }
+        .mls_encode_to_vec()
+        .map_err(Into::into)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn from_compact_state(config: C, bytes: &[u8]) -> Result<Self, MlsError> {
+        let state = CompactGroupState::mls_decode(&mut &*bytes)?;
+
+        if state.version != COMPACT_STATE_VERSION {
+            return Err(MlsError::UnsupportedCompactStateVersion(state.version));
+        }
+
+        let cipher_suite_provider =
+            cipher_suite_provider(config.crypto_provider(), state.cipher_suite)?;
+
+        let digest = cipher_suite_provider
+            .hash(&state.payload)
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        if digest != state.digest {
+            return Err(MlsError::InvalidCompactState);
+        }
+
+        let payload = CompactPayload::mls_decode(&mut &*state.payload)?;
+
+        if payload.snapshot.state.context.cipher_suite != state.cipher_suite {
+            return Err(MlsError::InvalidCompactState);
+        }
+
+        #[cfg_attr(not(feature = "prior_epoch"), allow(unused_mut))]
+        let mut group = Group::from_snapshot(config, payload.snapshot).await?;
+
+        #[cfg(feature = "prior_epoch")]
+        for epoch in payload.prior_epochs {
+            group
+                .state_repo
+                .insert(PriorEpoch::mls_decode(&mut &*epoch)?)
+                .await?;
+        }
+
+        Ok(group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_codec::{MlsDecode, MlsEncode};
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientBuilder,
+        group::{
+            test_utils::{test_group, test_n_member_group},
+            Group,
+        },
+    };
+
+    use super::{CompactGroupState, COMPACT_STATE_VERSION};
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn compact_state_round_trips() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        group.group.commit(vec![]).await.unwrap();
+        group.process_pending_commit().await.unwrap();
+
+        let bytes = group.group.write_to_storage_compact().await.unwrap();
+
+        let restored = TestClientBuilder::new_for_test()
+            .build()
+            .load_group_compact(&bytes)
+            .await
+            .unwrap();
+
+        assert!(Group::equal_group_state(&group.group, &restored));
+    }
+
+    #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn compact_state_includes_prior_epochs() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+
+        groups[1]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let bytes = groups[1].group.write_to_storage_compact().await.unwrap();
+
+        let mut restored = TestClientBuilder::new_for_test()
+            .build()
+            .load_group_compact(&bytes)
+            .await
+            .unwrap();
+
+        let received = restored.process_incoming_message(message).await.unwrap();
+
+        assert_matches!(
+            received,
+            crate::group::ReceivedMessage::ApplicationMessage(m) if m.data() == b"hello"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn corrupted_compact_state_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let bytes = groups[0].group.write_to_storage_compact().await.unwrap();
+        let client = TestClientBuilder::new_for_test().build();
+
+        let mut state = CompactGroupState::mls_decode(&mut &*bytes).unwrap();
+        *state.payload.last_mut().unwrap() ^= 1;
+
+        let res = client
+            .load_group_compact(&state.mls_encode_to_vec().unwrap())
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::InvalidCompactState));
+
+        state.version = COMPACT_STATE_VERSION + 1;
+
+        let res = client
+            .load_group_compact(&state.mls_encode_to_vec().unwrap())
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::UnsupportedCompactStateVersion(v)) if v == COMPACT_STATE_VERSION + 1);
+    }
+}
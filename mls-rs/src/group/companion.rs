@@ -0,0 +1,393 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::crypto::{CipherSuite, CryptoProvider, SignaturePublicKey};
+
+#[cfg(feature = "psk")]
+use crate::psk::PreSharedKey;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        ciphertext_processor::{CiphertextProcessor, GroupStateProvider},
+        epoch::{EpochSecrets, SenderDataSecret},
+        framing::{Content, ContentType, Sender},
+        message_processor::ApplicationMessageDescription,
+        message_verifier::{verify_auth_content_signature, SignaturePublicKeysContainer},
+        secret_tree::SecretTree,
+        Group, GroupContext,
+    },
+    tree_kem::node::{LeafIndex, NodeIndex},
+    MlsMessage,
+};
+
+/// Receive keys of a member for the current epoch, minted on the primary
+/// device of the member with [`Group::companion_bundle`] and loaded on a
+/// companion device with
+/// [`Client::companion_group`](crate::Client::companion_group).
+///
+/// The bundle contains the secret tree of the member, which allows to decrypt
+/// all messages of the epoch. It must only be transferred over a channel
+/// that is confidential and authenticated between the devices of the member.
+/// It contains no signature secret key, so a companion device can neither
+/// sign nor commit on behalf of the member.
+#[derive(Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+pub struct CompanionBundle {
+    context: GroupContext,
+    member_index: LeafIndex,
+    sender_data_secret: SenderDataSecret,
+    secret_tree: SecretTree<NodeIndex>,
+    signature_public_keys: Vec<Option<SignaturePublicKey>>,
+}
+
+impl Debug for CompanionBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompanionBundle")
+            .field(
+                "group_id",
+                &mls_rs_core::debug::pretty_group_id(&self.context.group_id),
+            )
+            .field("epoch", &self.context.epoch)
+            .field("member_index", &self.member_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CompanionBundle {
+    pub fn group_id(&self) -> &[u8] {
+        &self.context.group_id
+    }
+
+    /// Epoch whose messages can be decrypted with this bundle.
+    pub fn epoch(&self) -> u64 {
+        self.context.epoch
+    }
+
+    /// Index of the member that minted this bundle.
+    pub fn member_index(&self) -> u32 {
+        *self.member_index
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        Self::mls_decode(&mut &*bytes).map_err(Into::into)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Mint a [`CompanionBundle`] with the receive keys of this member for
+    /// the current epoch.
+    ///
+    /// A new bundle must be minted and passed to
+    /// [`CompanionGroup::refresh`] after every commit. Keys that this member
+    /// already used to decrypt a message are not included.
+    pub fn companion_bundle(&self) -> CompanionBundle {
+        CompanionBundle {
+            context: self.context().clone(),
+            member_index: self.private_tree.self_index,
+            sender_data_secret: self.epoch_secrets.sender_data_secret.clone(),
+            secret_tree: self.epoch_secrets.secret_tree.clone(),
+            signature_public_keys: self
+                .state
+                .public_tree
+                .leaves()
+                .map(|l| l.map(|n| n.signing_identity.signature_key.clone()))
+                .collect(),
+        }
+    }
+}
+
+struct CompanionState {
+    context: GroupContext,
+    member_index: LeafIndex,
+    secrets: EpochSecrets,
+    signature_public_keys: Vec<Option<SignaturePublicKey>>,
+}
+
+impl From<CompanionBundle> for CompanionState {
+    fn from(bundle: CompanionBundle) -> Self {
+        Self {
+            context: bundle.context,
+            member_index: bundle.member_index,
+            secrets: EpochSecrets {
+                #[cfg(feature = "psk")]
+                resumption_secret: PreSharedKey::new(Vec::new()),
+                sender_data_secret: bundle.sender_data_secret,
+                secret_tree: bundle.secret_tree,
+            },
+            signature_public_keys: bundle.signature_public_keys,
+        }
+    }
+}
+
+impl GroupStateProvider for CompanionState {
+    fn group_context(&self) -> &GroupContext {
+        &self.context
+    }
+
+    fn self_index(&self) -> LeafIndex {
+        self.member_index
+    }
+
+    fn epoch_secrets_mut(&mut self) -> &mut EpochSecrets {
+        &mut self.secrets
+    }
+
+    fn epoch_secrets(&self) -> &EpochSecrets {
+        &self.secrets
+    }
+}
+
+/// Receive-only view of a group on a companion device of a member.
+///
+/// A companion group is created from a [`CompanionBundle`] with
+/// [`Client::companion_group`](crate::Client::companion_group). It can
+/// decrypt application messages sent by other members in the epoch of the
+/// bundle, but it can not send messages, commit or process handshake
+/// messages. Messages sent by the member itself can not be decrypted, and
+/// neither can messages with a group id concealed by
+/// [`EncryptionOptions::with_hide_group_id`](crate::mls_rules::EncryptionOptions::with_hide_group_id).
+///
+/// Decrypting a message consumes its key as required by forward secrecy.
+pub struct CompanionGroup<C>
+where
+    C: ClientConfig,
+{
+    state: CompanionState,
+    cipher_suite_provider: <C::CryptoProvider as CryptoProvider>::CipherSuiteProvider,
+}
+
+impl<C> CompanionGroup<C>
+where
+    C: ClientConfig + Clone,
+{
+    pub(crate) fn new(config: &C, bundle: CompanionBundle) -> Result<Self, MlsError> {
+        let cipher_suite = bundle.context.cipher_suite;
+
+        let cipher_suite_provider = config
+            .crypto_provider()
+            .cipher_suite_provider(cipher_suite)
+            .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))?;
+
+        Ok(Self {
+            state: bundle.into(),
+            cipher_suite_provider,
+        })
+    }
+
+    pub fn group_id(&self) -> &[u8] {
+        &self.state.context.group_id
+    }
+
+    pub fn cipher_suite(&self) -> CipherSuite {
+        self.state.context.cipher_suite
+    }
+
+    /// Epoch of the bundle that this companion group was last refreshed with.
+    pub fn current_epoch(&self) -> u64 {
+        self.state.context.epoch
+    }
+
+    /// Index of the member that this companion group belongs to.
+    pub fn member_index(&self) -> u32 {
+        *self.state.member_index
+    }
+
+    /// Replace the receive keys with the keys of a newer `bundle` of the
+    /// same group and member.
+    ///
+    /// Keys of the previous epoch are discarded.
+    pub fn refresh(&mut self, bundle: CompanionBundle) -> Result<(), MlsError> {
+        if bundle.context.group_id != self.state.context.group_id
+            || bundle.member_index != self.state.member_index
+        {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        if bundle.context.epoch < self.state.context.epoch {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        self.state = bundle.into();
+
+        Ok(())
+    }
+
+    /// Decrypt an application message sent by another member in the
+    /// [current epoch](CompanionGroup::current_epoch).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn decrypt_application_message(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<ApplicationMessageDescription, MlsError> {
+        let ciphertext = message
+            .into_ciphertext()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        if ciphertext.content_type != ContentType::Application {
+            return Err(MlsError::UnexpectedMessageType);
+        }
+
+        if ciphertext.group_id != self.state.context.group_id {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        if ciphertext.epoch != self.state.context.epoch {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        let auth_content =
+            CiphertextProcessor::new(&mut self.state, self.cipher_suite_provider.clone())
+                .open(&ciphertext)
+                .await?;
+
+        verify_auth_content_signature(
+            &self.cipher_suite_provider,
+            SignaturePublicKeysContainer::List(&self.state.signature_public_keys),
+            &self.state.context,
+            &auth_content,
+            #[cfg(feature = "by_ref_proposal")]
+            &[],
+        )
+        .await?;
+
+        let Content::Application(data) = auth_content.content.content else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        let Sender::Member(sender_index) = auth_content.content.sender else {
+            return Err(MlsError::InvalidSender);
+        };
+
+        Ok(ApplicationMessageDescription {
+            sender_index,
+            data,
+            authenticated_data: auth_content.content.authenticated_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientBuilder,
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::CompanionBundle;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn companion_decrypts_messages_of_other_members() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let bundle = groups[1].group.companion_bundle();
+        let bundle = CompanionBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+
+        let mut companion = TestClientBuilder::new_for_test()
+            .build()
+            .companion_group(bundle)
+            .unwrap();
+
+        assert_eq!(companion.member_index(), 1);
+
+        for sender in [0, 2] {
+            let message = groups[sender]
+                .group
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+
+            let decrypted = companion
+                .decrypt_application_message(message)
+                .await
+                .unwrap();
+
+            assert_eq!(decrypted.sender_index, sender as u32);
+            assert_eq!(decrypted.data(), b"hello");
+        }
+
+        let own_message = groups[1]
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let res = companion.decrypt_application_message(own_message).await;
+        assert_matches!(res, Err(MlsError::CantProcessMessageFromSelf));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn companion_is_refreshed_after_commit() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let old_bundle = groups[1].group.companion_bundle();
+
+        let mut companion = TestClientBuilder::new_for_test()
+            .build()
+            .companion_group(old_bundle.clone())
+            .unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+        groups[1]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let res = companion.decrypt_application_message(message.clone()).await;
+        assert_matches!(res, Err(MlsError::InvalidEpoch));
+
+        companion
+            .refresh(groups[1].group.companion_bundle())
+            .unwrap();
+
+        assert_eq!(companion.current_epoch(), groups[1].group.current_epoch());
+        companion
+            .decrypt_application_message(message)
+            .await
+            .unwrap();
+
+        assert_matches!(companion.refresh(old_bundle), Err(MlsError::InvalidEpoch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn companion_rejects_handshake_messages() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let mut companion = TestClientBuilder::new_for_test()
+            .build()
+            .companion_group(groups[1].group.companion_bundle())
+            .unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap();
+
+        let res = companion
+            .decrypt_application_message(commit.commit_message)
+            .await;
+
+        assert_matches!(res, Err(MlsError::UnexpectedMessageType));
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{client_config::ClientConfig, group::Group};
+
+/// Selection of the conveniences that a client applies on top of RFC 9420.
+///
+/// In [`ComplianceMode::Strict`], the following settings are ignored and
+/// the client behaves exactly as specified by the RFC:
+///
+/// * Messages sent by this member and echoed back by the delivery service
+///   are not matched against the pending commit and cached proposals. Own
+///   commits are processed like any other commit and own encrypted proposals
+///   fail with
+///   [`MlsError::CantProcessMessageFromSelf`](crate::client::MlsError::CantProcessMessageFromSelf).
+/// * [`EncryptionOptions::with_hide_group_id`](crate::mls_rules::EncryptionOptions::with_hide_group_id)
+///   is ignored. Messages are sent with the group id and received messages
+///   with a concealed group id are rejected.
+/// * The decryption journal configured with
+///   [`ClientBuilder::decryption_journal_size`](crate::client_builder::ClientBuilder::decryption_journal_size)
+///   is disabled.
+/// * New members are always validated while processing the commit that adds
+///   them, even if
+///   [`ClientBuilder::quarantine_new_members`](crate::client_builder::ClientBuilder::quarantine_new_members)
+///   is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ComplianceMode {
+    /// Apply the conveniences enabled by the client configuration.
+    #[default]
+    Default,
+    /// Disable all conveniences that deviate from RFC 9420.
+    Strict,
+}
+
+impl ComplianceMode {
+    pub fn is_strict(&self) -> bool {
+        matches!(self, ComplianceMode::Strict)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    pub(crate) fn is_strict(&self) -> bool {
+        self.config.compliance_mode().is_strict()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::ReceivedMessage,
+    };
+
+    #[cfg(feature = "private_message")]
+    use crate::group::{
+        mls_rules::{DefaultMlsRules, EncryptionOptions},
+        padding::PaddingMode,
+        test_utils::{test_group_custom_config, TEST_GROUP},
+    };
+
+    use super::ComplianceMode;
+
+    #[cfg(feature = "private_message")]
+    fn hiding_rules(hide_group_id: bool) -> DefaultMlsRules {
+        DefaultMlsRules::default().with_encryption_options(
+            EncryptionOptions::new(true, PaddingMode::None).with_hide_group_id(hide_group_id),
+        )
+    }
+
+    #[test]
+    fn default_mode_is_not_strict() {
+        assert!(!ComplianceMode::default().is_strict());
+        assert!(ComplianceMode::Strict.is_strict());
+    }
+
+    #[cfg(all(feature = "private_message", feature = "by_ref_proposal"))]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn echo_own_proposal(mode: ComplianceMode) -> Result<ReceivedMessage, MlsError> {
+        let mut group = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(hiding_rules(false))
+        })
+        .await;
+
+        group.group.config.0.settings.compliance_mode = mode;
+
+        let proposal = group.group.propose_update(vec![]).await.unwrap();
+
+        group.group.process_incoming_message(proposal).await
+    }
+
+    #[cfg(all(feature = "private_message", feature = "by_ref_proposal"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn own_proposal_echo_is_recognized_in_default_mode() {
+        let res = echo_own_proposal(ComplianceMode::Default).await;
+        assert_matches!(res, Ok(ReceivedMessage::Proposal(_)));
+    }
+
+    #[cfg(all(feature = "private_message", feature = "by_ref_proposal"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn own_proposal_echo_is_rejected_in_strict_mode() {
+        let res = echo_own_proposal(ComplianceMode::Strict).await;
+        assert_matches!(res, Err(MlsError::CantProcessMessageFromSelf));
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn strict_mode_uses_rfc_group_id() {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(hiding_rules(true))
+        })
+        .await;
+
+        let (mut bob, _) = alice.join("bob").await;
+        bob.group.config.0.settings.compliance_mode = ComplianceMode::Strict;
+
+        let concealed = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        assert_ne!(concealed.group_id(), Some(TEST_GROUP));
+
+        let res = bob.process_message(concealed).await;
+        assert_matches!(res, Err(MlsError::GroupIdMismatch));
+
+        alice.group.config.0.settings.compliance_mode = ComplianceMode::Strict;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        assert_eq!(message.group_id(), Some(TEST_GROUP));
+        bob.process_message(message).await.unwrap();
+    }
+
+    #[cfg(feature = "decryption_journal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn strict_mode_disables_decryption_journal() {
+        use crate::group::test_utils::test_n_member_group;
+
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        groups[1].group.config.0.settings.decryption_journal_size = 10;
+        groups[1].group.config.0.settings.compliance_mode = ComplianceMode::Strict;
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        groups[1].process_message(message.clone()).await.unwrap();
+        let res = groups[1].process_message(message).await;
+
+        assert_matches!(res, Err(MlsError::KeyMissing(0)));
+    }
+
+    #[cfg(feature = "member_quarantine")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn strict_mode_disables_member_quarantine() {
+        use crate::group::test_utils::test_n_member_group;
+
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        groups[1].group.config.0.settings.quarantine_new_members = true;
+        groups[1].group.config.0.settings.compliance_mode = ComplianceMode::Strict;
+
+        let (_, commit) = groups[0].join("carol").await;
+        groups[1].process_message(commit).await.unwrap();
+
+        assert!(groups[1].group.quarantined_members().is_empty());
+    }
+}
@@ -36,6 +36,12 @@ impl Deref for ConfirmationTag {
     }
 }
 
+impl From<Vec<u8>> for ConfirmationTag {
+    fn from(tag: Vec<u8>) -> Self {
+        Self(tag)
+    }
+}
+
 impl ConfirmationTag {
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub(crate) async fn create<P: CipherSuiteProvider>(
@@ -0,0 +1,202 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{extension::ExtensionList, group::Member};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::{AcceptedMediaTypesExt, MediaType, RequiredMediaTypesExt},
+    group::Group,
+};
+
+/// Media types advertised by a [`Member`] in its [`AcceptedMediaTypesExt`]
+/// leaf node extension.
+pub trait MemberMediaTypes {
+    /// Media types accepted by the member, in its order of preference.
+    fn accepted_media_types(&self) -> Result<Vec<MediaType>, MlsError>;
+
+    /// Determine if the member accepts `media_type`.
+    fn accepts_media_type(&self, media_type: &MediaType) -> Result<bool, MlsError> {
+        Ok(self.accepted_media_types()?.contains(media_type))
+    }
+}
+
+impl MemberMediaTypes for Member {
+    fn accepted_media_types(&self) -> Result<Vec<MediaType>, MlsError> {
+        accepted_media_types(&self.extensions)
+    }
+}
+
+pub(crate) fn accepted_media_types(
+    leaf_node_extensions: &ExtensionList,
+) -> Result<Vec<MediaType>, MlsError> {
+    Ok(leaf_node_extensions
+        .get_as::<AcceptedMediaTypesExt>()?
+        .map(|ext| ext.media_types)
+        .unwrap_or_default())
+}
+
+/// Media types of `preferred` accepted by all members with the
+/// `leaf_node_extensions`, in the order of `preferred`.
+pub(crate) fn common_media_types<'a>(
+    preferred: Vec<MediaType>,
+    leaf_node_extensions: impl IntoIterator<Item = &'a ExtensionList>,
+) -> Result<Vec<MediaType>, MlsError> {
+    leaf_node_extensions
+        .into_iter()
+        .try_fold(preferred, |mut common, extensions| {
+            let accepted = accepted_media_types(extensions)?;
+            common.retain(|media_type| accepted.contains(media_type));
+            Ok(common)
+        })
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Media types every member must accept, as defined by the
+    /// [`RequiredMediaTypesExt`] group context extension.
+    pub fn required_media_types(&self) -> Result<Vec<MediaType>, MlsError> {
+        Ok(self
+            .context()
+            .extensions
+            .get_as::<RequiredMediaTypesExt>()?
+            .map(|ext| ext.media_types)
+            .unwrap_or_default())
+    }
+
+    /// Media types accepted by every current member, in the order of
+    /// preference of this member.
+    ///
+    /// The result can be used to choose the format of application messages,
+    /// or be required for future members by committing a
+    /// [`RequiredMediaTypesExt`] using
+    /// [`CommitBuilder::set_group_context_ext`](crate::group::CommitBuilder::set_group_context_ext).
+    pub fn common_media_types(&self) -> Result<Vec<MediaType>, MlsError> {
+        let preferred = accepted_media_types(&self.current_user_leaf_node()?.extensions)?;
+
+        common_media_types(
+            preferred,
+            self.current_epoch_tree()
+                .non_empty_leaves()
+                .map(|(_, leaf)| &leaf.extensions),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::extension::{ExtensionList, ExtensionType};
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        client_builder::test_utils::{TestClientBuilder, TestClientConfig},
+        extension::{
+            AcceptedMediaTypesExt, MediaType, ACCEPTED_MEDIA_TYPES_EXTENSION_TYPE,
+            REQUIRED_MEDIA_TYPES_EXTENSION_TYPE,
+        },
+        identity::test_utils::get_test_signing_identity,
+        Client,
+    };
+
+    use super::MemberMediaTypes;
+
+    fn media_types(names: &[&str]) -> Vec<MediaType> {
+        names.iter().map(|name| MediaType::new(name)).collect()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str, accepted: &[&str]) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        let mut leaf_node_extensions = ExtensionList::new();
+
+        leaf_node_extensions
+            .set_from(AcceptedMediaTypesExt::new(media_types(accepted)))
+            .unwrap();
+
+        TestClientBuilder::new_for_test()
+            .extension_types(vec![
+                ExtensionType::new(ACCEPTED_MEDIA_TYPES_EXTENSION_TYPE),
+                ExtensionType::new(REQUIRED_MEDIA_TYPES_EXTENSION_TYPE),
+            ])
+            .leaf_node_extensions(leaf_node_extensions)
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn media_types_are_negotiated_at_group_creation() {
+        let alice = test_client("alice", &["text/markdown", "text/plain", "image/png"]).await;
+        let bob = test_client("bob", &["image/png", "text/plain"]).await;
+        let carol = test_client("carol", &["text/markdown"]).await;
+
+        let bob_key_package = bob.generate_key_package_message().await.unwrap();
+
+        let required = alice
+            .negotiate_media_types(&[bob_key_package.clone()])
+            .unwrap();
+
+        assert_eq!(
+            required.media_types,
+            media_types(&["text/plain", "image/png"])
+        );
+
+        let mut extensions = ExtensionList::new();
+        extensions.set_from(required).unwrap();
+
+        let mut group = alice.create_group(extensions).await.unwrap();
+
+        group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        group.apply_pending_commit().await.unwrap();
+
+        assert_eq!(
+            group.required_media_types().unwrap(),
+            media_types(&["text/plain", "image/png"])
+        );
+
+        assert_eq!(
+            group.common_media_types().unwrap(),
+            media_types(&["text/plain", "image/png"])
+        );
+
+        let bob_member = group.roster().member_with_index(1).unwrap();
+
+        assert!(bob_member
+            .accepts_media_type(&MediaType::new("image/png"))
+            .unwrap());
+
+        assert!(!bob_member
+            .accepts_media_type(&MediaType::new("text/markdown"))
+            .unwrap());
+
+        let carol_key_package = carol.generate_key_package_message().await.unwrap();
+
+        let res = group
+            .commit_builder()
+            .add_member(carol_key_package)
+            .unwrap()
+            .build()
+            .await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::RequiredMediaTypeNotAccepted(media_type))
+                if media_type == MediaType::new("text/plain")
+        );
+    }
+}
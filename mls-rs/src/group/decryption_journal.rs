@@ -0,0 +1,253 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{crypto::CipherSuiteProvider, error::IntoAnyError};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        ciphertext_processor::CiphertextProcessor,
+        framing::{Content, ContentType, PrivateMessage},
+        message_processor::EventOrContent,
+        Group, ReceivedMessage,
+    },
+};
+
+/// Application message that was already decrypted by this client.
+///
+/// Returned as [`ReceivedMessage::AlreadyProcessed`] when a ciphertext is
+/// processed again after its first successful decryption was recorded in
+/// the decryption journal, see
+/// [`ClientBuilder::decryption_journal_size`](crate::client_builder::ClientBuilder::decryption_journal_size).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AlreadyProcessedMessage {
+    /// Epoch in which the message was sent.
+    pub epoch: u64,
+    /// Leaf index of the sender.
+    pub sender_index: u32,
+    /// Generation of the application secret ratchet of the sender used to
+    /// encrypt the message.
+    pub generation: u32,
+    /// Hash of the application data, computed with the hash function of the
+    /// cipher suite of the group when the message was first decrypted.
+    pub plaintext_hash: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct DecryptionReceipt {
+    epoch: u64,
+    sender: u32,
+    generation: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
+    plaintext_hash: Vec<u8>,
+}
+
+/// Bounded list of receipts of decrypted application messages, oldest first.
+///
+/// The journal is part of the group snapshot and therefore only survives a
+/// restart if the group is written to storage after processing a message.
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct DecryptionJournal {
+    receipts: Vec<DecryptionReceipt>,
+}
+
+impl DecryptionJournal {
+    fn find(&self, epoch: u64, sender: u32, generation: u32) -> Option<&DecryptionReceipt> {
+        self.receipts
+            .iter()
+            .find(|r| r.epoch == epoch && r.sender == sender && r.generation == generation)
+    }
+
+    fn insert(&mut self, receipt: DecryptionReceipt, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+
+        if self.receipts.len() >= capacity {
+            self.receipts.drain(..=self.receipts.len() - capacity);
+        }
+
+        self.receipts.push(receipt);
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(super) async fn process_ciphertext_with_journal(
+        &mut self,
+        message: &PrivateMessage,
+    ) -> Result<EventOrContent<ReceivedMessage>, MlsError> {
+        let capacity = if self.is_strict() {
+            0
+        } else {
+            self.config.decryption_journal_size()
+        };
+
+        if capacity == 0 || message.content_type != ContentType::Application {
+            return self
+                .decrypt_incoming_ciphertext(message)
+                .await
+                .map(EventOrContent::Content);
+        }
+
+        let sender_data = self.journal_sender_data(message).await?;
+
+        if let Some((sender, generation)) = sender_data {
+            if let Some(receipt) = self
+                .decryption_journal
+                .find(message.epoch, sender, generation)
+            {
+                let processed = AlreadyProcessedMessage {
+                    epoch: receipt.epoch,
+                    sender_index: receipt.sender,
+                    generation: receipt.generation,
+                    plaintext_hash: receipt.plaintext_hash.clone(),
+                };
+
+                return Ok(EventOrContent::Event(ReceivedMessage::AlreadyProcessed(
+                    processed,
+                )));
+            }
+        }
+
+        let auth_content = self.decrypt_incoming_ciphertext(message).await?;
+
+        if let (Some((sender, generation)), Content::Application(data)) =
+            (sender_data, &auth_content.content.content)
+        {
+            let plaintext_hash = self
+                .cipher_suite_provider
+                .hash(data.as_bytes())
+                .await
+                .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+            let receipt = DecryptionReceipt {
+                epoch: message.epoch,
+                sender,
+                generation,
+                plaintext_hash,
+            };
+
+            self.decryption_journal.insert(receipt, capacity);
+        }
+
+        Ok(EventOrContent::Content(auth_content))
+    }
+
+    // Sender and generation of `message`, or `None` if the sender data can't
+    // be decrypted. In that case, the error is reported by the regular
+    // decryption path.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn journal_sender_data(
+        &mut self,
+        message: &PrivateMessage,
+    ) -> Result<Option<(u32, u32)>, MlsError> {
+        let sender_data = if message.epoch == self.context().epoch {
+            CiphertextProcessor::new(self, self.cipher_suite_provider.clone())
+                .open_sender_data(message)
+                .await
+        } else {
+            #[cfg(feature = "prior_epoch")]
+            {
+                let Some(epoch) = self.state_repo.get_epoch_mut(message.epoch).await? else {
+                    return Ok(None);
+                };
+
+                CiphertextProcessor::new(epoch, self.cipher_suite_provider.clone())
+                    .open_sender_data(message)
+                    .await
+            }
+
+            #[cfg(not(feature = "prior_epoch"))]
+            return Ok(None);
+        };
+
+        Ok(sender_data
+            .ok()
+            .map(|sender_data| (*sender_data.sender, sender_data.generation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::crypto::CipherSuiteProvider;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        crypto::test_utils::test_cipher_suite_provider,
+        group::{test_utils::test_group, ReceivedMessage},
+    };
+
+    use super::{DecryptionJournal, DecryptionReceipt};
+
+    fn receipt(generation: u32) -> DecryptionReceipt {
+        DecryptionReceipt {
+            epoch: 1,
+            sender: 0,
+            generation,
+            plaintext_hash: vec![],
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn replayed_message_is_already_processed() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob, _) = alice
+            .join_with_custom_config("bob", false, |c| c.0.settings.decryption_journal_size = 16)
+            .await
+            .unwrap();
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let received = bob
+            .group
+            .process_incoming_message(message.clone())
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedMessage::ApplicationMessage(_));
+
+        let received = bob.group.process_incoming_message(message).await.unwrap();
+
+        let expected_hash = test_cipher_suite_provider(TEST_CIPHER_SUITE)
+            .hash(b"hello")
+            .await
+            .unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedMessage::AlreadyProcessed(processed)
+                if processed.sender_index == 0 && processed.plaintext_hash == expected_hash
+        );
+    }
+
+    #[test]
+    fn journal_evicts_oldest_receipts() {
+        let mut journal = DecryptionJournal::default();
+
+        (0..5).for_each(|generation| journal.insert(receipt(generation), 3));
+
+        assert!(journal.find(1, 0, 1).is_none());
+        assert!(journal.find(1, 0, 2).is_some());
+        assert!(journal.find(1, 0, 4).is_some());
+        assert_eq!(journal.receipts.len(), 3);
+    }
+}
@@ -14,7 +14,9 @@ use core::{
     ops::Deref,
 };
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
-use zeroize::Zeroizing;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::crypto::secret_material::SecretMaterial;
 
 #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
 use super::ciphertext_processor::GroupStateProvider;
@@ -30,6 +32,11 @@ pub(crate) struct PriorEpoch {
     pub(crate) self_index: LeafIndex,
     pub(crate) secrets: EpochSecrets,
     pub(crate) signature_public_keys: Vec<Option<SignaturePublicKey>>,
+    #[cfg(feature = "epoch_history")]
+    pub(crate) leaves: Vec<Option<crate::tree_kem::leaf_node::LeafNode>>,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::zeroizing_serde"))]
+    pub(crate) epoch_authenticator: Zeroizing<Vec<u8>>,
 }
 
 #[cfg(feature = "prior_epoch")]
@@ -45,6 +52,25 @@ impl PriorEpoch {
     }
 }
 
+#[cfg(feature = "prior_epoch")]
+impl ZeroizeOnDrop for PriorEpoch {}
+
+#[cfg(feature = "prior_epoch")]
+impl Zeroize for PriorEpoch {
+    fn zeroize(&mut self) {
+        self.secrets.zeroize();
+        self.epoch_authenticator.zeroize();
+    }
+}
+
+#[cfg(feature = "prior_epoch")]
+impl SecretMaterial for PriorEpoch {
+    #[cfg(any(test, feature = "zeroize_audit"))]
+    fn is_wiped(&self) -> bool {
+        self.secrets.is_wiped() && self.epoch_authenticator.is_empty()
+    }
+}
+
 #[cfg(all(feature = "private_message", feature = "prior_epoch"))]
 impl GroupStateProvider for PriorEpoch {
     fn group_context(&self) -> &GroupContext {
@@ -76,6 +102,38 @@ pub(crate) struct EpochSecrets {
     pub(crate) secret_tree: SecretTree<NodeIndex>,
 }
 
+impl ZeroizeOnDrop for EpochSecrets {}
+
+impl Zeroize for EpochSecrets {
+    fn zeroize(&mut self) {
+        #[cfg(feature = "psk")]
+        {
+            self.resumption_secret = PreSharedKey::from(Vec::new());
+        }
+
+        self.sender_data_secret.0.zeroize();
+
+        #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+        self.secret_tree.zeroize();
+    }
+}
+
+impl SecretMaterial for EpochSecrets {
+    #[cfg(any(test, feature = "zeroize_audit"))]
+    fn is_wiped(&self) -> bool {
+        #[cfg(feature = "psk")]
+        let wiped = self.resumption_secret.is_empty();
+
+        #[cfg(not(feature = "psk"))]
+        let wiped = true;
+
+        #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+        let wiped = wiped && self.secret_tree.is_wiped();
+
+        wiped && self.sender_data_secret.is_empty()
+    }
+}
+
 #[derive(Clone, PartialEq, MlsEncode, MlsDecode, MlsSize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct SenderDataSecret(
@@ -84,6 +142,8 @@ pub(crate) struct SenderDataSecret(
     Zeroizing<Vec<u8>>,
 );
 
+impl ZeroizeOnDrop for SenderDataSecret {}
+
 impl Debug for SenderDataSecret {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         mls_rs_core::debug::pretty_bytes(&self.0)
@@ -160,6 +220,9 @@ pub(crate) mod test_utils {
             self_index: LeafIndex(0),
             secrets: get_test_epoch_secrets(cipher_suite),
             signature_public_keys: Default::default(),
+            #[cfg(feature = "epoch_history")]
+            leaves: Default::default(),
+            epoch_authenticator: Default::default(),
         }
     }
 }
@@ -0,0 +1,170 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::MlsDecode;
+use mls_rs_core::{error::IntoAnyError, extension::ExtensionList, group::GroupStateStorage};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::MlsError,
+    group::{
+        epoch::PriorEpoch, roster::member_from_leaf_node, snapshot::Snapshot, GroupContext, Member,
+    },
+    tree_kem::node::LeafIndex,
+};
+
+/// Read-only view of the public state of a group at a past epoch.
+///
+/// A historical epoch is loaded with
+/// [`Client::load_group_at_epoch`](crate::Client::load_group_at_epoch) and
+/// contains only the group context and the roster of the epoch. No secrets
+/// of the epoch are kept, so the view can be handed to support and audit
+/// tooling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoricalEpoch {
+    context: GroupContext,
+    members: Vec<Member>,
+}
+
+impl HistoricalEpoch {
+    fn from_prior_epoch(epoch: &PriorEpoch) -> Self {
+        let members = epoch
+            .leaves
+            .iter()
+            .zip(0..)
+            .filter_map(|(leaf, index)| {
+                leaf.as_ref()
+                    .map(|leaf| member_from_leaf_node(leaf, LeafIndex(index)))
+            })
+            .collect();
+
+        Self {
+            context: epoch.context.clone(),
+            members,
+        }
+    }
+
+    fn from_snapshot(snapshot: &Snapshot) -> Self {
+        Self {
+            context: snapshot.state.context.clone(),
+            members: snapshot.state.public_tree.roster().members(),
+        }
+    }
+
+    /// Group context of the epoch.
+    pub fn context(&self) -> &GroupContext {
+        &self.context
+    }
+
+    /// Id of the group.
+    pub fn group_id(&self) -> &[u8] {
+        &self.context.group_id
+    }
+
+    /// Epoch id.
+    pub fn epoch(&self) -> u64 {
+        self.context.epoch
+    }
+
+    /// Group context extensions in use in the epoch.
+    pub fn extensions(&self) -> &ExtensionList {
+        &self.context.extensions
+    }
+
+    /// Members of the group in the epoch.
+    pub fn roster(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// Member with the given leaf `index` in the epoch.
+    pub fn member_at_index(&self, index: u32) -> Option<&Member> {
+        self.members.iter().find(|member| member.index == index)
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn load_historical_epoch<S: GroupStateStorage>(
+    storage: &S,
+    group_id: &[u8],
+    epoch_id: u64,
+) -> Result<HistoricalEpoch, MlsError> {
+    let state = storage
+        .state(group_id)
+        .await
+        .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+        .map(Zeroizing::new)
+        .ok_or(MlsError::GroupNotFound)?;
+
+    let snapshot = Snapshot::mls_decode(&mut &**state)?;
+
+    if snapshot.state.context.epoch == epoch_id {
+        return Ok(HistoricalEpoch::from_snapshot(&snapshot));
+    }
+
+    let epoch = storage
+        .epoch(group_id, epoch_id)
+        .await
+        .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+        .map(Zeroizing::new)
+        .ok_or(MlsError::EpochNotFound)?;
+
+    let epoch = PriorEpoch::mls_decode(&mut &**epoch)?;
+
+    Ok(HistoricalEpoch::from_prior_epoch(&epoch))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientBuilder,
+        client_config::ClientConfig,
+        group::test_utils::test_group,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn loads_roster_of_past_epochs() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (bob, _) = alice.join("bob").await;
+
+        alice.group.commit(vec![]).await.unwrap();
+        alice.process_pending_commit().await.unwrap();
+        alice.group.write_to_storage().await.unwrap();
+
+        let auditor = TestClientBuilder::new_for_test()
+            .group_state_storage(alice.group.config.group_state_storage())
+            .build();
+
+        let group_id = alice.group.group_id().to_vec();
+
+        let initial = auditor.load_group_at_epoch(&group_id, 0).await.unwrap();
+
+        assert_eq!(initial.epoch(), 0);
+        assert_eq!(initial.roster().len(), 1);
+        assert!(initial.member_at_index(1).is_none());
+
+        let joined = auditor.load_group_at_epoch(&group_id, 1).await.unwrap();
+
+        assert_eq!(joined.roster(), bob.group.roster().members());
+        assert_eq!(joined.context(), bob.group.context());
+
+        let current = auditor.load_group_at_epoch(&group_id, 2).await.unwrap();
+
+        assert_eq!(current.context(), alice.group.context());
+        assert_eq!(current.roster(), alice.group.roster().members());
+
+        let res = auditor.load_group_at_epoch(&group_id, 3).await;
+        assert_matches!(res, Err(MlsError::EpochNotFound));
+
+        let res = auditor.load_group_at_epoch(b"unknown", 0).await;
+        assert_matches!(res, Err(MlsError::GroupNotFound));
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use core::fmt::{self, Debug};
+use mls_rs_core::{
+    crypto::CipherSuite,
+    group::{Member, MemberUpdate},
+};
+use std::sync::Arc;
+
+#[cfg(feature = "psk")]
+use mls_rs_core::psk::ExternalPskId;
+
+use crate::{
+    client_config::ClientConfig,
+    group::{CommitMessageDescription, Group},
+};
+
+/// Callbacks invoked for every commit applied to a group of a client, set
+/// with
+/// [`ClientBuilder::group_event_listener`](crate::client_builder::ClientBuilder::group_event_listener).
+///
+/// Callbacks are invoked synchronously once the commit is applied, both for
+/// received commits and for own commits applied with
+/// [`Group::apply_pending_commit`], in the order of the declarations of
+/// this trait. They should therefore be cheap. All callbacks do nothing by
+/// default.
+pub trait GroupEventListener: Send + Sync {
+    /// `member` was added to the group `group_id`.
+    fn on_member_added(&self, _group_id: &[u8], _member: &Member) {}
+
+    /// `member` was removed from the group `group_id`, or left it.
+    fn on_member_removed(&self, _group_id: &[u8], _member: &Member) {}
+
+    /// A member of the group `group_id` updated its leaf.
+    fn on_member_updated(&self, _group_id: &[u8], _update: &MemberUpdate) {}
+
+    /// The group `group_id` is pending reinitialization with
+    /// `cipher_suite`.
+    fn on_pending_reinit(&self, _group_id: &[u8], _cipher_suite: CipherSuite) {}
+
+    /// The external pre-shared key `psk_id` was injected into the key
+    /// schedule of the group `group_id`.
+    #[cfg(feature = "psk")]
+    fn on_psk_used(&self, _group_id: &[u8], _psk_id: &ExternalPskId) {}
+
+    /// The group `group_id` advanced to `epoch`.
+    fn on_epoch_advanced(&self, _group_id: &[u8], _epoch: u64) {}
+
+    /// The local member was removed from the group `group_id`, which is now
+    /// [revoked](crate::group::MembershipStatus::Revoked).
+    fn on_membership_revoked(&self, _group_id: &[u8]) {}
+}
+
+#[derive(Clone)]
+pub(crate) struct SharedGroupEventListener(pub(crate) Arc<dyn GroupEventListener>);
+
+impl Debug for SharedGroupEventListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedGroupEventListener")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    pub(crate) fn notify_group_event_listener(&self, commit: &CommitMessageDescription) {
+        let Some(listener) = self.config.group_event_listener() else {
+            return;
+        };
+
+        let group_id = self.group_id();
+        let update = &commit.state_update;
+        let roster_update = update.roster_update();
+
+        for member in roster_update.added() {
+            listener.on_member_added(group_id, member);
+        }
+
+        for member in roster_update.removed() {
+            listener.on_member_removed(group_id, member);
+        }
+
+        for member_update in roster_update.updated() {
+            listener.on_member_updated(group_id, member_update);
+        }
+
+        if let Some(cipher_suite) = update.pending_reinit_ciphersuite() {
+            listener.on_pending_reinit(group_id, cipher_suite);
+        }
+
+        #[cfg(feature = "psk")]
+        for psk_id in update.added_psks() {
+            listener.on_psk_used(group_id, psk_id);
+        }
+
+        listener.on_epoch_advanced(group_id, update.new_epoch());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use mls_rs_core::group::{Member, MemberUpdate};
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client_builder::test_utils::TestClientBuilder,
+        client_config::ClientConfig,
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::GroupEventListener;
+
+    #[derive(Clone, Default)]
+    struct RecordingListener(Arc<Mutex<Vec<String>>>);
+
+    impl GroupEventListener for RecordingListener {
+        fn on_member_added(&self, _: &[u8], member: &Member) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("added {}", member.index));
+        }
+
+        fn on_member_removed(&self, _: &[u8], member: &Member) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("removed {}", member.index));
+        }
+
+        fn on_member_updated(&self, _: &[u8], update: &MemberUpdate) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("updated {}", update.new.index));
+        }
+
+        fn on_epoch_advanced(&self, _: &[u8], epoch: u64) {
+            self.0.lock().unwrap().push(format!("epoch {epoch}"));
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn listener_receives_roster_changes() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let listener = RecordingListener::default();
+
+        let group_id = groups[0].group.group_id().to_vec();
+        groups[0].group.write_to_storage().await.unwrap();
+
+        let mut observer = TestClientBuilder::new_for_test()
+            .group_state_storage(groups[0].group.config.group_state_storage())
+            .group_event_listener(listener.clone())
+            .build()
+            .load_group(&group_id)
+            .await
+            .unwrap();
+
+        let commit = groups[1]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        observer
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        observer.commit(vec![]).await.unwrap();
+        observer.apply_pending_commit().await.unwrap();
+
+        let events = listener.0.lock().unwrap().clone();
+
+        assert_eq!(
+            events,
+            ["removed 2", "updated 1", "epoch 3", "updated 0", "epoch 4"].map(ToString::to_string)
+        );
+    }
+}
@@ -7,6 +7,26 @@ use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 
 use crate::{client::MlsError, tree_kem::node::NodeVec};
 
+#[cfg(feature = "std")]
+use mls_rs_codec::VarInt;
+
+#[cfg(feature = "std")]
+use mls_rs_core::error::IntoAnyError;
+
+#[cfg(feature = "std")]
+use crate::tree_kem::node::Node;
+
+#[cfg(feature = "std")]
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// Maximum size in bytes of a serialized tree decompressed by
+/// [`ExportedTree::from_compressed_bytes`].
+#[cfg(feature = "tree_compression")]
+pub const MAX_DECOMPRESSED_TREE_SIZE: usize = 64 * 1024 * 1024;
+
+#[cfg(feature = "tree_compression")]
+const COMPRESSION_LEVEL: u8 = 6;
+
 #[cfg_attr(
     all(feature = "ffi", not(test)),
     safer_ffi_gen::ffi_type(clone, opaque)
@@ -44,8 +64,182 @@ impl ExportedTree<'static> {
     }
 }
 
+#[cfg(feature = "tree_compression")]
+impl ExportedTree<'_> {
+    /// Serialize the tree like [`ExportedTree::to_bytes`] and compress the
+    /// result with deflate.
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        Ok(miniz_oxide::deflate::compress_to_vec(
+            &self.to_bytes()?,
+            COMPRESSION_LEVEL,
+        ))
+    }
+}
+
+#[cfg(feature = "tree_compression")]
+impl ExportedTree<'static> {
+    /// Decompress and deserialize a tree produced by
+    /// [`ExportedTree::to_compressed_bytes`].
+    ///
+    /// Fails if the serialized tree is larger than
+    /// [`MAX_DECOMPRESSED_TREE_SIZE`].
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        let bytes =
+            miniz_oxide::inflate::decompress_to_vec_with_limit(bytes, MAX_DECOMPRESSED_TREE_SIZE)
+                .map_err(|_| MlsError::InvalidCompressedTree)?;
+
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExportedTree<'_> {
+    /// Write the serialized tree to `writer` one node at a time.
+    ///
+    /// The output is identical to [`ExportedTree::to_bytes`].
+    pub fn write_to<W: std::io::Write>(&self, writer: &mut W) -> Result<(), MlsError> {
+        let content_len = self.0.iter().map(MlsSize::mls_encoded_len).sum::<usize>();
+
+        let mut buffer = VarInt::try_from(content_len)?.mls_encode_to_vec()?;
+        writer.write_all(&buffer).map_err(io_error)?;
+
+        for node in self.0.iter() {
+            buffer.clear();
+            node.mls_encode(&mut buffer)?;
+            writer.write_all(&buffer).map_err(io_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExportedTree<'static> {
+    /// Read a serialized tree, as produced by [`ExportedTree::write_to`] or
+    /// [`ExportedTree::to_bytes`], from `reader`.
+    ///
+    /// Nodes are decoded as soon as they are read, so only the decoded tree
+    /// is kept in memory rather than both the tree and its encoding.
+    pub fn read_from<R: std::io::Read>(reader: &mut R) -> Result<Self, MlsError> {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header[..1]).map_err(io_error)?;
+
+        let header_len = match header[0] >> 6 {
+            0 => 1,
+            1 => 2,
+            2 => 4,
+            prefix => return Err(mls_rs_codec::Error::InvalidVarIntPrefix(prefix).into()),
+        };
+
+        reader
+            .read_exact(&mut header[1..header_len])
+            .map_err(io_error)?;
+
+        let mut unread = u32::from(VarInt::mls_decode(&mut &header[..header_len])?) as usize;
+
+        let mut buffer = Vec::new();
+        let mut nodes = Vec::new();
+
+        while unread > 0 || !buffer.is_empty() {
+            let mut remaining = &*buffer;
+
+            match Option::<Node>::mls_decode(&mut remaining) {
+                Ok(node) => {
+                    let consumed = buffer.len() - remaining.len();
+                    nodes.push(node);
+                    buffer.drain(..consumed);
+                }
+                Err(mls_rs_codec::Error::UnexpectedEOF) if unread > 0 => {
+                    let chunk_len = unread.min(READ_CHUNK_SIZE);
+                    let start = buffer.len();
+
+                    buffer.resize(start + chunk_len, 0);
+                    reader.read_exact(&mut buffer[start..]).map_err(io_error)?;
+
+                    unread -= chunk_len;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(Self::new(NodeVec::from(nodes)))
+    }
+}
+
+#[cfg(feature = "std")]
+fn io_error(e: std::io::Error) -> MlsError {
+    MlsError::SerializationError(e.into_any_error())
+}
+
 impl From<ExportedTree<'_>> for NodeVec {
     fn from(value: ExportedTree) -> Self {
         value.0.into_owned()
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::vec::Vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_group,
+    };
+
+    use super::ExportedTree;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn streamed_tree_matches_serialized_tree() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice.join("bob").await;
+        alice.join("carol").await;
+
+        let tree = alice.group.export_tree();
+
+        let mut streamed = Vec::new();
+        alice.group.export_tree_to(&mut streamed).unwrap();
+
+        assert_eq!(streamed, tree.to_bytes().unwrap());
+
+        let imported = ExportedTree::read_from(&mut &*streamed).unwrap();
+
+        assert_eq!(imported, tree.into_owned());
+    }
+
+    #[cfg(feature = "tree_compression")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn compressed_tree_round_trips() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice.join("bob").await;
+
+        let tree = alice.group.export_tree();
+        let compressed = tree.to_compressed_bytes().unwrap();
+
+        assert_eq!(
+            ExportedTree::from_compressed_bytes(&compressed).unwrap(),
+            tree.into_owned()
+        );
+
+        assert_matches!(
+            ExportedTree::from_compressed_bytes(&[0xff; 8]),
+            Err(MlsError::InvalidCompressedTree)
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn truncated_stream_is_rejected() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let bytes = alice.group.export_tree().to_bytes().unwrap();
+        let truncated = &bytes[..bytes.len() - 1];
+
+        assert_matches!(
+            ExportedTree::read_from(&mut &*truncated),
+            Err(MlsError::SerializationError(_))
+        );
+    }
+}
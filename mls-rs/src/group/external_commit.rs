@@ -6,6 +6,7 @@ use mls_rs_core::{crypto::SignatureSecretKey, identity::SigningIdentity};
 
 use crate::{
     client_config::ClientConfig,
+    extension::JoinTicketExt,
     group::{
         cipher_suite_provider,
         epoch::SenderDataSecret,
@@ -49,6 +50,7 @@ pub struct ExternalCommitBuilder<C: ClientConfig> {
     config: C,
     tree_data: Option<ExportedTree<'static>>,
     to_remove: Option<u32>,
+    join_ticket: Option<JoinTicketExt>,
     #[cfg(feature = "psk")]
     external_psks: Vec<ExternalPskId>,
     authenticated_data: Vec<u8>,
@@ -67,6 +69,7 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
         Self {
             tree_data: None,
             to_remove: None,
+            join_ticket: None,
             authenticated_data: Vec::new(),
             signer,
             signing_identity,
@@ -100,6 +103,18 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
         }
     }
 
+    #[must_use]
+    /// Present a join ticket that was delivered outside of the GroupInfo message.
+    ///
+    /// By default, the [`JoinTicketExt`] contained in the GroupInfo message is
+    /// used, if any.
+    pub fn with_join_ticket(self, ticket: JoinTicketExt) -> Self {
+        Self {
+            join_ticket: Some(ticket),
+            ..self
+        }
+    }
+
     #[must_use]
     /// Add plaintext authenticated data to the resulting commit message.
     pub fn with_authenticated_data(self, data: Vec<u8>) -> Self {
@@ -112,6 +127,11 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
     #[cfg(feature = "psk")]
     #[must_use]
     /// Add an external psk to the group as part of the external commit.
+    ///
+    /// This allows joining groups that require a proof of knowledge of a
+    /// pre-shared key at join time. The psk must be present in the
+    /// [`PreSharedKeyStorage`](crate::PreSharedKeyStorage) of the joiner and
+    /// of the existing members.
     pub fn with_external_psk(mut self, psk: ExternalPskId) -> Self {
         self.external_psks.push(psk);
         self
@@ -172,9 +192,20 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
         )
         .await?;
 
+        let join_ticket = match self.join_ticket {
+            Some(ticket) => Some(ticket),
+            None => group_info.extensions.get_as::<JoinTicketExt>()?,
+        };
+
+        let mut leaf_properties = self.config.leaf_properties();
+
+        if let Some(ticket) = join_ticket {
+            leaf_properties.extensions.set_from(ticket)?;
+        }
+
         let (leaf_node, _) = LeafNode::generate(
             &cipher_suite,
-            self.config.leaf_properties(),
+            leaf_properties,
             self.signing_identity,
             &self.signer,
             self.config.lifetime(),
@@ -254,6 +285,8 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
                 Default::default(),
                 None,
                 None,
+                None,
+                false,
             )
             .await?;
 
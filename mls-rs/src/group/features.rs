@@ -0,0 +1,264 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::extension::ExtensionList;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::{GroupFeaturesExt, SupportedFeaturesExt},
+    group::Group,
+};
+
+#[cfg(feature = "by_ref_proposal")]
+use crate::MlsMessage;
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Application feature flags currently enabled in the group, as defined
+    /// by the [`GroupFeaturesExt`] group context extension.
+    pub fn features(&self) -> Result<Vec<u16>, MlsError> {
+        Ok(self
+            .context()
+            .extensions
+            .get_as::<GroupFeaturesExt>()?
+            .map(|ext| ext.enabled)
+            .unwrap_or_default())
+    }
+
+    /// Determine if the application feature `flag` is enabled in the group.
+    pub fn feature_enabled(&self, flag: u16) -> Result<bool, MlsError> {
+        Ok(self.features()?.contains(&flag))
+    }
+
+    /// Determine if every current member advertises support for `flag`
+    /// using a [`SupportedFeaturesExt`] leaf node extension.
+    pub fn feature_supported_by_all(&self, flag: u16) -> Result<bool, MlsError> {
+        for (_, leaf) in self.current_epoch_tree().non_empty_leaves() {
+            let supported = leaf.extensions.get_as::<SupportedFeaturesExt>()?;
+
+            if !supported.map_or(false, |ext| ext.is_supported(flag)) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Group context extensions resulting from enabling `flag`.
+    ///
+    /// The result can be committed using
+    /// [`CommitBuilder::set_group_context_ext`](crate::group::CommitBuilder::set_group_context_ext).
+    /// Returns [`MlsError::RequiredFeatureNotSupported`] if some member does
+    /// not support `flag`.
+    pub fn extensions_with_feature(&self, flag: u16) -> Result<ExtensionList, MlsError> {
+        if !self.feature_supported_by_all(flag)? {
+            return Err(MlsError::RequiredFeatureNotSupported(flag));
+        }
+
+        let mut extensions = self.context().extensions.clone();
+
+        let mut features = extensions.get_as::<GroupFeaturesExt>()?.unwrap_or_default();
+
+        if !features.is_enabled(flag) {
+            features.enabled.push(flag);
+        }
+
+        extensions.set_from(features)?;
+
+        Ok(extensions)
+    }
+
+    /// Create a proposal message that enables the application feature `flag`
+    /// after checking that all current members support it.
+    ///
+    /// Once the feature is enabled, new members must also support it in
+    /// order to be added to the group.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg(feature = "by_ref_proposal")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_enable_feature(
+        &mut self,
+        flag: u16,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        let extensions = self.extensions_with_feature(flag)?;
+
+        self.propose_group_context_extensions(extensions, authenticated_data)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::extension::{ExtensionList, ExtensionType};
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::{TestClientBuilder, TestClientConfig},
+        extension::{
+            GroupFeaturesExt, SupportedFeaturesExt, GROUP_FEATURES_EXTENSION_TYPE,
+            SUPPORTED_FEATURES_EXTENSION_TYPE,
+        },
+        group::test_utils::{test_group_custom_config, TestGroup},
+        identity::test_utils::get_test_signing_identity,
+        Client,
+    };
+
+    fn feature_extension_types() -> [ExtensionType; 2] {
+        [
+            GROUP_FEATURES_EXTENSION_TYPE,
+            SUPPORTED_FEATURES_EXTENSION_TYPE,
+        ]
+    }
+
+    fn leaf_extensions(supported: &[u16]) -> ExtensionList {
+        let mut extensions = ExtensionList::new();
+
+        extensions
+            .set_from(SupportedFeaturesExt::new(supported.to_vec()))
+            .unwrap();
+
+        extensions
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str, supported: &[u16]) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .extension_types(feature_extension_types().to_vec())
+            .leaf_node_extensions(leaf_extensions(supported))
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn feature_group(supported: &[u16]) -> TestGroup {
+        let supported = supported.to_vec();
+
+        test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, move |b| {
+            b.extension_types(feature_extension_types().to_vec())
+                .leaf_node_extensions(leaf_extensions(&supported))
+        })
+        .await
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn feature_can_be_enabled() {
+        let mut alice = feature_group(&[1, 2]).await;
+
+        assert_eq!(alice.group.features().unwrap(), Vec::<u16>::new());
+
+        let extensions = alice.group.extensions_with_feature(2).unwrap();
+
+        alice
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        assert!(alice.group.feature_enabled(2).unwrap());
+        assert!(!alice.group.feature_enabled(1).unwrap());
+
+        let features = alice.group.context().extensions.get_as().unwrap();
+        assert_eq!(features, Some(GroupFeaturesExt::new(vec![2])));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn feature_not_supported_by_self_is_rejected() {
+        let alice = feature_group(&[1]).await;
+
+        assert_matches!(
+            alice.group.extensions_with_feature(2),
+            Err(MlsError::RequiredFeatureNotSupported(2))
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_without_enabled_feature_cannot_be_added() {
+        let mut alice = feature_group(&[1]).await;
+
+        let extensions = alice.group.extensions_with_feature(1).unwrap();
+
+        alice
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let bob = test_client("bob", &[]).await;
+
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let res = alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await;
+
+        assert_matches!(res, Err(MlsError::RequiredFeatureNotSupported(1)));
+
+        let carol = test_client("carol", &[1]).await;
+
+        let key_package = carol.generate_key_package_message().await.unwrap();
+
+        alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn propose_enable_feature_requires_support_of_all_members() {
+        let mut alice = feature_group(&[1]).await;
+
+        let bob = test_client("bob", &[]).await;
+
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let res = alice.group.propose_enable_feature(1, vec![]).await;
+
+        assert_matches!(res, Err(MlsError::RequiredFeatureNotSupported(1)));
+    }
+}
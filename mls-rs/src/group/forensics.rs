@@ -0,0 +1,233 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use core::fmt::{self, Debug};
+
+use mls_rs_codec::MlsDecode;
+use mls_rs_core::{
+    crypto::{CipherSuite, CryptoProvider},
+    key_package::KeyPackageData,
+    protocol_version::ProtocolVersion,
+};
+
+use crate::{
+    client::MlsError,
+    group::{framing::MlsMessagePayload, key_schedule::WelcomeSecret, GroupInfo, GroupSecrets},
+    key_package::{KeyPackage, KeyPackageRef},
+    psk::secret::PskSecret,
+    tree_kem::hpke_encryption::HpkeEncryptable,
+    MlsMessage,
+};
+
+/// Decrypted contents of a Welcome message, produced by [`decrypt_welcome`].
+///
+/// The [`Debug`] implementation prints all decrypted values, including the
+/// secrets, and is meant to be used with `{:#?}`.
+#[derive(Clone)]
+pub struct WelcomeReport {
+    protocol_version: ProtocolVersion,
+    cipher_suite: CipherSuite,
+    key_package_ref: KeyPackageRef,
+    group_secrets: GroupSecrets,
+    group_info: Option<GroupInfo>,
+}
+
+impl WelcomeReport {
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn cipher_suite(&self) -> CipherSuite {
+        self.cipher_suite
+    }
+
+    /// Reference of the key package the Welcome was encrypted to.
+    pub fn key_package_ref(&self) -> &[u8] {
+        &self.key_package_ref
+    }
+
+    /// True if the Welcome contains a path secret for the new member.
+    pub fn has_path_secret(&self) -> bool {
+        self.group_secrets.path_secret.is_some()
+    }
+
+    /// Number of pre-shared keys required to join.
+    pub fn psk_count(&self) -> usize {
+        self.group_secrets.psks.len()
+    }
+
+    /// The decrypted GroupInfo. This is `None` if joining requires
+    /// pre-shared keys, since the GroupInfo is encrypted under a key
+    /// derived from them.
+    ///
+    /// The GroupInfo is decoded but not verified.
+    pub fn group_info(&self) -> Option<&GroupInfo> {
+        self.group_info.as_ref()
+    }
+}
+
+impl Debug for WelcomeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WelcomeReport")
+            .field("protocol_version", &self.protocol_version)
+            .field("cipher_suite", &self.cipher_suite)
+            .field("key_package_ref", &self.key_package_ref)
+            .field("group_secrets", &self.group_secrets)
+            .field("group_info", &self.group_info)
+            .finish()
+    }
+}
+
+/// Decrypt `welcome` using the secrets of the key package it was sent to,
+/// without joining the group.
+///
+/// This is intended for debugging failed joins from collected artifacts.
+/// Unlike [`Client::join_group`](crate::Client::join_group), no validation
+/// of the GroupInfo or the ratchet tree is performed.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn decrypt_welcome<P: CryptoProvider>(
+    crypto_provider: &P,
+    welcome: &MlsMessage,
+    key_package_data: &KeyPackageData,
+) -> Result<WelcomeReport, MlsError> {
+    let protocol_version = welcome.version;
+
+    let MlsMessagePayload::Welcome(welcome) = &welcome.payload else {
+        return Err(MlsError::UnexpectedMessageType);
+    };
+
+    let cipher_suite_provider = crypto_provider
+        .cipher_suite_provider(welcome.cipher_suite)
+        .ok_or(MlsError::UnsupportedCipherSuite(welcome.cipher_suite))?;
+
+    let key_package = KeyPackage::mls_decode(&mut &*key_package_data.key_package_bytes)?;
+    let key_package_ref = key_package.to_reference(&cipher_suite_provider).await?;
+
+    let encrypted_group_secrets = welcome
+        .secrets
+        .iter()
+        .find(|secrets| secrets.new_member == key_package_ref)
+        .ok_or(MlsError::WelcomeKeyPackageNotFound)?;
+
+    let group_secrets = GroupSecrets::decrypt(
+        &cipher_suite_provider,
+        &key_package_data.init_key,
+        &key_package.hpke_init_key,
+        &welcome.encrypted_group_info,
+        &encrypted_group_secrets.encrypted_group_secrets,
+    )
+    .await?;
+
+    let group_info = if group_secrets.psks.is_empty() {
+        let welcome_secret = WelcomeSecret::from_joiner_secret(
+            &cipher_suite_provider,
+            &group_secrets.joiner_secret,
+            &PskSecret::new(&cipher_suite_provider),
+        )
+        .await?;
+
+        let decrypted_group_info = welcome_secret
+            .decrypt(&welcome.encrypted_group_info)
+            .await?;
+
+        Some(GroupInfo::mls_decode(&mut &**decrypted_group_info)?)
+    } else {
+        None
+    };
+
+    Ok(WelcomeReport {
+        protocol_version,
+        cipher_suite: welcome.cipher_suite,
+        key_package_ref,
+        group_secrets,
+        group_info,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use assert_matches::assert_matches;
+    use mls_rs_core::key_package::KeyPackageData;
+
+    use crate::{
+        client::{
+            test_utils::{test_client_with_key_pkg, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_config::ClientConfig,
+        crypto::test_utils::TestCryptoProvider,
+        group::test_utils::test_group,
+        MlsMessage,
+    };
+
+    use super::decrypt_welcome;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn key_package_with_data(identity: &str) -> (MlsMessage, KeyPackageData) {
+        let (client, key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, identity).await;
+
+        let (_, data) = client.config.key_package_repo().key_packages().remove(0);
+
+        (key_package, data)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_can_be_decrypted_without_joining() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (bob_key_package, bob_data) = key_package_with_data("bob").await;
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let report = decrypt_welcome(
+            &TestCryptoProvider::new(),
+            &commit.welcome_messages[0],
+            &bob_data,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.cipher_suite(), TEST_CIPHER_SUITE);
+        assert_eq!(report.psk_count(), 0);
+
+        let group_info = report.group_info().unwrap();
+        assert_eq!(group_info.group_context().group_id, alice.group.group_id());
+        assert_eq!(group_info.group_context().epoch, 1);
+
+        assert!(format!("{report:#?}").contains("GroupInfo"));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_for_other_key_package_is_rejected() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (bob_key_package, _) = key_package_with_data("bob").await;
+        let (_, carol_data) = key_package_with_data("carol").await;
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let res = decrypt_welcome(
+            &TestCryptoProvider::new(),
+            &commit.welcome_messages[0],
+            &carol_data,
+        )
+        .await;
+
+        assert_matches!(res, Err(MlsError::WelcomeKeyPackageNotFound));
+    }
+}
@@ -0,0 +1,401 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+
+use crate::{client::MlsError, client_config::ClientConfig, group::Group, MlsMessage};
+
+use super::{
+    resumption::{resumption_create_group, ResumptionGroupParameters},
+    ExportedTree, JustPreSharedKeyID, NewMemberInfo, PreSharedKeyID, PskGroupId, PskSecretInput,
+    ResumptionPSKUsage, ResumptionPsk,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+struct EpochFingerprint {
+    epoch: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    confirmed_transcript_hash: Vec<u8>,
+}
+
+/// Confirmed transcript hashes of the recent epochs of a group, as seen by
+/// one member.
+///
+/// Members exchange fork info out of band, e.g. through the delivery
+/// service, and [compare](ForkInfo::compare) it to detect that the group
+/// was split into divergent forks by a network partition.
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct ForkInfo {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    group_id: Vec<u8>,
+    // Newest epoch first.
+    epochs: Vec<EpochFingerprint>,
+}
+
+/// Result of comparing the [`ForkInfo`] of two members.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkStatus {
+    /// All epochs known to both members have the same transcript hash. One
+    /// of the members may be behind the other.
+    Consistent,
+    /// The group forked after `fork_epoch`, which is the newest epoch both
+    /// members agree on. The fork can be merged with
+    /// [`Group::merge_fork`] as long as both members still retain the
+    /// secrets of `fork_epoch`.
+    Forked { fork_epoch: u64 },
+    /// The members disagree on every epoch they both know about, so no
+    /// common epoch can be used to prove continuity.
+    Diverged,
+}
+
+impl ForkInfo {
+    pub fn group_id(&self) -> &[u8] {
+        &self.group_id
+    }
+
+    /// Newest epoch known to the member that created this fork info.
+    pub fn epoch(&self) -> u64 {
+        self.epochs.first().map(|e| e.epoch).unwrap_or_default()
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        Self::mls_decode(&mut &*bytes).map_err(Into::into)
+    }
+
+    /// Compare with the fork info of another member of the same group.
+    pub fn compare(&self, other: &ForkInfo) -> Result<ForkStatus, MlsError> {
+        if self.group_id != other.group_id {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        let mut forked = false;
+
+        for fingerprint in &self.epochs {
+            let Some(theirs) = other.epochs.iter().find(|e| e.epoch == fingerprint.epoch) else {
+                continue;
+            };
+
+            if theirs == fingerprint {
+                return Ok(if forked {
+                    ForkStatus::Forked {
+                        fork_epoch: fingerprint.epoch,
+                    }
+                } else {
+                    ForkStatus::Consistent
+                });
+            }
+
+            forked = true;
+        }
+
+        Ok(if forked {
+            ForkStatus::Diverged
+        } else {
+            ForkStatus::Consistent
+        })
+    }
+
+    /// Check if the fork described by this fork info should survive a merge
+    /// with the fork described by `other`.
+    ///
+    /// The fork with the newest epoch survives, since it has likely seen
+    /// the most activity. Ties are broken by the lowest transcript hash, so
+    /// that all members designate the same surviving fork.
+    pub fn survives_over(&self, other: &ForkInfo) -> bool {
+        let key = |info: &ForkInfo| {
+            info.epochs.first().map(|e| {
+                (
+                    core::cmp::Reverse(e.epoch),
+                    e.confirmed_transcript_hash.clone(),
+                )
+            })
+        };
+
+        key(self) <= key(other)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create a [`ForkInfo`] describing the current epoch and up to
+    /// `max_prior_epochs` prior epochs retained in storage.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn fork_info(&mut self, max_prior_epochs: u64) -> Result<ForkInfo, MlsError> {
+        let current_epoch = self.current_epoch();
+
+        let mut epochs = alloc::vec![EpochFingerprint {
+            epoch: current_epoch,
+            confirmed_transcript_hash: self.context().confirmed_transcript_hash.to_vec(),
+        }];
+
+        let oldest_epoch = current_epoch.saturating_sub(max_prior_epochs);
+
+        for epoch in (oldest_epoch..current_epoch).rev() {
+            let Some(prior_epoch) = self.state_repo.get_epoch_mut(epoch).await? else {
+                break;
+            };
+
+            epochs.push(EpochFingerprint {
+                epoch,
+                confirmed_transcript_hash: prior_epoch.context.confirmed_transcript_hash.to_vec(),
+            });
+        }
+
+        Ok(ForkInfo {
+            group_id: self.group_id().to_vec(),
+            epochs,
+        })
+    }
+
+    /// Merge a forked group into a new group with id `merged_group_id`.
+    ///
+    /// This is called by one member of the surviving fork, as designated by
+    /// [`ForkInfo::survives_over`]. The new group contains this member and
+    /// the members owning `key_packages`, which should include the members
+    /// of both forks. It keeps the extensions of the current epoch of this
+    /// member.
+    ///
+    /// The welcome messages are bound to the resumption secret of
+    /// `fork_epoch`, the last epoch before the group forked. Joining with
+    /// [`Group::join_merged_group`] proves that both the creator and the
+    /// joiner were members of the group in that epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn merge_fork(
+        &self,
+        fork_epoch: u64,
+        merged_group_id: Vec<u8>,
+        key_packages: Vec<MlsMessage>,
+    ) -> Result<(Group<C>, Vec<MlsMessage>), MlsError> {
+        let new_group_params = ResumptionGroupParameters {
+            group_id: &merged_group_id,
+            cipher_suite: self.cipher_suite(),
+            version: self.protocol_version(),
+            extensions: &self.state.context.extensions,
+        };
+
+        resumption_create_group(
+            self.config.clone(),
+            key_packages,
+            &new_group_params,
+            self.current_member_signing_identity()?.clone(),
+            self.signer.clone(),
+            self.fork_psk_input(fork_epoch).await?,
+        )
+        .await
+    }
+
+    /// Join a group created by [`Group::merge_fork`] from either fork.
+    ///
+    /// Joining fails unless the creator of the merged group used the same
+    /// `fork_epoch` and held its resumption secret.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn join_merged_group(
+        &self,
+        fork_epoch: u64,
+        welcome: &MlsMessage,
+        tree_data: Option<ExportedTree<'_>>,
+    ) -> Result<(Group<C>, NewMemberInfo), MlsError> {
+        let psk_input = self.fork_psk_input(fork_epoch).await?;
+
+        let (group, new_member_info) = Group::<C>::from_welcome_message(
+            welcome,
+            tree_data,
+            #[cfg(feature = "tree_fetcher")]
+            None::<&super::tree_fetcher::NoTreeFetcher>,
+            self.config.clone(),
+            self.signer.clone(),
+            Some(psk_input),
+            #[cfg(feature = "welcome_retargeting")]
+            false,
+        )
+        .await?;
+
+        if group.protocol_version() != self.protocol_version() {
+            Err(MlsError::ProtocolVersionMismatch)
+        } else if group.cipher_suite() != self.cipher_suite() {
+            Err(MlsError::CipherSuiteMismatch)
+        } else {
+            Ok((group, new_member_info))
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn fork_psk_input(&self, fork_epoch: u64) -> Result<PskSecretInput, MlsError> {
+        let resumption_psk = ResumptionPsk {
+            usage: ResumptionPSKUsage::Branch,
+            psk_group_id: PskGroupId(self.group_id().to_vec()),
+            psk_epoch: fork_epoch,
+        };
+
+        let psk = if fork_epoch == self.current_epoch() {
+            self.epoch_secrets.resumption_secret.clone()
+        } else {
+            self.state_repo
+                .resumption_secret(&resumption_psk)
+                .await?
+                .ok_or(MlsError::OldGroupStateNotFound)?
+        };
+
+        let id = JustPreSharedKeyID::Resumption(resumption_psk);
+        let id = PreSharedKeyID::new(id, &self.cipher_suite_provider)?;
+
+        Ok(PskSecretInput { id, psk })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::{test_n_member_group, TestGroup},
+        Client, MlsMessage,
+    };
+
+    use super::ForkStatus;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn new_key_package(member: &TestGroup) -> MlsMessage {
+        let identity = member.group.current_member_signing_identity().unwrap();
+
+        Client::new(
+            member.group.config.clone(),
+            Some(member.group.signer.clone()),
+            Some((identity.clone(), TEST_CIPHER_SUITE)),
+            TEST_PROTOCOL_VERSION,
+        )
+        .generate_key_package_message()
+        .await
+        .unwrap()
+    }
+
+    // Alice and Bob move to one fork while Carol concurrently commits to
+    // another fork of the same epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn forked_groups() -> (Vec<TestGroup>, u64) {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+        let fork_epoch = groups[0].group.current_epoch();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+        groups[1]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        groups[2].group.commit(vec![]).await.unwrap();
+        groups[2].process_pending_commit().await.unwrap();
+
+        (groups, fork_epoch)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn fork_is_detected() {
+        let (mut groups, fork_epoch) = forked_groups().await;
+
+        let alice = groups[0].group.fork_info(3).await.unwrap();
+        let bob = groups[1].group.fork_info(3).await.unwrap();
+        let carol = groups[2].group.fork_info(3).await.unwrap();
+
+        assert_eq!(alice.compare(&bob).unwrap(), ForkStatus::Consistent);
+        assert_eq!(
+            alice.compare(&carol).unwrap(),
+            ForkStatus::Forked { fork_epoch }
+        );
+        assert_eq!(
+            carol.compare(&bob).unwrap(),
+            ForkStatus::Forked { fork_epoch }
+        );
+
+        assert_ne!(alice.survives_over(&carol), carol.survives_over(&alice));
+
+        let carol_current_only = groups[2].group.fork_info(0).await.unwrap();
+        assert_eq!(
+            alice.compare(&carol_current_only).unwrap(),
+            ForkStatus::Diverged
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_of_both_forks_join_merged_group() {
+        let (groups, fork_epoch) = forked_groups().await;
+
+        let bob_key_package = new_key_package(&groups[1]).await;
+        let carol_key_package = new_key_package(&groups[2]).await;
+        let key_packages = vec![bob_key_package, carol_key_package];
+
+        let (mut merged, welcomes) = groups[0]
+            .group
+            .merge_fork(fork_epoch, b"merged".to_vec(), key_packages)
+            .await
+            .unwrap();
+
+        let (mut bob, _) = groups[1]
+            .group
+            .join_merged_group(fork_epoch, &welcomes[0], None)
+            .await
+            .unwrap();
+
+        let (mut carol, _) = groups[2]
+            .group
+            .join_merged_group(fork_epoch, &welcomes[0], None)
+            .await
+            .unwrap();
+
+        let commit = merged.commit(vec![]).await.unwrap();
+        merged.apply_pending_commit().await.unwrap();
+
+        bob.process_incoming_message(commit.commit_message.clone())
+            .await
+            .unwrap();
+
+        carol
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(carol.roster().members().len(), 3);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn merged_group_requires_fork_epoch_secret() {
+        let (groups, fork_epoch) = forked_groups().await;
+        let carol_key_package = new_key_package(&groups[2]).await;
+
+        let (_, welcomes) = groups[0]
+            .group
+            .merge_fork(fork_epoch, b"merged".to_vec(), vec![carol_key_package])
+            .await
+            .unwrap();
+
+        // The first epoch of Carol's fork is not known to Alice.
+        let res = groups[2]
+            .group
+            .join_merged_group(fork_epoch + 1, &welcomes[0], None)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(_));
+
+        let res = groups[2]
+            .group
+            .join_merged_group(fork_epoch + 2, &welcomes[0], None)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::OldGroupStateNotFound));
+    }
+}
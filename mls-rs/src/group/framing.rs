@@ -4,7 +4,10 @@
 
 use core::ops::Deref;
 
-use crate::{client::MlsError, tree_kem::node::LeafIndex, KeyPackage, KeyPackageRef};
+use crate::{
+    client::MlsError, hash_reference::HashReference, tree_kem::node::LeafIndex, KeyPackage,
+    KeyPackageRef,
+};
 
 use super::{Commit, FramedContentAuthData, GroupInfo, MembershipTag, Welcome};
 
@@ -26,8 +29,14 @@ use alloc::boxed::Box;
 #[cfg(feature = "custom_proposal")]
 use crate::group::proposal::{CustomProposal, ProposalOrRef};
 
+#[cfg(feature = "targeted_message")]
+use crate::group::targeted_message::TargetedMessage;
+
+const CANONICAL_HASH_LABEL: &[u8] = b"mls-rs canonical message hash";
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum ContentType {
     #[cfg(feature = "private_message")]
@@ -145,6 +154,7 @@ impl Content {
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct PublicMessage {
     pub content: FramedContent,
     pub auth: FramedContentAuthData,
@@ -291,16 +301,21 @@ impl Debug for PrivateContentAAD {
 #[cfg(feature = "private_message")]
 #[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrivateMessage {
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub group_id: Vec<u8>,
     pub epoch: u64,
     pub content_type: ContentType,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub authenticated_data: Vec<u8>,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub encrypted_sender_data: Vec<u8>,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub ciphertext: Vec<u8>,
 }
 
@@ -348,7 +363,13 @@ impl From<&PrivateMessage> for PrivateContentAAD {
     ::safer_ffi_gen::ffi_type(clone, opaque)
 )]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A MLS protocol message for sending data over the wire.
+///
+/// With the `serde` feature, messages can also be serialized to
+/// human-readable formats such as JSON for logging and inspection, in
+/// which case opaque byte strings are hex encoded. The TLS encoding
+/// remains the only format used on the wire.
 pub struct MlsMessage {
     pub(crate) version: ProtocolVersion,
     pub(crate) payload: MlsMessagePayload,
@@ -419,6 +440,8 @@ impl MlsMessage {
             MlsMessagePayload::Welcome(_) => WireFormat::Welcome,
             MlsMessagePayload::GroupInfo(_) => WireFormat::GroupInfo,
             MlsMessagePayload::KeyPackage(_) => WireFormat::KeyPackage,
+            #[cfg(feature = "targeted_message")]
+            MlsMessagePayload::Targeted(_) => WireFormat::TargetedMessage,
         }
     }
 
@@ -433,6 +456,8 @@ impl MlsMessage {
             #[cfg(feature = "private_message")]
             MlsMessagePayload::Cipher(c) => Some(c.epoch),
             MlsMessagePayload::GroupInfo(gi) => Some(gi.group_context.epoch),
+            #[cfg(feature = "targeted_message")]
+            MlsMessagePayload::Targeted(t) => Some(t.epoch),
             _ => None,
         }
     }
@@ -453,10 +478,26 @@ impl MlsMessage {
             #[cfg(feature = "private_message")]
             MlsMessagePayload::Cipher(p) => Some(&p.group_id),
             MlsMessagePayload::GroupInfo(p) => Some(&p.group_context.group_id),
+            #[cfg(feature = "targeted_message")]
+            MlsMessagePayload::Targeted(t) => Some(&t.group_id),
             MlsMessagePayload::KeyPackage(_) | MlsMessagePayload::Welcome(_) => None,
         }
     }
 
+    /// The content type of a [`WireFormat::PublicMessage`] or
+    /// [`WireFormat::PrivateMessage`], which is sent in the clear.
+    ///
+    /// Returns `None` for all other wire formats.
+    #[cfg_attr(all(feature = "ffi", not(test)), ::safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn content_type(&self) -> Option<ContentType> {
+        match &self.payload {
+            MlsMessagePayload::Plain(p) => Some(p.content.content_type()),
+            #[cfg(feature = "private_message")]
+            MlsMessagePayload::Cipher(c) => Some(c.content_type),
+            _ => None,
+        }
+    }
+
     /// Deserialize a message from transport.
     #[inline(never)]
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
@@ -504,6 +545,28 @@ impl MlsMessage {
         kp.to_reference(cipher_suite).await.map(Some)
     }
 
+    /// Stable identifier of this message, suitable for deduplication and as
+    /// a storage key.
+    ///
+    /// The hash is computed over the canonical encoding of the message, so
+    /// it does not depend on transport padding such as trailing bytes after
+    /// the encoded message or the padding added by a handshake queue. The
+    /// padding of a [`PrivateMessage`] is encrypted together with its
+    /// content and is part of the message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn canonical_hash<C: CipherSuiteProvider>(
+        &self,
+        cipher_suite: &C,
+    ) -> Result<Vec<u8>, MlsError> {
+        HashReference::compute(
+            &self.mls_encode_to_vec()?,
+            CANONICAL_HASH_LABEL,
+            cipher_suite,
+        )
+        .await
+        .map(|hash| hash.to_vec())
+    }
+
     /// If this is a plaintext proposal, return the proposal reference that can be matched e.g. with
     /// [`StateUpdate::unused_proposals`](super::StateUpdate::unused_proposals).
     #[cfg(feature = "by_ref_proposal")]
@@ -542,6 +605,7 @@ impl MlsMessage {
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub(crate) enum MlsMessagePayload {
     Plain(PublicMessage) = 1u16,
@@ -550,6 +614,8 @@ pub(crate) enum MlsMessagePayload {
     Welcome(Welcome) = 3u16,
     GroupInfo(GroupInfo) = 4u16,
     KeyPackage(KeyPackage) = 5u16,
+    #[cfg(feature = "targeted_message")]
+    Targeted(TargetedMessage) = 6u16,
 }
 
 impl From<PublicMessage> for MlsMessagePayload {
@@ -572,6 +638,7 @@ pub enum WireFormat {
     Welcome = 3u16,
     GroupInfo = 4u16,
     KeyPackage = 5u16,
+    TargetedMessage = 6u16,
 }
 
 #[derive(Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
@@ -695,6 +762,57 @@ mod tests {
         assert_eq!(ciphertext_content, decoded);
     }
 
+    #[cfg(feature = "private_message")]
+    fn test_private_message(group_id: &[u8]) -> MlsMessage {
+        let ciphertext = PrivateMessage {
+            group_id: group_id.to_vec(),
+            epoch: 1,
+            content_type: ContentType::Application,
+            authenticated_data: Vec::new(),
+            encrypted_sender_data: vec![1; 32],
+            ciphertext: vec![2; 128],
+        };
+
+        MlsMessage::new(TEST_PROTOCOL_VERSION, MlsMessagePayload::Cipher(ciphertext))
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn canonical_hash_ignores_transport_padding() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let message = test_private_message(b"group");
+
+        let mut padded = message.to_bytes().unwrap();
+        padded.extend_from_slice(&[0u8; 64]);
+
+        let padded_message = MlsMessage::from_bytes(&padded).unwrap();
+        let hash = message.canonical_hash(&cs).await.unwrap();
+
+        let padded_hash = padded_message.canonical_hash(&cs).await.unwrap();
+        assert_eq!(padded_hash, hash);
+
+        let other = test_private_message(b"other group");
+
+        let other_hash = other.canonical_hash(&cs).await.unwrap();
+        assert_ne!(other_hash, hash);
+    }
+
+    #[cfg(all(feature = "private_message", feature = "serde"))]
+    #[test]
+    fn mls_message_serializes_to_readable_json() {
+        let message = test_private_message(b"group");
+        let json = serde_json::to_value(&message).unwrap();
+
+        let ciphertext = &json["payload"]["Cipher"];
+        assert_eq!(ciphertext["group_id"], hex::encode(b"group"));
+        assert_eq!(ciphertext["epoch"], 1);
+        assert_eq!(ciphertext["ciphertext"], hex::encode([2; 128]));
+
+        let recovered: MlsMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(recovered, message);
+    }
+
     #[test]
     fn test_mls_ciphertext_content_non_zero_padding_error() {
         let ciphertext_content = get_test_ciphertext_content();
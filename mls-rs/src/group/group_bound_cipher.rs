@@ -0,0 +1,357 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{crypto::CipherSuiteProvider, error::IntoAnyError};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{key_schedule::kdf_expand_with_label, Group},
+};
+
+const GROUP_BOUND_CIPHER_LABEL: &[u8] = b"mls-rs group bound cipher";
+const ROW_KEY_LABEL: &[u8] = b"row key";
+
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+struct EpochKey {
+    epoch: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    secret: Zeroizing<Vec<u8>>,
+}
+
+#[derive(MlsSize, MlsEncode, MlsDecode)]
+struct RowCiphertext {
+    key_id: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    nonce: Vec<u8>,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    ciphertext: Vec<u8>,
+}
+
+/// Encryption of data stored locally by the application under keys derived
+/// from the exporter secret of a group.
+///
+/// Every ciphertext is encrypted under a key specific to a table and row,
+/// and its header contains the epoch of the exporter secret the key was
+/// derived from as key id. Encryption always uses the key of the current
+/// epoch of the group, adding it to the cipher when the group advanced.
+///
+/// Keys of prior epochs are kept so that existing rows remain readable. To
+/// let stored data heal with the group, the application re-encrypts rows
+/// for which [`GroupBoundCipher::needs_reencryption`] returns `true` and
+/// then drops old keys with [`GroupBoundCipher::retire_keys_before`].
+///
+/// The cipher holds secret keys. Its serialization returned by
+/// [`GroupBoundCipher::to_bytes`] must be stored as securely as the group
+/// state.
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct GroupBoundCipher {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    group_id: Vec<u8>,
+    keys: Vec<EpochKey>,
+}
+
+impl Debug for GroupBoundCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupBoundCipher")
+            .field(
+                "group_id",
+                &mls_rs_core::debug::pretty_group_id(&self.group_id),
+            )
+            .field("epochs", &self.epochs().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl GroupBoundCipher {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        Self::mls_decode(&mut &*bytes).map_err(Into::into)
+    }
+
+    /// Epochs whose keys are held by this cipher, in ascending order.
+    pub fn epochs(&self) -> impl Iterator<Item = u64> + '_ {
+        self.keys.iter().map(|key| key.epoch)
+    }
+
+    /// Key id, i.e. epoch, found in the header of `ciphertext`.
+    pub fn key_id(ciphertext: &[u8]) -> Result<u64, MlsError> {
+        Ok(RowCiphertext::mls_decode(&mut &*ciphertext)?.key_id)
+    }
+
+    /// Determine if `ciphertext` is encrypted under a key older than the
+    /// newest key of this cipher.
+    pub fn needs_reencryption(&self, ciphertext: &[u8]) -> Result<bool, MlsError> {
+        let key_id = Self::key_id(ciphertext)?;
+        Ok(self.keys.last().map_or(false, |key| key.epoch > key_id))
+    }
+
+    /// Delete the keys of all epochs before `epoch`. Rows encrypted under
+    /// these keys can no longer be decrypted.
+    pub fn retire_keys_before(&mut self, epoch: u64) {
+        self.keys.retain(|key| key.epoch >= epoch);
+    }
+
+    /// Add the key of the current epoch of `group` if it is not held yet.
+    /// Returns `true` if a key was added.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn rotate<C: ClientConfig + Clone>(
+        &mut self,
+        group: &Group<C>,
+    ) -> Result<bool, MlsError> {
+        if group.group_id() != self.group_id.as_slice() {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        let epoch = group.current_epoch();
+
+        if self.keys.last().map_or(false, |key| key.epoch >= epoch) {
+            return Ok(false);
+        }
+
+        let secret = group
+            .export_secret(
+                GROUP_BOUND_CIPHER_LABEL,
+                &[],
+                group.cipher_suite_provider.kdf_extract_size(),
+            )
+            .await?;
+
+        self.keys.push(EpochKey {
+            epoch,
+            secret: Zeroizing::new(secret.to_vec()),
+        });
+
+        Ok(true)
+    }
+
+    /// Encrypt `plaintext` stored in `row` of `table` under the key of the
+    /// current epoch of `group`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn encrypt<C: ClientConfig + Clone>(
+        &mut self,
+        group: &Group<C>,
+        table: &[u8],
+        row: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, MlsError> {
+        self.rotate(group).await?;
+
+        let cs = &group.cipher_suite_provider;
+        let key_id = group.current_epoch();
+        let key = self.row_key(cs, key_id, table, row).await?;
+
+        let nonce = cs
+            .random_bytes_vec(cs.aead_nonce_size())
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        let ciphertext = cs
+            .aead_seal(&key, plaintext, Some(&key_id.to_be_bytes()), &nonce)
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        RowCiphertext {
+            key_id,
+            nonce,
+            ciphertext,
+        }
+        .mls_encode_to_vec()
+        .map_err(Into::into)
+    }
+
+    /// Decrypt `ciphertext` stored in `row` of `table`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn decrypt<C: ClientConfig + Clone>(
+        &self,
+        group: &Group<C>,
+        table: &[u8],
+        row: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, MlsError> {
+        if group.group_id() != self.group_id.as_slice() {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        let cs = &group.cipher_suite_provider;
+        let ciphertext = RowCiphertext::mls_decode(&mut &*ciphertext)?;
+        let key = self.row_key(cs, ciphertext.key_id, table, row).await?;
+
+        cs.aead_open(
+            &key,
+            &ciphertext.ciphertext,
+            Some(&ciphertext.key_id.to_be_bytes()),
+            &ciphertext.nonce,
+        )
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+    }
+
+    /// Decrypt `ciphertext` stored in `row` of `table` and encrypt it again
+    /// under the key of the current epoch of `group`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn reencrypt<C: ClientConfig + Clone>(
+        &mut self,
+        group: &Group<C>,
+        table: &[u8],
+        row: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, MlsError> {
+        let plaintext = self.decrypt(group, table, row, ciphertext).await?;
+        self.encrypt(group, table, row, &plaintext).await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn row_key<P: CipherSuiteProvider>(
+        &self,
+        cipher_suite_provider: &P,
+        key_id: u64,
+        table: &[u8],
+        row: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, MlsError> {
+        let epoch_key = self
+            .keys
+            .iter()
+            .find(|key| key.epoch == key_id)
+            .ok_or(MlsError::EpochNotFound)?;
+
+        let mut context = Vec::new();
+        mls_rs_codec::byte_vec::mls_encode(&table, &mut context)?;
+        mls_rs_codec::byte_vec::mls_encode(&row, &mut context)?;
+
+        kdf_expand_with_label(
+            cipher_suite_provider,
+            &epoch_key.secret,
+            ROW_KEY_LABEL,
+            &context,
+            Some(cipher_suite_provider.aead_key_size()),
+        )
+        .await
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create a [`GroupBoundCipher`] holding the key of the current epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn group_bound_cipher(&self) -> Result<GroupBoundCipher, MlsError> {
+        let mut cipher = GroupBoundCipher {
+            group_id: self.group_id().to_vec(),
+            keys: Vec::new(),
+        };
+
+        cipher.rotate(self).await?;
+
+        Ok(cipher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::{test_group, test_n_member_group},
+    };
+
+    use super::GroupBoundCipher;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_decrypt_rows_of_other_members() {
+        let groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let mut alice = groups[0].group.group_bound_cipher().await.unwrap();
+        let bob = groups[1].group.group_bound_cipher().await.unwrap();
+
+        let ciphertext = alice
+            .encrypt(&groups[0].group, b"messages", b"1", b"hello")
+            .await
+            .unwrap();
+
+        let plaintext = bob
+            .decrypt(&groups[1].group, b"messages", b"1", &ciphertext)
+            .await
+            .unwrap();
+
+        assert_eq!(plaintext.as_slice(), b"hello");
+
+        // Keys are bound to the table and row.
+        let res = bob
+            .decrypt(&groups[1].group, b"messages", b"2", &ciphertext)
+            .await;
+
+        assert_matches!(res, Err(MlsError::CryptoProviderError(_)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn keys_rotate_on_epoch_change() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let epoch = groups[0].group.current_epoch();
+        let mut cipher = groups[0].group.group_bound_cipher().await.unwrap();
+
+        let old = cipher
+            .encrypt(&groups[0].group, b"messages", b"1", b"hello")
+            .await
+            .unwrap();
+
+        groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+
+        let mut cipher = GroupBoundCipher::from_bytes(&cipher.to_bytes().unwrap()).unwrap();
+
+        assert!(!cipher.needs_reencryption(&old).unwrap());
+
+        let new = cipher
+            .reencrypt(&groups[0].group, b"messages", b"1", &old)
+            .await
+            .unwrap();
+
+        assert_eq!(GroupBoundCipher::key_id(&old).unwrap(), epoch);
+        assert_eq!(GroupBoundCipher::key_id(&new).unwrap(), epoch + 1);
+        assert!(cipher.needs_reencryption(&old).unwrap());
+
+        cipher.retire_keys_before(epoch + 1);
+
+        let res = cipher
+            .decrypt(&groups[0].group, b"messages", b"1", &old)
+            .await;
+
+        assert_matches!(res, Err(MlsError::EpochNotFound));
+
+        let plaintext = cipher
+            .decrypt(&groups[0].group, b"messages", b"1", &new)
+            .await
+            .unwrap();
+
+        assert_eq!(plaintext.as_slice(), b"hello");
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn cipher_is_bound_to_its_group() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let mut cipher = alice.group.group_bound_cipher().await.unwrap();
+
+        cipher.group_id = b"other group".to_vec();
+
+        let res = cipher
+            .encrypt(&alice.group, b"messages", b"1", b"hello")
+            .await;
+
+        assert_matches!(res, Err(MlsError::GroupIdMismatch));
+    }
+}
@@ -17,12 +17,14 @@ use super::{ConfirmationTag, GroupContext};
     all(feature = "ffi", not(test)),
     safer_ffi_gen::ffi_type(clone, opaque)
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupInfo {
     pub(crate) group_context: GroupContext,
     pub(crate) extensions: ExtensionList,
     pub(crate) confirmation_tag: ConfirmationTag,
     pub(crate) signer: LeafIndex,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub(crate) signature: Vec<u8>,
 }
 
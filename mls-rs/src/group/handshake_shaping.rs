@@ -0,0 +1,282 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::{collections::VecDeque, vec::Vec};
+use mls_rs_core::{crypto::CipherSuiteProvider, error::IntoAnyError, time::MlsTime};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{framing::MlsMessagePayload, Group},
+    MlsMessage,
+};
+
+#[cfg(feature = "private_message")]
+use crate::group::framing::ContentType;
+
+/// Policy of a [`HandshakeQueue`] describing how handshake messages are
+/// delayed and padded before they are sent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShapingPolicy {
+    /// Upper bound in seconds of the random delay applied to handshake
+    /// messages. A new delay is drawn for every epoch.
+    pub max_delay_seconds: u64,
+    /// Sizes in bytes that shaped messages are padded to. A message is padded
+    /// to the smallest bucket it fits in, or to a multiple of the largest
+    /// bucket if it fits in none.
+    pub size_buckets: Vec<usize>,
+}
+
+impl ShapingPolicy {
+    pub fn new(max_delay_seconds: u64, size_buckets: Vec<usize>) -> Self {
+        Self {
+            max_delay_seconds,
+            size_buckets,
+        }
+    }
+
+    fn padded_size(&self, len: usize) -> usize {
+        let fitting = self.size_buckets.iter().filter(|&&size| size >= len).min();
+
+        match (fitting, self.size_buckets.iter().max()) {
+            (Some(&size), _) => size,
+            (None, Some(&largest)) if largest > 0 => ((len - 1) / largest + 1) * largest,
+            _ => len,
+        }
+    }
+}
+
+/// Outbound queue of handshake messages that hides commit frequency and
+/// message sizes from passive network observers.
+///
+/// Messages are added with [`Group::enqueue_handshake`]. All messages of an
+/// epoch are delayed by the same random amount chosen according to the
+/// [`ShapingPolicy`], and messages are always released in the order they
+/// were added. The application is responsible for calling
+/// [`HandshakeQueue::pop_ready`] once [`HandshakeQueue::next_release`] has
+/// passed, and receivers recover messages with
+/// [`HandshakeQueue::unpad_message`].
+#[derive(Clone, Debug, Default)]
+pub struct HandshakeQueue {
+    policy: ShapingPolicy,
+    epoch_delay: Option<(u64, u64)>,
+    pending: VecDeque<(MlsTime, Vec<u8>)>,
+}
+
+impl HandshakeQueue {
+    pub fn new(policy: ShapingPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Time at which the next message can be sent.
+    pub fn next_release(&self) -> Option<MlsTime> {
+        self.pending.front().map(|(release, _)| *release)
+    }
+
+    /// Remove and return the padded messages that can be sent at `now`, in
+    /// the order they were added.
+    pub fn pop_ready(&mut self, now: MlsTime) -> Vec<Vec<u8>> {
+        let mut ready = Vec::new();
+
+        while matches!(self.pending.front(), Some((release, _)) if *release <= now) {
+            ready.extend(self.pending.pop_front().map(|(_, message)| message));
+        }
+
+        ready
+    }
+
+    /// Recover a message padded by a [`HandshakeQueue`].
+    pub fn unpad_message(bytes: &[u8]) -> Result<MlsMessage, MlsError> {
+        let reader = &mut &*bytes;
+        let message: Vec<u8> = mls_rs_codec::byte_vec::mls_decode(reader)?;
+
+        if reader.iter().any(|&b| b != 0) {
+            return Err(MlsError::InvalidHandshakePadding);
+        }
+
+        MlsMessage::from_bytes(&message)
+    }
+
+    fn push<F>(
+        &mut self,
+        epoch: u64,
+        message: &MlsMessage,
+        now: MlsTime,
+        random_delay: F,
+    ) -> Result<(), MlsError>
+    where
+        F: FnOnce(u64) -> Result<u64, MlsError>,
+    {
+        let delay = match self.epoch_delay {
+            Some((delay_epoch, delay)) if delay_epoch == epoch => delay,
+            _ => {
+                let delay = random_delay(self.policy.max_delay_seconds)?;
+                self.epoch_delay = Some((epoch, delay));
+                delay
+            }
+        };
+
+        let release = MlsTime::from(now.seconds_since_epoch().saturating_add(delay));
+
+        // Never release a message before one that was added earlier.
+        let release = self
+            .pending
+            .back()
+            .map_or(release, |(last, _)| release.max(*last));
+
+        let mut padded = Vec::new();
+        mls_rs_codec::byte_vec::mls_encode(&message.to_bytes()?, &mut padded)?;
+        padded.resize(self.policy.padded_size(padded.len()), 0);
+
+        self.pending.push_back((release, padded));
+
+        Ok(())
+    }
+}
+
+fn is_handshake(message: &MlsMessage) -> bool {
+    match &message.payload {
+        MlsMessagePayload::Plain(_) | MlsMessagePayload::Welcome(_) => true,
+        #[cfg(feature = "private_message")]
+        MlsMessagePayload::Cipher(ciphertext) => {
+            ciphertext.content_type != ContentType::Application
+        }
+        MlsMessagePayload::GroupInfo(_) | MlsMessagePayload::KeyPackage(_) => false,
+        #[cfg(feature = "targeted_message")]
+        MlsMessagePayload::Targeted(_) => false,
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Add a proposal, commit or welcome `message` created by this group to
+    /// `queue` at time `now`.
+    pub fn enqueue_handshake(
+        &self,
+        queue: &mut HandshakeQueue,
+        message: &MlsMessage,
+        now: MlsTime,
+    ) -> Result<(), MlsError> {
+        if !is_handshake(message) {
+            return Err(MlsError::UnexpectedMessageType);
+        }
+
+        queue.push(self.current_epoch(), message, now, |max_delay| {
+            if max_delay == 0 {
+                return Ok(0);
+            }
+
+            let mut random = [0u8; 8];
+
+            self.cipher_suite_provider
+                .random_bytes(&mut random)
+                .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+            Ok(u64::from_be_bytes(random) % (max_delay + 1))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::time::MlsTime;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::{HandshakeQueue, ShapingPolicy};
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn handshake_messages_are_released_in_order() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let mut queue = HandshakeQueue::new(ShapingPolicy::new(30, vec![]));
+
+        let first = groups[0].group.commit(vec![]).await.unwrap().commit_message;
+        groups[0].group.clear_pending_commit();
+        let second = groups[0]
+            .group
+            .commit(vec![1])
+            .await
+            .unwrap()
+            .commit_message;
+
+        let now = MlsTime::from(1000);
+
+        for message in [&first, &second] {
+            groups[0]
+                .group
+                .enqueue_handshake(&mut queue, message, now)
+                .unwrap();
+        }
+
+        let release = queue.next_release().unwrap();
+        assert!(release >= now && release <= MlsTime::from(1030));
+
+        // Both messages belong to the same epoch and share the same delay.
+        let ready = queue.pop_ready(release);
+        assert!(queue.is_empty());
+
+        let ready = ready
+            .iter()
+            .map(|bytes| HandshakeQueue::unpad_message(bytes).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ready, vec![first, second]);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn handshake_messages_are_padded_to_buckets() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let mut queue = HandshakeQueue::new(ShapingPolicy::new(0, vec![64, 4096, 16384]));
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap().commit_message;
+        let now = MlsTime::from(1000);
+
+        groups[0]
+            .group
+            .enqueue_handshake(&mut queue, &commit, now)
+            .unwrap();
+
+        let ready = queue.pop_ready(now);
+
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].len(), 4096);
+        assert_eq!(HandshakeQueue::unpad_message(&ready[0]).unwrap(), commit);
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn application_messages_are_not_shaped() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let mut queue = HandshakeQueue::default();
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hi", vec![])
+            .await
+            .unwrap();
+
+        let res = groups[0]
+            .group
+            .enqueue_handshake(&mut queue, &message, MlsTime::from(0));
+
+        assert_matches!(res, Err(MlsError::UnexpectedMessageType));
+    }
+}
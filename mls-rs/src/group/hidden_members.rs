@@ -0,0 +1,174 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use mls_rs_core::identity::Credential;
+
+use crate::{
+    client::MlsError, client_config::ClientConfig, crypto::HpkeSecretKey,
+    extension::HiddenMembersExt, group::Group, identity::blinded::BlindedCredential,
+};
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Recover the real credential of the hidden member at `index` using the
+    /// secret key of the admin key in the
+    /// [`HiddenMembersExt`] of the group.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn unblind_member(
+        &self,
+        index: u32,
+        admin_secret_key: &HpkeSecretKey,
+    ) -> Result<Credential, MlsError> {
+        let hidden_members = self
+            .context()
+            .extensions
+            .get_as::<HiddenMembersExt>()?
+            .ok_or(MlsError::MemberNotHidden(index))?;
+
+        let member = self
+            .member_at_index(index)
+            .ok_or(MlsError::LeafNotFound(index))?;
+
+        BlindedCredential::from_credential(&member.signing_identity.credential)?
+            .ok_or(MlsError::MemberNotHidden(index))?
+            .unblind(
+                &self.cipher_suite_provider,
+                admin_secret_key,
+                &hidden_members.admin_key,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        crypto::{CipherSuiteProvider, HpkePublicKey},
+        extension::{ExtensionList, ExtensionType},
+        identity::{BasicCredential, MlsCredential, SigningIdentity},
+    };
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        client_builder::{BaseConfig, ClientBuilder, WithCryptoProvider, WithIdentityProvider},
+        crypto::test_utils::{test_cipher_suite_provider, TestCryptoProvider},
+        extension::{HiddenMembersExt, HIDDEN_MEMBERS_EXTENSION_TYPE},
+        group::Group,
+        identity::{
+            basic::BasicIdentityProvider,
+            blinded::{BlindedCredential, BlindedIdentityProvider},
+            test_utils::get_test_signing_identity,
+        },
+        Client,
+    };
+
+    type HiddenConfig = WithIdentityProvider<
+        BlindedIdentityProvider<BasicIdentityProvider>,
+        WithCryptoProvider<TestCryptoProvider, BaseConfig>,
+    >;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str, admin_key: Option<&HpkePublicKey>) -> Client<HiddenConfig> {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (mut identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        if let Some(admin_key) = admin_key {
+            identity = SigningIdentity::new(
+                BlindedCredential::new(&cs, identity.credential, admin_key)
+                    .await
+                    .unwrap()
+                    .into_credential()
+                    .unwrap(),
+                identity.signature_key,
+            );
+        }
+
+        ClientBuilder::new()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(BlindedIdentityProvider::new(BasicIdentityProvider::new()))
+            .extension_type(ExtensionType::new(HIDDEN_MEMBERS_EXTENSION_TYPE))
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn add_hidden_member(
+        group_admin_key: Option<&HpkePublicKey>,
+        admin_key: &HpkePublicKey,
+    ) -> Result<Group<HiddenConfig>, MlsError> {
+        let mut extensions = ExtensionList::new();
+
+        if let Some(group_admin_key) = group_admin_key {
+            extensions
+                .set_from(HiddenMembersExt::new(group_admin_key.clone()))
+                .unwrap();
+        }
+
+        let mut group = test_client("alice", None)
+            .await
+            .create_group(extensions)
+            .await
+            .unwrap();
+
+        let key_package = test_client("observer", Some(admin_key))
+            .await
+            .generate_key_package_message()
+            .await
+            .unwrap();
+
+        group
+            .commit_builder()
+            .add_member(key_package)?
+            .build()
+            .await?;
+
+        group.apply_pending_commit().await?;
+
+        Ok(group)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn hidden_member_can_only_be_unblinded_by_admin() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (admin_secret, admin_public) = cs.kem_generate().await.unwrap();
+        let (other_secret, _) = cs.kem_generate().await.unwrap();
+
+        let group = add_hidden_member(Some(&admin_public), &admin_public)
+            .await
+            .unwrap();
+
+        let member = group.member_at_index(1).unwrap();
+
+        assert_eq!(
+            member.signing_identity.credential.credential_type(),
+            BlindedCredential::credential_type()
+        );
+
+        let credential = group.unblind_member(1, &admin_secret).await.unwrap();
+
+        assert_eq!(
+            credential,
+            BasicCredential::new(b"observer".to_vec()).into_credential()
+        );
+
+        let res = group.unblind_member(1, &other_secret).await;
+        assert_matches!(res, Err(MlsError::InvalidBlindedCredential));
+
+        let res = group.unblind_member(0, &admin_secret).await;
+        assert_matches!(res, Err(MlsError::MemberNotHidden(0)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn hidden_member_requires_group_extension() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (_, admin_public) = cs.kem_generate().await.unwrap();
+
+        let res = add_hidden_member(None, &admin_public).await.map(|_| ());
+        assert_matches!(res, Err(MlsError::IdentityProviderError(_)));
+    }
+}
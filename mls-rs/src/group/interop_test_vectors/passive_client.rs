@@ -184,6 +184,7 @@ async fn interop_passive_client() {
             key_package,
             init_secret_key: test_case.init_priv.into(),
             leaf_node_secret_key: test_case.encryption_priv.into(),
+            last_resort: false,
         };
 
         let (id, pkg) = key_pckg_gen.to_storage().unwrap();
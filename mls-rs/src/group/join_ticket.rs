@@ -0,0 +1,379 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::MlsEncode;
+use mls_rs_core::{
+    crypto::CipherSuiteProvider, error::IntoAnyError, extension::ExtensionList,
+    identity::SigningIdentity, time::MlsTime,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::{JoinTicketExt, JoinTicketRequiredExt},
+    group::{framing::Content, key_schedule::KeySchedule, Group},
+    tree_kem::leaf_node::LeafNode,
+    MlsMessage,
+};
+
+const JOIN_TICKET_LABEL: &[u8] = b"mls-rs join ticket";
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn join_ticket_tag<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    key_schedule: &KeySchedule,
+    signature_key: &[u8],
+    not_after: u64,
+) -> Result<Vec<u8>, MlsError> {
+    let key = key_schedule
+        .export_secret(
+            JOIN_TICKET_LABEL,
+            &[],
+            cipher_suite_provider.kdf_extract_size(),
+            cipher_suite_provider,
+        )
+        .await?;
+
+    let mut data = Vec::new();
+    signature_key.mls_encode(&mut data)?;
+    not_after.mls_encode(&mut data)?;
+
+    cipher_suite_provider
+        .mac(&key, &data)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Issue a ticket allowing the client using `signing_identity` to join
+    /// the group with an external commit until `not_after`.
+    ///
+    /// The ticket is only valid in the current epoch. It is required by
+    /// members of groups with a [`JoinTicketRequiredExt`] group context
+    /// extension and can be delivered to the joiner using
+    /// [`Group::group_info_message_with_join_ticket`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn issue_join_ticket(
+        &self,
+        signing_identity: &SigningIdentity,
+        not_after: MlsTime,
+    ) -> Result<JoinTicketExt, MlsError> {
+        self.check_secrets_available()?;
+
+        let signature_key = signing_identity.signature_key.to_vec();
+        let not_after = not_after.seconds_since_epoch();
+
+        let tag = join_ticket_tag(
+            &self.cipher_suite_provider,
+            &self.key_schedule,
+            &signature_key,
+            not_after,
+        )
+        .await?;
+
+        Ok(JoinTicketExt {
+            signature_key,
+            not_after,
+            tag,
+        })
+    }
+
+    /// Create a group info message that can be used for an external commit
+    /// by the holder of `ticket`, see
+    /// [`Group::group_info_message_allowing_ext_commit`].
+    ///
+    /// The ticket is copied into the new leaf of the joiner by
+    /// [`ExternalCommitBuilder`](crate::group::external_commit::ExternalCommitBuilder).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn group_info_message_with_join_ticket(
+        &self,
+        ticket: JoinTicketExt,
+        with_tree_in_extension: bool,
+    ) -> Result<MlsMessage, MlsError> {
+        let mut extensions = ExtensionList::new();
+
+        extensions.set_from({
+            self.key_schedule
+                .get_external_key_pair_ext(&self.cipher_suite_provider)
+                .await?
+        })?;
+
+        extensions.set_from(ticket)?;
+
+        self.group_info_message_internal(extensions, with_tree_in_extension)
+            .await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn validate_join_ticket(
+        &self,
+        leaf_node: &LeafNode,
+        time_sent: Option<MlsTime>,
+    ) -> Result<(), MlsError> {
+        let Some(required) = self
+            .context()
+            .extensions
+            .get_as::<JoinTicketRequiredExt>()?
+        else {
+            return Ok(());
+        };
+
+        // An external joiner applying its own commit does not have the key
+        // schedule of the epoch the ticket was issued in.
+        let own_commit = self.pending_commit.as_ref().map_or(false, |pending| {
+            matches!(
+                &pending.content.content.content,
+                Content::Commit(commit)
+                    if commit.path.as_ref().map_or(false, |path| &path.leaf_node == leaf_node)
+            )
+        });
+
+        if own_commit {
+            return Ok(());
+        }
+
+        let ticket = leaf_node
+            .extensions
+            .get_as::<JoinTicketExt>()?
+            .ok_or(MlsError::JoinTicketRequired)?;
+
+        if ticket.signature_key != *leaf_node.signing_identity.signature_key {
+            return Err(MlsError::InvalidJoinTicket);
+        }
+
+        // Without a time for the message, e.g. when validating a key package,
+        // the ticket must be valid now.
+        #[cfg(feature = "std")]
+        let time_sent = time_sent.or_else(|| Some(MlsTime::now()));
+
+        if let Some(time_sent) = time_sent.map(|t| t.seconds_since_epoch()) {
+            if ticket.not_after < time_sent {
+                return Err(MlsError::JoinTicketExpired);
+            }
+
+            if ticket.not_after - time_sent > required.max_lifetime_in_s {
+                return Err(MlsError::InvalidJoinTicket);
+            }
+        }
+
+        let expected_tag = join_ticket_tag(
+            &self.cipher_suite_provider,
+            &self.key_schedule,
+            &ticket.signature_key,
+            ticket.not_after,
+        )
+        .await?;
+
+        if ticket.tag != expected_tag {
+            return Err(MlsError::InvalidJoinTicket);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        extension::{ExtensionList, ExtensionType},
+        time::MlsTime,
+    };
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::{TestClientBuilder, TestClientConfig},
+        extension::{
+            JoinTicketRequiredExt, JOIN_TICKET_EXTENSION_TYPE, JOIN_TICKET_REQUIRED_EXTENSION_TYPE,
+        },
+        group::test_utils::{test_group_custom_config, TestGroup},
+        identity::test_utils::get_test_signing_identity,
+        Client,
+    };
+
+    fn ticket_extension_types() -> Vec<ExtensionType> {
+        vec![
+            ExtensionType::new(JOIN_TICKET_EXTENSION_TYPE),
+            ExtensionType::new(JOIN_TICKET_REQUIRED_EXTENSION_TYPE),
+        ]
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn joiner(name: &str) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .extension_types(ticket_extension_types())
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn ticket_group() -> TestGroup {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.extension_types(ticket_extension_types())
+        })
+        .await;
+
+        let mut extensions = ExtensionList::new();
+        extensions
+            .set_from(JoinTicketRequiredExt::new(3600))
+            .unwrap();
+
+        alice
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        alice
+    }
+
+    fn ticket_not_after() -> u64 {
+        MlsTime::now().seconds_since_epoch() + 60
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_commit_with_ticket_is_accepted() {
+        let mut alice = ticket_group().await;
+        let bob = joiner("bob").await;
+
+        let (bob_identity, _) = bob.signing_identity().unwrap();
+
+        let ticket = alice
+            .group
+            .issue_join_ticket(bob_identity, MlsTime::from(ticket_not_after()))
+            .await
+            .unwrap();
+
+        let group_info = alice
+            .group
+            .group_info_message_with_join_ticket(ticket, true)
+            .await
+            .unwrap();
+
+        let (_, commit) = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .unwrap();
+
+        alice.group.process_incoming_message(commit).await.unwrap();
+
+        assert_eq!(alice.group.roster().members_iter().count(), 2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_commit_without_ticket_is_rejected() {
+        let mut alice = ticket_group().await;
+        let bob = joiner("bob").await;
+
+        let group_info = alice
+            .group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let (_, commit) = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .unwrap();
+
+        let res = alice.group.process_incoming_message(commit).await;
+
+        assert_matches!(res, Err(MlsError::JoinTicketRequired));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn leaked_ticket_cannot_be_used_by_other_client() {
+        let mut alice = ticket_group().await;
+        let bob = joiner("bob").await;
+        let mallory = joiner("mallory").await;
+
+        let (bob_identity, _) = bob.signing_identity().unwrap();
+
+        let ticket = alice
+            .group
+            .issue_join_ticket(bob_identity, MlsTime::from(ticket_not_after()))
+            .await
+            .unwrap();
+
+        let group_info = alice
+            .group
+            .group_info_message_with_join_ticket(ticket, true)
+            .await
+            .unwrap();
+
+        let (_, commit) = mallory
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .unwrap();
+
+        let res = alice.group.process_incoming_message(commit).await;
+
+        assert_matches!(res, Err(MlsError::InvalidJoinTicket));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn expired_ticket_is_rejected() {
+        let mut alice = ticket_group().await;
+        let bob = joiner("bob").await;
+
+        let (bob_identity, _) = bob.signing_identity().unwrap();
+        let not_after = MlsTime::from(MlsTime::now().seconds_since_epoch() - 1);
+
+        let ticket = alice
+            .group
+            .issue_join_ticket(bob_identity, not_after)
+            .await
+            .unwrap();
+
+        let group_info = alice
+            .group
+            .group_info_message_with_join_ticket(ticket, true)
+            .await
+            .unwrap();
+
+        let (_, commit) = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .unwrap();
+
+        let res = alice
+            .group
+            .process_incoming_message_with_time(
+                commit.clone(),
+                MlsTime::from(not_after.seconds_since_epoch() + 1),
+            )
+            .await;
+
+        assert_matches!(res, Err(MlsError::JoinTicketExpired));
+
+        // Without a sent time, the ticket is checked against the current
+        // time.
+        let res = alice.group.process_incoming_message(commit).await;
+        assert_matches!(res, Err(MlsError::JoinTicketExpired));
+    }
+}
@@ -19,7 +19,7 @@ use alloc::vec::Vec;
 use core::fmt::{self, Debug};
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::error::IntoAnyError;
-use zeroize::Zeroizing;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 use crate::crypto::{HpkeContextR, HpkeContextS, HpkePublicKey, HpkeSecretKey};
 
@@ -44,6 +44,9 @@ pub struct KeySchedule {
     init_secret: InitSecret,
 }
 
+// All secrets are wrapped in `Zeroizing`.
+impl ZeroizeOnDrop for KeySchedule {}
+
 impl Debug for KeySchedule {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("KeySchedule")
@@ -83,6 +86,23 @@ impl KeySchedule {
         }
     }
 
+    #[cfg(feature = "openmls_import")]
+    pub(crate) fn from_parts(
+        init_secret: Zeroizing<Vec<u8>>,
+        exporter_secret: Zeroizing<Vec<u8>>,
+        authentication_secret: Zeroizing<Vec<u8>>,
+        external_secret: Zeroizing<Vec<u8>>,
+        membership_key: Zeroizing<Vec<u8>>,
+    ) -> Self {
+        KeySchedule {
+            exporter_secret,
+            authentication_secret,
+            external_secret,
+            membership_key,
+            init_secret: InitSecret(init_secret),
+        }
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn derive_for_external<P: CipherSuiteProvider>(
         &self,
@@ -560,6 +580,20 @@ pub(crate) mod test_utils {
             self.membership_key = Zeroizing::new(key)
         }
     }
+
+    #[cfg(feature = "openmls_import")]
+    impl KeySchedule {
+        /// Secrets in the order of the arguments of `KeySchedule::from_parts`.
+        pub fn parts(&self) -> [Vec<u8>; 5] {
+            [
+                self.init_secret.0.to_vec(),
+                self.exporter_secret.to_vec(),
+                self.authentication_secret.to_vec(),
+                self.external_secret.to_vec(),
+                self.membership_key.to_vec(),
+            ]
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,417 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    extension::ExtensionList,
+    group::{Capabilities, Member, MemberUpdate},
+    identity::SigningIdentity,
+};
+use std::sync::Arc;
+
+use crate::{
+    client_config::ClientConfig,
+    group::{CommitMessageDescription, Group},
+};
+
+/// Change of a single member of a group caused by a commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemberChange {
+    /// The member was added to the group.
+    Joined(Member),
+    /// The member was removed from the group.
+    Left(Member),
+    /// The member updated its leaf, e.g. to change its credential.
+    Updated(Box<MemberUpdate>),
+}
+
+/// Member change together with the epoch created by the commit causing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberEvent {
+    pub epoch: u64,
+    pub change: MemberChange,
+}
+
+impl MemberEvent {
+    fn matches(&self, predicate: &dyn Fn(&Member) -> bool) -> bool {
+        match &self.change {
+            MemberChange::Joined(member) | MemberChange::Left(member) => predicate(member),
+            MemberChange::Updated(update) => predicate(&update.prior) || predicate(&update.new),
+        }
+    }
+}
+
+/// Callback receiving the [`MemberEvent`]s of a subscription created with
+/// [`Group::on_member_matching`].
+///
+/// The callback is invoked synchronously while the commit is processed and
+/// should therefore be cheap.
+pub trait MemberEventHandler: Send + Sync {
+    fn on_event(&self, event: &MemberEvent);
+}
+
+impl<F> MemberEventHandler for F
+where
+    F: Fn(&MemberEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &MemberEvent) {
+        self(event)
+    }
+}
+
+/// Identifier of a subscription created with [`Group::on_member_matching`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberSubscriptionId(u64);
+
+type MemberPredicate = dyn Fn(&Member) -> bool + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) struct MemberSubscription {
+    id: MemberSubscriptionId,
+    predicate: Arc<MemberPredicate>,
+    handler: Arc<dyn MemberEventHandler>,
+}
+
+impl Debug for MemberSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemberSubscription")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct LoggedMember {
+    index: u32,
+    signing_identity: SigningIdentity,
+    capabilities: Capabilities,
+    extensions: ExtensionList,
+}
+
+impl From<&Member> for LoggedMember {
+    fn from(member: &Member) -> Self {
+        Self {
+            index: member.index,
+            signing_identity: member.signing_identity.clone(),
+            capabilities: member.capabilities.clone(),
+            extensions: member.extensions.clone(),
+        }
+    }
+}
+
+impl From<LoggedMember> for Member {
+    fn from(member: LoggedMember) -> Self {
+        Member::new(
+            member.index,
+            member.signing_identity,
+            member.capabilities,
+            member.extensions,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+enum LoggedChange {
+    Joined(LoggedMember) = 1u8,
+    Left(LoggedMember) = 2u8,
+    Updated(Box<LoggedUpdate>) = 3u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct LoggedUpdate {
+    before: LoggedMember,
+    after: LoggedMember,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct LoggedEvent {
+    epoch: u64,
+    change: LoggedChange,
+}
+
+impl From<&MemberEvent> for LoggedEvent {
+    fn from(event: &MemberEvent) -> Self {
+        let change = match &event.change {
+            MemberChange::Joined(member) => LoggedChange::Joined(member.into()),
+            MemberChange::Left(member) => LoggedChange::Left(member.into()),
+            MemberChange::Updated(update) => LoggedChange::Updated(Box::new(LoggedUpdate {
+                before: (&update.prior).into(),
+                after: (&update.new).into(),
+            })),
+        };
+
+        Self {
+            epoch: event.epoch,
+            change,
+        }
+    }
+}
+
+impl From<LoggedEvent> for MemberEvent {
+    fn from(event: LoggedEvent) -> Self {
+        let change = match event.change {
+            LoggedChange::Joined(member) => MemberChange::Joined(member.into()),
+            LoggedChange::Left(member) => MemberChange::Left(member.into()),
+            LoggedChange::Updated(update) => {
+                let update = MemberUpdate::new(update.before.into(), update.after.into());
+                MemberChange::Updated(Box::new(update))
+            }
+        };
+
+        Self {
+            epoch: event.epoch,
+            change,
+        }
+    }
+}
+
+/// Bounded list of member events, oldest first.
+///
+/// The log is part of the group snapshot so that subscribers registering
+/// after a restart can catch up on the events they missed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct MemberEventLog {
+    events: Vec<LoggedEvent>,
+}
+
+impl MemberEventLog {
+    fn insert(&mut self, events: &[MemberEvent], capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+
+        self.events.extend(events.iter().map(LoggedEvent::from));
+
+        if self.events.len() > capacity {
+            self.events.drain(..self.events.len() - capacity);
+        }
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Subscribe `handler` to the [`MemberEvent`]s of members accepted by
+    /// `predicate`.
+    ///
+    /// An update matches if either the prior or the new state of the member
+    /// is accepted. If `since_epoch` is set, logged events of commits
+    /// creating that epoch or a later one are delivered to `handler` before
+    /// this function returns. The number of logged events is bounded by
+    /// [`ClientBuilder::member_event_log_size`](crate::client_builder::ClientBuilder::member_event_log_size).
+    ///
+    /// Subscriptions are not persisted and have to be created again after
+    /// loading the group from storage.
+    pub fn on_member_matching<P, H>(
+        &mut self,
+        predicate: P,
+        handler: H,
+        since_epoch: Option<u64>,
+    ) -> MemberSubscriptionId
+    where
+        P: Fn(&Member) -> bool + Send + Sync + 'static,
+        H: MemberEventHandler + 'static,
+    {
+        let id = MemberSubscriptionId(
+            self.member_subscriptions
+                .iter()
+                .map(|s| s.id.0 + 1)
+                .max()
+                .unwrap_or_default(),
+        );
+
+        let subscription = MemberSubscription {
+            id,
+            predicate: Arc::new(predicate),
+            handler: Arc::new(handler),
+        };
+
+        if let Some(since_epoch) = since_epoch {
+            self.logged_member_events(since_epoch)
+                .filter(|event| event.matches(&*subscription.predicate))
+                .for_each(|event| subscription.handler.on_event(&event));
+        }
+
+        self.member_subscriptions.push(subscription);
+
+        id
+    }
+
+    /// Remove a subscription created with [`Group::on_member_matching`].
+    pub fn unsubscribe_member_events(&mut self, id: MemberSubscriptionId) {
+        self.member_subscriptions.retain(|s| s.id != id);
+    }
+
+    /// Logged member events of commits creating `since_epoch` or a later
+    /// epoch.
+    pub fn member_events_since(&self, since_epoch: u64) -> Vec<MemberEvent> {
+        self.logged_member_events(since_epoch).collect()
+    }
+
+    fn logged_member_events(&self, since_epoch: u64) -> impl Iterator<Item = MemberEvent> + '_ {
+        self.member_event_log
+            .events
+            .iter()
+            .filter(move |event| event.epoch >= since_epoch)
+            .cloned()
+            .map(MemberEvent::from)
+    }
+
+    pub(crate) fn record_member_events(&mut self, commit: &CommitMessageDescription) {
+        let epoch = self.current_epoch();
+        let roster_update = commit.state_update.roster_update();
+
+        let events = roster_update
+            .added()
+            .iter()
+            .cloned()
+            .map(MemberChange::Joined)
+            .chain(
+                roster_update
+                    .removed()
+                    .iter()
+                    .cloned()
+                    .map(MemberChange::Left),
+            )
+            .chain(
+                roster_update
+                    .updated()
+                    .iter()
+                    .cloned()
+                    .map(|update| MemberChange::Updated(Box::new(update))),
+            )
+            .map(|change| MemberEvent { epoch, change })
+            .collect::<Vec<_>>();
+
+        self.member_event_log
+            .insert(&events, self.config.member_event_log_size());
+
+        for subscription in &self.member_subscriptions {
+            events
+                .iter()
+                .filter(|event| event.matches(&*subscription.predicate))
+                .for_each(|event| subscription.handler.on_event(event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::group::Member;
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::test_utils::{test_group, TestGroup},
+    };
+
+    use super::{MemberChange, MemberEvent};
+
+    fn is_bob(member: &Member) -> bool {
+        member
+            .signing_identity
+            .credential
+            .as_basic()
+            .map(|c| c.identifier.as_slice())
+            == Some(b"bob".as_slice())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn collector() -> (
+        Arc<Mutex<Vec<MemberEvent>>>,
+        impl Fn(&MemberEvent) + Send + Sync + 'static,
+    ) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+
+        let handler = move |event: &MemberEvent| sink.lock().unwrap().push(event.clone());
+
+        (events, handler)
+    }
+
+    fn collected(events: &Mutex<Vec<MemberEvent>>) -> Vec<MemberEvent> {
+        events.lock().unwrap().clone()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn remove_bob(alice: &mut TestGroup) {
+        alice
+            .group
+            .commit_builder()
+            .remove_member(1)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.process_pending_commit().await.unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn matching_events_are_delivered() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (events, handler) = collector();
+
+        alice.group.on_member_matching(is_bob, handler, None);
+
+        alice.join("bob").await;
+        alice.join("carol").await;
+        remove_bob(&mut alice).await;
+
+        let events = collected(&events);
+
+        assert_eq!(events.len(), 2);
+        assert_matches!(&events[0].change, MemberChange::Joined(m) if is_bob(m));
+        assert_matches!(&events[1].change, MemberChange::Left(m) if is_bob(m));
+        assert_eq!(events[1].epoch, alice.group.current_epoch());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn missed_events_are_delivered_from_the_log() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice.group.config.0.settings.member_event_log_size = 10;
+
+        alice.join("bob").await;
+        let epoch = alice.group.current_epoch();
+        remove_bob(&mut alice).await;
+
+        let (events, handler) = collector();
+        alice
+            .group
+            .on_member_matching(is_bob, handler, Some(epoch + 1));
+
+        let events = collected(&events);
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(&events[0].change, MemberChange::Left(m) if is_bob(m));
+
+        let logged = alice.group.member_events_since(0);
+
+        assert_eq!(logged.iter().filter(|e| e.matches(&is_bob)).count(), 2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn unsubscribed_handler_receives_no_events() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (events, handler) = collector();
+
+        let id = alice.group.on_member_matching(|_| true, handler, None);
+        alice.group.unsubscribe_member_events(id);
+
+        alice.join("bob").await;
+
+        assert_eq!(collected(&events), vec![]);
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{group::Member, time::MlsTime};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::MemberExpiryExt,
+    group::{
+        proposal::{Proposal, RemoveProposal},
+        proposal_filter::{ProposalBundle, ProposalSource},
+        Group, Sender,
+    },
+    tree_kem::{leaf_node::LeafNode, node::LeafIndex},
+};
+
+/// Indexes of the members of `members` that are expired at `time`.
+pub(crate) fn expired_members(
+    members: impl Iterator<Item = Member>,
+    time: MlsTime,
+) -> impl Iterator<Item = u32> {
+    members.filter_map(move |member| {
+        member
+            .extensions
+            .get_as::<MemberExpiryExt>()
+            .ok()
+            .flatten()
+            .filter(|expiry| expiry.is_expired(time))
+            .map(|_| member.index)
+    })
+}
+
+/// Add a Remove proposal from `committer` to `proposals` for every expired
+/// member that is not already removed. The committer is never removed.
+pub(crate) fn add_expired_member_removals(
+    proposals: &mut ProposalBundle,
+    members: impl Iterator<Item = Member>,
+    committer: u32,
+    time: MlsTime,
+) {
+    let expired = expired_members(members, time)
+        .filter(|&index| {
+            index != committer
+                && !proposals
+                    .remove_proposals()
+                    .iter()
+                    .any(|p| p.proposal.to_remove() == index)
+        })
+        .collect::<Vec<_>>();
+
+    for index in expired {
+        let proposal = Proposal::Remove(RemoveProposal {
+            to_remove: LeafIndex(index),
+        });
+
+        proposals.add(proposal, Sender::Member(committer), ProposalSource::ByValue);
+    }
+}
+
+/// Check that `new` keeps the [`MemberExpiryExt`] of the leaf `old` at
+/// `index`, possibly with an earlier expiry time.
+pub(crate) fn ensure_expiry_not_extended(
+    old: &LeafNode,
+    new: &LeafNode,
+    index: u32,
+) -> Result<(), MlsError> {
+    let Some(old_expiry) = old.extensions.get_as::<MemberExpiryExt>()? else {
+        return Ok(());
+    };
+
+    new.extensions
+        .get_as::<MemberExpiryExt>()?
+        .filter(|new_expiry| new_expiry.expires_at <= old_expiry.expires_at)
+        .map(|_| ())
+        .ok_or(MlsError::MemberExpiryExtended(index))
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Indexes of the members whose [`MemberExpiryExt`] is expired at `time`.
+    pub fn expired_members(&self, time: MlsTime) -> Vec<u32> {
+        expired_members(self.roster().members_iter(), time).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        extension::{ExtensionList, ExtensionType},
+        time::MlsTime,
+    };
+
+    use crate::{
+        client::{
+            test_utils::{TestClientBuilder, TEST_CIPHER_SUITE},
+            MlsError,
+        },
+        extension::{MemberExpiryExt, MEMBER_EXPIRY_EXTENSION_TYPE},
+        group::Group,
+        identity::test_utils::get_test_signing_identity,
+        mls_rules::DefaultMlsRules,
+        Client,
+    };
+
+    use crate::client::test_utils::TestClientConfig;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str, expires_at: Option<u64>) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        let mut leaf_node_extensions = ExtensionList::new();
+
+        if let Some(expires_at) = expires_at {
+            leaf_node_extensions
+                .set_from(MemberExpiryExt::new(expires_at.into()))
+                .unwrap();
+        }
+
+        TestClientBuilder::new_for_test()
+            .extension_type(ExtensionType::new(MEMBER_EXPIRY_EXTENSION_TYPE))
+            .leaf_node_extensions(leaf_node_extensions)
+            .mls_rules(DefaultMlsRules::new().with_remove_expired_members(true))
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    // Alice creates a group and adds Bob, who expires at `expires_at`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn group_with_guest(
+        expires_at: u64,
+    ) -> (Group<TestClientConfig>, Group<TestClientConfig>) {
+        let alice = test_client("alice", None).await;
+        let bob = test_client("bob", Some(expires_at)).await;
+
+        let mut alice_group = alice.create_group(Default::default()).await.unwrap();
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        (alice_group, bob_group)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn expired_member_is_removed_by_next_commit() {
+        for (expires_at, remaining_members) in [(u64::MAX, 2), (1000, 1)] {
+            let (mut alice_group, _) = group_with_guest(expires_at).await;
+
+            alice_group.commit(vec![]).await.unwrap();
+            alice_group.apply_pending_commit().await.unwrap();
+
+            assert_eq!(
+                alice_group.roster().members_iter().count(),
+                remaining_members
+            );
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn expiry_can_not_be_extended_by_update() {
+        let (mut alice_group, mut bob_group) = group_with_guest(1000).await;
+
+        let mut extensions = ExtensionList::new();
+
+        extensions
+            .set_from(MemberExpiryExt::new(MlsTime::from(2000)))
+            .unwrap();
+
+        bob_group.config.0.settings.leaf_node_extensions = extensions;
+
+        let commit = bob_group.commit(vec![]).await.unwrap();
+
+        let res = alice_group
+            .process_incoming_message(commit.commit_message)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::MemberExpiryExtended(1)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_builder_removes_expired_members() {
+        let (mut alice_group, _) = group_with_guest(1000).await;
+        alice_group.config.0.mls_rules = DefaultMlsRules::new();
+
+        assert!(alice_group.expired_members(MlsTime::from(999)).is_empty());
+        assert_eq!(alice_group.expired_members(MlsTime::from(1000)), [1]);
+
+        alice_group
+            .commit_builder()
+            .remove_expired_members(MlsTime::from(999))
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+        assert_eq!(alice_group.roster().members_iter().count(), 2);
+
+        alice_group
+            .commit_builder()
+            .remove_expired_members(MlsTime::from(1000))
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+        assert_eq!(alice_group.roster().members_iter().count(), 1);
+    }
+}
@@ -0,0 +1,353 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{
+    error::IntoAnyError,
+    extension::ExtensionList,
+    identity::{IdentityProvider, SigningIdentity},
+    psk::ExternalPskId,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::{CommitReason, MemberResetExt, MemberResetSlot},
+    group::{proposal_filter::ProposalBundle, CommitOutput, Group},
+    tree_kem::leaf_node::LeafNode,
+};
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn identity<C: IdentityProvider>(
+    identity_provider: &C,
+    signing_identity: &SigningIdentity,
+    extensions: &ExtensionList,
+) -> Result<Vec<u8>, MlsError> {
+    identity_provider
+        .identity(signing_identity, extensions)
+        .await
+        .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))
+}
+
+/// Check that every member added by `proposals`, or joining with
+/// `external_leaf`, whose identity matches a [`MemberResetSlot`] of the
+/// group is added together with the pre-shared key of the slot.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn ensure_reset_members_have_psk<C: IdentityProvider>(
+    proposals: &ProposalBundle,
+    external_leaf: Option<&LeafNode>,
+    identity_provider: &C,
+    extensions: &ExtensionList,
+) -> Result<(), MlsError> {
+    let Some(reset) = extensions.get_as::<MemberResetExt>()? else {
+        return Ok(());
+    };
+
+    let added = proposals
+        .add_proposals()
+        .iter()
+        .map(|p| &p.proposal.key_package.leaf_node)
+        .chain(external_leaf);
+
+    for leaf in added {
+        let identity = identity(identity_provider, &leaf.signing_identity, extensions).await?;
+
+        let Some(slot) = reset.slots.iter().find(|slot| slot.identity == identity) else {
+            continue;
+        };
+
+        let has_psk = proposals
+            .psk_proposals()
+            .iter()
+            .any(|p| p.proposal.external_psk_id() == Some(&slot.psk_id));
+
+        if !has_psk {
+            return Err(MlsError::MemberResetPskRequired);
+        }
+    }
+
+    Ok(())
+}
+
+/// Slots of `new` that are not in `old`.
+pub(crate) fn new_reset_slots(
+    old: &ExtensionList,
+    new: &ExtensionList,
+) -> Result<Vec<MemberResetSlot>, MlsError> {
+    let old = old.get_as::<MemberResetExt>()?.unwrap_or_default();
+    let new = new.get_as::<MemberResetExt>()?.unwrap_or_default();
+
+    Ok(new
+        .slots
+        .into_iter()
+        .filter(|slot| !old.slots.contains(slot))
+        .collect())
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Commit the removal of the member at `index`, suspected to be
+    /// compromised, and record a [`MemberResetSlot`] in the
+    /// [`MemberResetExt`] of the group.
+    ///
+    /// The member can then only be added back, with new keys, by a commit
+    /// including the external pre-shared key `psk_id`, which must be shared
+    /// with the member out of band. The commit is sent with the
+    /// [`CommitReason::MEMBER_RESET`] reason and receivers find the new slot
+    /// in `StateUpdate::member_resets`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn force_reset_member(
+        &mut self,
+        index: u32,
+        psk_id: ExternalPskId,
+    ) -> Result<CommitOutput, MlsError> {
+        let member = self
+            .member_at_index(index)
+            .ok_or(MlsError::LeafNotFound(index))?;
+
+        let mut extensions = self.context().extensions.clone();
+
+        let identity = identity(
+            &self.config.identity_provider(),
+            &member.signing_identity,
+            &extensions,
+        )
+        .await?;
+
+        let mut reset = extensions.get_as::<MemberResetExt>()?.unwrap_or_default();
+        reset.slots.retain(|slot| slot.identity != identity);
+        reset.slots.push(MemberResetSlot { identity, psk_id });
+        extensions.set_from(reset)?;
+
+        self.commit_builder()
+            .remove_member(index)?
+            .set_group_context_ext(extensions)?
+            .commit_reason(CommitReason::MEMBER_RESET)
+            .build()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        extension::ExtensionType,
+        psk::{ExternalPskId, PreSharedKey},
+    };
+
+    use crate::{
+        client::{
+            test_utils::{TestClientBuilder, TestClientConfig, TEST_CIPHER_SUITE},
+            MlsError,
+        },
+        extension::{CommitReason, MemberResetSlot, MEMBER_RESET_EXTENSION_TYPE},
+        group::{Group, ReceivedMessage},
+        identity::test_utils::get_test_signing_identity,
+        Client,
+    };
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .extension_type(ExtensionType::new(MEMBER_RESET_EXTENSION_TYPE))
+            .psk(
+                ExternalPskId::new(b"reset".to_vec()),
+                PreSharedKey::from(vec![1]),
+            )
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    // Alice creates a group and adds Bob and Carol in this order.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_groups() -> Vec<Group<TestClientConfig>> {
+        let alice_group = test_client("alice")
+            .await
+            .create_group(Default::default())
+            .await
+            .unwrap();
+
+        let mut groups = vec![alice_group];
+
+        for name in ["bob", "carol"] {
+            let client = test_client(name).await;
+            let key_package = client.generate_key_package_message().await.unwrap();
+
+            let commit = groups[0]
+                .commit_builder()
+                .add_member(key_package)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+
+            groups[0].apply_pending_commit().await.unwrap();
+
+            for group in groups[1..].iter_mut() {
+                group
+                    .process_incoming_message(commit.commit_message.clone())
+                    .await
+                    .unwrap();
+            }
+
+            let (group, _) = client
+                .join_group(None, &commit.welcome_messages[0])
+                .await
+                .unwrap();
+
+            groups.push(group);
+        }
+
+        groups
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn reset_member_is_reported_to_all_members() {
+        let mut groups = test_groups().await;
+        let psk_id = ExternalPskId::new(b"reset".to_vec());
+
+        let commit = groups[0]
+            .force_reset_member(1, psk_id.clone())
+            .await
+            .unwrap();
+
+        let expected = vec![MemberResetSlot {
+            identity: b"bob".to_vec(),
+            psk_id,
+        }];
+
+        let alice_update = groups[0].apply_pending_commit().await.unwrap().state_update;
+
+        let received = groups[2]
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let ReceivedMessage::Commit(carol_commit) = received else {
+            panic!("expected commit");
+        };
+
+        for update in [alice_update, carol_commit.state_update] {
+            assert_eq!(update.commit_reason(), Some(CommitReason::MEMBER_RESET));
+            assert_eq!(update.member_resets(), expected);
+            assert_eq!(update.roster_update().removed().len(), 1);
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn reset_member_can_only_be_added_back_with_psk() {
+        let mut groups = test_groups().await;
+        let psk_id = ExternalPskId::new(b"reset".to_vec());
+
+        let commit = groups[0]
+            .force_reset_member(1, psk_id.clone())
+            .await
+            .unwrap();
+
+        groups[0].apply_pending_commit().await.unwrap();
+
+        groups[2]
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let key_package = test_client("bob")
+            .await
+            .generate_key_package_message()
+            .await
+            .unwrap();
+
+        let res = groups[0]
+            .commit_builder()
+            .add_member(key_package.clone())
+            .unwrap()
+            .build()
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::MemberResetPskRequired));
+
+        let commit = groups[0]
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .add_external_psk(psk_id)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[0].apply_pending_commit().await.unwrap();
+
+        groups[2]
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(groups[2].roster().members_iter().count(), 3);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn reset_member_can_rejoin_by_external_commit_with_psk() {
+        let mut groups = test_groups().await;
+        let psk_id = ExternalPskId::new(b"reset".to_vec());
+
+        let commit = groups[0]
+            .force_reset_member(1, psk_id.clone())
+            .await
+            .unwrap();
+
+        groups[0].apply_pending_commit().await.unwrap();
+
+        groups[2]
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let group_info = groups[0]
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let bob = test_client("bob").await;
+
+        let res = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info.clone())
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::MemberResetPskRequired));
+
+        let (_, external_commit) = bob
+            .external_commit_builder()
+            .unwrap()
+            .with_external_psk(psk_id)
+            .with_authenticated_data(b"rejoin".to_vec())
+            .build(group_info)
+            .await
+            .unwrap();
+
+        let received = groups[2]
+            .process_incoming_message(external_commit)
+            .await
+            .unwrap();
+
+        let ReceivedMessage::Commit(description) = received else {
+            panic!("expected commit");
+        };
+
+        assert!(description.is_external);
+        assert_eq!(description.authenticated_data, b"rejoin");
+        assert_eq!(groups[2].roster().members_iter().count(), 3);
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::Group,
+    tree_kem::{node::LeafIndex, MembershipProof},
+};
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create a [`MembershipProof`] for the member at `leaf_index` that can
+    /// be verified against the tree hash of the current epoch.
+    pub fn membership_proof(&self, leaf_index: u32) -> Result<MembershipProof, MlsError> {
+        self.state
+            .public_tree
+            .membership_proof(LeafIndex(leaf_index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        crypto::test_utils::test_cipher_suite_provider,
+        group::test_utils::test_n_member_group,
+        tree_kem::MembershipProof,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn membership_proofs_verify_for_all_members() {
+        let groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 5).await;
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let context = groups[0].group.context();
+
+        for index in 0..5 {
+            let proof = groups[0].group.membership_proof(index).unwrap();
+            let proof = MembershipProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+
+            assert_eq!(proof.leaf_index(), index);
+
+            assert_eq!(
+                proof.signing_identity(),
+                groups[index as usize]
+                    .group
+                    .current_member_signing_identity()
+                    .unwrap()
+            );
+
+            proof.verify(&cs, context).await.unwrap();
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn membership_proof_of_previous_epoch_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let proof = groups[0].group.membership_proof(2).unwrap();
+
+        groups[0]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[0].process_pending_commit().await.unwrap();
+
+        let res = proof.verify(&cs, groups[0].group.context()).await;
+        assert_matches!(res, Err(MlsError::InvalidMembershipProof));
+
+        assert!(groups[0].group.membership_proof(2).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{crypto::CipherSuite, group::ProposalType, protocol_version::ProtocolVersion};
+
+use crate::{
+    group::{
+        framing::{Content, ContentType, MlsMessagePayload, Sender, WireFormat},
+        proposal::ProposalOrRef,
+    },
+    MlsMessage,
+};
+
+#[cfg(feature = "by_ref_proposal")]
+use crate::mls_rules::ProposalRef;
+
+/// Proposal covered by a commit, see
+/// [`MlsMessageDescription::committed_proposals`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommittedProposal {
+    /// Proposal of the given type sent by value in the commit.
+    ByValue(ProposalType),
+    /// Reference to a proposal sent in a separate message.
+    #[cfg(feature = "by_ref_proposal")]
+    ByReference(ProposalRef),
+}
+
+/// Breakdown of the fields of an [`MlsMessage`] that are readable without
+/// group state, produced by [`MlsMessage::describe`].
+///
+/// Fields that are encrypted or not defined for the wire format of the
+/// message are `None`.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct MlsMessageDescription {
+    /// Protocol version of the message.
+    pub version: ProtocolVersion,
+    /// Wire format of the message.
+    pub wire_format: WireFormat,
+    /// Id of the group the message belongs to. Not defined for welcome
+    /// messages and key packages.
+    pub group_id: Option<Vec<u8>>,
+    /// Epoch the message belongs to. Not defined for welcome messages and
+    /// key packages.
+    pub epoch: Option<u64>,
+    /// Cipher suite of welcome messages, group infos and key packages.
+    pub cipher_suite: Option<CipherSuite>,
+    /// Sender of a public message.
+    pub sender: Option<Sender>,
+    /// Content type of a public or private message.
+    pub content_type: Option<ContentType>,
+    /// Proposals covered by a commit sent as a public message, in the order
+    /// they appear in the commit.
+    pub committed_proposals: Vec<CommittedProposal>,
+    /// For a commit sent as a public message, true if the commit contains
+    /// an update path.
+    pub has_path: Option<bool>,
+}
+
+impl MlsMessage {
+    /// Describe the fields of this message that are readable without group
+    /// state, e.g. to route and log messages in a delivery service.
+    pub fn describe(&self) -> MlsMessageDescription {
+        let mut description = MlsMessageDescription {
+            version: self.version,
+            wire_format: self.wire_format(),
+            group_id: self.group_id().map(|id| id.to_vec()),
+            epoch: self.epoch(),
+            cipher_suite: self.cipher_suite(),
+            sender: None,
+            content_type: self.content_type(),
+            committed_proposals: Vec::new(),
+            has_path: None,
+        };
+
+        if let MlsMessagePayload::Plain(message) = &self.payload {
+            description.sender = Some(message.content.sender);
+
+            if let Content::Commit(commit) = &message.content.content {
+                description.committed_proposals =
+                    commit.proposals.iter().map(committed_proposal).collect();
+
+                description.has_path = Some(commit.path.is_some());
+            }
+        }
+
+        description
+    }
+}
+
+fn committed_proposal(proposal: &ProposalOrRef) -> CommittedProposal {
+    match proposal {
+        ProposalOrRef::Proposal(p) => CommittedProposal::ByValue(p.proposal_type()),
+        #[cfg(feature = "by_ref_proposal")]
+        ProposalOrRef::Reference(r) => CommittedProposal::ByReference(r.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::group::ProposalType;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::{
+            framing::{ContentType, Sender, WireFormat},
+            test_utils::{test_group, test_n_member_group},
+        },
+        key_package::test_utils::test_key_package_message,
+    };
+
+    use super::CommittedProposal;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn describes_public_commit() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let commit = groups[1]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let description = commit.commit_message.describe();
+
+        assert_eq!(description.wire_format, WireFormat::PublicMessage);
+        assert_eq!(
+            description.group_id.as_deref(),
+            Some(groups[1].group.group_id())
+        );
+        assert_eq!(description.epoch, Some(groups[1].group.current_epoch()));
+        assert_eq!(description.sender, Some(Sender::Member(1)));
+        assert_eq!(description.content_type, Some(ContentType::Commit));
+        assert_eq!(description.has_path, Some(true));
+
+        assert_eq!(
+            description.committed_proposals,
+            vec![CommittedProposal::ByValue(ProposalType::REMOVE)]
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn describes_welcome_and_key_package() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let key_package =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let description = key_package.describe();
+
+        assert_eq!(description.wire_format, WireFormat::KeyPackage);
+        assert_eq!(description.cipher_suite, Some(TEST_CIPHER_SUITE));
+        assert_matches!(description.group_id, None);
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let description = commit.welcome_messages[0].describe();
+
+        assert_eq!(description.wire_format, WireFormat::Welcome);
+        assert_eq!(description.epoch, None);
+        assert_eq!(description.sender, None);
+        assert!(description.committed_proposals.is_empty());
+    }
+}
@@ -9,17 +9,19 @@ use super::{
         ApplicationData, Content, ContentType, MlsMessage, MlsMessagePayload, PublicMessage, Sender,
     },
     message_signature::AuthenticatedContent,
-    mls_rules::{CommitDirection, MlsRules},
+    mls_rules::{is_cipher_suite_downgrade, CommitDirection, MlsRules},
     proposal_filter::ProposalBundle,
     state::GroupState,
     transcript_hash::InterimTranscriptHash,
     transcript_hashes, validate_group_info_member, GroupContext, GroupInfo, Welcome,
 };
 use crate::{
+    cipher_suite::CipherSuite,
     client::MlsError,
     key_package::validate_key_package_properties,
     time::MlsTime,
     tree_kem::{
+        leaf_node::LeafNode,
         leaf_node_validator::{LeafNodeValidator, ValidationContext},
         node::LeafIndex,
         path_secret::PathSecret,
@@ -34,7 +36,8 @@ use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::fmt::{self, Debug};
 use mls_rs_core::{
-    identity::IdentityProvider, protocol_version::ProtocolVersion, psk::PreSharedKeyStorage,
+    extension::ExtensionType, identity::IdentityProvider, protocol_version::ProtocolVersion,
+    psk::PreSharedKeyStorage,
 };
 
 #[cfg(feature = "by_ref_proposal")]
@@ -50,10 +53,13 @@ use super::proposal::Proposal;
 use super::proposal_filter::ProposalInfo;
 
 #[cfg(feature = "state_update")]
-use mls_rs_core::{
-    crypto::CipherSuite,
-    group::{MemberUpdate, RosterUpdate},
-};
+use mls_rs_core::group::{MemberUpdate, RosterUpdate};
+
+#[cfg(feature = "state_update")]
+use crate::extension::{CommitReason, CommitReasonExt};
+
+#[cfg(feature = "member_reset")]
+use crate::extension::MemberResetSlot;
 
 #[cfg(all(feature = "state_update", feature = "psk"))]
 use mls_rs_core::psk::ExternalPskId;
@@ -70,6 +76,12 @@ use super::proposal::CustomProposal;
 #[cfg(feature = "private_message")]
 use crate::group::framing::PrivateMessage;
 
+#[cfg(feature = "decryption_journal")]
+use super::AlreadyProcessedMessage;
+
+#[cfg(feature = "member_quarantine")]
+use super::quarantine::QuarantineIdentityProvider;
+
 #[derive(Debug)]
 pub(crate) struct ProvisionalState {
     pub(crate) public_tree: TreeKemPublic,
@@ -116,6 +128,12 @@ pub struct StateUpdate {
     pub(crate) custom_proposals: Vec<ProposalInfo<CustomProposal>>,
     #[cfg(feature = "by_ref_proposal")]
     pub(crate) unused_proposals: Vec<crate::mls_rules::ProposalInfo<Proposal>>,
+    pub(crate) commit_reason: Option<CommitReason>,
+    #[cfg(feature = "member_reset")]
+    pub(crate) member_resets: Vec<MemberResetSlot>,
+    #[cfg(feature = "system_events")]
+    pub(crate) system_events: Vec<crate::group::SystemEvent>,
+    pub(crate) warnings: Vec<ProcessingWarning>,
 }
 
 #[cfg(not(feature = "state_update"))]
@@ -169,6 +187,48 @@ impl StateUpdate {
     pub fn pending_reinit_ciphersuite(&self) -> Option<CipherSuite> {
         self.pending_reinit
     }
+
+    /// Reason for the commit set by the committer with
+    /// [`CommitBuilder::commit_reason`](crate::group::CommitBuilder::commit_reason).
+    pub fn commit_reason(&self) -> Option<CommitReason> {
+        self.commit_reason
+    }
+
+    /// Members reset by the commit with
+    /// [`Group::force_reset_member`](crate::group::Group::force_reset_member).
+    #[cfg(feature = "member_reset")]
+    pub fn member_resets(&self) -> &[MemberResetSlot] {
+        &self.member_resets
+    }
+
+    /// Changes made by the commit, described as events suitable for
+    /// rendering localized system messages.
+    #[cfg(feature = "system_events")]
+    pub fn system_events(&self) -> &[crate::group::SystemEvent] {
+        &self.system_events
+    }
+
+    /// Issues found while processing the commit that did not cause it to be
+    /// rejected.
+    pub fn warnings(&self) -> &[ProcessingWarning] {
+        &self.warnings
+    }
+}
+
+/// Issue found while joining a group or processing a commit that did not
+/// cause it to be rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProcessingWarning {
+    /// The group context contains an extension not supported by this client,
+    /// see [`UnknownExtensionPolicy::Warn`](crate::mls_rules::UnknownExtensionPolicy::Warn).
+    UnknownGroupContextExtension(ExtensionType),
+    /// The group info used to join contains an extension not supported by
+    /// this client, see [`UnknownExtensionPolicy::Warn`](crate::mls_rules::UnknownExtensionPolicy::Warn).
+    UnknownGroupInfoExtension(ExtensionType),
+    /// A ReInit proposal moves the group to a weaker cipher suite, see
+    /// [`MlsRules::allow_cipher_suite_downgrade`](crate::MlsRules::allow_cipher_suite_downgrade).
+    CipherSuiteDowngrade { from: CipherSuite, to: CipherSuite },
 }
 
 #[cfg_attr(
@@ -177,8 +237,12 @@ impl StateUpdate {
 )]
 #[derive(Debug, Clone)]
 #[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 /// An event generated as a result of processing a message for a group with
 /// [`Group::process_incoming_message`](crate::group::Group::process_incoming_message).
+///
+/// Some variants, such as `TargetedMessage`, only exist when the matching
+/// Cargo feature is enabled, so matches on this type need a wildcard arm.
 pub enum ReceivedMessage {
     /// An application message was decrypted.
     ApplicationMessage(ApplicationMessageDescription),
@@ -192,6 +256,13 @@ pub enum ReceivedMessage {
     Welcome,
     /// Validated key package
     KeyPackage(KeyPackage),
+    /// An application message that was already decrypted by this client was
+    /// received again.
+    #[cfg(feature = "decryption_journal")]
+    AlreadyProcessed(AlreadyProcessedMessage),
+    /// An application message encrypted to this member only was decrypted.
+    #[cfg(feature = "targeted_message")]
+    TargetedMessage(ApplicationMessageDescription),
 }
 
 impl TryFrom<ApplicationMessageDescription> for ReceivedMessage {
@@ -242,7 +313,7 @@ pub struct ApplicationMessageDescription {
     /// Index of this user in the group state.
     pub sender_index: u32,
     /// Received application data.
-    data: ApplicationData,
+    pub(crate) data: ApplicationData,
     /// Plaintext authenticated data in the received MLS packet.
     pub authenticated_data: Vec<u8>,
 }
@@ -523,6 +594,10 @@ pub(crate) trait MessageProcessor: Send + Sync {
 
                 Ok(EventOrContent::Event(key_package.into()))
             }
+            // Targeted messages are handled by the recipient group before
+            // reaching the message processor.
+            #[cfg(feature = "targeted_message")]
+            MlsMessagePayload::Targeted(_) => Err(MlsError::UnexpectedMessageType),
         }
     }
 
@@ -703,6 +778,12 @@ pub(crate) trait MessageProcessor: Send + Sync {
 
         let roster_update = RosterUpdate::new(added, removed, updated);
 
+        let commit_reason = path
+            .map(|path| path.leaf_node.extensions.get_as::<CommitReasonExt>())
+            .transpose()?
+            .flatten()
+            .map(|ext| ext.reason);
+
         let update = StateUpdate {
             roster_update,
             #[cfg(feature = "psk")]
@@ -718,6 +799,21 @@ pub(crate) trait MessageProcessor: Send + Sync {
             custom_proposals: provisional.applied_proposals.custom_proposals.clone(),
             #[cfg(feature = "by_ref_proposal")]
             unused_proposals: provisional.unused_proposals.clone(),
+            commit_reason,
+            #[cfg(feature = "member_reset")]
+            member_resets: crate::group::member_reset::new_reset_slots(
+                &self.group_state().context.extensions,
+                &provisional.group_context.extensions,
+            )?,
+            #[cfg(feature = "system_events")]
+            system_events: crate::group::system_event::system_events(
+                &self.group_state().context,
+                old_tree,
+                provisional,
+                path,
+                sender,
+            )?,
+            warnings: Vec::new(),
         };
 
         Ok(update)
@@ -749,6 +845,11 @@ pub(crate) trait MessageProcessor: Send + Sync {
         #[cfg(not(any(feature = "private_message", feature = "by_ref_proposal")))]
         let Content::Commit(commit) = auth_content.content.content;
 
+        if let (Sender::NewMemberCommit, Some(path)) = (auth_content.content.sender, &commit.path) {
+            self.validate_external_joiner(&path.leaf_node, time_sent)
+                .await?;
+        }
+
         let group_state = self.group_state();
         let id_provider = self.identity_provider();
 
@@ -760,6 +861,21 @@ pub(crate) trait MessageProcessor: Send + Sync {
         #[cfg(not(feature = "by_ref_proposal"))]
         let proposals = resolve_for_commit(auth_content.content.sender, commit.proposals)?;
 
+        #[cfg(feature = "member_quarantine")]
+        let id_provider = {
+            let deferred = if self.defers_add_validation() {
+                proposals
+                    .add_proposals()
+                    .iter()
+                    .map(|p| p.proposal.signing_identity().signature_key.clone())
+                    .collect()
+            } else {
+                Default::default()
+            };
+
+            QuarantineIdentityProvider::new(id_provider, deferred)
+        };
+
         let mut provisional_state = group_state
             .apply_resolved(
                 auth_content.content.sender,
@@ -776,11 +892,45 @@ pub(crate) trait MessageProcessor: Send + Sync {
 
         let sender = commit_sender(&auth_content.content.sender, &provisional_state)?;
 
+        let new_extensions = &provisional_state.group_context.extensions;
+
+        #[cfg_attr(not(feature = "state_update"), allow(unused_variables, unused_mut))]
+        let mut warnings = if new_extensions != &group_state.context.extensions {
+            self.mls_rules().unknown_extension_policy().apply(
+                &self.supported_extensions(),
+                new_extensions,
+                ProcessingWarning::UnknownGroupContextExtension,
+            )?
+        } else {
+            Vec::new()
+        };
+
+        let current_cipher_suite = group_state.context.cipher_suite;
+
+        let downgrade = provisional_state
+            .applied_proposals
+            .reinitializations
+            .first()
+            .map(|reinit| reinit.proposal.new_cipher_suite())
+            .filter(|&new| is_cipher_suite_downgrade(current_cipher_suite, new));
+
+        if let Some(to) = downgrade {
+            warnings.push(ProcessingWarning::CipherSuiteDowngrade {
+                from: current_cipher_suite,
+                to,
+            });
+        }
+
         #[cfg(feature = "state_update")]
         let mut state_update = self
             .make_state_update(&provisional_state, commit.path.as_ref(), sender)
             .await?;
 
+        #[cfg(feature = "state_update")]
+        {
+            state_update.warnings = warnings;
+        }
+
         #[cfg(not(feature = "state_update"))]
         let state_update = StateUpdate {};
 
@@ -796,6 +946,8 @@ pub(crate) trait MessageProcessor: Send + Sync {
                 state_update.active = false;
             }
 
+            self.on_membership_revoked();
+
             return Ok(CommitMessageDescription {
                 is_external: matches!(auth_content.content.sender, Sender::NewMemberCommit),
                 authenticated_data: auth_content.content.authenticated_data,
@@ -876,10 +1028,31 @@ pub(crate) trait MessageProcessor: Send + Sync {
     fn group_state_mut(&mut self) -> &mut GroupState;
     fn mls_rules(&self) -> Self::MlsRules;
     fn identity_provider(&self) -> Self::IdentityProvider;
+    fn supported_extensions(&self) -> Vec<ExtensionType>;
+
+    /// True if the identity of members added by received commits is
+    /// validated later, see [`Group::quarantined_members`](crate::group::Group::quarantined_members).
+    #[cfg(feature = "member_quarantine")]
+    fn defers_add_validation(&self) -> bool {
+        false
+    }
     fn cipher_suite_provider(&self) -> &Self::CipherSuiteProvider;
     fn psk_storage(&self) -> Self::PreSharedKeyStorage;
     fn can_continue_processing(&self, provisional_state: &ProvisionalState) -> bool;
 
+    /// Called when processing a commit that removes the current member.
+    fn on_membership_revoked(&mut self) {}
+
+    /// Additional validation of the new leaf of a member joining with an
+    /// external commit.
+    async fn validate_external_joiner(
+        &self,
+        _leaf_node: &LeafNode,
+        _time_sent: Option<MlsTime>,
+    ) -> Result<(), MlsError> {
+        Ok(())
+    }
+
     #[cfg(feature = "private_message")]
     fn min_epoch_available(&self) -> Option<u64>;
 
@@ -908,6 +1081,11 @@ pub(crate) trait MessageProcessor: Send + Sync {
                 return Err(MlsError::GroupIdMismatch);
             }
 
+            // Messages from a later epoch mean that commits were missed
+            if epoch > context.epoch {
+                return Err(MlsError::StateDesync(epoch));
+            }
+
             match content_type {
                 ContentType::Commit => {
                     if context.epoch != epoch {
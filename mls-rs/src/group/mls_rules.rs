@@ -2,18 +2,25 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::group::{proposal_filter::ProposalBundle, Roster};
+use crate::{
+    client::MlsError,
+    group::{proposal_filter::ProposalBundle, ProcessingWarning, Roster},
+};
 
 #[cfg(feature = "private_message")]
 use crate::{
-    group::{padding::PaddingMode, Sender},
+    group::{padding::PaddingMode, secret_tree::MAX_RATCHET_BACK_HISTORY, Sender},
     WireFormat,
 };
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::convert::Infallible;
 use mls_rs_core::{
-    error::IntoAnyError, extension::ExtensionList, group::Member, identity::SigningIdentity,
+    crypto::CipherSuite,
+    error::IntoAnyError,
+    extension::{ExtensionList, ExtensionType},
+    group::Member,
+    identity::SigningIdentity,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -30,6 +37,16 @@ pub enum CommitSource {
     NewMember(SigningIdentity),
 }
 
+/// Maximum size of a Welcome message, for example to fit the message size
+/// limit of a delivery service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WelcomeSize {
+    /// Maximum length in bytes of the encoded message.
+    Bytes(usize),
+    /// Maximum number of new members a message is sent to.
+    Recipients(usize),
+}
+
 /// Options controlling commit generation
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -38,6 +55,16 @@ pub struct CommitOptions {
     pub ratchet_tree_extension: bool,
     pub single_welcome_message: bool,
     pub allow_external_commit: bool,
+    /// Maximum size of each Welcome message. If set, the new members of a
+    /// commit are partitioned into as many Welcome messages as needed
+    /// instead of a single one.
+    pub max_welcome_size: Option<WelcomeSize>,
+    /// Send the ratchet tree compressed in a
+    /// [`CompressedRatchetTreeExt`](crate::extension::built_in::CompressedRatchetTreeExt)
+    /// if `ratchet_tree_extension` is set. New members must also enable the
+    /// `tree_compression` feature.
+    #[cfg(feature = "tree_compression")]
+    pub compress_ratchet_tree: bool,
 }
 
 impl Default for CommitOptions {
@@ -47,6 +74,9 @@ impl Default for CommitOptions {
             ratchet_tree_extension: true,
             single_welcome_message: true,
             allow_external_commit: false,
+            max_welcome_size: None,
+            #[cfg(feature = "tree_compression")]
+            compress_ratchet_tree: false,
         }
     }
 }
@@ -83,16 +113,90 @@ impl CommitOptions {
             ..self
         }
     }
+
+    pub fn with_max_welcome_size(self, max_welcome_size: Option<WelcomeSize>) -> Self {
+        Self {
+            max_welcome_size,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "tree_compression")]
+    pub fn with_compress_ratchet_tree(self, compress_ratchet_tree: bool) -> Self {
+        Self {
+            compress_ratchet_tree,
+            ..self
+        }
+    }
 }
 
 /// Options controlling encryption of control and application messages
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct EncryptionOptions {
     #[cfg(feature = "private_message")]
     pub encrypt_control_messages: bool,
+    /// Padding applied to the content of encrypted messages before
+    /// encryption, hiding their exact length from observers.
     #[cfg(feature = "private_message")]
     pub padding_mode: PaddingMode,
+    #[cfg(feature = "private_message")]
+    pub(crate) hide_group_id: bool,
+    /// Maximum number of generations that a received message can skip ahead
+    /// of the last message received from the same sender. With the
+    /// `out_of_order` feature, keys of skipped generations are retained only
+    /// while they are within this many generations of the newest key.
+    #[cfg(feature = "private_message")]
+    pub max_out_of_order_generations: u32,
+    /// Maximum number of generations that a received application message
+    /// can skip, bounding the number of keys derived to decrypt it. Larger
+    /// skips fail with [`MlsError::GenerationSkipTooLarge`] before any key is
+    /// derived, and can be allowed explicitly with
+    /// [`Group::force_skip_to`](crate::Group::force_skip_to). If `None`,
+    /// [`max_out_of_order_generations`](Self::max_out_of_order_generations)
+    /// is used. Values above it have no effect.
+    #[cfg(feature = "private_message")]
+    pub max_generation_skip: Option<u32>,
+    /// Maximum number of epochs that a received message can lag behind the
+    /// current epoch. If `None`, messages of any epoch still held by the
+    /// [`GroupStateStorage`](mls_rs_core::group::GroupStateStorage) are
+    /// decrypted.
+    #[cfg(feature = "private_message")]
+    pub max_past_epochs_retained: Option<u64>,
+    /// Prefix the authenticated data of application messages with a hash
+    /// of the group context of the epoch, which commits to the roster, and
+    /// reject received application messages without a matching prefix. The
+    /// prefix is removed before the authenticated data is returned to the
+    /// application.
+    ///
+    /// This prevents messages from being replayed into a state with the
+    /// same epoch number but a different membership, e.g. after the group
+    /// state storage was rolled back. All members must use the same setting.
+    /// The prefix is visible to observers and is ignored in
+    /// [`ComplianceMode::Strict`](crate::group::ComplianceMode::Strict).
+    #[cfg(feature = "roster_binding")]
+    pub bind_roster: bool,
+}
+
+impl Default for EncryptionOptions {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "private_message")]
+            encrypt_control_messages: false,
+            #[cfg(feature = "private_message")]
+            padding_mode: PaddingMode::default(),
+            #[cfg(feature = "private_message")]
+            hide_group_id: false,
+            #[cfg(feature = "private_message")]
+            max_out_of_order_generations: MAX_RATCHET_BACK_HISTORY,
+            #[cfg(feature = "private_message")]
+            max_generation_skip: None,
+            #[cfg(feature = "private_message")]
+            max_past_epochs_retained: None,
+            #[cfg(feature = "roster_binding")]
+            bind_roster: false,
+        }
+    }
 }
 
 #[cfg(feature = "private_message")]
@@ -101,9 +205,68 @@ impl EncryptionOptions {
         Self {
             encrypt_control_messages,
             padding_mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_padding_mode(self, padding_mode: PaddingMode) -> Self {
+        Self {
+            padding_mode,
+            ..self
+        }
+    }
+
+    /// Replace the group id of encrypted messages with a value derived from
+    /// the current epoch so that observers can not correlate messages of
+    /// different epochs. See [`Group::wire_group_id`](crate::Group::wire_group_id).
+    ///
+    /// Received messages are only restored to the true group id if they
+    /// were sent in the current epoch. Application messages from a previous
+    /// epoch are therefore rejected unless the delivery service restored
+    /// their group id, e.g. using a
+    /// [`WireGroupIdMap`](crate::group::WireGroupIdMap).
+    pub fn with_hide_group_id(self, hide_group_id: bool) -> Self {
+        Self {
+            hide_group_id,
+            ..self
+        }
+    }
+
+    pub fn with_max_out_of_order_generations(self, max_out_of_order_generations: u32) -> Self {
+        Self {
+            max_out_of_order_generations,
+            ..self
+        }
+    }
+
+    pub fn with_max_generation_skip(self, max_generation_skip: Option<u32>) -> Self {
+        Self {
+            max_generation_skip,
+            ..self
+        }
+    }
+
+    pub fn with_max_past_epochs_retained(self, max_past_epochs_retained: Option<u64>) -> Self {
+        Self {
+            max_past_epochs_retained,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "roster_binding")]
+    pub fn with_bind_roster(self, bind_roster: bool) -> Self {
+        Self {
+            bind_roster,
+            ..self
         }
     }
 
+    #[cfg(feature = "prior_epoch")]
+    pub(crate) fn accepts_epoch(&self, epoch: u64, current_epoch: u64) -> bool {
+        self.max_past_epochs_retained
+            .map_or(true, |max| current_epoch.saturating_sub(epoch) <= max)
+    }
+
     pub(crate) fn control_wire_format(&self, sender: Sender) -> WireFormat {
         match sender {
             Sender::Member(_) if self.encrypt_control_messages => WireFormat::PrivateMessage,
@@ -112,6 +275,47 @@ impl EncryptionOptions {
     }
 }
 
+/// Handling of GroupContext and GroupInfo extensions that are not supported
+/// by the local client, see [`MlsRules::unknown_extension_policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnknownExtensionPolicy {
+    /// Accept unknown extensions without reporting them.
+    #[default]
+    Ignore,
+    /// Accept unknown extensions and report them as [`ProcessingWarning`]s.
+    Warn,
+    /// Fail with [`MlsError::UnknownExtension`].
+    Reject,
+}
+
+impl UnknownExtensionPolicy {
+    /// Apply the policy to the types in `extensions` that are neither default
+    /// nor `supported`, creating warnings with `warning`.
+    pub(crate) fn apply<F>(
+        self,
+        supported: &[ExtensionType],
+        extensions: &ExtensionList,
+        warning: F,
+    ) -> Result<Vec<ProcessingWarning>, MlsError>
+    where
+        F: Fn(ExtensionType) -> ProcessingWarning,
+    {
+        let mut unknown = extensions
+            .iter()
+            .map(|ext| ext.extension_type)
+            .filter(|ext_type| !ext_type.is_default() && !supported.contains(ext_type));
+
+        match self {
+            UnknownExtensionPolicy::Ignore => Ok(Vec::new()),
+            UnknownExtensionPolicy::Warn => Ok(unknown.map(warning).collect()),
+            UnknownExtensionPolicy::Reject => unknown
+                .next()
+                .map_or(Ok(Vec::new()), |ext| Err(MlsError::UnknownExtension(ext))),
+        }
+    }
+}
+
 /// A set of user controlled rules that customize the behavior of MLS.
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
@@ -170,6 +374,47 @@ pub trait MlsRules: Send + Sync {
         current_roster: &Roster,
         current_extension_list: &ExtensionList,
     ) -> Result<EncryptionOptions, Self::Error>;
+
+    /// This is called when joining a group and when receiving a commit that changes the group
+    /// context extensions, to decide how GroupContext and GroupInfo extensions not supported by
+    /// this client are handled. Unknown extensions are ignored by default.
+    fn unknown_extension_policy(&self) -> UnknownExtensionPolicy {
+        UnknownExtensionPolicy::Ignore
+    }
+
+    /// This is called when preparing or receiving a commit with a ReInit proposal whose `new`
+    /// cipher suite is weaker than the `current` cipher suite of the group. A cipher suite is
+    /// weaker if it offers a lower security level, or if it is different and the security level
+    /// of either suite is not known to the library.
+    ///
+    /// If this returns `false`, the commit fails with [`MlsError::CipherSuiteDowngrade`], except
+    /// for by-reference proposals when preparing a commit, which are filtered out. Downgrades are
+    /// rejected by default. Allowed downgrades are reported as
+    /// [`ProcessingWarning::CipherSuiteDowngrade`].
+    fn allow_cipher_suite_downgrade(&self, _current: CipherSuite, _new: CipherSuite) -> bool {
+        false
+    }
+}
+
+/// Security level in bits of the ciphersuites defined by the MLS RFC.
+fn security_level(cipher_suite: CipherSuite) -> Option<u16> {
+    match cipher_suite {
+        CipherSuite::CURVE25519_AES128
+        | CipherSuite::P256_AES128
+        | CipherSuite::CURVE25519_CHACHA => Some(128),
+        CipherSuite::CURVE448_AES256
+        | CipherSuite::P521_AES256
+        | CipherSuite::CURVE448_CHACHA
+        | CipherSuite::P384_AES256 => Some(256),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_cipher_suite_downgrade(current: CipherSuite, new: CipherSuite) -> bool {
+    match (security_level(current), security_level(new)) {
+        (Some(current), Some(new)) => new < current,
+        _ => current != new,
+    }
 }
 
 macro_rules! delegate_mls_rules {
@@ -209,6 +454,14 @@ macro_rules! delegate_mls_rules {
             ) -> Result<EncryptionOptions, Self::Error> {
                 (**self).encryption_options(roster, extension_list)
             }
+
+            fn unknown_extension_policy(&self) -> UnknownExtensionPolicy {
+                (**self).unknown_extension_policy()
+            }
+
+            fn allow_cipher_suite_downgrade(&self, current: CipherSuite, new: CipherSuite) -> bool {
+                (**self).allow_cipher_suite_downgrade(current, new)
+            }
         }
     };
 }
@@ -222,6 +475,10 @@ delegate_mls_rules!(&T);
 pub struct DefaultMlsRules {
     pub commit_options: CommitOptions,
     pub encryption_options: EncryptionOptions,
+    pub unknown_extension_policy: UnknownExtensionPolicy,
+    pub allow_cipher_suite_downgrade: bool,
+    #[cfg(feature = "member_expiry")]
+    pub remove_expired_members: bool,
 }
 
 impl DefaultMlsRules {
@@ -235,15 +492,44 @@ impl DefaultMlsRules {
     pub fn with_commit_options(self, commit_options: CommitOptions) -> Self {
         Self {
             commit_options,
-            encryption_options: self.encryption_options,
+            ..self
         }
     }
 
     /// Set encryption options.
     pub fn with_encryption_options(self, encryption_options: EncryptionOptions) -> Self {
         Self {
-            commit_options: self.commit_options,
             encryption_options,
+            ..self
+        }
+    }
+
+    /// Set the policy for unknown GroupContext and GroupInfo extensions.
+    pub fn with_unknown_extension_policy(
+        self,
+        unknown_extension_policy: UnknownExtensionPolicy,
+    ) -> Self {
+        Self {
+            unknown_extension_policy,
+            ..self
+        }
+    }
+
+    /// Set whether ReInit proposals may move the group to a weaker cipher suite.
+    pub fn with_allow_cipher_suite_downgrade(self, allow_cipher_suite_downgrade: bool) -> Self {
+        Self {
+            allow_cipher_suite_downgrade,
+            ..self
+        }
+    }
+
+    /// Set whether commits created by this client remove the members whose
+    /// [`MemberExpiryExt`](crate::extension::MemberExpiryExt) is expired.
+    #[cfg(feature = "member_expiry")]
+    pub fn with_remove_expired_members(self, remove_expired_members: bool) -> Self {
+        Self {
+            remove_expired_members,
+            ..self
         }
     }
 }
@@ -253,14 +539,27 @@ impl DefaultMlsRules {
 impl MlsRules for DefaultMlsRules {
     type Error = Infallible;
 
+    #[cfg_attr(not(feature = "member_expiry"), allow(unused_mut, unused_variables))]
     async fn filter_proposals(
         &self,
-        _direction: CommitDirection,
-        _source: CommitSource,
-        _current_roster: &Roster,
+        direction: CommitDirection,
+        source: CommitSource,
+        current_roster: &Roster,
         _extension_list: &ExtensionList,
-        proposals: ProposalBundle,
+        mut proposals: ProposalBundle,
     ) -> Result<ProposalBundle, Self::Error> {
+        #[cfg(feature = "member_expiry")]
+        if let (true, CommitDirection::Send, CommitSource::ExistingMember(committer)) =
+            (self.remove_expired_members, direction, &source)
+        {
+            crate::group::member_expiry::add_expired_member_removals(
+                &mut proposals,
+                current_roster.members_iter(),
+                committer.index,
+                mls_rs_core::time::MlsTime::now(),
+            );
+        }
+
         Ok(proposals)
     }
 
@@ -280,4 +579,12 @@ impl MlsRules for DefaultMlsRules {
     ) -> Result<EncryptionOptions, Self::Error> {
         Ok(self.encryption_options)
     }
+
+    fn unknown_extension_policy(&self) -> UnknownExtensionPolicy {
+        self.unknown_extension_policy
+    }
+
+    fn allow_cipher_suite_downgrade(&self, _: CipherSuite, _: CipherSuite) -> bool {
+        self.allow_cipher_suite_downgrade
+    }
 }
@@ -5,15 +5,17 @@
 use alloc::vec;
 use alloc::vec::Vec;
 use core::fmt::{self, Debug};
+use core::ops::ControlFlow;
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::error::IntoAnyError;
+use mls_rs_core::extension::ExtensionType;
 use mls_rs_core::secret::Secret;
 use mls_rs_core::time::MlsTime;
 
 use crate::cipher_suite::CipherSuite;
 use crate::client::MlsError;
 use crate::client_config::ClientConfig;
-use crate::crypto::{HpkeCiphertext, SignatureSecretKey};
+use crate::crypto::{secret_material::wipe, HpkeCiphertext, SignatureSecretKey};
 use crate::extension::RatchetTreeExt;
 use crate::identity::SigningIdentity;
 use crate::key_package::{KeyPackage, KeyPackageRef};
@@ -41,7 +43,8 @@ use crate::extension::ExternalPubExt;
 
 use self::message_hash::MessageHash;
 #[cfg(feature = "private_message")]
-use self::mls_rules::{EncryptionOptions, MlsRules};
+use self::mls_rules::EncryptionOptions;
+use self::mls_rules::MlsRules;
 
 #[cfg(feature = "psk")]
 pub use self::resumption::ReinitClient;
@@ -84,8 +87,8 @@ use self::epoch::PriorEpoch;
 
 use self::epoch::EpochSecrets;
 pub use self::message_processor::{
-    ApplicationMessageDescription, CommitMessageDescription, ProposalMessageDescription,
-    ProposalSender, ReceivedMessage, StateUpdate,
+    ApplicationMessageDescription, CommitMessageDescription, ProcessingWarning,
+    ProposalMessageDescription, ProposalSender, ReceivedMessage, StateUpdate,
 };
 use self::message_processor::{EventOrContent, MessageProcessor, ProvisionalState};
 #[cfg(feature = "by_ref_proposal")]
@@ -107,32 +110,123 @@ pub use self::message_processor::CachedProposal;
 #[cfg(feature = "private_message")]
 mod ciphertext_processor;
 
+#[cfg(feature = "co_signed_commit")]
+pub mod co_signature;
+
+#[cfg(feature = "app_ack")]
+pub mod app_ack;
+
+#[cfg(feature = "archival_client")]
+mod archival;
+
+#[cfg(feature = "archival_client")]
+pub use archival::ArchivalGroup;
+
+#[cfg(feature = "companion_device")]
+mod companion;
+
+#[cfg(feature = "companion_device")]
+pub use companion::{CompanionBundle, CompanionGroup};
+
+#[cfg(feature = "light_client")]
+mod membership_proof;
+
+#[cfg(feature = "light_client")]
+pub use crate::tree_kem::MembershipProof;
+
 mod commit;
+#[cfg(feature = "commit_scheduler")]
+mod commit_scheduler;
+#[cfg(feature = "compact_state")]
+mod compact_state;
+mod compliance;
 pub(crate) mod confirmation_tag;
+#[cfg(feature = "content_advertisement")]
+pub(crate) mod content_advertisement;
 mod context;
+#[cfg(feature = "decryption_journal")]
+mod decryption_journal;
 pub(crate) mod epoch;
+#[cfg(feature = "epoch_history")]
+pub(crate) mod epoch_history;
+#[cfg(feature = "group_events")]
+pub(crate) mod event_listener;
+#[cfg(feature = "group_features")]
+mod features;
+/// Inspection of Welcome messages for debugging failed joins.
+#[cfg(feature = "forensics")]
+pub mod forensics;
+#[cfg(feature = "fork_recovery")]
+mod fork_recovery;
 pub(crate) mod framing;
+#[cfg(feature = "group_bound_cipher")]
+mod group_bound_cipher;
 mod group_info;
+#[cfg(feature = "handshake_shaping")]
+mod handshake_shaping;
+#[cfg(feature = "hidden_members")]
+mod hidden_members;
+mod join_ticket;
 pub(crate) mod key_schedule;
+#[cfg(feature = "member_events")]
+mod member_events;
+#[cfg(feature = "member_expiry")]
+pub(crate) mod member_expiry;
+#[cfg(feature = "member_reset")]
+pub(crate) mod member_reset;
 mod membership_tag;
+#[cfg(feature = "message_description")]
+mod message_description;
 pub(crate) mod message_hash;
 pub(crate) mod message_processor;
 pub(crate) mod message_signature;
 pub(crate) mod message_verifier;
 pub mod mls_rules;
+#[cfg(feature = "openmls_import")]
+mod openmls;
 #[cfg(feature = "private_message")]
 pub(crate) mod padding;
+#[cfg(feature = "processing_stats")]
+mod processing_stats;
 /// Proposals to evolve a MLS [`Group`]
 pub mod proposal;
 mod proposal_cache;
+#[cfg(feature = "proposal_export")]
+mod proposal_export;
 pub(crate) mod proposal_filter;
 #[cfg(feature = "by_ref_proposal")]
 pub(crate) mod proposal_ref;
+#[cfg(feature = "push_preview")]
+mod push_preview;
+#[cfg(feature = "member_quarantine")]
+mod quarantine;
+#[cfg(feature = "rekey_policy")]
+mod rekey;
 #[cfg(feature = "psk")]
 mod resumption;
+mod resync;
+mod revocation;
 mod roster;
+#[cfg(feature = "roster_binding")]
+mod roster_binding;
+#[cfg(feature = "roster_export")]
+mod roster_export;
+#[cfg(feature = "shadow_migration")]
+mod shadow_migration;
 pub(crate) mod snapshot;
+#[cfg(feature = "split_processing")]
+mod split_processing;
 pub(crate) mod state;
+#[cfg(feature = "system_events")]
+mod system_event;
+#[cfg(feature = "targeted_message")]
+pub(crate) mod targeted_message;
+#[cfg(feature = "tree_snapshot")]
+mod tree_snapshot;
+#[cfg(feature = "welcome_retargeting")]
+mod welcome_retargeting;
+#[cfg(feature = "private_message")]
+mod wire_group_id;
 
 #[cfg(feature = "prior_epoch")]
 pub(crate) mod state_repo;
@@ -142,7 +236,15 @@ pub(crate) mod state_repo_light;
 pub(crate) use state_repo_light as state_repo;
 
 pub(crate) mod transcript_hash;
+/// Standalone ratchet tree verification for auditing tools and light clients.
+#[cfg(feature = "tree_audit")]
+pub mod tree_audit;
+#[cfg(feature = "tree_fetcher")]
+mod tree_fetcher;
+#[cfg(feature = "by_ref_proposal")]
+mod update_leaf;
 mod util;
+mod verification_code;
 
 /// External commit building.
 pub mod external_commit;
@@ -160,6 +262,86 @@ mod exported_tree;
 
 pub use exported_tree::ExportedTree;
 
+#[cfg(feature = "tree_compression")]
+pub use exported_tree::MAX_DECOMPRESSED_TREE_SIZE;
+
+pub use compliance::ComplianceMode;
+pub use revocation::{MembershipStatus, RemovedSecretsPolicy};
+pub use verification_code::VerificationCode;
+
+#[cfg(feature = "push_preview")]
+pub use push_preview::PushPreview;
+
+#[cfg(feature = "handshake_shaping")]
+pub use handshake_shaping::{HandshakeQueue, ShapingPolicy};
+
+#[cfg(feature = "commit_scheduler")]
+pub use commit_scheduler::{CadencePolicy, CommitScheduler};
+
+#[cfg(feature = "rekey_policy")]
+pub use rekey::ReKeyPolicy;
+
+#[cfg(feature = "group_bound_cipher")]
+pub use group_bound_cipher::GroupBoundCipher;
+
+#[cfg(feature = "tree_fetcher")]
+pub use tree_fetcher::TreeFetcher;
+
+#[cfg(feature = "roster_export")]
+pub use roster_export::{BasicIdentityRenderer, IdentityRenderer, RosterExportFormat};
+
+#[cfg(all(feature = "roster_export", feature = "x509"))]
+pub use roster_export::X509IdentityRenderer;
+
+#[cfg(feature = "fork_recovery")]
+pub use fork_recovery::{ForkInfo, ForkStatus};
+
+#[cfg(feature = "decryption_journal")]
+pub use decryption_journal::AlreadyProcessedMessage;
+
+#[cfg(feature = "by_ref_proposal")]
+pub use update_leaf::UpdateLeafNode;
+
+#[cfg(feature = "processing_stats")]
+pub use processing_stats::ProcessingStats;
+
+#[cfg(feature = "shadow_migration")]
+pub use shadow_migration::ShadowMembershipDiff;
+
+#[cfg(feature = "tree_snapshot")]
+pub use tree_snapshot::TreeSnapshot;
+
+#[cfg(feature = "content_advertisement")]
+pub use content_advertisement::MemberMediaTypes;
+
+#[cfg(feature = "epoch_history")]
+pub use epoch_history::HistoricalEpoch;
+
+#[cfg(feature = "system_events")]
+pub use system_event::SystemEvent;
+
+#[cfg(feature = "group_events")]
+pub use event_listener::GroupEventListener;
+
+#[cfg(feature = "message_description")]
+pub use message_description::{CommittedProposal, MlsMessageDescription};
+
+#[cfg(feature = "openmls_import")]
+pub use openmls::{
+    OpenMlsEncryptionKeyPair, OpenMlsEpochSecrets, OpenMlsGroupState, OpenMlsMessageSecrets,
+};
+
+#[cfg(feature = "member_events")]
+pub use member_events::{MemberChange, MemberEvent, MemberEventHandler, MemberSubscriptionId};
+
+#[cfg(all(feature = "split_processing", feature = "private_message"))]
+pub use split_processing::ReceivedApplicationMessage;
+#[cfg(feature = "split_processing")]
+pub use split_processing::ReceivedHandshakeMessage;
+
+#[cfg(feature = "private_message")]
+pub use wire_group_id::{wire_group_id, WireGroupIdMap};
+
 #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 struct GroupSecrets {
     joiner_secret: JoinerSecret,
@@ -181,6 +363,7 @@ impl HpkeEncryptable for GroupSecrets {
 
 #[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct EncryptedGroupSecrets {
     pub new_member: KeyPackageRef,
     pub encrypted_group_secrets: HpkeCiphertext,
@@ -188,10 +371,12 @@ pub(crate) struct EncryptedGroupSecrets {
 
 #[derive(Clone, Eq, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct Welcome {
     pub cipher_suite: CipherSuite,
     pub secrets: Vec<EncryptedGroupSecrets>,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
     pub encrypted_group_info: Vec<u8>,
 }
 
@@ -219,6 +404,7 @@ pub struct NewMemberInfo {
     /// Group info extensions found within the Welcome message used to join
     /// the group.
     pub group_info_extensions: ExtensionList,
+    pub(crate) warnings: Vec<ProcessingWarning>,
 }
 
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
@@ -226,6 +412,7 @@ impl NewMemberInfo {
     pub(crate) fn new(group_info_extensions: ExtensionList) -> Self {
         let mut new_member_info = Self {
             group_info_extensions,
+            warnings: Vec::new(),
         };
 
         new_member_info.ungrease();
@@ -239,6 +426,12 @@ impl NewMemberInfo {
     pub fn group_info_extensions(&self) -> &ExtensionList {
         &self.group_info_extensions
     }
+
+    /// Issues found while joining the group that did not cause the join to
+    /// fail.
+    pub fn warnings(&self) -> &[ProcessingWarning] {
+        &self.warnings
+    }
 }
 
 /// An MLS end-to-end encrypted group.
@@ -273,6 +466,21 @@ where
     #[cfg(test)]
     pub(crate) commit_modifiers: CommitModifiers,
     pub(crate) signer: SignatureSecretKey,
+    membership_status: MembershipStatus,
+    #[cfg(feature = "decryption_journal")]
+    decryption_journal: decryption_journal::DecryptionJournal,
+    #[cfg(feature = "member_quarantine")]
+    quarantine: Vec<quarantine::QuarantinedMember>,
+    #[cfg(feature = "member_events")]
+    member_event_log: member_events::MemberEventLog,
+    #[cfg(feature = "member_events")]
+    member_subscriptions: Vec<member_events::MemberSubscription>,
+    #[cfg(feature = "welcome_retargeting")]
+    retargetable_welcome: Option<welcome_retargeting::RetargetableWelcome>,
+    #[cfg(feature = "app_ack")]
+    app_acks: app_ack::AppAckState,
+    #[cfg(feature = "rekey_policy")]
+    rekey: rekey::ReKeyState,
 }
 
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
@@ -382,6 +590,21 @@ where
             #[cfg(feature = "psk")]
             previous_psk: None,
             signer,
+            membership_status: MembershipStatus::Active,
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: Default::default(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_event_log: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_subscriptions: Default::default(),
+            #[cfg(feature = "welcome_retargeting")]
+            retargetable_welcome: None,
+            #[cfg(feature = "app_ack")]
+            app_acks: Default::default(),
+            #[cfg(feature = "rekey_policy")]
+            rekey: Default::default(),
         })
     }
 
@@ -395,21 +618,27 @@ where
         Self::from_welcome_message(
             welcome,
             tree_data,
+            #[cfg(feature = "tree_fetcher")]
+            None::<&tree_fetcher::NoTreeFetcher>,
             config,
             signer,
             #[cfg(feature = "psk")]
             None,
+            #[cfg(feature = "welcome_retargeting")]
+            false,
         )
         .await
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    async fn from_welcome_message(
+    async fn from_welcome_message<#[cfg(feature = "tree_fetcher")] F: TreeFetcher>(
         welcome: &MlsMessage,
         tree_data: Option<ExportedTree<'_>>,
+        #[cfg(feature = "tree_fetcher")] tree_fetcher: Option<&F>,
         config: C,
         signer: SignatureSecretKey,
         #[cfg(feature = "psk")] additional_psk: Option<PskSecretInput>,
+        #[cfg(feature = "welcome_retargeting")] allow_retargeted: bool,
     ) -> Result<(Self, NewMemberInfo), MlsError> {
         let protocol_version = welcome.version;
 
@@ -498,6 +727,16 @@ where
 
         let group_info = GroupInfo::mls_decode(&mut &**decrypted_group_info)?;
 
+        // The fetched tree is verified against the tree hash in the GroupInfo
+        // together with the rest of the tree.
+        #[cfg(feature = "tree_fetcher")]
+        let tree_data = match (tree_data, tree_fetcher) {
+            (None, Some(fetcher)) if !group_info_has_tree(&group_info) => {
+                Some(tree_fetcher::fetch_tree(fetcher, &group_info).await?)
+            }
+            (tree_data, _) => tree_data,
+        };
+
         let public_tree = validate_group_info_joiner(
             protocol_version,
             &group_info,
@@ -511,11 +750,22 @@ where
         // to the leaf_node field of the KeyPackage. If no such field exists, return an error. Let
         // index represent the index of this node among the leaves in the tree, namely the index of
         // the node in the tree array divided by two.
-        let self_index = public_tree
-            .find_leaf_node(&key_package_generation.key_package.leaf_node)
-            .ok_or(MlsError::WelcomeKeyPackageNotFound)?;
+        let self_index = public_tree.find_leaf_node(&key_package_generation.key_package.leaf_node);
+
+        #[cfg(feature = "welcome_retargeting")]
+        let self_index = match self_index {
+            None if allow_retargeted => welcome_retargeting::find_retargeted_leaf(
+                &public_tree,
+                &key_package_generation.key_package.leaf_node,
+            ),
+            self_index => self_index,
+        };
+
+        let self_index = self_index.ok_or(MlsError::WelcomeKeyPackageNotFound)?;
 
-        let used_key_package_ref = key_package_generation.reference;
+        // Last resort key packages are kept in storage so they can be reused.
+        let used_key_package_ref =
+            (!key_package_generation.last_resort).then_some(key_package_generation.reference);
 
         let mut private_tree =
             TreeKemPrivate::new_self_leaf(self_index, key_package_generation.leaf_node_secret_key);
@@ -565,7 +815,7 @@ where
             key_schedule_result.key_schedule,
             key_schedule_result.epoch_secrets,
             private_tree,
-            Some(used_key_package_ref),
+            used_key_package_ref,
             signer,
         )
         .await
@@ -583,6 +833,25 @@ where
         used_key_package_ref: Option<KeyPackageRef>,
         signer: SignatureSecretKey,
     ) -> Result<(Self, NewMemberInfo), MlsError> {
+        let mut new_member_info = NewMemberInfo::new(group_info.extensions);
+
+        let unknown_extension_policy = config.mls_rules().unknown_extension_policy();
+        let supported_extensions = config.supported_extensions();
+
+        new_member_info.warnings = [
+            unknown_extension_policy.apply(
+                &supported_extensions,
+                &group_info.group_context.extensions,
+                ProcessingWarning::UnknownGroupContextExtension,
+            )?,
+            unknown_extension_policy.apply(
+                &supported_extensions,
+                &new_member_info.group_info_extensions,
+                ProcessingWarning::UnknownGroupInfoExtension,
+            )?,
+        ]
+        .concat();
+
         let cs = group_info.group_context.cipher_suite;
 
         let cs = config
@@ -628,9 +897,24 @@ where
             #[cfg(feature = "psk")]
             previous_psk: None,
             signer,
+            membership_status: MembershipStatus::Active,
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: Default::default(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_event_log: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_subscriptions: Default::default(),
+            #[cfg(feature = "welcome_retargeting")]
+            retargetable_welcome: None,
+            #[cfg(feature = "app_ack")]
+            app_acks: Default::default(),
+            #[cfg(feature = "rekey_policy")]
+            rekey: Default::default(),
         };
 
-        Ok((group, NewMemberInfo::new(group_info.extensions)))
+        Ok((group, new_member_info))
     }
 
     #[inline(always)]
@@ -789,6 +1073,37 @@ where
         #[cfg(feature = "psk")] psks: Vec<PreSharedKeyID>,
         encrypted_group_info: &[u8],
     ) -> Result<EncryptedGroupSecrets, MlsError> {
+        let group_secrets = self.new_member_group_secrets(
+            leaf_index,
+            joiner_secret,
+            path_secrets,
+            #[cfg(feature = "psk")]
+            psks,
+        )?;
+
+        let encrypted_group_secrets = group_secrets
+            .encrypt(
+                &self.cipher_suite_provider,
+                &key_package.hpke_init_key,
+                encrypted_group_info,
+            )
+            .await?;
+
+        Ok(EncryptedGroupSecrets {
+            new_member: key_package
+                .to_reference(&self.cipher_suite_provider)
+                .await?,
+            encrypted_group_secrets,
+        })
+    }
+
+    fn new_member_group_secrets(
+        &self,
+        leaf_index: LeafIndex,
+        joiner_secret: &JoinerSecret,
+        path_secrets: Option<&Vec<Option<PathSecret>>>,
+        #[cfg(feature = "psk")] psks: Vec<PreSharedKeyID>,
+    ) -> Result<GroupSecrets, MlsError> {
         let path_secret = path_secrets
             .map(|secrets| {
                 secrets
@@ -806,25 +1121,10 @@ where
         #[cfg(not(feature = "psk"))]
         let psks = Vec::new();
 
-        let group_secrets = GroupSecrets {
+        Ok(GroupSecrets {
             joiner_secret: joiner_secret.clone(),
             path_secret,
             psks,
-        };
-
-        let encrypted_group_secrets = group_secrets
-            .encrypt(
-                &self.cipher_suite_provider,
-                &key_package.hpke_init_key,
-                encrypted_group_info,
-            )
-            .await?;
-
-        Ok(EncryptedGroupSecrets {
-            new_member: key_package
-                .to_reference(&self.cipher_suite_provider)
-                .await?,
-            encrypted_group_secrets,
         })
     }
 
@@ -844,10 +1144,20 @@ where
     }
 
     fn add_proposal(&self, key_package: MlsMessage) -> Result<Proposal, MlsError> {
+        let key_package = key_package
+            .into_key_package()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        // Fail early with the offending suite, full validation happens later
+        if key_package.cipher_suite != self.cipher_suite() {
+            return Err(MlsError::KeyPackageCipherSuiteMismatch(
+                key_package.cipher_suite,
+                self.cipher_suite(),
+            ));
+        }
+
         Ok(Proposal::Add(alloc::boxed::Box::new(AddProposal {
-            key_package: key_package
-                .into_key_package()
-                .ok_or(MlsError::UnexpectedMessageType)?,
+            key_package,
         })))
     }
 
@@ -922,6 +1232,16 @@ where
             )
             .await?;
 
+        Ok(self.pending_update_proposal(new_leaf_node, secret_key, signer))
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    fn pending_update_proposal(
+        &mut self,
+        new_leaf_node: LeafNode,
+        secret_key: HpkeSecretKey,
+        signer: Option<SignatureSecretKey>,
+    ) -> Proposal {
         // Store the secret key in the pending updates storage for later
         #[cfg(feature = "std")]
         self.pending_updates
@@ -931,9 +1251,9 @@ where
         self.pending_updates
             .push((new_leaf_node.public_key.clone(), (secret_key, signer)));
 
-        Ok(Proposal::Update(UpdateProposal {
+        Proposal::Update(UpdateProposal {
             leaf_node: new_leaf_node,
-        }))
+        })
     }
 
     /// Create a proposal message that removes an existing member from the
@@ -952,6 +1272,33 @@ where
         self.proposal_message(proposal, authenticated_data).await
     }
 
+    /// Create a proposal message that removes this member from the group.
+    ///
+    /// The proposal must be committed by another member. Once that commit is
+    /// processed, this group is no longer active. Self removal is not a
+    /// default proposal type, so every member of the group has to list
+    /// [`ProposalType::SELF_REMOVE`] in its capabilities, e.g. with
+    /// [`ClientBuilder::custom_proposal_type`](crate::client_builder::ClientBuilder::custom_proposal_type).
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg(feature = "self_remove_proposal")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_self_remove(
+        &mut self,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        if !self
+            .current_epoch_tree()
+            .can_support_proposal(ProposalType::SELF_REMOVE)
+        {
+            return Err(MlsError::SelfRemoveUnsupported);
+        }
+
+        let proposal = Proposal::SelfRemove(SelfRemoveProposal::default());
+        self.proposal_message(proposal, authenticated_data).await
+    }
+
     fn remove_proposal(&self, index: u32) -> Result<Proposal, MlsError> {
         let leaf_index = LeafIndex(index);
 
@@ -1081,12 +1428,45 @@ where
         extensions: ExtensionList,
         authenticated_data: Vec<u8>,
     ) -> Result<MlsMessage, MlsError> {
-        let proposal = self.group_context_extensions_proposal(extensions);
+        let proposal = self.group_context_extensions_proposal(extensions)?;
         self.proposal_message(proposal, authenticated_data).await
     }
 
-    fn group_context_extensions_proposal(&self, extensions: ExtensionList) -> Proposal {
-        Proposal::GroupContextExtensions(extensions)
+    fn group_context_extensions_proposal(
+        &self,
+        extensions: ExtensionList,
+    ) -> Result<Proposal, MlsError> {
+        #[cfg(feature = "extension_migration")]
+        let extensions = self.migrate_group_context_extensions(extensions)?;
+
+        Ok(Proposal::GroupContextExtensions(extensions))
+    }
+
+    /// Replace the deprecated extensions in `extensions` according to the
+    /// [`ExtensionMigration`](crate::extension::ExtensionMigration)s of the
+    /// client.
+    ///
+    /// A deprecated extension is replaced only if all current members
+    /// support its replacement. This is applied automatically to every group
+    /// context extensions proposal created by this member. Proposing the
+    /// current extensions, returned by [`Group::context`], migrates the
+    /// group as soon as possible.
+    #[cfg(feature = "extension_migration")]
+    pub fn migrate_group_context_extensions(
+        &self,
+        extensions: ExtensionList,
+    ) -> Result<ExtensionList, MlsError> {
+        let members = self
+            .current_epoch_tree()
+            .non_empty_leaves()
+            .map(|(_, leaf)| &leaf.capabilities)
+            .collect::<Vec<_>>();
+
+        crate::extension::migration::migrate_extensions(
+            &self.config.extension_migrations(),
+            extensions,
+            &members,
+        )
     }
 
     /// Create a custom proposal message.
@@ -1115,6 +1495,8 @@ where
         &mut self,
         content: AuthenticatedContent,
     ) -> Result<MlsMessage, MlsError> {
+        self.check_can_send()?;
+
         #[cfg(feature = "private_message")]
         let payload = if content.wire_format == WireFormat::PrivateMessage {
             MlsMessagePayload::Cipher(self.create_ciphertext(content).await?)
@@ -1124,7 +1506,14 @@ where
         #[cfg(not(feature = "private_message"))]
         let payload = MlsMessagePayload::Plain(self.create_plaintext(content).await?);
 
-        Ok(MlsMessage::new(self.protocol_version(), payload))
+        let message = MlsMessage::new(self.protocol_version(), payload);
+
+        #[cfg(feature = "private_message")]
+        if self.encryption_options()?.hide_group_id {
+            return self.conceal_group_id(message).await;
+        }
+
+        Ok(message)
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -1174,6 +1563,11 @@ where
         message: &[u8],
         authenticated_data: Vec<u8>,
     ) -> Result<MlsMessage, MlsError> {
+        self.check_can_send()?;
+
+        #[cfg(feature = "member_quarantine")]
+        self.check_quarantine()?;
+
         // A group member that has observed one or more proposals within an epoch MUST send a Commit message
         // before sending application data
         #[cfg(feature = "by_ref_proposal")]
@@ -1181,6 +1575,18 @@ where
             return Err(MlsError::CommitRequired);
         }
 
+        #[cfg(feature = "roster_binding")]
+        let authenticated_data = if self.encryption_options()?.bind_roster {
+            roster_binding::bind_roster(
+                &self.cipher_suite_provider,
+                self.context(),
+                authenticated_data,
+            )
+            .await?
+        } else {
+            authenticated_data
+        };
+
         let auth_content = AuthenticatedContent::new_signed(
             &self.cipher_suite_provider,
             self.context(),
@@ -1192,7 +1598,12 @@ where
         )
         .await?;
 
-        self.format_for_wire(auth_content).await
+        let message = self.format_for_wire(auth_content).await?;
+
+        #[cfg(feature = "rekey_policy")]
+        self.rekey.record_sent();
+
+        Ok(message)
     }
 
     #[cfg(feature = "private_message")]
@@ -1202,11 +1613,16 @@ where
         message: &PrivateMessage,
     ) -> Result<AuthenticatedContent, MlsError> {
         let epoch_id = message.epoch;
+        let options = self.encryption_options()?;
 
         let auth_content = if epoch_id == self.context().epoch {
-            let content = CiphertextProcessor::new(self, self.cipher_suite_provider.clone())
-                .open(message)
-                .await?;
+            #[allow(unused_mut)]
+            let (mut content, _generation) =
+                CiphertextProcessor::new(self, self.cipher_suite_provider.clone())
+                    .with_max_out_of_order_generations(options.max_out_of_order_generations)
+                    .with_max_generation_skip(options.max_generation_skip)
+                    .open_with_generation(message)
+                    .await?;
 
             verify_auth_content_signature(
                 &self.cipher_suite_provider,
@@ -1218,19 +1634,41 @@ where
             )
             .await?;
 
+            #[cfg(feature = "roster_binding")]
+            if options.bind_roster {
+                roster_binding::verify_roster_binding(
+                    &self.cipher_suite_provider,
+                    self.context(),
+                    &mut content.content,
+                )
+                .await?;
+            }
+
+            #[cfg(feature = "app_ack")]
+            self.app_acks
+                .record_received(self.context().epoch, &content.content, _generation);
+
             Ok::<_, MlsError>(content)
         } else {
             #[cfg(feature = "prior_epoch")]
             {
+                if !options.accepts_epoch(epoch_id, self.context().epoch) {
+                    return Err(MlsError::EpochNotFound);
+                }
+
                 let epoch = self
                     .state_repo
                     .get_epoch_mut(epoch_id)
                     .await?
                     .ok_or(MlsError::EpochNotFound)?;
 
-                let content = CiphertextProcessor::new(epoch, self.cipher_suite_provider.clone())
-                    .open(message)
-                    .await?;
+                #[allow(unused_mut)]
+                let mut content =
+                    CiphertextProcessor::new(epoch, self.cipher_suite_provider.clone())
+                        .with_max_out_of_order_generations(options.max_out_of_order_generations)
+                        .with_max_generation_skip(options.max_generation_skip)
+                        .open(message)
+                        .await?;
 
                 verify_auth_content_signature(
                     &self.cipher_suite_provider,
@@ -1242,6 +1680,16 @@ where
                 )
                 .await?;
 
+                #[cfg(feature = "roster_binding")]
+                if options.bind_roster {
+                    roster_binding::verify_roster_binding(
+                        &self.cipher_suite_provider,
+                        &epoch.context,
+                        &mut content.content,
+                    )
+                    .await?;
+                }
+
                 Ok(content)
             }
 
@@ -1252,6 +1700,41 @@ where
         Ok(auth_content)
     }
 
+    /// Advance the application ratchet of the member with leaf index
+    /// `sender` in the current epoch, so that a message with generation
+    /// `generation` can be decrypted without skipping.
+    ///
+    /// This is the explicit escape hatch for messages rejected with
+    /// [`MlsError::GenerationSkipTooLarge`]. All keys between the current
+    /// generation and `generation` are derived, so the application should
+    /// only call it for senders it trusts not to waste its resources. Keys of
+    /// skipped generations are retained according to
+    /// [`EncryptionOptions::max_out_of_order_generations`](crate::mls_rules::EncryptionOptions::max_out_of_order_generations).
+    /// Generations the ratchet already passed are left unchanged.
+    #[cfg(feature = "private_message")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn force_skip_to(&mut self, sender: u32, generation: u32) -> Result<(), MlsError> {
+        if sender == self.current_member_index() {
+            return Err(MlsError::CantProcessMessageFromSelf);
+        }
+
+        self.member_at_index(sender)
+            .ok_or(MlsError::LeafNotFound(sender))?;
+
+        let options = self.encryption_options()?;
+
+        self.epoch_secrets
+            .secret_tree
+            .skip_to_generation(
+                &self.cipher_suite_provider,
+                crate::tree_kem::node::NodeIndex::from(LeafIndex(sender)),
+                KeyType::Application,
+                generation,
+                options.max_out_of_order_generations,
+            )
+            .await
+    }
+
     /// Apply a pending commit that was created by [`Group::commit`] or
     /// [`CommitBuilder::build`].
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -1261,7 +1744,15 @@ where
             .clone()
             .ok_or(MlsError::PendingCommitNotFound)?;
 
-        self.process_commit(pending_commit.content, None).await
+        let commit = self.process_commit(pending_commit.content, None).await?;
+
+        #[cfg(feature = "member_events")]
+        self.record_member_events(&commit);
+
+        #[cfg(feature = "group_events")]
+        self.notify_group_event_listener(&commit);
+
+        Ok(commit)
     }
 
     /// Returns true if a commit has been created but not yet applied
@@ -1293,13 +1784,96 @@ where
         &mut self,
         message: MlsMessage,
     ) -> Result<ReceivedMessage, MlsError> {
+        let message = match self.prepare_incoming_message(message).await? {
+            ControlFlow::Break(received) => return Ok(received),
+            ControlFlow::Continue(message) => message,
+        };
+
+        let received = MessageProcessor::process_incoming_message(
+            self,
+            message,
+            #[cfg(feature = "by_ref_proposal")]
+            true,
+        )
+        .await?;
+
+        self.on_incoming_message_processed(&received);
+
+        Ok(received)
+    }
+
+    /// Checks shared by all entry points processing inbound messages.
+    ///
+    /// Returns `Break` if `message` was fully handled without going through
+    /// the [`MessageProcessor`], and `Continue` with the message to process
+    /// otherwise.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn prepare_incoming_message(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<ControlFlow<ReceivedMessage, MlsMessage>, MlsError> {
+        self.check_secrets_available()?;
+
+        if let Some(received) = self.process_own_message(&message).await? {
+            return Ok(ControlFlow::Break(received));
+        }
+
+        #[cfg(feature = "targeted_message")]
+        if let MlsMessagePayload::Targeted(targeted) = &message.payload {
+            return self
+                .process_targeted_message(&message, targeted)
+                .await
+                .map(ControlFlow::Break);
+        }
+
+        #[cfg(feature = "private_message")]
+        let message = self.reveal_group_id(message).await?;
+
+        Ok(ControlFlow::Continue(message))
+    }
+
+    /// Hooks shared by all entry points processing inbound messages, run
+    /// once `received` was processed by the [`MessageProcessor`].
+    pub(crate) fn on_incoming_message_processed(&mut self, received: &ReceivedMessage) {
+        #[cfg(feature = "member_quarantine")]
+        self.quarantine_added_members(received);
+
+        #[cfg(feature = "member_events")]
+        if let ReceivedMessage::Commit(commit) = received {
+            self.record_member_events(commit);
+        }
+
+        #[cfg(feature = "group_events")]
+        if let ReceivedMessage::Commit(commit) = received {
+            self.notify_group_event_listener(commit);
+        }
+
+        #[cfg(feature = "app_ack")]
+        if let ReceivedMessage::Proposal(proposal) = received {
+            self.record_app_ack(proposal);
+        }
+
+        let _ = received;
+    }
+
+    /// Handle messages that were sent by this member and are echoed back by
+    /// the delivery service.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn process_own_message(
+        &mut self,
+        message: &MlsMessage,
+    ) -> Result<Option<ReceivedMessage>, MlsError> {
+        if self.is_strict() {
+            return Ok(None);
+        }
+
         if let Some(pending) = &self.pending_commit {
-            let message_hash = MessageHash::compute(&self.cipher_suite_provider, &message).await?;
+            let message_hash = MessageHash::compute(&self.cipher_suite_provider, message).await?;
 
             if message_hash == pending.commit_message_hash {
                 let message_description = self.apply_pending_commit().await?;
 
-                return Ok(ReceivedMessage::Commit(message_description));
+                return Ok(Some(ReceivedMessage::Commit(message_description)));
             }
         }
 
@@ -1308,21 +1882,15 @@ where
             let cached_own_proposal = self
                 .state
                 .proposals
-                .get_own(&self.cipher_suite_provider, &message)
+                .get_own(&self.cipher_suite_provider, message)
                 .await?;
 
             if let Some(cached) = cached_own_proposal {
-                return Ok(ReceivedMessage::Proposal(cached));
+                return Ok(Some(ReceivedMessage::Proposal(cached)));
             }
         }
 
-        MessageProcessor::process_incoming_message(
-            self,
-            message,
-            #[cfg(feature = "by_ref_proposal")]
-            true,
-        )
-        .await
+        Ok(None)
     }
 
     /// Process an inbound message for this group, providing additional context
@@ -1347,14 +1915,23 @@ where
         message: MlsMessage,
         time: MlsTime,
     ) -> Result<ReceivedMessage, MlsError> {
-        MessageProcessor::process_incoming_message_with_time(
+        let message = match self.prepare_incoming_message(message).await? {
+            ControlFlow::Break(received) => return Ok(received),
+            ControlFlow::Continue(message) => message,
+        };
+
+        let received = MessageProcessor::process_incoming_message_with_time(
             self,
             message,
             #[cfg(feature = "by_ref_proposal")]
             true,
             Some(time),
         )
-        .await
+        .await?;
+
+        self.on_incoming_message_processed(&received);
+
+        Ok(received)
     }
 
     /// Find a group member by
@@ -1419,6 +1996,35 @@ where
             .await
     }
 
+    /// Create a group info message that includes the ratchet tree compressed
+    /// in a
+    /// [`CompressedRatchetTreeExt`](crate::extension::built_in::CompressedRatchetTreeExt),
+    /// which is much smaller in large groups. If `allow_external_commit` is
+    /// set, the message can be used for external commits like the one of
+    /// [`Group::group_info_message_allowing_ext_commit`].
+    #[cfg(feature = "tree_compression")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn compressed_group_info_message(
+        &self,
+        allow_external_commit: bool,
+    ) -> Result<MlsMessage, MlsError> {
+        let mut extensions = ExtensionList::new();
+
+        if allow_external_commit {
+            extensions.set_from({
+                self.key_schedule
+                    .get_external_key_pair_ext(&self.cipher_suite_provider)
+                    .await?
+            })?;
+        }
+
+        extensions.set_from(crate::extension::CompressedRatchetTreeExt::new(
+            &self.export_tree(),
+        )?)?;
+
+        self.group_info_message_internal(extensions, false).await
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn group_info_message_internal(
         &self,
@@ -1456,6 +2062,16 @@ where
         &self.group_state().context
     }
 
+    /// Get the confirmation tag of the current epoch.
+    ///
+    /// Together with the [context](Group::context) and the
+    /// [exported tree](Group::export_tree), it allows an external client to
+    /// start observing the group with
+    /// [`ExternalClient::observe_from_parts`](crate::external_client::ExternalClient::observe_from_parts).
+    pub fn confirmation_tag(&self) -> &[u8] {
+        &self.group_state().confirmation_tag
+    }
+
     /// Get the
     /// [epoch_authenticator](https://messaginglayersecurity.rocks/mls-protocol/draft-ietf-mls-protocol.html#name-key-schedule)
     /// of the current epoch.
@@ -1463,6 +2079,25 @@ where
         Ok(self.key_schedule.authentication_secret.clone().into())
     }
 
+    /// Get the epoch authenticator of `epoch_id`.
+    ///
+    /// Authenticators of past epochs are available as long as the epoch is
+    /// kept in the group state storage, which allows verifying them for
+    /// messages delivered after the group moved to a new epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn epoch_authenticator_for(&mut self, epoch_id: u64) -> Result<Secret, MlsError> {
+        if epoch_id == self.current_epoch() {
+            return self.epoch_authenticator();
+        }
+
+        #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+        if let Some(prior_epoch) = self.state_repo.get_epoch_mut(epoch_id).await? {
+            return Ok(prior_epoch.epoch_authenticator.clone().into());
+        }
+
+        Err(MlsError::EpochNotFound)
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn export_secret(
         &self,
@@ -1470,6 +2105,8 @@ where
         context: &[u8],
         len: usize,
     ) -> Result<Secret, MlsError> {
+        self.check_secrets_available()?;
+
         self.key_schedule
             .export_secret(label, context, len, &self.cipher_suite_provider)
             .await
@@ -1484,6 +2121,16 @@ where
         ExportedTree::new_borrowed(&self.current_epoch_tree().nodes)
     }
 
+    /// Write the current epoch's ratchet tree in serialized format to `writer`.
+    ///
+    /// The output is identical to [`ExportedTree::to_bytes`] applied to the
+    /// result of [`Group::export_tree`], but it is produced one node at a
+    /// time instead of being allocated all at once.
+    #[cfg(feature = "std")]
+    pub fn export_tree_to<W: std::io::Write>(&self, writer: &mut W) -> Result<(), MlsError> {
+        self.export_tree().write_to(writer)
+    }
+
     /// Current version of the MLS protocol in use by this group.
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.context().protocol_version
@@ -1539,10 +2186,24 @@ where
 
     #[cfg(feature = "private_message")]
     pub(crate) fn encryption_options(&self) -> Result<EncryptionOptions, MlsError> {
-        self.config
+        #[allow(unused_mut)]
+        let mut options = self
+            .config
             .mls_rules()
             .encryption_options(&self.roster(), self.group_context().extensions())
-            .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))
+            .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
+
+        #[cfg(feature = "private_message")]
+        if self.is_strict() {
+            options.hide_group_id = false;
+        }
+
+        #[cfg(feature = "roster_binding")]
+        if self.is_strict() {
+            options.bind_roster = false;
+        }
+
+        Ok(options)
     }
 
     #[cfg(not(feature = "psk"))]
@@ -1578,6 +2239,7 @@ where
                 crate::tree_kem::node::NodeIndex::from(sender),
                 KeyType::Application,
                 generation,
+                MAX_RATCHET_BACK_HISTORY,
             )
             .await
     }
@@ -1626,6 +2288,10 @@ where
         &mut self,
         cipher_text: &PrivateMessage,
     ) -> Result<EventOrContent<Self::OutputType>, MlsError> {
+        #[cfg(feature = "decryption_journal")]
+        return self.process_ciphertext_with_journal(cipher_text).await;
+
+        #[cfg(not(feature = "decryption_journal"))]
         self.decrypt_incoming_ciphertext(cipher_text)
             .await
             .map(EventOrContent::Content)
@@ -1785,12 +2451,24 @@ where
             self_index: self.private_tree.self_index,
             secrets: self.epoch_secrets.clone(),
             signature_public_keys,
+            #[cfg(feature = "epoch_history")]
+            leaves: self
+                .state
+                .public_tree
+                .leaves()
+                .map(|l| l.cloned())
+                .collect(),
+            epoch_authenticator: self.key_schedule.authentication_secret.clone(),
         };
 
         #[cfg(feature = "prior_epoch")]
         self.state_repo.insert(past_epoch).await?;
 
-        self.epoch_secrets = key_schedule_result.epoch_secrets;
+        let mut prior_secrets =
+            core::mem::replace(&mut self.epoch_secrets, key_schedule_result.epoch_secrets);
+
+        wipe(&mut prior_secrets);
+
         self.state.context = provisional_state.group_context;
         self.state.interim_transcript_hash = interim_transcript_hash;
         self.key_schedule = key_schedule_result.key_schedule;
@@ -1809,6 +2487,9 @@ where
 
         self.pending_commit = None;
 
+        #[cfg(feature = "rekey_policy")]
+        self.rekey.start_epoch();
+
         Ok(())
     }
 
@@ -1820,6 +2501,15 @@ where
         self.config.identity_provider()
     }
 
+    fn supported_extensions(&self) -> Vec<ExtensionType> {
+        self.config.supported_extensions()
+    }
+
+    #[cfg(feature = "member_quarantine")]
+    fn defers_add_validation(&self) -> bool {
+        self.config.quarantine_new_members() && !self.is_strict()
+    }
+
     fn psk_storage(&self) -> Self::PreSharedKeyStorage {
         self.config.secret_store()
     }
@@ -1841,6 +2531,18 @@ where
             && self.pending_commit.is_none())
     }
 
+    fn on_membership_revoked(&mut self) {
+        self.revoke_membership();
+    }
+
+    async fn validate_external_joiner(
+        &self,
+        leaf_node: &LeafNode,
+        time_sent: Option<MlsTime>,
+    ) -> Result<(), MlsError> {
+        self.validate_join_ticket(leaf_node, time_sent).await
+    }
+
     #[cfg(feature = "private_message")]
     fn min_epoch_available(&self) -> Option<u64> {
         None
@@ -2226,11 +2928,52 @@ mod tests {
 
         let proposal = test_group
             .group
-            .group_context_extensions_proposal(extension_list.clone());
+            .group_context_extensions_proposal(extension_list.clone())
+            .unwrap();
 
         assert_matches!(proposal, Proposal::GroupContextExtensions(ext) if ext == extension_list);
     }
 
+    #[cfg(feature = "extension_migration")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_context_extensions_are_migrated() {
+        use crate::extension::ExtensionMigration;
+
+        let mut group = super::test_utils::test_group_custom_config(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            |builder| {
+                builder
+                    .extension_types([42.into(), 43.into()])
+                    .extension_migration(ExtensionMigration::new(42.into(), 43.into()))
+            },
+        )
+        .await;
+
+        let mut extensions = group.group.context().extensions.clone();
+        extensions.set(Extension::new(42.into(), vec![1, 2, 3]));
+
+        group
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        group.process_pending_commit().await.unwrap();
+
+        let extensions = &group.group.context().extensions;
+
+        assert!(!extensions.has_extension(42.into()));
+
+        assert_eq!(
+            extensions.get(43.into()),
+            Some(Extension::new(43.into(), vec![1, 2, 3]))
+        );
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn group_context_extension_proposal_test(
         ext_list: ExtensionList,
@@ -2295,7 +3038,58 @@ mod tests {
 
         assert_matches!(
             commit,
-            Err(MlsError::RequiredExtensionNotFound(a)) if a == 999.into()
+            Err(MlsError::RequiredCapabilitiesNotSupported(leaves)) if leaves == [0]
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_context_ext_proposal_names_all_unsupported_leaves() {
+        let mut test_group = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            vec![42.into()],
+            None,
+            None,
+        )
+        .await;
+
+        for name in ["bob", "carol"] {
+            let (_, key_package) =
+                test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, name).await;
+
+            test_group
+                .group
+                .commit_builder()
+                .add_member(key_package)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+
+            test_group.group.apply_pending_commit().await.unwrap();
+        }
+
+        let mut extension_list = ExtensionList::new();
+
+        extension_list
+            .set_from(RequiredCapabilitiesExt {
+                extensions: vec![42.into()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let res = test_group
+            .group
+            .commit_builder()
+            .set_group_context_ext(extension_list)
+            .unwrap()
+            .build()
+            .await
+            .map(|_| ());
+
+        assert_matches!(
+            res,
+            Err(MlsError::RequiredCapabilitiesNotSupported(leaves)) if leaves == [1, 2]
         );
     }
 
@@ -2737,24 +3531,56 @@ mod tests {
             .await
             .unwrap();
 
-        let event = alice_group.process_message(commit).await.unwrap();
-
-        let ReceivedMessage::Commit(commit_description) = event else {
-            panic!("expected commit");
-        };
-
-        assert!(commit_description.is_external);
-        assert_eq!(commit_description.committer, 1);
-
-        assert_eq!(
-            commit_description.state_update.roster_update.added(),
-            &bob_group.roster().members()[1..2]
-        );
+        let event = alice_group.process_message(commit).await.unwrap();
+
+        let ReceivedMessage::Commit(commit_description) = event else {
+            panic!("expected commit");
+        };
+
+        assert!(commit_description.is_external);
+        assert_eq!(commit_description.committer, 1);
+
+        assert_eq!(
+            commit_description.state_update.roster_update.added(),
+            &bob_group.roster().members()[1..2]
+        );
+
+        itertools::assert_equal(
+            bob_group.roster().members_iter(),
+            alice_group.group.roster().members_iter(),
+        );
+    }
+
+    #[cfg(feature = "tree_compression")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn can_join_externally_with_compressed_tree() {
+        use crate::client::test_utils::TestClientBuilder;
+
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice_group.join("carol").await;
+
+        let (bob_identity, secret_key) = get_test_signing_identity(TEST_CIPHER_SUITE, b"bob").await;
+
+        let bob = TestClientBuilder::new_for_test()
+            .signing_identity(bob_identity, secret_key, TEST_CIPHER_SUITE)
+            .build();
+
+        let group_info = alice_group
+            .group
+            .compressed_group_info_message(true)
+            .await
+            .unwrap();
+
+        let (bob_group, commit) = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .unwrap();
+
+        alice_group.process_message(commit).await.unwrap();
 
-        itertools::assert_equal(
-            bob_group.roster().members_iter(),
-            alice_group.group.roster().members_iter(),
-        );
+        assert_eq!(bob_group.export_tree(), alice_group.group.export_tree());
     }
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
@@ -3027,6 +3853,123 @@ mod tests {
         );
     }
 
+    #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn epoch_authenticators_of_prior_epochs_are_available() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let epoch = groups[0].group.current_epoch();
+        let prior_authenticator = groups[0].group.epoch_authenticator().unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap().commit_message;
+        groups[0].process_pending_commit().await.unwrap();
+        groups[1].process_message(commit).await.unwrap();
+
+        for group in groups.iter_mut() {
+            let authenticator = group.group.epoch_authenticator_for(epoch).await.unwrap();
+            assert_eq!(authenticator, prior_authenticator);
+
+            let authenticator = group
+                .group
+                .epoch_authenticator_for(epoch + 1)
+                .await
+                .unwrap();
+            assert_eq!(authenticator, group.group.epoch_authenticator().unwrap());
+        }
+
+        let res = groups[0].group.epoch_authenticator_for(epoch + 2).await;
+        assert_matches!(res, Err(MlsError::EpochNotFound));
+    }
+
+    #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn messages_outside_past_epoch_window_are_rejected() {
+        use crate::group::{
+            mls_rules::{DefaultMlsRules, EncryptionOptions},
+            padding::PaddingMode,
+            test_utils::test_group_custom_config,
+        };
+
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(
+                DefaultMlsRules::default().with_encryption_options(
+                    EncryptionOptions::new(true, PaddingMode::None)
+                        .with_max_past_epochs_retained(Some(1)),
+                ),
+            )
+        })
+        .await;
+
+        let (mut bob, _) = alice.join("bob").await;
+
+        let mut messages = Vec::new();
+
+        for _ in 0..2 {
+            let message = bob
+                .group
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+
+            messages.push(message);
+        }
+
+        let commit = bob.group.commit(vec![]).await.unwrap().commit_message;
+        bob.process_pending_commit().await.unwrap();
+        alice.process_message(commit).await.unwrap();
+
+        alice.process_message(messages.remove(0)).await.unwrap();
+
+        let commit = bob.group.commit(vec![]).await.unwrap().commit_message;
+        bob.process_pending_commit().await.unwrap();
+        alice.process_message(commit).await.unwrap();
+
+        let res = alice.process_message(messages.remove(0)).await;
+        assert_matches!(res, Err(MlsError::EpochNotFound));
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn large_generation_skips_require_force_skip() {
+        use crate::group::{
+            mls_rules::{DefaultMlsRules, EncryptionOptions},
+            padding::PaddingMode,
+            test_utils::test_group_custom_config,
+        };
+
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(DefaultMlsRules::default().with_encryption_options(
+                EncryptionOptions::new(false, PaddingMode::None).with_max_generation_skip(Some(2)),
+            ))
+        })
+        .await;
+
+        let (mut bob, _) = alice.join("bob").await;
+
+        let mut messages = Vec::new();
+
+        for _ in 0..7 {
+            let message = bob
+                .group
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+
+            messages.push(message);
+        }
+
+        alice.process_message(messages[2].clone()).await.unwrap();
+
+        let res = alice.process_message(messages[6].clone()).await;
+        assert_matches!(res, Err(MlsError::GenerationSkipTooLarge(1, 6)));
+
+        // The rejected message did not consume the ratchet of bob.
+        alice.process_message(messages[4].clone()).await.unwrap();
+
+        alice.group.force_skip_to(1, 6).await.unwrap();
+        alice.process_message(messages[6].clone()).await.unwrap();
+    }
+
     #[cfg(feature = "private_message")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn member_cannot_decrypt_same_message_twice() {
@@ -3832,6 +4775,91 @@ mod tests {
         assert_matches!(res, ReceivedMessage::Commit(_));
     }
 
+    #[cfg(feature = "self_remove_proposal")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn self_remove_setup() -> Vec<TestGroup> {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.custom_proposal_type(ProposalType::SELF_REMOVE)
+        })
+        .await;
+
+        let (mut bob, _) = alice
+            .join_with_custom_config("bob", true, |c| {
+                c.0.settings
+                    .custom_proposal_types
+                    .push(ProposalType::SELF_REMOVE)
+            })
+            .await
+            .unwrap();
+
+        let (carol, commit) = alice
+            .join_with_custom_config("carol", true, |c| {
+                c.0.settings
+                    .custom_proposal_types
+                    .push(ProposalType::SELF_REMOVE)
+            })
+            .await
+            .unwrap();
+
+        bob.process_message(commit).await.unwrap();
+
+        vec![alice, bob, carol]
+    }
+
+    #[cfg(feature = "self_remove_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn self_remove_is_committed_by_other_member() {
+        let mut groups = self_remove_setup().await;
+
+        let proposal = groups[2].group.propose_self_remove(vec![]).await.unwrap();
+
+        groups[0].process_message(proposal.clone()).await.unwrap();
+        groups[1].process_message(proposal).await.unwrap();
+
+        let commit = groups[0].group.commit(vec![]).await.unwrap().commit_message;
+        groups[0].process_pending_commit().await.unwrap();
+
+        groups[1].process_message(commit.clone()).await.unwrap();
+        groups[2].process_message(commit).await.unwrap();
+
+        assert_eq!(groups[0].group.roster().members().len(), 2);
+        assert_eq!(groups[1].group.roster().members().len(), 2);
+
+        assert_eq!(
+            groups[2].group.membership_status(),
+            MembershipStatus::Revoked
+        );
+    }
+
+    #[cfg(feature = "self_remove_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn self_remove_is_not_committed_by_its_sender() {
+        let mut groups = self_remove_setup().await;
+
+        groups[2].group.propose_self_remove(vec![]).await.unwrap();
+
+        let commit = groups[2].group.commit(vec![]).await.unwrap();
+
+        assert!(commit
+            .unused_proposals
+            .iter()
+            .any(|p| matches!(p.proposal, Proposal::SelfRemove(_))));
+
+        groups[2].process_pending_commit().await.unwrap();
+
+        assert_eq!(groups[2].group.roster().members().len(), 3);
+    }
+
+    #[cfg(feature = "self_remove_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn self_remove_requires_support_by_all_members() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let res = groups[1].group.propose_self_remove(vec![]).await;
+
+        assert_matches!(res, Err(MlsError::SelfRemoveUnsupported));
+    }
+
     #[cfg(feature = "psk")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn can_join_with_psk() {
@@ -4333,4 +5361,220 @@ mod tests {
         assert!(groups[0].group.state.proposals.proposals.is_empty());
         assert!(groups[0].group.state.proposals.own_proposals.is_empty());
     }
+
+    const UNKNOWN_EXTENSION: ExtensionType = ExtensionType::new(999);
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn join_with_unknown_extension(
+        policy: mls_rules::UnknownExtensionPolicy,
+        group_info_extensions: ExtensionList,
+    ) -> (
+        TestGroup,
+        Result<(Group<TestClientConfig>, NewMemberInfo), MlsError>,
+    ) {
+        let mut alice = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            vec![UNKNOWN_EXTENSION],
+            None,
+            None,
+        )
+        .await;
+
+        // Bob advertises the extension in his key package but his client does not support it.
+        let (mut bob, key_package) =
+            test_client_with_key_pkg_custom(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob", |c| {
+                c.0.settings.extension_types.push(UNKNOWN_EXTENSION)
+            })
+            .await;
+
+        bob.config.0.settings.extension_types.clear();
+        bob.config.0.mls_rules.unknown_extension_policy = policy;
+
+        let welcome = alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .set_group_info_ext(group_info_extensions)
+            .build()
+            .await
+            .unwrap()
+            .welcome_messages
+            .remove(0);
+
+        alice.process_pending_commit().await.unwrap();
+
+        (alice, bob.join_group(None, &welcome).await)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn unknown_group_info_extensions_follow_policy() {
+        use crate::group::mls_rules::UnknownExtensionPolicy;
+
+        let group_info_extensions =
+            ExtensionList::from(vec![Extension::new(UNKNOWN_EXTENSION, vec![])]);
+
+        let (_, res) = join_with_unknown_extension(
+            UnknownExtensionPolicy::Ignore,
+            group_info_extensions.clone(),
+        )
+        .await;
+
+        assert!(res.unwrap().1.warnings().is_empty());
+
+        let (_, res) = join_with_unknown_extension(
+            UnknownExtensionPolicy::Warn,
+            group_info_extensions.clone(),
+        )
+        .await;
+
+        assert_eq!(
+            res.unwrap().1.warnings(),
+            vec![ProcessingWarning::UnknownGroupInfoExtension(
+                UNKNOWN_EXTENSION
+            )]
+        );
+
+        let (_, res) =
+            join_with_unknown_extension(UnknownExtensionPolicy::Reject, group_info_extensions)
+                .await;
+
+        assert_matches!(
+            res.map(|_| ()),
+            Err(MlsError::UnknownExtension(UNKNOWN_EXTENSION))
+        );
+    }
+
+    #[cfg(feature = "state_update")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn unknown_group_context_extensions_follow_policy() {
+        use crate::group::mls_rules::UnknownExtensionPolicy;
+
+        let (mut alice, res) =
+            join_with_unknown_extension(UnknownExtensionPolicy::Warn, Default::default()).await;
+
+        let (mut bob, _) = res.unwrap();
+
+        let mut extensions = group_extensions();
+        extensions.set(Extension::new(UNKNOWN_EXTENSION, vec![]));
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions.clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .commit_message;
+
+        let ReceivedMessage::Commit(description) =
+            bob.process_incoming_message(commit).await.unwrap()
+        else {
+            panic!("expected commit")
+        };
+
+        assert_eq!(
+            description.state_update.warnings(),
+            [ProcessingWarning::UnknownGroupContextExtension(
+                UNKNOWN_EXTENSION
+            )]
+        );
+
+        // Commits that keep the extensions unchanged are not reported again.
+        alice.process_pending_commit().await.unwrap();
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+
+        let ReceivedMessage::Commit(description) =
+            bob.process_incoming_message(commit).await.unwrap()
+        else {
+            panic!("expected commit")
+        };
+
+        assert!(description.state_update.warnings().is_empty());
+
+        alice.process_pending_commit().await.unwrap();
+
+        // Changing the extensions fails once the policy rejects unknown extensions.
+        bob.config.0.mls_rules.unknown_extension_policy = UnknownExtensionPolicy::Reject;
+        extensions.set(Extension::new(UNKNOWN_EXTENSION, vec![1]));
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .set_group_context_ext(extensions)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .commit_message;
+
+        let res = bob.process_incoming_message(commit).await;
+
+        assert_matches!(res, Err(MlsError::UnknownExtension(UNKNOWN_EXTENSION)));
+    }
+
+    #[cfg(feature = "state_update")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn cipher_suite_downgrade_requires_allowance() {
+        let weaker = CipherSuite::new(0xF001);
+
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let res = alice
+            .group
+            .commit_builder()
+            .reinit(None, TEST_PROTOCOL_VERSION, weaker, ExtensionList::new())
+            .unwrap()
+            .build()
+            .await;
+
+        assert_matches!(
+            res.map(|_| ()),
+            Err(MlsError::CipherSuiteDowngrade(from, to)) if from == TEST_CIPHER_SUITE && to == weaker
+        );
+
+        alice.group.config.0.mls_rules.allow_cipher_suite_downgrade = true;
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .reinit(None, TEST_PROTOCOL_VERSION, weaker, ExtensionList::new())
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .commit_message;
+
+        let res = bob.group.process_incoming_message(commit.clone()).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::CipherSuiteDowngrade(from, to)) if from == TEST_CIPHER_SUITE && to == weaker
+        );
+
+        bob.group.config.0.mls_rules.allow_cipher_suite_downgrade = true;
+
+        let ReceivedMessage::Commit(description) =
+            bob.group.process_incoming_message(commit).await.unwrap()
+        else {
+            panic!("expected commit")
+        };
+
+        assert_eq!(
+            description.state_update.warnings(),
+            [ProcessingWarning::CipherSuiteDowngrade {
+                from: TEST_CIPHER_SUITE,
+                to: weaker
+            }]
+        );
+
+        assert_eq!(
+            description.state_update.pending_reinit_ciphersuite(),
+            Some(weaker)
+        );
+    }
 }
@@ -0,0 +1,575 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::{fmt, mem};
+use mls_rs_codec::MlsDecode;
+use mls_rs_core::crypto::{CipherSuiteProvider, SignatureSecretKey};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    crypto::{HpkePublicKey, HpkeSecretKey},
+    extension::RequiredCapabilitiesExt,
+    group::{
+        cipher_suite_provider,
+        epoch::{EpochSecrets, SenderDataSecret},
+        key_schedule::KeySchedule,
+        message_processor::ProcessingWarning,
+        mls_rules::MlsRules,
+        snapshot::{RawGroupState, Snapshot},
+        util::validate_tree_joiner,
+        ConfirmationTag, ExportedTree, Group, GroupContext, InterimTranscriptHash,
+    },
+    signer::signer_matches_identity,
+    tree_kem::{
+        node::{LeafIndex, Node},
+        TreeKemPrivate,
+    },
+};
+
+#[cfg(feature = "psk")]
+use crate::psk::PreSharedKey;
+
+#[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+use crate::group::secret_tree::SecretTree;
+
+/// Group state exported from OpenMLS.
+///
+/// The state is imported with
+/// [`Client::import_openmls_group`](crate::Client::import_openmls_group).
+/// Pending commits and proposals of the OpenMLS group are not imported.
+/// Groups using a protocol version, cipher suite or group context extension
+/// that the importing client doesn't support, or requiring capabilities it
+/// doesn't have, are rejected with the corresponding [`MlsError`] instead of
+/// being imported into a group that would fail later.
+///
+/// OpenMLS storage providers keep these values under separate keys, so the
+/// state is assembled by the application from the values read from its
+/// storage provider. The group context and the ratchet tree are expected in
+/// their TLS encoding, as produced by OpenMLS when exporting them. Bytes can
+/// be given as arrays of bytes, hex strings or the `{"vec": [...]}` wrapper
+/// OpenMLS secrets are serialized with, and unknown fields are ignored.
+#[derive(Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenMlsGroupState {
+    #[serde(deserialize_with = "tolerant_bytes")]
+    pub group_context: Vec<u8>,
+    #[serde(deserialize_with = "tolerant_bytes")]
+    pub ratchet_tree: Vec<u8>,
+    pub own_leaf_index: u32,
+    #[serde(deserialize_with = "tolerant_bytes")]
+    pub confirmation_tag: Vec<u8>,
+    pub epoch_secrets: OpenMlsEpochSecrets,
+    pub message_secrets: OpenMlsMessageSecrets,
+    /// HPKE key pairs of the own leaf and of the parent nodes on its direct
+    /// path.
+    pub encryption_keys: Vec<OpenMlsEncryptionKeyPair>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub signature_private_key: Zeroizing<Vec<u8>>,
+}
+
+impl fmt::Debug for OpenMlsGroupState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenMlsGroupState")
+            .field(
+                "group_context",
+                &mls_rs_core::debug::pretty_bytes(&self.group_context),
+            )
+            .field("own_leaf_index", &self.own_leaf_index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Epoch secrets of an [`OpenMlsGroupState`].
+#[derive(Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenMlsEpochSecrets {
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub init_secret: Zeroizing<Vec<u8>>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub exporter_secret: Zeroizing<Vec<u8>>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub epoch_authenticator: Zeroizing<Vec<u8>>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub external_secret: Zeroizing<Vec<u8>>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub resumption_psk: Zeroizing<Vec<u8>>,
+}
+
+/// Message secrets of an [`OpenMlsGroupState`].
+#[derive(Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenMlsMessageSecrets {
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub sender_data_secret: Zeroizing<Vec<u8>>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub membership_key: Zeroizing<Vec<u8>>,
+    /// Root of the secret tree of the epoch.
+    ///
+    /// OpenMLS discards the root once the first message of the epoch is sent
+    /// or received. Without it, messages of the imported epoch can't be
+    /// encrypted or decrypted, and a commit must be made or processed before
+    /// exchanging application messages.
+    #[serde(default, deserialize_with = "tolerant_secret_opt")]
+    pub encryption_secret: Option<Zeroizing<Vec<u8>>>,
+}
+
+/// HPKE key pair of an [`OpenMlsGroupState`].
+#[derive(Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenMlsEncryptionKeyPair {
+    #[serde(deserialize_with = "tolerant_bytes")]
+    pub public_key: Vec<u8>,
+    #[serde(deserialize_with = "tolerant_secret")]
+    pub private_key: Zeroizing<Vec<u8>>,
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn from_openmls_state(
+        config: C,
+        mut state: OpenMlsGroupState,
+    ) -> Result<Self, MlsError> {
+        let context = GroupContext::mls_decode(&mut &*state.group_context)?;
+
+        if !config.version_supported(context.protocol_version) {
+            return Err(MlsError::UnsupportedProtocolVersion(
+                context.protocol_version,
+            ));
+        }
+
+        let cipher_suite_provider =
+            cipher_suite_provider(config.crypto_provider(), context.cipher_suite)?;
+
+        check_supported_context(&config, &context)?;
+        check_secret_lengths(&state, &cipher_suite_provider)?;
+
+        let public_tree = validate_tree_joiner(
+            &context,
+            ExportedTree::from_bytes(&state.ratchet_tree)?,
+            &config.identity_provider(),
+            &cipher_suite_provider,
+        )
+        .await?;
+
+        let self_index = LeafIndex(state.own_leaf_index);
+        let own_leaf = public_tree.get_leaf_node(self_index)?;
+        let signer = SignatureSecretKey::from(mem::take(&mut *state.signature_private_key));
+
+        let matches =
+            signer_matches_identity(&cipher_suite_provider, &own_leaf.signing_identity, &signer)
+                .await?;
+
+        if !matches {
+            return Err(MlsError::SignerIdentityMismatch);
+        }
+
+        let find_key = |public_key: &HpkePublicKey| {
+            state
+                .encryption_keys
+                .iter()
+                .find(|pair| pair.public_key == **public_key)
+                .map(|pair| HpkeSecretKey::from(pair.private_key.to_vec()))
+        };
+
+        let leaf_key = find_key(&own_leaf.public_key).ok_or(MlsError::OpenMlsKeyNotFound)?;
+        let mut private_tree = TreeKemPrivate::new_self_leaf(self_index, leaf_key);
+
+        for copath in public_tree.nodes.direct_copath(self_index) {
+            let key = match public_tree.nodes.borrow_node(copath.path)? {
+                Some(Node::Parent(parent)) if !parent.unmerged_leaves.contains(&self_index) => {
+                    Some(find_key(&parent.public_key).ok_or(MlsError::OpenMlsKeyNotFound)?)
+                }
+                _ => None,
+            };
+
+            private_tree.secret_keys.push(key);
+        }
+
+        let message_secrets = state.message_secrets;
+        let epoch_secrets = state.epoch_secrets;
+
+        #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+        let secret_tree = match message_secrets.encryption_secret {
+            Some(secret) => SecretTree::new(public_tree.total_leaf_count(), secret),
+            None => SecretTree::empty(),
+        };
+
+        let group_epoch_secrets = EpochSecrets {
+            #[cfg(feature = "psk")]
+            resumption_secret: PreSharedKey::from(epoch_secrets.resumption_psk),
+            sender_data_secret: SenderDataSecret::from(message_secrets.sender_data_secret),
+            #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+            secret_tree,
+        };
+
+        let key_schedule = KeySchedule::from_parts(
+            epoch_secrets.init_secret,
+            epoch_secrets.exporter_secret,
+            epoch_secrets.epoch_authenticator,
+            epoch_secrets.external_secret,
+            message_secrets.membership_key,
+        );
+
+        let confirmation_tag = ConfirmationTag::from(state.confirmation_tag);
+
+        let interim_transcript_hash = InterimTranscriptHash::create(
+            &cipher_suite_provider,
+            &context.confirmed_transcript_hash,
+            &confirmation_tag,
+        )
+        .await?;
+
+        let state = RawGroupState {
+            context,
+            #[cfg(feature = "by_ref_proposal")]
+            proposals: Default::default(),
+            #[cfg(feature = "by_ref_proposal")]
+            own_proposals: Default::default(),
+            public_tree,
+            interim_transcript_hash,
+            pending_reinit: None,
+            confirmation_tag,
+            membership_status: Default::default(),
+        };
+
+        let snapshot = Snapshot::from_parts(
+            state,
+            private_tree,
+            group_epoch_secrets,
+            key_schedule,
+            signer,
+        );
+
+        Group::from_snapshot(config, snapshot).await
+    }
+}
+
+fn check_supported_context<C: ClientConfig>(
+    config: &C,
+    context: &GroupContext,
+) -> Result<(), MlsError> {
+    // Warnings can't be reported on import, only a rejection is.
+    config.mls_rules().unknown_extension_policy().apply(
+        &config.supported_extensions(),
+        &context.extensions,
+        ProcessingWarning::UnknownGroupContextExtension,
+    )?;
+
+    let Some(required) = context.extensions.get_as::<RequiredCapabilitiesExt>()? else {
+        return Ok(());
+    };
+
+    let capabilities = config.capabilities();
+
+    if let Some(ext) = required
+        .extensions
+        .iter()
+        .find(|ext| !ext.is_default() && !capabilities.extensions.contains(ext))
+    {
+        return Err(MlsError::RequiredExtensionNotFound(*ext));
+    }
+
+    if let Some(proposal) = required
+        .proposals
+        .iter()
+        .find(|p| !capabilities.proposals.contains(p))
+    {
+        return Err(MlsError::RequiredProposalNotFound(*proposal));
+    }
+
+    if let Some(credential) = required
+        .credentials
+        .iter()
+        .find(|c| !capabilities.credentials.contains(c))
+    {
+        return Err(MlsError::RequiredCredentialNotFound(*credential));
+    }
+
+    Ok(())
+}
+
+// OpenMLS serializes secrets without their cipher suite, so a state exported
+// for a different cipher suite than its context is only detected by length.
+fn check_secret_lengths<P: CipherSuiteProvider>(
+    state: &OpenMlsGroupState,
+    cipher_suite_provider: &P,
+) -> Result<(), MlsError> {
+    let epoch = &state.epoch_secrets;
+    let message = &state.message_secrets;
+
+    let secrets = [
+        ("init_secret", Some(&epoch.init_secret)),
+        ("exporter_secret", Some(&epoch.exporter_secret)),
+        ("epoch_authenticator", Some(&epoch.epoch_authenticator)),
+        ("external_secret", Some(&epoch.external_secret)),
+        ("resumption_psk", Some(&epoch.resumption_psk)),
+        ("sender_data_secret", Some(&message.sender_data_secret)),
+        ("membership_key", Some(&message.membership_key)),
+        ("encryption_secret", message.encryption_secret.as_ref()),
+    ];
+
+    let expected = cipher_suite_provider.kdf_extract_size();
+
+    secrets
+        .into_iter()
+        .find(|(_, secret)| secret.map_or(false, |s| s.len() != expected))
+        .map_or(Ok(()), |(name, _)| {
+            Err(MlsError::InvalidOpenMlsSecretLength(name))
+        })
+}
+
+fn tolerant_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    TolerantBytes::deserialize(deserializer).map(|bytes| bytes.0)
+}
+
+fn tolerant_secret<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Zeroizing<Vec<u8>>, D::Error> {
+    tolerant_bytes(deserializer).map(Zeroizing::new)
+}
+
+fn tolerant_secret_opt<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Zeroizing<Vec<u8>>>, D::Error> {
+    Option::<TolerantBytes>::deserialize(deserializer)
+        .map(|bytes| bytes.map(|b| Zeroizing::new(b.0)))
+}
+
+/// Bytes given as a byte string, an array of bytes, a hex string, or any of
+/// these wrapped in a single field struct.
+struct TolerantBytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for TolerantBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TolerantBytesVisitor)
+    }
+}
+
+struct TolerantBytesVisitor;
+
+impl<'de> Visitor<'de> for TolerantBytesVisitor {
+    type Value = TolerantBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bytes, an array of bytes or a hex string")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(TolerantBytes(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(TolerantBytes(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let v = v.strip_prefix("0x").unwrap_or(v);
+
+        hex::decode(v)
+            .map(TolerantBytes)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+
+        Ok(TolerantBytes(bytes))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let Some((_, bytes)) = map.next_entry::<de::IgnoredAny, TolerantBytes>()? else {
+            return Err(de::Error::invalid_length(0, &self));
+        };
+
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::custom("expected a single field wrapping bytes"));
+        }
+
+        Ok(bytes)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        TolerantBytes::deserialize(deserializer)
+    }
+}
+
+#[cfg(all(test, feature = "private_message", feature = "psk"))]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_codec::MlsEncode;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::{
+            test_utils::{test_group, TestGroup},
+            Group,
+        },
+    };
+
+    use super::OpenMlsGroupState;
+
+    // Export the state of `group` in the layout of OpenMLS, with secrets
+    // encoded in the various ways OpenMLS storage providers use.
+    fn openmls_json(group: &TestGroup) -> serde_json::Value {
+        let group = &group.group;
+        let [init, exporter, authenticator, external, membership] = group.key_schedule.parts();
+
+        let encryption_keys = group
+            .private_tree
+            .secret_keys
+            .iter()
+            .zip(
+                core::iter::once(group.current_user_leaf_node().unwrap().public_key.to_vec())
+                    .chain(
+                        group
+                            .state
+                            .public_tree
+                            .nodes
+                            .direct_copath(group.private_tree.self_index)
+                            .into_iter()
+                            .map(|n| {
+                                group
+                                    .state
+                                    .public_tree
+                                    .nodes
+                                    .borrow_node(n.path)
+                                    .unwrap()
+                                    .as_ref()
+                                    .map(|n| n.public_key().to_vec())
+                                    .unwrap_or_default()
+                            }),
+                    ),
+            )
+            .filter_map(|(secret, public)| {
+                secret.as_ref().map(|secret| {
+                    serde_json::json!({
+                        "public_key": { "vec": public },
+                        "private_key": hex::encode(secret.as_ref()),
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "group_context": group.context().mls_encode_to_vec().unwrap(),
+            "ratchet_tree": group.export_tree().to_bytes().unwrap(),
+            "own_leaf_index": group.current_member_index(),
+            "confirmation_tag": hex::encode(&*group.state.confirmation_tag),
+            "epoch_secrets": {
+                "init_secret": { "value": { "vec": init } },
+                "exporter_secret": exporter,
+                "epoch_authenticator": authenticator,
+                "external_secret": external,
+                "resumption_psk": group.epoch_secrets.resumption_secret.to_vec(),
+                "unknown_field": 42,
+            },
+            "message_secrets": {
+                "sender_data_secret": group.epoch_secrets.sender_data_secret.to_vec(),
+                "membership_key": membership,
+                "encryption_secret": group.epoch_secrets.secret_tree.get_root_secret(),
+            },
+            "encryption_keys": encryption_keys,
+            "signature_private_key": group.signer.to_vec(),
+        })
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn imported_group_interoperates() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let state: OpenMlsGroupState = serde_json::from_value(openmls_json(&alice)).unwrap();
+
+        let mut imported = Group::from_openmls_state(alice.group.config.clone(), state)
+            .await
+            .unwrap();
+
+        assert_eq!(imported.context(), alice.group.context());
+        assert_eq!(
+            imported.epoch_authenticator().unwrap(),
+            alice.group.epoch_authenticator().unwrap()
+        );
+
+        let message = imported
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        bob.group.process_incoming_message(message).await.unwrap();
+
+        let commit = imported.commit(vec![]).await.unwrap();
+        imported.apply_pending_commit().await.unwrap();
+        bob.process_message(commit.commit_message).await.unwrap();
+
+        assert_eq!(imported.context(), bob.group.context());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn import_requires_own_keys() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice.join("bob").await;
+
+        let mut json = openmls_json(&alice);
+        json["encryption_keys"] = serde_json::json!([]);
+
+        let state: OpenMlsGroupState = serde_json::from_value(json).unwrap();
+
+        let res = Group::from_openmls_state(alice.group.config.clone(), state)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::OpenMlsKeyNotFound));
+
+        let mut json = openmls_json(&alice);
+        json["own_leaf_index"] = 1.into();
+
+        let state: OpenMlsGroupState = serde_json::from_value(json).unwrap();
+
+        let res = Group::from_openmls_state(alice.group.config.clone(), state)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::SignerIdentityMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn import_rejects_secrets_of_other_cipher_suite() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let mut json = openmls_json(&alice);
+        json["message_secrets"]["membership_key"] = serde_json::json!([1, 2, 3]);
+
+        let state: OpenMlsGroupState = serde_json::from_value(json).unwrap();
+
+        let res = Group::from_openmls_state(alice.group.config.clone(), state)
+            .await
+            .map(|_| ());
+
+        assert_matches!(
+            res,
+            Err(MlsError::InvalidOpenMlsSecretLength("membership_key"))
+        );
+    }
+}
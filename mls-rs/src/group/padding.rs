@@ -14,6 +14,10 @@ pub enum PaddingMode {
     StepFunction,
     /// No padding.
     None,
+    /// Pad to the next multiple of the given number of bytes, so that all
+    /// messages up to that size have the same length. A size of 0 disables
+    /// padding.
+    Fixed(u32),
 }
 
 impl PaddingMode {
@@ -31,7 +35,11 @@ impl PaddingMode {
 
                 (content_size | (blind - 1)) + 1
             }
-            PaddingMode::None => content_size,
+            PaddingMode::None | PaddingMode::Fixed(0) => content_size,
+            PaddingMode::Fixed(size) => {
+                let size = *size as usize;
+                ((content_size + size - 1) / size).max(1) * size
+            }
         }
     }
 }
@@ -77,6 +85,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fixed_padding() {
+        assert_eq!(PaddingMode::Fixed(256).padded_size(0), 256);
+        assert_eq!(PaddingMode::Fixed(256).padded_size(1), 256);
+        assert_eq!(PaddingMode::Fixed(256).padded_size(256), 256);
+        assert_eq!(PaddingMode::Fixed(256).padded_size(257), 512);
+        assert_eq!(PaddingMode::Fixed(0).padded_size(100), 100);
+    }
+
     #[test]
     fn test_padding_length() {
         assert_eq!(PaddingMode::StepFunction.padded_size(0), 32);
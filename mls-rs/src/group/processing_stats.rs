@@ -0,0 +1,248 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use core::ops::ControlFlow;
+use mls_rs_codec::MlsSize;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        framing::{Content, MlsMessagePayload, WireFormat},
+        message_processor::{EventOrContent, MessageProcessor},
+        message_signature::AuthenticatedContent,
+        proposal::{Proposal, ProposalOrRef},
+        Group, ReceivedMessage,
+    },
+    MlsMessage,
+};
+
+/// Cost of processing a single message, as reported by
+/// [`Group::process_incoming_message_with_stats`].
+///
+/// These values are computed from the structure of the message and are
+/// therefore deterministic, which makes them suitable for metering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProcessingStats {
+    /// Size of the encoded message.
+    pub bytes_processed: usize,
+    /// Number of signature verifications, AEAD and HPKE operations
+    /// required to process the message.
+    pub crypto_operations: u32,
+    /// Number of epochs between the epoch of the message and the current
+    /// epoch of the group, for messages sent in a prior epoch.
+    pub epochs_skipped: u64,
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Process an inbound message for this group, see
+    /// [`Group::process_incoming_message`], and report the cost of
+    /// processing it.
+    ///
+    /// Messages sent by this member that are resolved locally, such as the
+    /// pending commit, and targeted messages are reported with zero crypto
+    /// operations.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn process_incoming_message_with_stats(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<(ReceivedMessage, ProcessingStats), MlsError> {
+        let mut stats = ProcessingStats {
+            bytes_processed: message.mls_encoded_len(),
+            ..Default::default()
+        };
+
+        let message = match self.prepare_incoming_message(message).await? {
+            ControlFlow::Break(received) => return Ok((received, stats)),
+            ControlFlow::Continue(message) => message,
+        };
+
+        stats.epochs_skipped = message
+            .epoch()
+            .map_or(0, |epoch| self.current_epoch().saturating_sub(epoch));
+
+        stats.crypto_operations = match &message.payload {
+            MlsMessagePayload::GroupInfo(_) => 1,
+            MlsMessagePayload::KeyPackage(_) => 2,
+            _ => 0,
+        };
+
+        let event_or_content = self.get_event_from_incoming_message(message).await?;
+
+        if let EventOrContent::Content(content) = &event_or_content {
+            stats.crypto_operations = content_crypto_operations(content);
+        }
+
+        let received = self
+            .process_event_or_content(
+                event_or_content,
+                #[cfg(feature = "by_ref_proposal")]
+                true,
+                None,
+            )
+            .await?;
+
+        self.on_incoming_message_processed(&received);
+
+        Ok((received, stats))
+    }
+}
+
+fn content_crypto_operations(auth_content: &AuthenticatedContent) -> u32 {
+    // Verification of the content signature
+    let mut operations = 1;
+
+    // Decryption of the sender data and the content
+    if auth_content.wire_format == WireFormat::PrivateMessage {
+        operations += 2;
+    }
+
+    operations += match &auth_content.content.content {
+        #[cfg(feature = "private_message")]
+        Content::Application(_) => 0,
+        #[cfg(feature = "by_ref_proposal")]
+        Content::Proposal(proposal) => proposal_crypto_operations(proposal),
+        Content::Commit(commit) => {
+            let proposals = commit
+                .proposals
+                .iter()
+                .map(|proposal| match proposal {
+                    ProposalOrRef::Proposal(proposal) => proposal_crypto_operations(proposal),
+                    #[cfg(feature = "by_ref_proposal")]
+                    ProposalOrRef::Reference(_) => 0,
+                })
+                .sum::<u32>();
+
+            // Verification of the new leaf node and decryption of the path secret
+            let path = if commit.path.is_some() { 2 } else { 0 };
+
+            proposals + path
+        }
+    };
+
+    operations
+}
+
+fn proposal_crypto_operations(proposal: &Proposal) -> u32 {
+    match proposal {
+        // Verification of the key package and its leaf node
+        Proposal::Add(_) => 2,
+        // Verification of the new leaf node
+        #[cfg(feature = "by_ref_proposal")]
+        Proposal::Update(_) => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_codec::MlsSize;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::{test_utils::test_group, ReceivedMessage},
+    };
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn application_message_stats() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let len = message.mls_encoded_len();
+
+        let (received, stats) = bob
+            .group
+            .process_incoming_message_with_stats(message)
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedMessage::ApplicationMessage(_));
+        assert_eq!(stats.bytes_processed, len);
+        assert_eq!(stats.crypto_operations, 3);
+        assert_eq!(stats.epochs_skipped, 0);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_stats_count_path() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+
+        let (received, stats) = bob
+            .group
+            .process_incoming_message_with_stats(commit)
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedMessage::Commit(_));
+        assert!(stats.crypto_operations >= 3);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn own_commit_has_no_crypto_operations() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+
+        let (received, stats) = alice
+            .group
+            .process_incoming_message_with_stats(commit)
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedMessage::Commit(_));
+        assert_eq!(stats.crypto_operations, 0);
+        assert!(stats.bytes_processed > 0);
+    }
+
+    #[cfg(feature = "member_events")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn stats_keep_processing_hooks() {
+        use crate::group::member_events::MemberChange;
+
+        let mut groups = crate::group::test_utils::test_n_member_group(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            3,
+        )
+        .await;
+
+        groups[1].group.config.0.settings.member_event_log_size = 10;
+
+        let commit = groups[0]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[1]
+            .group
+            .process_incoming_message_with_stats(commit.commit_message)
+            .await
+            .unwrap();
+
+        let events = groups[1].group.member_events_since(0);
+
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.change, MemberChange::Left(_))));
+    }
+}
@@ -137,6 +137,17 @@ impl From<u32> for RemoveProposal {
     }
 }
 
+#[cfg(feature = "self_remove_proposal")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A proposal by a [`Member`](mls_rs_core::group::Member) to remove itself
+/// from a [`Group`](crate::group::Group).
+///
+/// The member to remove is the sender of the proposal. It can only be sent by
+/// reference and must be committed by another member.
+pub struct SelfRemoveProposal {}
+
 #[cfg(feature = "psk")]
 #[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -332,6 +343,8 @@ pub enum Proposal {
     ReInit(ReInitProposal),
     ExternalInit(ExternalInit),
     GroupContextExtensions(ExtensionList),
+    #[cfg(feature = "self_remove_proposal")]
+    SelfRemove(SelfRemoveProposal),
     #[cfg(feature = "custom_proposal")]
     Custom(CustomProposal),
 }
@@ -348,6 +361,8 @@ impl MlsSize for Proposal {
             Proposal::ReInit(p) => p.mls_encoded_len(),
             Proposal::ExternalInit(p) => p.mls_encoded_len(),
             Proposal::GroupContextExtensions(p) => p.mls_encoded_len(),
+            #[cfg(feature = "self_remove_proposal")]
+            Proposal::SelfRemove(p) => p.mls_encoded_len(),
             #[cfg(feature = "custom_proposal")]
             Proposal::Custom(p) => mls_rs_codec::byte_vec::mls_encoded_len(&p.data),
         };
@@ -370,6 +385,8 @@ impl MlsEncode for Proposal {
             Proposal::ReInit(p) => p.mls_encode(writer),
             Proposal::ExternalInit(p) => p.mls_encode(writer),
             Proposal::GroupContextExtensions(p) => p.mls_encode(writer),
+            #[cfg(feature = "self_remove_proposal")]
+            Proposal::SelfRemove(p) => p.mls_encode(writer),
             #[cfg(feature = "custom_proposal")]
             Proposal::Custom(p) => {
                 if p.proposal_type.raw_value() <= 7 {
@@ -407,6 +424,10 @@ impl MlsDecode for Proposal {
             ProposalType::GROUP_CONTEXT_EXTENSIONS => {
                 Proposal::GroupContextExtensions(ExtensionList::mls_decode(reader)?)
             }
+            #[cfg(feature = "self_remove_proposal")]
+            ProposalType::SELF_REMOVE => {
+                Proposal::SelfRemove(SelfRemoveProposal::mls_decode(reader)?)
+            }
             #[cfg(feature = "custom_proposal")]
             custom => Proposal::Custom(CustomProposal {
                 proposal_type: custom,
@@ -431,6 +452,8 @@ impl Proposal {
             Proposal::ReInit(_) => ProposalType::RE_INIT,
             Proposal::ExternalInit(_) => ProposalType::EXTERNAL_INIT,
             Proposal::GroupContextExtensions(_) => ProposalType::GROUP_CONTEXT_EXTENSIONS,
+            #[cfg(feature = "self_remove_proposal")]
+            Proposal::SelfRemove(_) => ProposalType::SELF_REMOVE,
             #[cfg(feature = "custom_proposal")]
             Proposal::Custom(c) => c.proposal_type,
         }
@@ -449,6 +472,8 @@ pub enum BorrowedProposal<'a> {
     ReInit(&'a ReInitProposal),
     ExternalInit(&'a ExternalInit),
     GroupContextExtensions(&'a ExtensionList),
+    #[cfg(feature = "self_remove_proposal")]
+    SelfRemove(&'a SelfRemoveProposal),
     #[cfg(feature = "custom_proposal")]
     Custom(&'a CustomProposal),
 }
@@ -467,6 +492,8 @@ impl<'a> From<BorrowedProposal<'a>> for Proposal {
             BorrowedProposal::GroupContextExtensions(ext) => {
                 Proposal::GroupContextExtensions(ext.clone())
             }
+            #[cfg(feature = "self_remove_proposal")]
+            BorrowedProposal::SelfRemove(self_remove) => Proposal::SelfRemove(self_remove.clone()),
             #[cfg(feature = "custom_proposal")]
             BorrowedProposal::Custom(custom) => Proposal::Custom(custom.clone()),
         }
@@ -485,6 +512,8 @@ impl BorrowedProposal<'_> {
             BorrowedProposal::ReInit(_) => ProposalType::RE_INIT,
             BorrowedProposal::ExternalInit(_) => ProposalType::EXTERNAL_INIT,
             BorrowedProposal::GroupContextExtensions(_) => ProposalType::GROUP_CONTEXT_EXTENSIONS,
+            #[cfg(feature = "self_remove_proposal")]
+            BorrowedProposal::SelfRemove(_) => ProposalType::SELF_REMOVE,
             #[cfg(feature = "custom_proposal")]
             BorrowedProposal::Custom(c) => c.proposal_type,
         }
@@ -503,6 +532,8 @@ impl<'a> From<&'a Proposal> for BorrowedProposal<'a> {
             Proposal::ReInit(p) => BorrowedProposal::ReInit(p),
             Proposal::ExternalInit(p) => BorrowedProposal::ExternalInit(p),
             Proposal::GroupContextExtensions(p) => BorrowedProposal::GroupContextExtensions(p),
+            #[cfg(feature = "self_remove_proposal")]
+            Proposal::SelfRemove(p) => BorrowedProposal::SelfRemove(p),
             #[cfg(feature = "custom_proposal")]
             Proposal::Custom(p) => BorrowedProposal::Custom(p),
         }
@@ -553,6 +584,13 @@ impl<'a> From<&'a ExtensionList> for BorrowedProposal<'a> {
     }
 }
 
+#[cfg(feature = "self_remove_proposal")]
+impl<'a> From<&'a SelfRemoveProposal> for BorrowedProposal<'a> {
+    fn from(p: &'a SelfRemoveProposal) -> Self {
+        Self::SelfRemove(p)
+    }
+}
+
 #[cfg(feature = "custom_proposal")]
 impl<'a> From<&'a CustomProposal> for BorrowedProposal<'a> {
     fn from(p: &'a CustomProposal) -> Self {
@@ -6,14 +6,14 @@ use alloc::vec::Vec;
 
 use super::{
     message_processor::ProvisionalState,
-    mls_rules::{CommitDirection, CommitSource, MlsRules},
+    mls_rules::{is_cipher_suite_downgrade, CommitDirection, CommitSource, MlsRules},
     GroupState, ProposalOrRef,
 };
 use crate::{
     client::MlsError,
     group::{
-        proposal_filter::{ProposalApplier, ProposalBundle, ProposalSource},
-        Proposal, Sender,
+        proposal_filter::{ConflictReport, ProposalApplier, ProposalBundle, ProposalSource},
+        Proposal, ReInitProposal, Sender,
     },
     time::MlsTime,
 };
@@ -286,11 +286,32 @@ impl GroupState {
             )),
         }?;
 
+        proposals.conflict_report = ConflictReport::new(&proposals);
+
         proposals = user_rules
             .filter_proposals(direction, origin, &roster, group_extensions, proposals)
             .await
             .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
 
+        let current_cipher_suite = self.context.cipher_suite;
+
+        proposals.retain_by_type::<ReInitProposal, _, _>(|p| {
+            let new_cipher_suite = p.proposal.new_cipher_suite();
+
+            if !is_cipher_suite_downgrade(current_cipher_suite, new_cipher_suite)
+                || user_rules.allow_cipher_suite_downgrade(current_cipher_suite, new_cipher_suite)
+            {
+                Ok(true)
+            } else if direction == CommitDirection::Send && p.is_by_reference() {
+                Ok(false)
+            } else {
+                Err(MlsError::CipherSuiteDowngrade(
+                    current_cipher_suite,
+                    new_cipher_suite,
+                ))
+            }
+        })?;
+
         let applier = ProposalApplier::new(
             &self.public_tree,
             self.context.protocol_version,
@@ -2539,6 +2560,34 @@ mod tests {
         assert_eq!(processed_proposals.1.unused_proposals, vec![proposal_info]);
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn sending_reinit_downgrading_cipher_suite_filters_it_out() {
+        let (alice, tree) = new_tree("alice").await;
+
+        let proposal = Proposal::ReInit(ReInitProposal {
+            cipher_suite: CipherSuite::new(0xF001),
+            ..make_reinit(TEST_PROTOCOL_VERSION)
+        });
+
+        let proposal_info = make_proposal_info(&proposal, alice).await;
+
+        let processed_proposals =
+            CommitSender::new(&tree, alice, test_cipher_suite_provider(TEST_CIPHER_SUITE))
+                .cache(
+                    proposal_info.proposal_ref().unwrap().clone(),
+                    proposal.clone(),
+                    alice,
+                )
+                .send()
+                .await
+                .unwrap();
+
+        assert_eq!(processed_proposals.0, Vec::new());
+
+        #[cfg(feature = "state_update")]
+        assert_eq!(processed_proposals.1.unused_proposals, vec![proposal_info]);
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn receiving_update_for_committer_fails() {
         let (alice, tree) = new_tree("alice").await;
@@ -3414,7 +3463,7 @@ mod tests {
 
         assert_matches!(
             res,
-            Err(MlsError::RequiredExtensionNotFound(v)) if v == 33.into()
+            Err(MlsError::RequiredCapabilitiesNotSupported(leaves)) if leaves == [*alice]
         );
     }
 
@@ -3429,7 +3478,7 @@ mod tests {
 
         assert_matches!(
             res,
-            Err(MlsError::RequiredExtensionNotFound(v)) if v == 33.into()
+            Err(MlsError::RequiredCapabilitiesNotSupported(leaves)) if leaves == [*alice]
         );
     }
 
@@ -3838,7 +3887,11 @@ mod tests {
 
     #[cfg(feature = "psk")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
     impl PreSharedKeyStorage for AlwaysNotFoundPskStorage {
         type Error = Infallible;
 
@@ -4042,6 +4095,64 @@ mod tests {
         }
     }
 
+    struct ConflictRejectingMlsRules;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    impl MlsRules for ConflictRejectingMlsRules {
+        type Error = MlsError;
+
+        async fn filter_proposals(
+            &self,
+            _: CommitDirection,
+            _: CommitSource,
+            _: &Roster,
+            _: &ExtensionList,
+            proposals: ProposalBundle,
+        ) -> Result<ProposalBundle, Self::Error> {
+            if proposals.conflict_report().is_empty() {
+                Ok(proposals)
+            } else {
+                Err(MlsError::InvalidSignature)
+            }
+        }
+
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        fn commit_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+            _: &ProposalBundle,
+        ) -> Result<CommitOptions, Self::Error> {
+            Ok(Default::default())
+        }
+
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        fn encryption_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+        ) -> Result<EncryptionOptions, Self::Error> {
+            Ok(Default::default())
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn user_defined_filter_receives_conflict_report() {
+        let (alice, tree) = new_tree("alice").await;
+
+        let res = CommitSender::new(&tree, alice, test_cipher_suite_provider(TEST_CIPHER_SUITE))
+            .with_additional([
+                Proposal::GroupContextExtensions(Default::default()),
+                Proposal::GroupContextExtensions(Default::default()),
+            ])
+            .with_user_rules(ConflictRejectingMlsRules)
+            .send()
+            .await;
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn user_defined_filter_can_inject_proposals() {
         let (alice, tree) = new_tree("alice").await;
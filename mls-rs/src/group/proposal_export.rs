@@ -0,0 +1,143 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{message_processor::CachedProposal, Group},
+};
+
+#[derive(MlsSize, MlsEncode, MlsDecode)]
+struct PendingProposals {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    group_id: Vec<u8>,
+    epoch: u64,
+    proposals: Vec<CachedProposal>,
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Export the proposals received by reference in the current epoch that
+    /// were not committed yet.
+    ///
+    /// Cached proposals are part of the group state written by
+    /// [`Group::write_to_storage`], so they survive a restart as long as
+    /// the state is written after every proposal. This function exports
+    /// only the proposals, which is cheaper to persist after each received
+    /// proposal. The export can be restored with
+    /// [`Group::pending_proposals_restore`] after loading the group.
+    pub fn export_pending_proposals(&self) -> Result<Vec<u8>, MlsError> {
+        let proposals = self
+            .state
+            .proposals
+            .proposals
+            .iter()
+            .map(|(proposal_ref, cached)| CachedProposal {
+                proposal: cached.proposal.clone(),
+                proposal_ref: proposal_ref.clone(),
+                sender: cached.sender,
+            })
+            .collect();
+
+        PendingProposals {
+            group_id: self.group_id().to_vec(),
+            epoch: self.current_epoch(),
+            proposals,
+        }
+        .mls_encode_to_vec()
+        .map_err(Into::into)
+    }
+
+    /// Restore proposals exported with [`Group::export_pending_proposals`]
+    /// into the proposal cache, so that the next commit covers them.
+    ///
+    /// Proposals are not validated again and should only be restored from
+    /// storage trusted as much as the group state. Proposals already in the
+    /// cache are skipped. Returns the number of restored proposals, or
+    /// [`MlsError::InvalidEpoch`] if the proposals were exported in a
+    /// different epoch, in which case they are no longer valid.
+    pub fn pending_proposals_restore(&mut self, exported: &[u8]) -> Result<usize, MlsError> {
+        let pending = PendingProposals::mls_decode(&mut &*exported)?;
+
+        if pending.group_id != self.group_id() {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        if pending.epoch != self.current_epoch() {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        let cache = &mut self.state.proposals;
+        let mut restored = 0;
+
+        for cached in pending.proposals {
+            if cache.proposals.get(&cached.proposal_ref).is_some() {
+                continue;
+            }
+
+            cache.insert(cached.proposal_ref, cached.proposal, cached.sender);
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientBuilder,
+        client_config::ClientConfig,
+        group::test_utils::test_n_member_group,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn restored_proposals_are_committed() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        groups[0].group.write_to_storage().await.unwrap();
+
+        let proposal = groups[1].group.propose_remove(2, vec![]).await.unwrap();
+
+        groups[0].process_message(proposal).await.unwrap();
+
+        let exported = groups[0].group.export_pending_proposals().unwrap();
+
+        let mut restarted = TestClientBuilder::new_for_test()
+            .group_state_storage(groups[0].group.config.group_state_storage())
+            .build()
+            .load_group(groups[0].group.group_id())
+            .await
+            .unwrap();
+
+        assert_eq!(restarted.pending_proposals_restore(&exported).unwrap(), 1);
+        assert_eq!(restarted.pending_proposals_restore(&exported).unwrap(), 0);
+
+        let commit = restarted.commit(vec![]).await.unwrap();
+        restarted.apply_pending_commit().await.unwrap();
+
+        groups[1]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(restarted.roster().members().len(), 2);
+        assert_eq!(groups[1].group.roster().members().len(), 2);
+
+        let res = restarted.pending_proposals_restore(&exported);
+        assert_matches!(res, Err(MlsError::InvalidEpoch));
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 mod bundle;
+mod conflicts;
 mod filtering_common;
 
 #[cfg(feature = "by_ref_proposal")]
@@ -13,6 +14,7 @@ pub mod filtering_lite;
 use filtering_lite as filtering;
 
 pub use bundle::{ProposalBundle, ProposalInfo, ProposalSource};
+pub use conflicts::{ConflictKind, ConflictReport, ProposalConflict, ProposalPosition};
 
 #[cfg(feature = "by_ref_proposal")]
 pub(crate) use filtering::FilterStrategy;
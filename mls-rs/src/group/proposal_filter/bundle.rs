@@ -25,8 +25,13 @@ use crate::group::PreSharedKeyProposal;
 #[cfg(feature = "custom_proposal")]
 use crate::group::proposal::CustomProposal;
 
+#[cfg(feature = "self_remove_proposal")]
+use crate::group::proposal::SelfRemoveProposal;
+
 use crate::group::ExternalInit;
 
+use super::ConflictReport;
+
 use core::iter::empty;
 
 #[derive(Clone, Debug, Default)]
@@ -44,8 +49,12 @@ pub struct ProposalBundle {
     pub(crate) reinitializations: Vec<ProposalInfo<ReInitProposal>>,
     pub(crate) external_initializations: Vec<ProposalInfo<ExternalInit>>,
     pub(crate) group_context_extensions: Vec<ProposalInfo<ExtensionList>>,
+    #[cfg(feature = "self_remove_proposal")]
+    pub(crate) self_removals: Vec<ProposalInfo<SelfRemoveProposal>>,
     #[cfg(feature = "custom_proposal")]
     pub(crate) custom_proposals: Vec<ProposalInfo<CustomProposal>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) conflict_report: ConflictReport,
 }
 
 impl ProposalBundle {
@@ -90,6 +99,12 @@ impl ProposalBundle {
                     source,
                 })
             }
+            #[cfg(feature = "self_remove_proposal")]
+            Proposal::SelfRemove(proposal) => self.self_removals.push(ProposalInfo {
+                proposal,
+                sender,
+                source,
+            }),
             #[cfg(feature = "custom_proposal")]
             Proposal::Custom(proposal) => self.custom_proposals.push(ProposalInfo {
                 proposal,
@@ -198,6 +213,11 @@ impl ProposalBundle {
             f(&proposal.as_ref().map(BorrowedProposal::from))
         })?;
 
+        #[cfg(feature = "self_remove_proposal")]
+        self.retain_by_type::<SelfRemoveProposal, _, _>(|proposal| {
+            f(&proposal.as_ref().map(BorrowedProposal::from))
+        })?;
+
         Ok(())
     }
 
@@ -216,6 +236,9 @@ impl ProposalBundle {
         #[cfg(feature = "by_ref_proposal")]
         let len = len + self.updates.len();
 
+        #[cfg(feature = "self_remove_proposal")]
+        let len = len + self.self_removals.len();
+
         len + self.additions.len()
             + self.removals.len()
             + self.reinitializations.len()
@@ -265,6 +288,13 @@ impl ProposalBundle {
                 .map(|p| p.as_ref().map(BorrowedProposal::GroupContextExtensions)),
         );
 
+        #[cfg(feature = "self_remove_proposal")]
+        let res = res.chain(
+            self.self_removals
+                .iter()
+                .map(|p| p.as_ref().map(BorrowedProposal::SelfRemove)),
+        );
+
         #[cfg(feature = "custom_proposal")]
         let res = res.chain(
             self.custom_proposals
@@ -298,6 +328,13 @@ impl ProposalBundle {
         #[cfg(feature = "by_ref_proposal")]
         let res = res.chain(self.updates.into_iter().map(|p| p.map(Proposal::Update)));
 
+        #[cfg(feature = "self_remove_proposal")]
+        let res = res.chain(
+            self.self_removals
+                .into_iter()
+                .map(|p| p.map(Proposal::SelfRemove)),
+        );
+
         res.chain(
             self.additions
                 .into_iter()
@@ -349,6 +386,12 @@ impl ProposalBundle {
         &self.removals
     }
 
+    /// Self remove proposals in the bundle.
+    #[cfg(feature = "self_remove_proposal")]
+    pub fn self_remove_proposals(&self) -> &[ProposalInfo<SelfRemoveProposal>] {
+        &self.self_removals
+    }
+
     /// Pre-shared key proposals in the bundle.
     #[cfg(feature = "psk")]
     pub fn psk_proposals(&self) -> &[ProposalInfo<PreSharedKeyProposal>] {
@@ -401,6 +444,16 @@ impl ProposalBundle {
         res
     }
 
+    /// Conflicts between the proposals of this bundle.
+    ///
+    /// The report is computed before the bundle is passed to
+    /// [`MlsRules::filter_proposals`](crate::MlsRules::filter_proposals) and is
+    /// not updated when the bundle is modified. Use [`ConflictReport::new`] to
+    /// analyze a modified bundle.
+    pub fn conflict_report(&self) -> &ConflictReport {
+        &self.conflict_report
+    }
+
     /// Standard proposal types that are in use within this bundle.
     pub fn proposal_types(&self) -> impl Iterator<Item = ProposalType> + '_ {
         let res = (!self.additions.is_empty())
@@ -419,6 +472,9 @@ impl ProposalBundle {
             (!self.external_initializations.is_empty()).then_some(ProposalType::EXTERNAL_INIT),
         );
 
+        #[cfg(feature = "self_remove_proposal")]
+        let res = res.chain((!self.self_removals.is_empty()).then_some(ProposalType::SELF_REMOVE));
+
         #[cfg(not(feature = "custom_proposal"))]
         return res.chain(
             (!self.group_context_extensions.is_empty())
@@ -631,3 +687,5 @@ impl_proposable!(
     GROUP_CONTEXT_EXTENSIONS,
     group_context_extensions
 );
+#[cfg(feature = "self_remove_proposal")]
+impl_proposable!(SelfRemoveProposal, SELF_REMOVE, self_removals);
@@ -0,0 +1,342 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+
+use crate::{
+    group::{AddProposal, ProposalType},
+    ExtensionList,
+};
+
+#[cfg(feature = "by_ref_proposal")]
+use crate::group::{Sender, UpdateProposal};
+
+use super::ProposalBundle;
+
+/// Position of a proposal within a [`ProposalBundle`].
+///
+/// `index` refers to the list of proposals of type `proposal_type`, for
+/// example [`ProposalBundle::add_proposals`] for [`ProposalType::ADD`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProposalPosition {
+    pub proposal_type: ProposalType,
+    pub index: usize,
+}
+
+impl ProposalPosition {
+    fn new(proposal_type: ProposalType, index: usize) -> Self {
+        Self {
+            proposal_type,
+            index,
+        }
+    }
+}
+
+/// Kind of a [`ProposalConflict`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictKind {
+    /// The leaf with the given index is both updated and removed.
+    #[cfg(feature = "by_ref_proposal")]
+    UpdateOfRemovedLeaf(u32),
+    /// The same key package is added more than once.
+    DuplicateKeyPackage,
+    /// Different key packages with the same credential are added.
+    DuplicateIdentity,
+    /// More than one GroupContextExtensions proposal is present.
+    MultipleGroupContextExtensions,
+}
+
+/// Set of proposals that can't be committed together.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProposalConflict {
+    pub kind: ConflictKind,
+    /// All proposals involved in the conflict.
+    pub proposals: Vec<ProposalPosition>,
+    /// Proposals whose removal from the bundle resolves the conflict.
+    pub suggested_removals: Vec<ProposalPosition>,
+}
+
+/// Conflicts between the proposals of a [`ProposalBundle`], available to
+/// [`MlsRules::filter_proposals`](crate::MlsRules::filter_proposals) with
+/// [`ProposalBundle::conflict_report`].
+///
+/// The suggested resolutions keep removals over updates of the same leaf,
+/// the first add of a key package or credential and the last
+/// GroupContextExtensions proposal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictReport {
+    conflicts: Vec<ProposalConflict>,
+}
+
+impl ConflictReport {
+    /// Detect the conflicts between the proposals of `bundle`.
+    pub fn new(bundle: &ProposalBundle) -> Self {
+        let mut conflicts = Vec::new();
+
+        #[cfg(feature = "by_ref_proposal")]
+        conflicts.extend(update_remove_conflicts(bundle));
+
+        conflicts.extend(duplicate_add_conflicts(bundle));
+
+        let extension_proposals = bundle.by_type::<ExtensionList>().count();
+
+        if extension_proposals > 1 {
+            let positions = (0..extension_proposals)
+                .map(|i| ProposalPosition::new(ProposalType::GROUP_CONTEXT_EXTENSIONS, i))
+                .collect::<Vec<_>>();
+
+            conflicts.push(ProposalConflict {
+                kind: ConflictKind::MultipleGroupContextExtensions,
+                suggested_removals: positions[..extension_proposals - 1].to_vec(),
+                proposals: positions,
+            });
+        }
+
+        Self { conflicts }
+    }
+
+    pub fn conflicts(&self) -> &[ProposalConflict] {
+        &self.conflicts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Remove the suggested proposals of all conflicts from `bundle`.
+    ///
+    /// `bundle` must be the bundle that this report was created from.
+    pub fn resolve(&self, bundle: &mut ProposalBundle) {
+        let mut removals = self
+            .conflicts
+            .iter()
+            .flat_map(|c| c.suggested_removals.iter().copied())
+            .collect::<Vec<_>>();
+
+        // Remove from the back so that the remaining positions stay valid.
+        removals.sort_unstable_by(|a, b| b.cmp(a));
+        removals.dedup();
+
+        for position in removals {
+            match position.proposal_type {
+                ProposalType::ADD => bundle.remove::<AddProposal>(position.index),
+                #[cfg(feature = "by_ref_proposal")]
+                ProposalType::UPDATE => bundle.remove::<UpdateProposal>(position.index),
+                ProposalType::GROUP_CONTEXT_EXTENSIONS => {
+                    bundle.remove::<ExtensionList>(position.index)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "by_ref_proposal")]
+fn update_remove_conflicts(bundle: &ProposalBundle) -> impl Iterator<Item = ProposalConflict> + '_ {
+    bundle
+        .update_proposals()
+        .iter()
+        .enumerate()
+        .filter_map(|(update, info)| {
+            let Sender::Member(leaf_index) = info.sender else {
+                return None;
+            };
+
+            let remove = bundle
+                .remove_proposals()
+                .iter()
+                .position(|p| p.proposal.to_remove() == leaf_index)?;
+
+            let update = ProposalPosition::new(ProposalType::UPDATE, update);
+
+            Some(ProposalConflict {
+                kind: ConflictKind::UpdateOfRemovedLeaf(leaf_index),
+                proposals: alloc::vec![update, ProposalPosition::new(ProposalType::REMOVE, remove)],
+                suggested_removals: alloc::vec![update],
+            })
+        })
+}
+
+fn duplicate_add_conflicts(bundle: &ProposalBundle) -> Vec<ProposalConflict> {
+    let adds = bundle.add_proposals();
+    let mut conflicts = Vec::new();
+    let mut reported = alloc::vec![false; adds.len()];
+
+    for (i, add) in adds.iter().enumerate() {
+        if reported[i] {
+            continue;
+        }
+
+        let key_package = &add.proposal.key_package;
+        let credential = &key_package.leaf_node.signing_identity.credential;
+
+        let same_key_package = (i + 1..adds.len())
+            .filter(|&j| !reported[j] && adds[j].proposal.key_package == *key_package)
+            .collect::<Vec<_>>();
+
+        same_key_package.iter().for_each(|&j| reported[j] = true);
+
+        let same_identity = (i + 1..adds.len())
+            .filter(|&j| {
+                !reported[j]
+                    && adds[j]
+                        .proposal
+                        .key_package
+                        .leaf_node
+                        .signing_identity
+                        .credential
+                        == *credential
+            })
+            .collect::<Vec<_>>();
+
+        same_identity.iter().for_each(|&j| reported[j] = true);
+
+        let groups = [
+            (ConflictKind::DuplicateKeyPackage, same_key_package),
+            (ConflictKind::DuplicateIdentity, same_identity),
+        ];
+
+        for (kind, duplicates) in groups {
+            if duplicates.is_empty() {
+                continue;
+            }
+
+            let suggested_removals = duplicates
+                .into_iter()
+                .map(|j| ProposalPosition::new(ProposalType::ADD, j))
+                .collect::<Vec<_>>();
+
+            let mut proposals = alloc::vec![ProposalPosition::new(ProposalType::ADD, i)];
+            proposals.extend(suggested_removals.iter().copied());
+
+            conflicts.push(ProposalConflict {
+                kind,
+                proposals,
+                suggested_removals,
+            });
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::{AddProposal, Proposal, ProposalType, Sender},
+        key_package::test_utils::test_key_package,
+        ExtensionList,
+    };
+
+    use super::{ConflictKind, ConflictReport, ProposalBundle, ProposalPosition};
+    use crate::group::proposal_filter::ProposalSource;
+
+    fn add(bundle: &mut ProposalBundle, proposal: Proposal) {
+        bundle.add(proposal, Sender::Member(0), ProposalSource::ByValue);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn duplicate_adds_are_reported() {
+        let bob = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+        let bob2 = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+        let carol = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await;
+
+        let mut bundle = ProposalBundle::default();
+
+        for key_package in [bob.clone(), carol, bob, bob2] {
+            add(
+                &mut bundle,
+                Proposal::Add(AddProposal { key_package }.into()),
+            );
+        }
+
+        let report = ConflictReport::new(&bundle);
+        let kinds = report
+            .conflicts()
+            .iter()
+            .map(|c| c.kind)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                ConflictKind::DuplicateKeyPackage,
+                ConflictKind::DuplicateIdentity
+            ]
+        );
+
+        assert_eq!(
+            report.conflicts()[1].suggested_removals,
+            [ProposalPosition::new(ProposalType::ADD, 3)]
+        );
+
+        report.resolve(&mut bundle);
+
+        assert_eq!(bundle.add_proposals().len(), 2);
+        assert!(ConflictReport::new(&bundle).is_empty());
+    }
+
+    #[test]
+    fn last_group_context_extensions_proposal_is_kept() {
+        let mut bundle = ProposalBundle::default();
+
+        for _ in 0..3 {
+            add(
+                &mut bundle,
+                Proposal::GroupContextExtensions(ExtensionList::new()),
+            );
+        }
+
+        let report = ConflictReport::new(&bundle);
+
+        assert_eq!(report.conflicts().len(), 1);
+        assert_eq!(
+            report.conflicts()[0].kind,
+            ConflictKind::MultipleGroupContextExtensions
+        );
+
+        report.resolve(&mut bundle);
+
+        assert_eq!(bundle.group_context_ext_proposals().len(), 1);
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn update_of_removed_leaf_is_reported() {
+        use crate::group::{test_utils::test_group, RemoveProposal};
+        use crate::tree_kem::node::LeafIndex;
+
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let update = group.update_proposal().await;
+
+        let mut bundle = ProposalBundle::default();
+
+        bundle.add(update, Sender::Member(1), ProposalSource::ByValue);
+
+        add(
+            &mut bundle,
+            Proposal::Remove(RemoveProposal {
+                to_remove: LeafIndex(1),
+            }),
+        );
+
+        let report = ConflictReport::new(&bundle);
+
+        assert_eq!(report.conflicts().len(), 1);
+        assert_eq!(
+            report.conflicts()[0].kind,
+            ConflictKind::UpdateOfRemovedLeaf(1)
+        );
+
+        report.resolve(&mut bundle);
+
+        assert!(bundle.update_proposals().is_empty());
+        assert_eq!(bundle.remove_proposals().len(), 1);
+    }
+}
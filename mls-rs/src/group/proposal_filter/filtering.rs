@@ -39,6 +39,9 @@ use crate::group::ExternalInit;
 #[cfg(feature = "psk")]
 use crate::group::proposal::PreSharedKeyProposal;
 
+#[cfg(feature = "self_remove_proposal")]
+use crate::group::proposal::SelfRemoveProposal;
+
 #[cfg(all(not(mls_build_async), feature = "rayon"))]
 use {crate::iter::ParallelIteratorExt, rayon::prelude::*};
 
@@ -71,6 +74,9 @@ where
             .map(leaf_index_of_update_sender)
             .collect::<Result<_, _>>()?;
 
+        #[cfg(feature = "self_remove_proposal")]
+        let proposals = convert_self_removals(strategy, self.original_tree, proposals)?;
+
         let mut proposals = filter_out_removal_of_committer(strategy, commit_sender, proposals)?;
 
         filter_out_invalid_psks(
@@ -169,11 +175,14 @@ where
         group_extensions_in_use: &ExtensionList,
         commit_time: Option<MlsTime>,
     ) -> Result<ProposalBundle, MlsError> {
+        let signatures_verified = self.verify_new_node_signatures(&proposals).await?;
+
         let leaf_node_validator = &LeafNodeValidator::new(
             self.cipher_suite_provider,
             self.identity_provider,
             Some(group_extensions_in_use),
-        );
+        )
+        .with_signatures_verified(signatures_verified);
 
         let bad_indices: Vec<_> = wrap_iter(proposals.update_proposals())
             .zip(wrap_iter(proposals.update_proposal_senders()))
@@ -205,6 +214,15 @@ where
                         .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))
                         .and_then(|valid| valid.then_some(()).ok_or(MlsError::InvalidSuccessor));
 
+                    #[cfg(feature = "member_expiry")]
+                    let valid_successor = valid_successor.and_then(|_| {
+                        crate::group::member_expiry::ensure_expiry_not_extended(
+                            old_leaf,
+                            leaf,
+                            *sender_index,
+                        )
+                    });
+
                     res.and(valid_successor)
                 };
 
@@ -308,6 +326,42 @@ fn filter_out_removal_of_committer(
     Ok(proposals)
 }
 
+/// Self removals are applied as removals of their sender. After conversion, they
+/// are subject to the same checks as any other removal, e.g. the committer can't
+/// commit its own self removal.
+#[cfg(feature = "self_remove_proposal")]
+fn convert_self_removals(
+    strategy: FilterStrategy,
+    tree: &TreeKemPublic,
+    mut proposals: ProposalBundle,
+) -> Result<ProposalBundle, MlsError> {
+    let supported = tree.can_support_proposal(ProposalType::SELF_REMOVE);
+
+    for p in core::mem::take(&mut proposals.self_removals) {
+        let res = supported
+            .then_some(())
+            .ok_or(MlsError::SelfRemoveUnsupported);
+
+        if !apply_strategy(strategy, p.is_by_reference(), res)? {
+            continue;
+        }
+
+        let Sender::Member(sender) = p.sender else {
+            return Err(MlsError::InvalidProposalTypeForSender);
+        };
+
+        proposals.removals.push(ProposalInfo {
+            proposal: RemoveProposal {
+                to_remove: LeafIndex(sender),
+            },
+            sender: p.sender,
+            source: p.source,
+        });
+    }
+
+    Ok(proposals)
+}
+
 #[cfg(feature = "by_ref_proposal")]
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 async fn filter_out_invalid_group_extensions<C>(
@@ -448,6 +502,7 @@ pub(crate) fn proposer_can_propose(
                 | ProposalType::PSK
                 | ProposalType::RE_INIT
                 | ProposalType::GROUP_CONTEXT_EXTENSIONS
+                | ProposalType::SELF_REMOVE
         ),
         #[cfg(feature = "by_ref_proposal")]
         (Sender::External(_), false) => false,
@@ -544,6 +599,16 @@ pub(crate) fn filter_out_invalid_proposers(
         }
     }
 
+    #[cfg(feature = "self_remove_proposal")]
+    for i in (0..proposals.self_remove_proposals().len()).rev() {
+        let p = &proposals.self_remove_proposals()[i];
+        let res = proposer_can_propose(p.sender, ProposalType::SELF_REMOVE, p.is_by_reference());
+
+        if !apply_strategy(strategy, p.is_by_reference(), res)? {
+            proposals.remove::<SelfRemoveProposal>(i);
+        }
+    }
+
     Ok(proposals)
 }
 
@@ -5,8 +5,11 @@
 use crate::{
     client::MlsError,
     group::{proposal_filter::ProposalBundle, Sender},
-    key_package::{validate_key_package_properties, KeyPackage},
+    key_package::{
+        validate_key_package_properties, validate_unsigned_key_package_properties, KeyPackage,
+    },
     protocol_version::ProtocolVersion,
+    signer::SignatureBatch,
     time::MlsTime,
     tree_kem::{
         leaf_node_validator::{LeafNodeValidator, ValidationContext},
@@ -22,9 +25,18 @@ use super::ProposalInfo;
 
 use crate::extension::{MlsExtension, RequiredCapabilitiesExt};
 
+#[cfg(feature = "group_features")]
+use crate::extension::GroupFeaturesExt;
+
 #[cfg(feature = "by_ref_proposal")]
 use crate::extension::ExternalSendersExt;
 
+#[cfg(feature = "content_advertisement")]
+use crate::extension::RequiredMediaTypesExt;
+
+#[cfg(feature = "device_attestation")]
+use crate::extension::DeviceAttestationRequiredExt;
+
 use mls_rs_core::error::IntoAnyError;
 
 use alloc::vec::Vec;
@@ -109,6 +121,15 @@ where
         #[cfg(feature = "by_ref_proposal")] proposals: ProposalBundle,
         commit_time: Option<MlsTime>,
     ) -> Result<ApplyProposalsOutput, MlsError> {
+        #[cfg(feature = "member_reset")]
+        crate::group::member_reset::ensure_reset_members_have_psk(
+            &proposals,
+            self.external_leaf,
+            self.identity_provider,
+            self.original_group_extensions,
+        )
+        .await?;
+
         let output = match commit_sender {
             Sender::Member(sender) => {
                 self.apply_proposals_from_member(
@@ -243,12 +264,30 @@ where
             .proposal
             .has_extension(RequiredCapabilitiesExt::extension_type());
 
+        #[cfg(feature = "group_features")]
+        let must_check = must_check
+            || group_context_extensions_proposal
+                .proposal
+                .has_extension(GroupFeaturesExt::extension_type());
+
         #[cfg(feature = "by_ref_proposal")]
         let must_check = must_check
             || group_context_extensions_proposal
                 .proposal
                 .has_extension(ExternalSendersExt::extension_type());
 
+        #[cfg(feature = "device_attestation")]
+        let must_check = must_check
+            || group_context_extensions_proposal
+                .proposal
+                .has_extension(DeviceAttestationRequiredExt::extension_type());
+
+        #[cfg(feature = "content_advertisement")]
+        let must_check = must_check
+            || group_context_extensions_proposal
+                .proposal
+                .has_extension(RequiredMediaTypesExt::extension_type());
+
         let new_capabilities_supported = if must_check {
             let leaf_validator = LeafNodeValidator::new(
                 self.cipher_suite_provider,
@@ -256,17 +295,45 @@ where
                 Some(&group_context_extensions_proposal.proposal),
             );
 
-            output
+            // Report all leaves violating new required capabilities, so that
+            // the committer knows which members to remove or wait for.
+            let unsupported = output
                 .new_tree
                 .non_empty_leaves()
-                .try_for_each(|(_, leaf)| {
-                    leaf_validator.validate_required_capabilities(leaf)?;
+                .filter(|(_, leaf)| {
+                    matches!(
+                        leaf_validator.validate_required_capabilities(leaf),
+                        Err(MlsError::RequiredExtensionNotFound(_)
+                            | MlsError::RequiredProposalNotFound(_)
+                            | MlsError::RequiredCredentialNotFound(_))
+                    )
+                })
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>();
 
-                    #[cfg(feature = "by_ref_proposal")]
-                    leaf_validator.validate_external_senders_ext_credentials(leaf)?;
+            if !unsupported.is_empty() {
+                Err(MlsError::RequiredCapabilitiesNotSupported(unsupported))
+            } else {
+                output
+                    .new_tree
+                    .non_empty_leaves()
+                    .try_for_each(|(_, leaf)| {
+                        leaf_validator.validate_required_capabilities(leaf)?;
+                        #[cfg(feature = "group_features")]
+                        leaf_validator.validate_group_features(leaf)?;
 
-                    Ok(())
-                })
+                        #[cfg(feature = "content_advertisement")]
+                        leaf_validator.validate_required_media_types(leaf)?;
+
+                        #[cfg(feature = "device_attestation")]
+                        leaf_validator.validate_device_attestation(leaf)?;
+
+                        #[cfg(feature = "by_ref_proposal")]
+                        leaf_validator.validate_external_senders_ext_credentials(leaf)?;
+
+                        Ok(())
+                    })
+            }
         } else {
             Ok(())
         };
@@ -314,6 +381,43 @@ where
         }
     }
 
+    /// Verify the signatures of the leaf nodes and key packages of all add
+    /// and update proposals in one batch. Returns `false` if any signature
+    /// is invalid, in which case the proposals must be validated one by one
+    /// to find the invalid ones.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_new_node_signatures(
+        &self,
+        proposals: &ProposalBundle,
+    ) -> Result<bool, MlsError> {
+        let mut batch = SignatureBatch::default();
+
+        #[cfg(feature = "by_ref_proposal")]
+        for (p, &sender_index) in proposals
+            .update_proposals()
+            .iter()
+            .zip(proposals.update_proposal_senders())
+        {
+            let leaf_node = &p.proposal.leaf_node;
+
+            batch.add(
+                leaf_node,
+                &leaf_node.signing_identity.signature_key,
+                &(self.group_id, *sender_index).into(),
+            )?;
+        }
+
+        for p in proposals.add_proposals() {
+            let key_package = &p.proposal.key_package;
+            let signature_key = &key_package.leaf_node.signing_identity.signature_key;
+
+            batch.add(&key_package.leaf_node, signature_key, &Default::default())?;
+            batch.add(key_package, signature_key, &())?;
+        }
+
+        Ok(batch.verify(self.cipher_suite_provider).await.is_ok())
+    }
+
     #[cfg(any(mls_build_async, not(feature = "rayon")))]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn validate_new_node<Ip: IdentityProvider, Cp: CipherSuiteProvider>(
@@ -326,6 +430,14 @@ where
             .check_if_valid(&key_package.leaf_node, ValidationContext::Add(commit_time))
             .await?;
 
+        if leaf_node_validator.signatures_verified() {
+            return validate_unsigned_key_package_properties(
+                key_package,
+                self.protocol_version,
+                self.cipher_suite_provider,
+            );
+        }
+
         validate_key_package_properties(
             key_package,
             self.protocol_version,
@@ -347,11 +459,19 @@ where
                     .check_if_valid(&key_package.leaf_node, ValidationContext::Add(commit_time))
             },
             || {
-                validate_key_package_properties(
-                    key_package,
-                    self.protocol_version,
-                    self.cipher_suite_provider,
-                )
+                if leaf_node_validator.signatures_verified() {
+                    validate_unsigned_key_package_properties(
+                        key_package,
+                        self.protocol_version,
+                        self.cipher_suite_provider,
+                    )
+                } else {
+                    validate_key_package_properties(
+                        key_package,
+                        self.protocol_version,
+                        self.cipher_suite_provider,
+                    )
+                }
             },
         );
         a?;
@@ -493,9 +613,11 @@ fn ensure_proposals_in_external_commit_are_allowed(
         ProposalType::PSK,
     ];
 
-    let unsupported_type = proposals
-        .proposal_types()
-        .find(|ty| !supported_default_types.contains(ty) && ProposalType::DEFAULT.contains(ty));
+    // Self removals have to be committed by another existing member.
+    let unsupported_type = proposals.proposal_types().find(|ty| {
+        !supported_default_types.contains(ty)
+            && (ProposalType::DEFAULT.contains(ty) || *ty == ProposalType::SELF_REMOVE)
+    });
 
     match unsupported_type {
         Some(kind) => Err(MlsError::InvalidProposalTypeInExternalCommit(kind)),
@@ -543,7 +665,15 @@ async fn ensure_removal_is_for_self<C>(
 where
     C: IdentityProvider,
 {
-    let existing_signing_id = &tree.get_leaf_node(removal.to_remove)?.signing_identity;
+    let existing_leaf = tree.get_leaf_node(removal.to_remove)?;
+    let existing_signing_id = &existing_leaf.signing_identity;
+
+    #[cfg(feature = "member_expiry")]
+    crate::group::member_expiry::ensure_expiry_not_extended(
+        existing_leaf,
+        external_leaf,
+        *removal.to_remove,
+    )?;
 
     identity_provider
         .valid_successor(
@@ -124,11 +124,14 @@ where
         group_extensions_in_use: &ExtensionList,
         commit_time: Option<MlsTime>,
     ) -> Result<(), MlsError> {
+        let signatures_verified = self.verify_new_node_signatures(proposals).await?;
+
         let leaf_node_validator = &LeafNodeValidator::new(
             self.cipher_suite_provider,
             self.identity_provider,
             Some(group_extensions_in_use),
-        );
+        )
+        .with_signatures_verified(signatures_verified);
 
         let adds = wrap_iter(proposals.add_proposals());
 
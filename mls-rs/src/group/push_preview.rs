@@ -0,0 +1,191 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{crypto::CipherSuiteProvider, error::IntoAnyError};
+use zeroize::Zeroizing;
+
+use crate::{client::MlsError, client_config::ClientConfig, group::Group};
+
+const PUSH_PREVIEW_LABEL: &[u8] = b"mls-rs push preview";
+const PREVIEW_ID_SIZE: usize = 16;
+
+/// Small notification payload encrypted under a per-message preview key.
+///
+/// The preview key is derived from the exporter secret of the epoch the
+/// preview was created in, using a random preview id as context. A push
+/// notification service can deliver the serialized preview next to the
+/// MLS ciphertext, and any member holding the group state of that epoch can
+/// decrypt it with [`Group::decrypt_push_preview`] without processing the
+/// full message.
+///
+/// Previews are not authenticated as coming from a particular member and
+/// are not protected by the forward secrecy of the MLS secret tree. Their
+/// content should be limited to what is acceptable to show on a lock screen.
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct PushPreview {
+    epoch: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    preview_id: Vec<u8>,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    ciphertext: Vec<u8>,
+}
+
+impl PushPreview {
+    /// Maximum size of a preview payload in bytes.
+    pub const MAX_PAYLOAD_SIZE: usize = 256;
+
+    /// Epoch of the group the preview was created in.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        Self::mls_decode(&mut &*bytes).map_err(Into::into)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Encrypt a notification `payload` of at most
+    /// [`PushPreview::MAX_PAYLOAD_SIZE`] bytes under a fresh preview key of
+    /// the current epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn encrypt_push_preview(&self, payload: &[u8]) -> Result<PushPreview, MlsError> {
+        if payload.len() > PushPreview::MAX_PAYLOAD_SIZE {
+            return Err(MlsError::PushPreviewTooLarge(payload.len()));
+        }
+
+        let preview_id = self
+            .cipher_suite_provider
+            .random_bytes_vec(PREVIEW_ID_SIZE)
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        let epoch = self.current_epoch();
+        let (key, nonce) = self.push_preview_key(&preview_id).await?;
+
+        let ciphertext = self
+            .cipher_suite_provider
+            .aead_seal(&key, payload, Some(&epoch.to_be_bytes()), &nonce)
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        Ok(PushPreview {
+            epoch,
+            preview_id,
+            ciphertext,
+        })
+    }
+
+    /// Decrypt a [`PushPreview`] created by any member in the current epoch.
+    ///
+    /// Previews of other epochs can not be decrypted, since exporter secrets
+    /// of prior epochs are not retained.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn decrypt_push_preview(
+        &self,
+        preview: &PushPreview,
+    ) -> Result<Zeroizing<Vec<u8>>, MlsError> {
+        if preview.epoch != self.current_epoch() {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        let (key, nonce) = self.push_preview_key(&preview.preview_id).await?;
+
+        self.cipher_suite_provider
+            .aead_open(
+                &key,
+                &preview.ciphertext,
+                Some(&preview.epoch.to_be_bytes()),
+                &nonce,
+            )
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+    }
+
+    // The key and nonce are derived together, since every preview id is used
+    // with a single payload.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn push_preview_key(
+        &self,
+        preview_id: &[u8],
+    ) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>), MlsError> {
+        let key_size = self.cipher_suite_provider.aead_key_size();
+        let nonce_size = self.cipher_suite_provider.aead_nonce_size();
+
+        let secret = self
+            .export_secret(PUSH_PREVIEW_LABEL, preview_id, key_size + nonce_size)
+            .await?;
+
+        let (key, nonce) = secret.split_at(key_size);
+
+        Ok((Zeroizing::new(key.to_vec()), nonce.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::PushPreview;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_decrypt_push_preview() {
+        let groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let preview = groups[0]
+            .group
+            .encrypt_push_preview(b"alice: hi")
+            .await
+            .unwrap();
+
+        let preview = PushPreview::from_bytes(&preview.to_bytes().unwrap()).unwrap();
+        let payload = groups[1]
+            .group
+            .decrypt_push_preview(&preview)
+            .await
+            .unwrap();
+
+        assert_eq!(payload.as_slice(), b"alice: hi");
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn push_preview_of_other_epoch_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let preview = groups[0].group.encrypt_push_preview(b"hi").await.unwrap();
+
+        groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+
+        let res = groups[0].group.decrypt_push_preview(&preview).await;
+        assert_matches!(res, Err(MlsError::InvalidEpoch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn large_push_preview_is_rejected() {
+        let groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let payload = vec![0u8; PushPreview::MAX_PAYLOAD_SIZE + 1];
+
+        let res = groups[0].group.encrypt_push_preview(&payload).await;
+
+        assert_matches!(res, Err(MlsError::PushPreviewTooLarge(len)) if len == payload.len());
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::SignaturePublicKey,
+    error::IntoAnyError,
+    extension::ExtensionList,
+    group::Member,
+    identity::{CredentialType, IdentityProvider, SigningIdentity},
+    time::MlsTime,
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{CommitOutput, Group, ReceivedMessage},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct QuarantinedMember {
+    index: u32,
+    signature_key: SignaturePublicKey,
+}
+
+/// Identity provider used when processing a received commit, that accepts
+/// the members added by the commit without validating their identity.
+pub(crate) struct QuarantineIdentityProvider<I> {
+    inner: I,
+    deferred: Vec<SignaturePublicKey>,
+}
+
+impl<I> QuarantineIdentityProvider<I> {
+    pub(crate) fn new(inner: I, deferred: Vec<SignaturePublicKey>) -> Self {
+        Self { inner, deferred }
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<I: IdentityProvider> IdentityProvider for QuarantineIdentityProvider<I> {
+    type Error = I::Error;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        if self.deferred.contains(&signing_identity.signature_key) {
+            return Ok(());
+        }
+
+        self.inner
+            .validate_member(signing_identity, timestamp, extensions)
+            .await
+    }
+
+    async fn validate_leaf_node_extensions(
+        &self,
+        signing_identity: &SigningIdentity,
+        leaf_node_extensions: &ExtensionList,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_leaf_node_extensions(signing_identity, leaf_node_extensions, extensions)
+            .await
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_external_sender(signing_identity, timestamp, extensions)
+            .await
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.identity(signing_identity, extensions).await
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        self.inner
+            .valid_successor(predecessor, successor, extensions)
+            .await
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        self.inner.supported_types()
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Members added by received commits whose identity was not validated
+    /// yet.
+    ///
+    /// Members are only quarantined if
+    /// [`ClientBuilder::quarantine_new_members`](crate::client_builder::ClientBuilder::quarantine_new_members)
+    /// is enabled. While any member is quarantined, application messages
+    /// can not be sent to the group.
+    pub fn quarantined_members(&self) -> Vec<Member> {
+        self.quarantine
+            .iter()
+            .filter_map(|entry| self.quarantined_member(entry))
+            .collect()
+    }
+
+    /// Validate the identity of the quarantined member at `index` with the
+    /// [`IdentityProvider`] of this client and release it from quarantine.
+    ///
+    /// If validation fails, the member stays quarantined and can be removed
+    /// with [`Group::evict_quarantined_member`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn validate_quarantined_member(&mut self, index: u32) -> Result<(), MlsError> {
+        let member = self
+            .quarantined_members()
+            .into_iter()
+            .find(|member| member.index == index)
+            .ok_or(MlsError::MemberNotQuarantined(index))?;
+
+        self.config
+            .identity_provider()
+            .validate_member(
+                &member.signing_identity,
+                None,
+                Some(&self.context().extensions),
+            )
+            .await
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
+
+        self.quarantine.retain(|entry| entry.index != index);
+
+        Ok(())
+    }
+
+    /// Commit the removal of the quarantined member at `index`.
+    ///
+    /// The member leaves quarantine once the commit is applied.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn evict_quarantined_member(&mut self, index: u32) -> Result<CommitOutput, MlsError> {
+        if !self.quarantined_members().iter().any(|m| m.index == index) {
+            return Err(MlsError::MemberNotQuarantined(index));
+        }
+
+        self.commit_builder().remove_member(index)?.build().await
+    }
+
+    pub(crate) fn check_quarantine(&self) -> Result<(), MlsError> {
+        if self.quarantined_members().is_empty() {
+            Ok(())
+        } else {
+            Err(MlsError::MembersQuarantined)
+        }
+    }
+
+    pub(crate) fn quarantine_added_members(&mut self, received: &ReceivedMessage) {
+        let ReceivedMessage::Commit(commit) = received else {
+            return;
+        };
+
+        if !self.config.quarantine_new_members() || self.is_strict() {
+            return;
+        }
+
+        let added = commit
+            .state_update
+            .roster_update()
+            .added()
+            .iter()
+            .map(|member| QuarantinedMember {
+                index: member.index,
+                signature_key: member.signing_identity.signature_key.clone(),
+            });
+
+        self.quarantine.extend(added);
+
+        // Forget members that were removed since they were quarantined.
+        let quarantine = core::mem::take(&mut self.quarantine);
+
+        self.quarantine = quarantine
+            .into_iter()
+            .filter(|entry| self.quarantined_member(entry).is_some())
+            .collect();
+    }
+
+    fn quarantined_member(&self, entry: &QuarantinedMember) -> Option<Member> {
+        self.roster()
+            .member_with_index(entry.index)
+            .ok()
+            .filter(|member| member.signing_identity.signature_key == entry.signature_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{test_client_with_key_pkg, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::{test_n_member_group, TestGroup},
+    };
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn quarantining_groups() -> (TestGroup, TestGroup) {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let mut bob = groups.remove(1);
+        bob.group.config.0.settings.quarantine_new_members = true;
+
+        let (_, carol) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await;
+
+        let commit = groups[0]
+            .group
+            .commit_builder()
+            .add_member(carol)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[0].process_pending_commit().await.unwrap();
+        bob.process_message(commit.commit_message).await.unwrap();
+
+        (groups.remove(0), bob)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn added_members_are_quarantined_until_validated() {
+        let (_, mut bob) = quarantining_groups().await;
+
+        let quarantined = bob.group.quarantined_members();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].index, 2);
+
+        let res = bob.group.encrypt_application_message(b"hi", vec![]).await;
+        assert_matches!(res, Err(MlsError::MembersQuarantined));
+
+        bob.group.validate_quarantined_member(2).await.unwrap();
+
+        assert!(bob.group.quarantined_members().is_empty());
+
+        bob.group
+            .encrypt_application_message(b"hi", vec![])
+            .await
+            .unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn quarantined_member_can_be_evicted() {
+        let (mut alice, mut bob) = quarantining_groups().await;
+
+        let commit = bob.group.evict_quarantined_member(2).await.unwrap();
+        bob.process_pending_commit().await.unwrap();
+        alice.process_message(commit.commit_message).await.unwrap();
+
+        assert!(bob.group.quarantined_members().is_empty());
+        assert_eq!(alice.group.roster().members().len(), 2);
+
+        let res = bob.group.validate_quarantined_member(2).await;
+        assert_matches!(res, Err(MlsError::MemberNotQuarantined(2)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_are_not_quarantined_by_default() {
+        let (mut alice, _) = quarantining_groups().await;
+
+        // Alice added Carol herself, and the default is to validate added
+        // members synchronously.
+        assert!(alice.group.quarantined_members().is_empty());
+
+        alice
+            .group
+            .encrypt_application_message(b"hi", vec![])
+            .await
+            .unwrap();
+    }
+}
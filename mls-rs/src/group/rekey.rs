@@ -0,0 +1,206 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::time::MlsTime;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{CommitOutput, Group},
+};
+
+/// Policy describing when a member should update its path to rotate the
+/// epoch secrets of a [`Group`], for post-compromise security.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReKeyPolicy {
+    /// Maximum time in seconds the group stays in the same epoch. If 0, the
+    /// age of the epoch is not limited.
+    pub max_epoch_age: u64,
+    /// Maximum number of application messages this member sends in the same
+    /// epoch. If 0, the number of messages is not limited.
+    pub max_messages_sent: u64,
+}
+
+impl ReKeyPolicy {
+    pub fn new(max_epoch_age: u64, max_messages_sent: u64) -> Self {
+        Self {
+            max_epoch_age,
+            max_messages_sent,
+        }
+    }
+}
+
+/// Age and usage of the current epoch, only kept in memory. After loading a
+/// group from storage, the epoch is considered to start at load time.
+#[derive(Clone, Debug)]
+pub(crate) struct ReKeyState {
+    policy: ReKeyPolicy,
+    epoch_start: MlsTime,
+    messages_sent: u64,
+}
+
+impl Default for ReKeyState {
+    fn default() -> Self {
+        Self {
+            policy: Default::default(),
+            epoch_start: MlsTime::now(),
+            messages_sent: 0,
+        }
+    }
+}
+
+impl ReKeyState {
+    pub(crate) fn start_epoch(&mut self) {
+        self.epoch_start = MlsTime::now();
+        self.messages_sent = 0;
+    }
+
+    pub(crate) fn record_sent(&mut self) {
+        self.messages_sent = self.messages_sent.saturating_add(1);
+    }
+
+    fn needs_update(&self, now: MlsTime) -> bool {
+        let ReKeyPolicy {
+            max_epoch_age,
+            max_messages_sent,
+        } = self.policy;
+
+        let age = now
+            .seconds_since_epoch()
+            .saturating_sub(self.epoch_start.seconds_since_epoch());
+
+        (max_epoch_age > 0 && age >= max_epoch_age)
+            || (max_messages_sent > 0 && self.messages_sent >= max_messages_sent)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Policy deciding when [`Group::needs_update`] asks for a path update.
+    pub fn rekey_policy(&self) -> &ReKeyPolicy {
+        &self.rekey.policy
+    }
+
+    /// Set the policy deciding when [`Group::needs_update`] asks for a path
+    /// update. The default policy never does.
+    pub fn set_rekey_policy(&mut self, policy: ReKeyPolicy) {
+        self.rekey.policy = policy;
+    }
+
+    /// Number of application messages this member sent in the current
+    /// epoch.
+    pub fn messages_sent_in_epoch(&self) -> u64 {
+        self.rekey.messages_sent
+    }
+
+    /// Determine if the current epoch is older than allowed by the
+    /// [`ReKeyPolicy`] of this group, or if this member sent more
+    /// application messages in it than allowed.
+    ///
+    /// Any commit, by this or another member, starts a new epoch.
+    pub fn needs_update(&self) -> bool {
+        self.rekey.needs_update(MlsTime::now())
+    }
+
+    /// Create a commit updating the path of this member if
+    /// [`Group::needs_update`] says so.
+    ///
+    /// Returns `None` if no update is needed. Otherwise, the commit must be
+    /// applied with [`Group::apply_pending_commit`] as usual.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn auto_update_commit(&mut self) -> Result<Option<CommitOutput>, MlsError> {
+        if !self.needs_update() {
+            return Ok(None);
+        }
+
+        // A commit without proposals always updates the path of the
+        // committer.
+        self.commit(Vec::new()).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "private_message")]
+    use alloc::vec;
+    use mls_rs_core::time::MlsTime;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::test_utils::test_n_member_group,
+    };
+
+    use super::ReKeyPolicy;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn old_epochs_need_update() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        assert!(!groups[0].group.needs_update());
+
+        groups[0].group.set_rekey_policy(ReKeyPolicy::new(3600, 0));
+
+        let epoch_start = groups[0].group.rekey.epoch_start.seconds_since_epoch();
+        let rekey = &groups[0].group.rekey;
+
+        assert!(!rekey.needs_update(MlsTime::from(epoch_start + 3599)));
+        assert!(rekey.needs_update(MlsTime::from(epoch_start + 3600)));
+
+        let output = groups[0].group.auto_update_commit().await.unwrap();
+        assert!(output.is_none());
+
+        // Move the start of the epoch an hour back.
+        groups[0].group.rekey.epoch_start = MlsTime::from(epoch_start - 3600);
+        assert!(groups[0].group.needs_update());
+
+        let epoch = groups[0].group.current_epoch();
+        let output = groups[0].group.auto_update_commit().await.unwrap().unwrap();
+
+        groups[0].process_pending_commit().await.unwrap();
+
+        groups[1]
+            .process_message(output.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(groups[0].group.current_epoch(), epoch + 1);
+        assert!(!groups[0].group.needs_update());
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn busy_epochs_need_update() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        groups[0].group.set_rekey_policy(ReKeyPolicy::new(0, 2));
+
+        for _ in 0..2 {
+            assert!(!groups[0].group.needs_update());
+
+            groups[0]
+                .group
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(groups[0].group.messages_sent_in_epoch(), 2);
+        assert!(groups[0].group.needs_update());
+
+        // A commit of another member also starts a new epoch.
+        let commit = groups[1].group.commit(vec![]).await.unwrap();
+        groups[1].process_pending_commit().await.unwrap();
+
+        groups[0]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(groups[0].group.messages_sent_in_epoch(), 0);
+        assert!(!groups[0].group.needs_update());
+    }
+}
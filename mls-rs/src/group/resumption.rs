@@ -5,7 +5,7 @@
 use alloc::vec::Vec;
 
 use mls_rs_core::{
-    crypto::{CipherSuite, SignatureSecretKey},
+    crypto::{CipherSuite, CipherSuiteProvider, SignatureSecretKey},
     extension::ExtensionList,
     identity::SigningIdentity,
     protocol_version::ProtocolVersion,
@@ -18,11 +18,11 @@ use super::{
     NewMemberInfo, PreSharedKeyID, PskGroupId, PskSecretInput, ResumptionPSKUsage, ResumptionPsk,
 };
 
-struct ResumptionGroupParameters<'a> {
-    group_id: &'a [u8],
-    cipher_suite: CipherSuite,
-    version: ProtocolVersion,
-    extensions: &'a ExtensionList,
+pub(super) struct ResumptionGroupParameters<'a> {
+    pub group_id: &'a [u8],
+    pub cipher_suite: CipherSuite,
+    pub version: ProtocolVersion,
+    pub extensions: &'a ExtensionList,
 }
 
 pub struct ReinitClient<C: ClientConfig + Clone> {
@@ -143,7 +143,20 @@ where
         })
     }
 
-    fn resumption_psk_input(&self, usage: ResumptionPSKUsage) -> Result<PskSecretInput, MlsError> {
+    pub(super) fn resumption_psk_input(
+        &self,
+        usage: ResumptionPSKUsage,
+    ) -> Result<PskSecretInput, MlsError> {
+        self.resumption_psk_input_for(usage, self.cipher_suite_provider())
+    }
+
+    /// Resumption PSK of the current epoch for a group using the cipher
+    /// suite of `cipher_suite_provider`, which determines the nonce length.
+    pub(super) fn resumption_psk_input_for<P: CipherSuiteProvider>(
+        &self,
+        usage: ResumptionPSKUsage,
+        cipher_suite_provider: &P,
+    ) -> Result<PskSecretInput, MlsError> {
         let psk = self.epoch_secrets.resumption_secret.clone();
 
         let id = JustPreSharedKeyID::Resumption(ResumptionPsk {
@@ -152,7 +165,7 @@ where
             psk_epoch: self.current_epoch(),
         });
 
-        let id = PreSharedKeyID::new(id, self.cipher_suite_provider())?;
+        let id = PreSharedKeyID::new(id, cipher_suite_provider)?;
         Ok(PskSecretInput { id, psk })
     }
 }
@@ -231,7 +244,7 @@ impl<C: ClientConfig + Clone> ReinitClient<C> {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-async fn resumption_create_group<C: ClientConfig + Clone>(
+pub(super) async fn resumption_create_group<C: ClientConfig + Clone>(
     config: C,
     new_key_packages: Vec<MlsMessage>,
     new_group_params: &ResumptionGroupParameters<'_>,
@@ -282,8 +295,18 @@ async fn resumption_join_group<C: ClientConfig + Clone>(
 ) -> Result<(Group<C>, NewMemberInfo), MlsError> {
     let psk_input = Some(psk_input);
 
-    let (group, new_member_info) =
-        Group::<C>::from_welcome_message(welcome, tree_data, config, signer, psk_input).await?;
+    let (group, new_member_info) = Group::<C>::from_welcome_message(
+        welcome,
+        tree_data,
+        #[cfg(feature = "tree_fetcher")]
+        None::<&crate::group::tree_fetcher::NoTreeFetcher>,
+        config,
+        signer,
+        psk_input,
+        #[cfg(feature = "welcome_retargeting")]
+        false,
+    )
+    .await?;
 
     if group.protocol_version() != expected_new_group_params.version {
         Err(MlsError::ProtocolVersionMismatch)
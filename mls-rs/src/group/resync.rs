@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{external_commit::ExternalCommitBuilder, Group},
+    MlsMessage,
+};
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create an [`ExternalCommitBuilder`] that replaces the current member
+    /// with a fresh leaf, removing the current leaf as part of the same
+    /// external commit.
+    ///
+    /// This is used to recover from a local state that diverged from the
+    /// rest of the group, which is reported by [`MlsError::StateDesync`].
+    pub fn resync_builder(&self) -> Result<ExternalCommitBuilder<C>, MlsError> {
+        let builder = ExternalCommitBuilder::new(
+            self.signer.clone(),
+            self.current_member_signing_identity()?.clone(),
+            self.config.clone(),
+        );
+
+        Ok(builder.with_removal(self.current_member_index()))
+    }
+
+    /// Rejoin the group using `group_info`, obtained from a current member
+    /// with [`Group::group_info_message_allowing_ext_commit`].
+    ///
+    /// The returned group replaces this group and the returned commit
+    /// message must be sent to the other members. See
+    /// [`Group::resync_builder`] for more options.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn resync(&self, group_info: MlsMessage) -> Result<(Group<C>, MlsMessage), MlsError> {
+        self.resync_builder()?.build(group_info).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_group,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_missing_commits_can_resync() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        // Bob misses the first commit
+        alice.group.commit(vec![]).await.unwrap();
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let res = bob.group.process_incoming_message(commit).await;
+        assert_matches!(res, Err(MlsError::StateDesync(_)));
+
+        let group_info = alice
+            .group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let (mut bob_group, commit) = bob.group.resync(group_info).await.unwrap();
+
+        alice.group.process_incoming_message(commit).await.unwrap();
+
+        assert_eq!(alice.group.roster().members_iter().count(), 2);
+        assert_eq!(bob_group.current_epoch(), alice.group.current_epoch());
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        alice.group.apply_pending_commit().await.unwrap();
+
+        bob_group.process_incoming_message(commit).await.unwrap();
+
+        assert_eq!(bob_group.current_epoch(), alice.group.current_epoch());
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+
+use crate::{client::MlsError, client_config::ClientConfig, group::Group};
+
+#[cfg(feature = "psk")]
+use crate::psk::PreSharedKey;
+
+#[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+use crate::group::secret_tree::SecretTree;
+
+use super::{epoch::EpochSecrets, key_schedule::KeySchedule};
+
+/// Membership status of the current member within a [`Group`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum MembershipStatus {
+    /// The current member is part of the group.
+    #[default]
+    Active = 1u8,
+    /// The current member was removed from the group by a commit. The group
+    /// can no longer be used to send messages.
+    Revoked = 2u8,
+}
+
+/// Handling of group secrets once the current member is removed from a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum RemovedSecretsPolicy {
+    /// Erase all secrets of the group. Further messages can't be processed
+    /// and secrets can't be exported.
+    #[default]
+    Erase,
+    /// Keep the secrets of the last epoch the member was part of, allowing
+    /// application messages from that epoch to be decrypted.
+    Retain,
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Membership status of the current member.
+    pub fn membership_status(&self) -> MembershipStatus {
+        self.membership_status
+    }
+
+    pub(crate) fn revoke_membership(&mut self) {
+        self.membership_status = MembershipStatus::Revoked;
+        self.pending_commit = None;
+
+        #[cfg(feature = "by_ref_proposal")]
+        {
+            self.pending_updates = Default::default();
+        }
+
+        if self.config.removed_secrets_policy() == RemovedSecretsPolicy::Erase {
+            self.erase_secrets();
+        }
+
+        #[cfg(feature = "group_events")]
+        if let Some(listener) = self.config.group_event_listener() {
+            listener.on_membership_revoked(self.group_id());
+        }
+    }
+
+    fn erase_secrets(&mut self) {
+        self.private_tree
+            .secret_keys
+            .iter_mut()
+            .for_each(|key| *key = None);
+
+        self.key_schedule = KeySchedule::default();
+
+        self.epoch_secrets = EpochSecrets {
+            #[cfg(feature = "psk")]
+            resumption_secret: PreSharedKey::new(Vec::new()),
+            sender_data_secret: Vec::new().into(),
+            #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+            secret_tree: SecretTree::empty(),
+        };
+
+        #[cfg(feature = "psk")]
+        {
+            self.previous_psk = None;
+        }
+
+        // Secrets of prior epochs are deleted from storage by the next call
+        // to `write_to_storage`.
+        #[cfg(feature = "prior_epoch")]
+        self.state_repo.purge_epochs();
+    }
+
+    pub(crate) fn check_can_send(&self) -> Result<(), MlsError> {
+        match self.membership_status {
+            MembershipStatus::Active => Ok(()),
+            MembershipStatus::Revoked => Err(MlsError::MembershipRevoked),
+        }
+    }
+
+    pub(crate) fn check_secrets_available(&self) -> Result<(), MlsError> {
+        let erased = self.membership_status == MembershipStatus::Revoked
+            && self.config.removed_secrets_policy() == RemovedSecretsPolicy::Erase;
+
+        if erased {
+            Err(MlsError::MembershipRevoked)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_codec::MlsDecode;
+    use mls_rs_core::{group::GroupStateStorage, time::MlsTime};
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_builder::test_utils::TestClientConfig,
+        client_config::ClientConfig,
+        group::{
+            snapshot::Snapshot,
+            test_utils::{test_group, TestGroup},
+            Group, ReceivedMessage,
+        },
+    };
+
+    use super::{MembershipStatus, RemovedSecretsPolicy};
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn remove_bob(policy: RemovedSecretsPolicy) -> (TestGroup, TestGroup) {
+        remove_bob_with_config(|c| c.0.settings.removed_secrets_policy = policy).await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn remove_bob_with_config<F>(config: F) -> (TestGroup, TestGroup)
+    where
+        F: FnMut(&mut TestClientConfig),
+    {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob, _) = alice
+            .join_with_custom_config("bob", false, config)
+            .await
+            .unwrap();
+
+        // Give Bob a prior epoch in storage.
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        alice.group.apply_pending_commit().await.unwrap();
+
+        bob.group.process_incoming_message(commit).await.unwrap();
+        bob.group.write_to_storage().await.unwrap();
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .remove_member(1)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let received = bob
+            .group
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedMessage::Commit(_));
+
+        (alice, bob)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_cannot_send() {
+        let (_, mut bob) = remove_bob(RemovedSecretsPolicy::Erase).await;
+
+        assert_eq!(bob.group.membership_status(), MembershipStatus::Revoked);
+
+        let res = bob.group.commit(vec![]).await;
+        assert_matches!(res, Err(MlsError::MembershipRevoked));
+
+        #[cfg(feature = "private_message")]
+        {
+            let res = bob.group.encrypt_application_message(b"hi", vec![]).await;
+            assert_matches!(res, Err(MlsError::MembershipRevoked));
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_secrets_are_erased() {
+        let (mut alice, mut bob) = remove_bob(RemovedSecretsPolicy::Erase).await;
+
+        let res = bob.group.export_secret(b"label", b"context", 32).await;
+        assert_matches!(res, Err(MlsError::MembershipRevoked));
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        let res = bob.group.process_incoming_message(commit).await;
+        assert_matches!(res, Err(MlsError::MembershipRevoked));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_can_retain_secrets() {
+        let (_, bob) = remove_bob(RemovedSecretsPolicy::Retain).await;
+
+        assert_eq!(bob.group.membership_status(), MembershipStatus::Revoked);
+
+        bob.group
+            .export_secret(b"label", b"context", 32)
+            .await
+            .unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn active_member_status() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        assert_eq!(group.group.membership_status(), MembershipStatus::Active);
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn reload(group: &Group<TestClientConfig>) -> Group<TestClientConfig> {
+        let state = group
+            .config
+            .group_state_storage()
+            .state(group.group_id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let snapshot = Snapshot::mls_decode(&mut &*state).unwrap();
+
+        Group::from_snapshot(group.config.clone(), snapshot)
+            .await
+            .unwrap()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_rejects_messages_with_time() {
+        let (mut alice, mut bob) = remove_bob(RemovedSecretsPolicy::Erase).await;
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+
+        let res = bob
+            .group
+            .process_incoming_message_with_time(commit, MlsTime::now())
+            .await;
+
+        assert_matches!(res, Err(MlsError::MembershipRevoked));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn revoked_status_is_persisted() {
+        for policy in [RemovedSecretsPolicy::Erase, RemovedSecretsPolicy::Retain] {
+            let (_, mut bob) = remove_bob(policy).await;
+
+            bob.group.write_to_storage().await.unwrap();
+            let restored = reload(&bob.group).await;
+
+            assert_eq!(restored.membership_status(), MembershipStatus::Revoked);
+        }
+    }
+
+    #[cfg(feature = "prior_epoch")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn erased_prior_epochs_are_deleted_from_storage() {
+        let (_, mut bob) = remove_bob(RemovedSecretsPolicy::Erase).await;
+        let storage = bob.group.config.group_state_storage();
+
+        let max_epoch_id = storage.max_epoch_id(bob.group.group_id()).await.unwrap();
+        assert!(max_epoch_id.is_some());
+
+        bob.group.write_to_storage().await.unwrap();
+
+        let max_epoch_id = storage.max_epoch_id(bob.group.group_id()).await.unwrap();
+        assert_eq!(max_epoch_id, None);
+    }
+
+    #[cfg(feature = "group_events")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn event_listener_is_notified_of_revocation() {
+        use crate::group::event_listener::SharedGroupEventListener;
+        use crate::group::GroupEventListener;
+        use alloc::vec::Vec;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct RevocationListener(Mutex<Vec<Vec<u8>>>);
+
+        impl GroupEventListener for RevocationListener {
+            fn on_membership_revoked(&self, group_id: &[u8]) {
+                self.0.lock().unwrap().push(group_id.to_vec());
+            }
+        }
+
+        let listener = Arc::new(RevocationListener::default());
+        let shared = SharedGroupEventListener(listener.clone());
+
+        let (_, bob) =
+            remove_bob_with_config(|c| c.0.settings.group_event_listener = Some(shared.clone()))
+                .await;
+
+        let revoked = listener.0.lock().unwrap().clone();
+
+        assert_eq!(revoked, vec![bob.group.group_id().to_vec()]);
+    }
+}
@@ -4,7 +4,7 @@
 
 use super::*;
 
-pub use mls_rs_core::group::Member;
+pub use mls_rs_core::group::{Member, MemberFingerprint};
 
 #[cfg(feature = "state_update")]
 pub(crate) fn member_from_key_package(key_package: &KeyPackage, index: LeafIndex) -> Member {
@@ -82,10 +82,82 @@ impl<'a> Roster<'a> {
             .non_empty_leaves()
             .map(|(_, node)| &node.signing_identity)
     }
+
+    /// Find the member with the given [`MemberFingerprint`] computed using
+    /// `cipher_suite_provider`, see [`Member::fingerprint`].
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn find_by_fingerprint<P: CipherSuiteProvider>(
+        &self,
+        fingerprint: &MemberFingerprint,
+        cipher_suite_provider: &P,
+    ) -> Result<Option<Member>, MlsError> {
+        for member in self.members_iter() {
+            let member_fingerprint = member
+                .fingerprint(cipher_suite_provider)
+                .await
+                .map_err(MlsError::CryptoProviderError)?;
+
+            if &member_fingerprint == fingerprint {
+                return Ok(Some(member));
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl TreeKemPublic {
-    pub(crate) fn roster(&self) -> Roster {
+    pub(crate) fn roster(&self) -> Roster<'_> {
         Roster { public_tree: self }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        crypto::test_utils::test_cipher_suite_provider,
+        group::test_utils::test_group,
+    };
+
+    use super::MemberFingerprint;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_can_be_found_by_fingerprint() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (bob, _) = alice.join("bob").await;
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let bob_member = alice.group.roster().member_with_index(1).unwrap();
+        let fingerprint = bob_member.fingerprint(&cs).await.unwrap();
+
+        let bob_view = bob.group.roster().member_with_index(1).unwrap();
+        let bob_view_fingerprint = bob_view.fingerprint(&cs).await.unwrap();
+        assert_eq!(bob_view_fingerprint, fingerprint);
+
+        let alice_member = alice.group.roster().member_with_index(0).unwrap();
+        let alice_fingerprint = alice_member.fingerprint(&cs).await.unwrap();
+        assert_ne!(alice_fingerprint, fingerprint);
+
+        let found = alice
+            .group
+            .roster()
+            .find_by_fingerprint(&fingerprint, &cs)
+            .await
+            .unwrap();
+
+        assert_eq!(found, Some(bob_member));
+
+        let unknown = MemberFingerprint::from(vec![0; fingerprint.len()]);
+
+        let found = alice
+            .group
+            .roster()
+            .find_by_fingerprint(&unknown, &cs)
+            .await
+            .unwrap();
+
+        assert_eq!(found, None);
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsEncode, MlsSize};
+use mls_rs_core::{crypto::CipherSuiteProvider, error::IntoAnyError};
+
+use crate::{
+    client::MlsError,
+    group::{
+        framing::{Content, FramedContent},
+        GroupContext,
+    },
+};
+
+#[derive(MlsSize, MlsEncode)]
+struct RosterBindingInput<'a> {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    label: &'a [u8],
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    group_id: &'a [u8],
+    epoch: u64,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    tree_hash: &'a [u8],
+}
+
+// The tree hash commits to the leaves of all members, so the binding changes
+// with the roster even if the epoch number is the same.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn roster_binding<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    context: &GroupContext,
+) -> Result<Vec<u8>, MlsError> {
+    let input = RosterBindingInput {
+        label: b"MLS 1.0 roster binding",
+        group_id: &context.group_id,
+        epoch: context.epoch,
+        tree_hash: &context.tree_hash,
+    };
+
+    cipher_suite_provider
+        .hash(&input.mls_encode_to_vec()?)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+}
+
+/// Prefix `authenticated_data` with the roster binding of `context`.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn bind_roster<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    context: &GroupContext,
+    authenticated_data: Vec<u8>,
+) -> Result<Vec<u8>, MlsError> {
+    let mut bound = roster_binding(cipher_suite_provider, context).await?;
+    bound.extend(authenticated_data);
+    Ok(bound)
+}
+
+/// Verify and remove the roster binding of a received application message
+/// decrypted in the epoch described by `context`. Other content is left
+/// unchanged.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn verify_roster_binding<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    context: &GroupContext,
+    content: &mut FramedContent,
+) -> Result<(), MlsError> {
+    if !matches!(content.content, Content::Application(_)) {
+        return Ok(());
+    }
+
+    let binding = roster_binding(cipher_suite_provider, context).await?;
+
+    if !content.authenticated_data.starts_with(&binding) {
+        return Err(MlsError::RosterBindingMismatch);
+    }
+
+    content.authenticated_data.drain(..binding.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::{
+            test_utils::{test_n_member_group, TestGroup},
+            ReceivedMessage,
+        },
+    };
+
+    fn bind_roster(group: &mut TestGroup, enabled: bool) {
+        group
+            .group
+            .config
+            .0
+            .mls_rules
+            .encryption_options
+            .bind_roster = enabled;
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn roster_binding_is_verified_and_removed() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        bind_roster(&mut groups[0], true);
+        bind_roster(&mut groups[1], true);
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hello", b"aad".to_vec())
+            .await
+            .unwrap();
+
+        let received = groups[1].process_message(message).await.unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedMessage::ApplicationMessage(m) if m.authenticated_data == b"aad"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn unbound_message_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        bind_roster(&mut groups[0], false);
+        bind_roster(&mut groups[1], true);
+
+        let message = groups[0]
+            .group
+            .encrypt_application_message(b"hello", vec![0; 64])
+            .await
+            .unwrap();
+
+        let res = groups[1].process_message(message).await;
+
+        assert_matches!(res, Err(MlsError::RosterBindingMismatch));
+    }
+}
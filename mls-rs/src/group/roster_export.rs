@@ -0,0 +1,319 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+use mls_rs_core::identity::SigningIdentity;
+
+use crate::{client::MlsError, group::Roster};
+
+#[cfg(feature = "x509")]
+use mls_rs_core::error::IntoAnyError;
+
+#[cfg(feature = "x509")]
+use crate::identity::x509::{SubjectComponent, X509CertificateReader};
+
+/// Output format of [`Roster::export`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RosterExportFormat {
+    /// A JSON array containing one object per member.
+    Json,
+    /// RFC 4180 CSV with a header row followed by one row per member.
+    Csv,
+}
+
+/// Mapping from a member's [`SigningIdentity`] to human readable fields used
+/// by [`Roster::export`].
+pub trait IdentityRenderer {
+    /// Names of the fields returned by [`IdentityRenderer::render`].
+    fn field_names(&self) -> Vec<String>;
+
+    /// Values of the fields named by [`IdentityRenderer::field_names`], in the
+    /// same order.
+    fn render(&self, identity: &SigningIdentity) -> Result<Vec<String>, MlsError>;
+}
+
+/// Renderer displaying the identifier of a basic credential as a UTF-8 `name`.
+///
+/// Members with any other credential type have an empty name.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BasicIdentityRenderer;
+
+impl IdentityRenderer for BasicIdentityRenderer {
+    fn field_names(&self) -> Vec<String> {
+        vec!["name".to_string()]
+    }
+
+    fn render(&self, identity: &SigningIdentity) -> Result<Vec<String>, MlsError> {
+        let name = identity
+            .credential
+            .as_basic()
+            .map(|basic| String::from_utf8_lossy(&basic.identifier).into_owned())
+            .unwrap_or_default();
+
+        Ok(vec![name])
+    }
+}
+
+/// Renderer displaying the common name of the leaf certificate of an X.509
+/// credential as `name`.
+///
+/// Basic credentials are rendered as by [`BasicIdentityRenderer`].
+#[cfg(feature = "x509")]
+#[derive(Clone, Debug)]
+pub struct X509IdentityRenderer<R: X509CertificateReader> {
+    reader: R,
+}
+
+#[cfg(feature = "x509")]
+impl<R: X509CertificateReader> X509IdentityRenderer<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+#[cfg(feature = "x509")]
+impl<R: X509CertificateReader> IdentityRenderer for X509IdentityRenderer<R> {
+    fn field_names(&self) -> Vec<String> {
+        BasicIdentityRenderer.field_names()
+    }
+
+    fn render(&self, identity: &SigningIdentity) -> Result<Vec<String>, MlsError> {
+        let Some(leaf) = identity.credential.as_x509().and_then(|chain| chain.leaf()) else {
+            return BasicIdentityRenderer.render(identity);
+        };
+
+        let name = self
+            .reader
+            .subject_components(leaf)
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?
+            .into_iter()
+            .find_map(|component| match component {
+                SubjectComponent::CommonName(name) => Some(name),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        Ok(vec![name])
+    }
+}
+
+impl Roster<'_> {
+    /// Export the current members in `format`, using `renderer` to display
+    /// their identities.
+    ///
+    /// Every entry contains the member's `index` and `credential_type`
+    /// followed by the fields produced by `renderer`.
+    pub fn export<R: IdentityRenderer + ?Sized>(
+        &self,
+        format: RosterExportFormat,
+        renderer: &R,
+    ) -> Result<String, MlsError> {
+        let mut names = vec!["index".to_string(), "credential_type".to_string()];
+        names.extend(renderer.field_names());
+
+        let rows = self
+            .public_tree
+            .non_empty_leaves()
+            .map(|(index, leaf)| {
+                let identity = &leaf.signing_identity;
+                let credential_type = identity.credential.credential_type().raw_value();
+
+                let mut row = vec![index.0.to_string(), credential_type.to_string()];
+                row.extend(renderer.render(identity)?);
+
+                if row.len() != names.len() {
+                    return Err(MlsError::InvalidRosterExportFields);
+                }
+
+                Ok(row)
+            })
+            .collect::<Result<Vec<_>, MlsError>>()?;
+
+        Ok(match format {
+            RosterExportFormat::Json => export_json(&names, &rows),
+            RosterExportFormat::Csv => export_csv(&names, &rows),
+        })
+    }
+}
+
+fn export_json(names: &[String], rows: &[Vec<String>]) -> String {
+    let objects = rows
+        .iter()
+        .map(|row| {
+            let fields = names
+                .iter()
+                .zip(row)
+                .enumerate()
+                .map(|(i, (name, value))| {
+                    // The index and credential type are numbers.
+                    if i < 2 {
+                        format!("{}:{value}", json_string(name))
+                    } else {
+                        format!("{}:{}", json_string(name), json_string(value))
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", objects.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn export_csv(names: &[String], rows: &[Vec<String>]) -> String {
+    core::iter::once(names)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|record| {
+            let fields = record.iter().map(|f| csv_field(f)).collect::<Vec<_>>();
+            format!("{}\r\n", fields.join(","))
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(['"', ',', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use assert_matches::assert_matches;
+    use mls_rs_core::identity::SigningIdentity;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_group,
+    };
+
+    use super::{BasicIdentityRenderer, IdentityRenderer, RosterExportFormat};
+
+    struct QuotingRenderer;
+
+    impl IdentityRenderer for QuotingRenderer {
+        fn field_names(&self) -> Vec<String> {
+            vec!["display".to_string()]
+        }
+
+        fn render(&self, identity: &SigningIdentity) -> Result<Vec<String>, MlsError> {
+            let name = BasicIdentityRenderer.render(identity)?.remove(0);
+            Ok(vec![format!("\"{name}\", admin\n")])
+        }
+    }
+
+    struct MissingFieldRenderer;
+
+    impl IdentityRenderer for MissingFieldRenderer {
+        fn field_names(&self) -> Vec<String> {
+            vec!["name".to_string()]
+        }
+
+        fn render(&self, _: &SigningIdentity) -> Result<Vec<String>, MlsError> {
+            Ok(vec![])
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn roster_can_be_exported() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        alice.join("bob").await;
+
+        let roster = alice.group.roster();
+
+        let json = roster
+            .export(RosterExportFormat::Json, &BasicIdentityRenderer)
+            .unwrap();
+
+        assert_eq!(
+            json,
+            r#"[{"index":0,"credential_type":1,"name":"member"},{"index":1,"credential_type":1,"name":"bob"}]"#
+        );
+
+        let csv = roster
+            .export(RosterExportFormat::Csv, &BasicIdentityRenderer)
+            .unwrap();
+
+        assert_eq!(
+            csv,
+            "index,credential_type,name\r\n0,1,member\r\n1,1,bob\r\n"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn rendered_fields_are_escaped() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let roster = alice.group.roster();
+
+        let json = roster
+            .export(RosterExportFormat::Json, &QuotingRenderer)
+            .unwrap();
+
+        assert_eq!(
+            json,
+            r#"[{"index":0,"credential_type":1,"display":"\"member\", admin\n"}]"#
+        );
+
+        let csv = roster
+            .export(RosterExportFormat::Csv, &QuotingRenderer)
+            .unwrap();
+
+        assert_eq!(
+            csv,
+            "index,credential_type,display\r\n0,1,\"\"\"member\"\", admin\n\"\r\n"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn renderer_must_produce_all_fields() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let res = alice
+            .group
+            .roster()
+            .export(RosterExportFormat::Csv, &MissingFieldRenderer);
+
+        assert_matches!(res, Err(MlsError::InvalidRosterExportFields));
+    }
+}
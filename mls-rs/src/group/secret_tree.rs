@@ -8,7 +8,9 @@ use core::{
     ops::{Deref, DerefMut},
 };
 
-use zeroize::Zeroizing;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::crypto::secret_material::SecretMaterial;
 
 use crate::{client::MlsError, map::LargeMap, tree_kem::math::TreeIndex, CipherSuiteProvider};
 
@@ -17,6 +19,7 @@ use mls_rs_core::error::IntoAnyError;
 
 use super::key_schedule::kdf_expand_with_label;
 
+/// Default for [`EncryptionOptions::max_out_of_order_generations`](crate::mls_rules::EncryptionOptions::max_out_of_order_generations).
 pub(crate) const MAX_RATCHET_BACK_HISTORY: u32 = 1024;
 
 #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
@@ -45,6 +48,8 @@ struct TreeSecret(
     Zeroizing<Vec<u8>>,
 );
 
+impl ZeroizeOnDrop for TreeSecret {}
+
 impl Debug for TreeSecret {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         mls_rs_core::debug::pretty_bytes(&self.0)
@@ -108,6 +113,23 @@ pub struct SecretTree<T: TreeIndex> {
     leaf_count: T,
 }
 
+// Every node holds its secrets in `Zeroizing` buffers.
+impl<T: TreeIndex> ZeroizeOnDrop for SecretTree<T> {}
+
+impl<T: TreeIndex> Zeroize for SecretTree<T> {
+    fn zeroize(&mut self) {
+        // Dropping the nodes zeroizes them.
+        self.known_secrets.inner.clear();
+    }
+}
+
+impl<T: TreeIndex> SecretMaterial for SecretTree<T> {
+    #[cfg(any(test, feature = "zeroize_audit"))]
+    fn is_wiped(&self) -> bool {
+        self.known_secrets.inner.is_empty()
+    }
+}
+
 impl<T: TreeIndex> SecretTree<T> {
     pub(crate) fn empty() -> SecretTree<T> {
         SecretTree {
@@ -131,16 +153,17 @@ impl SecretRatchets {
         cipher_suite_provider: &P,
         generation: u32,
         key_type: KeyType,
+        max_out_of_order: u32,
     ) -> Result<MessageKeyData, MlsError> {
         match key_type {
             KeyType::Handshake => {
                 self.handshake
-                    .get_message_key(cipher_suite_provider, generation)
+                    .get_message_key(cipher_suite_provider, generation, max_out_of_order)
                     .await
             }
             KeyType::Application => {
                 self.application
-                    .get_message_key(cipher_suite_provider, generation)
+                    .get_message_key(cipher_suite_provider, generation, max_out_of_order)
                     .await
             }
         }
@@ -157,6 +180,20 @@ impl SecretRatchets {
             KeyType::Application => self.application.next_message_key(cipher_suite).await,
         }
     }
+
+    fn ratchet(&self, key_type: KeyType) -> &SecretKeyRatchet {
+        match key_type {
+            KeyType::Handshake => &self.handshake,
+            KeyType::Application => &self.application,
+        }
+    }
+
+    fn ratchet_mut(&mut self, key_type: KeyType) -> &mut SecretKeyRatchet {
+        match key_type {
+            KeyType::Handshake => &mut self.handshake,
+            KeyType::Application => &mut self.application,
+        }
+    }
 }
 
 impl<T: TreeIndex> SecretTree<T> {
@@ -257,17 +294,54 @@ impl<T: TreeIndex> SecretTree<T> {
         leaf_index: T,
         key_type: KeyType,
         generation: u32,
+        max_out_of_order: u32,
     ) -> Result<MessageKeyData, MlsError> {
         let mut ratchet = self.take_leaf_ratchet(cipher_suite, &leaf_index).await?;
 
         let res = ratchet
-            .message_key_generation(cipher_suite, generation, key_type)
-            .await?;
+            .message_key_generation(cipher_suite, generation, key_type, max_out_of_order)
+            .await;
 
+        // The ratchet is restored even if the generation is rejected, so
+        // that later messages of the sender can still be decrypted.
         self.known_secrets
             .set_node(leaf_index, SecretTreeNode::Ratchet(ratchet));
 
-        Ok(res)
+        res
+    }
+
+    /// Generation of the next key of the `key_type` ratchet of
+    /// `leaf_index`, without deriving any secrets.
+    pub fn current_generation(&self, leaf_index: &T, key_type: KeyType) -> u32 {
+        match self.known_secrets.inner.get(leaf_index) {
+            Some(SecretTreeNode::Ratchet(ratchet)) => ratchet.ratchet(key_type).generation,
+            _ => 0,
+        }
+    }
+
+    /// Advance the `key_type` ratchet of `leaf_index` so that its next key
+    /// has generation `generation`. Keys of skipped generations are retained
+    /// as for out of order messages.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn skip_to_generation<P: CipherSuiteProvider>(
+        &mut self,
+        cipher_suite: &P,
+        leaf_index: T,
+        key_type: KeyType,
+        generation: u32,
+        max_out_of_order: u32,
+    ) -> Result<(), MlsError> {
+        let mut ratchet = self.take_leaf_ratchet(cipher_suite, &leaf_index).await?;
+
+        let res = ratchet
+            .ratchet_mut(key_type)
+            .skip_to(cipher_suite, generation, max_out_of_order)
+            .await;
+
+        self.known_secrets
+            .set_node(leaf_index, SecretTreeNode::Ratchet(ratchet));
+
+        res
     }
 }
 
@@ -294,6 +368,8 @@ pub struct MessageKeyData {
     pub(crate) generation: u32,
 }
 
+impl ZeroizeOnDrop for MessageKeyData {}
+
 impl Debug for MessageKeyData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MessageKeyData")
@@ -412,6 +488,7 @@ impl SecretKeyRatchet {
         &mut self,
         cipher_suite_provider: &P,
         generation: u32,
+        max_out_of_order: u32,
     ) -> Result<MessageKeyData, MlsError> {
         #[cfg(feature = "out_of_order")]
         if generation < self.generation {
@@ -427,7 +504,7 @@ impl SecretKeyRatchet {
             return Err(MlsError::KeyMissing(generation));
         }
 
-        let max_generation_allowed = self.generation + MAX_RATCHET_BACK_HISTORY;
+        let max_generation_allowed = self.generation.saturating_add(max_out_of_order);
 
         if generation > max_generation_allowed {
             return Err(MlsError::InvalidFutureGeneration(generation));
@@ -444,7 +521,40 @@ impl SecretKeyRatchet {
             self.history.insert(key_data.generation, key_data);
         }
 
-        self.next_message_key(cipher_suite_provider).await
+        let key = self.next_message_key(cipher_suite_provider).await?;
+
+        // Discard skipped keys that fell out of the out of order window.
+        #[cfg(feature = "out_of_order")]
+        self.history
+            .retain(|&skipped, _| skipped.saturating_add(max_out_of_order) >= generation);
+
+        Ok(key)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn skip_to<P: CipherSuiteProvider>(
+        &mut self,
+        cipher_suite_provider: &P,
+        generation: u32,
+        max_out_of_order: u32,
+    ) -> Result<(), MlsError> {
+        while self.generation < generation {
+            let key_data = self.next_message_key(cipher_suite_provider).await?;
+
+            #[cfg(feature = "out_of_order")]
+            if key_data.generation.saturating_add(max_out_of_order) >= generation {
+                self.history.insert(key_data.generation, key_data);
+            }
+
+            #[cfg(not(feature = "out_of_order"))]
+            let _ = (key_data, max_out_of_order);
+        }
+
+        #[cfg(feature = "out_of_order")]
+        self.history
+            .retain(|&skipped, _| skipped.saturating_add(max_out_of_order) >= generation);
+
+        Ok(())
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -699,12 +809,18 @@ mod tests {
             let clone_2 = ratchet_clone.next_message_key(&provider).await.unwrap();
 
             // Going back in time should result in an error
-            let res = ratchet_clone.get_message_key(&provider, 0).await;
+            let res = ratchet_clone
+                .get_message_key(&provider, 0, MAX_RATCHET_BACK_HISTORY)
+                .await;
             assert!(res.is_err());
 
             // Calling get key should be the same as calling next until hitting the desired generation
             let second_key = ratchet
-                .get_message_key(&provider, ratchet_clone.generation - 1)
+                .get_message_key(
+                    &provider,
+                    ratchet_clone.generation - 1,
+                    MAX_RATCHET_BACK_HISTORY,
+                )
                 .await
                 .unwrap();
 
@@ -747,12 +863,21 @@ mod tests {
         let mut ordered_keys = Vec::<MessageKeyData>::new();
 
         for i in 0..=MAX_RATCHET_BACK_HISTORY {
-            ordered_keys.push(ratchet.get_message_key(&provider, i).await.unwrap());
+            ordered_keys.push(
+                ratchet
+                    .get_message_key(&provider, i, MAX_RATCHET_BACK_HISTORY)
+                    .await
+                    .unwrap(),
+            );
         }
 
         // Ask for a key at index MAX_RATCHET_BACK_HISTORY in the clone
         let last_key = ratchet_clone
-            .get_message_key(&provider, MAX_RATCHET_BACK_HISTORY)
+            .get_message_key(
+                &provider,
+                MAX_RATCHET_BACK_HISTORY,
+                MAX_RATCHET_BACK_HISTORY,
+            )
             .await
             .unwrap();
 
@@ -762,7 +887,12 @@ mod tests {
         let mut back_history_keys = Vec::<MessageKeyData>::new();
 
         for i in 0..MAX_RATCHET_BACK_HISTORY - 1 {
-            back_history_keys.push(ratchet_clone.get_message_key(&provider, i).await.unwrap());
+            back_history_keys.push(
+                ratchet_clone
+                    .get_message_key(&provider, i, MAX_RATCHET_BACK_HISTORY)
+                    .await
+                    .unwrap(),
+            );
         }
 
         assert_eq!(
@@ -771,6 +901,33 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "out_of_order")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn skipped_keys_outside_window_are_discarded() {
+        let provider = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let mut ratchet = SecretKeyRatchet::new(&provider, &[0u8; 32], KeyType::Handshake)
+            .await
+            .unwrap();
+
+        let res = ratchet.get_message_key(&provider, 5, 4).await;
+        assert_matches!(res, Err(MlsError::InvalidFutureGeneration(5)));
+
+        ratchet.get_message_key(&provider, 4, 4).await.unwrap();
+        ratchet.get_message_key(&provider, 8, 4).await.unwrap();
+
+        // Generations 0 to 3 are more than 4 generations behind generation 8.
+        let res = ratchet.get_message_key(&provider, 3, 4).await;
+        assert_matches!(res, Err(MlsError::KeyMissing(3)));
+
+        for generation in 5..8 {
+            ratchet
+                .get_message_key(&provider, generation, 4)
+                .await
+                .unwrap();
+        }
+    }
+
     #[cfg(not(feature = "out_of_order"))]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn out_of_order_keys_should_throw_error() {
@@ -781,8 +938,13 @@ mod tests {
             .await
             .unwrap();
 
-        ratchet.get_message_key(&provider, 10).await.unwrap();
-        let res = ratchet.get_message_key(&provider, 9).await;
+        ratchet
+            .get_message_key(&provider, 10, MAX_RATCHET_BACK_HISTORY)
+            .await
+            .unwrap();
+        let res = ratchet
+            .get_message_key(&provider, 9, MAX_RATCHET_BACK_HISTORY)
+            .await;
         assert_matches!(res, Err(MlsError::KeyMissing(9)))
     }
 
@@ -796,7 +958,11 @@ mod tests {
             .unwrap();
 
         let res = ratchet
-            .get_message_key(&provider, MAX_RATCHET_BACK_HISTORY + 1)
+            .get_message_key(
+                &provider,
+                MAX_RATCHET_BACK_HISTORY + 1,
+                MAX_RATCHET_BACK_HISTORY,
+            )
             .await;
 
         let invalid_generation = MAX_RATCHET_BACK_HISTORY + 1;
@@ -926,7 +1092,7 @@ mod interop_tests {
         group::{ciphertext_processor::InteropSenderData, secret_tree::KeyType},
     };
 
-    use super::SecretTree;
+    use super::{SecretTree, MAX_RATCHET_BACK_HISTORY};
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn interop_test_vector() {
@@ -953,6 +1119,7 @@ mod interop_tests {
                             (index as u32) * 2,
                             KeyType::Application,
                             leaf.generation,
+                            MAX_RATCHET_BACK_HISTORY,
                         )
                         .await
                         .unwrap();
@@ -966,6 +1133,7 @@ mod interop_tests {
                             (index as u32) * 2,
                             KeyType::Handshake,
                             leaf.generation,
+                            MAX_RATCHET_BACK_HISTORY,
                         )
                         .await
                         .unwrap();
@@ -1028,11 +1196,23 @@ mod interop_tests {
                                 let index = leaf * 2u32;
 
                                 let handshake_key = tree
-                                    .message_key_generation(&cs, index, KeyType::Handshake, gen)
+                                    .message_key_generation(
+                                        &cs,
+                                        index,
+                                        KeyType::Handshake,
+                                        gen,
+                                        MAX_RATCHET_BACK_HISTORY,
+                                    )
                                     .unwrap();
 
                                 let app_key = tree
-                                    .message_key_generation(&cs, index, KeyType::Application, gen)
+                                    .message_key_generation(
+                                        &cs,
+                                        index,
+                                        KeyType::Application,
+                                        gen,
+                                        MAX_RATCHET_BACK_HISTORY,
+                                    )
                                     .unwrap();
 
                                 InteropLeaf {
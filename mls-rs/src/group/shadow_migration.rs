@@ -0,0 +1,483 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{
+    crypto::{CipherSuite, CipherSuiteProvider, SignatureSecretKey},
+    error::IntoAnyError,
+    extension::ExtensionList,
+    identity::{IdentityProvider, SigningIdentity},
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::ShadowMigrationExt,
+    group::{cipher_suite_provider, framing::MlsMessagePayload, Group},
+    MlsMessage,
+};
+
+use super::{
+    resumption::{resumption_create_group, ResumptionGroupParameters},
+    ExportedTree, NewMemberInfo, ResumptionPSKUsage,
+};
+
+/// Differences between the membership of a group and the membership of a
+/// shadow group created with [`Group::create_shadow_group`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShadowMembershipDiff {
+    /// Identities of members of the group that are not members of the
+    /// shadow group and should be added to it.
+    pub missing: Vec<Vec<u8>>,
+    /// Indexes of members of the shadow group that are no longer members of
+    /// the group and should be removed from it.
+    pub extra: Vec<u32>,
+}
+
+impl ShadowMembershipDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn identity<C: IdentityProvider>(
+    identity_provider: &C,
+    signing_identity: &SigningIdentity,
+    extensions: &ExtensionList,
+) -> Result<Vec<u8>, MlsError> {
+    identity_provider
+        .identity(signing_identity, extensions)
+        .await
+        .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create a shadow group that takes over from this group at
+    /// `cutover_epoch`, e.g. to move the group to a new cipher suite or new
+    /// group context extensions.
+    ///
+    /// `new_key_packages` must contain exactly one key package for every
+    /// other member of this group, identified by the
+    /// [`IdentityProvider`](crate::IdentityProvider) in use. The new group is
+    /// keyed with the resumption secret of the current epoch of this group
+    /// and records the migration in a [`ShadowMigrationExt`]. The signing
+    /// identity of this member is reused unless `new_signing_identity` is
+    /// provided, which is required if the cipher suite changes.
+    ///
+    /// Both groups are expected to run side by side until this group reaches
+    /// `cutover_epoch`, as reported by [`Group::migration_cutover_reached`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn create_shadow_group(
+        &self,
+        cutover_epoch: u64,
+        cipher_suite: CipherSuite,
+        extensions: ExtensionList,
+        new_signing_identity: Option<(SigningIdentity, SignatureSecretKey)>,
+        new_key_packages: Vec<MlsMessage>,
+    ) -> Result<(Group<C>, Vec<MlsMessage>), MlsError> {
+        if cutover_epoch <= self.current_epoch() {
+            return Err(MlsError::InvalidCutoverEpoch(cutover_epoch));
+        }
+
+        self.check_shadow_membership(&new_key_packages).await?;
+
+        let mut extensions = extensions;
+
+        extensions.set_from(ShadowMigrationExt {
+            source_group_id: self.group_id().to_vec(),
+            source_epoch: self.current_epoch(),
+            cutover_epoch,
+        })?;
+
+        let cipher_suite_provider =
+            cipher_suite_provider(self.config.crypto_provider(), cipher_suite)?;
+
+        let group_id = cipher_suite_provider
+            .random_bytes_vec(cipher_suite_provider.kdf_extract_size())
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        let (signing_identity, signer) = match new_signing_identity {
+            Some(new_signing_identity) => new_signing_identity,
+            None => (
+                self.current_member_signing_identity()?.clone(),
+                self.signer.clone(),
+            ),
+        };
+
+        let new_group_params = ResumptionGroupParameters {
+            group_id: &group_id,
+            cipher_suite,
+            version: self.protocol_version(),
+            extensions: &extensions,
+        };
+
+        resumption_create_group(
+            self.config.clone(),
+            new_key_packages,
+            &new_group_params,
+            signing_identity,
+            signer,
+            self.resumption_psk_input_for(ResumptionPSKUsage::Branch, &cipher_suite_provider)?,
+        )
+        .await
+    }
+
+    /// Join a shadow group of this group created by
+    /// [`Group::create_shadow_group`].
+    ///
+    /// `new_signer` must be provided if the key package used to join was
+    /// generated with a different signing identity than the one used in this
+    /// group.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn join_shadow_group(
+        &self,
+        welcome: &MlsMessage,
+        tree_data: Option<ExportedTree<'_>>,
+        new_signer: Option<SignatureSecretKey>,
+    ) -> Result<(Group<C>, NewMemberInfo), MlsError> {
+        let (group, new_member_info) = Group::<C>::from_welcome_message(
+            welcome,
+            tree_data,
+            #[cfg(feature = "tree_fetcher")]
+            None::<&crate::group::tree_fetcher::NoTreeFetcher>,
+            self.config.clone(),
+            new_signer.unwrap_or_else(|| self.signer.clone()),
+            Some(self.resumption_psk_input(ResumptionPSKUsage::Branch)?),
+            #[cfg(feature = "welcome_retargeting")]
+            false,
+        )
+        .await?;
+
+        let migration = group
+            .context()
+            .extensions
+            .get_as::<ShadowMigrationExt>()?
+            .ok_or(MlsError::NotShadowGroup)?;
+
+        if migration.source_group_id != self.group_id()
+            || migration.source_epoch != self.current_epoch()
+        {
+            return Err(MlsError::NotShadowGroup);
+        }
+
+        Ok((group, new_member_info))
+    }
+
+    /// Compare the membership of this group with the membership of its
+    /// `shadow` group.
+    ///
+    /// Members added to or removed from this group after the shadow group
+    /// was created show up in the result and should be mirrored in the
+    /// shadow group before the cutover.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn shadow_membership_diff(
+        &self,
+        shadow: &Group<C>,
+    ) -> Result<ShadowMembershipDiff, MlsError> {
+        self.shadow_migration(shadow)?;
+
+        let mut missing = self.member_identities().await?;
+        let mut extra = Vec::new();
+
+        for member in shadow.roster().members_iter() {
+            let identity = identity(
+                &shadow.config.identity_provider(),
+                &member.signing_identity,
+                &shadow.context().extensions,
+            )
+            .await?;
+
+            match missing.iter().position(|id| *id == identity) {
+                Some(pos) => {
+                    missing.swap_remove(pos);
+                }
+                None => extra.push(member.index),
+            }
+        }
+
+        Ok(ShadowMembershipDiff { missing, extra })
+    }
+
+    /// Returns `true` once this group has reached the cutover epoch recorded
+    /// in its `shadow` group, at which point applications should stop using
+    /// this group and switch to the shadow group.
+    pub fn migration_cutover_reached(&self, shadow: &Group<C>) -> Result<bool, MlsError> {
+        Ok(self.current_epoch() >= self.shadow_migration(shadow)?.cutover_epoch)
+    }
+
+    fn shadow_migration(&self, shadow: &Group<C>) -> Result<ShadowMigrationExt, MlsError> {
+        shadow
+            .context()
+            .extensions
+            .get_as::<ShadowMigrationExt>()?
+            .filter(|migration| migration.source_group_id == self.group_id())
+            .ok_or(MlsError::NotShadowGroup)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn member_identities(&self) -> Result<Vec<Vec<u8>>, MlsError> {
+        let mut identities = Vec::new();
+
+        for member in self.roster().members_iter() {
+            identities.push(
+                identity(
+                    &self.config.identity_provider(),
+                    &member.signing_identity,
+                    &self.context().extensions,
+                )
+                .await?,
+            );
+        }
+
+        Ok(identities)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn check_shadow_membership(&self, key_packages: &[MlsMessage]) -> Result<(), MlsError> {
+        let mut expected = self.member_identities().await?;
+
+        let own = identity(
+            &self.config.identity_provider(),
+            self.current_member_signing_identity()?,
+            &self.context().extensions,
+        )
+        .await?;
+
+        expected.retain(|id| *id != own);
+
+        let mut actual = Vec::with_capacity(key_packages.len());
+
+        for key_package in key_packages {
+            let MlsMessagePayload::KeyPackage(key_package) = &key_package.payload else {
+                return Err(MlsError::UnexpectedMessageType);
+            };
+
+            actual.push(
+                identity(
+                    &self.config.identity_provider(),
+                    &key_package.leaf_node.signing_identity,
+                    &self.context().extensions,
+                )
+                .await?,
+            );
+        }
+
+        expected.sort_unstable();
+        actual.sort_unstable();
+
+        (expected == actual)
+            .then_some(())
+            .ok_or(MlsError::ShadowMembershipMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::extension::{ExtensionList, ExtensionType};
+
+    use crate::{
+        client::{
+            test_utils::{TestClientBuilder, TestClientConfig, TEST_CIPHER_SUITE},
+            MlsError,
+        },
+        extension::{ShadowMigrationExt, SHADOW_MIGRATION_EXTENSION_TYPE},
+        group::Group,
+        identity::test_utils::get_test_signing_identity,
+        Client,
+    };
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_client(name: &str) -> Client<TestClientConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        TestClientBuilder::new_for_test()
+            .extension_type(ExtensionType::new(SHADOW_MIGRATION_EXTENSION_TYPE))
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    // Alice creates a group and adds Bob and Carol in this order.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn test_groups() -> (Vec<Client<TestClientConfig>>, Vec<Group<TestClientConfig>>) {
+        let alice = test_client("alice").await;
+        let alice_group = alice.create_group(Default::default()).await.unwrap();
+
+        let mut clients = vec![alice];
+        let mut groups = vec![alice_group];
+
+        for name in ["bob", "carol"] {
+            let client = test_client(name).await;
+            let key_package = client.generate_key_package_message().await.unwrap();
+
+            let commit = groups[0]
+                .commit_builder()
+                .add_member(key_package)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+
+            groups[0].apply_pending_commit().await.unwrap();
+
+            for group in groups[1..].iter_mut() {
+                group
+                    .process_incoming_message(commit.commit_message.clone())
+                    .await
+                    .unwrap();
+            }
+
+            let (group, _) = client
+                .join_group(None, &commit.welcome_messages[0])
+                .await
+                .unwrap();
+
+            clients.push(client);
+            groups.push(group);
+        }
+
+        (clients, groups)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_can_join_shadow_group_and_cut_over() {
+        let (clients, mut groups) = test_groups().await;
+
+        let mut key_packages = Vec::new();
+
+        for client in &clients[1..] {
+            key_packages.push(client.generate_key_package_message().await.unwrap());
+        }
+
+        let cutover_epoch = groups[0].current_epoch() + 1;
+
+        let (alice_shadow, welcomes) = groups[0]
+            .create_shadow_group(
+                cutover_epoch,
+                TEST_CIPHER_SUITE,
+                ExtensionList::new(),
+                None,
+                key_packages,
+            )
+            .await
+            .unwrap();
+
+        let migration = alice_shadow
+            .context()
+            .extensions
+            .get_as::<ShadowMigrationExt>()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(migration.source_group_id, groups[0].group_id());
+        assert_eq!(migration.cutover_epoch, cutover_epoch);
+
+        let mut shadows = vec![alice_shadow];
+
+        for group in &groups[1..] {
+            let (shadow, _) = group
+                .join_shadow_group(&welcomes[0], None, None)
+                .await
+                .unwrap();
+
+            let diff = group.shadow_membership_diff(&shadow).await.unwrap();
+            assert!(diff.is_empty());
+
+            shadows.push(shadow);
+        }
+
+        for (group, shadow) in groups.iter().zip(&shadows) {
+            assert!(!group.migration_cutover_reached(shadow).unwrap());
+        }
+
+        let commit = groups[0].commit(Vec::new()).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+
+        for group in groups[1..].iter_mut() {
+            group
+                .process_incoming_message(commit.commit_message.clone())
+                .await
+                .unwrap();
+        }
+
+        for (group, shadow) in groups.iter().zip(&shadows) {
+            assert!(group.migration_cutover_reached(shadow).unwrap());
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn shadow_group_requires_all_other_members() {
+        let (clients, groups) = test_groups().await;
+        let key_package = clients[1].generate_key_package_message().await.unwrap();
+
+        let res = groups[0]
+            .create_shadow_group(
+                groups[0].current_epoch() + 1,
+                TEST_CIPHER_SUITE,
+                ExtensionList::new(),
+                None,
+                vec![key_package],
+            )
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::ShadowMembershipMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn cutover_epoch_must_be_in_the_future() {
+        let (_, groups) = test_groups().await;
+        let epoch = groups[0].current_epoch();
+
+        let res = groups[0]
+            .create_shadow_group(epoch, TEST_CIPHER_SUITE, ExtensionList::new(), None, vec![])
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::InvalidCutoverEpoch(e)) if e == epoch);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn membership_diff_reports_changes_after_creation() {
+        let (clients, mut groups) = test_groups().await;
+
+        let mut key_packages = Vec::new();
+
+        for client in &clients[1..] {
+            key_packages.push(client.generate_key_package_message().await.unwrap());
+        }
+
+        let (shadow, _) = groups[0]
+            .create_shadow_group(
+                groups[0].current_epoch() + 2,
+                TEST_CIPHER_SUITE,
+                ExtensionList::new(),
+                None,
+                key_packages,
+            )
+            .await
+            .unwrap();
+
+        groups[0]
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        groups[0].apply_pending_commit().await.unwrap();
+
+        let diff = groups[0].shadow_membership_diff(&shadow).await.unwrap();
+
+        assert!(diff.missing.is_empty());
+        assert_eq!(diff.extra.len(), 1);
+    }
+}
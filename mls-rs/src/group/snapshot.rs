@@ -8,11 +8,20 @@ use crate::{
     group::{
         cipher_suite_provider, epoch::EpochSecrets, key_schedule::KeySchedule,
         state_repo::GroupStateRepository, CommitGeneration, ConfirmationTag, Group, GroupContext,
-        GroupState, InterimTranscriptHash, ReInitProposal, TreeKemPublic,
+        GroupState, InterimTranscriptHash, MembershipStatus, ReInitProposal, TreeKemPublic,
     },
     tree_kem::TreeKemPrivate,
 };
 
+#[cfg(feature = "decryption_journal")]
+use crate::group::decryption_journal::DecryptionJournal;
+
+#[cfg(feature = "member_quarantine")]
+use crate::group::quarantine::QuarantinedMember;
+
+#[cfg(feature = "member_events")]
+use crate::group::member_events::MemberEventLog;
+
 #[cfg(feature = "by_ref_proposal")]
 use crate::{
     crypto::{HpkePublicKey, HpkeSecretKey},
@@ -41,6 +50,12 @@ pub(crate) struct Snapshot {
     pending_updates: SmallMap<HpkePublicKey, (HpkeSecretKey, Option<SignatureSecretKey>)>,
     pending_commit: Option<CommitGeneration>,
     signer: SignatureSecretKey,
+    #[cfg(feature = "decryption_journal")]
+    decryption_journal: DecryptionJournal,
+    #[cfg(feature = "member_quarantine")]
+    quarantine: Vec<QuarantinedMember>,
+    #[cfg(feature = "member_events")]
+    member_event_log: MemberEventLog,
 }
 
 #[derive(Debug, MlsEncode, MlsDecode, MlsSize, PartialEq, Clone)]
@@ -55,6 +70,36 @@ pub(crate) struct RawGroupState {
     pub(crate) interim_transcript_hash: InterimTranscriptHash,
     pub(crate) pending_reinit: Option<ReInitProposal>,
     pub(crate) confirmation_tag: ConfirmationTag,
+    pub(crate) membership_status: MembershipStatus,
+}
+
+#[cfg(feature = "openmls_import")]
+impl Snapshot {
+    pub(crate) fn from_parts(
+        state: RawGroupState,
+        private_tree: TreeKemPrivate,
+        epoch_secrets: EpochSecrets,
+        key_schedule: KeySchedule,
+        signer: SignatureSecretKey,
+    ) -> Self {
+        Snapshot {
+            version: 1,
+            state,
+            private_tree,
+            epoch_secrets,
+            key_schedule,
+            #[cfg(feature = "by_ref_proposal")]
+            pending_updates: Default::default(),
+            pending_commit: None,
+            signer,
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: Default::default(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_event_log: Default::default(),
+        }
+    }
 }
 
 impl RawGroupState {
@@ -79,6 +124,7 @@ impl RawGroupState {
             interim_transcript_hash: state.interim_transcript_hash.clone(),
             pending_reinit: state.pending_reinit.clone(),
             confirmation_tag: state.confirmation_tag.clone(),
+            membership_status: MembershipStatus::Active,
         }
     }
 
@@ -154,7 +200,10 @@ where
 
     pub(crate) fn snapshot(&self) -> Snapshot {
         Snapshot {
-            state: RawGroupState::export(&self.state),
+            state: RawGroupState {
+                membership_status: self.membership_status,
+                ..RawGroupState::export(&self.state)
+            },
             private_tree: self.private_tree.clone(),
             key_schedule: self.key_schedule.clone(),
             #[cfg(feature = "by_ref_proposal")]
@@ -163,6 +212,12 @@ where
             epoch_secrets: self.epoch_secrets.clone(),
             version: 1,
             signer: self.signer.clone(),
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: self.decryption_journal.clone(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine: self.quarantine.clone(),
+            #[cfg(feature = "member_events")]
+            member_event_log: self.member_event_log.clone(),
         }
     }
 
@@ -176,6 +231,8 @@ where
         #[cfg(feature = "tree_index")]
         let identity_provider = config.identity_provider();
 
+        let membership_status = snapshot.state.membership_status;
+
         let state_repo = GroupStateRepository::new(
             #[cfg(feature = "prior_epoch")]
             snapshot.state.context.group_id.clone(),
@@ -206,6 +263,21 @@ where
             #[cfg(feature = "psk")]
             previous_psk: None,
             signer: snapshot.signer,
+            membership_status,
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: snapshot.decryption_journal,
+            #[cfg(feature = "member_quarantine")]
+            quarantine: snapshot.quarantine,
+            #[cfg(feature = "member_events")]
+            member_event_log: snapshot.member_event_log,
+            #[cfg(feature = "member_events")]
+            member_subscriptions: Default::default(),
+            #[cfg(feature = "welcome_retargeting")]
+            retargetable_welcome: None,
+            #[cfg(feature = "app_ack")]
+            app_acks: Default::default(),
+            #[cfg(feature = "rekey_policy")]
+            rekey: Default::default(),
         })
     }
 }
@@ -241,6 +313,7 @@ pub(crate) mod test_utils {
                 pending_reinit: None,
                 confirmation_tag: ConfirmationTag::empty(&test_cipher_suite_provider(cipher_suite))
                     .await,
+                membership_status: Default::default(),
             },
             private_tree: TreeKemPrivate::new(LeafIndex(0)),
             epoch_secrets: get_test_epoch_secrets(cipher_suite),
@@ -250,6 +323,12 @@ pub(crate) mod test_utils {
             pending_commit: None,
             version: 1,
             signer: vec![].into(),
+            #[cfg(feature = "decryption_journal")]
+            decryption_journal: Default::default(),
+            #[cfg(feature = "member_quarantine")]
+            quarantine: Default::default(),
+            #[cfg(feature = "member_events")]
+            member_event_log: Default::default(),
         }
     }
 }
@@ -0,0 +1,207 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        CommitMessageDescription, ContentType, Group, ProposalMessageDescription, ReceivedMessage,
+    },
+    MlsMessage,
+};
+
+#[cfg(feature = "private_message")]
+use crate::group::ApplicationMessageDescription;
+
+#[cfg(feature = "decryption_journal")]
+use crate::group::AlreadyProcessedMessage;
+
+/// An event generated as a result of processing a message with
+/// [`Group::process_handshake_message`].
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum ReceivedHandshakeMessage {
+    /// A new commit was processed creating a new group state.
+    Commit(CommitMessageDescription),
+    /// A proposal was received.
+    Proposal(ProposalMessageDescription),
+}
+
+/// An event generated as a result of processing a message with
+/// [`Group::process_application_message`].
+#[cfg(feature = "private_message")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ReceivedApplicationMessage {
+    /// An application message was decrypted.
+    ApplicationMessage(ApplicationMessageDescription),
+    /// An application message that was already decrypted by this client was
+    /// received again.
+    #[cfg(feature = "decryption_journal")]
+    AlreadyProcessed(AlreadyProcessedMessage),
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Process an inbound commit or proposal for this group.
+    ///
+    /// This is the entry point for services that only handle handshake
+    /// traffic. Messages with application content are rejected with
+    /// [`MlsError::UnexpectedContentType`] before any processing takes place.
+    /// Otherwise, this function behaves like
+    /// [`Group::process_incoming_message`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn process_handshake_message(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<ReceivedHandshakeMessage, MlsError> {
+        match message.content_type() {
+            #[cfg(feature = "private_message")]
+            Some(ContentType::Application) => {
+                return Err(MlsError::UnexpectedContentType(ContentType::Application))
+            }
+            Some(_) => {}
+            None => return Err(MlsError::UnexpectedMessageType),
+        }
+
+        match self.process_incoming_message(message).await? {
+            ReceivedMessage::Commit(commit) => Ok(ReceivedHandshakeMessage::Commit(commit)),
+            ReceivedMessage::Proposal(proposal) => Ok(ReceivedHandshakeMessage::Proposal(proposal)),
+            _ => Err(MlsError::UnexpectedMessageType),
+        }
+    }
+
+    /// Process an inbound application message for this group.
+    ///
+    /// This is the entry point for services that only handle application
+    /// traffic. Commits and proposals are rejected with
+    /// [`MlsError::UnexpectedContentType`] before any processing takes place,
+    /// so the group state never advances through this function.
+    #[cfg(feature = "private_message")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn process_application_message(
+        &mut self,
+        message: MlsMessage,
+    ) -> Result<ReceivedApplicationMessage, MlsError> {
+        match message.content_type() {
+            Some(ContentType::Application) => {}
+            Some(content_type) => return Err(MlsError::UnexpectedContentType(content_type)),
+            None => return Err(MlsError::UnexpectedMessageType),
+        }
+
+        match self.process_incoming_message(message).await? {
+            ReceivedMessage::ApplicationMessage(message) => {
+                Ok(ReceivedApplicationMessage::ApplicationMessage(message))
+            }
+            #[cfg(feature = "decryption_journal")]
+            ReceivedMessage::AlreadyProcessed(message) => {
+                Ok(ReceivedApplicationMessage::AlreadyProcessed(message))
+            }
+            _ => Err(MlsError::UnexpectedMessageType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::test_utils::test_group,
+    };
+
+    use super::ReceivedHandshakeMessage;
+
+    #[cfg(feature = "private_message")]
+    use alloc::vec;
+
+    #[cfg(feature = "private_message")]
+    use crate::{client::MlsError, group::ContentType};
+
+    #[cfg(feature = "private_message")]
+    use super::ReceivedApplicationMessage;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn handshake_messages_are_processed() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let commit = alice.group.commit(Vec::new()).await.unwrap();
+        alice.group.apply_pending_commit().await.unwrap();
+
+        let received = bob
+            .group
+            .process_handshake_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_matches!(received, ReceivedHandshakeMessage::Commit(_));
+        assert_eq!(bob.group.current_epoch(), alice.group.current_epoch());
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn application_messages_are_processed() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let received = bob
+            .group
+            .process_application_message(message)
+            .await
+            .unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedApplicationMessage::ApplicationMessage(m) if m.data() == b"hello"
+        );
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn wrong_content_type_is_rejected_without_processing() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let res = bob.group.process_handshake_message(message.clone()).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::UnexpectedContentType(ContentType::Application))
+        );
+
+        let commit = alice.group.commit(Vec::new()).await.unwrap();
+        let res = bob
+            .group
+            .process_application_message(commit.commit_message)
+            .await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::UnexpectedContentType(ContentType::Commit))
+        );
+
+        // Bob can still process the application message.
+        bob.group
+            .process_application_message(message)
+            .await
+            .unwrap();
+    }
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::client::MlsError;
-use crate::{group::PriorEpoch, key_package::KeyPackageRef};
+use crate::{crypto::secret_material::wipe, group::PriorEpoch, key_package::KeyPackageRef};
 
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
@@ -11,6 +11,7 @@ use core::fmt::{self, Debug};
 use mls_rs_codec::{MlsDecode, MlsEncode};
 use mls_rs_core::group::{EpochRecord, GroupState};
 use mls_rs_core::{error::IntoAnyError, group::GroupStateStorage, key_package::KeyPackageStorage};
+use zeroize::Zeroizing;
 
 use super::snapshot::Snapshot;
 
@@ -26,6 +27,7 @@ use mls_rs_core::psk::PreSharedKey;
 struct EpochStorageCommit {
     pub(crate) inserts: VecDeque<PriorEpoch>,
     pub(crate) updates: Vec<PriorEpoch>,
+    pub(crate) delete_all: bool,
 }
 
 #[derive(Clone)]
@@ -130,7 +132,8 @@ where
             .epoch(&psk_id.psk_group_id.0, psk_id.psk_epoch)
             .await
             .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
-            .map(|e| Ok(PriorEpoch::mls_decode(&mut &*e)?.secrets.resumption_secret))
+            .map(Zeroizing::new)
+            .map(|e| Ok(PriorEpoch::mls_decode(&mut &**e)?.secrets.resumption_secret))
             .transpose()
     }
 
@@ -159,8 +162,9 @@ where
                 .epoch(&self.group_id, epoch_id)
                 .await
                 .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+                .map(Zeroizing::new)
                 .and_then(|epoch| {
-                    PriorEpoch::mls_decode(&mut &*epoch)
+                    PriorEpoch::mls_decode(&mut &**epoch)
                         .map(|epoch| {
                             self.pending_commit.updates.push(epoch);
                             self.pending_commit.updates.last_mut()
@@ -191,6 +195,18 @@ where
         Ok(())
     }
 
+    /// Drop pending prior epochs and delete the stored ones on the next
+    /// write.
+    pub fn purge_epochs(&mut self) {
+        self.pending_commit
+            .inserts
+            .drain(..)
+            .chain(self.pending_commit.updates.drain(..))
+            .for_each(|mut epoch| wipe(&mut epoch));
+
+        self.pending_commit.delete_all = true;
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn write_to_storage(&mut self, group_snapshot: Snapshot) -> Result<(), MlsError> {
         let inserts = self
@@ -217,6 +233,15 @@ where
             .await
             .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?;
 
+        if self.pending_commit.delete_all {
+            self.storage
+                .delete_epochs(&self.group_id)
+                .await
+                .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?;
+
+            self.pending_commit.delete_all = false;
+        }
+
         if let Some(ref key_package_ref) = self.pending_key_package_removal {
             self.key_package_repo
                 .delete(key_package_ref)
@@ -224,12 +249,50 @@ where
                 .map_err(|e| MlsError::KeyPackageRepoError(e.into_any_error()))?;
         }
 
-        self.pending_commit.inserts.clear();
-        self.pending_commit.updates.clear();
+        self.pending_commit
+            .inserts
+            .drain(..)
+            .chain(self.pending_commit.updates.drain(..))
+            .for_each(|mut epoch| wipe(&mut epoch));
 
         Ok(())
     }
 
+    /// Prior epochs kept in storage, oldest first.
+    #[cfg(feature = "compact_state")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn stored_epochs(&self) -> Result<Vec<PriorEpoch>, MlsError> {
+        let mut epochs = VecDeque::new();
+
+        let max_epoch_id = self
+            .storage
+            .max_epoch_id(&self.group_id)
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?;
+
+        let Some(mut epoch_id) = max_epoch_id else {
+            return Ok(Vec::new());
+        };
+
+        while let Some(epoch) = self
+            .storage
+            .epoch(&self.group_id, epoch_id)
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+            .map(Zeroizing::new)
+        {
+            epochs.push_front(PriorEpoch::mls_decode(&mut &**epoch)?);
+
+            let Some(previous) = epoch_id.checked_sub(1) else {
+                break;
+            };
+
+            epoch_id = previous;
+        }
+
+        Ok(epochs.into())
+    }
+
     #[cfg(any(feature = "psk", feature = "private_message"))]
     fn find_pending(&self, epoch_id: u64) -> Option<usize> {
         self.pending_commit
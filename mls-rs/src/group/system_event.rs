@@ -0,0 +1,217 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::{
+    crypto::CipherSuite,
+    extension::{ExtensionList, ExtensionType},
+    group::Member,
+};
+
+use crate::{
+    client::MlsError,
+    group::{
+        member_from_key_package, member_from_leaf_node, message_processor::ProvisionalState,
+        GroupContext, Sender,
+    },
+    tree_kem::{node::LeafIndex, TreeKemPublic, UpdatePath},
+};
+
+/// Change to a group made by a commit, described in a form that maps
+/// directly to a user-facing system message such as "Alice added Bob".
+///
+/// The `actor` of an event is the sender of the proposal that caused it,
+/// which is the committer for proposals sent by value. Members are
+/// described as they were in the epoch before the commit for removals, and
+/// in the new epoch otherwise.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SystemEvent {
+    /// `actor` added `subject` to the group.
+    MemberAdded { actor: Sender, subject: Member },
+    /// `subject` joined the group with an external commit.
+    MemberJoined { subject: Member },
+    /// `actor` removed `subject` from the group.
+    MemberRemoved { actor: Sender, subject: Member },
+    /// `subject` left the group.
+    MemberLeft { subject: Member },
+    /// `actor` started a reinitialization of the group moving it from the
+    /// `old` to the `new` cipher suite.
+    CipherSuiteChanged {
+        actor: Sender,
+        old: CipherSuite,
+        new: CipherSuite,
+    },
+    /// `actor` added, removed or modified the group context extension of
+    /// type `extension_type`.
+    ExtensionChanged {
+        actor: Sender,
+        extension_type: ExtensionType,
+    },
+}
+
+pub(crate) fn system_events(
+    old_context: &GroupContext,
+    old_tree: &TreeKemPublic,
+    provisional: &ProvisionalState,
+    path: Option<&UpdatePath>,
+    committer: LeafIndex,
+) -> Result<Vec<SystemEvent>, MlsError> {
+    let proposals = &provisional.applied_proposals;
+    let mut events = Vec::new();
+
+    if let Some(path) = path.filter(|_| !proposals.external_initializations.is_empty()) {
+        events.push(SystemEvent::MemberJoined {
+            subject: member_from_leaf_node(&path.leaf_node, committer),
+        });
+    }
+
+    for (p, index) in proposals
+        .additions
+        .iter()
+        .zip(provisional.indexes_of_added_kpkgs.iter())
+    {
+        events.push(SystemEvent::MemberAdded {
+            actor: p.sender,
+            subject: member_from_key_package(&p.proposal.key_package, *index),
+        });
+    }
+
+    for p in proposals.removals.iter() {
+        let index = p.proposal.to_remove;
+        let subject = member_from_leaf_node(old_tree.get_leaf_node(index)?, index);
+
+        events.push(match p.sender {
+            Sender::Member(sender) if sender == *index => SystemEvent::MemberLeft { subject },
+            actor => SystemEvent::MemberRemoved { actor, subject },
+        });
+    }
+
+    if let Some(p) = proposals.reinitializations.first() {
+        let new = p.proposal.new_cipher_suite();
+
+        if new != old_context.cipher_suite {
+            events.push(SystemEvent::CipherSuiteChanged {
+                actor: p.sender,
+                old: old_context.cipher_suite,
+                new,
+            });
+        }
+    }
+
+    if let Some(p) = proposals.group_context_extensions.first() {
+        events.extend(
+            changed_extensions(&old_context.extensions, &p.proposal).map(|extension_type| {
+                SystemEvent::ExtensionChanged {
+                    actor: p.sender,
+                    extension_type,
+                }
+            }),
+        );
+    }
+
+    Ok(events)
+}
+
+fn changed_extensions<'a>(
+    old: &'a ExtensionList,
+    new: &'a ExtensionList,
+) -> impl Iterator<Item = ExtensionType> + 'a {
+    let modified = new
+        .iter()
+        .filter(|ext| old.get(ext.extension_type).as_ref() != Some(*ext))
+        .map(|ext| ext.extension_type);
+
+    let removed = old
+        .iter()
+        .map(|ext| ext.extension_type)
+        .filter(|ext_type| !new.has_extension(*ext_type));
+
+    modified.chain(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::extension::{Extension, ExtensionList, ExtensionType};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::{
+            test_utils::{test_group, test_n_member_group},
+            ReceivedMessage, Sender,
+        },
+        key_package::test_utils::test_key_package_message,
+    };
+
+    use super::{changed_extensions, SystemEvent};
+
+    #[test]
+    fn changed_extensions_covers_added_modified_and_removed() {
+        let ext = |t: u16, data: u8| Extension::new(ExtensionType::new(t), vec![data]);
+
+        let old = ExtensionList::from(vec![ext(0xff00, 1), ext(0xff01, 1), ext(0xff02, 1)]);
+        let new = ExtensionList::from(vec![ext(0xff00, 1), ext(0xff01, 2), ext(0xff03, 1)]);
+
+        let changed = changed_extensions(&old, &new).collect::<Vec<_>>();
+
+        assert_eq!(
+            changed,
+            [0xff01, 0xff03, 0xff02].map(ExtensionType::new).to_vec()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_reports_added_members() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let key_package =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let update = alice.process_pending_commit().await.unwrap().state_update;
+
+        assert_matches!(
+            update.system_events(),
+            [SystemEvent::MemberAdded { actor: Sender::Member(0), subject }] if subject.index == 1
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_reports_removed_members() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let commit = groups[1]
+            .group
+            .commit_builder()
+            .remove_member(2)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let received = groups[0]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let ReceivedMessage::Commit(commit) = received else {
+            panic!("expected commit");
+        };
+
+        assert_matches!(
+            commit.state_update.system_events(),
+            [SystemEvent::MemberRemoved { actor: Sender::Member(1), subject }] if subject.index == 2
+        );
+    }
+}
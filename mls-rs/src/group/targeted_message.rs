@@ -0,0 +1,361 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::crypto::CipherSuiteProvider;
+use zeroize::Zeroizing;
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    crypto::HpkeCiphertext,
+    group::{
+        framing::MlsMessagePayload, message_processor::ApplicationMessageDescription, Group,
+        GroupContext, ReceivedMessage,
+    },
+    signer::Signable,
+    tree_kem::{hpke_encryption::HpkeEncryptable, node::LeafIndex},
+    MlsMessage,
+};
+
+const EXPORTER_LABEL: &[u8] = b"targeted message";
+
+/// Message encrypted to the leaf key of a single member of a group.
+#[derive(Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct TargetedMessage {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
+    pub group_id: Vec<u8>,
+    pub epoch: u64,
+    pub recipient_leaf_index: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    #[cfg_attr(feature = "serde", serde(with = "mls_rs_core::vec_serde"))]
+    pub authenticated_data: Vec<u8>,
+    pub ciphertext: HpkeCiphertext,
+}
+
+impl Debug for TargetedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TargetedMessage")
+            .field(
+                "group_id",
+                &mls_rs_core::debug::pretty_group_id(&self.group_id),
+            )
+            .field("epoch", &self.epoch)
+            .field("recipient_leaf_index", &self.recipient_leaf_index)
+            .field(
+                "authenticated_data",
+                &mls_rs_core::debug::pretty_bytes(&self.authenticated_data),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(MlsSize, MlsEncode)]
+struct TargetedMessageContext<'a> {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    group_id: &'a [u8],
+    epoch: u64,
+    recipient_leaf_index: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    authenticated_data: &'a [u8],
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    epoch_binding: &'a [u8],
+}
+
+#[derive(MlsSize, MlsEncode, MlsDecode)]
+struct TargetedMessageContent {
+    sender_leaf_index: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    application_data: Vec<u8>,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    signature: Vec<u8>,
+}
+
+impl HpkeEncryptable for TargetedMessageContent {
+    const ENCRYPT_LABEL: &'static str = "TargetedMessage";
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, MlsError> {
+        let decrypted = Zeroizing::new(bytes);
+
+        Self::mls_decode(&mut &**decrypted).map_err(Into::into)
+    }
+
+    fn get_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+}
+
+struct TargetedMessageSigningContext<'a> {
+    group_context: &'a GroupContext,
+    recipient_leaf_index: u32,
+    authenticated_data: &'a [u8],
+}
+
+#[derive(MlsSize, MlsEncode)]
+struct TargetedMessageTBS<'a> {
+    group_context: &'a GroupContext,
+    recipient_leaf_index: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    authenticated_data: &'a [u8],
+    sender_leaf_index: u32,
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    application_data: &'a [u8],
+}
+
+impl<'a> Signable<'a> for TargetedMessageContent {
+    const SIGN_LABEL: &'static str = "TargetedMessageTBS";
+
+    type SigningContext = TargetedMessageSigningContext<'a>;
+
+    fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    fn signable_content(
+        &self,
+        context: &Self::SigningContext,
+    ) -> Result<Vec<u8>, mls_rs_codec::Error> {
+        TargetedMessageTBS {
+            group_context: context.group_context,
+            recipient_leaf_index: context.recipient_leaf_index,
+            authenticated_data: context.authenticated_data,
+            sender_leaf_index: self.sender_leaf_index,
+            application_data: &self.application_data,
+        }
+        .mls_encode_to_vec()
+    }
+
+    fn write_signature(&mut self, signature: Vec<u8>) {
+        self.signature = signature
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Encrypt an application message that only the member with leaf index
+    /// `recipient` can decrypt.
+    ///
+    /// The message is encrypted with HPKE to the leaf key of the recipient
+    /// in the current epoch and signed by this member. The encryption is
+    /// bound to a secret exported from the current epoch, so the message
+    /// can only be decrypted by the recipient while it is a member of the
+    /// group in this epoch. The recipient receives the message as a
+    /// [`ReceivedMessage::TargetedMessage`] from
+    /// [`Group::process_incoming_message`].
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the
+    /// contents of the message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn encrypt_targeted_message(
+        &self,
+        recipient: u32,
+        message: &[u8],
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        self.check_can_send()?;
+
+        let recipient_key = self
+            .state
+            .public_tree
+            .get_leaf_node(LeafIndex(recipient))?
+            .public_key
+            .clone();
+
+        let mut content = TargetedMessageContent {
+            sender_leaf_index: *self.private_tree.self_index,
+            application_data: message.to_vec(),
+            signature: Vec::new(),
+        };
+
+        let signing_context = TargetedMessageSigningContext {
+            group_context: self.context(),
+            recipient_leaf_index: recipient,
+            authenticated_data: &authenticated_data,
+        };
+
+        content
+            .sign(&self.cipher_suite_provider, &self.signer, &signing_context)
+            .await?;
+
+        let hpke_context = self
+            .targeted_message_context(recipient, &authenticated_data)
+            .await?;
+
+        let ciphertext = content
+            .encrypt(&self.cipher_suite_provider, &recipient_key, &hpke_context)
+            .await?;
+
+        let targeted_message = TargetedMessage {
+            group_id: self.group_id().to_vec(),
+            epoch: self.current_epoch(),
+            recipient_leaf_index: recipient,
+            authenticated_data,
+            ciphertext,
+        };
+
+        Ok(MlsMessage::new(
+            self.protocol_version(),
+            MlsMessagePayload::Targeted(targeted_message),
+        ))
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn process_targeted_message(
+        &self,
+        message: &MlsMessage,
+        targeted_message: &TargetedMessage,
+    ) -> Result<ReceivedMessage, MlsError> {
+        if message.version != self.protocol_version() {
+            return Err(MlsError::ProtocolVersionMismatch);
+        }
+
+        if targeted_message.group_id != self.group_id() {
+            return Err(MlsError::GroupIdMismatch);
+        }
+
+        if targeted_message.epoch != self.current_epoch() {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        let self_index = self.private_tree.self_index;
+
+        if targeted_message.recipient_leaf_index != *self_index {
+            return Err(MlsError::TargetedMessageRecipientMismatch(
+                targeted_message.recipient_leaf_index,
+            ));
+        }
+
+        let secret_key = self
+            .private_tree
+            .secret_keys
+            .first()
+            .and_then(Option::as_ref)
+            .ok_or(MlsError::InvalidTreeKemPrivateKey)?;
+
+        let hpke_context = self
+            .targeted_message_context(self_index.0, &targeted_message.authenticated_data)
+            .await?;
+
+        let content = TargetedMessageContent::decrypt(
+            &self.cipher_suite_provider,
+            secret_key,
+            &self.current_user_leaf_node()?.public_key,
+            &hpke_context,
+            &targeted_message.ciphertext,
+        )
+        .await?;
+
+        let sender = self
+            .state
+            .public_tree
+            .get_leaf_node(LeafIndex(content.sender_leaf_index))?;
+
+        let signing_context = TargetedMessageSigningContext {
+            group_context: self.context(),
+            recipient_leaf_index: targeted_message.recipient_leaf_index,
+            authenticated_data: &targeted_message.authenticated_data,
+        };
+
+        content
+            .verify(
+                &self.cipher_suite_provider,
+                &sender.signing_identity.signature_key,
+                &signing_context,
+            )
+            .await?;
+
+        Ok(ReceivedMessage::TargetedMessage(
+            ApplicationMessageDescription {
+                sender_index: content.sender_leaf_index,
+                data: content.application_data.into(),
+                authenticated_data: targeted_message.authenticated_data.clone(),
+            },
+        ))
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn targeted_message_context(
+        &self,
+        recipient: u32,
+        authenticated_data: &[u8],
+    ) -> Result<Vec<u8>, MlsError> {
+        let epoch_binding = self
+            .export_secret(
+                EXPORTER_LABEL,
+                &[],
+                self.cipher_suite_provider.kdf_extract_size(),
+            )
+            .await?;
+
+        TargetedMessageContext {
+            group_id: self.group_id(),
+            epoch: self.current_epoch(),
+            recipient_leaf_index: recipient,
+            authenticated_data,
+            epoch_binding: &epoch_binding,
+        }
+        .mls_encode_to_vec()
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::{framing::WireFormat, test_utils::test_n_member_group, ReceivedMessage},
+        MlsMessage,
+    };
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn targeted_message_is_decrypted_by_recipient_only() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let message = groups[0]
+            .group
+            .encrypt_targeted_message(2, b"for charlie", b"aad".to_vec())
+            .await
+            .unwrap();
+
+        let message = MlsMessage::from_bytes(&message.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(message.wire_format(), WireFormat::TargetedMessage);
+        assert_eq!(message.epoch(), Some(groups[0].group.current_epoch()));
+
+        let received = groups[2].process_message(message.clone()).await.unwrap();
+
+        assert_matches!(
+            received,
+            ReceivedMessage::TargetedMessage(m)
+                if m.sender_index == 0 && m.data() == b"for charlie" && m.authenticated_data == b"aad"
+        );
+
+        let res = groups[1].process_message(message.clone()).await;
+        assert_matches!(res, Err(MlsError::TargetedMessageRecipientMismatch(2)));
+
+        let commit = groups[1].group.commit(vec![]).await.unwrap();
+        groups[1].process_pending_commit().await.unwrap();
+
+        groups[2]
+            .process_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let res = groups[2].process_message(message).await;
+        assert_matches!(res, Err(MlsError::InvalidEpoch));
+    }
+}
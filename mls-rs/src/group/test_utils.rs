@@ -440,6 +440,10 @@ impl MessageProcessor for GroupWithoutKeySchedule {
         self.inner.group_state()
     }
 
+    fn supported_extensions(&self) -> Vec<ExtensionType> {
+        self.inner.supported_extensions()
+    }
+
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn group_state_mut(&mut self) -> &mut GroupState {
         self.inner.group_state_mut()
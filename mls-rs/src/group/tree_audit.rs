@@ -0,0 +1,136 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+
+use crate::{
+    client::MlsError, crypto::CipherSuiteProvider, group::ExportedTree, tree_kem::TreeKemPublic,
+};
+
+fn decode_tree(tree_data: &[u8]) -> Result<TreeKemPublic, MlsError> {
+    let mut tree = TreeKemPublic::new();
+    tree.nodes = ExportedTree::from_bytes(tree_data)?.0.into_owned();
+
+    if tree.nodes.is_empty() {
+        return Err(MlsError::UnexpectedEmptyTree);
+    }
+
+    Ok(tree)
+}
+
+/// Compute the tree hash of a serialized ratchet tree, as produced by
+/// [`ExportedTree::to_bytes`].
+///
+/// The result can be compared to the tree hash of a
+/// [GroupContext](crate::group::GroupContext).
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn compute_tree_hash<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    tree_data: &[u8],
+) -> Result<Vec<u8>, MlsError> {
+    decode_tree(tree_data)?
+        .tree_hash(cipher_suite_provider)
+        .await
+}
+
+/// Verify that the tree hash of a serialized ratchet tree equals
+/// `expected_tree_hash`.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn verify_tree_hash<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    tree_data: &[u8],
+    expected_tree_hash: &[u8],
+) -> Result<(), MlsError> {
+    let tree_hash = compute_tree_hash(cipher_suite_provider, tree_data).await?;
+
+    (tree_hash == expected_tree_hash)
+        .then_some(())
+        .ok_or(MlsError::TreeHashMismatch)
+}
+
+/// Verify the parent hashes of a serialized ratchet tree, as described in
+/// [RFC 9420 Section 7.9.2](https://www.rfc-editor.org/rfc/rfc9420.html#section-7.9.2).
+///
+/// Only the parent hash chains are checked. Leaf signatures and credentials
+/// are not validated.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn verify_parent_hashes<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    tree_data: &[u8],
+) -> Result<(), MlsError> {
+    let mut tree = decode_tree(tree_data)?;
+
+    // Parent hash validation reuses the tree hashes of the full tree.
+    tree.tree_hash(cipher_suite_provider).await?;
+    tree.validate_parent_hashes(cipher_suite_provider).await
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        crypto::test_utils::test_cipher_suite_provider,
+        group::{test_utils::test_n_member_group, ExportedTree},
+        tree_kem::parent_hash::ParentHash,
+    };
+
+    use super::{compute_tree_hash, verify_parent_hashes, verify_tree_hash};
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn exported_tree_passes_audit() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let tree_data = groups[0].group.export_tree().to_bytes().unwrap();
+        let expected = &groups[0].group.context().tree_hash;
+
+        let tree_hash = compute_tree_hash(&cs, &tree_data).await.unwrap();
+        assert_eq!(&tree_hash, expected);
+        verify_tree_hash(&cs, &tree_data, expected).await.unwrap();
+        verify_parent_hashes(&cs, &tree_data).await.unwrap();
+
+        let res = verify_tree_hash(&cs, &tree_data, b"wrong").await;
+        assert_matches!(res, Err(MlsError::TreeHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn tampered_parent_hash_fails_audit() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        groups[0].group.commit(vec![]).await.unwrap();
+        groups[0].process_pending_commit().await.unwrap();
+
+        let mut nodes = groups[0].group.export_tree().0.into_owned();
+        let (parent_index, _) = nodes.non_empty_parents().next().unwrap();
+
+        nodes
+            .borrow_as_parent_mut(parent_index)
+            .unwrap()
+            .parent_hash = ParentHash::from(vec![0u8; 32]);
+
+        let tree_data = ExportedTree::new(nodes).to_bytes().unwrap();
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let res = verify_parent_hashes(&cs, &tree_data).await;
+        assert_matches!(res, Err(MlsError::ParentHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn empty_tree_is_rejected() {
+        let tree_data = ExportedTree::new(Default::default()).to_bytes().unwrap();
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let res = verify_parent_hashes(&cs, &tree_data).await;
+        assert_matches!(res, Err(MlsError::UnexpectedEmptyTree));
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use core::convert::Infallible;
+use mls_rs_core::{crypto::SignatureSecretKey, error::IntoAnyError};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    extension::RatchetTreeLocationExt,
+    group::{ExportedTree, Group, GroupContext, GroupInfo, NewMemberInfo},
+    MlsMessage,
+};
+
+/// Source of ratchet trees that are referenced by, rather than embedded in,
+/// Welcome messages.
+///
+/// Large groups can omit the `ratchet_tree_extension` from their Welcome
+/// messages and instead publish the tree, for example on a CDN, at the
+/// location advertised in a
+/// [`RatchetTreeLocationExt`](crate::extension::built_in::RatchetTreeLocationExt)
+/// of the GroupInfo. The fetched tree does not need to be trusted, since it
+/// is verified against the tree hash of the GroupInfo before it is used.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait TreeFetcher: Send + Sync {
+    /// Error type that this fetcher returns on internal failure.
+    type Error: IntoAnyError;
+
+    /// Fetch the ratchet tree of the group with `group_context`.
+    ///
+    /// `location` is the value of the
+    /// [`RatchetTreeLocationExt`](crate::extension::built_in::RatchetTreeLocationExt)
+    /// of the GroupInfo, if present. Returning `None` fails the join with
+    /// [`MlsError::RatchetTreeNotFound`].
+    async fn fetch_tree(
+        &self,
+        group_context: &GroupContext,
+        location: Option<&[u8]>,
+    ) -> Result<Option<ExportedTree<'static>>, Self::Error>;
+}
+
+/// Placeholder type for joins without a [`TreeFetcher`].
+pub(crate) enum NoTreeFetcher {}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl TreeFetcher for NoTreeFetcher {
+    type Error = Infallible;
+
+    async fn fetch_tree(
+        &self,
+        _group_context: &GroupContext,
+        _location: Option<&[u8]>,
+    ) -> Result<Option<ExportedTree<'static>>, Self::Error> {
+        match *self {}
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn fetch_tree<F: TreeFetcher>(
+    tree_fetcher: &F,
+    group_info: &GroupInfo,
+) -> Result<ExportedTree<'static>, MlsError> {
+    let location = group_info.extensions.get_as::<RatchetTreeLocationExt>()?;
+
+    tree_fetcher
+        .fetch_tree(
+            &group_info.group_context,
+            location.as_ref().map(|ext| ext.location.as_slice()),
+        )
+        .await
+        .map_err(|e| MlsError::TreeFetcherError(e.into_any_error()))?
+        .ok_or(MlsError::RatchetTreeNotFound)
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn join_with_tree_fetcher<F: TreeFetcher>(
+        welcome: &MlsMessage,
+        tree_fetcher: &F,
+        config: C,
+        signer: SignatureSecretKey,
+    ) -> Result<(Self, NewMemberInfo), MlsError> {
+        Self::from_welcome_message(
+            welcome,
+            None,
+            Some(tree_fetcher),
+            config,
+            signer,
+            #[cfg(feature = "psk")]
+            None,
+            #[cfg(feature = "welcome_retargeting")]
+            false,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use assert_matches::assert_matches;
+    use core::convert::Infallible;
+    use mls_rs_core::extension::ExtensionList;
+
+    use crate::{
+        client::{
+            test_utils::{
+                test_client_with_key_pkg, TestClientConfig, TEST_CIPHER_SUITE,
+                TEST_PROTOCOL_VERSION,
+            },
+            MlsError,
+        },
+        extension::built_in::RatchetTreeLocationExt,
+        group::{
+            mls_rules::CommitOptions,
+            test_utils::{test_group_custom, TestGroup},
+            ExportedTree, GroupContext,
+        },
+        Client, MlsMessage,
+    };
+
+    use super::TreeFetcher;
+
+    const LOCATION: &[u8] = b"https://cdn.example/tree";
+
+    /// Serves a single tree from `LOCATION`.
+    struct TestTreeFetcher(Option<ExportedTree<'static>>);
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
+    impl TreeFetcher for TestTreeFetcher {
+        type Error = Infallible;
+
+        async fn fetch_tree(
+            &self,
+            _group_context: &GroupContext,
+            location: Option<&[u8]>,
+        ) -> Result<Option<ExportedTree<'static>>, Self::Error> {
+            Ok(self.0.clone().filter(|_| location == Some(LOCATION)))
+        }
+    }
+
+    /// Add bob to a group that references its tree instead of embedding it.
+    ///
+    /// Returns alice's group in the new epoch, the tree of the previous epoch
+    /// and bob's client together with his welcome.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn add_bob_without_tree() -> (
+        TestGroup,
+        ExportedTree<'static>,
+        Client<TestClientConfig>,
+        MlsMessage,
+    ) {
+        let mut alice = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            Default::default(),
+            None,
+            Some(CommitOptions::new().with_ratchet_tree_extension(false)),
+        )
+        .await;
+
+        let old_tree = alice.group.export_tree().into_owned();
+
+        let (bob, bob_key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let mut group_info_extensions = ExtensionList::new();
+
+        group_info_extensions
+            .set_from(RatchetTreeLocationExt::new(LOCATION.to_vec()))
+            .unwrap();
+
+        let mut commit = alice
+            .group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .set_group_info_ext(group_info_extensions)
+            .build()
+            .await
+            .unwrap();
+
+        alice.group.apply_pending_commit().await.unwrap();
+
+        (alice, old_tree, bob, commit.welcome_messages.remove(0))
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_can_be_joined_with_fetched_tree() {
+        let (alice, _, bob, welcome) = add_bob_without_tree().await;
+        let tree = alice.group.export_tree().into_owned();
+
+        let (bob_group, _) = bob
+            .join_group_with_tree_fetcher(&welcome, &TestTreeFetcher(Some(tree)))
+            .await
+            .unwrap();
+
+        assert_eq!(bob_group.export_tree(), alice.group.export_tree());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn fetched_tree_must_match_tree_hash() {
+        let (_, old_tree, bob, welcome) = add_bob_without_tree().await;
+
+        let res = bob
+            .join_group_with_tree_fetcher(&welcome, &TestTreeFetcher(Some(old_tree)))
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::TreeHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn missing_fetched_tree_is_rejected() {
+        let (_, _, bob, welcome) = add_bob_without_tree().await;
+
+        let res = bob
+            .join_group_with_tree_fetcher(&welcome, &TestTreeFetcher(None))
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::RatchetTreeNotFound));
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::{
+    client_config::ClientConfig,
+    group::{
+        roster::member_from_leaf_node, ExportedTree, Group, GroupContext, Member, Roster,
+        TreeKemPublic,
+    },
+    tree_kem::node::LeafIndex,
+};
+
+/// Immutable view of the ratchet tree and context of a group at one epoch.
+///
+/// A snapshot shares the nodes of the tree with the group until the group
+/// modifies them, so taking a snapshot does not copy the tree. Snapshots are
+/// `Send` and `Sync` and can be read concurrently, e.g. to answer roster
+/// queries or export the tree, while the group processes the next commit.
+#[derive(Clone, Debug)]
+pub struct TreeSnapshot {
+    context: GroupContext,
+    tree: TreeKemPublic,
+}
+
+impl TreeSnapshot {
+    /// Group context of the epoch of the snapshot.
+    pub fn context(&self) -> &GroupContext {
+        &self.context
+    }
+
+    /// Epoch of the snapshot.
+    pub fn epoch(&self) -> u64 {
+        self.context.epoch
+    }
+
+    /// Members of the group in the epoch of the snapshot.
+    pub fn roster(&self) -> Roster<'_> {
+        self.tree.roster()
+    }
+
+    /// Member with the given leaf `index` in the epoch of the snapshot.
+    pub fn member_at_index(&self, index: u32) -> Option<Member> {
+        let index = LeafIndex(index);
+
+        self.tree
+            .get_leaf_node(index)
+            .ok()
+            .map(|leaf| member_from_leaf_node(leaf, index))
+    }
+
+    /// Ratchet tree of the epoch of the snapshot.
+    pub fn export_tree(&self) -> ExportedTree<'_> {
+        ExportedTree::new_borrowed(&self.tree.nodes)
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Take an immutable snapshot of the ratchet tree and context of the
+    /// current epoch.
+    pub fn tree_snapshot(&self) -> TreeSnapshot {
+        TreeSnapshot {
+            context: self.context().clone(),
+            tree: self.state.public_tree.node_snapshot(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::test_utils::test_group,
+    };
+
+    use super::TreeSnapshot;
+
+    #[test]
+    fn tree_snapshot_is_send_and_sync() {
+        fn send_and_sync<T: Send + Sync>() {}
+
+        send_and_sync::<TreeSnapshot>();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn tree_snapshot_is_not_affected_by_commits() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let snapshot = alice.group.tree_snapshot();
+        let tree = alice.group.export_tree().to_bytes().unwrap();
+
+        alice.join("bob").await;
+
+        assert_eq!(snapshot.epoch(), alice.group.current_epoch() - 1);
+        assert_eq!(snapshot.roster().members().len(), 1);
+        assert!(snapshot.member_at_index(1).is_none());
+        assert_eq!(snapshot.export_tree().to_bytes().unwrap(), tree);
+
+        let snapshot = alice.group.tree_snapshot();
+
+        assert_eq!(snapshot.roster().members(), alice.group.roster().members());
+        assert_eq!(snapshot.context(), alice.group.context());
+
+        alice.group.commit(vec![]).await.unwrap();
+        alice.process_pending_commit().await.unwrap();
+
+        assert_eq!(snapshot.member_at_index(1), alice.group.member_at_index(1));
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::{CipherSuiteProvider, HpkePublicKey, HpkeSecretKey, SignatureSecretKey},
+    error::IntoAnyError,
+    identity::{IdentityProvider, SigningIdentity},
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::Group,
+    signer::{signer_matches_identity, Signable},
+    tree_kem::{
+        leaf_node::{LeafNode, LeafNodeSigningContext, LeafNodeSource},
+        leaf_node_validator::{LeafNodeValidator, ValidationContext},
+    },
+    MlsMessage,
+};
+
+const KEY_CHECK_LABEL: &[u8] = b"mls-rs update leaf key check";
+
+/// Leaf node replacing the current member's leaf with an
+/// [update proposal](Group::propose_update_with_leaf_node).
+///
+/// The leaf node can be generated and signed outside of this library, e.g.
+/// by a secure element. It is encoded as an MLS `LeafNode` structure.
+/// [`Group::update_leaf_node_template`] provides a leaf node with the
+/// capabilities and extensions configured for the client that only needs to
+/// be signed.
+#[derive(Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+pub struct UpdateLeafNode(LeafNode);
+
+impl Debug for UpdateLeafNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UpdateLeafNode").field(&self.0).finish()
+    }
+}
+
+impl UpdateLeafNode {
+    /// HPKE public key of the leaf node.
+    pub fn public_key(&self) -> &HpkePublicKey {
+        &self.0.public_key
+    }
+
+    /// Signing identity of the leaf node.
+    pub fn signing_identity(&self) -> &SigningIdentity {
+        &self.0.signing_identity
+    }
+
+    /// Set the signature computed over the data returned by
+    /// [`Group::update_leaf_node_to_be_signed`].
+    pub fn set_signature(&mut self, signature: Vec<u8>) {
+        self.0.signature = signature;
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Create an unsigned leaf node for the current member using the HPKE
+    /// `public_key` and optionally a new `signing_identity`.
+    ///
+    /// The signature must be computed over the output of
+    /// [`Group::update_leaf_node_to_be_signed`] and set with
+    /// [`UpdateLeafNode::set_signature`].
+    pub fn update_leaf_node_template(
+        &self,
+        public_key: HpkePublicKey,
+        signing_identity: Option<SigningIdentity>,
+    ) -> Result<UpdateLeafNode, MlsError> {
+        let current_leaf = self.current_user_leaf_node()?;
+        let properties = self.config.leaf_properties();
+
+        let mut leaf_node = LeafNode {
+            public_key,
+            signing_identity: signing_identity
+                .unwrap_or_else(|| current_leaf.signing_identity.clone()),
+            capabilities: properties.capabilities,
+            leaf_node_source: LeafNodeSource::Update,
+            extensions: properties.extensions,
+            signature: Vec::new(),
+        };
+
+        leaf_node.grease(&self.cipher_suite_provider)?;
+
+        Ok(UpdateLeafNode(leaf_node))
+    }
+
+    /// Data that must be signed by the signature key of `leaf_node` for the
+    /// leaf node to be valid in this group.
+    ///
+    /// The output already includes the `LeafNodeTBS` signing label and can be
+    /// passed to the signature scheme as is.
+    pub fn update_leaf_node_to_be_signed(
+        &self,
+        leaf_node: &UpdateLeafNode,
+    ) -> Result<Vec<u8>, MlsError> {
+        let context = LeafNodeSigningContext::from((self.group_id(), self.current_member_index()));
+
+        Ok(leaf_node.0.to_be_signed(&context)?)
+    }
+
+    /// Create a proposal message that replaces the current member's leaf with
+    /// a pre-generated `leaf_node`.
+    ///
+    /// `secret_key` is the HPKE secret key corresponding to the public key
+    /// of `leaf_node`. If the leaf node changes the signature key of the
+    /// member, `signer` must contain the new signature secret key, which may
+    /// be a handle understood by the configured crypto provider.
+    ///
+    /// Before the proposal is sent, the leaf node is validated in the same
+    /// way as update proposals received from other members, including the
+    /// [`IdentityProvider`] checks that the new identity is
+    /// [valid](IdentityProvider::validate_member) and a
+    /// [valid successor](IdentityProvider::valid_successor) of the current
+    /// one. It is also checked that `secret_key` and `signer` match the keys
+    /// of the leaf node.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_update_with_leaf_node(
+        &mut self,
+        leaf_node: UpdateLeafNode,
+        secret_key: HpkeSecretKey,
+        signer: Option<SignatureSecretKey>,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        let leaf_node = leaf_node.0;
+
+        self.validate_update_leaf_node(&leaf_node, &secret_key, signer.as_ref())
+            .await?;
+
+        let proposal = self.pending_update_proposal(leaf_node, secret_key, signer);
+
+        self.proposal_message(proposal, authenticated_data).await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn validate_update_leaf_node(
+        &self,
+        leaf_node: &LeafNode,
+        secret_key: &HpkeSecretKey,
+        signer: Option<&SignatureSecretKey>,
+    ) -> Result<(), MlsError> {
+        let current_leaf = self.current_user_leaf_node()?;
+
+        if leaf_node.public_key == current_leaf.public_key {
+            return Err(MlsError::SameHpkeKey(self.current_member_index()));
+        }
+
+        let identity_provider = self.config.identity_provider();
+        let extensions = &self.context().extensions;
+
+        LeafNodeValidator::new(
+            &self.cipher_suite_provider,
+            &identity_provider,
+            Some(extensions),
+        )
+        .check_if_valid(
+            leaf_node,
+            ValidationContext::Update((self.group_id(), self.current_member_index(), None)),
+        )
+        .await?;
+
+        identity_provider
+            .valid_successor(
+                &current_leaf.signing_identity,
+                &leaf_node.signing_identity,
+                extensions,
+            )
+            .await
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?
+            .then_some(())
+            .ok_or(MlsError::InvalidSuccessor)?;
+
+        let signer_matches = match signer {
+            Some(signer) => {
+                signer_matches_identity(
+                    &self.cipher_suite_provider,
+                    &leaf_node.signing_identity,
+                    signer,
+                )
+                .await?
+            }
+            None => {
+                leaf_node.signing_identity.signature_key
+                    == current_leaf.signing_identity.signature_key
+            }
+        };
+
+        if !signer_matches {
+            return Err(MlsError::SignerIdentityMismatch);
+        }
+
+        // Check that the secret key decrypts messages sent to the leaf. Some
+        // providers reject empty plaintexts, so the label is sealed as well.
+        let ciphertext = self
+            .cipher_suite_provider
+            .hpke_seal(
+                &leaf_node.public_key,
+                KEY_CHECK_LABEL,
+                None,
+                KEY_CHECK_LABEL,
+            )
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        self.cipher_suite_provider
+            .hpke_open(
+                &ciphertext,
+                secret_key,
+                &leaf_node.public_key,
+                KEY_CHECK_LABEL,
+                None,
+            )
+            .await
+            .map_err(|_| MlsError::LeafNodeKeyMismatch)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::crypto::{CipherSuiteProvider, HpkePublicKey, HpkeSecretKey};
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        crypto::test_utils::test_cipher_suite_provider,
+        group::test_utils::{test_n_member_group, TestGroup},
+        identity::test_utils::get_test_signing_identity,
+    };
+
+    use super::UpdateLeafNode;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn signed_leaf_node(group: &TestGroup) -> (UpdateLeafNode, HpkeSecretKey) {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (secret_key, public_key) = cs.kem_generate().await.unwrap();
+
+        let mut leaf_node = group
+            .group
+            .update_leaf_node_template(public_key, None)
+            .unwrap();
+
+        // The signature is computed as if by an external device.
+        let to_be_signed = group
+            .group
+            .update_leaf_node_to_be_signed(&leaf_node)
+            .unwrap();
+
+        let signature = cs.sign(&group.group.signer, &to_be_signed).await.unwrap();
+        leaf_node.set_signature(signature);
+
+        (leaf_node, secret_key)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn update_with_external_leaf_node() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let (leaf_node, secret_key) = signed_leaf_node(&groups[0]).await;
+        let public_key = leaf_node.public_key().clone();
+
+        let update = groups[0]
+            .group
+            .propose_update_with_leaf_node(leaf_node, secret_key, None, vec![])
+            .await
+            .unwrap();
+
+        groups[1].process_message(update).await.unwrap();
+        let commit_output = groups[1].group.commit(vec![]).await.unwrap();
+        groups[1].process_pending_commit().await.unwrap();
+
+        groups[0]
+            .process_message(commit_output.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            groups[0].group.current_user_leaf_node().unwrap().public_key,
+            public_key
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn leaf_node_with_invalid_signature_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let (mut leaf_node, secret_key) = signed_leaf_node(&groups[0]).await;
+
+        leaf_node.set_signature(vec![0u8; 64]);
+
+        let res = groups[0]
+            .group
+            .propose_update_with_leaf_node(leaf_node, secret_key, None, vec![])
+            .await;
+
+        assert_matches!(res, Err(MlsError::InvalidSignature));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn leaf_node_with_wrong_secret_key_is_rejected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let (leaf_node, _) = signed_leaf_node(&groups[0]).await;
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (other_secret_key, _) = cs.kem_generate().await.unwrap();
+
+        let res = groups[0]
+            .group
+            .propose_update_with_leaf_node(leaf_node, other_secret_key, None, vec![])
+            .await;
+
+        assert_matches!(res, Err(MlsError::LeafNodeKeyMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn new_identity_requires_signer() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let (identity, new_signer) = get_test_signing_identity(TEST_CIPHER_SUITE, b"member").await;
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (secret_key, public_key): (HpkeSecretKey, HpkePublicKey) =
+            cs.kem_generate().await.unwrap();
+
+        let mut leaf_node = groups[0]
+            .group
+            .update_leaf_node_template(public_key, Some(identity))
+            .unwrap();
+
+        let to_be_signed = groups[0]
+            .group
+            .update_leaf_node_to_be_signed(&leaf_node)
+            .unwrap();
+
+        leaf_node.set_signature(cs.sign(&new_signer, &to_be_signed).await.unwrap());
+
+        let res = groups[0]
+            .group
+            .propose_update_with_leaf_node(leaf_node.clone(), secret_key.clone(), None, vec![])
+            .await;
+
+        assert_matches!(res, Err(MlsError::SignerIdentityMismatch));
+
+        groups[0]
+            .group
+            .propose_update_with_leaf_node(leaf_node, secret_key, Some(new_signer), vec![])
+            .await
+            .unwrap();
+    }
+}
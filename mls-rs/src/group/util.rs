@@ -20,10 +20,16 @@ use crate::{
 #[cfg(feature = "by_ref_proposal")]
 use crate::extension::ExternalSendersExt;
 
+#[cfg(feature = "tree_compression")]
+use crate::extension::CompressedRatchetTreeExt;
+
+#[cfg(all(feature = "tree_fetcher", feature = "tree_compression"))]
+use mls_rs_core::extension::MlsCodecExtension;
+
 use super::{
     framing::Sender, message_signature::AuthenticatedContent,
     transcript_hash::InterimTranscriptHash, ConfirmedTranscriptHash, EncryptedGroupSecrets,
-    ExportedTree, GroupInfo, GroupState,
+    ExportedTree, GroupContext, GroupInfo, GroupState,
 };
 
 use super::message_processor::ProvisionalState;
@@ -52,6 +58,36 @@ pub(crate) async fn validate_group_info_common<C: CipherSuiteProvider>(
     Ok(())
 }
 
+// Ratchet tree sent in the extensions of `group_info`, decompressing it if
+// needed
+fn group_info_tree(group_info: &GroupInfo) -> Result<Option<ExportedTree<'static>>, MlsError> {
+    if let Some(ext) = group_info.extensions.get_as::<RatchetTreeExt>()? {
+        return Ok(Some(ext.tree_data));
+    }
+
+    #[cfg(feature = "tree_compression")]
+    if let Some(ext) = group_info.extensions.get_as::<CompressedRatchetTreeExt>()? {
+        return ext.tree().map(Some);
+    }
+
+    Ok(None)
+}
+
+#[cfg(feature = "tree_fetcher")]
+pub(crate) fn group_info_has_tree(group_info: &GroupInfo) -> bool {
+    #[cfg(feature = "tree_compression")]
+    if group_info
+        .extensions
+        .has_extension(CompressedRatchetTreeExt::extension_type())
+    {
+        return true;
+    }
+
+    group_info
+        .extensions
+        .has_extension(mls_rs_core::extension::ExtensionType::RATCHET_TREE)
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 pub(crate) async fn validate_group_info_member<C: CipherSuiteProvider>(
     self_state: &GroupState,
@@ -63,8 +99,8 @@ pub(crate) async fn validate_group_info_member<C: CipherSuiteProvider>(
 
     let self_tree = ExportedTree::new_borrowed(&self_state.public_tree.nodes);
 
-    if let Some(tree) = group_info.extensions.get_as::<RatchetTreeExt>()? {
-        (tree.tree_data == self_tree)
+    if let Some(tree) = group_info_tree(group_info)? {
+        (tree == self_tree)
             .then_some(())
             .ok_or(MlsError::InvalidGroupInfo)?;
     }
@@ -89,13 +125,29 @@ where
     C: CipherSuiteProvider,
     I: IdentityProvider,
 {
-    let tree = match group_info.extensions.get_as::<RatchetTreeExt>()? {
-        Some(ext) => ext.tree_data,
+    let tree = match group_info_tree(group_info)? {
+        Some(tree) => tree,
         None => tree.ok_or(MlsError::RatchetTreeNotFound)?,
     };
 
-    let context = &group_info.group_context;
+    let tree = validate_tree_joiner(&group_info.group_context, tree, id_provider, cs).await?;
+
+    validate_group_info_common(msg_version, group_info, &tree, cs).await?;
+
+    Ok(tree)
+}
 
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn validate_tree_joiner<C, I>(
+    context: &GroupContext,
+    tree: ExportedTree<'_>,
+    id_provider: &I,
+    cs: &C,
+) -> Result<TreeKemPublic, MlsError>
+where
+    C: CipherSuiteProvider,
+    I: IdentityProvider,
+{
     let mut tree =
         TreeKemPublic::import_node_data(tree.into(), id_provider, &context.extensions).await?;
 
@@ -113,8 +165,6 @@ where
             .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
     }
 
-    validate_group_info_common(msg_version, group_info, &tree, cs).await?;
-
     Ok(tree)
 }
 
@@ -0,0 +1,274 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Write};
+use mls_rs_codec::MlsEncode;
+use mls_rs_core::{
+    crypto::{CipherSuiteProvider, SignaturePublicKey},
+    error::IntoAnyError,
+};
+
+use crate::{client::MlsError, client_config::ClientConfig, group::Group};
+
+const VERIFICATION_CODE_LABEL: &[u8] = b"mls-rs verification code";
+
+// Each chunk of 5 bytes is reduced to 5 decimal digits.
+const CHUNK_SIZE: usize = 5;
+const CHUNK_MODULUS: u64 = 100_000;
+
+/// Short numeric code that members of a group can compare out of band to
+/// verify that they share the same view of the group.
+///
+/// The code is derived from the confirmed transcript hash of an epoch and the
+/// signature keys of all members in that epoch. Two members obtain the same
+/// code only if they agree on the history of the group and on its roster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationCode {
+    digits: String,
+}
+
+impl VerificationCode {
+    /// Maximum number of digits in a verification code.
+    pub const MAX_LENGTH: usize = 60;
+
+    /// Digits of the code without any formatting.
+    pub fn as_str(&self) -> &str {
+        &self.digits
+    }
+
+    /// Digits of the code split into groups of `group_size` digits separated
+    /// by spaces, e.g. `"12345 67890"` for a group size of 5.
+    pub fn formatted(&self, group_size: usize) -> String {
+        let group_size = group_size.max(1);
+        let mut formatted = String::with_capacity(self.digits.len() * 2);
+
+        for (i, digit) in self.digits.chars().enumerate() {
+            if i > 0 && i % group_size == 0 {
+                formatted.push(' ');
+            }
+
+            formatted.push(digit);
+        }
+
+        formatted
+    }
+
+    /// Check if `code` matches this code. Whitespace in `code` is ignored so
+    /// that [formatted](VerificationCode::formatted) codes can be compared.
+    pub fn matches(&self, code: &str) -> bool {
+        code.chars()
+            .filter(|c| !c.is_whitespace())
+            .eq(self.digits.chars())
+    }
+}
+
+impl Display for VerificationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.formatted(CHUNK_SIZE))
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+async fn compute_verification_code<'a, P, I>(
+    cipher_suite_provider: &P,
+    confirmed_transcript_hash: &[u8],
+    signature_keys: I,
+    len: usize,
+) -> Result<VerificationCode, MlsError>
+where
+    P: CipherSuiteProvider,
+    I: Iterator<Item = Option<&'a SignaturePublicKey>>,
+{
+    if len == 0 || len > VerificationCode::MAX_LENGTH {
+        return Err(MlsError::InvalidVerificationCodeLength(len));
+    }
+
+    let mut input = Vec::new();
+    confirmed_transcript_hash.mls_encode(&mut input)?;
+
+    for key in signature_keys {
+        key.mls_encode(&mut input)?;
+    }
+
+    let prk = cipher_suite_provider
+        .kdf_extract(&[], &input)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+    let chunks = (len + CHUNK_SIZE - 1) / CHUNK_SIZE;
+
+    let okm = cipher_suite_provider
+        .kdf_expand(&prk, VERIFICATION_CODE_LABEL, chunks * CHUNK_SIZE)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+    let mut digits = String::with_capacity(chunks * CHUNK_SIZE);
+
+    for chunk in okm.chunks(CHUNK_SIZE) {
+        let value = chunk.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+
+        // Writing to a String can't fail.
+        let _ = write!(digits, "{:05}", value % CHUNK_MODULUS);
+    }
+
+    digits.truncate(len);
+
+    Ok(VerificationCode { digits })
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Compute a verification code with `len` digits for the current epoch.
+    ///
+    /// Members can compare the code out of band, e.g. by reading it out
+    /// loud, to detect a compromised delivery service presenting them with
+    /// different views of the group. `len` must be between 1 and
+    /// [`VerificationCode::MAX_LENGTH`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verification_code(&self, len: usize) -> Result<VerificationCode, MlsError> {
+        let signature_keys = self
+            .state
+            .public_tree
+            .leaves()
+            .map(|leaf| leaf.map(|leaf| &leaf.signing_identity.signature_key));
+
+        compute_verification_code(
+            &self.cipher_suite_provider,
+            &self.context().confirmed_transcript_hash,
+            signature_keys,
+            len,
+        )
+        .await
+    }
+
+    /// Check if `code` is the verification code of `epoch`.
+    ///
+    /// Codes of past epochs can be verified as long as the epoch is kept in
+    /// the group state storage. Whitespace in `code` is ignored.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_verification_code(
+        &mut self,
+        epoch: u64,
+        code: &str,
+    ) -> Result<bool, MlsError> {
+        let len = code.chars().filter(|c| !c.is_whitespace()).count();
+
+        if epoch == self.current_epoch() {
+            return Ok(self.verification_code(len).await?.matches(code));
+        }
+
+        #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+        if let Some(prior_epoch) = self.state_repo.get_epoch_mut(epoch).await? {
+            let signature_keys = prior_epoch.signature_public_keys.iter().map(Option::as_ref);
+
+            let expected = compute_verification_code(
+                &self.cipher_suite_provider,
+                &prior_epoch.context.confirmed_transcript_hash,
+                signature_keys,
+                len,
+            )
+            .await?;
+
+            return Ok(expected.matches(code));
+        }
+
+        Err(MlsError::EpochNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        group::test_utils::test_group,
+    };
+
+    use super::VerificationCode;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_compute_same_code() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (bob, _) = alice.join("bob").await;
+
+        let alice_code = alice.group.verification_code(30).await.unwrap();
+        let bob_code = bob.group.verification_code(30).await.unwrap();
+
+        assert_eq!(alice_code, bob_code);
+        assert_eq!(alice_code.as_str().len(), 30);
+        assert!(alice_code.as_str().chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn code_changes_with_epoch() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let old_code = alice.group.verification_code(20).await.unwrap();
+        let old_epoch = alice.group.current_epoch();
+
+        let commit = alice.group.commit(vec![]).await.unwrap().commit_message;
+        alice.group.apply_pending_commit().await.unwrap();
+        bob.group.process_incoming_message(commit).await.unwrap();
+
+        let new_code = alice.group.verification_code(20).await.unwrap();
+        assert_ne!(old_code, new_code);
+
+        let current_epoch = bob.group.current_epoch();
+
+        let verified = bob
+            .group
+            .verify_verification_code(current_epoch, &new_code.to_string())
+            .await
+            .unwrap();
+
+        assert!(verified);
+
+        #[cfg(all(feature = "prior_epoch", feature = "private_message"))]
+        {
+            let verified = bob
+                .group
+                .verify_verification_code(old_epoch, &old_code.to_string())
+                .await
+                .unwrap();
+
+            assert!(verified);
+        }
+
+        #[cfg(not(all(feature = "prior_epoch", feature = "private_message")))]
+        let _ = old_epoch;
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn invalid_code_length_is_rejected() {
+        let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let res = alice
+            .group
+            .verification_code(VerificationCode::MAX_LENGTH + 1)
+            .await;
+
+        assert_matches!(res, Err(MlsError::InvalidVerificationCodeLength(_)));
+    }
+
+    #[test]
+    fn code_formatting() {
+        let code = VerificationCode {
+            digits: "123456789012".into(),
+        };
+
+        assert_eq!(code.formatted(4), "1234 5678 9012");
+        assert_eq!(code.to_string(), "12345 67890 12");
+        assert!(code.matches("1234 5678 9012"));
+        assert!(!code.matches("1234 5678 9013"));
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec;
+use alloc::vec::Vec;
+use mls_rs_core::{
+    crypto::SignatureSecretKey,
+    error::IntoAnyError,
+    identity::{IdentityProvider, SigningIdentity},
+};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{
+        framing::MlsMessagePayload, message_processor::validate_key_package, EncryptedGroupSecrets,
+        ExportedTree, Group, GroupContext, GroupSecrets, NewMemberInfo, Welcome,
+    },
+    tree_kem::{
+        hpke_encryption::HpkeEncryptable, leaf_node::LeafNode, node::LeafIndex, TreeKemPublic,
+    },
+    MlsMessage,
+};
+
+/// Secrets of the welcome created by the last commit of this member, kept
+/// so that they can be encrypted again to newer key packages of the added
+/// members.
+#[derive(Clone, Debug)]
+pub(crate) struct RetargetableWelcome {
+    group_context: GroupContext,
+    encrypted_group_info: Vec<u8>,
+    members: Vec<(SigningIdentity, GroupSecrets)>,
+}
+
+impl RetargetableWelcome {
+    pub(super) fn new(
+        group_context: &GroupContext,
+        encrypted_group_info: &[u8],
+        members: Vec<(SigningIdentity, GroupSecrets)>,
+    ) -> Option<Self> {
+        (!members.is_empty()).then(|| Self {
+            group_context: group_context.clone(),
+            encrypted_group_info: encrypted_group_info.to_vec(),
+            members,
+        })
+    }
+}
+
+// A retargeted welcome carries the leaf node of the original key package in
+// the ratchet tree. The joiner finds it by its signature key, which is unique
+// within the tree, and its encryption key, which the rotated key package must
+// keep so that the joiner can decrypt path secrets sent to the leaf.
+pub(crate) fn find_retargeted_leaf(
+    public_tree: &TreeKemPublic,
+    leaf_node: &LeafNode,
+) -> Option<LeafIndex> {
+    public_tree.non_empty_leaves().find_map(|(index, leaf)| {
+        (leaf.signing_identity.signature_key == leaf_node.signing_identity.signature_key
+            && leaf.public_key == leaf_node.public_key)
+            .then_some(index)
+    })
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn join_retargeted(
+        welcome: &MlsMessage,
+        tree_data: Option<ExportedTree<'_>>,
+        config: C,
+        signer: SignatureSecretKey,
+    ) -> Result<(Self, NewMemberInfo), MlsError> {
+        Self::from_welcome_message(
+            welcome,
+            tree_data,
+            #[cfg(feature = "tree_fetcher")]
+            None::<&super::tree_fetcher::NoTreeFetcher>,
+            config,
+            signer,
+            #[cfg(feature = "psk")]
+            None,
+            true,
+        )
+        .await
+    }
+
+    /// Create a welcome message for `key_package` to replace a welcome
+    /// created by the last commit of this member.
+    ///
+    /// This is useful when a new member rotated its key package after it
+    /// was added but before it fetched the welcome, and can therefore no
+    /// longer decrypt it. The group secrets sent to the member are
+    /// encrypted again to `key_package`, avoiding a remove and re-add of
+    /// the member.
+    ///
+    /// `key_package` must be a valid key package for the group, whose
+    /// signing identity is a
+    /// [valid successor](crate::IdentityProvider::valid_successor) of one
+    /// of the members added by the commit and uses the same signature key.
+    /// Otherwise [`MlsError::InvalidSuccessor`] is returned. Its leaf node
+    /// must also keep the encryption key of the added member, as done by
+    /// [`Client::rotate_key_package_init_key`](crate::Client::rotate_key_package_init_key).
+    /// Otherwise [`MlsError::RetargetedEncryptionKeyMismatch`] is returned.
+    ///
+    /// The secrets of the welcome are only kept in memory until this member
+    /// creates another commit, and can only be used while the group is in
+    /// the epoch created by the commit. Otherwise
+    /// [`MlsError::NoRetargetableWelcome`] is returned.
+    ///
+    /// The new member must join with
+    /// [`Client::join_group_retargeted`](crate::Client::join_group_retargeted),
+    /// as its leaf in the ratchet tree still contains the leaf node of the
+    /// original key package.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn retarget_welcome(&self, key_package: &MlsMessage) -> Result<MlsMessage, MlsError> {
+        let welcome = self
+            .retargetable_welcome
+            .as_ref()
+            .filter(|welcome| &welcome.group_context == self.context())
+            .ok_or(MlsError::NoRetargetableWelcome)?;
+
+        let MlsMessagePayload::KeyPackage(key_package) = &key_package.payload else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        if key_package.cipher_suite != self.cipher_suite() {
+            return Err(MlsError::CipherSuiteMismatch);
+        }
+
+        let identity_provider = self.config.identity_provider();
+
+        validate_key_package(
+            key_package,
+            self.protocol_version(),
+            &self.cipher_suite_provider,
+            &identity_provider,
+        )
+        .await?;
+
+        let new_identity = &key_package.leaf_node.signing_identity;
+        let mut group_secrets = None;
+
+        for (identity, secrets) in &welcome.members {
+            if identity.signature_key != new_identity.signature_key {
+                continue;
+            }
+
+            let valid_successor = identity_provider
+                .valid_successor(identity, new_identity, &self.context().extensions)
+                .await
+                .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
+
+            if valid_successor {
+                group_secrets = Some(secrets);
+                break;
+            }
+        }
+
+        let group_secrets = group_secrets.ok_or(MlsError::InvalidSuccessor)?;
+
+        find_retargeted_leaf(&self.state.public_tree, &key_package.leaf_node)
+            .ok_or(MlsError::RetargetedEncryptionKeyMismatch)?;
+
+        let encrypted_group_secrets = group_secrets
+            .encrypt(
+                &self.cipher_suite_provider,
+                &key_package.hpke_init_key,
+                &welcome.encrypted_group_info,
+            )
+            .await?;
+
+        let secrets = EncryptedGroupSecrets {
+            new_member: key_package
+                .to_reference(&self.cipher_suite_provider)
+                .await?,
+            encrypted_group_secrets,
+        };
+
+        Ok(MlsMessage::new(
+            self.protocol_version(),
+            MlsMessagePayload::Welcome(Welcome {
+                cipher_suite: self.cipher_suite(),
+                secrets: vec![secrets],
+                encrypted_group_info: welcome.encrypted_group_info.clone(),
+            }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::{
+            test_utils::{test_client_with_key_pkg, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        client_config::ClientConfig,
+        crypto::test_utils::test_cipher_suite_provider,
+        group::test_utils::test_group,
+        MlsMessage,
+    };
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn key_package_ref(key_package: &MlsMessage) -> Vec<u8> {
+        key_package
+            .clone()
+            .into_key_package()
+            .unwrap()
+            .to_reference(&test_cipher_suite_provider(TEST_CIPHER_SUITE))
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_can_be_retargeted_to_rotated_key_package() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (bob, old_key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let commit = alice
+            .group
+            .commit_builder()
+            .add_member(old_key_package.clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.process_pending_commit().await.unwrap();
+
+        // Bob rotates the key package, which erases the init key used to
+        // encrypt the welcome.
+        let new_key_package = bob
+            .rotate_key_package_init_key(&old_key_package)
+            .await
+            .unwrap();
+
+        let old_key_package_ref = key_package_ref(&old_key_package).await;
+        assert!(bob
+            .config
+            .key_package_repo()
+            .get(&old_key_package_ref)
+            .is_none());
+
+        let res = bob
+            .join_group_retargeted(None, &commit.welcome_messages[0])
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::WelcomeKeyPackageNotFound));
+
+        let welcome = alice
+            .group
+            .retarget_welcome(&new_key_package)
+            .await
+            .unwrap();
+
+        // The leaf in the tree is not the leaf of the new key package.
+        let res = bob.join_group(None, &welcome).await.map(|_| ());
+        assert_matches!(res, Err(MlsError::WelcomeKeyPackageNotFound));
+
+        let (mut bob_group, _) = bob.join_group_retargeted(None, &welcome).await.unwrap();
+
+        assert_eq!(bob_group.current_epoch(), alice.group.current_epoch());
+
+        // Bob holds the secret encryption key of the leaf, so path secrets
+        // sent by other members can be decrypted.
+        let commit = alice.group.commit(vec![]).await.unwrap();
+        alice.process_pending_commit().await.unwrap();
+
+        bob_group
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        let alice_secret = alice.group.export_secret(b"label", b"", 32).await.unwrap();
+        let bob_secret = bob_group.export_secret(b"label", b"", 32).await.unwrap();
+
+        assert_eq!(alice_secret, bob_secret);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_is_not_retargeted_to_other_identity() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (_, bob_key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let (_, carol_key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await;
+
+        let res = alice.group.retarget_welcome(&carol_key_package).await;
+        assert_matches!(res, Err(MlsError::NoRetargetableWelcome));
+
+        alice
+            .group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.process_pending_commit().await.unwrap();
+
+        let res = alice.group.retarget_welcome(&carol_key_package).await;
+        assert_matches!(res, Err(MlsError::InvalidSuccessor));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_is_not_retargeted_to_other_encryption_key() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (bob, key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        alice
+            .group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.process_pending_commit().await.unwrap();
+
+        let new_key_package = bob.generate_key_package_message().await.unwrap();
+        let res = alice.group.retarget_welcome(&new_key_package).await;
+
+        assert_matches!(res, Err(MlsError::RetargetedEncryptionKeyMismatch));
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_core::{error::IntoAnyError, secret::Secret};
+
+use crate::{
+    client::MlsError,
+    client_config::ClientConfig,
+    group::{framing::MlsMessagePayload, Group},
+    map::LargeMap,
+    CipherSuiteProvider, MlsMessage,
+};
+
+const WIRE_GROUP_ID_LABEL: &[u8] = b"wire group id";
+
+/// Compute the group id that is placed on the wire in place of `group_id`
+/// for an epoch with the given `salt`.
+///
+/// The salt for an epoch is obtained by a group member using
+/// [`Group::wire_group_id_salt`] and is meant to be shared with the
+/// delivery service so that it can map wire group ids back to groups,
+/// for example using a [`WireGroupIdMap`].
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn wire_group_id<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    group_id: &[u8],
+    salt: &[u8],
+) -> Result<Vec<u8>, MlsError> {
+    cipher_suite_provider
+        .kdf_extract(salt, group_id)
+        .await
+        .map(|id| id.to_vec())
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+}
+
+/// Delivery service side lookup table from wire group ids, as produced when
+/// [`EncryptionOptions::with_hide_group_id`](crate::mls_rules::EncryptionOptions::with_hide_group_id)
+/// is enabled, to the true group id and epoch.
+#[derive(Clone, Default)]
+pub struct WireGroupIdMap {
+    entries: LargeMap<Vec<u8>, (Vec<u8>, u64)>,
+}
+
+impl Debug for WireGroupIdMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WireGroupIdMap")
+            .field("len", &self.entries.len())
+            .finish()
+    }
+}
+
+impl WireGroupIdMap {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register the `salt` shared by a member of `group_id` for `epoch`.
+    /// Returns the wire group id that will be used by messages sent during
+    /// `epoch`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn insert<P: CipherSuiteProvider>(
+        &mut self,
+        cipher_suite_provider: &P,
+        group_id: Vec<u8>,
+        epoch: u64,
+        salt: &[u8],
+    ) -> Result<Vec<u8>, MlsError> {
+        let wire_id = wire_group_id(cipher_suite_provider, &group_id, salt).await?;
+        self.entries.insert(wire_id.clone(), (group_id, epoch));
+
+        Ok(wire_id)
+    }
+
+    /// Find the true group id and epoch of a wire group id.
+    pub fn resolve(&self, wire_group_id: &[u8]) -> Option<(&[u8], u64)> {
+        self.entries
+            .get(wire_group_id)
+            .map(|(group_id, epoch)| (group_id.as_slice(), *epoch))
+    }
+
+    /// Replace the group id of `message` with its true value if it is a
+    /// registered wire group id. Messages that are not recognized are
+    /// returned unchanged.
+    pub fn reveal(&self, mut message: MlsMessage) -> MlsMessage {
+        if let Some(group_id) = message_group_id_mut(&mut message) {
+            if let Some((true_id, _)) = self.entries.get(group_id.as_slice()) {
+                *group_id = true_id.clone();
+            }
+        }
+
+        message
+    }
+
+    /// Remove all entries for epochs of `group_id` that are older than
+    /// `min_epoch`.
+    pub fn remove_epochs_before(&mut self, group_id: &[u8], min_epoch: u64) {
+        self.entries
+            .retain(|_, (id, epoch)| id.as_slice() != group_id || *epoch >= min_epoch);
+    }
+
+    /// Remove all entries for `group_id`.
+    pub fn remove_group(&mut self, group_id: &[u8]) {
+        self.entries.retain(|_, (id, _)| id.as_slice() != group_id);
+    }
+}
+
+fn message_group_id_mut(message: &mut MlsMessage) -> Option<&mut Vec<u8>> {
+    match &mut message.payload {
+        MlsMessagePayload::Cipher(ciphertext) => Some(&mut ciphertext.group_id),
+        MlsMessagePayload::Plain(plaintext) => Some(&mut plaintext.content.group_id),
+        _ => None,
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Salt used to compute the wire group id for the current epoch.
+    ///
+    /// This value is derived from the exporter secret of the current epoch
+    /// and should be shared with the delivery service (e.g. using a
+    /// [`WireGroupIdMap`]) when
+    /// [`EncryptionOptions::with_hide_group_id`](crate::mls_rules::EncryptionOptions::with_hide_group_id)
+    /// is enabled.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn wire_group_id_salt(&self) -> Result<Secret, MlsError> {
+        self.export_secret(
+            WIRE_GROUP_ID_LABEL,
+            &self.current_epoch().to_be_bytes(),
+            self.cipher_suite_provider.kdf_extract_size(),
+        )
+        .await
+    }
+
+    /// Group id placed on the wire for encrypted messages sent during the
+    /// current epoch when
+    /// [`EncryptionOptions::with_hide_group_id`](crate::mls_rules::EncryptionOptions::with_hide_group_id)
+    /// is enabled.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn wire_group_id(&self) -> Result<Vec<u8>, MlsError> {
+        let salt = self.wire_group_id_salt().await?;
+        wire_group_id(&self.cipher_suite_provider, self.group_id(), &salt).await
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn conceal_group_id(
+        &self,
+        mut message: MlsMessage,
+    ) -> Result<MlsMessage, MlsError> {
+        if let MlsMessagePayload::Cipher(ciphertext) = &mut message.payload {
+            ciphertext.group_id = self.wire_group_id().await?;
+        }
+
+        Ok(message)
+    }
+
+    /// Messages carrying the wire group id of the current epoch are
+    /// restored to the true group id. Messages from prior epochs are
+    /// processed only if the delivery service already restored their
+    /// group id.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn reveal_group_id(
+        &self,
+        mut message: MlsMessage,
+    ) -> Result<MlsMessage, MlsError> {
+        let concealed = !self.is_strict()
+            && message.epoch() == Some(self.current_epoch())
+            && message
+                .group_id()
+                .map_or(false, |group_id| group_id != self.group_id());
+
+        if !concealed {
+            return Ok(message);
+        }
+
+        let wire_id = self.wire_group_id().await?;
+
+        if let Some(group_id) = message_group_id_mut(&mut message) {
+            if *group_id == wire_id {
+                *group_id = self.group_id().to_vec();
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        crypto::test_utils::test_cipher_suite_provider,
+        group::{
+            mls_rules::{DefaultMlsRules, EncryptionOptions},
+            padding::PaddingMode,
+            test_utils::{test_group_custom_config, TEST_GROUP},
+            ReceivedMessage,
+        },
+    };
+
+    use super::WireGroupIdMap;
+
+    fn hiding_rules() -> DefaultMlsRules {
+        DefaultMlsRules::default().with_encryption_options(
+            EncryptionOptions::new(true, PaddingMode::None).with_hide_group_id(true),
+        )
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn application_message_uses_wire_group_id() {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(hiding_rules())
+        })
+        .await;
+
+        let (mut bob, _) = alice.join("bob").await;
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        let wire_id = alice.group.wire_group_id().await.unwrap();
+
+        assert_eq!(message.group_id(), Some(wire_id.as_slice()));
+        assert_ne!(wire_id, TEST_GROUP);
+
+        let received = bob.process_message(message).await.unwrap();
+
+        assert_matches::assert_matches!(
+            received,
+            ReceivedMessage::ApplicationMessage(m) if m.data() == b"hello"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn wire_group_id_changes_every_epoch() {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(hiding_rules())
+        })
+        .await;
+
+        let before = alice.group.wire_group_id().await.unwrap();
+        alice.group.commit(vec![]).await.unwrap();
+        alice.group.apply_pending_commit().await.unwrap();
+        let after = alice.group.wire_group_id().await.unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn server_map_resolves_wire_group_id() {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(hiding_rules())
+        })
+        .await;
+
+        let salt = alice.group.wire_group_id_salt().await.unwrap();
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let mut map = WireGroupIdMap::new();
+
+        let wire_id = map
+            .insert(&cs, TEST_GROUP.to_vec(), alice.group.current_epoch(), &salt)
+            .await
+            .unwrap();
+
+        let group_wire_id = alice.group.wire_group_id().await.unwrap();
+        assert_eq!(wire_id, group_wire_id);
+        assert_eq!(map.resolve(&wire_id), Some((TEST_GROUP, 0)));
+
+        let message = alice
+            .group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        assert_eq!(map.reveal(message).group_id(), Some(TEST_GROUP));
+
+        map.remove_group(TEST_GROUP);
+        assert_eq!(map.resolve(&wire_id), None);
+    }
+}
@@ -5,6 +5,26 @@
 /// Basic credential identity provider.
 pub mod basic;
 
+/// Device attestation verification for member keys held in attested hardware.
+#[cfg(feature = "device_attestation")]
+pub mod attestation;
+
+/// Blinded credentials of hidden members that only admins can unblind.
+#[cfg(feature = "hidden_members")]
+pub mod blinded;
+
+/// Identity provider for members authenticated by a decentralized identifier.
+#[cfg(feature = "did_credential")]
+pub mod did;
+
+/// Identity provider for members authenticated by a JSON Web Token.
+#[cfg(feature = "jwt_credential")]
+pub mod jwt;
+
+/// Key transparency verification of member signature keys.
+#[cfg(feature = "key_transparency")]
+pub mod transparency;
+
 /// X.509 certificate identity provider.
 #[cfg(feature = "x509")]
 pub mod x509 {
@@ -112,7 +132,11 @@ pub(crate) mod test_utils {
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
     impl IdentityProvider for BasicWithCustomProvider {
         type Error = BasicWithCustomProviderError;
 
@@ -0,0 +1,336 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use mls_rs_core::{
+    error::{AnyError, IntoAnyError},
+    extension::ExtensionList,
+    identity::{CredentialType, IdentityProvider, SigningIdentity},
+    time::MlsTime,
+};
+
+use crate::extension::DeviceAttestationExt;
+
+/// Verifier of device attestations presented by members in a
+/// [`DeviceAttestationExt`].
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait AttestationVerifier: Send + Sync {
+    /// Error type that this verifier returns on internal failure or if an
+    /// attestation is invalid.
+    type Error: IntoAnyError;
+
+    /// Determine if `attestation` proves that the signature key of
+    /// `signing_identity` is held in attested hardware.
+    async fn verify(
+        &self,
+        signing_identity: &SigningIdentity,
+        attestation: &[u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[non_exhaustive]
+/// Error returned by an [`AttestedIdentityProvider`].
+pub enum AttestedIdentityProviderError {
+    #[cfg_attr(feature = "std", error(transparent))]
+    IdentityProviderError(AnyError),
+    #[cfg_attr(feature = "std", error("invalid device attestation: {0}"))]
+    InvalidAttestation(AnyError),
+    #[cfg_attr(feature = "std", error("device attestation required but not found"))]
+    MissingAttestation,
+}
+
+impl IntoAnyError for AttestedIdentityProviderError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+/// Identity provider that verifies the [`DeviceAttestationExt`] of members
+/// with an [`AttestationVerifier`] in addition to validating their identity
+/// with an inner identity provider.
+///
+/// Groups can require all members to present an attestation with
+/// [`DeviceAttestationRequiredExt`](crate::extension::built_in::DeviceAttestationRequiredExt).
+/// Independently of the group, [`AttestedIdentityProvider::with_required`]
+/// makes this client reject members without an attestation.
+#[derive(Clone, Debug)]
+pub struct AttestedIdentityProvider<I, V> {
+    inner: I,
+    verifier: V,
+    required: bool,
+}
+
+impl<I, V> AttestedIdentityProvider<I, V>
+where
+    I: IdentityProvider,
+    V: AttestationVerifier,
+{
+    pub fn new(inner: I, verifier: V) -> Self {
+        Self {
+            inner,
+            verifier,
+            required: false,
+        }
+    }
+
+    /// Reject members without a [`DeviceAttestationExt`] even if the group
+    /// does not require one.
+    pub fn with_required(self, required: bool) -> Self {
+        Self { required, ..self }
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<I, V> IdentityProvider for AttestedIdentityProvider<I, V>
+where
+    I: IdentityProvider,
+    V: AttestationVerifier,
+{
+    type Error = AttestedIdentityProviderError;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_member(signing_identity, timestamp, extensions)
+            .await
+            .map_err(|e| AttestedIdentityProviderError::IdentityProviderError(e.into_any_error()))
+    }
+
+    async fn validate_leaf_node_extensions(
+        &self,
+        signing_identity: &SigningIdentity,
+        leaf_node_extensions: &ExtensionList,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_leaf_node_extensions(signing_identity, leaf_node_extensions, extensions)
+            .await
+            .map_err(|e| {
+                AttestedIdentityProviderError::IdentityProviderError(e.into_any_error())
+            })?;
+
+        let attestation = leaf_node_extensions
+            .get_as::<DeviceAttestationExt>()
+            .map_err(|e| AttestedIdentityProviderError::InvalidAttestation(e.into_any_error()))?;
+
+        match attestation {
+            Some(ext) => self
+                .verifier
+                .verify(signing_identity, &ext.attestation)
+                .await
+                .map_err(|e| AttestedIdentityProviderError::InvalidAttestation(e.into_any_error())),
+            None if self.required => Err(AttestedIdentityProviderError::MissingAttestation),
+            None => Ok(()),
+        }
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_external_sender(signing_identity, timestamp, extensions)
+            .await
+            .map_err(|e| AttestedIdentityProviderError::IdentityProviderError(e.into_any_error()))
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner
+            .identity(signing_identity, extensions)
+            .await
+            .map_err(|e| AttestedIdentityProviderError::IdentityProviderError(e.into_any_error()))
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        self.inner
+            .valid_successor(predecessor, successor, extensions)
+            .await
+            .map_err(|e| AttestedIdentityProviderError::IdentityProviderError(e.into_any_error()))
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        self.inner.supported_types()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        extension::{ExtensionList, ExtensionType},
+        identity::SigningIdentity,
+    };
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        client_builder::{BaseConfig, ClientBuilder, WithCryptoProvider, WithIdentityProvider},
+        crypto::test_utils::TestCryptoProvider,
+        extension::built_in::{
+            DeviceAttestationExt, DeviceAttestationRequiredExt, DEVICE_ATTESTATION_EXTENSION_TYPE,
+            DEVICE_ATTESTATION_REQUIRED_EXTENSION_TYPE,
+        },
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        Client, MlsMessage,
+    };
+
+    use super::{AttestationVerifier, AttestedIdentityProvider};
+
+    /// Accepts attestations that equal the attested signature key.
+    #[derive(Clone, Debug)]
+    struct TestVerifier;
+
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(thiserror::Error))]
+    #[cfg_attr(feature = "std", error("attestation does not match signature key"))]
+    struct TestVerifierError;
+
+    impl mls_rs_core::error::IntoAnyError for TestVerifierError {
+        #[cfg(feature = "std")]
+        fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+            Ok(self.into())
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
+    impl AttestationVerifier for TestVerifier {
+        type Error = TestVerifierError;
+
+        async fn verify(
+            &self,
+            signing_identity: &SigningIdentity,
+            attestation: &[u8],
+        ) -> Result<(), Self::Error> {
+            (attestation == signing_identity.signature_key.as_bytes())
+                .then_some(())
+                .ok_or(TestVerifierError)
+        }
+    }
+
+    type AttestedConfig = WithIdentityProvider<
+        AttestedIdentityProvider<BasicIdentityProvider, TestVerifier>,
+        WithCryptoProvider<TestCryptoProvider, BaseConfig>,
+    >;
+
+    enum Attestation {
+        Valid,
+        Invalid,
+        Missing,
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn attested_client(name: &str, attestation: Attestation) -> Client<AttestedConfig> {
+        let (identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, name.as_bytes()).await;
+
+        let mut leaf_node_extensions = ExtensionList::new();
+
+        let blob = match attestation {
+            Attestation::Valid => Some(identity.signature_key.to_vec()),
+            Attestation::Invalid => Some(vec![0; 32]),
+            Attestation::Missing => None,
+        };
+
+        if let Some(blob) = blob {
+            leaf_node_extensions
+                .set_from(DeviceAttestationExt::new(blob))
+                .unwrap();
+        }
+
+        ClientBuilder::new()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(AttestedIdentityProvider::new(
+                BasicIdentityProvider::new(),
+                TestVerifier,
+            ))
+            .extension_types([
+                ExtensionType::new(DEVICE_ATTESTATION_EXTENSION_TYPE),
+                ExtensionType::new(DEVICE_ATTESTATION_REQUIRED_EXTENSION_TYPE),
+            ])
+            .leaf_node_extensions(leaf_node_extensions)
+            .signing_identity(identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn add_to_attested_group(attestation: Attestation) -> Result<MlsMessage, MlsError> {
+        let alice = attested_client("alice", Attestation::Valid).await;
+
+        let mut group_extensions = ExtensionList::new();
+
+        group_extensions
+            .set_from(DeviceAttestationRequiredExt::default())
+            .unwrap();
+
+        let mut group = alice.create_group(group_extensions).await.unwrap();
+
+        let key_package = attested_client("bob", attestation)
+            .await
+            .generate_key_package_message()
+            .await
+            .unwrap();
+
+        let output = group
+            .commit_builder()
+            .add_member(key_package)?
+            .build()
+            .await?;
+
+        Ok(output.commit_message)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn attested_member_can_be_added() {
+        add_to_attested_group(Attestation::Valid).await.unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_without_attestation_is_rejected() {
+        let res = add_to_attested_group(Attestation::Missing).await;
+        assert_matches!(res, Err(MlsError::DeviceAttestationRequired));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_with_invalid_attestation_is_rejected() {
+        let res = add_to_attested_group(Attestation::Invalid).await;
+        assert_matches!(res, Err(MlsError::IdentityProviderError(_)));
+    }
+}
@@ -0,0 +1,400 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::{CipherSuiteProvider, HpkeCiphertext, HpkePublicKey, HpkeSecretKey},
+    error::{AnyError, IntoAnyError},
+    extension::{ExtensionList, ExtensionType},
+    identity::{
+        Credential, CredentialType, CustomCredential, IdentityProvider, MlsCredential,
+        SigningIdentity,
+    },
+    time::MlsTime,
+};
+
+use crate::{
+    client::MlsError, extension::HIDDEN_MEMBERS_EXTENSION_TYPE, hash_reference::HashReference,
+};
+
+/// Credential type of [`BlindedCredential`], taken from the private use range.
+pub const BLINDED_CREDENTIAL_TYPE: u16 = 0xF0B0;
+
+const BLINDING_LABEL: &[u8] = b"mls-rs blinded credential";
+
+#[derive(MlsSize, MlsEncode, MlsDecode)]
+struct BlindedIdentity {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    salt: Vec<u8>,
+    credential: Credential,
+}
+
+impl BlindedIdentity {
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn commitment<P: CipherSuiteProvider>(
+        &self,
+        cipher_suite: &P,
+    ) -> Result<HashReference, MlsError> {
+        HashReference::compute(&self.mls_encode_to_vec()?, BLINDING_LABEL, cipher_suite).await
+    }
+}
+
+/// Credential of a hidden member.
+///
+/// Regular members only see a salted commitment to the real credential of
+/// the member. The real credential is sealed to the admin key of the
+/// [`HiddenMembersExt`](crate::extension::HiddenMembersExt) of the group and
+/// can be recovered with [`BlindedCredential::unblind`] by admins holding the
+/// corresponding secret key.
+#[derive(Clone, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct BlindedCredential {
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    commitment: Vec<u8>,
+    sealed_identity: HpkeCiphertext,
+}
+
+impl Debug for BlindedCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlindedCredential")
+            .field(
+                "commitment",
+                &mls_rs_core::debug::pretty_bytes(&self.commitment),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlindedCredential {
+    /// Blind `credential` and seal it to `admin_key`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn new<P: CipherSuiteProvider>(
+        cipher_suite: &P,
+        credential: Credential,
+        admin_key: &HpkePublicKey,
+    ) -> Result<Self, MlsError> {
+        let salt = cipher_suite
+            .random_bytes_vec(cipher_suite.kdf_extract_size())
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        let identity = BlindedIdentity { salt, credential };
+        let commitment = identity.commitment(cipher_suite).await?.to_vec();
+
+        let sealed_identity = cipher_suite
+            .hpke_seal(
+                admin_key,
+                BLINDING_LABEL,
+                Some(&commitment),
+                &identity.mls_encode_to_vec()?,
+            )
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        Ok(Self {
+            commitment,
+            sealed_identity,
+        })
+    }
+
+    /// Commitment to the real credential, which serves as the identity of
+    /// the hidden member for regular members.
+    pub fn commitment(&self) -> &[u8] {
+        &self.commitment
+    }
+
+    /// Decode a blinded credential from `credential`. Returns `None` if
+    /// `credential` is not of type [`BLINDED_CREDENTIAL_TYPE`].
+    pub fn from_credential(credential: &Credential) -> Result<Option<Self>, MlsError> {
+        credential
+            .as_custom()
+            .filter(|custom| custom.credential_type == Self::credential_type())
+            .map(|custom| Self::mls_decode(&mut &*custom.data))
+            .transpose()
+            .map_err(|_| MlsError::InvalidBlindedCredential)
+    }
+
+    /// Recover the real credential using the secret key of the admin key
+    /// that the credential was sealed to.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn unblind<P: CipherSuiteProvider>(
+        &self,
+        cipher_suite: &P,
+        admin_secret_key: &HpkeSecretKey,
+        admin_public_key: &HpkePublicKey,
+    ) -> Result<Credential, MlsError> {
+        let identity = cipher_suite
+            .hpke_open(
+                &self.sealed_identity,
+                admin_secret_key,
+                admin_public_key,
+                BLINDING_LABEL,
+                Some(&self.commitment),
+            )
+            .await
+            .map_err(|_| MlsError::InvalidBlindedCredential)?;
+
+        let identity = BlindedIdentity::mls_decode(&mut &*identity)
+            .map_err(|_| MlsError::InvalidBlindedCredential)?;
+
+        let commitment = identity.commitment(cipher_suite).await?;
+
+        (*commitment == *self.commitment)
+            .then_some(identity.credential)
+            .ok_or(MlsError::InvalidBlindedCredential)
+    }
+}
+
+impl MlsCredential for BlindedCredential {
+    type Error = mls_rs_codec::Error;
+
+    fn credential_type() -> CredentialType {
+        CredentialType::new(BLINDED_CREDENTIAL_TYPE)
+    }
+
+    fn into_credential(self) -> Result<Credential, Self::Error> {
+        Ok(Credential::Custom(CustomCredential::new(
+            Self::credential_type(),
+            self.mls_encode_to_vec()?,
+        )))
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[non_exhaustive]
+/// Error returned by a [`BlindedIdentityProvider`].
+pub enum BlindedIdentityProviderError {
+    #[cfg_attr(feature = "std", error(transparent))]
+    IdentityProviderError(AnyError),
+    #[cfg_attr(feature = "std", error("invalid blinded credential"))]
+    InvalidBlindedCredential,
+    #[cfg_attr(feature = "std", error("the group does not allow hidden members"))]
+    HiddenMembersNotAllowed,
+}
+
+impl IntoAnyError for BlindedIdentityProviderError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+/// Identity provider that accepts hidden members with a
+/// [`BlindedCredential`] in addition to the members accepted by an inner
+/// identity provider.
+///
+/// Blinded credentials are only accepted in groups with a
+/// [`HiddenMembersExt`](crate::extension::HiddenMembersExt). The identity of
+/// a hidden member is its [commitment](BlindedCredential::commitment), so a
+/// hidden member can change its signature key but not its credential. The
+/// real credential is not validated since only admins can unblind it.
+#[derive(Clone, Debug)]
+pub struct BlindedIdentityProvider<I> {
+    inner: I,
+}
+
+impl<I: IdentityProvider> BlindedIdentityProvider<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+
+    fn blinded(
+        signing_identity: &SigningIdentity,
+    ) -> Result<Option<BlindedCredential>, BlindedIdentityProviderError> {
+        BlindedCredential::from_credential(&signing_identity.credential)
+            .map_err(|_| BlindedIdentityProviderError::InvalidBlindedCredential)
+    }
+
+    fn inner_error(e: I::Error) -> BlindedIdentityProviderError {
+        BlindedIdentityProviderError::IdentityProviderError(e.into_any_error())
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<I: IdentityProvider> IdentityProvider for BlindedIdentityProvider<I> {
+    type Error = BlindedIdentityProviderError;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        if Self::blinded(signing_identity)?.is_none() {
+            return self
+                .inner
+                .validate_member(signing_identity, timestamp, extensions)
+                .await
+                .map_err(Self::inner_error);
+        }
+
+        // Key packages are validated without group context extensions.
+        let allowed = extensions.map_or(true, |extensions| {
+            extensions.has_extension(ExtensionType::new(HIDDEN_MEMBERS_EXTENSION_TYPE))
+        });
+
+        allowed
+            .then_some(())
+            .ok_or(BlindedIdentityProviderError::HiddenMembersNotAllowed)
+    }
+
+    async fn validate_leaf_node_extensions(
+        &self,
+        signing_identity: &SigningIdentity,
+        leaf_node_extensions: &ExtensionList,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        if Self::blinded(signing_identity)?.is_some() {
+            return Ok(());
+        }
+
+        self.inner
+            .validate_leaf_node_extensions(signing_identity, leaf_node_extensions, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        if Self::blinded(signing_identity)?.is_some() {
+            return Err(BlindedIdentityProviderError::HiddenMembersNotAllowed);
+        }
+
+        self.inner
+            .validate_external_sender(signing_identity, timestamp, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        match Self::blinded(signing_identity)? {
+            Some(blinded) => Ok(blinded.commitment),
+            None => self
+                .inner
+                .identity(signing_identity, extensions)
+                .await
+                .map_err(Self::inner_error),
+        }
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        match (Self::blinded(predecessor)?, Self::blinded(successor)?) {
+            (None, None) => self
+                .inner
+                .valid_successor(predecessor, successor, extensions)
+                .await
+                .map_err(Self::inner_error),
+            (Some(predecessor), Some(successor)) => {
+                Ok(predecessor.commitment == successor.commitment)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        let mut types = self.inner.supported_types();
+        types.push(BlindedCredential::credential_type());
+        types
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        crypto::CipherSuiteProvider,
+        identity::{BasicCredential, MlsCredential},
+    };
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        crypto::test_utils::test_cipher_suite_provider,
+    };
+
+    use super::BlindedCredential;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn blinded_credential_can_only_be_unblinded_by_admin() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (admin_secret, admin_public) = cs.kem_generate().await.unwrap();
+        let (other_secret, other_public) = cs.kem_generate().await.unwrap();
+
+        let credential = BasicCredential::new(b"observer".to_vec()).into_credential();
+
+        let blinded = BlindedCredential::new(&cs, credential.clone(), &admin_public)
+            .await
+            .unwrap();
+
+        let encoded = blinded.clone().into_credential().unwrap();
+
+        let decoded = BlindedCredential::from_credential(&encoded)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(decoded, blinded);
+
+        let unblinded = decoded
+            .unblind(&cs, &admin_secret, &admin_public)
+            .await
+            .unwrap();
+
+        assert_eq!(unblinded, credential);
+
+        let res = decoded.unblind(&cs, &other_secret, &other_public).await;
+        assert_matches!(res, Err(MlsError::InvalidBlindedCredential));
+
+        assert_eq!(
+            BlindedCredential::from_credential(&credential).unwrap(),
+            None
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commitment_must_match_sealed_credential() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let (admin_secret, admin_public) = cs.kem_generate().await.unwrap();
+
+        let credential = BasicCredential::new(b"observer".to_vec()).into_credential();
+
+        let blinded = BlindedCredential::new(&cs, credential.clone(), &admin_public)
+            .await
+            .unwrap();
+
+        let other = BlindedCredential::new(&cs, credential, &admin_public)
+            .await
+            .unwrap();
+
+        // Fresh salts make commitments to the same credential unlinkable.
+        assert_ne!(blinded.commitment(), other.commitment());
+
+        let forged = BlindedCredential {
+            commitment: other.commitment,
+            sealed_identity: blinded.sealed_identity,
+        };
+
+        let res = forged.unblind(&cs, &admin_secret, &admin_public).await;
+        assert_matches!(res, Err(MlsError::InvalidBlindedCredential));
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::{string::String, vec, vec::Vec};
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{
+    crypto::SignaturePublicKey,
+    error::{AnyError, IntoAnyError},
+    extension::ExtensionList,
+    identity::{
+        Credential, CredentialType, CustomCredential, IdentityProvider, MlsCredential,
+        SigningIdentity,
+    },
+    time::MlsTime,
+};
+
+use crate::extension::RequiredCapabilitiesExt;
+
+/// Credential type of [`DidCredential`], taken from the private use range.
+pub const DID_CREDENTIAL_TYPE: u16 = 0xF0B2;
+
+/// Credential of a member identified by a W3C decentralized identifier
+/// (DID).
+///
+/// The DID document of the member must authorize the signature key of the
+/// member. The credential can additionally carry a verifiable presentation
+/// of W3C verifiable credentials issued to the DID, for example to prove
+/// membership in an organization.
+#[derive(Clone, Debug, PartialEq, Eq, MlsSize, MlsEncode, MlsDecode)]
+pub struct DidCredential {
+    /// Decentralized identifier of the member, for example
+    /// `did:web:example.com:alice`.
+    pub did: String,
+    /// Serialized verifiable presentation signed by the DID, or empty.
+    #[mls_codec(with = "mls_rs_codec::byte_vec")]
+    pub presentation: Vec<u8>,
+}
+
+impl DidCredential {
+    pub fn new(did: String) -> Self {
+        Self {
+            did,
+            presentation: Vec::new(),
+        }
+    }
+
+    /// Attach a verifiable presentation to this credential.
+    pub fn with_presentation(self, presentation: Vec<u8>) -> Self {
+        Self {
+            presentation,
+            ..self
+        }
+    }
+
+    /// Decode a DID credential from `credential`. Returns `None` if
+    /// `credential` is not of type [`DID_CREDENTIAL_TYPE`].
+    pub fn from_credential(credential: &Credential) -> Result<Option<Self>, mls_rs_codec::Error> {
+        credential
+            .as_custom()
+            .filter(|custom| custom.credential_type == Self::credential_type())
+            .map(|custom| Self::mls_decode(&mut &*custom.data))
+            .transpose()
+    }
+
+    /// Required capabilities of a group in which all members must support
+    /// DID credentials, allowing members to join with one.
+    ///
+    /// Clients using a [`DidIdentityProvider`] advertise support in their
+    /// capabilities automatically.
+    pub fn required_capabilities() -> RequiredCapabilitiesExt {
+        RequiredCapabilitiesExt {
+            credentials: vec![Self::credential_type()],
+            ..Default::default()
+        }
+    }
+}
+
+impl MlsCredential for DidCredential {
+    type Error = mls_rs_codec::Error;
+
+    fn credential_type() -> CredentialType {
+        CredentialType::new(DID_CREDENTIAL_TYPE)
+    }
+
+    fn into_credential(self) -> Result<Credential, Self::Error> {
+        Ok(Credential::Custom(CustomCredential::new(
+            Self::credential_type(),
+            self.mls_encode_to_vec()?,
+        )))
+    }
+}
+
+/// Verifier of DIDs and verifiable presentations used by a
+/// [`DidIdentityProvider`].
+///
+/// Implementations resolve DID documents with the DID methods they support
+/// and verify presentations in the formats they support.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait DidVerifier: Send + Sync {
+    /// Error type that this verifier returns on internal failure or if a
+    /// DID or presentation is invalid.
+    type Error: IntoAnyError;
+
+    /// Determine if the DID document of `did` lists `signature_key` as a
+    /// verification method for authentication.
+    async fn verify_key(
+        &self,
+        did: &str,
+        signature_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error>;
+
+    /// Determine if `presentation` is signed by `did` and contains
+    /// verifiable credentials issued to `did` by trusted issuers.
+    ///
+    /// If `timestamp` is set to `None` then expiration checks should be
+    /// skipped.
+    async fn verify_presentation(
+        &self,
+        did: &str,
+        presentation: &[u8],
+        timestamp: Option<MlsTime>,
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[non_exhaustive]
+/// Error returned by a [`DidIdentityProvider`].
+pub enum DidIdentityProviderError {
+    #[cfg_attr(feature = "std", error("unsupported credential type {0:?}"))]
+    UnsupportedCredentialType(CredentialType),
+    #[cfg_attr(feature = "std", error("invalid DID credential"))]
+    InvalidCredential,
+    #[cfg_attr(
+        feature = "std",
+        error("signature key not authorized by the DID document: {0}")
+    )]
+    KeyNotAuthorized(AnyError),
+    #[cfg_attr(feature = "std", error("invalid verifiable presentation: {0}"))]
+    InvalidPresentation(AnyError),
+    #[cfg_attr(
+        feature = "std",
+        error("verifiable presentation required but not found")
+    )]
+    MissingPresentation,
+}
+
+impl IntoAnyError for DidIdentityProviderError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+/// Identity provider for members authenticated by a [`DidCredential`],
+/// without a central certificate authority.
+///
+/// The identity of a member is its DID, so a member can rotate its signature
+/// key as long as its DID document authorizes the new key.
+#[derive(Clone, Debug)]
+pub struct DidIdentityProvider<V> {
+    verifier: V,
+    presentation_required: bool,
+}
+
+impl<V> DidIdentityProvider<V>
+where
+    V: DidVerifier,
+{
+    pub fn new(verifier: V) -> Self {
+        Self {
+            verifier,
+            presentation_required: false,
+        }
+    }
+
+    /// Reject members whose credential does not carry a verifiable
+    /// presentation.
+    pub fn with_presentation_required(self, presentation_required: bool) -> Self {
+        Self {
+            presentation_required,
+            ..self
+        }
+    }
+
+    fn credential(
+        signing_identity: &SigningIdentity,
+    ) -> Result<DidCredential, DidIdentityProviderError> {
+        let credential = &signing_identity.credential;
+
+        DidCredential::from_credential(credential)
+            .map_err(|_| DidIdentityProviderError::InvalidCredential)?
+            .ok_or_else(|| {
+                DidIdentityProviderError::UnsupportedCredentialType(credential.credential_type())
+            })
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn validate(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+    ) -> Result<(), DidIdentityProviderError> {
+        let credential = Self::credential(signing_identity)?;
+
+        self.verifier
+            .verify_key(&credential.did, &signing_identity.signature_key)
+            .await
+            .map_err(|e| DidIdentityProviderError::KeyNotAuthorized(e.into_any_error()))?;
+
+        if credential.presentation.is_empty() {
+            return (!self.presentation_required)
+                .then_some(())
+                .ok_or(DidIdentityProviderError::MissingPresentation);
+        }
+
+        self.verifier
+            .verify_presentation(&credential.did, &credential.presentation, timestamp)
+            .await
+            .map_err(|e| DidIdentityProviderError::InvalidPresentation(e.into_any_error()))
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<V> IdentityProvider for DidIdentityProvider<V>
+where
+    V: DidVerifier,
+{
+    type Error = DidIdentityProviderError;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        _extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.validate(signing_identity, timestamp).await
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        _extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.validate(signing_identity, timestamp).await
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        _extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Ok(Self::credential(signing_identity)?.did.into_bytes())
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        _extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        Ok(Self::credential(predecessor)?.did == Self::credential(successor)?.did)
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        vec![DidCredential::credential_type()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use alloc::{string::ToString, vec::Vec};
+    use assert_matches::assert_matches;
+    use mls_rs_core::{
+        crypto::SignaturePublicKey,
+        extension::ExtensionList,
+        identity::{BasicCredential, IdentityProvider, MlsCredential, SigningIdentity},
+        time::MlsTime,
+    };
+
+    use super::{DidCredential, DidIdentityProvider, DidIdentityProviderError, DidVerifier};
+
+    /// Authorizes keys equal to the DID and presentations equal to the DID
+    /// that are checked before time 1000.
+    #[derive(Clone, Debug)]
+    struct TestVerifier;
+
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(thiserror::Error))]
+    #[cfg_attr(feature = "std", error("verification failed"))]
+    struct TestVerifierError;
+
+    impl mls_rs_core::error::IntoAnyError for TestVerifierError {
+        #[cfg(feature = "std")]
+        fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+            Ok(self.into())
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
+    impl DidVerifier for TestVerifier {
+        type Error = TestVerifierError;
+
+        async fn verify_key(
+            &self,
+            did: &str,
+            signature_key: &SignaturePublicKey,
+        ) -> Result<(), Self::Error> {
+            (did.as_bytes() == signature_key.as_bytes())
+                .then_some(())
+                .ok_or(TestVerifierError)
+        }
+
+        async fn verify_presentation(
+            &self,
+            did: &str,
+            presentation: &[u8],
+            timestamp: Option<MlsTime>,
+        ) -> Result<(), Self::Error> {
+            let fresh = timestamp.map_or(true, |t| t.seconds_since_epoch() < 1000);
+
+            (did.as_bytes() == presentation && fresh)
+                .then_some(())
+                .ok_or(TestVerifierError)
+        }
+    }
+
+    fn signing_identity(credential: DidCredential, key: &str) -> SigningIdentity {
+        SigningIdentity::new(
+            credential.into_credential().unwrap(),
+            SignaturePublicKey::from(key.as_bytes().to_vec()),
+        )
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn did_must_authorize_signature_key() {
+        let provider = DidIdentityProvider::new(TestVerifier);
+        let alice = DidCredential::new("did:example:alice".to_string());
+
+        let member = signing_identity(alice.clone(), "did:example:alice");
+
+        provider.validate_member(&member, None, None).await.unwrap();
+
+        let identity = provider
+            .identity(&member, &ExtensionList::new())
+            .await
+            .unwrap();
+
+        assert_eq!(identity, b"did:example:alice");
+
+        let impostor = signing_identity(alice, "did:example:mallory");
+        let res = provider.validate_member(&impostor, None, None).await;
+
+        assert_matches!(res, Err(DidIdentityProviderError::KeyNotAuthorized(_)));
+
+        let valid_successor = provider
+            .valid_successor(&member, &impostor, &ExtensionList::new())
+            .await
+            .unwrap();
+
+        assert!(valid_successor);
+
+        let basic = SigningIdentity::new(
+            BasicCredential::new(b"alice".to_vec()).into_credential(),
+            SignaturePublicKey::from(Vec::new()),
+        );
+
+        let res = provider.validate_member(&basic, None, None).await;
+
+        assert_matches!(
+            res,
+            Err(DidIdentityProviderError::UnsupportedCredentialType(_))
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn presentations_are_verified() {
+        let provider = DidIdentityProvider::new(TestVerifier).with_presentation_required(true);
+        let alice = DidCredential::new("did:example:alice".to_string());
+
+        let res = provider
+            .validate_member(
+                &signing_identity(alice.clone(), "did:example:alice"),
+                None,
+                None,
+            )
+            .await;
+
+        assert_matches!(res, Err(DidIdentityProviderError::MissingPresentation));
+
+        let member = signing_identity(
+            alice.with_presentation(b"did:example:alice".to_vec()),
+            "did:example:alice",
+        );
+
+        provider
+            .validate_member(&member, Some(MlsTime::from(999)), None)
+            .await
+            .unwrap();
+
+        let res = provider
+            .validate_member(&member, Some(MlsTime::from(1000)), None)
+            .await;
+
+        assert_matches!(res, Err(DidIdentityProviderError::InvalidPresentation(_)));
+
+        assert_eq!(
+            DidCredential::required_capabilities().credentials,
+            provider.supported_types()
+        );
+    }
+}
@@ -0,0 +1,644 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::{string::String, vec, vec::Vec};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use core::{
+    convert::Infallible,
+    fmt::{self, Debug},
+};
+use mls_rs_core::{
+    crypto::SignaturePublicKey,
+    error::{AnyError, IntoAnyError},
+    extension::ExtensionList,
+    identity::{
+        Credential, CredentialType, CustomCredential, IdentityProvider, MlsCredential,
+        SigningIdentity,
+    },
+    time::MlsTime,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Credential type of [`JwtCredential`], taken from the private use range.
+pub const JWT_CREDENTIAL_TYPE: u16 = 0xF0B1;
+
+/// Credential made of a JSON Web Token in compact serialization, for example
+/// an OpenID Connect ID token.
+#[derive(Clone, PartialEq, Eq)]
+pub struct JwtCredential {
+    token: String,
+}
+
+impl Debug for JwtCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The token is a bearer secret for the services it was issued for.
+        f.debug_struct("JwtCredential").finish_non_exhaustive()
+    }
+}
+
+impl JwtCredential {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Decode a JWT credential from `credential`. Returns `None` if
+    /// `credential` is not of type [`JWT_CREDENTIAL_TYPE`] or the token is
+    /// not UTF-8.
+    pub fn from_credential(credential: &Credential) -> Option<Self> {
+        credential
+            .as_custom()
+            .filter(|custom| custom.credential_type == Self::credential_type())
+            .and_then(|custom| String::from_utf8(custom.data.clone()).ok())
+            .map(Self::new)
+    }
+}
+
+impl MlsCredential for JwtCredential {
+    type Error = Infallible;
+
+    fn credential_type() -> CredentialType {
+        CredentialType::new(JWT_CREDENTIAL_TYPE)
+    }
+
+    fn into_credential(self) -> Result<Credential, Self::Error> {
+        Ok(Credential::Custom(CustomCredential::new(
+            Self::credential_type(),
+            self.token.into_bytes(),
+        )))
+    }
+}
+
+/// JOSE header of a JWT.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct JwtHeader {
+    /// Signature algorithm, for example `ES256`.
+    pub alg: String,
+    /// Identifier of the issuer key that signed the token.
+    #[serde(default)]
+    pub kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<Audience> for Vec<String> {
+    fn from(audience: Audience) -> Self {
+        match audience {
+            Audience::One(audience) => vec![audience],
+            Audience::Many(audiences) => audiences,
+        }
+    }
+}
+
+fn audience<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Audience::deserialize(deserializer).map(Into::into)
+}
+
+/// Registered claims of a JWT used by [`JwtIdentityProvider`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct JwtClaims {
+    /// Issuer of the token.
+    pub iss: String,
+    /// Subject of the token, used as MLS identity.
+    pub sub: String,
+    /// Audiences of the token.
+    #[serde(default, deserialize_with = "audience")]
+    pub aud: Vec<String>,
+    /// Expiration time in seconds since the Unix epoch.
+    pub exp: u64,
+    /// Time in seconds since the Unix epoch before which the token must not
+    /// be accepted.
+    #[serde(default)]
+    pub nbf: Option<u64>,
+    /// Proof-of-possession key of the token as defined in
+    /// [RFC 7800](https://www.rfc-editor.org/rfc/rfc7800).
+    #[serde(default)]
+    pub cnf: Option<JwtConfirmation>,
+}
+
+/// Confirmation claim of a JWT.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct JwtConfirmation {
+    /// Public key of the holder of the token.
+    #[serde(default)]
+    pub jwk: Option<Jwk>,
+}
+
+/// Public JSON Web Key as defined in
+/// [RFC 7517](https://www.rfc-editor.org/rfc/rfc7517). Only the members
+/// needed to represent elliptic curve keys are decoded.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct Jwk {
+    /// Key type, `EC` or `OKP`.
+    pub kty: String,
+    /// Curve of the key, for example `P-256` or `Ed25519`.
+    pub crv: String,
+    /// Base64url encoded x coordinate, or public key of an `OKP` key.
+    pub x: String,
+    /// Base64url encoded y coordinate of an `EC` key.
+    #[serde(default)]
+    pub y: Option<String>,
+}
+
+impl Jwk {
+    /// Encode this key the way MLS signature public keys are encoded: the
+    /// raw public key for `OKP` keys and the uncompressed point for `EC`
+    /// keys.
+    pub fn to_signature_public_key(&self) -> Option<SignaturePublicKey> {
+        let decode = |data: &str| URL_SAFE_NO_PAD.decode(data).ok();
+
+        match self.kty.as_str() {
+            "OKP" => decode(&self.x).map(Into::into),
+            "EC" => {
+                let x = decode(&self.x)?;
+                let y = decode(self.y.as_deref()?)?;
+
+                (x.len() == y.len()).then(|| [&[0x04][..], &x, &y].concat().into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Verifier of the signature of a JWT by its issuer.
+///
+/// Implementations typically look up the key identified by
+/// [`JwtHeader::kid`] in the JSON Web Key Set published by `issuer`.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait JwtSignatureVerifier: Send + Sync {
+    /// Error type that this verifier returns on internal failure or if a
+    /// signature is invalid.
+    type Error: IntoAnyError;
+
+    /// Determine if `signature` over `signing_input`, the encoded header and
+    /// claims of a token, was produced by `issuer`.
+    async fn verify(
+        &self,
+        issuer: &str,
+        header: &JwtHeader,
+        signing_input: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[non_exhaustive]
+/// Error returned by a [`JwtIdentityProvider`].
+pub enum JwtIdentityProviderError {
+    #[cfg_attr(feature = "std", error("unsupported credential type {0:?}"))]
+    UnsupportedCredentialType(CredentialType),
+    #[cfg_attr(feature = "std", error("malformed JWT"))]
+    MalformedToken,
+    #[cfg_attr(feature = "std", error("invalid JWT signature: {0}"))]
+    InvalidSignature(AnyError),
+    #[cfg_attr(feature = "std", error("untrusted JWT issuer {0}"))]
+    UntrustedIssuer(String),
+    #[cfg_attr(feature = "std", error("JWT was not issued for this audience"))]
+    AudienceMismatch,
+    #[cfg_attr(feature = "std", error("JWT expired"))]
+    Expired,
+    #[cfg_attr(feature = "std", error("JWT is not valid yet"))]
+    NotYetValid,
+    #[cfg_attr(feature = "std", error("JWT has no cnf claim with a public key"))]
+    MissingConfirmationKey,
+    #[cfg_attr(
+        feature = "std",
+        error("cnf claim of the JWT does not match the signature key")
+    )]
+    ConfirmationKeyMismatch,
+}
+
+impl IntoAnyError for JwtIdentityProviderError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+/// Identity provider for members authenticated by a [`JwtCredential`].
+///
+/// A token is valid if its signature is accepted by the
+/// [`JwtSignatureVerifier`], it was issued by a trusted issuer for the
+/// configured audience, it is not expired and the JWK in its `cnf` claim is
+/// the signature key of the member. The identity of a member is the subject
+/// of its token, so all trusted issuers must share a namespace of subjects.
+#[derive(Clone, Debug)]
+pub struct JwtIdentityProvider<V> {
+    verifier: V,
+    audience: String,
+    trusted_issuers: Vec<String>,
+    leeway_seconds: u64,
+}
+
+impl<V> JwtIdentityProvider<V>
+where
+    V: JwtSignatureVerifier,
+{
+    /// Create a provider accepting tokens issued for `audience` by any issuer
+    /// known to `verifier`.
+    pub fn new(verifier: V, audience: String) -> Self {
+        Self {
+            verifier,
+            audience,
+            trusted_issuers: Vec::new(),
+            leeway_seconds: 0,
+        }
+    }
+
+    /// Only accept tokens issued by `issuer` and the other issuers added with
+    /// this method.
+    pub fn with_trusted_issuer(mut self, issuer: String) -> Self {
+        self.trusted_issuers.push(issuer);
+        self
+    }
+
+    /// Tolerate clocks of issuers being off by `leeway_seconds`.
+    pub fn with_leeway(self, leeway_seconds: u64) -> Self {
+        Self {
+            leeway_seconds,
+            ..self
+        }
+    }
+
+    /// Decode the claims of the token of `signing_identity` without
+    /// validating them.
+    pub fn claims(
+        &self,
+        signing_identity: &SigningIdentity,
+    ) -> Result<JwtClaims, JwtIdentityProviderError> {
+        let credential = Self::credential(signing_identity)?;
+        let (_, claims, _) = split_token(credential.token())?;
+
+        decode_json(claims)
+    }
+
+    fn credential(
+        signing_identity: &SigningIdentity,
+    ) -> Result<JwtCredential, JwtIdentityProviderError> {
+        let credential = &signing_identity.credential;
+
+        JwtCredential::from_credential(credential).ok_or_else(|| {
+            JwtIdentityProviderError::UnsupportedCredentialType(credential.credential_type())
+        })
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn validate(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+    ) -> Result<(), JwtIdentityProviderError> {
+        let credential = Self::credential(signing_identity)?;
+        let token = credential.token();
+        let (header, claims, signature) = split_token(token)?;
+
+        // The signature covers the encoded header and claims.
+        let signing_input = &token[..header.len() + 1 + claims.len()];
+
+        let header: JwtHeader = decode_json(header)?;
+        let claims: JwtClaims = decode_json(claims)?;
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| JwtIdentityProviderError::MalformedToken)?;
+
+        if !self.trusted_issuers.is_empty() && !self.trusted_issuers.contains(&claims.iss) {
+            return Err(JwtIdentityProviderError::UntrustedIssuer(claims.iss));
+        }
+
+        self.verifier
+            .verify(&claims.iss, &header, signing_input.as_bytes(), &signature)
+            .await
+            .map_err(|e| JwtIdentityProviderError::InvalidSignature(e.into_any_error()))?;
+
+        if !claims.aud.contains(&self.audience) {
+            return Err(JwtIdentityProviderError::AudienceMismatch);
+        }
+
+        // Without proof of possession, anyone who saw the token could use it
+        // with their own signature key.
+        let confirmation_key = claims
+            .cnf
+            .and_then(|cnf| cnf.jwk)
+            .and_then(|jwk| jwk.to_signature_public_key())
+            .ok_or(JwtIdentityProviderError::MissingConfirmationKey)?;
+
+        if confirmation_key != signing_identity.signature_key {
+            return Err(JwtIdentityProviderError::ConfirmationKeyMismatch);
+        }
+
+        let Some(now) = timestamp.map(|t| t.seconds_since_epoch()) else {
+            return Ok(());
+        };
+
+        if claims.exp.saturating_add(self.leeway_seconds) <= now {
+            return Err(JwtIdentityProviderError::Expired);
+        }
+
+        if claims
+            .nbf
+            .map_or(false, |nbf| nbf > now.saturating_add(self.leeway_seconds))
+        {
+            return Err(JwtIdentityProviderError::NotYetValid);
+        }
+
+        Ok(())
+    }
+}
+
+fn split_token(token: &str) -> Result<(&str, &str, &str), JwtIdentityProviderError> {
+    let mut parts = token.split('.');
+
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(header), Some(claims), Some(signature), None) => Ok((header, claims, signature)),
+        _ => Err(JwtIdentityProviderError::MalformedToken),
+    }
+}
+
+fn decode_json<T: DeserializeOwned>(part: &str) -> Result<T, JwtIdentityProviderError> {
+    let json = URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| JwtIdentityProviderError::MalformedToken)?;
+
+    serde_json::from_slice(&json).map_err(|_| JwtIdentityProviderError::MalformedToken)
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<V> IdentityProvider for JwtIdentityProvider<V>
+where
+    V: JwtSignatureVerifier,
+{
+    type Error = JwtIdentityProviderError;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        _extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.validate(signing_identity, timestamp).await
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        _extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.validate(signing_identity, timestamp).await
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        _extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Ok(self.claims(signing_identity)?.sub.into_bytes())
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        _extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.claims(predecessor)?.sub == self.claims(successor)?.sub)
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        vec![JwtCredential::credential_type()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use alloc::{format, string::String, vec::Vec};
+    use assert_matches::assert_matches;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use mls_rs_core::{
+        crypto::SignaturePublicKey,
+        extension::ExtensionList,
+        identity::{BasicCredential, IdentityProvider, MlsCredential, SigningIdentity},
+        time::MlsTime,
+    };
+
+    use super::{
+        Jwk, JwtCredential, JwtHeader, JwtIdentityProvider, JwtIdentityProviderError,
+        JwtSignatureVerifier,
+    };
+
+    /// Accepts signatures that are the signing input followed by the issuer.
+    #[derive(Clone, Debug)]
+    struct TestVerifier;
+
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(thiserror::Error))]
+    #[cfg_attr(feature = "std", error("invalid signature"))]
+    struct TestVerifierError;
+
+    impl mls_rs_core::error::IntoAnyError for TestVerifierError {
+        #[cfg(feature = "std")]
+        fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+            Ok(self.into())
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
+    impl JwtSignatureVerifier for TestVerifier {
+        type Error = TestVerifierError;
+
+        async fn verify(
+            &self,
+            issuer: &str,
+            header: &JwtHeader,
+            signing_input: &[u8],
+            signature: &[u8],
+        ) -> Result<(), Self::Error> {
+            let expected = [signing_input, issuer.as_bytes()].concat();
+
+            (header.alg == "test" && signature == expected)
+                .then_some(())
+                .ok_or(TestVerifierError)
+        }
+    }
+
+    fn token(issuer: &str, claims: &str) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"test","kid":"1"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(claims);
+        let signing_input = format!("{header}.{claims}");
+        let signature =
+            URL_SAFE_NO_PAD.encode([signing_input.as_bytes(), issuer.as_bytes()].concat());
+
+        format!("{signing_input}.{signature}")
+    }
+
+    const ALICE_KEY: [u8; 32] = [1; 32];
+
+    fn signing_identity(token: String) -> SigningIdentity {
+        signing_identity_with_key(token, &ALICE_KEY)
+    }
+
+    fn signing_identity_with_key(token: String, key: &[u8]) -> SigningIdentity {
+        SigningIdentity::new(
+            JwtCredential::new(token).into_credential().unwrap(),
+            SignaturePublicKey::from(key.to_vec()),
+        )
+    }
+
+    fn alice_claims(audience: &str, exp: u64) -> String {
+        let x = URL_SAFE_NO_PAD.encode(ALICE_KEY);
+
+        format!(
+            r#"{{"iss":"https://idp.example.com","sub":"alice","aud":"{audience}","exp":{exp},"cnf":{{"jwk":{{"kty":"OKP","crv":"Ed25519","x":"{x}"}}}}}}"#
+        )
+    }
+
+    fn alice(audience: &str, exp: u64) -> SigningIdentity {
+        signing_identity(token(
+            "https://idp.example.com",
+            &alice_claims(audience, exp),
+        ))
+    }
+
+    fn provider() -> JwtIdentityProvider<TestVerifier> {
+        JwtIdentityProvider::new(TestVerifier, "mls".into())
+            .with_trusted_issuer("https://idp.example.com".into())
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn valid_token_is_accepted() {
+        let alice = alice("mls", 2000);
+        let provider = provider();
+
+        provider
+            .validate_member(&alice, Some(MlsTime::from(1000)), None)
+            .await
+            .unwrap();
+
+        let identity = provider
+            .identity(&alice, &ExtensionList::new())
+            .await
+            .unwrap();
+
+        assert_eq!(identity, b"alice");
+
+        let res = provider
+            .validate_member(&alice, Some(MlsTime::from(2000)), None)
+            .await;
+
+        assert_matches!(res, Err(JwtIdentityProviderError::Expired));
+
+        provider
+            .clone()
+            .with_leeway(60)
+            .validate_member(&alice, Some(MlsTime::from(2000)), None)
+            .await
+            .unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn invalid_tokens_are_rejected() {
+        let provider = provider();
+
+        let res = provider
+            .validate_member(&alice("other", 2000), None, None)
+            .await;
+
+        assert_matches!(res, Err(JwtIdentityProviderError::AudienceMismatch));
+
+        let claims = r#"{"iss":"https://evil.example.com","sub":"alice","aud":["mls"],"exp":2000}"#;
+        let mallory = signing_identity(token("https://evil.example.com", claims));
+
+        let res = provider.validate_member(&mallory, None, None).await;
+        assert_matches!(res, Err(JwtIdentityProviderError::UntrustedIssuer(_)));
+
+        let claims = r#"{"iss":"https://idp.example.com","sub":"alice","aud":["mls"],"exp":2000}"#;
+        let forged = signing_identity(token("https://evil.example.com", claims));
+
+        let res = provider.validate_member(&forged, None, None).await;
+        assert_matches!(res, Err(JwtIdentityProviderError::InvalidSignature(_)));
+
+        let res = provider
+            .validate_member(&signing_identity("not a token".into()), None, None)
+            .await;
+
+        assert_matches!(res, Err(JwtIdentityProviderError::MalformedToken));
+
+        let basic = SigningIdentity::new(
+            BasicCredential::new(b"alice".to_vec()).into_credential(),
+            SignaturePublicKey::from(Vec::new()),
+        );
+
+        let res = provider.validate_member(&basic, None, None).await;
+
+        assert_matches!(
+            res,
+            Err(JwtIdentityProviderError::UnsupportedCredentialType(_))
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn token_must_be_bound_to_signature_key() {
+        let provider = provider();
+        let alice_token = token("https://idp.example.com", &alice_claims("mls", 2000));
+
+        // Mallory saw the token of Alice and presents it with her own key.
+        let mallory = signing_identity_with_key(alice_token, &[2; 32]);
+        let res = provider.validate_member(&mallory, None, None).await;
+
+        assert_matches!(res, Err(JwtIdentityProviderError::ConfirmationKeyMismatch));
+
+        let claims = r#"{"iss":"https://idp.example.com","sub":"alice","aud":["mls"],"exp":2000}"#;
+        let unbound = signing_identity(token("https://idp.example.com", claims));
+        let res = provider.validate_member(&unbound, None, None).await;
+
+        assert_matches!(res, Err(JwtIdentityProviderError::MissingConfirmationKey));
+    }
+
+    #[test]
+    fn ec_jwk_is_uncompressed_point() {
+        let jwk = Jwk {
+            kty: "EC".into(),
+            crv: "P-256".into(),
+            x: URL_SAFE_NO_PAD.encode([1; 32]),
+            y: Some(URL_SAFE_NO_PAD.encode([2; 32])),
+        };
+
+        let expected = [&[0x04][..], &[1; 32], &[2; 32]].concat();
+
+        assert_eq!(jwk.to_signature_public_key().unwrap().to_vec(), expected);
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use alloc::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+    vec::Vec,
+};
+use mls_rs_codec::MlsEncode;
+use mls_rs_core::{
+    error::{AnyError, IntoAnyError},
+    extension::ExtensionList,
+    identity::{CredentialType, IdentityProvider, SigningIdentity},
+    time::MlsTime,
+};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::extension::KeyTransparencyProofExt;
+
+/// Default number of verified proofs remembered by a
+/// [`TransparentIdentityProvider`].
+pub const DEFAULT_PROOF_CACHE_CAPACITY: usize = 1024;
+
+/// Key transparency log verifying that signature keys of members are
+/// published, using proofs presented by members in a
+/// [`KeyTransparencyProofExt`].
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+pub trait KeyTransparency: Send + Sync {
+    /// Error type that this log returns on internal failure or if a proof is
+    /// invalid.
+    type Error: IntoAnyError;
+
+    /// Determine if `proof` shows that the signature key of
+    /// `signing_identity` is published in the log for its credential.
+    ///
+    /// Implementations may contact the log, for example to check that
+    /// `proof` is consistent with the latest tree head.
+    async fn verify(
+        &self,
+        signing_identity: &SigningIdentity,
+        proof: &[u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[non_exhaustive]
+/// Error returned by a [`TransparentIdentityProvider`].
+pub enum TransparentIdentityProviderError {
+    #[cfg_attr(feature = "std", error(transparent))]
+    IdentityProviderError(AnyError),
+    #[cfg_attr(feature = "std", error("invalid key transparency proof: {0}"))]
+    InvalidProof(AnyError),
+    #[cfg_attr(
+        feature = "std",
+        error("key transparency proof required but not found")
+    )]
+    MissingProof,
+    #[cfg_attr(feature = "std", error(transparent))]
+    SerializationError(AnyError),
+}
+
+impl IntoAnyError for TransparentIdentityProviderError {
+    #[cfg(feature = "std")]
+    fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+        Ok(self.into())
+    }
+}
+
+type CacheKey = (Vec<u8>, Vec<u8>);
+
+/// Bounded set of encoded signing identities and proofs that passed
+/// verification. Once full, the oldest entry is evicted first.
+#[derive(Debug, Default)]
+struct ProofCache {
+    entries: BTreeSet<CacheKey>,
+    order: VecDeque<CacheKey>,
+}
+
+impl ProofCache {
+    fn contains(&self, key: &CacheKey) -> bool {
+        self.entries.contains(key)
+    }
+
+    fn insert(&mut self, key: CacheKey, capacity: usize) {
+        if !self.entries.insert(key.clone()) {
+            return;
+        }
+
+        self.order.push_back(key);
+
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Identity provider that verifies the [`KeyTransparencyProofExt`] of
+/// members against a [`KeyTransparency`] log in addition to validating their
+/// identity with an inner identity provider.
+///
+/// Proofs are verified whenever a leaf node is validated, which includes
+/// key packages, update paths and updates processed with a commit.
+/// Successfully verified proofs are cached, shared between clones of the
+/// provider, so that the log is only consulted once per signing identity and
+/// proof.
+#[derive(Clone, Debug)]
+pub struct TransparentIdentityProvider<I, K> {
+    inner: I,
+    log: K,
+    required: bool,
+    cache_capacity: usize,
+    cache: Arc<Mutex<ProofCache>>,
+}
+
+impl<I, K> TransparentIdentityProvider<I, K>
+where
+    I: IdentityProvider,
+    K: KeyTransparency,
+{
+    pub fn new(inner: I, log: K) -> Self {
+        Self {
+            inner,
+            log,
+            required: false,
+            cache_capacity: DEFAULT_PROOF_CACHE_CAPACITY,
+            cache: Default::default(),
+        }
+    }
+
+    /// Reject members without a [`KeyTransparencyProofExt`].
+    pub fn with_required(self, required: bool) -> Self {
+        Self { required, ..self }
+    }
+
+    /// Set the number of verified proofs remembered. A capacity of 0
+    /// disables caching.
+    ///
+    /// The provider gets a new empty cache, no longer shared with the
+    /// provider it was created from.
+    pub fn with_cache_capacity(self, cache_capacity: usize) -> Self {
+        Self {
+            cache_capacity,
+            cache: Default::default(),
+            ..self
+        }
+    }
+
+    /// Forget all verified proofs, for example after the log reported a
+    /// misbehavior.
+    pub fn clear_cache(&self) {
+        *self.lock_cache() = Default::default();
+    }
+
+    // The cache only holds proofs that were already verified, so it stays
+    // usable even if a thread panicked while holding the lock.
+    fn lock_cache(&self) -> MutexGuard<'_, ProofCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn verify_proof(
+        &self,
+        signing_identity: &SigningIdentity,
+        proof: &[u8],
+    ) -> Result<(), TransparentIdentityProviderError> {
+        let cached = self.cache_capacity > 0;
+        let identity = signing_identity.mls_encode_to_vec().map_err(|e| {
+            TransparentIdentityProviderError::SerializationError(e.into_any_error())
+        })?;
+
+        let key = (identity, proof.to_vec());
+
+        if cached && self.lock_cache().contains(&key) {
+            return Ok(());
+        }
+
+        self.log
+            .verify(signing_identity, proof)
+            .await
+            .map_err(|e| TransparentIdentityProviderError::InvalidProof(e.into_any_error()))?;
+
+        if cached {
+            self.lock_cache().insert(key, self.cache_capacity);
+        }
+
+        Ok(())
+    }
+
+    fn inner_error(e: I::Error) -> TransparentIdentityProviderError {
+        TransparentIdentityProviderError::IdentityProviderError(e.into_any_error())
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<I, K> IdentityProvider for TransparentIdentityProvider<I, K>
+where
+    I: IdentityProvider,
+    K: KeyTransparency,
+{
+    type Error = TransparentIdentityProviderError;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_member(signing_identity, timestamp, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    async fn validate_leaf_node_extensions(
+        &self,
+        signing_identity: &SigningIdentity,
+        leaf_node_extensions: &ExtensionList,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_leaf_node_extensions(signing_identity, leaf_node_extensions, extensions)
+            .await
+            .map_err(Self::inner_error)?;
+
+        let proof = leaf_node_extensions
+            .get_as::<KeyTransparencyProofExt>()
+            .map_err(|e| TransparentIdentityProviderError::InvalidProof(e.into_any_error()))?;
+
+        match proof {
+            Some(ext) => self.verify_proof(signing_identity, &ext.proof).await,
+            None if self.required => Err(TransparentIdentityProviderError::MissingProof),
+            None => Ok(()),
+        }
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .validate_external_sender(signing_identity, timestamp, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner
+            .identity(signing_identity, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        self.inner
+            .valid_successor(predecessor, successor, extensions)
+            .await
+            .map_err(Self::inner_error)
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        self.inner.supported_types()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use alloc::sync::Arc;
+    use assert_matches::assert_matches;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use mls_rs_core::{
+        crypto::SignaturePublicKey,
+        extension::ExtensionList,
+        identity::{IdentityProvider, SigningIdentity},
+    };
+
+    use crate::{
+        extension::built_in::KeyTransparencyProofExt,
+        identity::basic::{BasicCredential, BasicIdentityProvider},
+    };
+
+    use super::{KeyTransparency, TransparentIdentityProvider, TransparentIdentityProviderError};
+
+    /// Accepts proofs that equal the signature key, counting verifications.
+    #[derive(Clone, Debug, Default)]
+    struct TestLog {
+        verifications: Arc<AtomicUsize>,
+    }
+
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(thiserror::Error))]
+    #[cfg_attr(feature = "std", error("key not in log"))]
+    struct TestLogError;
+
+    impl mls_rs_core::error::IntoAnyError for TestLogError {
+        #[cfg(feature = "std")]
+        fn into_dyn_error(self) -> Result<Box<dyn std::error::Error + Send + Sync>, Self> {
+            Ok(self.into())
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
+    impl KeyTransparency for TestLog {
+        type Error = TestLogError;
+
+        async fn verify(
+            &self,
+            signing_identity: &SigningIdentity,
+            proof: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.verifications.fetch_add(1, Ordering::SeqCst);
+
+            (proof == signing_identity.signature_key.as_bytes())
+                .then_some(())
+                .ok_or(TestLogError)
+        }
+    }
+
+    fn signing_identity(name: &[u8], key: &[u8]) -> SigningIdentity {
+        SigningIdentity::new(
+            BasicCredential::new(name.to_vec()).into_credential(),
+            SignaturePublicKey::from(key.to_vec()),
+        )
+    }
+
+    fn proof_extensions(proof: &[u8]) -> ExtensionList {
+        let mut extensions = ExtensionList::new();
+
+        extensions
+            .set_from(KeyTransparencyProofExt::new(proof.to_vec()))
+            .unwrap();
+
+        extensions
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn verified_proofs_are_cached() {
+        let log = TestLog::default();
+        let provider = TransparentIdentityProvider::new(BasicIdentityProvider::new(), log.clone());
+        let alice = signing_identity(b"alice", b"alice key");
+
+        for provider in [provider.clone(), provider.clone()] {
+            provider
+                .validate_leaf_node_extensions(&alice, &proof_extensions(b"alice key"), None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 1);
+
+        provider.clear_cache();
+
+        provider
+            .validate_leaf_node_extensions(&alice, &proof_extensions(b"alice key"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 2);
+
+        let uncached = provider.clone().with_cache_capacity(0);
+
+        for _ in 0..2 {
+            uncached
+                .validate_leaf_node_extensions(&alice, &proof_extensions(b"alice key"), None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 4);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn cache_is_keyed_by_signing_identity() {
+        let log = TestLog::default();
+        let provider = TransparentIdentityProvider::new(BasicIdentityProvider::new(), log.clone());
+
+        for name in [&b"alice"[..], b"mallory"] {
+            provider
+                .validate_leaf_node_extensions(
+                    &signing_identity(name, b"alice key"),
+                    &proof_extensions(b"alice key"),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn poisoned_cache_is_still_used() {
+        let log = TestLog::default();
+        let provider = TransparentIdentityProvider::new(BasicIdentityProvider::new(), log.clone());
+        let alice = signing_identity(b"alice", b"alice key");
+
+        let cache = provider.cache.clone();
+
+        std::thread::spawn(move || {
+            let _guard = cache.lock().unwrap();
+            panic!("poison the cache");
+        })
+        .join()
+        .unwrap_err();
+
+        for _ in 0..2 {
+            provider
+                .validate_leaf_node_extensions(&alice, &proof_extensions(b"alice key"), None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 1);
+
+        provider.clear_cache();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn invalid_and_missing_proofs_are_rejected() {
+        let log = TestLog::default();
+        let provider = TransparentIdentityProvider::new(BasicIdentityProvider::new(), log.clone());
+        let alice = signing_identity(b"alice", b"alice key");
+
+        let res = provider
+            .validate_leaf_node_extensions(&alice, &proof_extensions(b"other key"), None)
+            .await;
+
+        assert_matches!(res, Err(TransparentIdentityProviderError::InvalidProof(_)));
+
+        // Failed verifications are not cached.
+        let res = provider
+            .validate_leaf_node_extensions(&alice, &proof_extensions(b"other key"), None)
+            .await;
+
+        assert_matches!(res, Err(TransparentIdentityProviderError::InvalidProof(_)));
+        assert_eq!(log.verifications.load(Ordering::SeqCst), 2);
+
+        provider
+            .validate_leaf_node_extensions(&alice, &ExtensionList::new(), None)
+            .await
+            .unwrap();
+
+        let res = provider
+            .with_required(true)
+            .validate_leaf_node_extensions(&alice, &ExtensionList::new(), None)
+            .await;
+
+        assert_matches!(res, Err(TransparentIdentityProviderError::MissingProof));
+    }
+}
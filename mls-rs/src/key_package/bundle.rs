@@ -0,0 +1,224 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::crypto::{CipherSuite, CryptoProvider};
+
+use crate::{client::MlsError, group::framing::MlsMessagePayload, MlsMessage};
+
+use super::{validate_key_package_properties, KeyPackage, KeyPackageRef};
+
+/// Key package message together with the metadata a key directory needs to
+/// index it.
+///
+/// This is the interchange format between clients uploading key packages
+/// and key directories serving them. The metadata is derived from the key
+/// package when the bundle is created and checked again, together with the
+/// signature of the key package, when a bundle is parsed with
+/// [`KeyPackageBundle::from_bytes`].
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+pub struct KeyPackageBundle {
+    message: MlsMessage,
+    reference: KeyPackageRef,
+    expiration: u64,
+    cipher_suite: CipherSuite,
+}
+
+impl KeyPackageBundle {
+    /// Create a bundle for the key package contained in `message`.
+    ///
+    /// The signature of the key package is verified using a cipher suite
+    /// provider obtained from `crypto_provider`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn new<CP: CryptoProvider>(
+        message: MlsMessage,
+        crypto_provider: &CP,
+    ) -> Result<Self, MlsError> {
+        let key_package = key_package(&message)?;
+        let cipher_suite = key_package.cipher_suite;
+
+        let cipher_suite_provider = crypto_provider
+            .cipher_suite_provider(cipher_suite)
+            .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))?;
+
+        validate_key_package_properties(key_package, message.version, &cipher_suite_provider)
+            .await?;
+
+        Ok(Self {
+            reference: key_package.to_reference(&cipher_suite_provider).await?,
+            expiration: key_package.expiration()?,
+            cipher_suite,
+            message,
+        })
+    }
+
+    /// Key package message contained in this bundle.
+    pub fn key_package_message(&self) -> &MlsMessage {
+        &self.message
+    }
+
+    pub fn into_key_package_message(self) -> MlsMessage {
+        self.message
+    }
+
+    pub fn reference(&self) -> &KeyPackageRef {
+        &self.reference
+    }
+
+    /// Expiration of the key package in seconds since the Unix epoch.
+    pub fn expiration(&self) -> u64 {
+        self.expiration
+    }
+
+    pub fn cipher_suite(&self) -> CipherSuite {
+        self.cipher_suite
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    /// Parse a bundle created by [`KeyPackageBundle::to_bytes`].
+    ///
+    /// The signature of the key package is verified and the metadata of the
+    /// bundle is recomputed from the key package. A bundle whose metadata
+    /// does not match its key package is rejected with
+    /// [`MlsError::InvalidKeyPackageBundle`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn from_bytes<CP: CryptoProvider>(
+        bytes: &[u8],
+        crypto_provider: &CP,
+    ) -> Result<Self, MlsError> {
+        let parsed = Self::mls_decode(&mut &*bytes)?;
+        let bundle = Self::new(parsed.message.clone(), crypto_provider).await?;
+
+        (bundle == parsed)
+            .then_some(bundle)
+            .ok_or(MlsError::InvalidKeyPackageBundle)
+    }
+
+    /// Serialize a list of bundles, e.g. for a batch upload.
+    pub fn list_to_bytes(bundles: &[Self]) -> Result<Vec<u8>, MlsError> {
+        bundles
+            .iter()
+            .map(Self::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?
+            .mls_encode_to_vec()
+            .map_err(Into::into)
+    }
+
+    /// Parse a list of bundles serialized with
+    /// [`KeyPackageBundle::list_to_bytes`], verifying each of them as
+    /// [`KeyPackageBundle::from_bytes`] does.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn list_from_bytes<CP: CryptoProvider>(
+        bytes: &[u8],
+        crypto_provider: &CP,
+    ) -> Result<Vec<Self>, MlsError> {
+        let encoded = Vec::<Vec<u8>>::mls_decode(&mut &*bytes)?;
+        let mut bundles = Vec::with_capacity(encoded.len());
+
+        for bytes in encoded {
+            bundles.push(Self::from_bytes(&bytes, crypto_provider).await?);
+        }
+
+        Ok(bundles)
+    }
+}
+
+fn key_package(message: &MlsMessage) -> Result<&KeyPackage, MlsError> {
+    match &message.payload {
+        MlsMessagePayload::KeyPackage(key_package) => Ok(key_package),
+        _ => Err(MlsError::UnexpectedMessageType),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+    use mls_rs_codec::{MlsDecode, MlsEncode};
+
+    use crate::{
+        client::{
+            test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        crypto::test_utils::TestCryptoProvider,
+        group::framing::MlsMessagePayload,
+        key_package::test_utils::test_key_package_message,
+    };
+
+    use super::KeyPackageBundle;
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn bundle_round_trips() {
+        let message =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "alice").await;
+        let crypto_provider = TestCryptoProvider::new();
+
+        let bundle = KeyPackageBundle::new(message.clone(), &crypto_provider)
+            .await
+            .unwrap();
+
+        assert_eq!(bundle.key_package_message(), &message);
+        assert_eq!(bundle.cipher_suite(), TEST_CIPHER_SUITE);
+
+        let bytes = bundle.to_bytes().unwrap();
+        let parsed = KeyPackageBundle::from_bytes(&bytes, &crypto_provider)
+            .await
+            .unwrap();
+
+        assert_eq!(parsed, bundle);
+
+        let bytes = KeyPackageBundle::list_to_bytes(&[bundle.clone(), bundle.clone()]).unwrap();
+        let parsed = KeyPackageBundle::list_from_bytes(&bytes, &crypto_provider)
+            .await
+            .unwrap();
+
+        assert_eq!(parsed, vec![bundle.clone(), bundle]);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn bundle_with_wrong_metadata_is_rejected() {
+        let message =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "alice").await;
+        let crypto_provider = TestCryptoProvider::new();
+
+        let mut bundle = KeyPackageBundle::new(message, &crypto_provider)
+            .await
+            .unwrap();
+
+        bundle.expiration += 1;
+
+        let res = KeyPackageBundle::from_bytes(&bundle.to_bytes().unwrap(), &crypto_provider).await;
+
+        assert_matches!(res, Err(MlsError::InvalidKeyPackageBundle));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn bundle_with_invalid_signature_is_rejected() {
+        let message =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "alice").await;
+        let crypto_provider = TestCryptoProvider::new();
+
+        let bundle = KeyPackageBundle::new(message, &crypto_provider)
+            .await
+            .unwrap();
+
+        let mut bytes = bundle.to_bytes().unwrap();
+        let mut tampered = KeyPackageBundle::mls_decode(&mut &*bytes).unwrap();
+
+        if let MlsMessagePayload::KeyPackage(key_package) = &mut tampered.message.payload {
+            key_package.signature[0] ^= 1;
+        }
+
+        bytes = tampered.mls_encode_to_vec().unwrap();
+
+        let res = KeyPackageBundle::from_bytes(&bytes, &crypto_provider).await;
+
+        assert_matches!(res, Err(MlsError::InvalidSignature));
+    }
+}
@@ -5,7 +5,10 @@
 use alloc::vec;
 use alloc::vec::Vec;
 use mls_rs_codec::{MlsDecode, MlsEncode};
-use mls_rs_core::{error::IntoAnyError, key_package::KeyPackageData};
+use mls_rs_core::{
+    error::IntoAnyError,
+    key_package::{KeyPackageData, KeyPackageMetadata},
+};
 
 use crate::client::MlsError;
 use crate::{
@@ -21,6 +24,9 @@ use crate::{
     CipherSuiteProvider, ExtensionList, MlsMessage,
 };
 
+#[cfg(feature = "welcome_retargeting")]
+use crate::tree_kem::leaf_node::{LeafNodeSigningContext, LeafNodeSource};
+
 use super::{KeyPackage, KeyPackageRef};
 
 #[derive(Clone, Debug)]
@@ -40,17 +46,32 @@ pub struct KeyPackageGeneration {
     pub(crate) key_package: KeyPackage,
     pub(crate) init_secret_key: HpkeSecretKey,
     pub(crate) leaf_node_secret_key: HpkeSecretKey,
+    pub(crate) last_resort: bool,
 }
 
 impl KeyPackageGeneration {
     pub fn to_storage(&self) -> Result<(Vec<u8>, KeyPackageData), MlsError> {
         let id = self.reference.to_vec();
+        let lifetime = self.key_package.lifetime()?;
+
+        #[cfg(feature = "std")]
+        let created_at = crate::time::MlsTime::now().seconds_since_epoch();
+
+        #[cfg(not(feature = "std"))]
+        let created_at = lifetime.not_before;
+
+        let metadata = KeyPackageMetadata::new(
+            created_at,
+            lifetime.not_after,
+            self.key_package.cipher_suite,
+            self.last_resort,
+        );
 
         let data = KeyPackageData::new(
             self.key_package.mls_encode_to_vec()?,
             self.init_secret_key.clone(),
             self.leaf_node_secret_key.clone(),
-            self.key_package.expiration()?,
+            metadata,
         );
 
         Ok((id, data))
@@ -62,6 +83,7 @@ impl KeyPackageGeneration {
             key_package: KeyPackage::mls_decode(&mut &*data.key_package_bytes)?,
             init_secret_key: data.init_key,
             leaf_node_secret_key: data.leaf_node_key,
+            last_resort: data.metadata.last_resort,
         })
     }
 
@@ -132,6 +154,53 @@ where
             init_secret_key,
             leaf_node_secret_key: leaf_node_secret,
             reference,
+            last_resort: false,
+        })
+    }
+
+    /// Generate a key package with a new init key and lifetime, keeping the
+    /// leaf node encryption key of `generation`.
+    #[cfg(feature = "welcome_retargeting")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn rotate_init_key(
+        &self,
+        generation: &KeyPackageGeneration,
+        lifetime: Lifetime,
+    ) -> Result<KeyPackageGeneration, MlsError> {
+        let (init_secret_key, public_init) = self
+            .cipher_suite_provider
+            .kem_generate()
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+        let mut leaf_node = generation.key_package.leaf_node.clone();
+        leaf_node.leaf_node_source = LeafNodeSource::KeyPackage(lifetime);
+
+        leaf_node
+            .sign(
+                self.cipher_suite_provider,
+                self.signing_key,
+                &LeafNodeSigningContext::default(),
+            )
+            .await?;
+
+        let mut package = KeyPackage {
+            hpke_init_key: public_init,
+            leaf_node,
+            signature: vec![],
+            ..generation.key_package.clone()
+        };
+
+        self.sign(&mut package).await?;
+
+        let reference = package.to_reference(self.cipher_suite_provider).await?;
+
+        Ok(KeyPackageGeneration {
+            key_package: package,
+            init_secret_key,
+            leaf_node_secret_key: generation.leaf_node_secret_key.clone(),
+            reference,
+            last_resort: generation.last_resort,
         })
     }
 }
@@ -10,6 +10,7 @@ use crate::identity::SigningIdentity;
 use crate::protocol_version::ProtocolVersion;
 use crate::signer::Signable;
 use crate::tree_kem::leaf_node::{LeafNode, LeafNodeSource};
+use crate::tree_kem::Lifetime;
 use crate::CipherSuiteProvider;
 use alloc::vec::Vec;
 use core::{
@@ -21,12 +22,17 @@ use mls_rs_codec::MlsEncode;
 use mls_rs_codec::MlsSize;
 use mls_rs_core::extension::ExtensionList;
 
+#[cfg(feature = "key_package_bundle")]
+mod bundle;
 mod validator;
 pub(crate) use validator::*;
 
 pub(crate) mod generator;
 pub(crate) use generator::*;
 
+#[cfg(feature = "key_package_bundle")]
+pub use bundle::KeyPackageBundle;
+
 #[non_exhaustive]
 #[derive(Clone, MlsSize, MlsEncode, MlsDecode, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -68,6 +74,7 @@ impl Debug for KeyPackage {
     all(feature = "ffi", not(test)),
     safer_ffi_gen::ffi_type(clone, opaque)
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyPackageRef(HashReference);
 
 impl Deref for KeyPackageRef {
@@ -131,8 +138,12 @@ impl KeyPackage {
     }
 
     pub fn expiration(&self) -> Result<u64, MlsError> {
+        self.lifetime().map(|lifetime| lifetime.not_after)
+    }
+
+    pub(crate) fn lifetime(&self) -> Result<&Lifetime, MlsError> {
         if let LeafNodeSource::KeyPackage(lifetime) = &self.leaf_node.leaf_node_source {
-            Ok(lifetime.not_after)
+            Ok(lifetime)
         } else {
             Err(MlsError::InvalidLeafNodeSource)
         }
@@ -16,6 +16,16 @@ pub(crate) async fn validate_key_package_properties<CSP: CipherSuiteProvider>(
         .verify(cs, &package.leaf_node.signing_identity.signature_key, &())
         .await?;
 
+    validate_unsigned_key_package_properties(package, version, cs)
+}
+
+/// Same as [`validate_key_package_properties`], except that the signature of
+/// `package` is not verified.
+pub(crate) fn validate_unsigned_key_package_properties<CSP: CipherSuiteProvider>(
+    package: &KeyPackage,
+    version: ProtocolVersion,
+    cs: &CSP,
+) -> Result<(), MlsError> {
     // Verify that the protocol version matches
     if package.version != version {
         return Err(MlsError::ProtocolVersionMismatch);
@@ -52,6 +52,21 @@
 //! | AWS-LC | 1,2,3,5,7 | Stable |
 //! | Rust Crypto | 1,2,3 | ⚠️ Experimental |
 //!
+//! ## API Stability
+//!
+//! The types and traits re-exported by [`prelude`] are the stable core of the
+//! API and follow semantic versioning. Features that enable experimental
+//! functionality, such as `tree_audit` or `member_quarantine`, also enable
+//! the `unstable` feature and their API may change in minor releases.
+//!
+//! ## Async Build
+//!
+//! Building with `RUSTFLAGS="--cfg mls_build_async"` turns the public API and
+//! the provider traits, including [`GroupStateStorage`], [`KeyPackageStorage`]
+//! and [`PreSharedKeyStorage`], into `async` functions. Storage backed by a
+//! database can then await its I/O instead of blocking the executor. This
+//! mode is always used when targeting `wasm32-unknown-unknown`.
+//!
 //! ## Security Notice
 //!
 //! This library has been validated for conformance to the RFC 9420 specification but has not yet received a full security audit by a 3rd party.
@@ -133,6 +148,11 @@ pub mod client_builder;
 mod client_config;
 /// Dependencies of [`CryptoProvider`] and [`CipherSuiteProvider`]
 pub mod crypto;
+/// Integration point for delivering messages and key packages between
+/// clients.
+#[cfg(feature = "delivery_service")]
+#[cfg_attr(docsrs, doc(cfg(feature = "delivery_service")))]
+pub mod delivery_service;
 /// Extension utilities and built-in extension types.
 pub mod extension;
 /// Tools to observe groups without being a member, useful
@@ -149,6 +169,7 @@ pub mod identity;
 mod iter;
 mod key_package;
 pub(crate) mod map;
+pub mod prelude;
 /// Pre-shared key support.
 pub mod psk;
 mod signer;
@@ -169,8 +190,12 @@ pub mod mls_rules {
     pub use crate::group::{
         mls_rules::{
             CommitDirection, CommitOptions, CommitSource, DefaultMlsRules, EncryptionOptions,
+            UnknownExtensionPolicy, WelcomeSize,
+        },
+        proposal_filter::{
+            ConflictKind, ConflictReport, ProposalBundle, ProposalConflict, ProposalInfo,
+            ProposalPosition, ProposalSource,
         },
-        proposal_filter::{ProposalBundle, ProposalInfo, ProposalSource},
     };
 
     #[cfg(feature = "by_ref_proposal")]
@@ -180,7 +205,7 @@ pub mod mls_rules {
 pub use mls_rs_core::extension::{Extension, ExtensionList};
 
 pub use crate::{
-    client::Client,
+    client::{CapabilitiesSummary, Client},
     group::{
         framing::{MlsMessage, WireFormat},
         mls_rules::MlsRules,
@@ -189,9 +214,12 @@ pub use crate::{
     key_package::{KeyPackage, KeyPackageRef},
 };
 
+#[cfg(feature = "key_package_bundle")]
+pub use crate::key_package::KeyPackageBundle;
+
 /// Error types.
 pub mod error {
-    pub use crate::client::MlsError;
+    pub use crate::client::{EnvelopeRejection, MlsError};
     pub use mls_rs_core::error::{AnyError, IntoAnyError};
     pub use mls_rs_core::extension::ExtensionError;
 }
@@ -0,0 +1,36 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Stable core of the public API.
+//!
+//! Items re-exported here follow semantic versioning: they are only changed
+//! in a breaking way together with a new major version. Everything else,
+//! in particular anything enabled by a feature that requires `unstable`,
+//! may change in minor releases. Enums re-exported here, such as
+//! [`ReceivedMessage`], are `#[non_exhaustive]` so that new variants are not
+//! a breaking change.
+//!
+//! ```
+//! use mls_rs::prelude::*;
+//! ```
+
+pub use crate::{
+    client::Client,
+    client_builder::{BaseConfig, ClientBuilder, MlsConfig},
+    error::{IntoAnyError, MlsError},
+    group::{
+        ApplicationMessageDescription, CommitMessageDescription, CommitOutput, ExportedTree, Group,
+        NewMemberInfo, ReceivedMessage,
+    },
+    identity::{
+        basic::{BasicCredential, BasicIdentityProvider},
+        Credential, CredentialType, SigningIdentity,
+    },
+    mls_rules::{CommitOptions, DefaultMlsRules, EncryptionOptions},
+    CipherSuite, CipherSuiteProvider, CryptoProvider, Extension, ExtensionList, GroupStateStorage,
+    IdentityProvider, KeyPackage, KeyPackageStorage, MlsMessage, MlsRules, PreSharedKeyStorage,
+    ProtocolVersion, WireFormat,
+};
+
+pub use crate::crypto::{SignaturePublicKey, SignatureSecretKey};
@@ -137,7 +137,11 @@ pub(crate) struct AlwaysFoundPskStorage;
 
 #[cfg(any(test, feature = "external_client"))]
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 impl PreSharedKeyStorage for AlwaysFoundPskStorage {
     type Error = Infallible;
 
@@ -9,6 +9,12 @@ use mls_rs_core::error::IntoAnyError;
 
 use crate::client::MlsError;
 use crate::crypto::{CipherSuiteProvider, SignaturePublicKey, SignatureSecretKey};
+#[cfg(any(
+    feature = "by_ref_proposal",
+    feature = "external_client",
+    feature = "openmls_import"
+))]
+use crate::identity::SigningIdentity;
 
 #[derive(Clone, MlsSize, MlsEncode)]
 struct SignContent {
@@ -56,16 +62,19 @@ pub(crate) trait Signable<'a> {
 
     fn write_signature(&mut self, signature: Vec<u8>);
 
+    /// Data passed to the signature scheme, including the signing label.
+    fn to_be_signed(&self, context: &Self::SigningContext) -> Result<Vec<u8>, mls_rs_codec::Error> {
+        SignContent::new(Self::SIGN_LABEL, self.signable_content(context)?).mls_encode_to_vec()
+    }
+
     async fn sign<P: CipherSuiteProvider>(
         &mut self,
         signature_provider: &P,
         signer: &SignatureSecretKey,
         context: &Self::SigningContext,
     ) -> Result<(), MlsError> {
-        let sign_content = SignContent::new(Self::SIGN_LABEL, self.signable_content(context)?);
-
         let signature = signature_provider
-            .sign(signer, &sign_content.mls_encode_to_vec()?)
+            .sign(signer, &self.to_be_signed(context)?)
             .await
             .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
 
@@ -80,19 +89,70 @@ pub(crate) trait Signable<'a> {
         public_key: &SignaturePublicKey,
         context: &Self::SigningContext,
     ) -> Result<(), MlsError> {
-        let sign_content = SignContent::new(Self::SIGN_LABEL, self.signable_content(context)?);
-
         signature_provider
-            .verify(
-                public_key,
-                self.signature(),
-                &sign_content.mls_encode_to_vec()?,
-            )
+            .verify(public_key, self.signature(), &self.to_be_signed(context)?)
             .await
             .map_err(|_| MlsError::InvalidSignature)
     }
 }
 
+/// Signatures of several signed objects, verified together.
+#[derive(Debug, Default)]
+pub(crate) struct SignatureBatch<'a> {
+    signatures: Vec<(&'a SignaturePublicKey, &'a [u8], Vec<u8>)>,
+}
+
+impl<'a> SignatureBatch<'a> {
+    pub fn add<'b, S: Signable<'b>>(
+        &mut self,
+        signed: &'a S,
+        public_key: &'a SignaturePublicKey,
+        context: &S::SigningContext,
+    ) -> Result<(), MlsError> {
+        let data = signed.to_be_signed(context)?;
+        self.signatures.push((public_key, signed.signature(), data));
+        Ok(())
+    }
+
+    // Signatures are verified one by one, as batch verification may accept
+    // signatures rejected by single verification and fork the group.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify<P: CipherSuiteProvider>(
+        &self,
+        signature_provider: &P,
+    ) -> Result<(), MlsError> {
+        for (public_key, signature, data) in &self.signatures {
+            signature_provider
+                .verify(public_key, signature, data)
+                .await
+                .map_err(|_| MlsError::InvalidSignature)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that `signer` is the secret key corresponding to the signature key
+/// of `signing_identity`.
+#[cfg(any(
+    feature = "by_ref_proposal",
+    feature = "external_client",
+    feature = "openmls_import"
+))]
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn signer_matches_identity<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    signing_identity: &SigningIdentity,
+    signer: &SignatureSecretKey,
+) -> Result<bool, MlsError> {
+    let public_key = cipher_suite_provider
+        .signature_key_derive_public(signer)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+
+    Ok(public_key == signing_identity.signature_key)
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use alloc::vec;
@@ -354,4 +414,46 @@ mod tests {
 
         assert_matches!(res, Err(MlsError::InvalidSignature));
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn test_signature_batch() {
+        let cipher_suite_provider = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let mut signables = Vec::new();
+
+        for _ in 0..3 {
+            let (secret, public) = cipher_suite_provider
+                .signature_key_generate()
+                .await
+                .unwrap();
+
+            let mut test_signable = TestSignable {
+                content: random_bytes(32),
+                signature: vec![],
+            };
+
+            test_signable
+                .sign(&cipher_suite_provider, &secret, &vec![])
+                .await
+                .unwrap();
+
+            signables.push((test_signable, public));
+        }
+
+        let mut batch = SignatureBatch::default();
+
+        for (test_signable, public) in &signables {
+            batch.add(test_signable, public, &vec![]).unwrap();
+        }
+
+        batch.verify(&cipher_suite_provider).await.unwrap();
+
+        // One signature over the wrong context invalidates the whole batch.
+        let (test_signable, public) = &signables[1];
+        batch.add(test_signable, public, &random_bytes(32)).unwrap();
+
+        let res = batch.verify(&cipher_suite_provider).await;
+
+        assert_matches!(res, Err(MlsError::InvalidSignature));
+    }
 }
@@ -6,6 +6,11 @@
 pub mod in_memory;
 pub(crate) mod key_package;
 
+/// Storage wrappers that report operations for debugging and metrics.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod instrumented;
+
 pub use key_package::*;
 
 #[cfg(feature = "sqlite")]
@@ -17,6 +17,7 @@ use core::{
 use mls_rs_core::group::{EpochRecord, GroupState, GroupStateStorage};
 #[cfg(not(target_has_atomic = "ptr"))]
 use portable_atomic_util::Arc;
+use zeroize::Zeroize;
 
 use crate::{
     client::MlsError,
@@ -49,6 +50,14 @@ impl Debug for InMemoryGroupData {
     }
 }
 
+// Stored states and epochs contain secrets of the group.
+impl Drop for InMemoryGroupData {
+    fn drop(&mut self) {
+        self.state_data.zeroize();
+        self.epoch_data.iter_mut().for_each(|e| e.data.zeroize());
+    }
+}
+
 impl InMemoryGroupData {
     pub fn new(state_data: Vec<u8>) -> InMemoryGroupData {
         InMemoryGroupData {
@@ -82,13 +91,16 @@ impl InMemoryGroupData {
     // get_epoch calls and is no longer relevant.
     pub fn update_epoch(&mut self, epoch: EpochRecord) {
         if let Some(existing_epoch) = self.get_mut_epoch(epoch.id) {
+            existing_epoch.data.zeroize();
             *existing_epoch = epoch
         }
     }
 
     pub fn trim_epochs(&mut self, max_epoch_retention: usize) {
         while self.epoch_data.len() > max_epoch_retention {
-            self.epoch_data.pop_front();
+            if let Some(mut epoch) = self.epoch_data.pop_front() {
+                epoch.data.zeroize();
+            }
         }
     }
 }
@@ -168,7 +180,11 @@ impl Default for InMemoryGroupStateStorage {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 impl GroupStateStorage for InMemoryGroupStateStorage {
     type Error = Infallible;
 
@@ -204,6 +220,7 @@ impl GroupStateStorage for InMemoryGroupStateStorage {
         let group_data = match group_map.entry(state.id) {
             LargeMapEntry::Occupied(entry) => {
                 let data = entry.into_mut();
+                data.state_data.zeroize();
                 data.state_data = state.data;
                 data
             }
@@ -222,6 +239,34 @@ impl GroupStateStorage for InMemoryGroupStateStorage {
 
         Ok(())
     }
+
+    async fn group_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        Ok(self.stored_groups())
+    }
+
+    async fn compact(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        if let Some(group_data) = self.lock().get_mut(group_id) {
+            group_data.trim_epochs(self.max_epoch_retention);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_epochs(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        if let Some(group_data) = self.lock().get_mut(group_id) {
+            group_data.trim_epochs(0);
+        }
+
+        Ok(())
+    }
+
+    async fn vacuum(&mut self) -> Result<(), Self::Error> {
+        self.lock()
+            .values_mut()
+            .for_each(|group_data| group_data.epoch_data.shrink_to_fit());
+
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "prior_epoch"))]
@@ -310,6 +355,26 @@ mod tests {
         assert_eq!(storage.test_data().epoch_data.len(), 2);
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn compaction_applies_lowered_retention() {
+        let mut storage = test_storage(4).unwrap();
+
+        let epoch_inserts = vec![test_epoch(0), test_epoch(1), test_epoch(2), test_epoch(3)];
+
+        storage
+            .write(test_snapshot(3), epoch_inserts, Vec::new())
+            .await
+            .unwrap();
+
+        storage.max_epoch_retention = 2;
+        storage.compact(TEST_GROUP).await.unwrap();
+        storage.vacuum().await.unwrap();
+
+        let stored = storage.test_data().epoch_data.clone();
+
+        assert_eq!(stored, vec![test_epoch(2), test_epoch(3)]);
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn epoch_insert_over_limit() {
         test_epoch_insert_over_limit(false).await
@@ -14,7 +14,10 @@ use core::{
 };
 
 use alloc::vec::Vec;
-use mls_rs_core::key_package::{KeyPackageData, KeyPackageStorage};
+use mls_rs_core::{
+    crypto::CipherSuite,
+    key_package::{KeyPackageData, KeyPackageMetadata, KeyPackageStorage},
+};
 
 #[cfg(feature = "std")]
 use std::sync::{Mutex, MutexGuard};
@@ -74,6 +77,44 @@ impl InMemoryKeyPackageStorage {
         self.lock().remove(id);
     }
 
+    /// Delete all key packages that expired before the current time.
+    #[cfg(feature = "std")]
+    pub fn delete_expired(&self) {
+        self.delete_expired_by_time(crate::time::MlsTime::now().seconds_since_epoch())
+    }
+
+    /// Delete all key packages that expired before `time`, given in seconds
+    /// since the unix epoch.
+    pub fn delete_expired_by_time(&self, time: u64) {
+        self.lock().retain(|_, pkg| pkg.metadata.expires_at >= time);
+    }
+
+    /// Get the ids and metadata of all key packages that expire before
+    /// `time`, given in seconds since the unix epoch.
+    pub fn expiring_before(&self, time: u64) -> Vec<(Vec<u8>, KeyPackageMetadata)> {
+        self.lock()
+            .iter()
+            .filter(|(_, pkg)| pkg.metadata.expires_at < time)
+            .map(|(id, pkg)| (id.clone(), pkg.metadata))
+            .collect()
+    }
+
+    /// Count stored key packages for each cipher suite.
+    pub fn count_by_suite(&self) -> Vec<(CipherSuite, usize)> {
+        let mut counts = Vec::<(CipherSuite, usize)>::new();
+
+        for pkg in self.lock().values() {
+            let cipher_suite = pkg.metadata.cipher_suite;
+
+            match counts.binary_search_by_key(&cipher_suite, |(cs, _)| *cs) {
+                Ok(i) => counts[i].1 += 1,
+                Err(i) => counts.insert(i, (cipher_suite, 1)),
+            }
+        }
+
+        counts
+    }
+
     /// Get all key packages that are currently stored.
     pub fn key_packages(&self) -> Vec<(Vec<u8>, KeyPackageData)> {
         self.lock()
@@ -92,7 +133,11 @@ impl InMemoryKeyPackageStorage {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
 impl KeyPackageStorage for InMemoryKeyPackageStorage {
     type Error = Infallible;
 
@@ -109,4 +154,66 @@ impl KeyPackageStorage for InMemoryKeyPackageStorage {
     async fn get(&self, id: &[u8]) -> Result<Option<KeyPackageData>, Self::Error> {
         Ok(self.get(id))
     }
+
+    async fn expiring_before(
+        &self,
+        time: u64,
+    ) -> Result<Vec<(Vec<u8>, KeyPackageMetadata)>, Self::Error> {
+        Ok((*self).expiring_before(time))
+    }
+
+    async fn count_by_suite(&self) -> Result<Vec<(CipherSuite, usize)>, Self::Error> {
+        Ok((*self).count_by_suite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use mls_rs_core::{
+        crypto::{CipherSuite, HpkeSecretKey},
+        key_package::{KeyPackageData, KeyPackageMetadata},
+    };
+
+    use super::InMemoryKeyPackageStorage;
+
+    fn test_key_package(expires_at: u64, cipher_suite: CipherSuite) -> KeyPackageData {
+        KeyPackageData::new(
+            vec![],
+            HpkeSecretKey::from(vec![]),
+            HpkeSecretKey::from(vec![]),
+            KeyPackageMetadata::new(0, expires_at, cipher_suite, false),
+        )
+    }
+
+    #[test]
+    fn key_package_queries() {
+        let storage = InMemoryKeyPackageStorage::new();
+
+        storage.insert(
+            vec![1],
+            test_key_package(10, CipherSuite::CURVE25519_AES128),
+        );
+
+        storage.insert(vec![2], test_key_package(20, CipherSuite::P256_AES128));
+
+        storage.insert(
+            vec![3],
+            test_key_package(30, CipherSuite::CURVE25519_AES128),
+        );
+
+        let expiring = storage.expiring_before(25);
+        let mut ids = expiring.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        ids.sort();
+
+        assert_eq!(ids, vec![vec![1], vec![2]]);
+
+        assert_eq!(
+            storage.count_by_suite(),
+            vec![
+                (CipherSuite::CURVE25519_AES128, 2),
+                (CipherSuite::P256_AES128, 1)
+            ]
+        );
+    }
 }
@@ -0,0 +1,575 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug},
+    time::Duration,
+};
+
+use std::sync::Arc;
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+
+use mls_rs_core::{
+    crypto::CipherSuite,
+    group::{EpochRecord, GroupState, GroupStateStorage},
+    key_package::{KeyPackageData, KeyPackageMetadata, KeyPackageStorage},
+    psk::{ExternalPskId, PreSharedKey, PreSharedKeyStorage},
+};
+
+use mls_rs_codec::MlsSize;
+
+/// Kind of data accessed by a storage operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StorageTarget {
+    /// Current group state, see [`GroupStateStorage::state`].
+    GroupState,
+    /// Prior epoch data, see [`GroupStateStorage::epoch`].
+    Epoch,
+    /// Key package secrets, see [`KeyPackageStorage`].
+    KeyPackage,
+    /// Pre-shared key values, see [`PreSharedKeyStorage`].
+    PreSharedKey,
+}
+
+/// Type of a storage operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StorageOperation {
+    Get,
+    Put,
+    Delete,
+}
+
+/// Description of a single call made to the underlying storage.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StorageEvent {
+    pub target: StorageTarget,
+    pub operation: StorageOperation,
+    /// Key that was accessed. This is the group id for group state and epoch
+    /// operations, the key package id for key package operations and the
+    /// PSK id for PSK operations.
+    pub key: Vec<u8>,
+    /// Number of bytes read or written. For a read that returned no value
+    /// or a delete, this is zero.
+    pub size: usize,
+    /// Number of records touched by the operation. A group state write
+    /// counts the state itself along with every inserted or updated epoch.
+    pub records: usize,
+    /// Time spent in the underlying storage. This is always zero on
+    /// platforms without a monotonic clock.
+    pub elapsed: Duration,
+    /// Whether the underlying storage returned an error.
+    pub success: bool,
+}
+
+impl Debug for StorageEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageEvent")
+            .field("target", &self.target)
+            .field("operation", &self.operation)
+            .field("key", &mls_rs_core::debug::pretty_bytes(&self.key))
+            .field("size", &self.size)
+            .field("records", &self.records)
+            .field("elapsed", &self.elapsed)
+            .field("success", &self.success)
+            .finish()
+    }
+}
+
+/// Callback receiving every [`StorageEvent`] produced by an
+/// [`InstrumentedStorage`].
+///
+/// The callback is invoked synchronously on the calling thread after each
+/// storage operation completes and should therefore be cheap.
+pub trait StorageObserver: Send + Sync {
+    fn on_event(&self, event: &StorageEvent);
+}
+
+impl<F> StorageObserver for F
+where
+    F: Fn(&StorageEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &StorageEvent) {
+        self(event)
+    }
+}
+
+/// Storage wrapper reporting every get, put and delete performed on an
+/// underlying storage provider to a [`StorageObserver`].
+///
+/// The wrapper implements [`GroupStateStorage`], [`KeyPackageStorage`] and
+/// [`PreSharedKeyStorage`] whenever the wrapped type does, which makes it
+/// possible to diagnose state size growth and frequently accessed keys
+/// without changing the storage backend in use.
+///
+/// All clones of an instance of this type report to the same observer.
+#[derive(Clone)]
+pub struct InstrumentedStorage<S> {
+    inner: S,
+    observer: Arc<dyn StorageObserver>,
+}
+
+impl<S: Debug> Debug for InstrumentedStorage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstrumentedStorage")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<S> InstrumentedStorage<S> {
+    /// Wrap `inner` so that all operations are reported to `observer`.
+    pub fn new<O>(inner: S, observer: O) -> Self
+    where
+        O: StorageObserver + 'static,
+    {
+        Self {
+            inner,
+            observer: Arc::new(observer),
+        }
+    }
+
+    /// Underlying storage provider.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Remove the instrumentation and return the underlying storage provider.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn report<T, E>(
+        &self,
+        target: StorageTarget,
+        operation: StorageOperation,
+        key: &[u8],
+        timer: Timer,
+        result: &Result<T, E>,
+        size: impl FnOnce(&T) -> (usize, usize),
+    ) {
+        let (size, records) = result.as_ref().map(size).unwrap_or_default();
+
+        self.observer.on_event(&StorageEvent {
+            target,
+            operation,
+            key: key.to_vec(),
+            size,
+            records,
+            elapsed: timer.elapsed(),
+            success: result.is_ok(),
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct Timer(std::time::Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Timer {
+    fn start() -> Self {
+        Self(std::time::Instant::now())
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct Timer;
+
+#[cfg(target_arch = "wasm32")]
+impl Timer {
+    fn start() -> Self {
+        Self
+    }
+
+    fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+fn optional_size<T: AsRef<[u8]>>(value: &Option<T>) -> (usize, usize) {
+    value
+        .as_ref()
+        .map(|v| (v.as_ref().len(), 1))
+        .unwrap_or_default()
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<S> GroupStateStorage for InstrumentedStorage<S>
+where
+    S: GroupStateStorage,
+{
+    type Error = S::Error;
+
+    async fn state(&self, group_id: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.state(group_id).await;
+
+        self.report(
+            StorageTarget::GroupState,
+            StorageOperation::Get,
+            group_id,
+            timer,
+            &res,
+            optional_size,
+        );
+
+        res
+    }
+
+    async fn epoch(&self, group_id: &[u8], epoch_id: u64) -> Result<Option<Vec<u8>>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.epoch(group_id, epoch_id).await;
+
+        self.report(
+            StorageTarget::Epoch,
+            StorageOperation::Get,
+            group_id,
+            timer,
+            &res,
+            optional_size,
+        );
+
+        res
+    }
+
+    async fn write(
+        &mut self,
+        state: GroupState,
+        epoch_inserts: Vec<EpochRecord>,
+        epoch_updates: Vec<EpochRecord>,
+    ) -> Result<(), Self::Error> {
+        let group_id = state.id.clone();
+
+        let size = state.data.len()
+            + epoch_inserts
+                .iter()
+                .chain(epoch_updates.iter())
+                .map(|e| e.data.len())
+                .sum::<usize>();
+
+        let records = 1 + epoch_inserts.len() + epoch_updates.len();
+
+        let timer = Timer::start();
+        let res = self.inner.write(state, epoch_inserts, epoch_updates).await;
+
+        self.report(
+            StorageTarget::GroupState,
+            StorageOperation::Put,
+            &group_id,
+            timer,
+            &res,
+            |_| (size, records),
+        );
+
+        res
+    }
+
+    async fn max_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.max_epoch_id(group_id).await;
+
+        self.report(
+            StorageTarget::Epoch,
+            StorageOperation::Get,
+            group_id,
+            timer,
+            &res,
+            |id| (0, id.is_some() as usize),
+        );
+
+        res
+    }
+
+    async fn group_ids(&self) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.group_ids().await;
+
+        self.report(
+            StorageTarget::GroupState,
+            StorageOperation::Get,
+            &[],
+            timer,
+            &res,
+            |ids| (0, ids.len()),
+        );
+
+        res
+    }
+
+    async fn compact(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.compact(group_id).await;
+
+        self.report(
+            StorageTarget::Epoch,
+            StorageOperation::Delete,
+            group_id,
+            timer,
+            &res,
+            |_| (0, 0),
+        );
+
+        res
+    }
+
+    async fn delete_epochs(&mut self, group_id: &[u8]) -> Result<(), Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.delete_epochs(group_id).await;
+
+        self.report(
+            StorageTarget::Epoch,
+            StorageOperation::Delete,
+            group_id,
+            timer,
+            &res,
+            |_| (0, 0),
+        );
+
+        res
+    }
+
+    async fn vacuum(&mut self) -> Result<(), Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.vacuum().await;
+
+        self.report(
+            StorageTarget::GroupState,
+            StorageOperation::Delete,
+            &[],
+            timer,
+            &res,
+            |_| (0, 0),
+        );
+
+        res
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<S> KeyPackageStorage for InstrumentedStorage<S>
+where
+    S: KeyPackageStorage,
+{
+    type Error = S::Error;
+
+    async fn delete(&mut self, id: &[u8]) -> Result<(), Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.delete(id).await;
+
+        self.report(
+            StorageTarget::KeyPackage,
+            StorageOperation::Delete,
+            id,
+            timer,
+            &res,
+            |_| (0, 1),
+        );
+
+        res
+    }
+
+    async fn insert(&mut self, id: Vec<u8>, pkg: KeyPackageData) -> Result<(), Self::Error> {
+        let size = pkg.mls_encoded_len();
+        let key = id.clone();
+
+        let timer = Timer::start();
+        let res = self.inner.insert(id, pkg).await;
+
+        self.report(
+            StorageTarget::KeyPackage,
+            StorageOperation::Put,
+            &key,
+            timer,
+            &res,
+            |_| (size, 1),
+        );
+
+        res
+    }
+
+    async fn get(&self, id: &[u8]) -> Result<Option<KeyPackageData>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.get(id).await;
+
+        self.report(
+            StorageTarget::KeyPackage,
+            StorageOperation::Get,
+            id,
+            timer,
+            &res,
+            |pkg| {
+                pkg.as_ref()
+                    .map(|pkg| (pkg.mls_encoded_len(), 1))
+                    .unwrap_or_default()
+            },
+        );
+
+        res
+    }
+
+    async fn expiring_before(
+        &self,
+        time: u64,
+    ) -> Result<Vec<(Vec<u8>, KeyPackageMetadata)>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.expiring_before(time).await;
+
+        self.report(
+            StorageTarget::KeyPackage,
+            StorageOperation::Get,
+            &[],
+            timer,
+            &res,
+            |pkgs| (0, pkgs.len()),
+        );
+
+        res
+    }
+
+    async fn count_by_suite(&self) -> Result<Vec<(CipherSuite, usize)>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.count_by_suite().await;
+
+        self.report(
+            StorageTarget::KeyPackage,
+            StorageOperation::Get,
+            &[],
+            timer,
+            &res,
+            |counts| (0, counts.len()),
+        );
+
+        res
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<S> PreSharedKeyStorage for InstrumentedStorage<S>
+where
+    S: PreSharedKeyStorage,
+{
+    type Error = S::Error;
+
+    async fn get(&self, id: &ExternalPskId) -> Result<Option<PreSharedKey>, Self::Error> {
+        let timer = Timer::start();
+        let res = self.inner.get(id).await;
+
+        self.report(
+            StorageTarget::PreSharedKey,
+            StorageOperation::Get,
+            id,
+            timer,
+            &res,
+            optional_size,
+        );
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use std::sync::Mutex;
+
+    use mls_rs_core::group::{EpochRecord, GroupState, GroupStateStorage};
+
+    use super::*;
+    use crate::{
+        group::test_utils::TEST_GROUP, storage_provider::in_memory::InMemoryGroupStateStorage,
+    };
+
+    fn recorded(events: &Mutex<Vec<StorageEvent>>) -> Vec<StorageEvent> {
+        events.lock().unwrap().clone()
+    }
+
+    fn test_storage() -> (
+        InstrumentedStorage<InMemoryGroupStateStorage>,
+        Arc<Mutex<Vec<StorageEvent>>>,
+    ) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+
+        let storage = InstrumentedStorage::new(
+            InMemoryGroupStateStorage::new(),
+            move |event: &StorageEvent| events_clone.lock().unwrap().push(event.clone()),
+        );
+
+        (storage, events)
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn write_and_read_are_reported() {
+        let (mut storage, events) = test_storage();
+
+        let state = GroupState {
+            id: TEST_GROUP.to_vec(),
+            data: vec![0u8; 10],
+        };
+
+        storage
+            .write(state, vec![EpochRecord::new(0, vec![0u8; 5])], vec![])
+            .await
+            .unwrap();
+
+        storage.state(TEST_GROUP).await.unwrap();
+        storage.state(b"missing").await.unwrap();
+
+        let events = recorded(&events);
+
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].target, StorageTarget::GroupState);
+        assert_eq!(events[0].operation, StorageOperation::Put);
+        assert_eq!(events[0].key, TEST_GROUP);
+        assert_eq!(events[0].size, 15);
+        assert_eq!(events[0].records, 2);
+        assert!(events[0].success);
+
+        assert_eq!(events[1].operation, StorageOperation::Get);
+        assert_eq!(events[1].size, 10);
+        assert_eq!(events[1].records, 1);
+
+        assert_eq!(events[2].key, b"missing");
+        assert_eq!(events[2].size, 0);
+        assert_eq!(events[2].records, 0);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn clones_share_observer() {
+        let (storage, events) = test_storage();
+
+        storage.clone().max_epoch_id(TEST_GROUP).await.unwrap();
+        storage.epoch(TEST_GROUP, 0).await.unwrap();
+
+        let events = recorded(&events);
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.target == StorageTarget::Epoch));
+    }
+}
@@ -2,4 +2,4 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-pub use mls_rs_core::key_package::KeyPackageData;
+pub use mls_rs_core::key_package::{KeyPackageData, KeyPackageMetadata};
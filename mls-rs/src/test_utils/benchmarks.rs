@@ -1,5 +1,5 @@
 use mls_rs_codec::MlsEncode;
-use mls_rs_core::protocol_version::ProtocolVersion;
+use mls_rs_core::{crypto::CryptoProvider, protocol_version::ProtocolVersion};
 
 use crate::{
     cipher_suite::CipherSuite,
@@ -138,3 +138,15 @@ pub async fn join_group(cs: CipherSuite, group_info: MlsMessage) -> GroupStates<
 
     GroupStates { sender, receiver }
 }
+
+/// Recompute all tree hashes of the ratchet tree of `group`, as done when
+/// joining the group.
+pub fn recompute_tree_hash<C: MlsConfig>(group: &Group<C>) -> Vec<u8> {
+    let cipher_suite_provider = MlsCryptoProvider::new()
+        .cipher_suite_provider(group.cipher_suite())
+        .unwrap();
+
+    let mut tree = group.state.public_tree.clone();
+    tree.clear_hashes();
+    tree.tree_hash(&cipher_suite_provider).unwrap()
+}
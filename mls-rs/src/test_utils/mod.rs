@@ -26,6 +26,9 @@ use crate::{
 #[cfg(feature = "private_message")]
 use crate::group::{mls_rules::EncryptionOptions, padding::PaddingMode};
 
+#[cfg(all(feature = "test_util", feature = "std"))]
+pub use mls_rs_core::time::VirtualClock;
+
 use alloc::{vec, vec::Vec};
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -10,9 +10,18 @@ use mls_rs_core::{error::IntoAnyError, extension::ExtensionList, identity::Ident
 
 use crate::extension::RequiredCapabilitiesExt;
 
+#[cfg(feature = "group_features")]
+use crate::extension::{GroupFeaturesExt, SupportedFeaturesExt};
+
 #[cfg(feature = "by_ref_proposal")]
 use crate::extension::ExternalSendersExt;
 
+#[cfg(feature = "content_advertisement")]
+use crate::extension::{AcceptedMediaTypesExt, RequiredMediaTypesExt};
+
+#[cfg(feature = "device_attestation")]
+use crate::extension::{DeviceAttestationExt, DeviceAttestationRequiredExt, MlsExtension};
+
 pub enum ValidationContext<'a> {
     Add(Option<MlsTime>),
     Update((&'a [u8], u32, Option<MlsTime>)),
@@ -20,7 +29,20 @@ pub enum ValidationContext<'a> {
 }
 
 impl<'a> ValidationContext<'a> {
-    fn signing_context(&self) -> LeafNodeSigningContext {
+    /// Context of a leaf node that is already in the tree.
+    pub(crate) fn for_revalidation(
+        leaf_node: &LeafNode,
+        group_id: &'a [u8],
+        leaf_index: u32,
+    ) -> Self {
+        match leaf_node.leaf_node_source {
+            LeafNodeSource::KeyPackage(_) => ValidationContext::Add(None),
+            LeafNodeSource::Update => ValidationContext::Update((group_id, leaf_index, None)),
+            LeafNodeSource::Commit(_) => ValidationContext::Commit((group_id, leaf_index, None)),
+        }
+    }
+
+    pub(crate) fn signing_context(&self) -> LeafNodeSigningContext<'_> {
         match *self {
             ValidationContext::Add(_) => Default::default(),
             ValidationContext::Update((group_id, leaf_index, _)) => (group_id, leaf_index).into(),
@@ -46,6 +68,7 @@ where
     cipher_suite_provider: &'a CP,
     identity_provider: &'a C,
     group_context_extensions: Option<&'a ExtensionList>,
+    signatures_verified: bool,
 }
 
 impl<'a, C: IdentityProvider, CP: CipherSuiteProvider> LeafNodeValidator<'a, C, CP> {
@@ -58,9 +81,24 @@ impl<'a, C: IdentityProvider, CP: CipherSuiteProvider> LeafNodeValidator<'a, C,
             cipher_suite_provider,
             identity_provider,
             group_context_extensions,
+            signatures_verified: false,
+        }
+    }
+
+    /// Skip the verification of the signatures of validated leaf nodes and
+    /// key packages, if they were already verified with a
+    /// [`SignatureBatch`](crate::signer::SignatureBatch).
+    pub fn with_signatures_verified(self, signatures_verified: bool) -> Self {
+        Self {
+            signatures_verified,
+            ..self
         }
     }
 
+    pub fn signatures_verified(&self) -> bool {
+        self.signatures_verified
+    }
+
     fn check_context(
         &self,
         leaf_node: &LeafNode,
@@ -105,12 +143,7 @@ impl<'a, C: IdentityProvider, CP: CipherSuiteProvider> LeafNodeValidator<'a, C,
         group_id: &[u8],
         leaf_index: u32,
     ) -> Result<(), MlsError> {
-        let context = match leaf_node.leaf_node_source {
-            LeafNodeSource::KeyPackage(_) => ValidationContext::Add(None),
-            LeafNodeSource::Update => ValidationContext::Update((group_id, leaf_index, None)),
-            LeafNodeSource::Commit(_) => ValidationContext::Commit((group_id, leaf_index, None)),
-        };
-
+        let context = ValidationContext::for_revalidation(leaf_node, group_id, leaf_index);
         self.check_if_valid(leaf_node, context).await
     }
 
@@ -144,6 +177,71 @@ impl<'a, C: IdentityProvider, CP: CipherSuiteProvider> LeafNodeValidator<'a, C,
         Ok(())
     }
 
+    #[cfg(feature = "group_features")]
+    pub fn validate_group_features(&self, leaf_node: &LeafNode) -> Result<(), MlsError> {
+        let Some(group_features) = self
+            .group_context_extensions
+            .and_then(|exts| exts.get_as::<GroupFeaturesExt>().transpose())
+            .transpose()?
+        else {
+            return Ok(());
+        };
+
+        let supported = leaf_node
+            .extensions
+            .get_as::<SupportedFeaturesExt>()?
+            .unwrap_or_default();
+
+        group_features
+            .enabled
+            .iter()
+            .find(|flag| !supported.is_supported(**flag))
+            .map_or(Ok(()), |flag| {
+                Err(MlsError::RequiredFeatureNotSupported(*flag))
+            })
+    }
+
+    #[cfg(feature = "content_advertisement")]
+    pub fn validate_required_media_types(&self, leaf_node: &LeafNode) -> Result<(), MlsError> {
+        let Some(required) = self
+            .group_context_extensions
+            .and_then(|exts| exts.get_as::<RequiredMediaTypesExt>().transpose())
+            .transpose()?
+        else {
+            return Ok(());
+        };
+
+        let accepted = leaf_node
+            .extensions
+            .get_as::<AcceptedMediaTypesExt>()?
+            .unwrap_or_default();
+
+        required
+            .media_types
+            .into_iter()
+            .find(|media_type| !accepted.accepts(media_type))
+            .map_or(Ok(()), |media_type| {
+                Err(MlsError::RequiredMediaTypeNotAccepted(media_type))
+            })
+    }
+
+    #[cfg(feature = "device_attestation")]
+    pub fn validate_device_attestation(&self, leaf_node: &LeafNode) -> Result<(), MlsError> {
+        let required = self.group_context_extensions.map_or(false, |exts| {
+            exts.has_extension(DeviceAttestationRequiredExt::extension_type())
+        });
+
+        if required
+            && !leaf_node
+                .extensions
+                .has_extension(DeviceAttestationExt::extension_type())
+        {
+            return Err(MlsError::DeviceAttestationRequired);
+        }
+
+        Ok(())
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     pub fn validate_external_senders_ext_credentials(
         &self,
@@ -187,18 +285,41 @@ impl<'a, C: IdentityProvider, CP: CipherSuiteProvider> LeafNodeValidator<'a, C,
             .await
             .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
 
-        // Verify that the credential signed the leaf node
-        leaf_node
-            .verify(
-                self.cipher_suite_provider,
-                &leaf_node.signing_identity.signature_key,
-                &context.signing_context(),
+        self.identity_provider
+            .validate_leaf_node_extensions(
+                &leaf_node.signing_identity,
+                &leaf_node.extensions,
+                self.group_context_extensions,
             )
-            .await?;
+            .await
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
+
+        // Verify that the credential signed the leaf node
+        if !self.signatures_verified {
+            leaf_node
+                .verify(
+                    self.cipher_suite_provider,
+                    &leaf_node.signing_identity.signature_key,
+                    &context.signing_context(),
+                )
+                .await?;
+        }
 
         // If required capabilities are specified, verify the leaf node meets the requirements
         self.validate_required_capabilities(leaf_node)?;
 
+        // If application features are enabled, verify the leaf node supports them
+        #[cfg(feature = "group_features")]
+        self.validate_group_features(leaf_node)?;
+
+        // If media types are required, verify the leaf node accepts them
+        #[cfg(feature = "content_advertisement")]
+        self.validate_required_media_types(leaf_node)?;
+
+        // If device attestation is required, verify the leaf node presents one
+        #[cfg(feature = "device_attestation")]
+        self.validate_device_attestation(leaf_node)?;
+
         // If there are extensions, make sure they are referenced in the capabilities field
         for one_ext in &*leaf_node.extensions {
             if !leaf_node
@@ -659,7 +780,11 @@ pub(crate) mod test_utils {
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+    #[cfg_attr(
+        all(not(target_arch = "wasm32"), mls_build_async),
+        maybe_async::must_be_async
+    )]
     impl IdentityProvider for FailureIdentityProvider {
         type Error = TestFailureError;
 
@@ -0,0 +1,141 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
+use mls_rs_core::{crypto::CipherSuiteProvider, identity::SigningIdentity};
+
+use crate::{client::MlsError, group::GroupContext};
+
+use super::{
+    leaf_node::LeafNode,
+    math::TreeIndex,
+    node::{LeafIndex, NodeIndex, Parent},
+    tree_hash::{hash_for_leaf, hash_for_parent, TreeHash},
+    TreeKemPublic,
+};
+
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+struct ProofStep {
+    parent: Option<Parent>,
+    sibling_hash: TreeHash,
+}
+
+/// Proof that a leaf node is part of the ratchet tree of a group, verifiable
+/// with only the tree hash of the group context.
+///
+/// The proof contains the parent nodes on the direct path of the leaf and
+/// the tree hashes of its copath, so its size is logarithmic in the number
+/// of members. A constrained device that does not hold the ratchet tree can
+/// check the membership of a sender by verifying a proof provided by a
+/// member or the delivery service.
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+pub struct MembershipProof {
+    leaf_index: LeafIndex,
+    leaf_count: u32,
+    leaf_node: LeafNode,
+    path: Vec<ProofStep>,
+}
+
+impl MembershipProof {
+    pub fn leaf_index(&self) -> u32 {
+        *self.leaf_index
+    }
+
+    pub fn signing_identity(&self) -> &SigningIdentity {
+        &self.leaf_node.signing_identity
+    }
+
+    /// Verify that the leaf is part of the ratchet tree whose hash is
+    /// included in `context`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify<P: CipherSuiteProvider>(
+        &self,
+        cipher_suite_provider: &P,
+        context: &GroupContext,
+    ) -> Result<(), MlsError> {
+        if *self.leaf_index >= self.leaf_count {
+            return Err(MlsError::InvalidMembershipProof);
+        }
+
+        let mut node = NodeIndex::from(self.leaf_index);
+        let mut hash = hash_for_leaf(
+            self.leaf_index,
+            Some(&self.leaf_node),
+            cipher_suite_provider,
+        )
+        .await?;
+
+        for step in &self.path {
+            let ps = node
+                .parent_sibling(&self.leaf_count)
+                .ok_or(MlsError::InvalidMembershipProof)?;
+
+            let (left, right) = if node < ps.parent {
+                (&*hash, &*step.sibling_hash)
+            } else {
+                (&*step.sibling_hash, &*hash)
+            };
+
+            hash = hash_for_parent(
+                step.parent.as_ref(),
+                cipher_suite_provider,
+                &[],
+                left,
+                right,
+            )
+            .await?;
+
+            node = ps.parent;
+        }
+
+        (node == self.leaf_count.root() && hash == context.tree_hash)
+            .then_some(())
+            .ok_or(MlsError::InvalidMembershipProof)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.mls_encode_to_vec().map_err(Into::into)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MlsError> {
+        Self::mls_decode(&mut &*bytes).map_err(Into::into)
+    }
+}
+
+impl TreeKemPublic {
+    pub(crate) fn membership_proof(
+        &self,
+        leaf_index: LeafIndex,
+    ) -> Result<MembershipProof, MlsError> {
+        let leaf_node = self.nodes.borrow_as_leaf(leaf_index)?.clone();
+        let leaf_count = self.total_leaf_count();
+
+        let mut node = NodeIndex::from(leaf_index);
+        let mut path = Vec::new();
+
+        while let Some(ps) = node.parent_sibling(&leaf_count) {
+            let sibling_hash = self
+                .tree_hashes
+                .current
+                .get(ps.sibling as usize)
+                .ok_or(MlsError::InvalidNodeIndex(ps.sibling))?
+                .clone();
+
+            path.push(ProofStep {
+                parent: self.nodes.borrow_as_parent(ps.parent).ok().cloned(),
+                sibling_hash,
+            });
+
+            node = ps.parent;
+        }
+
+        Ok(MembershipProof {
+            leaf_index,
+            leaf_count,
+            leaf_node,
+            path,
+        })
+    }
+}
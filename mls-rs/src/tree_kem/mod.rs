@@ -59,6 +59,12 @@ mod tree_index;
 #[cfg(feature = "std")]
 pub(crate) mod tree_utils;
 
+#[cfg(feature = "light_client")]
+mod membership_proof;
+
+#[cfg(feature = "light_client")]
+pub use membership_proof::MembershipProof;
+
 #[cfg(test)]
 mod interop_test_vectors;
 
@@ -86,6 +92,17 @@ impl TreeKemPublic {
         Default::default()
     }
 
+    /// Copy of the nodes of this tree, sharing them until either tree is
+    /// modified. The copy has no identity index or cached tree hashes, which
+    /// are not needed to read the roster or export the tree.
+    #[cfg(feature = "tree_snapshot")]
+    pub(crate) fn node_snapshot(&self) -> TreeKemPublic {
+        TreeKemPublic {
+            nodes: self.nodes.clone(),
+            ..Default::default()
+        }
+    }
+
     #[cfg_attr(not(feature = "tree_index"), allow(unused))]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub(crate) async fn import_node_data<IP>(
@@ -241,7 +258,6 @@ impl TreeKemPublic {
         self.nodes.non_empty_leaves()
     }
 
-    #[cfg(feature = "prior_epoch")]
     pub fn leaves(&self) -> impl Iterator<Item = Option<&LeafNode>> + '_ {
         self.nodes.leaves()
     }
@@ -150,13 +150,23 @@ impl NodeTypeResolver for Option<Node> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub(crate) struct NodeVec(Vec<Option<Node>>);
+#[cfg(target_has_atomic = "ptr")]
+type SharedNodes = alloc::sync::Arc<Vec<Option<Node>>>;
+
+#[cfg(not(target_has_atomic = "ptr"))]
+type SharedNodes = Vec<Option<Node>>;
+
+/// Nodes of a ratchet tree in array representation.
+///
+/// The nodes are shared between clones and copied on the first mutation of
+/// a clone, so that snapshots of the tree taken for readers, exported trees
+/// and provisional states of commits don't copy the tree until it changes.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub(crate) struct NodeVec(SharedNodes);
 
 impl From<Vec<Option<Node>>> for NodeVec {
     fn from(x: Vec<Option<Node>>) -> Self {
-        NodeVec(x)
+        NodeVec(x.into())
     }
 }
 
@@ -169,11 +179,56 @@ impl Deref for NodeVec {
 }
 
 impl DerefMut for NodeVec {
+    #[cfg(target_has_atomic = "ptr")]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        alloc::sync::Arc::make_mut(&mut self.0)
+    }
+
+    #[cfg(not(target_has_atomic = "ptr"))]
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
+impl MlsSize for NodeVec {
+    fn mls_encoded_len(&self) -> usize {
+        self.deref().mls_encoded_len()
+    }
+}
+
+impl MlsEncode for NodeVec {
+    fn mls_encode(&self, writer: &mut Vec<u8>) -> Result<(), mls_rs_codec::Error> {
+        self.deref().mls_encode(writer)
+    }
+}
+
+impl MlsDecode for NodeVec {
+    fn mls_decode(reader: &mut &[u8]) -> Result<Self, mls_rs_codec::Error> {
+        Vec::mls_decode(reader).map(NodeVec::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NodeVec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NodeVec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(NodeVec::from)
+    }
+}
+
+#[cfg(all(test, target_has_atomic = "ptr"))]
+impl NodeVec {
+    pub fn shares_nodes_with(&self, other: &NodeVec) -> bool {
+        alloc::sync::Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 impl NodeVec {
     #[cfg(any(test, all(feature = "custom_proposal", feature = "tree_index")))]
     pub fn occupied_leaf_count(&self) -> u32 {
@@ -385,7 +440,7 @@ impl NodeVec {
         let mut n = NodeIndex::from(start) as usize;
 
         while n < self.len() {
-            if self.0[n].is_none() {
+            if self[n].is_none() {
                 return LeafIndex((n as u32) >> 1);
             }
 
@@ -407,7 +462,7 @@ impl NodeVec {
             self.push(None);
         }
 
-        self.0[node_index] = Some(leaf.into());
+        self[node_index] = Some(leaf.into());
     }
 }
 
@@ -574,4 +629,19 @@ mod tests {
         assert_eq!(test_vec.occupied_leaf_count(), 3);
         assert_eq!(test_vec.total_leaf_count(), 4);
     }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn clones_share_nodes_until_modified() {
+        let test_vec = get_test_node_vec().await;
+        let mut clone = test_vec.clone();
+
+        assert!(clone.shares_nodes_with(&test_vec));
+
+        clone.blank_leaf_node(LeafIndex(0)).unwrap();
+
+        assert!(!clone.shares_nodes_with(&test_vec));
+        assert!(clone.is_blank(0).unwrap());
+        assert!(!test_vec.is_blank(0).unwrap());
+    }
 }
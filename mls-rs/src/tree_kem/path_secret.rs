@@ -13,7 +13,7 @@ use core::{
 };
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::error::IntoAnyError;
-use zeroize::Zeroizing;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 use super::hpke_encryption::HpkeEncryptable;
 
@@ -33,6 +33,8 @@ impl Debug for PathSecret {
     }
 }
 
+impl ZeroizeOnDrop for PathSecret {}
+
 impl Deref for PathSecret {
     type Target = Vec<u8>;
 
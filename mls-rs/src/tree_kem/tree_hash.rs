@@ -10,6 +10,7 @@ use crate::crypto::CipherSuiteProvider;
 use crate::tree_kem::math as tree_math;
 use crate::tree_kem::node::Parent;
 use crate::tree_kem::TreeKemPublic;
+#[cfg(any(mls_build_async, not(feature = "parallel")))]
 use alloc::collections::VecDeque;
 use alloc::vec;
 use alloc::vec::Vec;
@@ -21,6 +22,14 @@ use tree_math::TreeIndex;
 
 use core::ops::Deref;
 
+#[cfg(all(not(mls_build_async), feature = "parallel"))]
+use rayon::prelude::*;
+
+// Minimum number of hashes computed by one rayon task, so that small trees
+// are not slowed down by scheduling overhead.
+#[cfg(all(not(mls_build_async), feature = "parallel"))]
+const PARALLEL_MIN_LEN: usize = 64;
+
 #[derive(Clone, Default, MlsSize, MlsEncode, MlsDecode, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct TreeHash(
@@ -120,6 +129,11 @@ impl TreeKemPublic {
         Ok(())
     }
 
+    #[cfg(feature = "benchmark_util")]
+    pub(crate) fn clear_hashes(&mut self) {
+        self.tree_hashes = TreeHashes::default();
+    }
+
     // Initialize all hashes after creating / importing a tree.
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn initialize_hashes<P>(&mut self, cipher_suite_provider: &P) -> Result<(), MlsError>
@@ -261,6 +275,7 @@ impl TreeKemPublic {
     }
 }
 
+#[cfg(any(mls_build_async, not(feature = "parallel")))]
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 async fn tree_hash<P: CipherSuiteProvider>(
     hashes: &mut Vec<TreeHash>,
@@ -312,8 +327,81 @@ async fn tree_hash<P: CipherSuiteProvider>(
     Ok(())
 }
 
+// Hashes of one level of the tree only depend on the level below, so each
+// level is computed in parallel, starting with the leaves.
+#[cfg(all(not(mls_build_async), feature = "parallel"))]
+fn tree_hash<P: CipherSuiteProvider>(
+    hashes: &mut Vec<TreeHash>,
+    nodes: &NodeVec,
+    leaves_to_update: Option<Vec<LeafIndex>>,
+    filtered_leaves: &[LeafIndex],
+    num_leaves: u32,
+    cipher_suite_provider: &P,
+) -> Result<(), MlsError> {
+    let leaves_to_update =
+        leaves_to_update.unwrap_or_else(|| (0..num_leaves).map(LeafIndex).collect::<Vec<_>>());
+
+    // Resize the array in case the tree was extended or truncated
+    hashes.resize(num_leaves as usize * 2 - 1, TreeHash::default());
+
+    let leaf_hashes = leaves_to_update
+        .par_iter()
+        .with_min_len(PARALLEL_MIN_LEN)
+        .filter(|l| ***l < num_leaves)
+        .map(|l| {
+            let leaf = (!filtered_leaves.contains(l))
+                .then_some(nodes.borrow_as_leaf(*l).ok())
+                .flatten();
+
+            Ok((
+                2 * **l,
+                TreeHash(hash_for_leaf(*l, leaf, cipher_suite_provider)?),
+            ))
+        })
+        .collect::<Result<Vec<_>, MlsError>>()?;
+
+    let mut level = Vec::with_capacity(leaf_hashes.len());
+
+    for (n, hash) in leaf_hashes {
+        hashes[n as usize] = hash;
+        level.push(n);
+    }
+
+    loop {
+        level = level
+            .into_iter()
+            .filter_map(|n| n.parent_sibling(&num_leaves).map(|ps| ps.parent))
+            .sorted_unstable()
+            .dedup()
+            .collect();
+
+        if level.is_empty() {
+            return Ok(());
+        }
+
+        let level_hashes = level
+            .par_iter()
+            .with_min_len(PARALLEL_MIN_LEN)
+            .map(|&n| {
+                hash_for_parent(
+                    nodes.borrow_as_parent(n).ok(),
+                    cipher_suite_provider,
+                    filtered_leaves,
+                    &hashes[n.left_unchecked() as usize],
+                    &hashes[n.right_unchecked() as usize],
+                )
+                .map(TreeHash)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (&n, hash) in level.iter().zip(level_hashes) {
+            hashes[n as usize] = hash;
+        }
+    }
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-async fn hash_for_leaf<P: CipherSuiteProvider>(
+pub(super) async fn hash_for_leaf<P: CipherSuiteProvider>(
     leaf_index: LeafIndex,
     leaf_node: Option<&LeafNode>,
     cipher_suite_provider: &P,
@@ -330,7 +418,7 @@ async fn hash_for_leaf<P: CipherSuiteProvider>(
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-async fn hash_for_parent<P: CipherSuiteProvider>(
+pub(super) async fn hash_for_parent<P: CipherSuiteProvider>(
     parent_node: Option<&Parent>,
     cipher_suite_provider: &P,
     filtered: &[LeafIndex],
@@ -14,9 +14,11 @@ use crate::client::MlsError;
 use crate::crypto::CipherSuiteProvider;
 use crate::group::GroupContext;
 use crate::iter::wrap_impl_iter;
+use crate::signer::SignatureBatch;
+use crate::tree_kem::leaf_node_validator::{LeafNodeValidator, ValidationContext};
 use crate::tree_kem::math as tree_math;
-use crate::tree_kem::{leaf_node_validator::LeafNodeValidator, TreeKemPublic};
-use mls_rs_core::identity::IdentityProvider;
+use crate::tree_kem::TreeKemPublic;
+use mls_rs_core::{extension::ExtensionList, identity::IdentityProvider};
 
 #[cfg(all(not(mls_build_async), feature = "rayon"))]
 use rayon::prelude::*;
@@ -30,7 +32,8 @@ where
     CSP: CipherSuiteProvider,
 {
     expected_tree_hash: &'a [u8],
-    leaf_node_validator: LeafNodeValidator<'a, C, CSP>,
+    identity_provider: &'a C,
+    group_context_extensions: &'a ExtensionList,
     group_id: &'a [u8],
     cipher_suite_provider: &'a CSP,
 }
@@ -43,11 +46,8 @@ impl<'a, C: IdentityProvider, CSP: CipherSuiteProvider> TreeValidator<'a, C, CSP
     ) -> Self {
         TreeValidator {
             expected_tree_hash: &context.tree_hash,
-            leaf_node_validator: LeafNodeValidator::new(
-                cipher_suite_provider,
-                identity_provider,
-                Some(&context.extensions),
-            ),
+            identity_provider,
+            group_context_extensions: &context.extensions,
             group_id: &context.group_id,
             cipher_suite_provider,
         }
@@ -88,6 +88,15 @@ impl<'a, C: IdentityProvider, CSP: CipherSuiteProvider> TreeValidator<'a, C, CSP
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn validate_leaves(&self, tree: &TreeKemPublic) -> Result<(), MlsError> {
+        let signatures_verified = self.verify_leaf_signatures(tree).await?;
+
+        let leaf_node_validator = &LeafNodeValidator::new(
+            self.cipher_suite_provider,
+            self.identity_provider,
+            Some(self.group_context_extensions),
+        )
+        .with_signatures_verified(signatures_verified);
+
         let leaves = wrap_impl_iter(tree.nodes.non_empty_leaves());
 
         #[cfg(mls_build_async)]
@@ -95,12 +104,32 @@ impl<'a, C: IdentityProvider, CSP: CipherSuiteProvider> TreeValidator<'a, C, CSP
 
         { leaves }
             .try_for_each(|(index, leaf_node)| async move {
-                self.leaf_node_validator
+                leaf_node_validator
                     .revalidate(leaf_node, self.group_id, *index)
                     .await
             })
             .await
     }
+
+    // Verifying the signatures of all leaves in one batch is much faster
+    // for large groups. If the batch is invalid, the leaves are validated
+    // one by one to report the first invalid leaf.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn verify_leaf_signatures(&self, tree: &TreeKemPublic) -> Result<bool, MlsError> {
+        let mut batch = SignatureBatch::default();
+
+        for (index, leaf_node) in tree.nodes.non_empty_leaves() {
+            let context = ValidationContext::for_revalidation(leaf_node, self.group_id, *index);
+
+            batch.add(
+                leaf_node,
+                &leaf_node.signing_identity.signature_key,
+                &context.signing_context(),
+            )?;
+        }
+
+        Ok(batch.verify(self.cipher_suite_provider).await.is_ok())
+    }
 }
 
 fn validate_unmerged(tree: &TreeKemPublic) -> Result<(), MlsError> {
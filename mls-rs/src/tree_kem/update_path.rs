@@ -80,6 +80,13 @@ pub(crate) async fn validate_update_path<C: IdentityProvider, CSP: CipherSuitePr
             .then_some(())
             .ok_or(MlsError::InvalidSuccessor)?;
 
+        #[cfg(feature = "member_expiry")]
+        crate::group::member_expiry::ensure_expiry_not_extended(
+            existing_leaf,
+            &path.leaf_node,
+            *sender,
+        )?;
+
         (existing_leaf.public_key != path.leaf_node.public_key)
             .then_some(())
             .ok_or(MlsError::SameHpkeKey(*sender))?;
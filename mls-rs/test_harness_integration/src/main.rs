@@ -12,6 +12,11 @@ use by_ref_proposal::ByRefProposalSender;
 
 mod branch_reinit;
 
+mod session;
+use session::{RecordingClient, SessionRecorder};
+
+mod transport;
+
 use mls_rs::{
     client_builder::{
         BaseInMemoryConfig, ClientBuilder, WithCryptoProvider, WithIdentityProvider, WithMlsRules,
@@ -35,8 +40,14 @@ use mls_rs::external_client::builder::ExternalBaseConfig;
 
 use mls_rs_crypto_openssl::OpensslCryptoProvider;
 
-use clap::Parser;
-use std::{collections::HashMap, convert::Infallible, net::IpAddr, sync::Arc};
+use clap::{Parser, ValueEnum};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+};
 use tokio::sync::Mutex;
 use tonic::{transport::Server, Request, Response, Status};
 
@@ -813,6 +824,14 @@ async fn create_client(cipher_suite: u16, identity: &[u8]) -> Result<ClientDetai
     let credential = BasicCredential::new(identity.to_vec()).into_credential();
     let signing_identity = SigningIdentity::new(credential, public_key);
 
+    Ok(client_details(cipher_suite, signing_identity, secret_key))
+}
+
+fn client_details(
+    cipher_suite: CipherSuite,
+    signing_identity: SigningIdentity,
+    secret_key: SignatureSecretKey,
+) -> ClientDetails {
     let psk_store = InMemoryPreSharedKeyStorage::default();
     let key_package_repo = InMemoryKeyPackageStorage::new();
     let mls_rules = TestMlsRules::new();
@@ -826,7 +845,7 @@ async fn create_client(cipher_suite: u16, identity: &[u8]) -> Result<ClientDetai
         .signing_identity(signing_identity.clone(), secret_key.clone(), cipher_suite)
         .build();
 
-    Ok(ClientDetails {
+    ClientDetails {
         client,
         psk_store,
         group: None,
@@ -834,7 +853,7 @@ async fn create_client(cipher_suite: u16, identity: &[u8]) -> Result<ClientDetai
         signer: secret_key,
         key_package_repo,
         mls_rules,
-    })
+    }
 }
 
 fn get_tree(tree: &[u8]) -> Result<Option<ExportedTree<'static>>, tonic::Status> {
@@ -861,6 +880,14 @@ fn find_member(roster: &[Member], cred: &Credential) -> Result<u32, Status> {
         .ok_or_else(|| Status::aborted(format!("member \"{:?}\" not found", cred)))
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Transport {
+    /// gRPC service defined in `proto/mls_client.proto`.
+    Grpc,
+    /// Length-prefixed protobuf messages over TCP, see [`transport`].
+    Tcp,
+}
+
 #[derive(Parser)]
 struct Opts {
     #[clap(long, value_parser, default_value = "0.0.0.0")]
@@ -868,20 +895,78 @@ struct Opts {
 
     #[clap(short, long, value_parser, default_value = "50009")]
     port: u16,
+
+    /// Transport used to receive RPCs.
+    #[clap(long, value_enum, default_value = "grpc")]
+    transport: Transport,
+
+    /// Append all received RPCs and their results to this session file.
+    #[clap(long, value_parser)]
+    record: Option<PathBuf>,
+
+    /// Replay a recorded session offline instead of serving RPCs.
+    #[clap(long, value_parser, conflicts_with = "record")]
+    replay: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opts = Opts::parse();
+
+    if let Some(path) = opts.replay {
+        let divergences = session::replay(&path).await?;
+
+        for divergence in &divergences {
+            println!(
+                "rpc {} ({}) diverged: recorded {:?}, replayed {:?}",
+                divergence.index, divergence.rpc, divergence.recorded, divergence.replayed
+            );
+        }
+
+        if !divergences.is_empty() {
+            std::process::exit(1);
+        }
+
+        println!("replayed {} without divergence", path.display());
+
+        return Ok(());
+    }
+
     let mls_client_impl =
         MlsClientImpl::new(format!("{IMPLEMENTATION_NAME} on port {}", opts.port));
 
-    println!("serving on host {} port {}", opts.host, opts.port);
+    println!(
+        "serving {:?} on host {} port {}",
+        opts.transport, opts.host, opts.port
+    );
 
-    Server::builder()
-        .add_service(MlsClientServer::new(mls_client_impl))
-        .serve((opts.host, opts.port).into())
-        .await?;
+    if let Some(path) = opts.record {
+        let client = RecordingClient::new(mls_client_impl, SessionRecorder::open(path)?);
+        serve(client, opts.transport, (opts.host, opts.port).into()).await
+    } else {
+        serve(
+            mls_client_impl,
+            opts.transport,
+            (opts.host, opts.port).into(),
+        )
+        .await
+    }
+}
+
+async fn serve<C: MlsClient>(
+    client: C,
+    transport: Transport,
+    addr: SocketAddr,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match transport {
+        Transport::Grpc => {
+            Server::builder()
+                .add_service(MlsClientServer::new(client))
+                .serve(addr)
+                .await?
+        }
+        Transport::Tcp => transport::serve_tcp(client, addr).await?,
+    }
 
     Ok(())
 }
@@ -0,0 +1,493 @@
+//! Recording of the RPCs received from the test runner and offline replay of
+//! recorded sessions against the library.
+//!
+//! A session file contains one line per RPC with tab separated fields: the
+//! name of the RPC, the hex encoded request and either `ok` followed by the
+//! hex encoded response or `err` followed by the status code and the hex
+//! encoded status message. Requests and responses are encoded as protobuf.
+//!
+//! Randomness makes it impossible to reproduce the artifacts created by this
+//! client, so replay only checks that every RPC succeeds or fails as it did
+//! when it was recorded. Key packages are restored from the recorded secrets
+//! such that welcome messages and commits created by other implementations
+//! for this client can be processed again.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::Mutex,
+};
+
+use mls_rs::{
+    crypto::SignatureSecretKey,
+    mls_rs_codec::MlsEncode,
+    storage_provider::{KeyPackageData, KeyPackageMetadata},
+    CipherSuite, CipherSuiteProvider, CryptoProvider, MlsMessage,
+};
+use mls_rs_crypto_openssl::OpensslCryptoProvider;
+use prost::Message;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    abort, client_details,
+    mls_client::{mls_client_server::MlsClient, *},
+    MlsClientImpl,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Outcome {
+    Ok(Vec<u8>),
+    Err(Code, String),
+}
+
+impl Outcome {
+    fn is_ok(&self) -> bool {
+        matches!(self, Outcome::Ok(_))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SessionEntry {
+    pub rpc: String,
+    pub request: Vec<u8>,
+    pub outcome: Outcome,
+}
+
+impl SessionEntry {
+    fn to_line(&self) -> String {
+        let outcome = match &self.outcome {
+            Outcome::Ok(response) => format!("ok\t{}", hex::encode(response)),
+            Outcome::Err(code, message) => {
+                format!("err\t{}\t{}", *code as i32, hex::encode(message))
+            }
+        };
+
+        format!("{}\t{}\t{outcome}", self.rpc, hex::encode(&self.request))
+    }
+
+    fn from_line(line: &str) -> Result<Self, io::Error> {
+        let invalid =
+            || io::Error::new(io::ErrorKind::InvalidData, format!("invalid entry {line}"));
+        let decode =
+            |field: Option<&str>| hex::decode(field.ok_or_else(invalid)?).map_err(|_| invalid());
+
+        let mut fields = line.split('\t');
+        let rpc = fields.next().ok_or_else(invalid)?.to_string();
+        let request = decode(fields.next())?;
+
+        let outcome = match fields.next() {
+            Some("ok") => Outcome::Ok(decode(fields.next())?),
+            Some("err") => {
+                let code = fields
+                    .next()
+                    .and_then(|code| code.parse::<i32>().ok())
+                    .ok_or_else(invalid)?;
+
+                let message = String::from_utf8(decode(fields.next())?).map_err(|_| invalid())?;
+
+                Outcome::Err(Code::from(code), message)
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            rpc,
+            request,
+            outcome,
+        })
+    }
+}
+
+/// Appends the RPCs handled by a [`RecordingClient`] to a session file.
+pub(crate) struct SessionRecorder {
+    file: Mutex<File>,
+}
+
+impl SessionRecorder {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn record<Req: Message, Res: Message>(
+        &self,
+        rpc: &str,
+        request: &Req,
+        result: &Result<Response<Res>, Status>,
+    ) {
+        let outcome = match result {
+            Ok(response) => Outcome::Ok(response.get_ref().encode_to_vec()),
+            Err(status) => Outcome::Err(status.code(), status.message().to_string()),
+        };
+
+        let entry = SessionEntry {
+            rpc: rpc.to_string(),
+            request: request.encode_to_vec(),
+            outcome,
+        };
+
+        let mut file = self.file.lock().unwrap();
+
+        if let Err(e) = writeln!(file, "{}", entry.to_line()).and_then(|_| file.flush()) {
+            eprintln!("failed to record {rpc}: {e}");
+        }
+    }
+}
+
+/// Client forwarding all RPCs to an [`MlsClientImpl`] and recording them.
+pub(crate) struct RecordingClient {
+    inner: MlsClientImpl,
+    recorder: SessionRecorder,
+}
+
+impl RecordingClient {
+    pub fn new(inner: MlsClientImpl, recorder: SessionRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+/// RPC of a replayed session whose outcome differs from the recorded one.
+#[derive(Debug)]
+pub(crate) struct Divergence {
+    pub index: usize,
+    pub rpc: String,
+    pub recorded: Outcome,
+    pub replayed: Outcome,
+}
+
+macro_rules! session_rpcs {
+    ($($rpc:ident: $request:ty => $response:ty,)*) => {
+        #[tonic::async_trait]
+        impl MlsClient for RecordingClient {
+            $(
+                async fn $rpc(
+                    &self,
+                    request: Request<$request>,
+                ) -> Result<Response<$response>, Status> {
+                    let message = request.get_ref().clone();
+                    let result = MlsClient::$rpc(&self.inner, request).await;
+                    self.recorder.record(stringify!($rpc), &message, &result);
+
+                    result
+                }
+            )*
+        }
+
+        /// Handle the RPC named `rpc` with the protobuf encoded `request` and
+        /// return the protobuf encoded response.
+        pub(crate) async fn dispatch<C: MlsClient>(
+            client: &C,
+            rpc: &str,
+            request: &[u8],
+        ) -> Result<Vec<u8>, Status> {
+            match rpc {
+                $(
+                    stringify!($rpc) => {
+                        let request = <$request>::decode(request).map_err(abort)?;
+
+                        MlsClient::$rpc(client, Request::new(request))
+                            .await
+                            .map(|response| response.get_ref().encode_to_vec())
+                    }
+                )*
+                _ => Err(Status::unimplemented(format!("unknown rpc {rpc}"))),
+            }
+        }
+    };
+}
+
+session_rpcs! {
+    name: NameRequest => NameResponse,
+    supported_ciphersuites: SupportedCiphersuitesRequest => SupportedCiphersuitesResponse,
+    create_group: CreateGroupRequest => CreateGroupResponse,
+    create_key_package: CreateKeyPackageRequest => CreateKeyPackageResponse,
+    join_group: JoinGroupRequest => JoinGroupResponse,
+    external_join: ExternalJoinRequest => ExternalJoinResponse,
+    group_info: GroupInfoRequest => GroupInfoResponse,
+    state_auth: StateAuthRequest => StateAuthResponse,
+    export: ExportRequest => ExportResponse,
+    protect: ProtectRequest => ProtectResponse,
+    unprotect: UnprotectRequest => UnprotectResponse,
+    store_psk: StorePskRequest => StorePskResponse,
+    add_proposal: AddProposalRequest => ProposalResponse,
+    update_proposal: UpdateProposalRequest => ProposalResponse,
+    remove_proposal: RemoveProposalRequest => ProposalResponse,
+    external_psk_proposal: ExternalPskProposalRequest => ProposalResponse,
+    resumption_psk_proposal: ResumptionPskProposalRequest => ProposalResponse,
+    group_context_extensions_proposal: GroupContextExtensionsProposalRequest => ProposalResponse,
+    commit: CommitRequest => CommitResponse,
+    handle_commit: HandleCommitRequest => HandleCommitResponse,
+    handle_pending_commit: HandlePendingCommitRequest => HandleCommitResponse,
+    re_init_proposal: ReInitProposalRequest => ProposalResponse,
+    re_init_commit: CommitRequest => CommitResponse,
+    handle_pending_re_init_commit: HandlePendingCommitRequest => HandleReInitCommitResponse,
+    handle_re_init_commit: HandleCommitRequest => HandleReInitCommitResponse,
+    re_init_welcome: ReInitWelcomeRequest => CreateSubgroupResponse,
+    handle_re_init_welcome: HandleReInitWelcomeRequest => JoinGroupResponse,
+    create_branch: CreateBranchRequest => CreateSubgroupResponse,
+    handle_branch: HandleBranchRequest => HandleBranchResponse,
+    new_member_add_proposal: NewMemberAddProposalRequest => NewMemberAddProposalResponse,
+    create_external_signer: CreateExternalSignerRequest => CreateExternalSignerResponse,
+    add_external_signer: AddExternalSignerRequest => ProposalResponse,
+    external_signer_proposal: ExternalSignerProposalRequest => ProposalResponse,
+    free: FreeRequest => FreeResponse,
+}
+
+pub(crate) fn read_session<P: AsRef<Path>>(path: P) -> io::Result<Vec<SessionEntry>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.is_empty()))
+        .map(|line| SessionEntry::from_line(&line?))
+        .collect()
+}
+
+/// Replay the RPCs of the session stored at `path` against a new client and
+/// return the RPCs whose outcome differs from the recorded one.
+pub(crate) async fn replay<P: AsRef<Path>>(path: P) -> io::Result<Vec<Divergence>> {
+    let client = MlsClientImpl::new("replay".to_string());
+    let mut divergences = Vec::new();
+
+    for (index, entry) in read_session(path)?.into_iter().enumerate() {
+        let replayed = match dispatch(&client, &entry.rpc, &entry.request).await {
+            Ok(response) => Outcome::Ok(response),
+            Err(status) => Outcome::Err(status.code(), status.message().to_string()),
+        };
+
+        if let (Outcome::Ok(_), Outcome::Ok(recorded), "create_key_package") =
+            (&replayed, &entry.outcome, entry.rpc.as_str())
+        {
+            let request = CreateKeyPackageRequest::decode(&*entry.request).map_err(invalid_data)?;
+            let recorded = CreateKeyPackageResponse::decode(&**recorded).map_err(invalid_data)?;
+
+            client
+                .restore_key_package(request, recorded)
+                .await
+                .map_err(invalid_data)?;
+        }
+
+        if replayed.is_ok() != entry.outcome.is_ok() {
+            divergences.push(Divergence {
+                index,
+                rpc: entry.rpc,
+                recorded: entry.outcome,
+                replayed,
+            });
+        }
+    }
+
+    Ok(divergences)
+}
+
+fn invalid_data<E: std::fmt::Debug>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{e:?}"))
+}
+
+impl MlsClientImpl {
+    /// Replace the client created for `request` with one that holds the
+    /// recorded key package and its secrets.
+    async fn restore_key_package(
+        &self,
+        request: CreateKeyPackageRequest,
+        recorded: CreateKeyPackageResponse,
+    ) -> Result<(), Status> {
+        let cipher_suite = CipherSuite::from(request.cipher_suite as u16);
+
+        let provider = OpensslCryptoProvider::new()
+            .cipher_suite_provider(cipher_suite)
+            .ok_or_else(|| Status::aborted("ciphersuite not supported"))?;
+
+        let key_package = MlsMessage::from_bytes(&recorded.key_package)
+            .map_err(abort)?
+            .into_key_package()
+            .ok_or_else(|| Status::aborted("recorded message is not a key package"))?;
+
+        let signer = SignatureSecretKey::new(recorded.signature_priv);
+        let client = client_details(cipher_suite, key_package.signing_identity().clone(), signer);
+
+        let metadata = KeyPackageMetadata::new(
+            0,
+            key_package.expiration().map_err(abort)?,
+            cipher_suite,
+            false,
+        );
+
+        let data = KeyPackageData::new(
+            key_package.mls_encode_to_vec().map_err(abort)?,
+            recorded.init_priv.into(),
+            recorded.encryption_priv.into(),
+            metadata,
+        );
+
+        let id = key_package.to_reference(&provider).map_err(abort)?;
+        client.key_package_repo.insert(id.to_vec(), data);
+
+        self.clients
+            .lock()
+            .await
+            .insert(recorded.transaction_id, client);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use tonic::{Code, Request};
+
+    use crate::{
+        mls_client::{
+            mls_client_server::MlsClient, CommitRequest, CreateGroupRequest,
+            CreateKeyPackageRequest, HandlePendingCommitRequest, JoinGroupRequest,
+            ProposalDescription, ProtectRequest, UnprotectRequest,
+        },
+        MlsClientImpl, PROPOSAL_DESC_ADD,
+    };
+
+    use super::{read_session, replay, Outcome, RecordingClient, SessionEntry, SessionRecorder};
+
+    const CIPHER_SUITE: u32 = 1;
+
+    fn session_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.session", std::process::id()))
+    }
+
+    #[test]
+    fn session_entries_round_trip() {
+        let entries = [
+            SessionEntry {
+                rpc: "commit".to_string(),
+                request: vec![1, 2, 3],
+                outcome: Outcome::Ok(vec![]),
+            },
+            SessionEntry {
+                rpc: "unprotect".to_string(),
+                request: vec![],
+                outcome: Outcome::Err(Code::Aborted, "invalid\tmessage".to_string()),
+            },
+        ];
+
+        for entry in entries {
+            assert_eq!(SessionEntry::from_line(&entry.to_line()).unwrap(), entry);
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_session_replays_without_divergence() {
+        let path = session_path("two-member-group");
+        let _ = fs::remove_file(&path);
+
+        let client = RecordingClient::new(
+            MlsClientImpl::new("record".to_string()),
+            SessionRecorder::open(&path).unwrap(),
+        );
+
+        let alice = client
+            .create_group(Request::new(CreateGroupRequest {
+                group_id: b"group".to_vec(),
+                cipher_suite: CIPHER_SUITE,
+                encrypt_handshake: false,
+                identity: b"alice".to_vec(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .state_id;
+
+        let bob = client
+            .create_key_package(Request::new(CreateKeyPackageRequest {
+                cipher_suite: CIPHER_SUITE,
+                identity: b"bob".to_vec(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let commit = client
+            .commit(Request::new(CommitRequest {
+                state_id: alice,
+                by_value: vec![ProposalDescription {
+                    proposal_type: PROPOSAL_DESC_ADD.to_vec(),
+                    key_package: bob.key_package,
+                    ..Default::default()
+                }],
+                external_tree: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let bob = client
+            .join_group(Request::new(JoinGroupRequest {
+                transaction_id: bob.transaction_id,
+                welcome: commit.welcome,
+                encrypt_handshake: false,
+                identity: b"bob".to_vec(),
+                ratchet_tree: commit.ratchet_tree,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .state_id;
+
+        client
+            .handle_pending_commit(Request::new(HandlePendingCommitRequest { state_id: alice }))
+            .await
+            .unwrap();
+
+        let ciphertext = client
+            .protect(Request::new(ProtectRequest {
+                state_id: alice,
+                authenticated_data: vec![],
+                plaintext: b"hello".to_vec(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .ciphertext;
+
+        let plaintext = client
+            .unprotect(Request::new(UnprotectRequest {
+                state_id: bob,
+                ciphertext,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .plaintext;
+
+        assert_eq!(plaintext, b"hello");
+
+        assert_eq!(read_session(&path).unwrap().len(), 7);
+
+        // The welcome was encrypted to the recorded key package, so Bob can
+        // only join during replay if its secrets are restored.
+        assert!(replay(&path).await.unwrap().is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn committed_sessions_replay_without_divergence() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("sessions");
+
+        let Ok(sessions) = fs::read_dir(dir) else {
+            return;
+        };
+
+        for session in sessions {
+            let path = session.unwrap().path();
+
+            if path.extension().map_or(false, |ext| ext == "session") {
+                let divergences = replay(&path).await.unwrap();
+                assert!(divergences.is_empty(), "{path:?}: {divergences:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Transports other than gRPC for driving the client.
+//!
+//! All transports identify an RPC by its name, as in the `.proto` service
+//! definition converted to snake case, and exchange protobuf encoded requests
+//! and responses. [`InProcessClient`] calls the client directly from Rust.
+//! [`serve_tcp`] exposes the client over a length-prefixed byte protocol
+//! which can be spoken by a test runner without an HTTP/2 stack.
+//!
+//! Each TCP request is framed as
+//!
+//! ```text
+//! u32 rpc_name_length, rpc_name, u32 request_length, request
+//! ```
+//!
+//! and answered with
+//!
+//! ```text
+//! u32 status_code, u32 payload_length, payload
+//! ```
+//!
+//! where lengths are big endian, the status code is a gRPC status code and
+//! the payload is the encoded response if the code is `0` or the UTF-8 error
+//! message otherwise. Requests on one connection are handled in order.
+
+use std::{io, net::SocketAddr, sync::Arc};
+
+use prost::Message;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tonic::{Code, Status};
+
+use crate::{abort, mls_client::mls_client_server::MlsClient, session::dispatch};
+
+/// Upper bound on the length of a frame, protecting the client from
+/// allocating memory for garbage.
+const MAX_FRAME_LENGTH: usize = 1 << 24;
+
+/// Client driven by direct calls instead of a network transport.
+pub(crate) struct InProcessClient<C> {
+    inner: C,
+}
+
+impl<C: MlsClient> InProcessClient<C> {
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+
+    /// Call the RPC named `rpc` with the protobuf encoded `request`.
+    pub async fn call_raw(&self, rpc: &str, request: &[u8]) -> Result<Vec<u8>, Status> {
+        dispatch(&self.inner, rpc, request).await
+    }
+
+    /// Call the RPC named `rpc` with `request` and decode its response.
+    #[allow(dead_code)]
+    pub async fn call<Req: Message, Res: Message + Default>(
+        &self,
+        rpc: &str,
+        request: &Req,
+    ) -> Result<Res, Status> {
+        let response = self.call_raw(rpc, &request.encode_to_vec()).await?;
+        Res::decode(&*response).map_err(abort)
+    }
+}
+
+/// Serve RPCs received over the TCP byte protocol at `addr` until an error
+/// occurs while accepting a connection.
+pub(crate) async fn serve_tcp<C>(client: C, addr: SocketAddr) -> io::Result<()>
+where
+    C: MlsClient,
+{
+    serve_tcp_listener(client, TcpListener::bind(addr).await?).await
+}
+
+pub(crate) async fn serve_tcp_listener<C>(client: C, listener: TcpListener) -> io::Result<()>
+where
+    C: MlsClient,
+{
+    let client = Arc::new(InProcessClient::new(client));
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let client = client.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(&client, stream).await {
+                eprintln!("connection from {peer} closed: {e}");
+            }
+        });
+    }
+}
+
+async fn handle_connection<C: MlsClient>(
+    client: &InProcessClient<C>,
+    mut stream: TcpStream,
+) -> io::Result<()> {
+    while let Some(rpc) = read_frame(&mut stream).await? {
+        let rpc = String::from_utf8(rpc)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "rpc name is not UTF-8"))?;
+
+        let request = read_frame(&mut stream)
+            .await?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+
+        let (code, payload) = match client.call_raw(&rpc, &request).await {
+            Ok(response) => (Code::Ok, response),
+            Err(status) => (status.code(), status.message().as_bytes().to_vec()),
+        };
+
+        stream.write_u32(code as u32).await?;
+        write_frame(&mut stream, &payload).await?;
+        stream.flush().await?;
+    }
+
+    Ok(())
+}
+
+/// Client side of the TCP byte protocol.
+#[allow(dead_code)]
+pub(crate) struct TcpClient {
+    stream: TcpStream,
+}
+
+#[allow(dead_code)]
+impl TcpClient {
+    pub async fn connect(addr: SocketAddr) -> io::Result<Self> {
+        Ok(Self {
+            stream: TcpStream::connect(addr).await?,
+        })
+    }
+
+    /// Call the RPC named `rpc` with `request` and decode its response.
+    pub async fn call<Req: Message, Res: Message + Default>(
+        &mut self,
+        rpc: &str,
+        request: &Req,
+    ) -> Result<Res, Status> {
+        write_frame(&mut self.stream, rpc.as_bytes())
+            .await
+            .map_err(abort)?;
+
+        write_frame(&mut self.stream, &request.encode_to_vec())
+            .await
+            .map_err(abort)?;
+
+        let code = Code::from(self.stream.read_u32().await.map_err(abort)? as i32);
+
+        let payload = read_frame(&mut self.stream)
+            .await
+            .map_err(abort)?
+            .ok_or_else(|| Status::unavailable("connection closed"))?;
+
+        match code {
+            Code::Ok => Res::decode(&*payload).map_err(abort),
+            code => Err(Status::new(code, String::from_utf8_lossy(&payload))),
+        }
+    }
+}
+
+/// Read a length-prefixed frame, or `None` if the stream ended before the
+/// frame started.
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let length = match reader.read_u32().await {
+        Ok(length) => length as usize,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    if length > MAX_FRAME_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {length} bytes is too long"),
+        ));
+    }
+
+    let mut frame = vec![0; length];
+    reader.read_exact(&mut frame).await?;
+
+    Ok(Some(frame))
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(frame.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame is too long"))?;
+
+    writer.write_u32(length).await?;
+    writer.write_all(frame).await
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use tonic::Code;
+
+    use crate::{
+        mls_client::{
+            CreateGroupRequest, CreateGroupResponse, NameRequest, NameResponse, ProtectRequest,
+            ProtectResponse, UnprotectRequest, UnprotectResponse,
+        },
+        MlsClientImpl,
+    };
+
+    use super::{serve_tcp_listener, InProcessClient, TcpClient};
+
+    fn create_group_request(identity: &[u8]) -> CreateGroupRequest {
+        CreateGroupRequest {
+            group_id: b"group".to_vec(),
+            cipher_suite: 1,
+            encrypt_handshake: false,
+            identity: identity.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn in_process_client_handles_rpcs() {
+        let client = InProcessClient::new(MlsClientImpl::new("in process".to_string()));
+
+        let name: NameResponse = client.call("name", &NameRequest {}).await.unwrap();
+        assert_eq!(name.name, "in process");
+
+        let group: CreateGroupResponse = client
+            .call("create_group", &create_group_request(b"alice"))
+            .await
+            .unwrap();
+
+        let ciphertext: ProtectResponse = client
+            .call(
+                "protect",
+                &ProtectRequest {
+                    state_id: group.state_id,
+                    authenticated_data: vec![],
+                    plaintext: b"hello".to_vec(),
+                },
+            )
+            .await
+            .unwrap();
+
+        // Members can't decrypt their own messages.
+        let res = client
+            .call::<_, UnprotectResponse>(
+                "unprotect",
+                &UnprotectRequest {
+                    state_id: group.state_id,
+                    ciphertext: ciphertext.ciphertext,
+                },
+            )
+            .await;
+
+        assert_eq!(res.unwrap_err().code(), Code::Aborted);
+
+        let res = client
+            .call::<_, NameResponse>("nonexistent", &NameRequest {})
+            .await;
+        assert_eq!(res.unwrap_err().code(), Code::Unimplemented);
+    }
+
+    #[tokio::test]
+    async fn tcp_client_handles_rpcs() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(serve_tcp_listener(
+            MlsClientImpl::new("tcp".to_string()),
+            listener,
+        ));
+
+        let mut client = TcpClient::connect(addr).await.unwrap();
+
+        let name: NameResponse = client.call("name", &NameRequest {}).await.unwrap();
+        assert_eq!(name.name, "tcp");
+
+        let first: CreateGroupResponse = client
+            .call("create_group", &create_group_request(b"alice"))
+            .await
+            .unwrap();
+
+        // State is shared between connections.
+        let mut other_client = TcpClient::connect(addr).await.unwrap();
+
+        let second: CreateGroupResponse = other_client
+            .call("create_group", &create_group_request(b"bob"))
+            .await
+            .unwrap();
+
+        assert_ne!(first.state_id, second.state_id);
+
+        let res = client
+            .call::<_, NameResponse>("nonexistent", &NameRequest {})
+            .await;
+
+        assert_eq!(res.unwrap_err().code(), Code::Unimplemented);
+    }
+}
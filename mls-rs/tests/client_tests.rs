@@ -845,3 +845,56 @@ async fn external_info_from_commit_allows_to_join() {
 
     alice.process_incoming_message(commit).await.unwrap();
 }
+
+#[cfg(feature = "std")]
+#[maybe_async::test(not(mls_build_async), async(mls_build_async, futures_test))]
+async fn virtual_clock_expires_key_packages() {
+    use core::time::Duration;
+    use mls_rs::test_utils::VirtualClock;
+
+    const YEAR: Duration = Duration::from_secs(365 * 24 * 3600);
+
+    let clock = VirtualClock::start_now();
+
+    let cs = CipherSuite::P256_AES128;
+    let version = ProtocolVersion::MLS_10;
+
+    let alice = generate_client(cs, version, 0, false).await;
+    let bob = generate_client(cs, version, 1, false).await;
+
+    let mut alice_group = alice.create_group(ExtensionList::default()).await.unwrap();
+
+    let stale_key_package = bob.generate_key_package_message().await.unwrap();
+
+    // Key packages are valid for one year by default
+    clock.advance(2 * YEAR);
+
+    let res = alice_group
+        .commit_builder()
+        .add_member(stale_key_package)
+        .unwrap()
+        .build()
+        .await;
+
+    assert_matches!(res, Err(MlsError::InvalidLifetime));
+
+    // Key packages generated in the future are valid in the future
+    let key_package = bob.generate_key_package_message().await.unwrap();
+
+    let commit = alice_group
+        .commit_builder()
+        .add_member(key_package)
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    alice_group.apply_pending_commit().await.unwrap();
+
+    let (bob_group, _) = bob
+        .join_group(None, &commit.welcome_messages[0])
+        .await
+        .unwrap();
+
+    assert!(Group::equal_group_state(&alice_group, &bob_group));
+}
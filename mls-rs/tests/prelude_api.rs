@@ -0,0 +1,87 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Signatures of the stable API in `mls_rs::prelude`.
+//!
+//! These tests fail to compile if a signature covered by semver changes.
+//! Such a change requires a new major version and an update of this file.
+
+#![cfg(not(mls_build_async))]
+#![allow(clippy::type_complexity)]
+
+use mls_rs::prelude::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn prelude_types_are_send_and_sync() {
+    assert_send_sync::<MlsMessage>();
+    assert_send_sync::<MlsError>();
+    assert_send_sync::<ExtensionList>();
+    assert_send_sync::<SigningIdentity>();
+    assert_send_sync::<CipherSuite>();
+    assert_send_sync::<ProtocolVersion>();
+}
+
+#[test]
+fn mls_message_api() {
+    let _: fn(&[u8]) -> Result<MlsMessage, MlsError> = MlsMessage::from_bytes;
+    let _: fn(&MlsMessage) -> Result<Vec<u8>, MlsError> = MlsMessage::to_bytes;
+    let _: fn(&MlsMessage) -> WireFormat = MlsMessage::wire_format;
+    let _: fn(&MlsMessage) -> Option<u64> = MlsMessage::epoch;
+    let _: fn(&MlsMessage) -> Option<CipherSuite> = MlsMessage::cipher_suite;
+}
+
+#[test]
+fn client_builder_api() {
+    let _: fn() -> ClientBuilder<BaseConfig> = Client::builder;
+}
+
+#[allow(dead_code)]
+fn client_api<C: MlsConfig>() {
+    let _: fn(&Client<C>) -> Result<MlsMessage, MlsError> =
+        Client::<C>::generate_key_package_message;
+
+    let _: fn(&Client<C>, ExtensionList) -> Result<Group<C>, MlsError> = Client::<C>::create_group;
+
+    let _: fn(&Client<C>, Vec<u8>, ExtensionList) -> Result<Group<C>, MlsError> =
+        Client::<C>::create_group_with_id;
+
+    let _: fn(
+        &Client<C>,
+        Option<ExportedTree<'static>>,
+        &MlsMessage,
+    ) -> Result<(Group<C>, NewMemberInfo), MlsError> = Client::<C>::join_group;
+
+    let _: fn(&Client<C>, &[u8]) -> Result<Group<C>, MlsError> = Client::<C>::load_group;
+}
+
+#[allow(dead_code)]
+fn group_api<C: MlsConfig>() {
+    let _: fn(&Group<C>) -> &[u8] = Group::<C>::group_id;
+    let _: fn(&Group<C>) -> u64 = Group::<C>::current_epoch;
+    let _: fn(&Group<C>) -> CipherSuite = Group::<C>::cipher_suite;
+
+    let _: fn(&mut Group<C>, Vec<u8>) -> Result<CommitOutput, MlsError> = Group::<C>::commit;
+
+    let _: fn(&mut Group<C>) -> Result<CommitMessageDescription, MlsError> =
+        Group::<C>::apply_pending_commit;
+
+    let _: fn(&mut Group<C>, &[u8], Vec<u8>) -> Result<MlsMessage, MlsError> =
+        Group::<C>::encrypt_application_message;
+
+    let _: fn(&mut Group<C>, MlsMessage) -> Result<ReceivedMessage, MlsError> =
+        Group::<C>::process_incoming_message;
+
+    let _: fn(&mut Group<C>) -> Result<(), MlsError> = Group::<C>::write_to_storage;
+}
+
+#[allow(dead_code)]
+fn received_message_api(message: ReceivedMessage) -> Option<ApplicationMessageDescription> {
+    match message {
+        ReceivedMessage::ApplicationMessage(description) => Some(description),
+        ReceivedMessage::Commit(_) | ReceivedMessage::Proposal(_) => None,
+        _ => None,
+    }
+}